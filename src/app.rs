@@ -1,21 +1,90 @@
+mod all_changes;
+mod attach;
+mod avatars;
+mod branch;
+mod checklist;
+mod checks;
+mod command;
+mod comment_edit;
+mod commit_range;
+mod dashboard;
+mod details;
+mod diagram;
+mod drafts;
 pub mod editor;
+mod external_editor;
+mod file_compare;
+mod file_finder;
+mod file_history;
+mod folds;
+mod follow;
+mod full_content;
 mod handler;
+mod heatmap;
+mod help;
 mod helpers;
+mod hunks;
+mod hyperlinks;
+mod interdiff;
+mod large_pr;
+mod latency;
+mod links;
 mod markdown;
 mod media;
+mod memory;
+mod mute;
 mod navigation;
+mod notes;
+mod pending;
+mod perf;
+mod permalink;
+mod permission;
+mod quick_reply;
+mod quit;
+mod quote;
+mod relationship;
 mod render;
+mod report;
+mod resolve_queue;
+mod review_templates;
+mod search;
+mod snippet;
+mod status_history;
+mod test_files;
 mod types;
+mod unread;
+mod viewed;
 
-use helpers::{format_datetime, open_url_in_browser, truncate_path, truncate_str};
+pub use help::{KeymapFormat, export_keymap};
+use helpers::{author_color, truncate_path, truncate_str};
 pub use media::{collect_image_urls, preprocess_pr_body};
 pub use types::*;
 
+use all_changes::ALL_CHANGES_KEY;
+use checklist::ChecklistItem;
+pub use checklist::DEFAULT_CHECKLIST;
+use command::{CommandResult, RunningCommand};
+use drafts::DraftKey;
+use file_compare::{FileCompareView, FilePin};
+use file_history::FileHistoryView;
+use hunks::HunkState;
+use large_pr::LargePrSummary;
+use links::LinkEntry;
+use perf::PerfStats;
+use permission::PermissionRetry;
+use status_history::StatusHistoryEntry;
+use test_files::{FileRow, TestSplit};
+
+use crate::config::{Keymap, MemoryConfig, TimeFormat};
+use crate::coverage::CoverageReport;
 use crate::github::comments::{self as comments, ReviewComment, ReviewThread};
 use crate::github::commits::CommitInfo;
+use crate::github::error::ApiErrorKind;
 use crate::github::files::DiffFile;
 use crate::github::media::MediaCache;
+use crate::github::pr::{MergeMethod, UpdateMethod};
 use crate::github::review::{self, PendingComment};
+use crate::lru::{LruBudget, SizedLru};
 use color_eyre::Result;
 use octocrab::Octocrab;
 use ratatui::{
@@ -27,7 +96,7 @@ use ratatui::{
 };
 use ratatui_image::picker::Picker;
 use ratatui_image::protocol::StatefulProtocol;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use tokio::runtime::Handle;
 use tokio::sync::mpsc;
 
@@ -77,18 +146,38 @@ pub struct App {
     status_message: Option<StatusMessage>,
     /// 2キーシーケンスの1文字目（`]` or `[`）を保持
     pending_key: Option<char>,
+    /// g 押下前の Conversation カーソルとスクロール（gd で元のエントリへ戻すため）
+    conversation_g_origin: Option<(usize, u16)>,
     /// ヘルプ画面のスクロール位置
     help_scroll: u16,
+    /// ヘルプ画面の表示行数（render で更新、PgDn / PgUp の移動量）
+    help_view_height: u16,
     /// ヘルプ画面のコンテキスト（`?` 押下時のフォーカスパネルで上書きされる。初期値は未使用）
     help_context_panel: Panel,
+    /// ヘルプの絞り込みクエリ
+    help_filter: String,
+    /// ヘルプの絞り込みクエリを入力中か
+    help_filter_input: bool,
     /// Zoom モード（フォーカスペインのみ全画面表示）
     zoomed: bool,
     /// viewed 済みファイルのマップ（コミット SHA → ファイル名の Set）
     viewed_files: HashMap<String, HashSet<String>>,
+    /// hunk の折りたたみ・確認済み状態（(コミット SHA, ファイル名) → 状態）
+    hunk_states: HashMap<(String, String), HunkState>,
     /// PR Description のマークダウンレンダリングキャッシュ
     pr_desc_rendered: Option<Text<'static>>,
     /// Conversation ペインのマークダウンレンダリングキャッシュ
     conversation_rendered: Option<Vec<Line<'static>>>,
+    /// PR Description キャッシュ生成時の内部幅（テーブル整形に使用、変化したら再生成）
+    pr_desc_rendered_width: u16,
+    /// Conversation キャッシュ生成時の内部幅（テーブル整形に使用、変化したら再生成）
+    conversation_rendered_width: u16,
+    /// PR 本文の <details> ごとの展開状態
+    pr_desc_fold_open: Vec<bool>,
+    /// <details> 見出しの論理行位置（pr_desc_rendered 内）
+    pr_desc_fold_lines: Vec<usize>,
+    /// <details> 見出しの Wrap 考慮済み視覚行位置（render で更新）
+    pr_desc_fold_rows: Vec<usize>,
     /// カラーテーマ（ライト/ダーク）
     theme: ThemeMode,
     /// 各ペインの描画領域キャッシュ（マウスヒットテスト用、render 時に更新）
@@ -101,10 +190,12 @@ pub struct App {
     media_cache: MediaCache,
     /// メディアビューアの現在のインデックス
     media_viewer_index: usize,
-    /// メディアビューアのプロトコルキャッシュ（URL → StatefulProtocol）
-    media_protocol_cache: HashMap<String, StatefulProtocol>,
+    /// メディアビューアのプロトコルキャッシュ（URL → StatefulProtocol、メモリ予算付き LRU）
+    media_protocol_cache: SizedLru<String, StatefulProtocol>,
     /// バックグラウンドでプロトコル生成中のワーカー
     media_protocol_worker: Option<std::thread::JoinHandle<(String, StatefulProtocol)>>,
+    /// カバレッジレポート（--coverage 指定時のみ）
+    coverage: Option<CoverageReport>,
     /// (commit_sha, filename) → 可視レビューコメント数のキャッシュ（起動時に計算）
     visible_review_comment_cache: HashMap<(String, String), usize>,
     /// 自分のPRかどうか（Approve/Request Changesを非表示にする）
@@ -121,12 +212,230 @@ pub struct App {
     conversation_visual_total: u16,
     /// Issue Comment 送信フラグ（draw 後に実行）
     needs_issue_comment_submit: bool,
+    /// 送信中の Issue Comment として仮表示しているエントリの位置
+    posting_issue_comment: Option<usize>,
     /// Reply Comment 送信フラグ（draw 後に実行）
     needs_reply_submit: bool,
+    /// 自分のレビューコメントの編集送信フラグ（draw 後に実行）
+    needs_comment_edit: bool,
+    /// 自分のレビューコメントの削除フラグ（draw 後に実行）
+    needs_comment_delete: bool,
     /// PR データリロードフラグ（draw 後に実行）
     needs_reload: bool,
+    /// リサイズ後のスクロールクランプフラグ（draw 後に実行）
+    needs_resize_clamp: bool,
+    /// Ctrl+Z によるサスペンドフラグ（draw 後に実行）
+    needs_suspend: bool,
+    /// Ctrl+E による外部エディタ起動フラグ（draw 後に実行）
+    needs_external_editor: bool,
+    /// `!` で実行するコマンド（--exec 指定時のみ）
+    run_command: Option<String>,
+    /// 直近のコマンド実行結果
+    command_result: Option<CommandResult>,
+    /// バックグラウンドで実行中のコマンド
+    running_command: Option<RunningCommand>,
+    /// コマンド出力オーバーレイのスクロール位置
+    command_output_scroll: u16,
+    /// 実行中の出力の末尾に追従するか（G で再開、j/k/g で解除）
+    command_output_follow: bool,
+    /// コマンド実行フラグ（draw 後に実行）
+    needs_run_command: bool,
+    /// DiffView 検索の入力中テキスト
+    search_input: String,
+    /// 検索マッチ全行に同じコメントを一括追加するか（CommentInput 中のみ有効）
+    queue_all_matches: bool,
+    /// 直前に挿入した定型返信のインデックス（Ctrl+R で循環）
+    quick_reply_index: Option<usize>,
+    /// ダッシュボードの未解決スレッドカーソル位置
+    dashboard_cursor: usize,
+    /// 再レビュー依頼フラグ（draw 後に実行）
+    needs_rerequest_review: bool,
+    /// Ready for review 化フラグ（draw 後に実行）
+    needs_mark_ready: bool,
+    /// レビューチェックリスト
+    checklist: Vec<ChecklistItem>,
+    /// チェックリストオーバーレイのカーソル位置
+    checklist_cursor: usize,
+    /// レビュー送信時にチェックリストを本文へ追記するか
+    checklist_append: bool,
+    /// 2コミット間比較のために固定したファイル
+    file_pin: Option<FilePin>,
+    /// ファイル比較結果（FileCompare モードで表示）
+    file_compare: Option<FileCompareView>,
+    /// ファイル比較オーバーレイのスクロール位置
+    file_compare_scroll: u16,
+    /// ファイル履歴（FileHistory モードで表示）
+    file_history: Option<FileHistoryView>,
+    /// ファイル検索の入力（FileFinder モード）
+    file_finder_input: String,
+    /// ファイル検索の候補のカーソル位置
+    file_finder_cursor: usize,
+    /// 大きな PR: 全コミットを先読みせず、選択したコミットのファイルだけを読み込む
+    lazy_files: bool,
+    /// 大きな PR の概要（LargePrSummary モードで表示）
+    large_pr_summary: Option<LargePrSummary>,
+    /// 遅延読み込みに失敗したコミット（リロードまで再試行しない）
+    lazy_failed: HashSet<String>,
+    /// PR 全体（base..head）の変更ファイル（Commits ペインの All changes、選択時に読み込む）
+    pr_files: Option<Vec<DiffFile>>,
+    /// All changes の読み込みに失敗したか（リロードまで再試行しない）
+    pr_files_failed: bool,
+    /// 概要画面で開いたパスの読み込みフラグ（draw 後に実行）
+    needs_path_load: Option<String>,
+    /// キャッシュのメモリ予算
+    memory: MemoryConfig,
+    /// files_map のコミットごとのおおよそのサイズと利用順
+    files_usage: LruBudget<String>,
+    /// files_map が変わり、files_usage を合わせ直す必要があるか
+    files_usage_stale: bool,
+    /// 予算で files_map から捨てたコミット（読み込み直すまでコメント数のバッジを残す）
+    files_evicted: HashSet<String>,
+    /// マウスキャプチャが有効か（--no-mouse・設定で無効、端末が非対応でも false）
+    mouse_captured: bool,
+    /// 性能オーバーレイの計測値（F12 で表示中のみ Some）
+    perf: Option<PerfStats>,
+    /// 追従中のファイルのパス（f で切替。コミットを変えても同じパスを選択する）
+    follow_file: Option<String>,
+    /// File Tree でのテストファイルの表示方法
+    test_split: TestSplit,
+    /// テストファイルとみなすパスの glob
+    test_patterns: Vec<String>,
+    /// まとめ表示中はテストファイルを viewed の進捗に数えない
+    tests_exclude_from_progress: bool,
+    /// 保留コメントプレビューのスクロール位置
+    pending_comments_scroll: u16,
+    /// 画像添付で入力中のパス（空ならクリップボード）
+    attach_input: String,
+    /// 画像添付の後に戻るエディタのモード
+    attach_return_mode: AppMode,
+    /// 画像をアップロードして URL を出力するコマンド（未設定ならドラッグ＆ドロップを案内）
+    attach_upload_command: Option<String>,
+    /// アップロード待ちの画像
+    needs_attach_upload: Option<std::path::PathBuf>,
+    /// 実行を確認しているプログラム
+    allow_command: String,
+    /// 実行の確認の後に戻るモード
+    allow_return_mode: AppMode,
+    /// 実行の確認を待って止まった操作（許可されたらやり直す）
+    permission_retry: Option<PermissionRetry>,
+    /// クリップボードから保存した画像（ブラウザで添付するときのドラッグ元。次に貼るか、アップロードするか、終了したら消す）
+    clipboard_image: Option<attach::ClipboardImage>,
+    /// 保留コメント一覧で選択中のコメント
+    pending_cursor: usize,
+    /// 保留コメントの見出しの入力中のテキスト（入力中のみ Some）
+    pending_header_input: Option<String>,
+    /// ファイル比較フラグ（draw 後に実行）
+    needs_file_compare: bool,
+    /// リンク一覧（gl で PR 本文と Conversation から収集）
+    link_list: Vec<LinkEntry>,
+    /// リンク一覧のカーソル位置
+    link_list_cursor: usize,
+    /// 表示したステータスメッセージの履歴（gm で一覧、古い順）
+    status_history: std::collections::VecDeque<StatusHistoryEntry>,
+    /// メッセージ履歴のスクロール位置
+    status_history_scroll: usize,
+    /// キーバインドのプリセット（設定ファイルの keymap）
+    keymap: Keymap,
+    /// 日時の表示形式（T で切り替え）
+    time_format: TimeFormat,
+    /// レビュー依頼中のユーザー
+    requested_reviewers: Vec<String>,
+    /// レビュー依頼中のチーム（slug）
+    requested_teams: Vec<String>,
+    /// 依頼中チームのうち自分が所属するもの（None は未確認）
+    my_requested_teams: Option<Vec<String>>,
+    /// PR の担当者（assignee）
+    assignees: Vec<String>,
+    /// 依頼中のレビューの依頼日時（Timeline API から取得）
+    review_request_times: crate::github::review::ReviewRequestTimes,
+    /// 自分への依頼をこの時間以上待たせたら強調する（0 で無効）
+    review_sla_hours: u64,
+    /// レビュー本文のテンプレート（送信イベントごと）
+    review_templates: crate::config::ReviewTemplates,
+    /// チーム所属確認フラグ（draw 後に実行）
+    needs_team_check: bool,
+    /// ファイルごとの非公開メモ（ファイルパス → 本文）
+    file_notes: BTreeMap<String, String>,
+    /// 編集中のメモの対象ファイル
+    note_target: Option<String>,
+    /// GraphQL 用の PR ノード ID（PR ミュートに使う）
+    pr_node_id: String,
+    /// ミュートしたスレッドのルートコメント ID
+    muted_threads: HashSet<u64>,
+    /// PR 全体の通知を無視しているか
+    pr_muted: bool,
+    /// 既読にした Conversation エントリのキー
+    seen_entries: HashSet<String>,
+    /// PR ミュート切替フラグ（draw 後に実行）
+    needs_pr_mute: bool,
+    /// 終了待ちで、バックグラウンド処理が終わったら終了するか
+    quit_when_idle: bool,
+    /// GitHub の "Viewed" の取得フラグ（draw 後に実行）
+    needs_viewed_fetch: bool,
+    /// GitHub に送る "Viewed" の変更（ファイル名, viewed）。送れたものから消し、送れなければ保存して次に送り直す
+    viewed_sync: Vec<(String, bool)>,
+    /// "Viewed" の同期フラグ（draw 後にバックグラウンドで送る）
+    needs_viewed_sync: bool,
+    /// "Viewed" を同期中（終わるまで次の同期は始めない）
+    viewed_sync_running: bool,
+    /// 送信できず再送待ちのスレッド resolve / unresolve（古い順）
+    resolve_queue: Vec<crate::github::cache::QueuedResolve>,
+    /// 再送待ちの resolve を送り直すフラグ（draw 後に実行）
+    needs_resolve_replay: bool,
+    /// サイドバー上段に PR Description の代わりに Conversation を表示するか
+    conversation_in_sidebar: bool,
+    /// Commits ペインの範囲選択の起点（v で開始）
+    commit_range_anchor: Option<usize>,
+    /// interdiff の基準にしたコミット（Commits ペインの m、インデックス）
+    interdiff_base: Option<usize>,
+    /// 取得済みの interdiff の変更ファイル（(古い方, 新しい方) のコミットのインデックス → ファイル）
+    interdiff_files: HashMap<(usize, usize), Vec<DiffFile>>,
+    /// 取得に失敗した interdiff の範囲（リロードまで再試行しない）
+    interdiff_failed: HashSet<(usize, usize)>,
+    /// レビューレポートの出力形式（--export-format）
+    report_format: crate::github::export::ReportFormat,
+    /// head ブランチが base より遅れているか（Update branch 可能）
+    behind_base: bool,
+    /// fork からの PR なら head リポジトリの owner
+    fork_owner: Option<String>,
+    /// fork の PR でメンテナーによる push が許可されているか
+    maintainer_can_modify: bool,
+    /// ローカルへのチェックアウトフラグ（draw 後に実行）
+    needs_checkout: bool,
+    /// ブランチ更新フラグ（draw 後に実行）
+    needs_update_branch: Option<UpdateMethod>,
+    /// 自動マージの設定（None は未設定）
+    auto_merge: Option<MergeMethod>,
+    /// 自動マージ状態の取得フラグ（draw 後に実行）
+    needs_auto_merge_check: bool,
+    /// 新しいリリースの確認フラグ（draw 後にバックグラウンドで確認）
+    needs_update_check: bool,
+    /// head コミットの CI チェック（None は未取得）
+    checks: Option<Vec<crate::github::checks::Check>>,
+    /// チェック一覧のカーソル位置
+    checks_cursor: usize,
+    /// CI チェックの取得フラグ（draw 後に実行）
+    needs_checks_fetch: bool,
+    /// チェックを取得中（ヘッダーと一覧に表示する）
+    checks_loading: bool,
+    /// 自動マージ設定変更フラグ（draw 後に実行）
+    needs_auto_merge: Option<AutoMergeAction>,
+    /// PR を閉じる・開き直すフラグ（draw 後に実行）
+    needs_pr_state_change: Option<PrStateChange>,
+    /// 破棄確認ダイアログから戻る先のエディタモード
+    discard_return_mode: AppMode,
+    /// Esc 時に保存したエディタの下書き
+    drafts: HashMap<DraftKey, String>,
+    /// 全文取得済みの新規ファイル（コミット SHA, ファイル名）
+    full_content_loaded: HashSet<(String, String)>,
+    /// ファイル全文取得フラグ（draw 後に実行）
+    needs_full_content: bool,
     /// バックグラウンド非同期データ受信チャネル
     async_rx: Option<mpsc::UnboundedReceiver<crate::AsyncData>>,
+    /// 起動後に App から始めたバックグラウンドタスクの送信側（結果は AsyncData で task_rx に届く）
+    task_tx: mpsc::UnboundedSender<crate::AsyncData>,
+    /// App から始めたバックグラウンドタスクの受信側（初回ロードと違い App が生きている間は閉じない）
+    task_rx: mpsc::UnboundedReceiver<crate::AsyncData>,
     /// 非同期データのロード状態
     pub loading: LoadingState,
     /// HEAD SHA（キャッシュ書き込み用）
@@ -137,8 +446,24 @@ pub struct App {
     conversation_cursor: usize,
     /// Conversation エントリごとの論理行オフセット（ensure_conversation_rendered で計算）
     conversation_entry_offsets: Vec<usize>,
+    /// 折りたたみ中の Conversation エントリ（インデックス）
+    conversation_folded: HashSet<usize>,
+    /// 次の描画でカーソルエントリを中央に配置し直すか（折りたたみ変更後）
+    conversation_recenter: bool,
     /// Conversation エントリごとの Wrap 考慮済み視覚行オフセット（render 時に計算、navigation で参照）
     conversation_visual_offsets: Vec<u16>,
+    /// 取得済みのアバター画像（ログイン名 → 画像）
+    avatar_images: HashMap<String, image::DynamicImage>,
+    /// アバターの描画プロトコル（描画時に作って使い回す）
+    avatar_protocols: HashMap<String, StatefulProtocol>,
+    /// アバターを取得中のワーカー（取得したログイン名と画像）
+    avatar_worker: Option<tokio::task::JoinHandle<(Vec<String>, MediaCache)>>,
+    /// 取得を始めたログイン名（失敗したものも再取得しない）
+    avatar_requested: HashSet<String>,
+    /// Conversation エントリごとのヘッダー内のアバターの列（表示しないなら None）
+    conversation_avatar_cols: Vec<Option<u16>>,
+    /// 描画中のフレームでリンクにする領域と URL（render の最後に OSC 8 で囲む）
+    hyperlinks: Vec<hyperlinks::Hyperlink>,
 }
 
 impl App {
@@ -171,6 +496,7 @@ impl App {
         if !commits.is_empty() {
             commit_list_state.select(Some(0));
         }
+        let (task_tx, task_rx) = mpsc::unbounded_channel();
 
         // root_comment_database_id → ReviewThread のマップを構築
         let thread_map: HashMap<u64, ReviewThread> = review_threads
@@ -190,6 +516,16 @@ impl App {
         {
             file_list_state.select(Some(0));
         }
+        // 初期表示ファイルの位置も保存対象にする
+        let diff = DiffViewState {
+            position_key: commits.first().and_then(|c| {
+                Some((
+                    c.sha.clone(),
+                    files_map.get(&c.sha)?.first()?.filename.clone(),
+                ))
+            }),
+            ..DiffViewState::default()
+        };
 
         Self {
             should_quit: false,
@@ -217,7 +553,7 @@ impl App {
             commit_overview_scroll: 0,
             commit_overview_view_height: 10, // 初期値、render で更新される
             commit_overview_visual_total: 0, // 初期値、render で更新される
-            diff: DiffViewState::default(),
+            diff,
             line_selection: None,
             review: ReviewState {
                 review_comments,
@@ -227,20 +563,31 @@ impl App {
             client,
             status_message: None,
             pending_key: None,
+            conversation_g_origin: None,
             help_scroll: 0,
+            help_view_height: 10, // 初期値、render で更新される
             help_context_panel: Panel::PrDescription,
+            help_filter: String::new(),
+            help_filter_input: false,
             zoomed: false,
             viewed_files: HashMap::new(),
+            hunk_states: HashMap::new(),
             pr_desc_rendered: None,
             conversation_rendered: None,
+            pr_desc_rendered_width: 0,
+            conversation_rendered_width: 0,
+            pr_desc_fold_open: Vec::new(),
+            pr_desc_fold_lines: Vec::new(),
+            pr_desc_fold_rows: Vec::new(),
             theme,
             layout: LayoutCache::default(),
             media_refs: Vec::new(),
             picker: None,
             media_cache: MediaCache::new(),
             media_viewer_index: 0,
-            media_protocol_cache: HashMap::new(),
+            media_protocol_cache: SizedLru::new(MemoryConfig::default().media_bytes()),
             media_protocol_worker: None,
+            coverage: None,
             visible_review_comment_cache,
             is_own_pr,
             current_user,
@@ -249,15 +596,132 @@ impl App {
             conversation_view_height: 10, // 初期値、render で更新される
             conversation_visual_total: 0, // 初期値、render で更新される
             needs_issue_comment_submit: false,
+            posting_issue_comment: None,
             needs_reply_submit: false,
+            needs_comment_edit: false,
+            needs_comment_delete: false,
             needs_reload: false,
+            needs_resize_clamp: false,
+            needs_suspend: false,
+            needs_external_editor: false,
+            run_command: None,
+            command_result: None,
+            running_command: None,
+            command_output_scroll: 0,
+            command_output_follow: false,
+            needs_run_command: false,
+            search_input: String::new(),
+            queue_all_matches: false,
+            quick_reply_index: None,
+            dashboard_cursor: 0,
+            needs_rerequest_review: false,
+            needs_mark_ready: false,
+            checklist: Vec::new(),
+            checklist_cursor: 0,
+            checklist_append: false,
+            file_pin: None,
+            file_compare: None,
+            file_compare_scroll: 0,
+            file_history: None,
+            file_finder_input: String::new(),
+            file_finder_cursor: 0,
+            lazy_files: false,
+            large_pr_summary: None,
+            lazy_failed: HashSet::new(),
+            pr_files: None,
+            pr_files_failed: false,
+            needs_path_load: None,
+            memory: MemoryConfig::default(),
+            files_usage: LruBudget::new(MemoryConfig::default().files_bytes()),
+            files_usage_stale: true,
+            files_evicted: HashSet::new(),
+            mouse_captured: false,
+            perf: None,
+            follow_file: None,
+            test_split: TestSplit::Off,
+            test_patterns: crate::config::TestFilesConfig::default().patterns,
+            tests_exclude_from_progress: false,
+            pending_comments_scroll: 0,
+            attach_input: String::new(),
+            attach_return_mode: AppMode::Normal,
+            attach_upload_command: None,
+            needs_attach_upload: None,
+            allow_command: String::new(),
+            allow_return_mode: AppMode::Normal,
+            permission_retry: None,
+            clipboard_image: None,
+            pending_cursor: 0,
+            pending_header_input: None,
+            needs_file_compare: false,
+            link_list: Vec::new(),
+            link_list_cursor: 0,
+            status_history: std::collections::VecDeque::new(),
+            status_history_scroll: 0,
+            keymap: Keymap::default(),
+            time_format: TimeFormat::default(),
+            requested_reviewers: Vec::new(),
+            requested_teams: Vec::new(),
+            my_requested_teams: None,
+            assignees: Vec::new(),
+            review_request_times: Default::default(),
+            review_sla_hours: crate::config::ReviewConfig::default().sla_hours,
+            review_templates: Default::default(),
+            needs_team_check: false,
+            file_notes: BTreeMap::new(),
+            note_target: None,
+            pr_node_id: String::new(),
+            muted_threads: HashSet::new(),
+            pr_muted: false,
+            seen_entries: HashSet::new(),
+            needs_pr_mute: false,
+            needs_viewed_fetch: false,
+            quit_when_idle: false,
+            viewed_sync: Vec::new(),
+            needs_viewed_sync: false,
+            viewed_sync_running: false,
+            resolve_queue: Vec::new(),
+            needs_resolve_replay: false,
+            conversation_in_sidebar: false,
+            commit_range_anchor: None,
+            interdiff_base: None,
+            interdiff_files: HashMap::new(),
+            interdiff_failed: HashSet::new(),
+            report_format: Default::default(),
+            behind_base: false,
+            fork_owner: None,
+            maintainer_can_modify: false,
+            needs_checkout: false,
+            needs_update_branch: None,
+            auto_merge: None,
+            needs_auto_merge_check: false,
+            needs_update_check: false,
+            checks: None,
+            checks_cursor: 0,
+            needs_checks_fetch: false,
+            checks_loading: false,
+            needs_auto_merge: None,
+            needs_pr_state_change: None,
+            discard_return_mode: AppMode::Normal,
+            drafts: HashMap::new(),
+            full_content_loaded: HashSet::new(),
+            needs_full_content: false,
             async_rx,
+            task_tx,
+            task_rx,
             loading,
             head_sha,
             cache_written,
             conversation_cursor: 0,
             conversation_entry_offsets: Vec::new(),
+            conversation_folded: HashSet::new(),
+            conversation_recenter: false,
             conversation_visual_offsets: Vec::new(),
+            avatar_images: HashMap::new(),
+            avatar_protocols: HashMap::new(),
+            avatar_worker: None,
+            avatar_requested: HashSet::new(),
+            conversation_avatar_cols: Vec::new(),
+            hyperlinks: Vec::new(),
         }
     }
 
@@ -270,10 +734,121 @@ impl App {
         }
     }
 
+    /// レビューイベントを選べない理由（選べる場合は None）
+    fn review_event_disabled_reason(&self, event: ReviewEvent) -> Option<&'static str> {
+        if !self.available_events().contains(&event) {
+            // GitHub は自分の PR への Approve / Request Changes を送信後に拒否する
+            Some(match event {
+                ReviewEvent::RequestChanges => "You cannot request changes on your own PR",
+                _ => "You cannot approve your own PR",
+            })
+        } else if event == ReviewEvent::Comment && self.review.pending_comments.is_empty() {
+            Some("no pending comments")
+        } else {
+            None
+        }
+    }
+
     /// 画像プロトコル検出結果と画像キャッシュをセットする
     pub fn set_media(&mut self, picker: Option<Picker>, media_cache: MediaCache) {
         self.picker = picker;
-        self.media_cache = media_cache;
+        self.apply_media_cache(media_cache);
+        // キャッシュから Conversation を読み込み済みならアバターを取りに行く
+        self.schedule_avatar_fetch();
+    }
+
+    /// マウスキャプチャの状態をセットする（有効にできなかったときはステータスバーで知らせる）
+    pub fn set_mouse_capture(&mut self, wanted: bool, captured: bool) {
+        self.mouse_captured = captured;
+        if wanted && !captured {
+            self.status_message = Some(StatusMessage::info(
+                "Mouse capture unavailable: use the keyboard",
+            ));
+        }
+    }
+
+    /// マウスキャプチャが有効か（終了時の解除に使う）
+    pub fn mouse_captured(&self) -> bool {
+        self.mouse_captured
+    }
+
+    /// カバレッジレポートをセットする
+    pub fn set_coverage(&mut self, coverage: Option<CoverageReport>) {
+        self.coverage = coverage;
+    }
+
+    /// キーバインドのプリセットをセットする
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        self.keymap = keymap;
+    }
+
+    /// レビュー依頼中のユーザーとチームをセットする（チーム所属は初回描画後に確認）
+    pub fn set_review_requests(&mut self, users: Vec<String>, teams: Vec<String>) {
+        self.requested_reviewers = users;
+        self.requested_teams = teams;
+        self.my_requested_teams = None;
+        self.needs_team_check = !self.requested_teams.is_empty() && !self.current_user.is_empty();
+    }
+
+    /// 日時の表示形式をセットする
+    pub fn set_time_format(&mut self, time_format: TimeFormat) {
+        self.time_format = time_format;
+    }
+
+    /// 新しいリリースの確認を予約する（起動を待たせないよう初回描画後に確認する）
+    pub fn schedule_update_check(&mut self) {
+        self.needs_update_check = true;
+    }
+
+    /// 新しいリリースをバックグラウンドで確認する（結果は AsyncData::UpdateAvailable で届く）
+    fn execute_update_check(&mut self) {
+        self.spawn_task(async {
+            let release =
+                Self::run_blocking(|| Ok(crate::update::check_for_update(crate::VERSION))).await;
+            crate::AsyncData::UpdateAvailable(release.ok().flatten().map(|r| r.tag_name))
+        });
+    }
+
+    /// 新しいリリースがある場合にステータスバーで通知する
+    pub fn set_update_notice(&mut self, tag: &str) {
+        self.status_message = Some(StatusMessage::info(format!(
+            "✓ prism {} is available — run `gh prism --update`",
+            tag
+        )));
+    }
+
+    /// 現在のファイルの追加行について diff 行インデックス → カバー済みかを返す
+    fn coverage_line_status(&self) -> HashMap<usize, bool> {
+        let mut status = HashMap::new();
+        if !self.diff.show_coverage {
+            return status;
+        }
+        let Some(coverage) = &self.coverage else {
+            return status;
+        };
+        let Some(file) = self.current_file() else {
+            return status;
+        };
+        let Some(patch) = file.patch.as_deref() else {
+            return status;
+        };
+        let Some(lines) = coverage.file_lines(&file.filename) else {
+            return status;
+        };
+
+        let line_map = review::parse_patch_line_map(patch);
+        for (idx, (text, info)) in patch.lines().zip(line_map.iter()).enumerate() {
+            // 追加行のみ対象（コンテキスト行・削除行は色付けしない）
+            if !text.starts_with('+') {
+                continue;
+            }
+            if let Some(info) = info
+                && let Some(&hits) = lines.get(&info.file_line)
+            {
+                status.insert(idx, hits > 0);
+            }
+        }
+        status
     }
 
     /// PR body 内のメディア参照の数を返す（画像 + 動画）
@@ -288,6 +863,12 @@ impl App {
 
     /// 現在選択中のコミットのファイル一覧を取得
     fn current_files(&self) -> &[DiffFile] {
+        if self.is_all_changes_selected() {
+            return self.pr_files.as_deref().unwrap_or(&[]);
+        }
+        if let Some(range) = self.interdiff_range() {
+            return self.interdiff_files(range);
+        }
         if let Some(idx) = self.commit_list_state.selected()
             && let Some(commit) = self.commits.get(idx)
             && let Some(files) = self.files_map.get(&commit.sha)
@@ -297,18 +878,32 @@ impl App {
         &[]
     }
 
-    /// ファイル選択をリセット（最初のファイルを選択、またはNone）
+    /// ファイル選択をリセット（追従中のファイル、なければ File Tree の表示順で最初のファイルを選択、またはNone）
     fn reset_file_selection(&mut self) {
         let has_files = !self.current_files().is_empty();
         if has_files {
-            self.file_list_state.select(Some(0));
+            let first = self.followed_file_index().or_else(|| {
+                self.file_tree_rows().into_iter().find_map(|row| match row {
+                    FileRow::File(i) => Some(i),
+                    FileRow::TestsHeader => None,
+                })
+            });
+            self.file_list_state.select(Some(first.unwrap_or(0)));
+            if self.followed_file_missing() {
+                self.status_message = Some(StatusMessage::info(
+                    "Followed file is not changed in this commit",
+                ));
+            }
         } else {
             self.file_list_state.select(None);
         }
-        self.diff.cursor_line = 0;
-        self.diff.scroll = 0;
         self.commit_msg_scroll = 0;
         self.commit_overview_scroll = 0;
+        if self.swap_diff_position() {
+            return;
+        }
+        self.diff.cursor_line = 0;
+        self.diff.scroll = 0;
         // 先頭の @@ 行をスキップ
         let max = self.current_diff_line_count();
         self.diff.cursor_line = self.skip_hunk_header_forward(0, max);
@@ -332,25 +927,35 @@ impl App {
 
     /// viewed フラグをトグル（FileTree 用）
     fn toggle_viewed(&mut self) {
+        if self.reject_in_interdiff() {
+            return;
+        }
         let Some(sha) = self.current_commit_sha() else {
             return;
         };
         if let Some(file) = self.current_file() {
             let name = file.filename.clone();
-            let set = self.viewed_files.entry(sha).or_default();
-            if !set.remove(&name) {
-                set.insert(name);
-            }
+            let viewed = !self.is_file_viewed(&sha, &name);
+            self.set_files_viewed(&sha, vec![name], viewed);
         }
     }
 
-    /// コミットの全ファイルが viewed か判定（導出状態）
+    /// コミットの全ファイルが viewed か判定（導出状態、進捗に数えないテストファイルは除く）
     fn is_commit_viewed(&self, sha: &str) -> bool {
-        if let Some(files) = self.files_map.get(sha) {
-            !files.is_empty() && files.iter().all(|f| self.is_file_viewed(sha, &f.filename))
+        let Some(files) = self.files_for(sha) else {
+            return false;
+        };
+        let counted: Vec<&DiffFile> = files
+            .iter()
+            .filter(|f| self.counts_for_progress(&f.filename))
+            .collect();
+        // テストファイルだけのコミットは全ファイルで判定する
+        let files: Vec<&DiffFile> = if counted.is_empty() {
+            files.iter().collect()
         } else {
-            false
-        }
+            counted
+        };
+        !files.is_empty() && files.iter().all(|f| self.is_file_viewed(sha, &f.filename))
     }
 
     /// viewed コミット数を返す
@@ -361,8 +966,11 @@ impl App {
             .count()
     }
 
-    /// 現在選択中のコミット SHA を返す
+    /// 現在選択中のコミット SHA を返す（All changes なら ALL_CHANGES_KEY）
     fn current_commit_sha(&self) -> Option<String> {
+        if self.is_all_changes_selected() {
+            return Some(ALL_CHANGES_KEY.to_string());
+        }
         self.commit_list_state
             .selected()
             .and_then(|idx| self.commits.get(idx))
@@ -374,24 +982,17 @@ impl App {
         let Some(sha) = self.current_commit_sha() else {
             return;
         };
-        let Some(files) = self.files_map.get(&sha) else {
+        let viewed = !self.is_commit_viewed(&sha);
+        self.set_commit_viewed(&sha, viewed);
+    }
+
+    /// コミットの全ファイルを viewed / unview にする
+    fn set_commit_viewed(&mut self, sha: &str, viewed: bool) {
+        let Some(files) = self.files_for(sha) else {
             return;
         };
         let filenames: Vec<String> = files.iter().map(|f| f.filename.clone()).collect();
-        if self.is_commit_viewed(&sha) {
-            // 全ファイルを unview
-            if let Some(set) = self.viewed_files.get_mut(&sha) {
-                for name in &filenames {
-                    set.remove(name);
-                }
-            }
-        } else {
-            // 全ファイルを view
-            let set = self.viewed_files.entry(sha).or_default();
-            for name in filenames {
-                set.insert(name);
-            }
-        }
+        self.set_files_viewed(sha, filenames, viewed);
     }
 
     /// リスト選択行のハイライトスタイル（テーマ対応）
@@ -412,46 +1013,48 @@ impl App {
 
     /// テキストをシステムクリップボードにコピー
     fn copy_to_clipboard(&mut self, text: &str, label: &str) {
-        let result = if cfg!(target_os = "macos") {
-            std::process::Command::new("pbcopy")
-                .stdin(std::process::Stdio::piped())
-                .spawn()
-                .and_then(|mut child| {
-                    use std::io::Write;
-                    if let Some(stdin) = child.stdin.as_mut() {
-                        stdin.write_all(text.as_bytes())?;
-                    }
-                    child.wait()
-                })
-        } else {
-            std::process::Command::new("xclip")
-                .args(["-selection", "clipboard"])
-                .stdin(std::process::Stdio::piped())
-                .spawn()
-                .and_then(|mut child| {
-                    use std::io::Write;
-                    if let Some(stdin) = child.stdin.as_mut() {
-                        stdin.write_all(text.as_bytes())?;
-                    }
-                    child.wait()
-                })
-        };
-
-        match result {
-            Ok(status) if status.success() => {
-                self.status_message =
-                    Some(StatusMessage::info(format!("✓ Copied {}: {}", label, text)));
-            }
-            _ => {
-                self.status_message = Some(StatusMessage::error("✗ Failed to copy to clipboard"));
+        let pending = crate::exec::pending_count();
+        self.status_message = Some(match crate::platform::copy_text(text) {
+            Ok(crate::platform::CopyMethod::Program(_)) => {
+                StatusMessage::info(format!("✓ Copied {}: {}", label, text))
             }
-        }
+            // 端末が OSC 52 に対応していなければ届かないため、経路を明示する
+            Ok(crate::platform::CopyMethod::Osc52) => StatusMessage::info(format!(
+                "✓ Copied {} via terminal (OSC 52): {}",
+                label, text
+            )),
+            Err(_) => StatusMessage::error("✗ Failed to copy to clipboard"),
+        });
+        // 使えるプログラムが未許可だった場合は、許可されたらそのプログラムでコピーし直す
+        self.retry_after_permission(
+            pending,
+            PermissionRetry::Copy {
+                text: text.to_string(),
+                label: label.to_string(),
+            },
+        );
+    }
+
+    /// URL をシステムのデフォルトブラウザで開き、結果をステータスバーに表示する
+    fn open_in_browser(&mut self, url: &str, label: &str) {
+        let pending = crate::exec::pending_count();
+        self.status_message = Some(match crate::platform::open_url(url) {
+            Ok(()) => StatusMessage::info(format!("✓ Opened {}", truncate_str(label, 50))),
+            Err(e) => StatusMessage::error(format!("✗ Failed to open browser: {e}")),
+        });
+        self.retry_after_permission(
+            pending,
+            PermissionRetry::OpenUrl {
+                url: url.to_string(),
+                label: label.to_string(),
+            },
+        );
     }
 
     /// (commit_sha, filename) → 可視レビューコメント数のキャッシュを構築する
-    fn build_visible_comment_cache(
+    fn build_visible_comment_cache<'a>(
         review_comments: &[ReviewComment],
-        files_map: &HashMap<String, Vec<DiffFile>>,
+        files_map: impl IntoIterator<Item = (&'a String, &'a Vec<DiffFile>)>,
     ) -> HashMap<(String, String), usize> {
         let mut cache = HashMap::new();
         for (sha, files) in files_map {
@@ -491,6 +1094,23 @@ impl App {
         cache
     }
 
+    /// visible_review_comment_cache を再計算（All changes を読み込み済みならそのファイルも含める）
+    /// 予算で捨てたコミットはパッチがないため、読み込み直すまで前回の件数を残す
+    fn rebuild_visible_comment_cache(&mut self) {
+        let key = ALL_CHANGES_KEY.to_string();
+        let all_changes = self.pr_files.as_ref().map(|files| (&key, files));
+        let mut cache = Self::build_visible_comment_cache(
+            &self.review.review_comments,
+            self.files_map.iter().chain(all_changes),
+        );
+        cache.extend(
+            self.visible_review_comment_cache
+                .drain()
+                .filter(|((sha, _), _)| self.files_evicted.contains(sha)),
+        );
+        self.visible_review_comment_cache = cache;
+    }
+
     /// キャッシュから (commit_sha, filename) の可視レビューコメント数を取得
     fn cached_visible_comment_count(&self, commit_sha: &str, filename: &str) -> usize {
         self.visible_review_comment_cache
@@ -576,25 +1196,102 @@ impl App {
             .collect()
     }
 
+    /// CommentView で表示中のスレッドが指す diff 行範囲（start_line〜line、論理行インデックス）
+    fn viewing_thread_range(&self) -> Option<(usize, usize)> {
+        let root = self
+            .review
+            .viewing_comments
+            .iter()
+            .find(|c| c.in_reply_to_id.is_none())
+            .or_else(|| self.review.viewing_comments.first())?;
+        let line_map = review::parse_patch_line_map(self.current_file()?.patch.as_deref()?);
+        Self::comment_diff_range(root, &line_map)
+    }
+
+    /// コメントが指す diff 行範囲（論理行インデックス）。始点の側は start_side、なければ side。
+    /// 始点が diff にない場合は範囲内で最初に見つかった行から
+    fn comment_diff_range(
+        comment: &ReviewComment,
+        line_map: &[Option<review::DiffLineInfo>],
+    ) -> Option<(usize, usize)> {
+        let (start, end) = comment.line_range()?;
+        let side = review::Side::from_api(comment.side.as_deref());
+        let start_side = comment
+            .start_side
+            .as_deref()
+            .map_or(side, |s| review::Side::from_api(Some(s)));
+        let find = |side: review::Side, pred: &dyn Fn(usize) -> bool| {
+            line_map
+                .iter()
+                .position(|info| info.is_some_and(|i| i.side == side && pred(i.file_line)))
+        };
+        let end_idx = find(side, &|line| line == end)?;
+        let start_idx = find(start_side, &|line| line == start)
+            .or_else(|| find(side, &|line| (start..=end).contains(&line)))
+            .filter(|&idx| idx <= end_idx)
+            .unwrap_or(end_idx);
+        Some((start_idx, end_idx))
+    }
+
+    /// 現在のファイルで複数行コメントの範囲に含まれる diff 行（コメントが付く末尾行は除く）
+    fn commented_range_lines(&self) -> HashSet<usize> {
+        let mut lines = HashSet::new();
+        let Some(file) = self.current_file() else {
+            return lines;
+        };
+        let Some(patch) = file.patch.as_deref() else {
+            return lines;
+        };
+        let line_map = review::parse_patch_line_map(patch);
+        for comment in &self.review.review_comments {
+            if comment.path != file.filename || comment.start_line.is_none() {
+                continue;
+            }
+            if let Some((start, end)) = Self::comment_diff_range(comment, &line_map) {
+                lines.extend(start..end);
+            }
+        }
+        lines
+    }
+
     pub fn run(&mut self, mut terminal: DefaultTerminal) -> Result<()> {
         while !self.should_quit {
-            // 期限切れのステータスメッセージを自動クリア
+            // 期限切れのステータスメッセージを自動クリア（消す前に履歴へ）
+            self.record_status_message();
             if self.status_message.as_ref().is_some_and(|m| m.is_expired()) {
                 self.status_message = None;
             }
 
             // バックグラウンドワーカーの完了チェック
             self.poll_media_protocol_worker();
+            self.poll_avatar_worker();
+            self.poll_run_command();
             self.poll_async_data();
+            self.poll_task_data();
+            self.poll_command_permission();
+            self.enforce_files_budget();
+            self.quit_if_idle();
+            if self.should_quit {
+                break;
+            }
 
-            terminal.draw(|frame| self.render(frame))?;
+            let frame_start = std::time::Instant::now();
+            let completed = terminal.draw(|frame| self.render(frame))?;
+            // OSC 8 はバッファに入れず、描画済みのリンクのセルだけを描き直す
+            if hyperlinks::enabled() {
+                let cells = hyperlinks::link_cells(completed.buffer, &self.hyperlinks);
+                hyperlinks::emit(terminal.backend_mut(), &cells)?;
+            }
+            if let Some(perf) = &mut self.perf {
+                perf.record_frame(frame_start.elapsed());
+            }
 
             // draw 後に submit を実行（ローディング表示を先にユーザーへ見せる）
             if let Some(event) = self.review.needs_submit.take() {
                 self.submit_review_with_event(event);
                 if self.review.quit_after_submit {
                     self.review.quit_after_submit = false;
-                    self.should_quit = true;
+                    self.request_quit();
                 }
             }
 
@@ -608,6 +1305,16 @@ impl App {
                 self.submit_reply_comment();
             }
 
+            if self.needs_comment_edit {
+                self.needs_comment_edit = false;
+                self.submit_comment_edit();
+            }
+
+            if self.needs_comment_delete {
+                self.needs_comment_delete = false;
+                self.submit_comment_delete();
+            }
+
             if self.needs_reload {
                 self.needs_reload = false;
                 self.execute_reload();
@@ -617,6 +1324,129 @@ impl App {
                 self.execute_resolve_toggle();
             }
 
+            if self.needs_resolve_replay {
+                self.needs_resolve_replay = false;
+                self.execute_resolve_replay();
+            }
+
+            if self.needs_rerequest_review {
+                self.needs_rerequest_review = false;
+                self.execute_rerequest_review();
+            }
+
+            if self.needs_mark_ready {
+                self.needs_mark_ready = false;
+                self.execute_mark_ready();
+            }
+
+            if self.needs_run_command {
+                self.needs_run_command = false;
+                self.execute_run_command();
+            }
+
+            if self.needs_file_compare {
+                self.needs_file_compare = false;
+                self.execute_file_compare();
+            }
+
+            if self.needs_full_content {
+                self.needs_full_content = false;
+                self.execute_full_content();
+            }
+
+            if self.needs_pr_mute {
+                self.needs_pr_mute = false;
+                self.execute_pr_mute();
+            }
+
+            if self.needs_checkout {
+                self.needs_checkout = false;
+                self.execute_checkout();
+            }
+
+            if let Some(path) = self.needs_path_load.take() {
+                self.execute_path_load(path);
+            }
+
+            if self.needs_commit_files() {
+                self.load_selected_commit_files();
+            }
+
+            if self.needs_all_changes_files() {
+                self.load_all_changes_files();
+            }
+
+            if self.needs_interdiff_files() {
+                self.load_interdiff_files();
+            }
+
+            if let Some(method) = self.needs_update_branch.take() {
+                self.execute_update_branch(method);
+            }
+
+            if let Some(action) = self.needs_auto_merge.take() {
+                self.execute_auto_merge(action);
+            }
+
+            if let Some(change) = self.needs_pr_state_change.take() {
+                self.execute_pr_state_change(change);
+            }
+
+            if let Some(path) = self.needs_attach_upload.take() {
+                self.execute_attach_upload(path);
+            }
+
+            if self.needs_team_check {
+                self.needs_team_check = false;
+                self.execute_team_check();
+            }
+
+            if self.needs_auto_merge_check {
+                self.needs_auto_merge_check = false;
+                self.execute_auto_merge_check();
+            }
+
+            if self.needs_update_check {
+                self.needs_update_check = false;
+                self.execute_update_check();
+            }
+
+            if self.needs_checks_fetch && !self.checks_loading {
+                self.needs_checks_fetch = false;
+                self.execute_checks_fetch();
+            }
+
+            if self.needs_viewed_sync && !self.viewed_sync_running {
+                self.needs_viewed_sync = false;
+                self.execute_viewed_sync();
+            }
+
+            if self.needs_viewed_fetch {
+                self.needs_viewed_fetch = false;
+                self.execute_viewed_fetch();
+            }
+
+            if self.needs_suspend {
+                self.needs_suspend = false;
+                drop(terminal);
+                terminal = self.suspend_and_resume()?;
+                continue;
+            }
+
+            if self.needs_external_editor {
+                self.needs_external_editor = false;
+                drop(terminal);
+                terminal = self.edit_in_external_editor()?;
+                continue;
+            }
+
+            // リサイズ直後は新しいサイズで計算された値でクランプし、イベント待ちせず再描画
+            if self.needs_resize_clamp {
+                self.needs_resize_clamp = false;
+                self.clamp_after_resize();
+                continue;
+            }
+
             self.handle_events()?;
         }
         Ok(())
@@ -648,7 +1478,8 @@ impl App {
             ])
         } else {
             let mut lines: Vec<Line<'static>> = vec![title_line, separator, Line::raw("")];
-            lines.extend(markdown::render_markdown(&processed_body, self.theme));
+            let offset = lines.len();
+            lines.extend(self.render_pr_body_with_folds(&processed_body, offset));
             Text::from(lines)
         };
         self.pr_desc_rendered = Some(text);
@@ -662,6 +1493,7 @@ impl App {
 
         let mut lines: Vec<Line<'static>> = Vec::new();
         let mut entry_offsets: Vec<usize> = Vec::new();
+        let mut avatar_cols: Vec<Option<u16>> = Vec::new();
 
         if self.conversation.is_empty() {
             lines.push(Line::styled(
@@ -669,20 +1501,54 @@ impl App {
                 Style::default().fg(Color::DarkGray),
             ));
         } else {
-            for entry in &self.conversation {
+            for (i, entry) in self.conversation.iter().enumerate() {
                 entry_offsets.push(lines.len());
-                // ヘッダー行: @author (date) [STATE]
-                let date_display = format_datetime(&entry.created_at);
+                let folded = self.conversation_folded.contains(&i);
+                // ヘッダー行: #N @author (date) [STATE]（#N はエントリのアンカー番号）
+                let date_display = self.format_time(&entry.created_at);
                 let mut header_spans = vec![
+                    Span::styled(
+                        format!(" {}#{}", if folded { "▸" } else { "▾" }, i + 1),
+                        Style::default().fg(Color::DarkGray),
+                    ),
                     Span::styled(
                         format!(" @{}", entry.author),
-                        Style::default().fg(Color::Cyan),
+                        Style::default().fg(author_color(&entry.author)),
                     ),
                     Span::styled(
                         format!(" ({})", date_display),
                         Style::default().fg(Color::DarkGray),
                     ),
                 ];
+                // 送信中の仮表示には印を付ける（未読にはしない）
+                let mut author_span = 1;
+                if self.posting_issue_comment == Some(i) {
+                    header_spans.push(Span::styled(
+                        " [posting…]",
+                        Style::default().fg(Color::Yellow),
+                    ));
+                } else if self.is_entry_unread(i) {
+                    header_spans.insert(1, Span::styled(" ●", Style::default().fg(Color::Cyan)));
+                    author_span += 1;
+                }
+                // アバターはイニシャルで場所を取り、画像があれば描画時に上から重ねる
+                let avatar_col = crate::format::avatars().then(|| {
+                    let col: usize = header_spans[..author_span].iter().map(Span::width).sum();
+                    header_spans.splice(
+                        author_span..author_span,
+                        [
+                            Span::raw(" "),
+                            Span::styled(
+                                avatars::avatar_initials(&entry.author),
+                                Style::default()
+                                    .fg(Color::Black)
+                                    .bg(author_color(&entry.author)),
+                            ),
+                        ],
+                    );
+                    col as u16 + 1
+                });
+                avatar_cols.push(avatar_col);
 
                 // Review の場合は state ラベルを追加（COMMENTED は非表示）
                 if let ConversationKind::Review { ref state } = entry.kind {
@@ -705,6 +1571,7 @@ impl App {
                     ref path,
                     line,
                     is_resolved,
+                    root_comment_id,
                     ..
                 } = entry.kind
                 {
@@ -720,23 +1587,56 @@ impl App {
                             Style::default().fg(Color::DarkGray),
                         ));
                     }
+                    if self.muted_threads.contains(&root_comment_id) {
+                        header_spans.push(Span::styled(
+                            " [Muted]",
+                            Style::default().fg(Color::DarkGray),
+                        ));
+                    }
+                }
+
+                // 折りたたみ中はヘッダーのみ（返信数を添える）
+                if folded {
+                    if let ConversationKind::CodeComment { ref replies, .. } = entry.kind
+                        && !replies.is_empty()
+                    {
+                        header_spans.push(Span::styled(
+                            format!(
+                                " … {} {}",
+                                replies.len(),
+                                if replies.len() == 1 {
+                                    "reply"
+                                } else {
+                                    "replies"
+                                }
+                            ),
+                            Style::default().fg(Color::DarkGray),
+                        ));
+                    }
+                    lines.push(Line::from(header_spans));
+                    lines.push(Line::raw(""));
+                    continue;
                 }
 
                 lines.push(Line::from(header_spans));
 
                 // 本文をマークダウンレンダリング（bat ハイライト or プレーンテキスト）
                 if !entry.body.is_empty() {
-                    lines.extend(markdown::render_markdown(&entry.body, self.theme));
+                    lines.extend(markdown::render_markdown(
+                        &entry.body,
+                        self.theme,
+                        self.conversation_rendered_width as usize,
+                    ));
                 }
 
                 // CodeComment のリプライを描画
                 if let ConversationKind::CodeComment { ref replies, .. } = entry.kind {
                     for reply in replies {
-                        let reply_date = format_datetime(&reply.created_at);
+                        let reply_date = self.format_time(&reply.created_at);
                         lines.push(Line::from(vec![
                             Span::styled(
                                 format!("   @{}", reply.author),
-                                Style::default().fg(Color::Cyan),
+                                Style::default().fg(author_color(&reply.author)),
                             ),
                             Span::styled(
                                 format!(" ({})", reply_date),
@@ -745,7 +1645,11 @@ impl App {
                         ]));
                         if !reply.body.is_empty() {
                             // リプライ本文もマークダウンレンダリング
-                            lines.extend(markdown::render_markdown(&reply.body, self.theme));
+                            lines.extend(markdown::render_markdown(
+                                &reply.body,
+                                self.theme,
+                                self.conversation_rendered_width as usize,
+                            ));
                         }
                     }
                 }
@@ -758,6 +1662,7 @@ impl App {
         }
 
         self.conversation_entry_offsets = entry_offsets;
+        self.conversation_avatar_cols = avatar_cols;
         // カーソル位置をクランプ
         if !self.conversation.is_empty() {
             self.conversation_cursor = self.conversation_cursor.min(self.conversation.len() - 1);
@@ -877,9 +1782,18 @@ impl App {
 
     /// コメント入力モードに入る（行選択がある場合のみ）
     fn enter_comment_input_mode(&mut self) {
-        if self.line_selection.is_some() {
+        if self.line_selection.is_some() && !self.reject_in_interdiff() {
             self.review.comment_editor.clear();
             self.mode = AppMode::CommentInput;
+            self.restore_draft();
+        }
+    }
+
+    /// s: 選択行の suggestion ブロックを入れた状態でコメント入力を始める（下書きがあればその後ろに足す）
+    fn enter_suggestion_input_mode(&mut self) {
+        self.enter_comment_input_mode();
+        if self.mode == AppMode::CommentInput {
+            self.insert_suggestion();
         }
     }
 
@@ -887,6 +1801,7 @@ impl App {
     fn cancel_comment_input(&mut self) {
         self.review.comment_editor.clear();
         self.line_selection = None;
+        self.queue_all_matches = false;
         self.mode = AppMode::Normal;
     }
 
@@ -896,18 +1811,15 @@ impl App {
             return;
         }
 
-        if let Some(selection) = self.line_selection {
+        if self.queue_all_matches {
+            self.queue_comment_on_matches();
+        } else if let Some(selection) = self.line_selection {
             let (start, end) = selection.range(self.diff.cursor_line);
             let file_path = self
                 .current_file()
                 .map(|f| f.filename.clone())
                 .unwrap_or_default();
-            let commit_sha = self
-                .commit_list_state
-                .selected()
-                .and_then(|idx| self.commits.get(idx))
-                .map(|c| c.sha.clone())
-                .unwrap_or_default();
+            let commit_sha = self.current_commit_sha().unwrap_or_default();
 
             self.review.pending_comments.push(PendingComment {
                 file_path,
@@ -923,6 +1835,36 @@ impl App {
         self.mode = AppMode::Normal;
     }
 
+    /// 検索マッチ全行に同じ本文の pending comment を1件ずつ追加する
+    fn queue_comment_on_matches(&mut self) {
+        self.queue_all_matches = false;
+        let matches = self.search_match_lines();
+        if matches.is_empty() {
+            return;
+        }
+        let file_path = self
+            .current_file()
+            .map(|f| f.filename.clone())
+            .unwrap_or_default();
+        let commit_sha = self.current_commit_sha().unwrap_or_default();
+        let body = self.review.comment_editor.text();
+        let count = matches.len();
+        for idx in matches {
+            self.review.pending_comments.push(PendingComment {
+                file_path: file_path.clone(),
+                start_line: idx,
+                end_line: idx,
+                body: body.clone(),
+                commit_sha: commit_sha.clone(),
+            });
+        }
+        self.status_message = Some(StatusMessage::info(format!(
+            "✓ Queued {} comment{}",
+            count,
+            if count == 1 { "" } else { "s" }
+        )));
+    }
+
     /// 選択範囲の diff 行から「新しい側」のコードを抽出する
     fn extract_suggestion_lines(&self, start: usize, end: usize) -> Result<Vec<String>, String> {
         let patch = self
@@ -957,8 +1899,16 @@ impl App {
         let (start, end) = selection.range(self.diff.cursor_line);
         match self.extract_suggestion_lines(start, end) {
             Ok(code_lines) => {
-                let template = format!("```suggestion\n{}\n```", code_lines.join("\n"));
+                let code = code_lines.join("\n");
+                // コードにバッククォートの連続があっても閉じないよう、それより長いフェンスにする
+                let fence = crate::github::export::code_fence(&code);
+                let template = format!("{fence}suggestion\n{code}\n{fence}");
                 self.review.comment_editor.insert_text(&template);
+                if self.fork_push_blocked() {
+                    self.status_message = Some(StatusMessage::info(
+                        "Fork without maintainer edits: only the author can apply suggestions",
+                    ));
+                }
             }
             Err(msg) => {
                 self.status_message = Some(StatusMessage::error(msg));
@@ -981,6 +1931,11 @@ impl App {
         if event == ReviewEvent::Comment && self.review.pending_comments.is_empty() {
             return;
         }
+        // 自分の PR では Comment のみ（API の不明瞭なエラーを避けるため送信前に止める）
+        if let Some(reason) = self.review_event_disabled_reason(event) {
+            self.status_message = Some(StatusMessage::error(format!("✗ {}", reason)));
+            return;
+        }
 
         let Some(client) = &self.client else {
             self.status_message = Some(StatusMessage::error("✗ No API client available"));
@@ -999,6 +1954,22 @@ impl App {
         };
 
         let count = self.review.pending_comments.len();
+        let body = self.review_body_text();
+        // All changes への保留コメントは PR 全体のパッチで行番号を解決する
+        let files_map = match &self.pr_files {
+            Some(files)
+                if self
+                    .review
+                    .pending_comments
+                    .iter()
+                    .any(|pc| pc.commit_sha == ALL_CHANGES_KEY) =>
+            {
+                let mut map = self.files_map.clone();
+                map.insert(ALL_CHANGES_KEY.to_string(), files.clone());
+                std::borrow::Cow::Owned(map)
+            }
+            _ => std::borrow::Cow::Borrowed(&self.files_map),
+        };
         let ctx = review::ReviewContext {
             client,
             owner,
@@ -1012,9 +1983,9 @@ impl App {
                 &ctx,
                 head_sha,
                 &self.review.pending_comments,
-                &self.files_map,
+                &files_map,
                 event.as_api_str(),
-                &self.review.review_body_editor.text(),
+                &body,
             ))
         });
 
@@ -1031,7 +2002,7 @@ impl App {
                     format!("✓ {}", event.label())
                 };
                 self.status_message = Some(StatusMessage::info(msg));
-                self.review.pending_comments.clear();
+                self.clear_pending_comments();
                 self.review.review_body_editor.clear();
             }
             Err(e) => {
@@ -1040,19 +2011,58 @@ impl App {
         }
     }
 
+    /// 送信する Issue Comment を Conversation の末尾に仮表示する（送信結果で置き換えるか取り除く）
+    fn append_posting_issue_comment(&mut self) {
+        let author = if self.current_user.is_empty() {
+            "you".to_string()
+        } else {
+            self.current_user.clone()
+        };
+        self.conversation.push(ConversationEntry {
+            author,
+            body: self.review.comment_editor.text(),
+            created_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            kind: ConversationKind::IssueComment,
+        });
+        self.posting_issue_comment = Some(self.conversation.len() - 1);
+        self.conversation_cursor = self.conversation.len() - 1;
+        self.conversation_rendered = None; // キャッシュ無効化
+        // 末尾までスクロール（次の render で visual_total が更新されるため大きな値を設定）
+        self.conversation_scroll = u16::MAX;
+    }
+
+    /// 送信に失敗した Issue Comment の仮表示を取り除き、本文は下書きに戻す（c で開き直せる）
+    fn withdraw_posting_issue_comment(&mut self, posting: Option<usize>) {
+        if let Some(index) = posting.filter(|&i| i < self.conversation.len()) {
+            self.conversation.remove(index);
+            self.conversation_cursor = self
+                .conversation_cursor
+                .min(self.conversation.len().saturating_sub(1));
+            self.conversation_rendered = None;
+        }
+        let body = self.review.comment_editor.text();
+        if !body.trim().is_empty() {
+            self.drafts.insert(DraftKey::IssueComment, body);
+        }
+        self.review.comment_editor.clear();
+    }
+
     /// Issue Comment を GitHub API に送信
     fn submit_issue_comment(&mut self) {
+        let posting = self.posting_issue_comment.take();
         let body = self.review.comment_editor.text();
         if body.trim().is_empty() {
             return;
         }
 
         let Some(client) = &self.client else {
+            self.withdraw_posting_issue_comment(posting);
             self.status_message = Some(StatusMessage::error("✗ No API client available"));
             return;
         };
 
         let Some((owner, repo)) = self.parse_repo() else {
+            self.withdraw_posting_issue_comment(posting);
             self.status_message = Some(StatusMessage::error("✗ Invalid repo format"));
             return;
         };
@@ -1069,20 +2079,33 @@ impl App {
 
         match result {
             Ok(comment) => {
-                self.conversation.push(ConversationEntry {
+                let entry = ConversationEntry {
                     author: comment.user.login,
                     body: comment.body.unwrap_or_default(),
                     created_at: comment.created_at,
                     kind: ConversationKind::IssueComment,
-                });
+                };
+                // 仮表示を送信結果で置き換える
+                match posting.and_then(|i| self.conversation.get_mut(i)) {
+                    Some(slot) => *slot = entry,
+                    None => {
+                        self.conversation.push(entry);
+                        self.conversation_cursor = self.conversation.len() - 1;
+                        self.conversation_scroll = u16::MAX;
+                    }
+                }
                 self.conversation_rendered = None; // キャッシュ無効化
                 self.review.comment_editor.clear();
-                // 末尾までスクロール（次の render で visual_total が更新されるため大きな値を設定）
-                self.conversation_scroll = u16::MAX;
+                // 自分のコメントは新着として数えない
+                self.mark_conversation_seen();
                 self.status_message = Some(StatusMessage::info("✓ Comment posted"));
             }
             Err(e) => {
-                self.status_message = Some(StatusMessage::error(format!("✗ Failed: {}", e)));
+                self.withdraw_posting_issue_comment(posting);
+                self.status_message = Some(StatusMessage::error(format!(
+                    "✗ Failed: {} (comment kept as a draft)",
+                    e
+                )));
             }
         }
     }
@@ -1128,6 +2151,7 @@ impl App {
                 // viewing_comments が表示中なら追加（CommentView 経由時）
                 if !self.review.viewing_comments.is_empty() {
                     self.review.viewing_comments.push(comment.clone());
+                    self.review.viewing_comment_cursor = self.review.viewing_comments.len() - 1;
                 }
 
                 // conversation 内の該当 CodeComment エントリに reply を追加
@@ -1140,6 +2164,7 @@ impl App {
                         && root_comment_id == in_reply_to
                     {
                         replies.push(CodeCommentReply {
+                            id: comment.id,
                             author: comment.user.login.clone(),
                             body: comment.body.clone(),
                             created_at: comment.created_at.clone(),
@@ -1151,6 +2176,18 @@ impl App {
                 self.conversation_rendered = None; // キャッシュ無効化
                 self.review.comment_editor.clear();
                 self.status_message = Some(StatusMessage::info("✓ Reply posted"));
+
+                // 返信後に resolve する指定があれば同じループで resolve を実行
+                if std::mem::take(&mut self.review.resolve_after_reply)
+                    && let Some(thread) = self.review.thread_map.get(&in_reply_to)
+                    && !thread.is_resolved
+                {
+                    self.review.needs_resolve_toggle = Some(ResolveToggleRequest {
+                        thread_node_id: thread.node_id.clone(),
+                        should_resolve: true,
+                        root_comment_id: in_reply_to,
+                    });
+                }
             }
             Err(e) => {
                 // 失敗時は reply_to_comment_id を復元して再試行可能に
@@ -1165,7 +2202,11 @@ impl App {
         let Some(root_id) = comments::root_comment_id(&self.review.viewing_comments) else {
             return;
         };
+        self.request_resolve_toggle(root_id);
+    }
 
+    /// 指定スレッドの resolve/unresolve を要求する（draw 後に実行）
+    pub(super) fn request_resolve_toggle(&mut self, root_id: u64) {
         let Some(thread) = self.review.thread_map.get(&root_id) else {
             self.status_message = Some(StatusMessage::error("Thread info not available"));
             return;
@@ -1193,41 +2234,57 @@ impl App {
 
         match result {
             Ok(is_resolved) if is_resolved == req.should_resolve => {
-                // thread_map を更新
-                if let Some(thread) = self.review.thread_map.get_mut(&req.root_comment_id) {
-                    thread.is_resolved = req.should_resolve;
-                }
-                // conversation 内の該当エントリを更新
-                for entry in &mut self.conversation {
-                    if let ConversationKind::CodeComment {
-                        ref mut is_resolved,
-                        ref thread_node_id,
-                        ..
-                    } = entry.kind
-                        && thread_node_id.as_deref() == Some(&req.thread_node_id)
-                    {
-                        *is_resolved = req.should_resolve;
-                    }
-                }
-                self.conversation_rendered = None; // キャッシュ無効化
+                self.set_thread_resolved(
+                    req.root_comment_id,
+                    &req.thread_node_id,
+                    req.should_resolve,
+                );
                 let label = if req.should_resolve {
                     "✓ Thread resolved"
                 } else {
                     "✓ Thread unresolved"
                 };
                 self.status_message = Some(StatusMessage::info(label));
+                // 通信できたので、再送待ちがあれば続けて送る
+                self.needs_resolve_replay = !self.resolve_queue.is_empty();
             }
             Ok(_) => {
                 self.status_message = Some(StatusMessage::error(
                     "✗ Operation returned unexpected state",
                 ));
             }
+            // オフラインなど一時的な失敗だけキューに積んで後で再送する
+            Err(e) if crate::github::retry::is_transient(&e.to_string()) => {
+                self.queue_resolve(req);
+            }
             Err(e) => {
                 self.status_message = Some(StatusMessage::error(format!("✗ Failed: {}", e)));
             }
         }
     }
 
+    /// 端末状態を復元して自プロセスを停止し、fg で再開したら TUI を再初期化する
+    fn suspend_and_resume(&mut self) -> Result<DefaultTerminal> {
+        crate::mouse::disable(self.mouse_captured);
+        ratatui::restore();
+
+        // SIGTSTP を自分に送る（シェルのジョブ制御に戻り、SIGCONT でここから再開）
+        #[cfg(unix)]
+        let _ = nix::sys::signal::raise(nix::sys::signal::Signal::SIGTSTP);
+
+        // 画像プロトコル再検出（raw mode では OSC クエリが動かないため init 前に実行）
+        self.picker = Picker::from_query_stdio().ok();
+        self.media_protocol_cache.clear();
+        self.media_protocol_worker = None;
+        self.avatar_protocols.clear();
+
+        let terminal = ratatui::init();
+        self.mouse_captured = crate::mouse::enable(self.mouse_captured);
+        // 停止中に端末サイズが変わっている可能性がある
+        self.handle_resize();
+        Ok(terminal)
+    }
+
     /// PR データをリロードして App 状態を更新する
     fn execute_reload(&mut self) {
         let Some(client) = &self.client else {
@@ -1252,10 +2309,20 @@ impl App {
         let saved_zoomed = self.zoomed;
         let saved_viewed_files = self.viewed_files.clone();
         let saved_pending_comments = self.review.pending_comments.clone();
+        // 遅延読み込み中は読み込み済みのコミットだけを取り直す
+        let lazy_commits = self
+            .lazy_files
+            .then(|| self.files_map.keys().cloned().collect::<HashSet<_>>());
 
         // block_in_place + block_on で async を呼ぶ（既存パターン踏襲）
         let result = tokio::task::block_in_place(|| {
-            Handle::current().block_on(crate::reload_pr_data(&client, &owner, &repo, pr_number))
+            Handle::current().block_on(crate::reload_pr_data(
+                &client,
+                &owner,
+                &repo,
+                pr_number,
+                lazy_commits.as_ref(),
+            ))
         });
 
         match result {
@@ -1263,18 +2330,38 @@ impl App {
                 // PR メタデータを更新
                 self.pr_title = data.metadata.pr_title;
                 self.pr_body = data.metadata.pr_body;
+                self.refresh_pr_tasks();
                 self.pr_author = data.metadata.pr_author;
                 self.pr_base_branch = data.metadata.pr_base_branch;
                 self.pr_head_branch = data.metadata.pr_head_branch;
                 self.pr_created_at = data.metadata.pr_created_at;
                 self.pr_state = data.metadata.pr_state;
+                self.set_review_requests(
+                    data.metadata.requested_reviewers,
+                    data.metadata.requested_teams,
+                );
+                self.set_assignees(data.metadata.assignees);
+                self.behind_base = data.metadata.behind_base;
+                self.set_fork(
+                    data.metadata.fork_owner,
+                    data.metadata.maintainer_can_modify,
+                );
+                self.schedule_auto_merge_check();
+                // head が進んでいればチェックも変わる
+                self.schedule_checks_fetch();
 
                 // コミット・ファイル・コメントを差し替え
                 self.commits = data.commits;
                 self.files_map = data.files_map;
+                self.files_map_replaced();
+                self.lazy_failed.clear();
+                // All changes は選択時に取り直す
+                self.pr_files = None;
+                self.pr_files_failed = false;
+                self.reset_interdiff();
                 self.review.review_comments = data.review_comments.clone();
 
-                // thread_map を再構築
+                // thread_map を再構築（再送待ちの resolve は後で上書き）
                 self.review.thread_map = data
                     .review_threads
                     .into_iter()
@@ -1282,10 +2369,7 @@ impl App {
                     .collect();
 
                 // visible_review_comment_cache を再計算
-                self.visible_review_comment_cache = Self::build_visible_comment_cache(
-                    &self.review.review_comments,
-                    &self.files_map,
-                );
+                self.rebuild_visible_comment_cache();
 
                 // conversation を再構築
                 self.conversation = crate::build_conversation(
@@ -1294,6 +2378,12 @@ impl App {
                     data.review_comments,
                     &self.review.thread_map.values().cloned().collect::<Vec<_>>(),
                 );
+                // 新しく加わった作者のアバターを取りに行く
+                self.schedule_avatar_fetch();
+
+                // 再送待ちの resolve を反映し、接続が戻ったので再送する
+                self.apply_resolve_queue();
+                self.needs_resolve_replay = !self.resolve_queue.is_empty();
 
                 // is_own_pr を再判定
                 self.is_own_pr =
@@ -1302,23 +2392,30 @@ impl App {
                 // キャッシュ無効化
                 self.pr_desc_rendered = None;
                 self.conversation_rendered = None;
-                self.diff.highlight_cache = None;
+                self.conversation_folded.clear();
+                self.diff.highlight_cache.clear();
+                self.full_content_loaded.clear();
 
                 // メディア状態リセット（pr_body 更新に追従）
                 self.media_refs = Vec::new();
                 self.media_protocol_cache.clear();
                 self.media_protocol_worker = None;
+                self.avatar_protocols.clear();
 
                 // 状態の復元
                 self.focused_panel = saved_focused_panel;
                 self.zoomed = saved_zoomed;
                 self.viewed_files = saved_viewed_files;
+                // web UI で付け外しした "Viewed" を取り直す
+                self.schedule_viewed_fetch();
                 self.review.pending_comments = saved_pending_comments;
 
                 // コミット選択の復元: SHA で再検索
                 if let Some(ref sha) = saved_commit_sha {
                     if let Some(idx) = self.commits.iter().position(|c| c.sha == *sha) {
                         self.commit_list_state.select(Some(idx));
+                    } else if sha == ALL_CHANGES_KEY && self.has_all_changes_row() {
+                        self.commit_list_state.select(Some(self.commits.len()));
                     } else if !self.commits.is_empty() {
                         // 見つからなければ末尾（最新コミット）
                         self.commit_list_state.select(Some(self.commits.len() - 1));
@@ -1351,6 +2448,7 @@ impl App {
                 let max = self.current_diff_line_count();
                 self.diff.cursor_line = self.skip_hunk_header_forward(0, max);
                 self.diff.visual_offsets = None;
+                self.diff.position_key = self.diff_position_key();
 
                 // スクロール位置のリセット
                 self.pr_desc_scroll = 0;
@@ -1381,43 +2479,7 @@ impl App {
         // try_recv() ループで全メッセージを処理
         loop {
             match rx.try_recv() {
-                Ok(data) => match data {
-                    crate::AsyncData::FilesMap(files_map) => {
-                        self.apply_files_map(files_map);
-                    }
-                    crate::AsyncData::ConversationData {
-                        review_comments,
-                        issue_comments,
-                        reviews,
-                        review_threads,
-                    } => {
-                        self.apply_conversation_data(
-                            review_comments,
-                            issue_comments,
-                            reviews,
-                            review_threads,
-                        );
-                    }
-                    crate::AsyncData::MediaData(media_cache) => {
-                        self.media_cache = media_cache;
-                        self.loading.media = LoadPhase::Done;
-                    }
-                    crate::AsyncData::Error(kind, msg) => {
-                        self.status_message =
-                            Some(StatusMessage::error(format!("✗ {msg} — press R to retry")));
-                        match kind {
-                            crate::AsyncErrorKind::Files => {
-                                self.loading.files = LoadPhase::Error;
-                            }
-                            crate::AsyncErrorKind::Conversation => {
-                                self.loading.conversation = LoadPhase::Error;
-                            }
-                            crate::AsyncErrorKind::Media => {
-                                self.loading.media = LoadPhase::Error;
-                            }
-                        }
-                    }
-                },
+                Ok(data) => self.apply_async_data(data),
                 Err(mpsc::error::TryRecvError::Empty) => break,
                 Err(mpsc::error::TryRecvError::Disconnected) => {
                     disconnected = true;
@@ -1430,13 +2492,13 @@ impl App {
             // 全タスク完了 → rx を返却せずに破棄
             // チャネル切断時に Loading のままのフェーズがあればエラーに強制遷移
             if self.loading.files == LoadPhase::Loading {
-                self.loading.files = LoadPhase::Error;
+                self.loading.files = LoadPhase::Error(ApiErrorKind::Other);
             }
             if self.loading.conversation == LoadPhase::Loading {
-                self.loading.conversation = LoadPhase::Error;
+                self.loading.conversation = LoadPhase::Error(ApiErrorKind::Other);
             }
             if self.loading.media == LoadPhase::Loading {
-                self.loading.media = LoadPhase::Error;
+                self.loading.media = LoadPhase::Error(ApiErrorKind::Other);
             }
             self.try_write_cache();
         } else {
@@ -1445,20 +2507,119 @@ impl App {
         }
     }
 
-    /// files_map をバックグラウンドデータで更新
-    fn apply_files_map(&mut self, files_map: HashMap<String, Vec<DiffFile>>) {
-        self.files_map = files_map;
-        self.loading.files = LoadPhase::Done;
-
+    /// App から始めたバックグラウンドタスクの結果を受信・適用する
+    fn poll_task_data(&mut self) {
+        while let Ok(data) = self.task_rx.try_recv() {
+            self.apply_async_data(data);
+        }
+    }
+
+    /// バックグラウンドタスクを始める（結果の AsyncData は poll_task_data で受け取る。ランタイム外では false）
+    pub(super) fn spawn_task<F>(&self, task: F) -> bool
+    where
+        F: std::future::Future<Output = crate::AsyncData> + Send + 'static,
+    {
+        let Ok(runtime) = Handle::try_current() else {
+            return false;
+        };
+        let tx = self.task_tx.clone();
+        runtime.spawn(async move {
+            let _ = tx.send(task.await);
+        });
+        true
+    }
+
+    /// バックグラウンドタスクの中で gh CLI などのブロッキング処理を実行する（エラーは表示用の文字列にする）
+    pub(super) async fn run_blocking<T, F>(f: F) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T> + Send + 'static,
+    {
+        match tokio::task::spawn_blocking(f).await {
+            Ok(result) => result.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// バックグラウンドから届いたデータを適用する
+    fn apply_async_data(&mut self, data: crate::AsyncData) {
+        match data {
+            crate::AsyncData::FileStats(stats) => self.apply_file_stats(stats),
+            crate::AsyncData::FilesMap(files_map) => {
+                self.apply_files_map(files_map);
+            }
+            crate::AsyncData::ConversationData {
+                review_comments,
+                issue_comments,
+                reviews,
+                review_threads,
+            } => {
+                self.apply_conversation_data(
+                    review_comments,
+                    issue_comments,
+                    reviews,
+                    review_threads,
+                );
+            }
+            crate::AsyncData::ReviewRequests(times) => {
+                self.apply_review_requests(times);
+            }
+            crate::AsyncData::MediaData(media_cache) => {
+                self.apply_media_cache(media_cache);
+                self.loading.media = LoadPhase::Done;
+            }
+            crate::AsyncData::Checks { sha, result } => self.apply_checks(&sha, result),
+            crate::AsyncData::TeamMembership { requested, mine } => {
+                self.apply_team_membership(&requested, mine);
+            }
+            crate::AsyncData::AutoMerge(result) => {
+                // 取得に失敗したら未設定として扱う
+                self.auto_merge = result.ok().flatten();
+            }
+            crate::AsyncData::UpdateAvailable(tag) => {
+                if let Some(tag) = tag {
+                    self.set_update_notice(&tag);
+                }
+            }
+            crate::AsyncData::BranchUpdated { old_head, landed } => {
+                self.apply_branch_updated(&old_head, landed);
+            }
+            crate::AsyncData::ViewedFiles(result) => self.apply_viewed_files(result),
+            crate::AsyncData::ViewedSynced { synced, failed } => {
+                self.apply_viewed_synced(synced, failed);
+            }
+            crate::AsyncData::Error(kind, error, msg) => {
+                self.status_message =
+                    Some(StatusMessage::error(format!("✗ {msg} — press R to retry")));
+                match kind {
+                    crate::AsyncErrorKind::Files => {
+                        self.loading.files = LoadPhase::Error(error);
+                    }
+                    crate::AsyncErrorKind::Conversation => {
+                        self.loading.conversation = LoadPhase::Error(error);
+                    }
+                    crate::AsyncErrorKind::Media => {
+                        self.loading.media = LoadPhase::Error(error);
+                    }
+                }
+            }
+        }
+    }
+
+    /// files_map をバックグラウンドデータで更新
+    fn apply_files_map(&mut self, files_map: HashMap<String, Vec<DiffFile>>) {
+        self.files_map = files_map;
+        self.files_map_replaced();
+        self.loading.files = LoadPhase::Done;
+
         // visible_review_comment_cache を再計算
-        self.visible_review_comment_cache =
-            Self::build_visible_comment_cache(&self.review.review_comments, &self.files_map);
+        self.rebuild_visible_comment_cache();
 
         // ファイル選択を初期化
         self.reset_file_selection();
 
         // diff キャッシュ無効化
-        self.diff.highlight_cache = None;
+        self.diff.highlight_cache.clear();
     }
 
     /// conversation データをバックグラウンドデータで更新
@@ -1476,25 +2637,25 @@ impl App {
             .map(|t| (t.root_comment_database_id, t))
             .collect();
 
-        // visible_review_comment_cache を事前計算（review_comments の参照のみ必要）
-        self.visible_review_comment_cache =
-            Self::build_visible_comment_cache(&review_comments, &self.files_map);
-
         // conversation を構築（review_comments の所有権を渡す）
         // build_conversation が所有権を要求するため、self.review.review_comments 用に先に clone
         self.review.review_comments = review_comments.clone();
+        self.rebuild_visible_comment_cache();
         self.conversation =
             crate::build_conversation(issue_comments, reviews, review_comments, &review_threads);
+        self.apply_resolve_queue();
 
         // レンダリングキャッシュ無効化
         self.conversation_rendered = None;
 
         self.loading.conversation = LoadPhase::Done;
+        self.schedule_avatar_fetch();
     }
 
     /// キャッシュ書き込みを試行（files + conversation 両方 Done かつ未書き込みの場合）
     fn try_write_cache(&mut self) {
-        if self.cache_written {
+        // 遅延読み込み中は一部のコミットしかないため書かない
+        if self.cache_written || self.lazy_files {
             return;
         }
         if self.loading.files != LoadPhase::Done || self.loading.conversation != LoadPhase::Done {
@@ -2980,6 +4141,7 @@ mod tests {
             },
             created_at: "2025-01-01T00:00:00Z".to_string(),
             in_reply_to_id: None,
+            diff_hunk: None,
         }
     }
 
@@ -3081,6 +4243,30 @@ mod tests {
         assert!(app.review.viewing_comments.is_empty());
     }
 
+    #[test]
+    fn test_diff_hunk_preview_marks_commented_lines() {
+        let mut comment = make_review_comment("src/main.rs", Some(12), "RIGHT", "nit");
+        assert!(helpers::diff_hunk_preview(&comment).is_empty());
+
+        comment.diff_hunk = Some("@@ -1,8 +1,9 @@\n a\n b\n c\n d\n e\n-f\n+F\n+G\n g".to_string());
+        let lines = helpers::diff_hunk_preview(&comment);
+        // 末尾 6 行のみ表示し、最終行（コメント対象行）にだけ印を付ける
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0].to_string(), "  d");
+        assert_eq!(lines[5].to_string(), "▎ g");
+        assert!(lines[..5].iter().all(|l| l.to_string().starts_with(' ')));
+
+        // 複数行コメントは範囲の行すべてに印を付ける
+        comment.start_line = Some(10);
+        comment.start_side = Some("RIGHT".to_string());
+        let lines = helpers::diff_hunk_preview(&comment);
+        let marked = lines
+            .iter()
+            .filter(|l| l.to_string().starts_with('▎'))
+            .count();
+        assert_eq!(marked, 3);
+    }
+
     /// 複数 hunk のパッチを持つ App を作成するヘルパー
     fn create_app_with_multi_hunk_patch() -> App {
         TestAppBuilder::new()
@@ -4002,7 +5188,10 @@ mod tests {
             app.review.review_body_editor.insert_char(ch);
         }
 
+        // 入力があるので破棄確認を挟む
         app.handle_review_body_input_mode(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::DiscardConfirm);
+        app.handle_discard_confirm_mode(KeyCode::Char('d'));
         assert_eq!(app.mode, AppMode::ReviewSubmit);
         assert!(app.review.review_body_editor.is_empty());
         assert!(app.review.needs_submit.is_none());
@@ -4120,11 +5309,13 @@ mod tests {
         app.handle_normal_mode(KeyCode::Char('c'), KeyModifiers::NONE);
         assert_eq!(app.mode, AppMode::IssueCommentInput);
 
-        // テキスト入力後に Esc → エディタクリア、Normal モード、Conversation パネル
+        // テキスト入力後に Esc → 破棄確認 → d でエディタクリア、Normal モード、Conversation パネル
         app.handle_issue_comment_input_mode(KeyCode::Char('x'), KeyModifiers::NONE);
         assert!(!app.review.comment_editor.is_empty());
 
         app.handle_issue_comment_input_mode(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::DiscardConfirm);
+        app.handle_discard_confirm_mode(KeyCode::Char('d'));
         assert_eq!(app.mode, AppMode::Normal);
         assert_eq!(app.focused_panel, Panel::Conversation);
         assert!(app.review.comment_editor.is_empty());
@@ -4251,4 +5442,3529 @@ mod tests {
         });
         assert_eq!(app.blocking_operation_message(), Some("Updating thread..."));
     }
+
+    // ── Coverage オーバーレイ ──────────────────────────
+
+    fn create_app_with_coverage() -> App {
+        let mut app = TestAppBuilder::new()
+            .with_custom_patch(
+                "@@ -1,2 +1,3 @@\n ctx\n+added1\n+added2\n-removed",
+                "modified",
+                2,
+                1,
+            )
+            .build();
+        app.set_coverage(Some(CoverageReport::parse_lcov(
+            "SF:/work/src/main.rs\nDA:1,1\nDA:2,3\nDA:3,0\nend_of_record\n",
+        )));
+        app
+    }
+
+    #[test]
+    fn test_coverage_line_status_added_lines_only() {
+        let app = create_app_with_coverage();
+        let status = app.coverage_line_status();
+        // idx 0: @@, 1: コンテキスト行（対象外）, 2: +added1 (line 2), 3: +added2 (line 3)
+        assert_eq!(status.get(&1), None);
+        assert_eq!(status.get(&2), Some(&true));
+        assert_eq!(status.get(&3), Some(&false));
+        assert_eq!(status.get(&4), None);
+    }
+
+    #[test]
+    fn test_coverage_line_status_without_report() {
+        let app = TestAppBuilder::new()
+            .with_custom_patch("@@ -0,0 +1 @@\n+line1", "added", 1, 0)
+            .build();
+        assert!(app.coverage_line_status().is_empty());
+    }
+
+    #[test]
+    fn test_coverage_toggle_key() {
+        let mut app = create_app_with_coverage();
+        app.focused_panel = Panel::DiffView;
+        app.handle_normal_mode(KeyCode::Char('i'), KeyModifiers::NONE);
+        assert!(!app.diff.show_coverage);
+        assert!(app.coverage_line_status().is_empty());
+        app.handle_normal_mode(KeyCode::Char('i'), KeyModifiers::NONE);
+        assert!(app.diff.show_coverage);
+        assert!(!app.coverage_line_status().is_empty());
+
+        // C はカバレッジがあっても CI チェックを開く（API クライアントがなければ開かない）
+        app.handle_normal_mode(KeyCode::Char('C'), KeyModifiers::NONE);
+        assert!(app.diff.show_coverage);
+    }
+
+    #[test]
+    fn test_coverage_toggle_without_report_shows_error() {
+        let mut app = create_app_with_patch();
+        app.focused_panel = Panel::DiffView;
+        app.handle_normal_mode(KeyCode::Char('i'), KeyModifiers::NONE);
+        assert!(app.diff.show_coverage);
+        assert!(
+            app.status_message
+                .as_ref()
+                .is_some_and(|m| m.level == StatusLevel::Error)
+        );
+    }
+
+    // ── Resize ──────────────────────────
+
+    #[test]
+    fn test_handle_resize_invalidates_layout_caches() {
+        let mut app = create_app_with_patch();
+        app.pr_desc_visual_total = 40;
+        app.commit_msg_visual_total = 10;
+        app.conversation_visual_total = 30;
+        app.commit_overview_visual_total = 20;
+        app.diff.visual_offsets = Some(vec![0, 1, 2]);
+        app.handle_resize();
+        assert_eq!(app.pr_desc_visual_total, 0);
+        assert_eq!(app.commit_msg_visual_total, 0);
+        assert_eq!(app.conversation_visual_total, 0);
+        assert_eq!(app.commit_overview_visual_total, 0);
+        assert!(app.diff.visual_offsets.is_none());
+        assert!(app.needs_resize_clamp);
+    }
+
+    #[test]
+    fn test_clamp_after_resize_limits_scrolls() {
+        let mut app = create_app_with_patch();
+        // 端末拡大でスクロール上限が縮んだ状態を再現
+        app.conversation_visual_total = 30;
+        app.conversation_view_height = 25;
+        app.conversation_scroll = 20;
+        app.commit_msg_visual_total = 5;
+        app.commit_msg_view_height = 10;
+        app.commit_msg_scroll = 3;
+        app.review.comment_view_max_scroll = 2;
+        app.review.viewing_comment_scroll = 8;
+        app.clamp_after_resize();
+        assert_eq!(app.conversation_scroll, 5);
+        assert_eq!(app.commit_msg_scroll, 0);
+        assert_eq!(app.review.viewing_comment_scroll, 2);
+    }
+
+    #[test]
+    fn test_clamp_after_resize_keeps_diff_cursor_visible() {
+        let mut app = create_app_with_patch();
+        app.diff.cursor_line = 9;
+        app.diff.scroll = 0;
+        // 端末縮小で表示行数が減った
+        app.diff.view_height = 3;
+        app.clamp_after_resize();
+        assert!(app.diff.scroll as usize + 3 > app.diff.cursor_line);
+        assert!(app.diff.scroll as usize <= app.diff.cursor_line);
+    }
+
+    // ── Command 実行 ──────────────────────────
+
+    #[test]
+    fn test_bang_without_command_shows_error() {
+        let mut app = create_app_with_patch();
+        app.handle_normal_mode(KeyCode::Char('!'), KeyModifiers::SHIFT);
+        assert!(!app.needs_run_command);
+        assert!(
+            app.status_message
+                .as_ref()
+                .is_some_and(|m| m.level == StatusLevel::Error)
+        );
+    }
+
+    #[test]
+    fn test_bang_sets_run_flag_without_blocking() {
+        let mut app = create_app_with_patch();
+        app.set_run_command(Some("true".to_string()));
+        app.handle_normal_mode(KeyCode::Char('!'), KeyModifiers::SHIFT);
+        assert!(app.needs_run_command);
+        // バックグラウンドで実行するので UI は止めない
+        assert_eq!(app.blocking_operation_message(), None);
+    }
+
+    /// 実行中のコマンドが終わるまで出力を取り込む
+    fn wait_run_command(app: &mut App) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while app.running_command.is_some() {
+            assert!(Instant::now() < deadline, "command did not finish");
+            std::thread::sleep(Duration::from_millis(10));
+            app.poll_run_command();
+        }
+    }
+
+    #[test]
+    fn test_execute_run_command_opens_output_overlay() {
+        let mut app = create_app_with_patch();
+        app.set_run_command(Some("echo hello; exit 1".to_string()));
+        app.execute_run_command();
+        assert_eq!(app.mode, AppMode::CommandOutput);
+        assert!(app.command_result.as_ref().unwrap().running);
+        wait_run_command(&mut app);
+        let result = app.command_result.as_ref().unwrap();
+        assert!(!result.running);
+        assert_eq!(result.output, "hello\n");
+        assert_eq!(result.exit_code, Some(1));
+
+        // r で再実行、Esc で閉じる
+        app.handle_command_output_mode(KeyCode::Char('r'));
+        assert!(app.needs_run_command);
+        app.handle_command_output_mode(KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_stop_run_command() {
+        let mut app = create_app_with_patch();
+        app.set_run_command(Some("echo started; sleep 30".to_string()));
+        app.execute_run_command();
+        assert!(app.running_command.is_some());
+
+        // x で止めると結果が確定し、閉じずにそのまま出力を見られる
+        app.handle_command_output_mode(KeyCode::Char('x'));
+        assert!(app.running_command.is_none());
+        assert_eq!(app.mode, AppMode::CommandOutput);
+        let result = app.command_result.as_ref().unwrap();
+        assert!(!result.running);
+        assert_eq!(result.exit_code, None);
+        assert_eq!(result.badge(), " [! ✗ signal]");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_stop_run_command_kills_child_processes() {
+        let mut app = create_app_with_patch();
+        app.set_run_command(Some("sleep 30 & echo $!; wait".to_string()));
+        app.execute_run_command();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while app.command_result.as_ref().unwrap().output.is_empty() {
+            assert!(Instant::now() < deadline, "no output");
+            std::thread::sleep(Duration::from_millis(10));
+            app.poll_run_command();
+        }
+        let pid = app
+            .command_result
+            .as_ref()
+            .unwrap()
+            .output
+            .trim()
+            .to_string();
+
+        app.stop_run_command();
+        // sh だけでなく sleep も止まる（init に回収されるまではゾンビとして残りうる）
+        let stat = format!("/proc/{pid}/stat");
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while std::fs::read_to_string(&stat).is_ok_and(|s| !s.contains(") Z ")) {
+            assert!(Instant::now() < deadline, "child process is still running");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    // ── Review Checklist ──────────────────────────
+
+    fn create_app_with_checklist() -> App {
+        let mut app = TestAppBuilder::new()
+            .repo("checklist-owner/checklist-repo")
+            .build();
+        let mut saved = crate::github::cache::ChecklistCache::default();
+        saved.checked.insert("Tests".to_string(), true);
+        app.set_checklist(
+            vec!["Security".to_string(), "Tests".to_string()],
+            Some(saved),
+        );
+        app
+    }
+
+    #[test]
+    fn test_set_checklist_restores_saved_state() {
+        let app = create_app_with_checklist();
+        assert!(!app.checklist[0].checked);
+        assert!(app.checklist[1].checked);
+        assert_eq!(app.checklist_checked_count(), 1);
+        assert_eq!(app.checklist_markdown(), "- [ ] Security\n- [x] Tests");
+    }
+
+    #[test]
+    fn test_checklist_mode_navigation() {
+        let mut app = create_app_with_checklist();
+        app.handle_normal_mode(KeyCode::Char('L'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::Checklist);
+        app.handle_checklist_mode(KeyCode::Char('k'));
+        assert_eq!(app.checklist_cursor, 1);
+        app.handle_checklist_mode(KeyCode::Char('j'));
+        assert_eq!(app.checklist_cursor, 0);
+        app.handle_checklist_mode(KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_checklist_open_without_items_shows_error() {
+        let mut app = create_app_with_patch();
+        app.handle_normal_mode(KeyCode::Char('L'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.status_message.is_some());
+    }
+
+    #[test]
+    fn test_review_body_text_appends_checklist() {
+        let mut app = create_app_with_checklist();
+        app.review.review_body_editor.insert_text("LGTM");
+        assert_eq!(app.review_body_text(), "LGTM");
+
+        app.checklist_append = true;
+        assert_eq!(
+            app.review_body_text(),
+            "LGTM\n\n- [ ] Security\n- [x] Tests"
+        );
+
+        // 本文が空ならチェックリストのみ
+        app.review.review_body_editor.clear();
+        assert_eq!(app.review_body_text(), "- [ ] Security\n- [x] Tests");
+    }
+
+    // ── DiffView 検索 / マッチ一括コメント ──────────────────────────
+
+    fn create_app_with_search_patch() -> App {
+        let mut app = TestAppBuilder::new()
+            .with_custom_patch(
+                "@@ -1,3 +1,4 @@\n old_api();\n+old_api(1);\n+new_api();\n-Old_Api(2);",
+                "modified",
+                2,
+                1,
+            )
+            .build();
+        app.focused_panel = Panel::DiffView;
+        app
+    }
+
+    fn type_search(app: &mut App, query: &str) {
+        app.handle_normal_mode(KeyCode::Char('/'), KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::DiffSearch);
+        for c in query.chars() {
+            app.handle_diff_search_mode(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_diff_search_mode(KeyCode::Enter, KeyModifiers::NONE);
+    }
+
+    #[test]
+    fn test_search_match_lines_case_insensitive() {
+        let mut app = create_app_with_search_patch();
+        type_search(&mut app, "old_api");
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.search_match_lines(), vec![1, 2, 4]);
+        // 最初のマッチへジャンプ
+        assert_eq!(app.diff.cursor_line, 1);
+    }
+
+    #[test]
+    fn test_search_jump_next_prev() {
+        let mut app = create_app_with_search_patch();
+        type_search(&mut app, "old_api");
+        app.handle_normal_mode(KeyCode::Char(']'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('s'), KeyModifiers::NONE);
+        assert_eq!(app.diff.cursor_line, 2);
+        app.handle_normal_mode(KeyCode::Char(']'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('s'), KeyModifiers::NONE);
+        assert_eq!(app.diff.cursor_line, 4);
+        app.handle_normal_mode(KeyCode::Char('['), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('s'), KeyModifiers::NONE);
+        assert_eq!(app.diff.cursor_line, 2);
+    }
+
+    #[test]
+    fn test_search_no_match_shows_error() {
+        let mut app = create_app_with_search_patch();
+        type_search(&mut app, "nothing");
+        assert!(app.search_match_lines().is_empty());
+        assert!(
+            app.status_message
+                .as_ref()
+                .is_some_and(|m| m.level == StatusLevel::Error)
+        );
+    }
+
+    #[test]
+    fn test_search_esc_keeps_previous_query() {
+        let mut app = create_app_with_search_patch();
+        type_search(&mut app, "new_api");
+        app.handle_normal_mode(KeyCode::Char('/'), KeyModifiers::NONE);
+        app.handle_diff_search_mode(KeyCode::Backspace, KeyModifiers::NONE);
+        app.handle_diff_search_mode(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.diff.search_query, "new_api");
+    }
+
+    #[test]
+    fn test_queue_comment_on_all_matches() {
+        let mut app = create_app_with_search_patch();
+        type_search(&mut app, "old_api");
+        app.handle_normal_mode(KeyCode::Char('A'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::CommentInput);
+        assert!(app.queue_all_matches);
+
+        app.review.comment_editor.insert_text("Deprecated");
+        app.confirm_comment();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(!app.queue_all_matches);
+        let lines: Vec<usize> = app
+            .review
+            .pending_comments
+            .iter()
+            .map(|c| c.start_line)
+            .collect();
+        assert_eq!(lines, vec![1, 2, 4]);
+        assert!(
+            app.review
+                .pending_comments
+                .iter()
+                .all(|c| c.body == "Deprecated" && c.start_line == c.end_line)
+        );
+    }
+
+    #[test]
+    fn test_queue_all_matches_without_search_shows_error() {
+        let mut app = create_app_with_search_patch();
+        app.handle_normal_mode(KeyCode::Char('A'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.status_message.is_some());
+    }
+
+    #[test]
+    fn test_cancel_queue_all_matches_resets_flag() {
+        let mut app = create_app_with_search_patch();
+        type_search(&mut app, "old_api");
+        app.handle_normal_mode(KeyCode::Char('A'), KeyModifiers::SHIFT);
+        app.cancel_comment_input();
+        assert!(!app.queue_all_matches);
+        assert!(app.review.pending_comments.is_empty());
+    }
+
+    // ── Author ダッシュボード ──────────────────────────
+
+    fn make_thread_entry(
+        author: &str,
+        root_id: u64,
+        reply_authors: &[&str],
+        is_resolved: bool,
+    ) -> ConversationEntry {
+        ConversationEntry {
+            author: author.to_string(),
+            body: format!("Comment {root_id}\nsecond line"),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            kind: ConversationKind::CodeComment {
+                path: "src/main.rs".to_string(),
+                line: Some(root_id as usize),
+                replies: reply_authors
+                    .iter()
+                    .enumerate()
+                    .map(|(i, a)| CodeCommentReply {
+                        id: root_id * 100 + i as u64 + 1,
+                        author: a.to_string(),
+                        body: "reply".to_string(),
+                        created_at: "2024-01-02T00:00:00Z".to_string(),
+                    })
+                    .collect(),
+                is_resolved,
+                thread_node_id: Some(format!("RT_{root_id}")),
+                root_comment_id: root_id,
+            },
+        }
+    }
+
+    fn make_review_entry(author: &str, state: &str) -> ConversationEntry {
+        ConversationEntry {
+            author: author.to_string(),
+            body: String::new(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            kind: ConversationKind::Review {
+                state: state.to_string(),
+            },
+        }
+    }
+
+    fn create_dashboard_app() -> App {
+        let mut app = create_own_pr_app();
+        app.current_user = "me".to_string();
+        app.conversation = vec![
+            make_review_entry("alice", "CHANGES_REQUESTED"),
+            make_thread_entry("alice", 1, &["me"], false),
+            make_thread_entry("bob", 2, &[], false),
+            make_thread_entry("bob", 3, &["me", "bob"], false),
+            make_thread_entry("alice", 4, &[], true),
+            make_review_entry("alice", "COMMENTED"),
+            make_review_entry("bob", "APPROVED"),
+            make_review_entry("me", "COMMENTED"),
+        ];
+        app
+    }
+
+    #[test]
+    fn test_dashboard_threads_unresolved_with_addressed_state() {
+        let app = create_dashboard_app();
+        let threads = app.dashboard_threads();
+        let ids: Vec<u64> = threads.iter().map(|t| t.root_comment_id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert!(threads[0].addressed);
+        assert!(!threads[1].addressed);
+        assert!(!threads[2].addressed);
+        assert_eq!(threads[0].summary, "Comment 1");
+        assert_eq!(threads[0].entry_index, 1);
+    }
+
+    #[test]
+    fn test_reviewer_states_latest_decisive_state() {
+        let app = create_dashboard_app();
+        assert_eq!(
+            app.reviewer_states(),
+            vec![
+                ("alice".to_string(), "CHANGES_REQUESTED".to_string()),
+                ("bob".to_string(), "APPROVED".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dashboard_only_on_own_pr() {
+        let mut app = create_app_with_patch();
+        app.handle_normal_mode(KeyCode::Char('D'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.status_message.is_some());
+
+        let mut app = create_dashboard_app();
+        app.handle_normal_mode(KeyCode::Char('D'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::Dashboard);
+    }
+
+    #[test]
+    fn test_dashboard_actions() {
+        let mut app = create_dashboard_app();
+        app.open_dashboard();
+        app.handle_dashboard_mode(KeyCode::Char('j'));
+        assert_eq!(app.dashboard_cursor, 1);
+
+        // r で resolve リクエスト
+        app.handle_dashboard_mode(KeyCode::Char('r'));
+        let req = app.review.needs_resolve_toggle.take().unwrap();
+        assert_eq!(req.root_comment_id, 2);
+        assert!(req.should_resolve);
+
+        // c で返信モード
+        app.dashboard_cursor = 1;
+        app.handle_dashboard_mode(KeyCode::Char('c'));
+        assert_eq!(app.mode, AppMode::ReplyInput);
+        assert_eq!(app.review.reply_to_comment_id, Some(2));
+        assert_eq!(app.focused_panel, Panel::Conversation);
+        assert_eq!(app.conversation_cursor, 2);
+    }
+
+    #[test]
+    fn test_dashboard_enter_jumps_to_conversation() {
+        let mut app = create_dashboard_app();
+        app.open_dashboard();
+        app.handle_dashboard_mode(KeyCode::Char('j'));
+        app.handle_dashboard_mode(KeyCode::Char('j'));
+        app.handle_dashboard_mode(KeyCode::Char('j'));
+        assert_eq!(app.dashboard_cursor, 2);
+        app.handle_dashboard_mode(KeyCode::Enter);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.focused_panel, Panel::Conversation);
+        assert_eq!(app.conversation_cursor, 3);
+    }
+
+    #[test]
+    fn test_dashboard_quick_action_flags() {
+        let mut app = create_dashboard_app();
+        app.open_dashboard();
+        app.handle_dashboard_mode(KeyCode::Char('Q'));
+        assert_eq!(
+            app.blocking_operation_message(),
+            Some("Requesting review...")
+        );
+        app.needs_rerequest_review = false;
+        app.handle_dashboard_mode(KeyCode::Char('M'));
+        assert_eq!(
+            app.blocking_operation_message(),
+            Some("Marking ready for review...")
+        );
+    }
+
+    // ── Quick reply ──────────────────────────
+
+    #[test]
+    fn test_quick_reply_cycles_through_templates() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.review.reply_to_comment_id = Some(1);
+        app.mode = AppMode::ReplyInput;
+
+        app.handle_reply_input_mode(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert_eq!(app.review.comment_editor.text(), "Done");
+
+        // 未編集なら次の定型文に置き換え（{sha} は最新コミットの短縮 SHA）
+        app.handle_reply_input_mode(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert_eq!(app.review.comment_editor.text(), "Fixed in def4567");
+        assert_eq!(app.mode, AppMode::ReplyInput);
+    }
+
+    #[test]
+    fn test_quick_reply_inserts_at_cursor_after_edit() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.mode = AppMode::ReplyInput;
+        app.handle_reply_input_mode(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        app.handle_reply_input_mode(KeyCode::Char('!'), KeyModifiers::NONE);
+        // 編集済みなら置き換えず先頭の定型文を挿入
+        app.handle_reply_input_mode(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert_eq!(app.review.comment_editor.text(), "Done!Done");
+    }
+
+    #[test]
+    fn test_insert_commit_link_uses_selected_commit() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.mode = AppMode::ReplyInput;
+        app.commit_list_state.select(Some(0));
+        app.handle_reply_input_mode(KeyCode::Char('l'), KeyModifiers::CONTROL);
+        assert_eq!(
+            app.review.comment_editor.text(),
+            format!("Addressed in {} (First commit)", TEST_SHA_0)
+        );
+    }
+
+    #[test]
+    fn test_resolve_after_reply_toggle_and_reset_on_cancel() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.mode = AppMode::ReplyInput;
+        app.review.reply_to_comment_id = Some(1);
+        app.handle_reply_input_mode(KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert!(app.review.resolve_after_reply);
+        app.handle_reply_input_mode(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.review.resolve_after_reply);
+    }
+
+    // ── ファイル比較 ──────────────────────────
+
+    #[test]
+    fn test_file_pin_toggle() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.commit_list_state.select(Some(0));
+        app.focused_panel = Panel::FileTree;
+        app.handle_normal_mode(KeyCode::Char('p'), KeyModifiers::NONE);
+        assert_eq!(
+            app.file_pin,
+            Some(FilePin {
+                filename: "src/main.rs".to_string(),
+                commit_sha: TEST_SHA_0.to_string(),
+            })
+        );
+        // 同じファイル・コミットで再度 p → 解除
+        app.handle_normal_mode(KeyCode::Char('p'), KeyModifiers::NONE);
+        assert_eq!(app.file_pin, None);
+    }
+
+    #[test]
+    fn test_file_compare_requires_pin_and_different_commit() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.commit_list_state.select(Some(0));
+        app.focused_panel = Panel::CommitList;
+        app.handle_normal_mode(KeyCode::Char('p'), KeyModifiers::NONE);
+        assert!(!app.needs_file_compare);
+
+        app.focused_panel = Panel::FileTree;
+        app.handle_normal_mode(KeyCode::Char('p'), KeyModifiers::NONE);
+        app.focused_panel = Panel::CommitList;
+        app.handle_normal_mode(KeyCode::Char('p'), KeyModifiers::NONE);
+        assert!(!app.needs_file_compare);
+
+        app.commit_list_state.select(Some(1));
+        app.handle_normal_mode(KeyCode::Char('p'), KeyModifiers::NONE);
+        assert!(app.needs_file_compare);
+    }
+
+    #[test]
+    fn test_file_compare_range_is_chronological() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.file_pin = Some(FilePin {
+            filename: "src/main.rs".to_string(),
+            commit_sha: TEST_SHA_1.to_string(),
+        });
+        app.commit_list_state.select(Some(0));
+        assert_eq!(
+            app.file_compare_range(),
+            Some((TEST_SHA_0.to_string(), TEST_SHA_1.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_file_compare_mode_scroll_and_close() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.mode = AppMode::FileCompare;
+        app.handle_file_compare_mode(KeyCode::Char('j'));
+        assert_eq!(app.file_compare_scroll, 1);
+        app.handle_file_compare_mode(KeyCode::Char('g'));
+        assert_eq!(app.file_compare_scroll, 0);
+        app.handle_file_compare_mode(KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    // ── リンク一覧 ──────────────────────────
+
+    #[test]
+    fn test_gl_opens_link_list_with_dedup() {
+        let mut app = TestAppBuilder::new()
+            .with_test_data()
+            .pr_body(
+                "[Doc](https://example.com/doc)\nhttps://example.com/doc\nhttps://ci.example.com",
+            )
+            .build();
+        app.focused_panel = Panel::PrDescription;
+        app.handle_normal_mode(KeyCode::Char('g'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('l'), KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::LinkList);
+        assert_eq!(app.link_list.len(), 2);
+        assert_eq!(app.link_list[0].title.as_deref(), Some("Doc"));
+        assert_eq!(app.link_index_for_digit('2'), Some(1));
+        assert_eq!(app.link_index_for_digit('3'), None);
+    }
+
+    #[test]
+    fn test_g_followed_by_other_key_is_not_swallowed() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.focused_panel = Panel::FileTree;
+        app.handle_normal_mode(KeyCode::Char('g'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(app.file_list_state.selected(), Some(1));
+        assert!(app.pending_key.is_none());
+    }
+
+    #[test]
+    fn test_gl_without_links_shows_status() {
+        let mut app = TestAppBuilder::new()
+            .with_test_data()
+            .pr_body("no links")
+            .build();
+        app.handle_normal_mode(KeyCode::Char('g'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('l'), KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.status_message.is_some());
+    }
+
+    // ── <details> 折りたたみ ──────────────────────────
+
+    #[test]
+    fn test_details_closed_by_default_and_toggle_with_enter() {
+        let mut app = TestAppBuilder::new()
+            .with_test_data()
+            .pr_body("<details>\n<summary>Logs</summary>\nsecret line\n</details>")
+            .build();
+        app.focused_panel = Panel::PrDescription;
+        app.ensure_pr_desc_rendered();
+        let text_of = |app: &App| {
+            app.pr_desc_rendered
+                .as_ref()
+                .unwrap()
+                .lines
+                .iter()
+                .map(|l| {
+                    l.spans
+                        .iter()
+                        .map(|s| s.content.as_ref())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        assert!(text_of(&app).contains("▶ Logs"));
+        assert!(!text_of(&app).contains("secret line"));
+
+        app.handle_normal_mode(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.focused_panel, Panel::PrDescription);
+        app.ensure_pr_desc_rendered();
+        assert!(text_of(&app).contains("▼ Logs"));
+        assert!(text_of(&app).contains("secret line"));
+    }
+
+    #[test]
+    fn test_enter_without_visible_details_opens_conversation() {
+        let mut app = TestAppBuilder::new()
+            .with_test_data()
+            .pr_body("plain body")
+            .build();
+        app.focused_panel = Panel::PrDescription;
+        app.ensure_pr_desc_rendered();
+        app.handle_normal_mode(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.focused_panel, Panel::Conversation);
+    }
+
+    // ── キーマップ ──────────────────────────
+
+    #[test]
+    fn test_emacs_keymap_ctrl_n_p_moves() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.focused_panel = Panel::FileTree;
+        app.handle_normal_mode(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert_eq!(app.file_list_state.selected(), Some(0));
+
+        app.set_keymap(Keymap::Emacs);
+        app.handle_normal_mode(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert_eq!(app.file_list_state.selected(), Some(1));
+        app.handle_normal_mode(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        assert_eq!(app.file_list_state.selected(), Some(0));
+    }
+
+    // ── API 統計 ──────────────────────────
+
+    #[test]
+    fn test_stats_overlay_open_and_close() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.handle_normal_mode(KeyCode::Char('I'), KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::Stats);
+        app.handle_stats_mode(KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_format_duration_and_bytes() {
+        use helpers::{format_bytes, format_duration};
+        assert_eq!(format_duration(Duration::from_millis(250)), "250 ms");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1.5 s");
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(2048), "2.0 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+    }
+
+    // ── パッチ省略ファイルの全文表示 ──────────────────────────
+
+    fn omitted_added_file_app() -> App {
+        let mut files_map = HashMap::new();
+        files_map.insert(
+            TEST_SHA_0.to_string(),
+            vec![DiffFile {
+                filename: "data/huge.json".to_string(),
+                status: "added".to_string(),
+                additions: 5000,
+                deletions: 0,
+                patch: None,
+            }],
+        );
+        let mut app = TestAppBuilder::new()
+            .with_commits()
+            .files_map(files_map)
+            .build();
+        app.commit_list_state.select(Some(0));
+        app.file_list_state.select(Some(0));
+        app.focused_panel = Panel::DiffView;
+        app
+    }
+
+    #[test]
+    fn test_added_file_patch() {
+        assert_eq!(
+            crate::github::files::added_file_patch("a\nb\n"),
+            "@@ -0,0 +1,2 @@\n+a\n+b"
+        );
+    }
+
+    #[test]
+    fn test_request_full_content_for_omitted_added_file() {
+        let mut app = omitted_added_file_app();
+        app.handle_normal_mode(KeyCode::Char('F'), KeyModifiers::NONE);
+        assert!(app.needs_full_content);
+
+        // パッチがあるファイルでは要求しない
+        let mut app = TestAppBuilder::new().with_patch().build();
+        app.focused_panel = Panel::DiffView;
+        app.handle_normal_mode(KeyCode::Char('F'), KeyModifiers::NONE);
+        assert!(!app.needs_full_content);
+    }
+
+    #[test]
+    fn test_new_file_view_forces_line_numbers() {
+        let mut app = omitted_added_file_app();
+        assert_eq!(app.line_number_prefix_width(), 0);
+        app.set_file_patch(
+            TEST_SHA_0,
+            "data/huge.json",
+            crate::github::files::added_file_patch("{}\n"),
+        );
+        app.full_content_loaded
+            .insert((TEST_SHA_0.to_string(), "data/huge.json".to_string()));
+        assert!(app.is_new_file_view());
+        assert_eq!(app.line_number_prefix_width(), 6);
+    }
+
+    #[test]
+    fn test_request_local_diff_for_truncated_modified_file() {
+        let mut files_map = HashMap::new();
+        files_map.insert(
+            TEST_SHA_0.to_string(),
+            vec![DiffFile {
+                filename: "big.lock".to_string(),
+                status: "modified".to_string(),
+                additions: 3000,
+                deletions: 2000,
+                patch: None,
+            }],
+        );
+        let mut app = TestAppBuilder::new()
+            .with_commits()
+            .files_map(files_map)
+            .build();
+        app.commit_list_state.select(Some(0));
+        app.file_list_state.select(Some(0));
+        app.focused_panel = Panel::DiffView;
+        app.handle_normal_mode(KeyCode::Char('F'), KeyModifiers::NONE);
+        assert!(app.needs_full_content);
+    }
+
+    #[test]
+    fn test_binary_file_is_not_treated_as_omitted() {
+        let file = DiffFile {
+            filename: "logo.png".to_string(),
+            status: "modified".to_string(),
+            additions: 0,
+            deletions: 0,
+            patch: None,
+        };
+        assert!(!App::is_patch_omitted(&file));
+    }
+
+    // ── チームへのレビュー依頼 ──────────────────────────
+
+    #[test]
+    fn test_set_review_requests_schedules_team_check() {
+        let mut app = TestAppBuilder::new().build();
+        app.current_user = "me".to_string();
+        app.set_review_requests(vec!["alice".to_string()], vec!["backend".to_string()]);
+        assert!(app.needs_team_check);
+        assert_eq!(app.my_requested_teams, None);
+        assert!(!app.team_review_requested());
+
+        // チーム依頼がなければ確認しない
+        app.set_review_requests(vec!["alice".to_string()], Vec::new());
+        assert!(!app.needs_team_check);
+    }
+
+    #[test]
+    fn test_team_review_requested_badge() {
+        let mut app = TestAppBuilder::new().build();
+        app.set_review_requests(Vec::new(), vec!["backend".to_string()]);
+        app.my_requested_teams = Some(Vec::new());
+        assert!(!app.team_review_requested());
+        app.my_requested_teams = Some(vec!["backend".to_string()]);
+        assert!(app.team_review_requested());
+    }
+
+    #[test]
+    fn test_team_membership_is_dropped_when_teams_changed() {
+        let mut app = TestAppBuilder::new().build();
+        app.set_review_requests(Vec::new(), vec!["backend".to_string()]);
+        // 確認中に依頼中チームが変わった結果は捨てる
+        app.apply_team_membership(&["frontend".to_string()], vec!["frontend".to_string()]);
+        assert_eq!(app.my_requested_teams, None);
+
+        app.apply_team_membership(&["backend".to_string()], vec!["backend".to_string()]);
+        assert!(app.team_review_requested());
+        assert!(app.status_message.is_some());
+    }
+
+    // ── スレッド・PR のミュート ──────────────────────────
+
+    #[test]
+    fn test_toggle_thread_mute_in_conversation() {
+        let mut app = TestAppBuilder::new().repo("mute-owner/mute-repo").build();
+        app.conversation = vec![
+            make_review_entry("alice", "COMMENTED"),
+            make_thread_entry("alice", 7, &[], false),
+        ];
+        app.focused_panel = Panel::Conversation;
+
+        // スレッド以外ではミュートできない
+        app.handle_normal_mode(KeyCode::Char('m'), KeyModifiers::NONE);
+        assert!(app.muted_threads.is_empty());
+
+        app.conversation_cursor = 1;
+        app.handle_normal_mode(KeyCode::Char('m'), KeyModifiers::NONE);
+        assert!(app.muted_threads.contains(&7));
+        app.handle_normal_mode(KeyCode::Char('m'), KeyModifiers::NONE);
+        assert!(app.muted_threads.is_empty());
+    }
+
+    #[test]
+    fn test_request_pr_mute_requires_node_id() {
+        let mut app = TestAppBuilder::new().build();
+        app.focused_panel = Panel::Conversation;
+        app.handle_normal_mode(KeyCode::Char('M'), KeyModifiers::SHIFT);
+        assert!(!app.needs_pr_mute);
+
+        app.set_mutes("PR_kwDO".to_string(), None);
+        app.handle_normal_mode(KeyCode::Char('M'), KeyModifiers::SHIFT);
+        assert!(app.needs_pr_mute);
+    }
+
+    // ── ファイルごとの非公開メモ ──────────────────────────
+
+    fn create_app_for_notes() -> App {
+        let mut app = TestAppBuilder::new()
+            .with_patch()
+            .repo("notes-owner/notes-repo")
+            .build();
+        app.commit_list_state.select(Some(0));
+        app.file_list_state.select(Some(0));
+        app.focused_panel = Panel::FileTree;
+        app
+    }
+
+    #[test]
+    fn test_note_save_and_delete() {
+        let mut app = create_app_for_notes();
+        let filename = app.current_file().unwrap().filename.clone();
+
+        app.handle_normal_mode(KeyCode::Char('N'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::NoteInput);
+        app.review.comment_editor.insert_text("check bounds");
+        app.handle_note_input_mode(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            app.file_notes.get(&filename).map(String::as_str),
+            Some("check bounds")
+        );
+
+        // 再編集時は既存のメモを読み込み、空にして保存すると削除
+        app.handle_normal_mode(KeyCode::Char('N'), KeyModifiers::SHIFT);
+        assert_eq!(app.review.comment_editor.text(), "check bounds");
+        app.review.comment_editor.clear();
+        app.handle_note_input_mode(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert!(app.file_notes.is_empty());
+    }
+
+    #[test]
+    fn test_promote_note_opens_pr_comment() {
+        let mut app = create_app_for_notes();
+        let filename = app.current_file().unwrap().filename.clone();
+        app.handle_normal_mode(KeyCode::Char('N'), KeyModifiers::SHIFT);
+        app.review.comment_editor.insert_text("Why unwrap here?");
+        app.handle_note_input_mode(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        assert_eq!(app.mode, AppMode::IssueCommentInput);
+        assert_eq!(app.focused_panel, Panel::Conversation);
+        assert_eq!(
+            app.review.comment_editor.text(),
+            format!("`{filename}`\n\nWhy unwrap here?")
+        );
+        assert!(app.file_notes.contains_key(&filename));
+    }
+
+    #[test]
+    fn test_notes_markdown() {
+        let mut app = TestAppBuilder::new().build();
+        app.file_notes
+            .insert("b.rs".to_string(), "second\n".to_string());
+        app.file_notes
+            .insert("a.rs".to_string(), "first".to_string());
+        assert_eq!(
+            app.notes_markdown(),
+            format!(
+                "# Review notes: {}#{}\n\n## `a.rs`\n\nfirst\n\n## `b.rs`\n\nsecond\n",
+                app.repo, app.pr_number
+            )
+        );
+    }
+
+    // ── 作者ごとの色 ──────────────────────────
+
+    #[test]
+    fn test_author_color_is_stable() {
+        assert_eq!(author_color("alice"), author_color("alice"));
+        assert_eq!(author_color("Alice"), author_color("alice"));
+        // 状態表示用の色は割り当てない
+        for login in ["alice", "bob", "carol", "dependabot[bot]", "octocat"] {
+            assert!(!matches!(
+                author_color(login),
+                Color::Red | Color::Green | Color::DarkGray
+            ));
+        }
+    }
+
+    // ── 相対時刻表示 ──────────────────────────
+
+    #[test]
+    fn test_format_relative() {
+        let now = chrono::DateTime::parse_from_rfc3339("2024-03-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let cases = [
+            ("2024-03-10T11:59:30Z", "just now"),
+            ("2024-03-10T11:55:00Z", "5m ago"),
+            ("2024-03-10T09:00:00Z", "3h ago"),
+            ("2024-03-08T12:00:00Z", "2d ago"),
+            ("2023-11-01T12:00:00Z", "4mo ago"),
+            ("2022-01-01T00:00:00Z", "2y ago"),
+            // 未来の時刻（時計ずれ）は just now
+            ("2024-03-10T12:05:00Z", "just now"),
+            ("not a date", "not a date"),
+        ];
+        for (iso, expected) in cases {
+            assert_eq!(crate::format::relative(iso, now), expected, "{iso}");
+        }
+    }
+
+    #[test]
+    fn test_toggle_time_format() {
+        let mut app = TestAppBuilder::new().build();
+        assert_eq!(app.time_format, TimeFormat::Relative);
+        app.handle_normal_mode(KeyCode::Char('T'), KeyModifiers::SHIFT);
+        assert_eq!(app.time_format, TimeFormat::Absolute);
+        assert_eq!(
+            app.format_time("2024-01-15T09:30:00Z"),
+            crate::format::datetime("2024-01-15T09:30:00Z")
+        );
+    }
+
+    // ── ヘルプの絞り込み ──────────────────────────
+
+    #[test]
+    fn test_help_filter_input() {
+        let mut app = TestAppBuilder::new().build();
+        app.handle_normal_mode(KeyCode::Char('?'), KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::Help);
+
+        app.handle_help_mode(KeyCode::Char('/'));
+        assert!(app.help_filter_input);
+        for c in "quit".chars() {
+            app.handle_help_mode(KeyCode::Char(c));
+        }
+        // 入力中の q はクエリ扱いで閉じない
+        assert_eq!(app.mode, AppMode::Help);
+        assert_eq!(app.help_filter, "quit");
+        app.handle_help_mode(KeyCode::Enter);
+        assert!(!app.help_filter_input);
+
+        // Esc は先に絞り込みを解除し、もう一度で閉じる
+        app.handle_help_mode(KeyCode::Esc);
+        assert!(app.help_filter.is_empty());
+        assert_eq!(app.mode, AppMode::Help);
+        app.handle_help_mode(KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    // ── ブランチ更新 ──────────────────────────
+
+    #[test]
+    fn test_update_branch_requires_behind_open_pr() {
+        let mut app = TestAppBuilder::new().build();
+        app.pr_state = "Open".to_string();
+        app.set_mutes("PR_kwDO".to_string(), None);
+        app.handle_normal_mode(KeyCode::Char('U'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::Normal);
+
+        app.set_behind_base(true);
+        app.pr_state = "Merged".to_string();
+        app.handle_normal_mode(KeyCode::Char('U'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::Normal);
+
+        app.pr_state = "Open".to_string();
+        app.handle_normal_mode(KeyCode::Char('U'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::UpdateBranch);
+    }
+
+    #[test]
+    fn test_update_branch_method_selection() {
+        let mut app = TestAppBuilder::new().build();
+        app.mode = AppMode::UpdateBranch;
+        app.handle_update_branch_mode(KeyCode::Char('r'));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.needs_update_branch, Some(UpdateMethod::Rebase));
+        assert_eq!(app.blocking_operation_message(), Some("Updating branch..."));
+
+        app.needs_update_branch = None;
+        app.mode = AppMode::UpdateBranch;
+        app.handle_update_branch_mode(KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.needs_update_branch, None);
+    }
+
+    #[test]
+    fn test_update_check_is_deferred_and_reported_through_task_data() {
+        let mut app = create_app_with_patch();
+        app.schedule_update_check();
+        assert!(app.needs_update_check);
+
+        app.apply_async_data(crate::AsyncData::UpdateAvailable(None));
+        assert!(app.status_message.is_none());
+        app.apply_async_data(crate::AsyncData::UpdateAvailable(Some(
+            "v99.1.1".to_string(),
+        )));
+        assert!(
+            app.status_message
+                .as_ref()
+                .is_some_and(|m| m.body.contains("v99.1.1"))
+        );
+    }
+
+    #[test]
+    fn test_branch_updated_reloads_only_for_current_head() {
+        let mut app = create_app_with_patch();
+        app.set_behind_base(true);
+        app.apply_branch_updated("stale", true);
+        assert!(!app.needs_reload);
+
+        let head = app.commits.last().unwrap().sha.clone();
+        app.apply_branch_updated(&head, false);
+        assert!(!app.needs_reload);
+        assert!(app.behind_base);
+
+        app.apply_branch_updated(&head, true);
+        assert!(app.needs_reload);
+        assert!(!app.behind_base);
+    }
+
+    #[test]
+    fn test_fork_pr_head_label_and_update_guard() {
+        let mut app = TestAppBuilder::new().build();
+        app.pr_head_branch = "fix-typo".to_string();
+        assert_eq!(app.head_label(), "fix-typo");
+
+        app.set_fork(Some("contributor".to_string()), false);
+        assert_eq!(app.head_label(), "contributor:fix-typo");
+        app.pr_state = "Open".to_string();
+        app.set_mutes("PR_kwDO".to_string(), None);
+        app.set_behind_base(true);
+        app.handle_normal_mode(KeyCode::Char('U'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(
+            app.status_message
+                .as_ref()
+                .is_some_and(|m| m.body.contains("Fork does not allow"))
+        );
+
+        // メンテナーの編集が許可されていれば更新できる
+        app.set_fork(Some("contributor".to_string()), true);
+        app.handle_normal_mode(KeyCode::Char('U'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::UpdateBranch);
+    }
+
+    #[test]
+    fn test_checkout_is_deferred() {
+        let mut app = TestAppBuilder::new().build();
+        app.open_checkout_confirm_with(0);
+        assert_eq!(app.mode, AppMode::CheckoutConfirm);
+        assert!(!app.needs_checkout);
+        app.handle_checkout_confirm_mode(KeyCode::Char('y'));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.needs_checkout);
+        assert_eq!(app.blocking_operation_message(), Some("Checking out..."));
+    }
+
+    #[test]
+    fn test_checkout_confirm_cancel() {
+        let mut app = TestAppBuilder::new().build();
+        app.open_checkout_confirm_with(0);
+        app.handle_checkout_confirm_mode(KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(!app.needs_checkout);
+    }
+
+    #[test]
+    fn test_checkout_refused_with_uncommitted_changes() {
+        let mut app = TestAppBuilder::new().build();
+        app.open_checkout_confirm_with(2);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(!app.needs_checkout);
+        assert!(
+            app.status_message
+                .as_ref()
+                .is_some_and(|m| m.level == StatusLevel::Error && m.body.contains("2 uncommitted"))
+        );
+    }
+
+    // ── 自動マージ ──────────────────────────
+
+    #[test]
+    fn test_auto_merge_check_scheduled_for_open_pr() {
+        let mut app = TestAppBuilder::new().build();
+        app.set_mutes("PR_kwDO".to_string(), None);
+        app.schedule_auto_merge_check();
+        assert!(!app.needs_auto_merge_check);
+
+        app.pr_state = "Open".to_string();
+        app.schedule_auto_merge_check();
+        assert!(app.needs_auto_merge_check);
+    }
+
+    #[test]
+    fn test_auto_merge_method_selection() {
+        let mut app = TestAppBuilder::new().build();
+        app.pr_state = "Open".to_string();
+        app.set_mutes("PR_kwDO".to_string(), None);
+        app.handle_normal_mode(KeyCode::Char('W'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::AutoMerge);
+
+        // 未設定では解除できない
+        app.handle_auto_merge_mode(KeyCode::Char('d'));
+        assert_eq!(app.mode, AppMode::AutoMerge);
+        assert_eq!(app.needs_auto_merge, None);
+
+        app.handle_auto_merge_mode(KeyCode::Char('s'));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            app.needs_auto_merge,
+            Some(AutoMergeAction::Enable(MergeMethod::Squash))
+        );
+
+        app.needs_auto_merge = None;
+        app.auto_merge = Some(MergeMethod::Squash);
+        app.mode = AppMode::AutoMerge;
+        app.handle_auto_merge_mode(KeyCode::Char('d'));
+        assert_eq!(app.needs_auto_merge, Some(AutoMergeAction::Disable));
+    }
+
+    // ── エディタの破棄確認と下書き ──────────────────────────
+
+    #[test]
+    fn test_esc_on_empty_editor_closes_without_confirm() {
+        let mut app = create_app_with_patch();
+        app.focused_panel = Panel::Conversation;
+        app.handle_normal_mode(KeyCode::Char('c'), KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::IssueCommentInput);
+        app.handle_issue_comment_input_mode(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_discard_confirm_keep_editing() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.mode = AppMode::ReplyInput;
+        app.review.reply_to_comment_id = Some(1);
+        app.handle_reply_input_mode(KeyCode::Char('x'), KeyModifiers::NONE);
+        app.handle_reply_input_mode(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::DiscardConfirm);
+
+        app.handle_discard_confirm_mode(KeyCode::Char('k'));
+        assert_eq!(app.mode, AppMode::ReplyInput);
+        assert_eq!(app.review.comment_editor.text(), "x");
+        assert_eq!(app.review.reply_to_comment_id, Some(1));
+    }
+
+    #[test]
+    fn test_save_as_draft_restores_on_reopen() {
+        let mut app = create_app_with_patch();
+        app.focused_panel = Panel::Conversation;
+        app.handle_normal_mode(KeyCode::Char('c'), KeyModifiers::NONE);
+        app.review.comment_editor.insert_text("LGTM\nthanks");
+        app.handle_issue_comment_input_mode(KeyCode::Esc, KeyModifiers::NONE);
+        app.handle_discard_confirm_mode(KeyCode::Char('s'));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.review.comment_editor.is_empty());
+
+        app.handle_normal_mode(KeyCode::Char('c'), KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::IssueCommentInput);
+        assert_eq!(app.review.comment_editor.text(), "LGTM\nthanks");
+        // 復元した下書きは消費される
+        assert!(app.drafts.is_empty());
+    }
+
+    // ── DiffView からのファイル移動 ──────────────────────────
+
+    #[test]
+    fn test_jump_to_adjacent_file_crosses_commits() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.focused_panel = Panel::DiffView;
+        app.commit_list_state.select(Some(0));
+        app.file_list_state.select(Some(0));
+        fn press(app: &mut App, first: char) -> (Option<usize>, Option<usize>) {
+            app.handle_normal_mode(KeyCode::Char(first), KeyModifiers::NONE);
+            app.handle_normal_mode(KeyCode::Char('f'), KeyModifiers::NONE);
+            (
+                app.commit_list_state.selected(),
+                app.file_list_state.selected(),
+            )
+        }
+
+        assert_eq!(press(&mut app, ']'), (Some(0), Some(1)));
+        assert_eq!(press(&mut app, ']'), (Some(1), Some(0)));
+        assert_eq!(press(&mut app, ']'), (Some(1), Some(1)));
+        // 末尾では移動しない
+        assert_eq!(press(&mut app, ']'), (Some(1), Some(1)));
+        assert!(app.status_message.is_some());
+
+        assert_eq!(press(&mut app, '['), (Some(1), Some(0)));
+        assert_eq!(press(&mut app, '['), (Some(0), Some(1)));
+        assert_eq!(app.focused_panel, Panel::DiffView);
+    }
+
+    #[test]
+    fn test_jump_to_unviewed_file_skips_viewed() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.focused_panel = Panel::FileTree;
+        app.commit_list_state.select(Some(0));
+        app.file_list_state.select(Some(0));
+        // コミット 0 の2番目とコミット 1 の1番目を確認済みにする
+        app.viewed_files
+            .entry(TEST_SHA_0.to_string())
+            .or_default()
+            .insert("src/app.rs".to_string());
+        app.viewed_files
+            .entry(TEST_SHA_1.to_string())
+            .or_default()
+            .insert("src/main.rs".to_string());
+
+        app.handle_normal_mode(KeyCode::Char('}'), KeyModifiers::NONE);
+        assert_eq!(app.commit_list_state.selected(), Some(1));
+        assert_eq!(app.file_list_state.selected(), Some(1));
+
+        app.handle_normal_mode(KeyCode::Char('}'), KeyModifiers::NONE);
+        assert_eq!(app.file_list_state.selected(), Some(1));
+        assert!(app.status_message.is_some());
+
+        app.handle_normal_mode(KeyCode::Char('{'), KeyModifiers::NONE);
+        assert_eq!(app.commit_list_state.selected(), Some(0));
+        assert_eq!(app.file_list_state.selected(), Some(0));
+    }
+
+    // ── 保留コメントのプレビュー ──────────────────────────
+
+    #[test]
+    fn test_pending_comment_preview_shows_code_and_body() {
+        let mut app = create_app_with_patch();
+        app.handle_normal_mode(KeyCode::Char('P'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::Normal);
+
+        app.review.pending_comments.push(PendingComment {
+            file_path: "src/main.rs".to_string(),
+            start_line: 2,
+            end_line: 3,
+            body: "Rename this".to_string(),
+            commit_sha: TEST_SHA_0.to_string(),
+        });
+        app.handle_normal_mode(KeyCode::Char('P'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::PendingComments);
+
+        let text: Vec<String> = app
+            .pending_comment_preview()
+            .0
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        // パッチに @@ がないため行番号は 0 始まり
+        assert!(text[0].starts_with("#1 src/main.rs L2–L3"));
+        assert_eq!(text[1], "  │ +line 2");
+        assert_eq!(text[2], "  │ +line 3");
+        assert_eq!(text[3], "  💬 Rename this");
+    }
+
+    // ── レビューイベントのショートカット ──────────────────
+
+    #[test]
+    fn test_review_submit_shortcut_selects_event() {
+        let mut app = create_app_with_patch();
+        app.mode = AppMode::ReviewSubmit;
+
+        app.handle_review_submit_mode(KeyCode::Char('r'));
+        assert_eq!(app.mode, AppMode::ReviewBodyInput);
+        assert_eq!(app.review.review_event_cursor, 2);
+    }
+
+    #[test]
+    fn test_review_submit_shortcut_disabled_shows_reason() {
+        let mut app = create_own_pr_app();
+        app.mode = AppMode::ReviewSubmit;
+
+        // 自分の PR では Approve できない
+        app.handle_review_submit_mode(KeyCode::Char('a'));
+        assert_eq!(app.mode, AppMode::ReviewSubmit);
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert!(msg.body.contains("You cannot approve your own PR"));
+
+        // 保留コメントがなければ Comment もできない
+        app.handle_review_submit_mode(KeyCode::Char('c'));
+        assert_eq!(app.mode, AppMode::ReviewSubmit);
+        assert!(
+            app.status_message
+                .as_ref()
+                .unwrap()
+                .body
+                .contains("no pending comments")
+        );
+
+        app.review.pending_comments.push(PendingComment {
+            file_path: "src/main.rs".to_string(),
+            start_line: 0,
+            end_line: 0,
+            body: "nit".to_string(),
+            commit_sha: TEST_SHA_0.to_string(),
+        });
+        assert_eq!(app.review_event_disabled_reason(ReviewEvent::Comment), None);
+        app.handle_review_submit_mode(KeyCode::Char('c'));
+        assert_eq!(app.mode, AppMode::ReviewBodyInput);
+    }
+
+    #[test]
+    fn test_own_pr_submit_approve_is_blocked() {
+        let mut app = create_own_pr_app();
+        app.mode = AppMode::ReviewSubmit;
+        assert_eq!(
+            app.review_event_disabled_reason(ReviewEvent::Approve),
+            Some("You cannot approve your own PR")
+        );
+        assert_eq!(
+            app.review_event_disabled_reason(ReviewEvent::RequestChanges),
+            Some("You cannot request changes on your own PR")
+        );
+
+        // 送信処理まで到達しても API を呼ばずにエラーを表示する
+        app.submit_review_with_event(ReviewEvent::Approve);
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert_eq!(msg.body, "✗ You cannot approve your own PR");
+    }
+
+    // ── 行範囲パーマリンク ────────────────────────────
+
+    #[test]
+    fn test_selection_permalink_uses_line_range() {
+        let mut app = create_app_with_patch();
+        app.focused_panel = Panel::DiffView;
+        app.diff.cursor_line = 2;
+        app.enter_line_select_mode();
+        app.handle_line_select_mode(KeyCode::Char('j'));
+        // パッチに @@ がないため行番号は 0 始まり
+        assert_eq!(
+            app.selection_permalink().as_deref(),
+            Some(
+                format!("https://github.com/owner/repo/blob/{TEST_SHA_0}/src/main.rs#L2-L3")
+                    .as_str()
+            )
+        );
+    }
+
+    // ── CommentView のスレッド行ハイライト ──────────────────
+
+    #[test]
+    fn test_comment_view_highlights_thread_range() {
+        let mut comment = make_review_comment("src/main.rs", Some(3), "RIGHT", "Range");
+        comment.start_line = Some(2);
+        let patch = (1..=30)
+            .map(|i| format!("+line{i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut app = TestAppBuilder::new()
+            .with_custom_patch(&format!("@@ -0,0 +1,30 @@\n{patch}"), "added", 30, 0)
+            .review_comments(vec![comment])
+            .build();
+        app.focused_panel = Panel::DiffView;
+        app.diff.view_height = 5;
+        app.diff.scroll = 3;
+        app.diff.cursor_line = 3;
+
+        app.handle_normal_mode(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::CommentView);
+        // @@ が idx 0 なので L2–L3 は idx 2..=3、範囲の先頭までスクロールされる
+        assert_eq!(app.viewing_thread_range(), Some((2, 3)));
+        assert_eq!(app.diff.scroll, 2);
+    }
+
+    // ── Conversation からの diff 位置ジャンプ ──────────────────
+
+    #[test]
+    fn test_conversation_thread_jumps_to_diff_line() {
+        let mut app = create_app_with_comments();
+        app.conversation = vec![
+            make_review_entry("bob", "APPROVED"),
+            make_thread_entry("alice", 1, &[], false),
+        ];
+        app.focused_panel = Panel::Conversation;
+        app.conversation_cursor = 1;
+
+        app.handle_normal_mode(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.focused_panel, Panel::DiffView);
+        assert_eq!(app.commit_list_state.selected(), Some(0));
+        // line=2 (RIGHT) → @@ が idx 0 なので diff 行 2
+        assert_eq!(app.diff.cursor_line, 2);
+
+        // gd: g で先頭に移動しても元のエントリからジャンプする
+        app.focused_panel = Panel::Conversation;
+        app.diff.cursor_line = 0;
+        app.handle_normal_mode(KeyCode::Char('g'), KeyModifiers::NONE);
+        assert_eq!(app.conversation_cursor, 0);
+        app.handle_normal_mode(KeyCode::Char('d'), KeyModifiers::NONE);
+        assert_eq!(app.focused_panel, Panel::DiffView);
+        assert_eq!(app.conversation_cursor, 1);
+        assert_eq!(app.diff.cursor_line, 2);
+    }
+
+    // ── Conversation の折りたたみ ──────────────────────
+
+    #[test]
+    fn test_conversation_fold_controls() {
+        let mut app = create_app_with_comments();
+        app.conversation = vec![
+            make_thread_entry("alice", 1, &["bob"], false),
+            make_review_entry("bob", "APPROVED"),
+        ];
+        app.focused_panel = Panel::Conversation;
+        app.ensure_conversation_rendered();
+        let expanded = app.conversation_rendered.as_ref().unwrap().len();
+        assert!(
+            app.conversation_rendered.as_ref().unwrap()[0]
+                .to_string()
+                .starts_with(" ▾#1 ● @alice")
+        );
+
+        // zm: 全て折りたたむ（z のズームは取り消される）
+        app.handle_normal_mode(KeyCode::Char('z'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('m'), KeyModifiers::NONE);
+        assert!(!app.zoomed);
+        assert_eq!(app.conversation_folded.len(), 2);
+        app.ensure_conversation_rendered();
+        let lines = app.conversation_rendered.as_ref().unwrap();
+        // ヘッダー + 空行 × 2
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].to_string().ends_with(" … 1 reply"));
+
+        // za: カーソル位置のみ展開、zr: 全て展開
+        app.handle_normal_mode(KeyCode::Char('z'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('a'), KeyModifiers::NONE);
+        assert!(!app.conversation_folded.contains(&0));
+        assert!(app.conversation_folded.contains(&1));
+        app.handle_normal_mode(KeyCode::Char('z'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('r'), KeyModifiers::NONE);
+        assert!(app.conversation_folded.is_empty());
+        app.ensure_conversation_rendered();
+        assert_eq!(app.conversation_rendered.as_ref().unwrap().len(), expanded);
+    }
+
+    // ── ファイルごとのカーソル・スクロール位置 ──────────────────
+    #[test]
+    fn test_diff_position_restored_per_file() {
+        let patch = (1..=30)
+            .map(|i| format!("+line{i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let file = |name: &str| DiffFile {
+            filename: name.to_string(),
+            status: "added".to_string(),
+            additions: 30,
+            deletions: 0,
+            patch: Some(format!("@@ -0,0 +1,30 @@\n{patch}")),
+        };
+        let mut files_map = HashMap::new();
+        files_map.insert(TEST_SHA_0.to_string(), vec![file("a.rs"), file("b.rs")]);
+        files_map.insert(TEST_SHA_1.to_string(), vec![file("a.rs")]);
+        let mut app = TestAppBuilder::new()
+            .with_commits()
+            .files_map(files_map)
+            .build();
+        app.diff.cursor_line = 20;
+        app.diff.scroll = 12;
+
+        // 別ファイルは先頭から
+        app.focused_panel = Panel::FileTree;
+        app.select_next();
+        assert_eq!(app.diff.cursor_line, 1);
+        assert_eq!(app.diff.scroll, 0);
+        app.diff.cursor_line = 5;
+
+        // 戻ると前回の位置を復元
+        app.select_prev();
+        assert_eq!(app.diff.cursor_line, 20);
+        assert_eq!(app.diff.scroll, 12);
+
+        // 同名でも別コミットのファイルは別の位置
+        app.focused_panel = Panel::CommitList;
+        app.select_next();
+        assert_eq!(app.diff.cursor_line, 1);
+        app.select_prev();
+        assert_eq!(app.file_list_state.selected(), Some(0));
+        assert_eq!(app.diff.cursor_line, 20);
+        assert_eq!(app.diff.scroll, 12);
+    }
+
+    // ── ファイル履歴 ──────────────────────────
+    #[test]
+    fn test_file_history_steps_through_commits() {
+        let file = |name: &str, patch: &str| DiffFile {
+            filename: name.to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: Some(patch.to_string()),
+        };
+        let mut files_map = HashMap::new();
+        files_map.insert(
+            TEST_SHA_0.to_string(),
+            vec![file("other.rs", "+x"), file("a.rs", "+first")],
+        );
+        files_map.insert(TEST_SHA_1.to_string(), vec![file("a.rs", "+second")]);
+        let mut app = TestAppBuilder::new()
+            .with_commits()
+            .files_map(files_map)
+            .build();
+        app.focused_panel = Panel::FileTree;
+        app.file_list_state.select(Some(1));
+
+        app.handle_normal_mode(KeyCode::Char('H'), KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::FileHistory);
+        let view = app.file_history.as_ref().unwrap();
+        assert_eq!(view.commits, vec![0, 1]);
+        assert_eq!(view.index, 0);
+
+        app.handle_file_history_mode(KeyCode::Char('n'));
+        assert_eq!(app.file_history.as_ref().unwrap().index, 1);
+        app.handle_file_history_mode(KeyCode::Char('n'));
+        assert_eq!(app.file_history.as_ref().unwrap().index, 1);
+
+        // Enter: そのコミットのファイルを DiffView で開く
+        app.handle_file_history_mode(KeyCode::Enter);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.file_history.is_none());
+        assert_eq!(app.commit_list_state.selected(), Some(1));
+        assert_eq!(app.file_list_state.selected(), Some(0));
+        assert_eq!(app.focused_panel, Panel::DiffView);
+    }
+
+    // ── hunk の折りたたみと確認済み ──────────────────────
+    #[test]
+    fn test_hunk_fold_and_review() {
+        let patch = "@@ -1,2 +1,2 @@\n-a\n+b\n@@ -10,1 +10,2 @@\n c\n+d";
+        let mut app = TestAppBuilder::new()
+            .with_custom_patch(patch, "modified", 2, 1)
+            .build();
+        app.focused_panel = Panel::DiffView;
+        app.diff.cursor_line = 1;
+
+        // za: 1 つ目の hunk を折りたたむ（カーソルは header へ）
+        app.handle_normal_mode(KeyCode::Char('z'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('a'), KeyModifiers::NONE);
+        assert!(!app.zoomed);
+        assert_eq!(app.diff.cursor_line, 0);
+        assert_eq!(app.collapsed_hunks(), vec![(0, 1..3)]);
+
+        // 折りたたまれた行は飛ばし、表示行も詰まる
+        app.handle_normal_mode(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(app.diff.cursor_line, 4);
+        assert_eq!(app.visual_line_offset(4), 2);
+        app.handle_normal_mode(KeyCode::Char('k'), KeyModifiers::NONE);
+        assert_eq!(app.diff.cursor_line, 0);
+
+        // x: 残りの hunk を確認済みにすると、ファイルが viewed になる
+        app.handle_normal_mode(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert!(!app.is_file_viewed(TEST_SHA_0, "src/main.rs"));
+        app.handle_normal_mode(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(app.reviewed_hunks(), vec![(3, 4..6)]);
+        assert!(app.is_file_viewed(TEST_SHA_0, "src/main.rs"));
+
+        // zr: 全て展開
+        app.handle_normal_mode(KeyCode::Char('z'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('r'), KeyModifiers::NONE);
+        assert!(app.collapsed_hunks().is_empty());
+        assert_eq!(app.visual_line_offset(4), 4);
+    }
+
+    // ── 読み込みエラーの分類 ──────────────────────────
+
+    #[test]
+    fn test_async_error_kind_is_kept_per_panel() {
+        let mut app = TestAppBuilder::new().build();
+        let (tx, rx) = mpsc::unbounded_channel();
+        app.async_rx = Some(rx);
+        app.loading.files = LoadPhase::Loading;
+        app.loading.conversation = LoadPhase::Loading;
+        tx.send(crate::AsyncData::Error(
+            crate::AsyncErrorKind::Conversation,
+            ApiErrorKind::Auth,
+            "Failed to load conversation: Bad credentials".to_string(),
+        ))
+        .unwrap();
+        app.poll_async_data();
+        assert_eq!(
+            app.loading.conversation,
+            LoadPhase::Error(ApiErrorKind::Auth)
+        );
+        assert_eq!(app.loading.files, LoadPhase::Loading);
+
+        // チャネルが切れたらロード中のものは種類不明のエラーにする
+        drop(tx);
+        app.poll_async_data();
+        assert_eq!(app.loading.files, LoadPhase::Error(ApiErrorKind::Other));
+        assert_eq!(
+            app.loading.conversation,
+            LoadPhase::Error(ApiErrorKind::Auth)
+        );
+    }
+
+    // ── 大きな PR の概要 ──────────────────────────
+
+    #[test]
+    fn test_large_pr_summary_opens_and_requests_path_load() {
+        let mut app = TestAppBuilder::new().build();
+        let (tx, rx) = mpsc::unbounded_channel();
+        app.async_rx = Some(rx);
+        app.set_lazy_files(true);
+        app.loading.files = LoadPhase::Loading;
+        let stat = |filename: &str| DiffFile {
+            filename: filename.to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 1,
+            patch: None,
+        };
+        tx.send(crate::AsyncData::FileStats(vec![
+            stat("src/main.rs"),
+            stat("README.md"),
+        ]))
+        .unwrap();
+        app.poll_async_data();
+        assert_eq!(app.mode, AppMode::LargePrSummary);
+        assert_eq!(app.loading.files, LoadPhase::Done);
+
+        // (root) → src の順。src を展開してファイルを開く
+        app.handle_large_pr_summary_mode(KeyCode::Char('j'));
+        app.handle_large_pr_summary_mode(KeyCode::Enter);
+        assert!(app.needs_path_load.is_none());
+        app.handle_large_pr_summary_mode(KeyCode::Char('j'));
+        app.handle_large_pr_summary_mode(KeyCode::Enter);
+        assert_eq!(app.needs_path_load.as_deref(), Some("src/main.rs"));
+
+        // h: 親ディレクトリを折りたたんでカーソルを戻す
+        app.handle_large_pr_summary_mode(KeyCode::Char('h'));
+        assert_eq!(app.large_pr_summary.as_ref().unwrap().cursor, 1);
+        app.handle_large_pr_summary_mode(KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    // ── キャッシュのメモリ予算 ──────────────────────────
+
+    #[test]
+    fn test_files_budget_keeps_selected_commit() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.enforce_files_budget();
+        assert_eq!(app.files_map.len(), 2);
+        assert!(!app.lazy_files);
+
+        // 予算を超えたら選択中以外のコミットを捨て、選択時に読み込み直す
+        app.set_memory_limits(MemoryConfig {
+            files_mb: 0,
+            ..MemoryConfig::default()
+        });
+        app.enforce_files_budget();
+        assert_eq!(app.files_map.len(), 1);
+        assert!(app.files_map.contains_key(TEST_SHA_0));
+        assert!(app.lazy_files);
+    }
+
+    #[test]
+    fn test_files_budget_follows_replaced_files_map() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.enforce_files_budget();
+
+        // 件数が同じまま中身が入れ替わっても追跡し直す
+        let files = app.files_map.remove(TEST_SHA_1).unwrap();
+        app.files_map.insert("replaced".to_string(), files);
+        app.files_map_replaced();
+        app.enforce_files_budget();
+        assert!(app.files_usage.contains(&"replaced".to_string()));
+        assert!(!app.files_usage.contains(&TEST_SHA_1.to_string()));
+    }
+
+    #[test]
+    fn test_files_budget_keeps_comment_badges_of_evicted_commits() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.visible_review_comment_cache
+            .insert((TEST_SHA_1.to_string(), "src/lib.rs".to_string()), 2);
+        app.set_memory_limits(MemoryConfig {
+            files_mb: 0,
+            ..MemoryConfig::default()
+        });
+        app.enforce_files_budget();
+        assert!(!app.files_map.contains_key(TEST_SHA_1));
+        assert_eq!(
+            app.cached_visible_comment_count(TEST_SHA_1, "src/lib.rs"),
+            2
+        );
+
+        // リロードで差し替えたら前回の件数は使わない
+        app.files_map_replaced();
+        app.rebuild_visible_comment_cache();
+        assert_eq!(
+            app.cached_visible_comment_count(TEST_SHA_1, "src/lib.rs"),
+            0
+        );
+    }
+
+    // ── テストファイルのまとめ表示 ──────────────────────────
+
+    #[test]
+    fn test_test_files_grouped_and_collapsed() {
+        let file = |name: &str| DiffFile {
+            filename: name.to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: Some("@@ -1,1 +1,2 @@\n a\n+b".to_string()),
+        };
+        let mut files_map = HashMap::new();
+        files_map.insert(
+            TEST_SHA_0.to_string(),
+            vec![file("tests/it.rs"), file("src/a.rs"), file("src/b.rs")],
+        );
+        let mut app = TestAppBuilder::new()
+            .with_commits()
+            .files_map(files_map)
+            .build();
+        app.set_test_files(crate::config::TestFilesConfig {
+            exclude_from_progress: true,
+            ..Default::default()
+        });
+        app.focused_panel = Panel::FileTree;
+        assert_eq!(app.file_tree_rows().len(), 3);
+        assert!(app.counts_for_progress("tests/it.rs"));
+
+        // t: 本体 → 見出し → テストの順にまとめ、テストは進捗に数えない
+        app.handle_normal_mode(KeyCode::Char('t'), KeyModifiers::NONE);
+        assert_eq!(
+            app.file_tree_rows(),
+            vec![
+                FileRow::File(1),
+                FileRow::File(2),
+                FileRow::TestsHeader,
+                FileRow::File(0),
+            ]
+        );
+        assert!(!app.counts_for_progress("tests/it.rs"));
+        app.file_list_state.select(Some(2));
+        app.handle_normal_mode(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(app.file_list_state.selected(), Some(0));
+
+        // もう一度 t: 折りたたむと選択中のテストファイルから本体の先頭へ移る
+        app.handle_normal_mode(KeyCode::Char('t'), KeyModifiers::NONE);
+        assert_eq!(app.file_tree_rows().len(), 3);
+        assert_eq!(app.file_list_state.selected(), Some(1));
+
+        // 本体のファイルを全て viewed にするとコミットも viewed
+        app.viewed_files.insert(
+            TEST_SHA_0.to_string(),
+            ["src/a.rs", "src/b.rs"]
+                .map(String::from)
+                .into_iter()
+                .collect(),
+        );
+        assert!(app.is_commit_viewed(TEST_SHA_0));
+        app.handle_normal_mode(KeyCode::Char('t'), KeyModifiers::NONE);
+        assert!(!app.is_commit_viewed(TEST_SHA_0));
+    }
+
+    // ── マウスキャプチャ ──────────────────────────
+
+    #[test]
+    fn test_mouse_capture_unavailable_notifies() {
+        let mut app = TestAppBuilder::new().build();
+        app.set_mouse_capture(false, false);
+        assert!(app.status_message.is_none());
+        assert!(!app.mouse_captured());
+
+        app.set_mouse_capture(true, false);
+        assert!(app.status_message.is_some());
+        assert!(!app.mouse_captured());
+    }
+
+    // ── PgDn / PgUp / Home / End ──────────────────────────
+
+    #[test]
+    fn test_page_keys_in_diff_view() {
+        let mut app = create_app_with_patch();
+        app.focused_panel = Panel::DiffView;
+        app.diff.view_height = 3;
+
+        app.handle_normal_mode(KeyCode::PageDown, KeyModifiers::NONE);
+        assert_eq!(app.diff.cursor_line, 3);
+        app.handle_normal_mode(KeyCode::PageUp, KeyModifiers::NONE);
+        assert_eq!(app.diff.cursor_line, 0);
+
+        app.handle_normal_mode(KeyCode::End, KeyModifiers::NONE);
+        assert!(app.diff.cursor_line > 0);
+        app.handle_normal_mode(KeyCode::Home, KeyModifiers::NONE);
+        assert_eq!(app.diff.cursor_line, 0);
+        // Home は g と違い 2 文字目を待たない
+        assert!(app.pending_key.is_none());
+    }
+
+    #[test]
+    fn test_page_keys_in_comment_view_and_help() {
+        let mut app = TestAppBuilder::new().build();
+        app.mode = AppMode::CommentView;
+        app.review.comment_view_height = 4;
+        app.review.comment_view_max_scroll = 6;
+
+        app.handle_comment_view_mode(KeyCode::PageDown);
+        assert_eq!(app.review.viewing_comment_scroll, 4);
+        app.handle_comment_view_mode(KeyCode::PageDown);
+        assert_eq!(app.review.viewing_comment_scroll, 6);
+        app.handle_comment_view_mode(KeyCode::PageUp);
+        assert_eq!(app.review.viewing_comment_scroll, 2);
+        app.handle_comment_view_mode(KeyCode::End);
+        assert_eq!(app.review.viewing_comment_scroll, 6);
+        app.handle_comment_view_mode(KeyCode::Home);
+        assert_eq!(app.review.viewing_comment_scroll, 0);
+
+        app.mode = AppMode::Help;
+        app.help_view_height = 5;
+        app.handle_help_mode(KeyCode::PageDown);
+        assert_eq!(app.help_scroll, 5);
+        app.handle_help_mode(KeyCode::PageUp);
+        assert_eq!(app.help_scroll, 0);
+        app.handle_help_mode(KeyCode::End);
+        assert_eq!(app.help_scroll, u16::MAX);
+        app.handle_help_mode(KeyCode::Home);
+        assert_eq!(app.help_scroll, 0);
+    }
+
+    // ── ファイル追従 ──────────────────────────
+
+    #[test]
+    fn test_follow_file_across_commits() {
+        let file = |name: &str| DiffFile {
+            filename: name.to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: None,
+        };
+        let mut files_map = HashMap::new();
+        files_map.insert(
+            TEST_SHA_0.to_string(),
+            vec![file("src/a.rs"), file("src/b.rs")],
+        );
+        files_map.insert(TEST_SHA_1.to_string(), vec![file("src/c.rs")]);
+        let mut app = TestAppBuilder::new()
+            .with_commits()
+            .files_map(files_map)
+            .build();
+        app.focused_panel = Panel::FileTree;
+        app.handle_normal_mode(KeyCode::Char('j'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('f'), KeyModifiers::NONE);
+        assert_eq!(app.follow_file.as_deref(), Some("src/b.rs"));
+
+        // 追従中のファイルがないコミットでは先頭を選び、ないことを知らせる
+        app.focused_panel = Panel::CommitList;
+        app.handle_normal_mode(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(app.file_list_state.selected(), Some(0));
+        assert!(app.followed_file_missing());
+        assert_eq!(app.follow_file.as_deref(), Some("src/b.rs"));
+
+        // 戻ると同じパスを選択する
+        app.handle_normal_mode(KeyCode::Char('k'), KeyModifiers::NONE);
+        assert_eq!(app.file_list_state.selected(), Some(1));
+        assert!(!app.followed_file_missing());
+
+        // 追従を止めると先頭に戻る
+        app.focused_panel = Panel::FileTree;
+        app.handle_normal_mode(KeyCode::Char('f'), KeyModifiers::NONE);
+        assert!(app.follow_file.is_none());
+        app.focused_panel = Panel::CommitList;
+        app.handle_normal_mode(KeyCode::Char('j'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('k'), KeyModifiers::NONE);
+        assert_eq!(app.file_list_state.selected(), Some(0));
+    }
+
+    // ── ヘッダーのコメント集計 ──────────────────────────
+
+    #[test]
+    fn test_comment_summary_badge() {
+        let mut app = TestAppBuilder::new().build();
+        assert_eq!(app.comment_summary_badge(), "");
+
+        app.review.review_comments = vec![
+            make_review_comment("a.rs", Some(1), "RIGHT", "one"),
+            make_review_comment("a.rs", Some(2), "RIGHT", "two"),
+        ];
+        app.review.thread_map.insert(
+            1,
+            ReviewThread {
+                node_id: "T1".to_string(),
+                is_resolved: false,
+                root_comment_database_id: 1,
+            },
+        );
+        app.review.thread_map.insert(
+            2,
+            ReviewThread {
+                node_id: "T2".to_string(),
+                is_resolved: true,
+                root_comment_database_id: 2,
+            },
+        );
+        assert_eq!(app.comment_summary_badge(), " 💬 2 · 1 unresolved ");
+
+        app.review.pending_comments.push(PendingComment {
+            file_path: "a.rs".to_string(),
+            start_line: 0,
+            end_line: 0,
+            body: "todo".to_string(),
+            commit_sha: TEST_SHA_0.to_string(),
+        });
+        assert_eq!(
+            app.comment_summary_badge(),
+            " 💬 2 · 1 unresolved · 1 pending "
+        );
+    }
+
+    // ── どのモードからでもレビュー送信 ──────────────────────────
+
+    #[test]
+    fn test_global_review_submit_keeps_draft() {
+        let mut app = create_app_with_patch();
+        app.focused_panel = Panel::Conversation;
+        app.handle_normal_mode(KeyCode::Char('c'), KeyModifiers::NONE);
+        app.review.comment_editor.insert_text("half-written");
+        assert!(app.can_open_review_submit_globally());
+
+        app.open_review_submit_globally();
+        assert_eq!(app.mode, AppMode::ReviewSubmit);
+        assert!(app.review.comment_editor.is_empty());
+
+        // ダイアログを閉じて開き直すと入力途中の内容が戻る
+        app.handle_review_submit_mode(KeyCode::Esc);
+        app.handle_normal_mode(KeyCode::Char('c'), KeyModifiers::NONE);
+        assert_eq!(app.review.comment_editor.text(), "half-written");
+
+        app.mode = AppMode::QuitConfirm;
+        assert!(!app.can_open_review_submit_globally());
+    }
+
+    #[test]
+    fn test_pending_comment_summaries() {
+        let mut app = create_app_with_patch();
+        app.review.pending_comments.push(PendingComment {
+            file_path: "src/missing.rs".to_string(),
+            start_line: 1,
+            end_line: 1,
+            body: "first line\nsecond line".to_string(),
+            commit_sha: TEST_SHA_0.to_string(),
+        });
+        assert_eq!(
+            app.pending_comment_summaries(),
+            vec!["src/missing.rs:L? first line".to_string()]
+        );
+    }
+
+    // ── 保留コメントの並べ替えと見出し ──────────────────────────
+
+    #[test]
+    fn test_reorder_and_group_pending_comments() {
+        let mut app = create_app_with_patch();
+        for body in ["a", "b", "c"] {
+            app.review.pending_comments.push(PendingComment {
+                file_path: "src/main.rs".to_string(),
+                start_line: 1,
+                end_line: 1,
+                body: body.to_string(),
+                commit_sha: TEST_SHA_0.to_string(),
+            });
+        }
+        let order = |app: &App| -> String {
+            app.review
+                .pending_comments
+                .iter()
+                .map(|c| c.body.as_str())
+                .collect()
+        };
+        app.handle_normal_mode(KeyCode::Char('P'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::PendingComments);
+
+        // J: 選択中のコメントを後ろへ（カーソルも付いていく）
+        app.handle_pending_comments_mode(KeyCode::Char('J'));
+        assert_eq!(order(&app), "bac");
+        assert_eq!(app.pending_cursor, 1);
+
+        // h: 見出しを付ける
+        app.handle_pending_comments_mode(KeyCode::Char('j'));
+        app.handle_pending_comments_mode(KeyCode::Char('h'));
+        for c in "Nits".chars() {
+            app.handle_pending_comments_mode(KeyCode::Char(c));
+        }
+        app.handle_pending_comments_mode(KeyCode::Enter);
+        assert_eq!(
+            app.review.pending_headers.get(&2).map(String::as_str),
+            Some("Nits")
+        );
+
+        // 見出しの直前のコメントを J で動かすと、入れ替えずに見出しの下へ入る
+        app.handle_pending_comments_mode(KeyCode::Char('k'));
+        app.handle_pending_comments_mode(KeyCode::Char('J'));
+        assert_eq!(order(&app), "bac");
+        assert_eq!(
+            app.review.pending_headers.get(&1).map(String::as_str),
+            Some("Nits")
+        );
+        // K で前のグループへ戻る
+        app.handle_pending_comments_mode(KeyCode::Char('K'));
+        assert_eq!(
+            app.review.pending_headers.get(&2).map(String::as_str),
+            Some("Nits")
+        );
+
+        // 目次は送信順どおりでレビュー本文に入る
+        assert_eq!(
+            app.review_body_text(),
+            "- `src/main.rs` L1: b\n- `src/main.rs` L1: a\n\n**Nits**\n- `src/main.rs` L1: c"
+        );
+
+        app.clear_pending_comments();
+        assert!(app.review.pending_headers.is_empty());
+    }
+
+    // ── 画像の添付 ──────────────────────────
+
+    #[test]
+    fn test_attach_image_without_upload_command_inserts_placeholder() {
+        let mut app = create_app_with_patch();
+        app.focused_panel = Panel::Conversation;
+        app.handle_normal_mode(KeyCode::Char('c'), KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::IssueCommentInput);
+
+        app.handle_issue_comment_input_mode(KeyCode::Char('t'), KeyModifiers::CONTROL);
+        assert_eq!(app.mode, AppMode::AttachImage);
+
+        // 存在しないパスはエラーで入力を続ける
+        app.attach_input = "/nonexistent/shot.png".to_string();
+        app.handle_attach_image_mode(KeyCode::Enter);
+        assert_eq!(app.mode, AppMode::AttachImage);
+
+        let path = std::env::temp_dir().join("prism-attach-test.png");
+        std::fs::write(&path, b"png").unwrap();
+        app.attach_input = path.display().to_string();
+        app.handle_attach_image_mode(KeyCode::Enter);
+        std::fs::remove_file(&path).ok();
+        assert_eq!(app.mode, AppMode::IssueCommentInput);
+        assert!(app.needs_attach_upload.is_none());
+        assert_eq!(app.review.comment_editor.text(), "![prism-attach-test]()");
+    }
+
+    #[test]
+    fn test_clipboard_image_is_removed_when_app_is_dropped() {
+        let mut app = TestAppBuilder::new().build();
+        let dir = std::env::temp_dir().join("prism-clipboard-drop-test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("prism-clipboard.png");
+        std::fs::write(&path, b"png").unwrap();
+        app.clipboard_image = Some(attach::ClipboardImage::new(path.clone()));
+        drop(app);
+        assert!(!path.exists());
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_clipboard_image_is_removed_after_upload() {
+        let mut app = TestAppBuilder::new().build();
+        app.set_attach_upload_command(Some("echo https://cdn.example/a.png #".to_string()));
+
+        let dir = std::env::temp_dir().join("prism-clipboard-upload-test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("prism-clipboard.png");
+        std::fs::write(&path, b"png").unwrap();
+        app.clipboard_image = Some(attach::ClipboardImage::new(path.clone()));
+        app.execute_attach_upload(path.clone());
+        assert_eq!(
+            app.review.comment_editor.text(),
+            "![prism-clipboard](https://cdn.example/a.png)"
+        );
+        assert!(app.clipboard_image.is_none());
+        assert!(!path.exists());
+        assert!(!dir.exists());
+
+        // 指定したファイルは残す
+        let own = std::env::temp_dir().join("prism-attach-upload-test.png");
+        std::fs::write(&own, b"png").unwrap();
+        app.execute_attach_upload(own.clone());
+        assert!(own.exists());
+        std::fs::remove_file(&own).ok();
+    }
+
+    // ── 引用返信 ──────────────────────────
+
+    #[test]
+    fn test_quote_reply_selects_lines_of_thread() {
+        let mut app = TestAppBuilder::new().build();
+        let root = make_review_comment("src/main.rs", Some(1), "RIGHT", "first\nsecond");
+        let mut reply = make_review_comment("src/main.rs", Some(1), "RIGHT", "why?\nreally?");
+        reply.id = 2;
+        reply.in_reply_to_id = Some(1);
+        app.review.viewing_comments = vec![root, reply];
+        app.mode = AppMode::CommentView;
+
+        // 最新コメントの先頭行から選択が始まる
+        app.handle_comment_view_mode(KeyCode::Char('>'));
+        assert_eq!(app.review.quote_select.map(|s| s.range()), Some((2, 2)));
+        app.handle_comment_view_mode(KeyCode::Char('k'));
+        app.handle_comment_view_mode(KeyCode::Char('J'));
+        assert_eq!(app.review.quote_select.map(|s| s.range()), Some((1, 2)));
+
+        app.handle_comment_view_mode(KeyCode::Enter);
+        assert_eq!(app.mode, AppMode::ReplyInput);
+        assert_eq!(app.review.reply_to_comment_id, Some(1));
+        assert!(app.review.quote_select.is_none());
+        assert_eq!(app.review.comment_editor.text(), "> second\n> why?\n\n");
+    }
+
+    #[test]
+    fn test_quote_select_esc_keeps_comment_view() {
+        let mut app = create_app_with_comments();
+        app.review.viewing_comments = app.review.review_comments.clone();
+        app.mode = AppMode::CommentView;
+        app.handle_comment_view_mode(KeyCode::Char('>'));
+        app.handle_comment_view_mode(KeyCode::Esc);
+        assert!(app.review.quote_select.is_none());
+        assert_eq!(app.mode, AppMode::CommentView);
+    }
+
+    // ── resolve の再送キュー ──────────────────────────
+
+    #[test]
+    fn test_failed_resolve_is_queued_and_reapplied() {
+        let mut app = TestAppBuilder::new().build();
+        app.conversation = vec![make_thread_entry("alice", 7, &[], false)];
+        let thread = ReviewThread {
+            node_id: "RT_7".to_string(),
+            is_resolved: false,
+            root_comment_database_id: 7,
+        };
+        app.review.thread_map.insert(7, thread.clone());
+
+        app.queue_resolve(ResolveToggleRequest {
+            thread_node_id: "RT_7".to_string(),
+            should_resolve: true,
+            root_comment_id: 7,
+        });
+        assert!(app.review.thread_map[&7].is_resolved);
+        assert!(matches!(
+            app.conversation[0].kind,
+            ConversationKind::CodeComment {
+                is_resolved: true,
+                ..
+            }
+        ));
+        assert!(app.comment_summary_badge().contains("⟳ 1 queued"));
+
+        // 同じスレッドへの操作は最後のものだけ残る
+        app.queue_resolve(ResolveToggleRequest {
+            thread_node_id: "RT_7".to_string(),
+            should_resolve: false,
+            root_comment_id: 7,
+        });
+        assert_eq!(app.resolve_queue.len(), 1);
+        assert!(!app.resolve_queue[0].resolve);
+
+        // 再取得で上書きされても再送待ちの状態を保つ
+        app.review.thread_map.insert(
+            7,
+            ReviewThread {
+                is_resolved: true,
+                ..thread
+            },
+        );
+        app.apply_resolve_queue();
+        assert!(!app.review.thread_map[&7].is_resolved);
+
+        // 再送できない操作は外して表示を戻す
+        app.drop_queued_resolve(0);
+        assert!(app.resolve_queue.is_empty());
+        assert!(app.review.thread_map[&7].is_resolved);
+    }
+
+    #[test]
+    fn test_saved_resolve_queue_schedules_replay() {
+        let mut app = TestAppBuilder::new().build();
+        app.set_resolve_queue(None);
+        assert!(!app.needs_resolve_replay);
+
+        app.set_resolve_queue(Some(crate::github::cache::ResolveQueueCache {
+            actions: vec![crate::github::cache::QueuedResolve {
+                thread_node_id: "RT_1".to_string(),
+                root_comment_id: 1,
+                resolve: true,
+            }],
+        }));
+        assert!(app.needs_resolve_replay);
+        assert_eq!(
+            app.blocking_operation_message(),
+            Some("Syncing queued thread actions...")
+        );
+    }
+
+    // ── 図のブロック ──────────────────────────
+
+    #[test]
+    fn test_diagram_blocks_are_listed_as_kroki_links() {
+        let app = TestAppBuilder::new()
+            .pr_body("Flow:\n```mermaid\ngraph TD\n  A-->B\n```")
+            .build();
+        let links = app.collect_links();
+        let diagram = links
+            .iter()
+            .find(|l| l.title.as_deref() == Some("📊 mermaid diagram"))
+            .expect("diagram link");
+        assert!(diagram.url.starts_with("https://kroki.io/mermaid/svg/"));
+    }
+
+    // ── 自分と PR の関係 ──────────────────────────
+
+    #[test]
+    fn test_relationship_badge() {
+        let mut app = TestAppBuilder::new().build();
+        // ログインユーザーが不明なら表示しない
+        assert_eq!(app.relationship_badge(), "");
+
+        app.current_user = "me".to_string();
+        assert_eq!(app.relationship_badge(), "");
+
+        app.set_assignees(vec!["me".to_string()]);
+        app.set_review_requests(vec!["me".to_string()], Vec::new());
+        assert_eq!(app.relationship_badge(), " 👤 assignee · review requested ");
+        assert!(app.my_relationship().unwrap().action_expected());
+
+        // 依頼が外れて最新のレビューが承認なら対応待ちではない
+        app.set_review_requests(Vec::new(), Vec::new());
+        app.conversation = vec![
+            make_review_entry("me", "CHANGES_REQUESTED"),
+            make_review_entry("alice", "COMMENTED"),
+            make_review_entry("me", "APPROVED"),
+        ];
+        assert_eq!(app.relationship_badge(), " 👤 assignee · you approved ");
+        assert!(!app.my_relationship().unwrap().action_expected());
+    }
+
+    // ── レビュー待ちの経過時間 ──────────────────────────
+
+    #[test]
+    fn test_review_waiting_and_overdue() {
+        let mut app = TestAppBuilder::new().build();
+        app.current_user = "me".to_string();
+        app.set_review_requests(vec!["alice".to_string()], vec!["core".to_string()]);
+        let mut times = crate::github::review::ReviewRequestTimes::default();
+        times
+            .users
+            .insert("alice".to_string(), "2024-01-03T00:00:00Z".to_string());
+        times
+            .teams
+            .insert("core".to_string(), "2024-01-02T00:00:00Z".to_string());
+        app.apply_review_requests(times);
+        let now = chrono::DateTime::parse_from_rfc3339("2024-01-04T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+
+        // 自分への依頼がなければ最も古い依頼から数え、強調しない
+        assert_eq!(app.my_review_requested_at(), None);
+        assert_eq!(app.review_waiting_since(), Some("2024-01-02T00:00:00Z"));
+        assert!(!app.review_overdue(now));
+
+        // 所属チームへの依頼は自分への依頼として SLA を判定する
+        app.my_requested_teams = Some(vec!["core".to_string()]);
+        assert_eq!(app.my_review_requested_at(), Some("2024-01-02T00:00:00Z"));
+        assert!(app.review_overdue(now));
+        app.set_review_sla(crate::config::ReviewConfig {
+            sla_hours: 72,
+            ..Default::default()
+        });
+        assert!(!app.review_overdue(now));
+        app.set_review_sla(crate::config::ReviewConfig {
+            sla_hours: 0,
+            ..Default::default()
+        });
+        assert!(!app.review_overdue(now));
+        assert_eq!(
+            super::latency::elapsed_label("2024-01-02T00:00:00Z", now),
+            "2d"
+        );
+    }
+
+    #[test]
+    fn test_author_last_active() {
+        let mut app = TestAppBuilder::new().build();
+        app.pr_author = "alice".to_string();
+        assert_eq!(app.author_last_active(), None);
+        app.conversation = vec![
+            make_thread_entry("bob", 1, &["alice"], false),
+            make_review_entry("bob", "COMMENTED"),
+        ];
+        // スレッドへの返信も作成者の活動に数える
+        assert_eq!(app.author_last_active(), Some("2024-01-02T00:00:00Z"));
+    }
+
+    // ── Conversation in sidebar ──
+
+    #[test]
+    fn test_conversation_in_sidebar_navigation() {
+        let mut app = TestAppBuilder::new().build();
+        assert_eq!(app.focused_panel, Panel::PrDescription);
+
+        // 上段にいればフォーカスも Conversation に移る
+        app.toggle_conversation_in_sidebar();
+        assert!(app.conversation_in_sidebar);
+        assert_eq!(app.focused_panel, Panel::Conversation);
+
+        // Tab 巡回は上段の Conversation を通る
+        app.next_panel();
+        assert_eq!(app.focused_panel, Panel::CommitList);
+        app.next_panel();
+        app.next_panel();
+        assert_eq!(app.focused_panel, Panel::Conversation);
+        app.prev_panel();
+        assert_eq!(app.focused_panel, Panel::FileTree);
+
+        // 右カラムの PR Description は巡回の対象外
+        app.focused_panel = Panel::PrDescription;
+        app.next_panel();
+        assert_eq!(app.focused_panel, Panel::PrDescription);
+
+        app.focused_panel = Panel::Conversation;
+        app.toggle_conversation_in_sidebar();
+        assert!(!app.conversation_in_sidebar);
+        assert_eq!(app.focused_panel, Panel::PrDescription);
+    }
+
+    // ── Commit range viewed ──
+
+    #[test]
+    fn test_commit_range_viewed() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.focused_panel = Panel::CommitList;
+        app.commit_list_state.select(Some(0));
+        app.handle_normal_mode(KeyCode::Char('v'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(app.commit_range(), Some((0, 1)));
+
+        // 範囲内をまとめて viewed にし、選択は解除される
+        app.handle_normal_mode(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(app.commit_range(), None);
+        assert!(app.is_commit_viewed(&app.commits[0].sha));
+        assert!(app.is_commit_viewed(&app.commits[1].sha));
+
+        // すべて viewed の範囲は解除する
+        app.handle_normal_mode(KeyCode::Char('v'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('k'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(app.viewed_commit_count(), 0);
+
+        // ペインを離れると範囲選択は解除される
+        app.handle_normal_mode(KeyCode::Char('v'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(app.commit_range_anchor, None);
+    }
+
+    // ── Wrap-aware line select ──
+
+    #[test]
+    fn test_visual_rows_cover_wrapped_line() {
+        let long_line = format!("+{}", "x".repeat(120));
+        let mut app = TestAppBuilder::new()
+            .with_custom_patch(
+                &format!("@@ -0,0 +120,3 @@\n+a\n{long_line}\n+b"),
+                "added",
+                3,
+                0,
+            )
+            .build();
+        app.diff.view_width = 80;
+        app.diff.wrap = true;
+        // 折り返した行は表示行をすべて含む
+        assert_eq!(app.visual_rows(1), 1..2);
+        assert_eq!(app.visual_rows(2), 2..4);
+        assert_eq!(app.visual_rows(3), 4..5);
+
+        app.focused_panel = Panel::DiffView;
+        app.diff.cursor_line = 1;
+        app.enter_line_select_mode();
+        app.handle_line_select_mode(KeyCode::Char('j'));
+        app.handle_line_select_mode(KeyCode::Char('j'));
+        assert_eq!(
+            app.selection_file_lines(),
+            Some((crate::github::review::Side::Right, 120, 122))
+        );
+    }
+
+    // ── Diff search options ──
+
+    #[test]
+    fn test_diff_search_options() {
+        let mut app = TestAppBuilder::new()
+            .with_custom_patch(
+                "@@ -1,3 +1,3 @@\n let Foo = 1;\n-foobar();\n+foo_bar();",
+                "modified",
+                1,
+                1,
+            )
+            .build();
+        app.focused_panel = Panel::DiffView;
+        app.diff.search_query = "foo".to_string();
+        assert_eq!(app.search_match_lines(), vec![1, 2, 3]);
+
+        app.toggle_search_option('c');
+        assert_eq!(app.search_match_lines(), vec![2, 3]);
+        app.toggle_search_option('w');
+        assert_eq!(app.search_match_lines(), Vec::<usize>::new());
+        app.toggle_search_option('c');
+        assert_eq!(app.search_match_lines(), vec![1]);
+
+        // 不正な正規表現は確定せず、入力モードに留まる
+        app.toggle_search_option('r');
+        app.mode = AppMode::DiffSearch;
+        app.search_input = "foo(".to_string();
+        app.handle_diff_search_mode(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::DiffSearch);
+        assert_eq!(app.diff.search_query, "foo");
+
+        app.handle_diff_search_mode(KeyCode::Char('w'), KeyModifiers::ALT);
+        app.search_input = "fo+_?bar".to_string();
+        app.handle_diff_search_mode(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.search_match_lines(), vec![2, 3]);
+        app.diff.cursor_line = 3;
+        assert_eq!(app.search_position(), Some((2, 2)));
+    }
+
+    // ── Review report ──
+
+    #[test]
+    fn test_review_report_includes_threads_and_pending() {
+        let mut reply = make_review_comment("src/main.rs", Some(3), "RIGHT", "Agreed");
+        reply.id = 2;
+        reply.in_reply_to_id = Some(1);
+        let mut root = make_review_comment("src/main.rs", Some(3), "RIGHT", "Why?");
+        root.start_line = Some(2);
+        let mut app = TestAppBuilder::new()
+            .with_patch()
+            .review_comments(vec![root, reply])
+            .build();
+        app.review.pending_comments.push(PendingComment {
+            file_path: "src/main.rs".to_string(),
+            start_line: 0,
+            end_line: 1,
+            body: "Rename this".to_string(),
+            commit_sha: TEST_SHA_0.to_string(),
+        });
+
+        let report = app.review_report();
+        assert!(report.contains("## Review threads (1)\n\n### src/main.rs L2–L3\n"));
+        assert!(report.contains("Why?\n\n**@"));
+        assert!(report.contains("Agreed"));
+        // パッチに @@ がないため行番号は 0 始まり
+        assert!(report.contains("### src/main.rs L0–L1 ("));
+        assert!(report.contains("Rename this"));
+    }
+
+    // ── Multi-line review comments ──
+
+    #[test]
+    fn test_multi_line_comment_range() {
+        let mut comment = make_review_comment("src/main.rs", Some(14), "RIGHT", "Range");
+        comment.start_line = Some(10);
+        assert_eq!(comment.line_range_label().as_deref(), Some("L10–L14"));
+        let patch = (1..=30)
+            .map(|i| format!("+line{i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut app = TestAppBuilder::new()
+            .with_custom_patch(&format!("@@ -0,0 +1,30 @@\n{patch}"), "added", 30, 0)
+            .review_comments(vec![comment])
+            .build();
+        // 💬 は末尾行、範囲の途中の行は下線の対象
+        assert_eq!(app.existing_comment_counts().get(&14), Some(&1));
+        assert_eq!(
+            app.commented_range_lines(),
+            (10..14).collect::<HashSet<usize>>()
+        );
+
+        // Conversation からのジャンプは範囲の先頭から見えるようにする
+        app.conversation = vec![make_thread_entry("alice", 1, &[], false)];
+        app.focused_panel = Panel::Conversation;
+        app.diff.view_height = 5;
+        app.jump_to_conversation_thread();
+        assert_eq!(app.diff.cursor_line, 14);
+        assert!(app.diff.scroll as usize <= 10 && app.diff.scroll as usize + 5 > 14);
+    }
+
+    // ── Unread conversation entries ──
+
+    #[test]
+    fn test_unread_conversation_entries() {
+        let mut app = TestAppBuilder::new()
+            .repo("unread-owner/unread-repo")
+            .build();
+        app.conversation = vec![
+            make_review_entry("alice", "COMMENTED"),
+            make_thread_entry("bob", 2, &[], false),
+            make_review_entry("carol", "APPROVED"),
+        ];
+        app.set_seen_entries(None);
+        assert_eq!(app.unread_count(), 3);
+
+        // カーソルが乗ったエントリは既読になる
+        app.focused_panel = Panel::Conversation;
+        app.conversation_cursor = 1;
+        app.mark_conversation_seen();
+        assert!(!app.is_entry_unread(1));
+        assert_eq!(app.unread_count(), 2);
+
+        // u: 最初の未読エントリへ
+        app.handle_normal_mode(KeyCode::Char('u'), KeyModifiers::NONE);
+        assert_eq!(app.conversation_cursor, 0);
+        app.handle_normal_mode(KeyCode::Char('u'), KeyModifiers::NONE);
+        assert_eq!(app.conversation_cursor, 2);
+        assert_eq!(app.unread_count(), 0);
+
+        // 既読のスレッドに返信が付くと未読に戻る
+        app.conversation[1] = make_thread_entry("bob", 2, &["alice"], false);
+        assert!(app.is_entry_unread(1));
+        app.handle_normal_mode(KeyCode::Char('u'), KeyModifiers::NONE);
+        assert_eq!(app.conversation_cursor, 1);
+        app.handle_normal_mode(KeyCode::Char('u'), KeyModifiers::NONE);
+        assert!(app.status_message.is_some());
+    }
+
+    // ── All changes ──
+
+    #[test]
+    fn test_all_changes_row() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        // API で取得できず未読み込みなら行を出さない
+        assert_eq!(app.commit_row_count(), 2);
+
+        app.pr_files = Some(vec![
+            DiffFile {
+                filename: "src/lib.rs".to_string(),
+                status: "modified".to_string(),
+                additions: 2,
+                deletions: 0,
+                patch: Some("@@ -1,1 +1,3 @@\n a\n+b\n+c".to_string()),
+            },
+            DiffFile {
+                filename: "src/main.rs".to_string(),
+                status: "modified".to_string(),
+                additions: 1,
+                deletions: 0,
+                patch: Some("@@ -1,1 +1,2 @@\n a\n+b".to_string()),
+            },
+        ]);
+        assert_eq!(app.commit_row_count(), 3);
+        app.focused_panel = Panel::CommitList;
+        app.select_next();
+        app.select_next();
+        assert!(app.is_all_changes_selected());
+        assert_eq!(app.current_files().len(), 2);
+        assert_eq!(app.current_commit_sha().as_deref(), Some(ALL_CHANGES_KEY));
+        assert_eq!(
+            app.current_revision(),
+            app.commits.last().map(|c| c.sha.clone())
+        );
+        app.select_next();
+        assert!(app.is_all_changes_selected()); // clamped at end
+
+        // ]f は All changes の中だけで移動する
+        app.focused_panel = Panel::DiffView;
+        app.file_list_state.select(Some(0));
+        app.jump_to_adjacent_file(true);
+        assert_eq!(app.file_list_state.selected(), Some(1));
+        assert!(app.is_all_changes_selected());
+        app.jump_to_adjacent_file(true);
+        assert_eq!(app.file_list_state.selected(), Some(1));
+
+        // コメントは All changes のキーで保持し、パッチから行番号を解決する
+        app.diff.cursor_line = 2;
+        app.enter_line_select_mode();
+        app.enter_comment_input_mode();
+        app.review.comment_editor.insert_text("Nice");
+        app.confirm_comment();
+        assert_eq!(app.review.pending_comments[0].commit_sha, ALL_CHANGES_KEY);
+        assert_eq!(app.pending_comment_summaries(), vec!["src/main.rs:L2 Nice"]);
+
+        // viewed は個々のコミットとは別に管理する
+        app.set_commit_viewed(ALL_CHANGES_KEY, true);
+        assert!(app.is_commit_viewed(ALL_CHANGES_KEY));
+        assert_eq!(app.viewed_commit_count(), 0);
+    }
+
+    // ── External editor ──
+
+    #[test]
+    fn test_external_editor_modes() {
+        for mode in [
+            AppMode::CommentInput,
+            AppMode::ReplyInput,
+            AppMode::IssueCommentInput,
+            AppMode::NoteInput,
+            AppMode::ReviewBodyInput,
+        ] {
+            assert!(App::is_editor_mode(mode), "{mode:?}");
+        }
+        assert!(!App::is_editor_mode(AppMode::Normal));
+        assert!(!App::is_editor_mode(AppMode::DiffSearch));
+
+        let mut app = TestAppBuilder::new().build();
+        app.mode = AppMode::ReviewBodyInput;
+        app.review.review_body_editor.insert_text("LGTM");
+        assert_eq!(app.editor_for(AppMode::ReviewBodyInput).text(), "LGTM");
+        assert!(app.editor_for(AppMode::NoteInput).is_empty());
+    }
+
+    // ── Review templates ──
+
+    #[test]
+    fn test_review_templates_prefill_body() {
+        let mut app = create_app_with_patch();
+        app.set_review_templates(crate::config::ReviewTemplates {
+            approve: "LGTM".to_string(),
+            request_changes: "Blocking:\n- ".to_string(),
+            ..Default::default()
+        });
+
+        app.mode = AppMode::ReviewSubmit;
+        app.handle_review_submit_mode(KeyCode::Char('r'));
+        assert_eq!(app.mode, AppMode::ReviewBodyInput);
+        assert_eq!(app.review.review_body_editor.text(), "Blocking:\n- ");
+        // テンプレートの続きから編集できる
+        app.handle_review_body_input_mode(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(app.review.review_body_editor.text(), "Blocking:\n- x");
+
+        app.mode = AppMode::ReviewSubmit;
+        app.handle_review_submit_mode(KeyCode::Char('a'));
+        assert_eq!(app.review.review_body_editor.text(), "LGTM");
+    }
+
+    // ── Suggestions from line selection ──
+
+    #[test]
+    fn test_suggestion_from_line_select() {
+        let patch = "@@ -1,2 +1,3 @@\n fn main() {\n+    // ```rust\n }";
+        let mut app = TestAppBuilder::new()
+            .with_custom_patch(patch, "modified", 1, 0)
+            .build();
+        app.focused_panel = Panel::DiffView;
+        app.diff.cursor_line = 1;
+        app.enter_line_select_mode();
+        app.diff.cursor_line = 2;
+
+        app.handle_line_select_mode(KeyCode::Char('s'));
+        assert_eq!(app.mode, AppMode::CommentInput);
+        // コード中の ``` で閉じないよう、フェンスを長くする
+        let expected = "````suggestion\nfn main() {\n    // ```rust\n````";
+        assert_eq!(app.review.comment_editor.text(), expected);
+
+        app.handle_comment_input_mode(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        let pending = &app.review.pending_comments[0];
+        assert_eq!((pending.start_line, pending.end_line), (1, 2));
+        assert_eq!(pending.body, expected);
+    }
+
+    // ── Status message history ──
+
+    #[test]
+    fn test_status_history_and_sticky_errors() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.focused_panel = Panel::DiffView;
+
+        app.status_message = Some(StatusMessage::info("✓ Copied"));
+        app.record_status_message();
+        // 同じメッセージは二重に記録しない
+        app.record_status_message();
+        app.status_message = Some(StatusMessage {
+            body: "✗ Failed".to_string(),
+            level: StatusLevel::Error,
+            created_at: Instant::now() - Duration::from_secs(60),
+        });
+        app.record_status_message();
+        assert_eq!(app.status_history.len(), 2);
+        // エラーは時間では消えない
+        assert!(!app.status_message.as_ref().unwrap().is_expired());
+
+        // Esc はまずエラーを消し、ペインはそのまま
+        app.handle_normal_mode(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.status_message.is_none());
+        assert_eq!(app.focused_panel, Panel::DiffView);
+
+        app.handle_normal_mode(KeyCode::Char('g'), KeyModifiers::NONE);
+        app.handle_normal_mode(KeyCode::Char('m'), KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::StatusHistory);
+        let lines = app.status_history_lines();
+        assert_eq!(lines[0].0, StatusLevel::Error);
+        assert!(lines[0].1.ends_with(" ✗ Failed"));
+        assert!(lines[1].1.ends_with(" ✓ Copied"));
+
+        app.handle_status_history_mode(KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    // ── Viewed sync ──
+
+    #[test]
+    fn test_all_changes_viewed_is_queued_for_github() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.schedule_viewed_fetch();
+        assert!(!app.needs_viewed_fetch);
+        app.set_mutes("PR_kwDO".to_string(), None);
+        app.schedule_viewed_fetch();
+        assert!(app.needs_viewed_fetch);
+
+        // コミット単位の viewed は GitHub に送らない
+        let sha = app.commits[0].sha.clone();
+        app.set_files_viewed(&sha, vec!["src/main.rs".to_string()], true);
+        assert!(app.viewed_sync.is_empty());
+
+        let files = vec!["src/a.rs".to_string(), "src/b.rs".to_string()];
+        app.set_files_viewed(ALL_CHANGES_KEY, files, true);
+        app.set_files_viewed(ALL_CHANGES_KEY, vec!["src/a.rs".to_string()], false);
+        assert!(app.is_file_viewed(ALL_CHANGES_KEY, "src/b.rs"));
+        assert!(!app.is_file_viewed(ALL_CHANGES_KEY, "src/a.rs"));
+        // 送る前に切り替え直したファイルは最後の状態だけ
+        assert_eq!(
+            app.viewed_sync,
+            vec![
+                ("src/b.rs".to_string(), true),
+                ("src/a.rs".to_string(), false)
+            ]
+        );
+        assert!(app.needs_viewed_sync);
+        // 状態が変わらなければ送らない
+        app.set_files_viewed(ALL_CHANGES_KEY, vec!["src/b.rs".to_string()], true);
+        assert_eq!(app.viewed_sync.len(), 2);
+    }
+
+    #[test]
+    fn test_viewed_fetch_merges_and_failed_sync_is_kept() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        app.set_mutes("PR_kwDO".to_string(), None);
+        app.set_files_viewed(ALL_CHANGES_KEY, vec!["local.rs".to_string()], true);
+        app.set_files_viewed(ALL_CHANGES_KEY, vec!["both.rs".to_string()], true);
+        app.set_files_viewed(ALL_CHANGES_KEY, vec!["both.rs".to_string()], false);
+        // GitHub に送り済みで、その後 Web UI で外されたファイル
+        app.viewed_files
+            .get_mut(ALL_CHANGES_KEY)
+            .unwrap()
+            .insert("unchecked.rs".to_string());
+        app.needs_viewed_sync = false;
+
+        // GitHub の状態で置き換える（送れていない変更だけはローカルを優先）
+        let remote = ["remote.rs", "both.rs"].map(String::from).into();
+        app.apply_viewed_files(Ok(remote));
+        assert!(!app.is_file_viewed(ALL_CHANGES_KEY, "unchecked.rs"));
+        assert!(app.is_file_viewed(ALL_CHANGES_KEY, "local.rs"));
+        assert!(app.is_file_viewed(ALL_CHANGES_KEY, "remote.rs"));
+        assert!(!app.is_file_viewed(ALL_CHANGES_KEY, "both.rs"));
+        assert!(app.needs_viewed_sync);
+
+        // 送れなかったものは残し、送れたものだけ消す
+        app.viewed_sync_running = true;
+        app.apply_viewed_synced(
+            vec![("local.rs".to_string(), true)],
+            vec![("both.rs".to_string(), "timeout".to_string())],
+        );
+        assert!(!app.viewed_sync_running);
+        assert_eq!(app.viewed_sync, vec![("both.rs".to_string(), false)]);
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Error)
+        );
+    }
+
+    // ── Quit while background work is running ──
+
+    #[test]
+    fn test_quit_waits_for_background_tasks() {
+        let mut app = create_app_with_patch();
+        app.loading.conversation = LoadPhase::Loading;
+
+        app.handle_normal_mode(KeyCode::Char('q'), KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::QuitWait);
+        assert!(!app.should_quit);
+        assert_eq!(app.background_tasks(), vec!["Loading conversation"]);
+
+        // c: 終了をやめる
+        app.handle_quit_wait_mode(KeyCode::Char('c'));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(!app.should_quit);
+
+        // w: 終わるまで待ってから終了
+        app.handle_normal_mode(KeyCode::Char('q'), KeyModifiers::NONE);
+        app.handle_quit_wait_mode(KeyCode::Char('w'));
+        app.quit_if_idle();
+        assert!(!app.should_quit);
+        app.loading.conversation = LoadPhase::Done;
+        app.quit_if_idle();
+        assert!(app.should_quit);
+
+        // q: そのまま終了
+        let mut app = create_app_with_patch();
+        app.loading.media = LoadPhase::Loading;
+        app.request_quit();
+        app.handle_quit_wait_mode(KeyCode::Char('q'));
+        assert!(app.should_quit);
+    }
+
+    // ── Files heatmap ──
+
+    #[test]
+    fn test_file_activity_counts_comments_threads_and_pending() {
+        let mut app = create_app_with_patch();
+        let mut root = make_review_comment("a.rs", Some(1), "RIGHT", "one");
+        root.id = 10;
+        let mut reply = make_review_comment("a.rs", Some(1), "RIGHT", "two");
+        reply.id = 11;
+        reply.in_reply_to_id = Some(10);
+        let mut other = make_review_comment("b.rs", Some(3), "RIGHT", "three");
+        other.id = 20;
+        app.review.review_comments = vec![root, reply, other];
+        for (id, is_resolved) in [(10, false), (20, true)] {
+            app.review.thread_map.insert(
+                id,
+                ReviewThread {
+                    node_id: format!("T{id}"),
+                    is_resolved,
+                    root_comment_database_id: id,
+                },
+            );
+        }
+        app.review.pending_comments.push(PendingComment {
+            file_path: "c.rs".to_string(),
+            start_line: 1,
+            end_line: 1,
+            body: "pending".to_string(),
+            commit_sha: TEST_SHA_0.to_string(),
+        });
+
+        let activity = app.file_activity();
+        // コメント 2 + 未解決スレッド 1
+        assert_eq!(activity["a.rs"], 3);
+        // 解決済みスレッドは加算しない
+        assert_eq!(activity["b.rs"], 1);
+        assert_eq!(activity["c.rs"], 1);
+    }
+
+    // ── CI checks ──
+
+    #[test]
+    fn test_checks_badge_and_overlay() {
+        use crate::github::checks::{Check, CheckState};
+
+        let mut app = create_app_with_patch();
+        // API クライアントがなければ開かない
+        app.handle_normal_mode(KeyCode::Char('C'), KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.checks_badge().is_none());
+
+        let check = |name: &str, state| Check {
+            name: name.to_string(),
+            state,
+            detail: String::new(),
+            url: None,
+        };
+        app.checks = Some(vec![
+            check("test", CheckState::Failure),
+            check("lint", CheckState::Success),
+        ]);
+        assert_eq!(
+            app.checks_badge(),
+            Some((" CI ✗1 ✓1 ".to_string(), Color::Red))
+        );
+
+        app.mode = AppMode::Checks;
+        app.handle_checks_mode(KeyCode::Char('j'));
+        app.handle_checks_mode(KeyCode::Char('j'));
+        assert_eq!(app.checks_cursor, 1);
+        // 詳細 URL がなければエラー表示
+        app.handle_checks_mode(KeyCode::Enter);
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Error)
+        );
+        app.handle_checks_mode(KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_allowed_command_reruns_the_stopped_action() {
+        let mut app = create_app_with_patch();
+        app.permission_retry = Some(PermissionRetry::RunCommand);
+        app.mode = AppMode::AllowCommand;
+        app.allow_command = "sh".to_string();
+        app.handle_allow_command_mode(KeyCode::Char('y'));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.permission_retry.is_none());
+        assert!(app.needs_run_command);
+
+        // 拒否したらやり直さない
+        app.needs_run_command = false;
+        app.permission_retry = Some(PermissionRetry::RunCommand);
+        app.mode = AppMode::AllowCommand;
+        app.allow_command = "sh".to_string();
+        app.handle_allow_command_mode(KeyCode::Char('n'));
+        assert!(app.permission_retry.is_none());
+        assert!(!app.needs_run_command);
+    }
+
+    #[test]
+    fn test_allow_command_dialog_returns_to_previous_mode() {
+        let mut app = create_app_with_patch();
+        app.mode = AppMode::AllowCommand;
+        app.allow_command = "delta".to_string();
+        app.allow_return_mode = AppMode::LineSelect;
+        // 他のキーでは閉じない
+        app.handle_allow_command_mode(KeyCode::Char('x'));
+        assert_eq!(app.mode, AppMode::AllowCommand);
+
+        app.handle_allow_command_mode(KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::LineSelect);
+        assert!(app.allow_command.is_empty());
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Info)
+        );
+    }
+
+    #[test]
+    fn test_checks_result_is_applied_only_for_current_head() {
+        use crate::github::checks::{Check, CheckState};
+
+        let mut app = create_app_with_patch();
+        app.checks_loading = true;
+        assert_eq!(
+            app.checks_badge(),
+            Some((" CI … ".to_string(), Color::DarkGray))
+        );
+
+        let checks = vec![Check {
+            name: "test".to_string(),
+            state: CheckState::Success,
+            detail: String::new(),
+            url: None,
+        }];
+        // 取得中に head が変わった結果は捨てる
+        app.apply_checks("stale", Ok(checks.clone()));
+        assert!(!app.checks_loading);
+        assert!(app.checks.is_none());
+
+        let head = app.commits.last().unwrap().sha.clone();
+        app.apply_checks(&head, Ok(checks));
+        assert_eq!(app.checks_len(), 1);
+
+        // 失敗したら前回の結果のまま
+        app.apply_checks(&head, Err("timeout".to_string()));
+        assert_eq!(app.checks_len(), 1);
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Error)
+        );
+    }
+
+    // ── Checklist from the PR task list ──
+
+    #[test]
+    fn test_checklist_imports_pr_tasks() {
+        let mut app = TestAppBuilder::new()
+            .repo("checklist-owner/pr-tasks")
+            .pr_body("- [x] Tests\n- [ ] Screenshots")
+            .build();
+        let mut saved = crate::github::cache::ChecklistCache::default();
+        saved.checked.insert("Screenshots".to_string(), true);
+        app.set_checklist(
+            vec!["Security".to_string(), "Tests".to_string()],
+            Some(saved),
+        );
+        // 設定と同じラベルは重複させない。チェック状態は作者ではなくレビュアーのもの
+        let labels: Vec<(&str, bool, bool)> = app
+            .checklist
+            .iter()
+            .map(|i| (i.label.as_str(), i.checked, i.from_pr))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("Security", false, false),
+                ("Tests", false, false),
+                ("Screenshots", true, true),
+            ]
+        );
+
+        // すべてチェックしたら本文への追記を勧める
+        app.mode = AppMode::Checklist;
+        app.handle_checklist_mode(KeyCode::Char('x'));
+        assert!(app.status_message.is_none());
+        app.checklist_cursor = 1;
+        app.handle_checklist_mode(KeyCode::Char('x'));
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.body.as_str()),
+            Some("✓ Checklist complete — a: add it to the review body")
+        );
+
+        // 本文が変わったら取り込み直し、残った項目の状態は引き継ぐ
+        app.pr_body = "- [ ] Screenshots\n- [ ] Changelog".to_string();
+        app.refresh_pr_tasks();
+        let labels: Vec<&str> = app.checklist.iter().map(|i| i.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec!["Security", "Tests", "Screenshots", "Changelog"]
+        );
+        assert!(app.checklist[2].checked);
+        assert!(!app.checklist[3].checked);
+    }
+
+    // ── File finder ──
+
+    #[test]
+    fn test_file_finder_jumps_across_commits() {
+        let file = |name: &str| DiffFile {
+            filename: name.to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: Some("+x".to_string()),
+        };
+        let mut files_map = HashMap::new();
+        files_map.insert(
+            TEST_SHA_0.to_string(),
+            vec![file("src/main.rs"), file("src/lib.rs")],
+        );
+        files_map.insert(
+            TEST_SHA_1.to_string(),
+            vec![file("src/main.rs"), file("docs/guide.md")],
+        );
+        let mut app = TestAppBuilder::new()
+            .with_commits()
+            .files_map(files_map)
+            .build();
+
+        app.handle_normal_mode(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        assert_eq!(app.mode, AppMode::FileFinder);
+        // 入力が空なら全ファイルをパス順に、変更したコミット数付きで出す
+        let all: Vec<(String, usize)> = app
+            .file_finder_matches()
+            .into_iter()
+            .map(|m| (m.filename, m.commits))
+            .collect();
+        assert_eq!(
+            all,
+            vec![
+                ("docs/guide.md".to_string(), 1),
+                ("src/lib.rs".to_string(), 1),
+                ("src/main.rs".to_string(), 2),
+            ]
+        );
+        for _ in 0..5 {
+            app.handle_file_finder_mode(KeyCode::Down, KeyModifiers::NONE);
+        }
+        assert_eq!(app.file_finder_cursor, 2);
+
+        // 選択中のコミットにないファイルは、それを変更したコミットへ移る
+        for c in "guide".chars() {
+            app.handle_file_finder_mode(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        assert_eq!(app.file_finder_cursor, 0);
+        app.handle_file_finder_mode(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.commit_list_state.selected(), Some(1));
+        assert_eq!(app.file_list_state.selected(), Some(1));
+        assert_eq!(app.focused_panel, Panel::DiffView);
+
+        // 選択中のコミットにあればコミットは変えない
+        app.handle_normal_mode(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        assert!(app.file_finder_input.is_empty());
+        for c in "mn".chars() {
+            app.handle_file_finder_mode(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_file_finder_mode(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.commit_list_state.selected(), Some(1));
+        assert_eq!(app.file_list_state.selected(), Some(0));
+
+        // 一致しなければ Enter で何もしない
+        app.open_file_finder();
+        for c in "zzz".chars() {
+            app.handle_file_finder_mode(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_file_finder_mode(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::FileFinder);
+        app.handle_file_finder_mode(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    // ── Commenting on PR description media ──
+
+    #[test]
+    fn test_comment_on_media_prefills_reference() {
+        let mut app = TestAppBuilder::new().with_test_data().build();
+        let (_, refs) = preprocess_pr_body(
+            "![before](https://example.com/a.png)\n<video src=\"https://example.com/b.mp4\"></video>",
+        );
+        app.media_refs = refs;
+        app.drafts
+            .insert(DraftKey::IssueComment, "Overall looks good.".to_string());
+        app.mode = AppMode::MediaViewer;
+        app.media_viewer_index = 1;
+        app.handle_media_viewer_mode(KeyCode::Char('c'));
+        assert_eq!(app.mode, AppMode::IssueCommentInput);
+        assert_eq!(app.focused_panel, Panel::Conversation);
+        // 下書きは残し、その後ろに参照を足す
+        assert_eq!(
+            app.review.comment_editor.text(),
+            "Overall looks good.\n\n> [Video 2 of 2](https://example.com/b.mp4)\n\n"
+        );
+    }
+
+    // ── Posting issue comments ──
+
+    #[test]
+    fn test_issue_comment_shown_while_posting_and_kept_on_failure() {
+        let mut app = create_app_with_patch();
+        app.current_user = "me".to_string();
+        app.focused_panel = Panel::Conversation;
+        app.handle_normal_mode(KeyCode::Char('c'), KeyModifiers::NONE);
+        for c in "LGTM".chars() {
+            app.handle_issue_comment_input_mode(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_issue_comment_input_mode(KeyCode::Char('s'), KeyModifiers::CONTROL);
+
+        // 送信前に末尾へ仮表示する
+        let last = app.conversation.len() - 1;
+        let entry = &app.conversation[last];
+        assert_eq!((entry.author.as_str(), entry.body.as_str()), ("me", "LGTM"));
+        assert!(matches!(entry.kind, ConversationKind::IssueComment));
+        assert_eq!(app.posting_issue_comment, Some(last));
+        assert_eq!(app.conversation_cursor, last);
+        app.ensure_conversation_rendered();
+        let header: String = app.conversation_rendered.as_ref().unwrap()
+            [app.conversation_entry_offsets[last]]
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert!(header.contains("[posting…]"));
+        assert!(!header.contains('●'));
+
+        // 送信に失敗したら仮表示を取り除き、本文は下書きに戻す
+        app.needs_issue_comment_submit = false;
+        app.submit_issue_comment();
+        assert!(app.conversation.is_empty());
+        assert_eq!(app.posting_issue_comment, None);
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Error)
+        );
+        app.handle_normal_mode(KeyCode::Char('c'), KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::IssueCommentInput);
+        assert_eq!(app.review.comment_editor.text(), "LGTM");
+    }
+
+    // ── PR の close / reopen ──────────────────────────
+
+    #[test]
+    fn test_close_and_reopen_pr_confirmation() {
+        let mut app = TestAppBuilder::new().build();
+        app.set_mutes("PR_kwDO".to_string(), None);
+        app.pr_state = "Merged".to_string();
+        app.handle_normal_mode(KeyCode::Char('Z'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Error)
+        );
+
+        app.pr_state = "Open".to_string();
+        app.handle_normal_mode(KeyCode::Char('Z'), KeyModifiers::SHIFT);
+        assert_eq!(app.mode, AppMode::PrStateChange);
+        app.handle_pr_state_change_mode(KeyCode::Esc);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.needs_pr_state_change, None);
+
+        app.mode = AppMode::PrStateChange;
+        app.handle_pr_state_change_mode(KeyCode::Char('y'));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.needs_pr_state_change, Some(PrStateChange::Close));
+        assert_eq!(app.blocking_operation_message(), Some("Closing PR..."));
+
+        app.needs_pr_state_change = None;
+        app.pr_state = "Closed".to_string();
+        app.mode = AppMode::PrStateChange;
+        app.handle_pr_state_change_mode(KeyCode::Char('y'));
+        assert_eq!(app.needs_pr_state_change, Some(PrStateChange::Reopen));
+    }
+
+    // ── interdiff ──────────────────────────
+
+    #[test]
+    fn test_interdiff_between_marked_commits() {
+        let mut app = create_app_with_patch();
+        app.focused_panel = Panel::CommitList;
+        app.commit_list_state.select(Some(0));
+        app.handle_normal_mode(KeyCode::Char('m'), KeyModifiers::NONE);
+        assert_eq!(app.interdiff_base, Some(0));
+        // 基準のコミット自身は通常の差分
+        assert!(!app.is_interdiff_active());
+        assert!(!app.needs_interdiff_files());
+
+        app.commit_list_state.select(Some(1));
+        assert_eq!(app.interdiff_range(), Some((0, 1)));
+        assert!(app.needs_interdiff_files());
+        assert!(app.current_files().is_empty());
+
+        app.interdiff_files.insert(
+            (0, 1),
+            vec![DiffFile {
+                filename: "src/combined.rs".to_string(),
+                status: "modified".to_string(),
+                additions: 1,
+                deletions: 0,
+                patch: Some("@@ -1,1 +1,2 @@\n line1\n+added".to_string()),
+            }],
+        );
+        assert!(!app.needs_interdiff_files());
+        assert_eq!(app.current_files()[0].filename, "src/combined.rs");
+
+        // 行がどのコミットの差分にも対応しないのでコメントできない
+        app.file_list_state.select(Some(0));
+        app.focused_panel = Panel::DiffView;
+        app.diff.cursor_line = 2;
+        app.handle_normal_mode(KeyCode::Char('v'), KeyModifiers::NONE);
+        app.handle_line_select_mode(KeyCode::Char('c'));
+        assert_ne!(app.mode, AppMode::CommentInput);
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Error)
+        );
+
+        app.mode = AppMode::Normal;
+        app.focused_panel = Panel::CommitList;
+        app.commit_list_state.select(Some(0));
+        app.handle_normal_mode(KeyCode::Char('m'), KeyModifiers::NONE);
+        assert_eq!(app.interdiff_base, None);
+        app.commit_list_state.select(Some(1));
+        assert!(!app.is_interdiff_active());
+    }
+
+    // ── 自分のコメントの編集・削除 ──────────────
+
+    #[test]
+    fn test_edit_and_delete_own_review_comment() {
+        let root = make_review_comment("src/main.rs", Some(2), "RIGHT", "Looks off");
+        let mut reply = make_review_comment("src/main.rs", Some(2), "RIGHT", "Fixed it");
+        reply.id = 2;
+        reply.in_reply_to_id = Some(1);
+        reply.user.login = "me".to_string();
+        reply.created_at = "2025-01-02T00:00:00Z".to_string();
+        let mut app = TestAppBuilder::new()
+            .with_custom_patch("@@ -0,0 +1,3 @@\n+line1\n+line2\n+line3", "added", 3, 0)
+            .review_comments(vec![root.clone(), reply.clone()])
+            .build();
+        app.current_user = "me".to_string();
+        app.review.viewing_comments = vec![root, reply];
+        app.select_default_viewing_comment();
+        app.mode = AppMode::CommentView;
+
+        app.handle_comment_view_mode(KeyCode::Char('e'));
+        assert_eq!(app.mode, AppMode::CommentEditInput);
+        assert_eq!(app.review.editing_comment_id, Some(2));
+        assert_eq!(app.review.comment_editor.text(), "Fixed it");
+
+        // Esc で空でない編集を捨てると CommentView に戻る
+        app.handle_comment_edit_input_mode(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.mode, AppMode::DiscardConfirm);
+        app.cancel_editor(AppMode::CommentEditInput);
+        assert_eq!(app.mode, AppMode::CommentView);
+        assert_eq!(app.review.editing_comment_id, None);
+
+        app.replace_review_comment_body(2, "Fixed in abc1234");
+        assert_eq!(app.review.review_comments[1].body, "Fixed in abc1234");
+        assert_eq!(app.review.viewing_comments[1].body, "Fixed in abc1234");
+
+        app.handle_comment_view_mode(KeyCode::Char('d'));
+        assert_eq!(app.mode, AppMode::CommentDeleteConfirm);
+        app.handle_comment_delete_confirm_mode(KeyCode::Char('y'));
+        assert!(app.needs_comment_delete);
+        assert_eq!(app.mode, AppMode::CommentView);
+
+        app.remove_review_comment(2);
+        assert_eq!(app.review.review_comments.len(), 1);
+        assert_eq!(app.review.viewing_comments.len(), 1);
+        assert_eq!(app.mode, AppMode::CommentView);
+
+        // 他人のコメントしか残っていなければ編集も削除もできない
+        app.status_message = None;
+        app.handle_comment_view_mode(KeyCode::Char('e'));
+        assert_eq!(app.mode, AppMode::CommentView);
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Error)
+        );
+    }
+
+    #[test]
+    fn test_edit_and_delete_selected_comment_by_id() {
+        let root = make_review_comment("src/main.rs", Some(2), "RIGHT", "Looks off");
+        // 同じ作者・同じ日時の返信が 2 つあっても ID で取り違えない
+        let replies: Vec<ReviewComment> = [(2, "First"), (3, "Second")]
+            .into_iter()
+            .map(|(id, body)| {
+                let mut reply = make_review_comment("src/main.rs", Some(2), "RIGHT", body);
+                reply.id = id;
+                reply.in_reply_to_id = Some(1);
+                reply.user.login = "me".to_string();
+                reply.created_at = "2025-01-02T00:00:00Z".to_string();
+                reply
+            })
+            .collect();
+        let mut app = TestAppBuilder::new()
+            .with_custom_patch("@@ -0,0 +1,3 @@\n+line1\n+line2\n+line3", "added", 3, 0)
+            .review_comments(
+                std::iter::once(root.clone())
+                    .chain(replies.iter().cloned())
+                    .collect(),
+            )
+            .build();
+        let mut entry = make_thread_entry("alice", 1, &["me", "me"], false);
+        if let ConversationKind::CodeComment {
+            ref mut replies, ..
+        } = entry.kind
+        {
+            for (reply, (id, body)) in replies.iter_mut().zip([(2, "First"), (3, "Second")]) {
+                reply.id = id;
+                reply.body = body.to_string();
+                reply.created_at = "2025-01-02T00:00:00Z".to_string();
+            }
+        }
+        app.conversation = vec![entry];
+        app.current_user = "me".to_string();
+        app.review.viewing_comments = std::iter::once(root).chain(replies).collect();
+        app.select_default_viewing_comment();
+        app.mode = AppMode::CommentView;
+        // 開いた時点では自分の最新のコメントを選ぶ
+        assert_eq!(app.review.viewing_comment_cursor, 2);
+
+        // K で 1 つ前の自分のコメントを選んで編集する
+        app.handle_comment_view_mode(KeyCode::Char('K'));
+        app.handle_comment_view_mode(KeyCode::Char('e'));
+        assert_eq!(app.review.editing_comment_id, Some(2));
+        assert_eq!(app.review.comment_editor.text(), "First");
+        app.mode = AppMode::CommentView;
+
+        app.replace_review_comment_body(2, "Edited");
+        let reply_bodies = |app: &App| -> Vec<(u64, String)> {
+            match &app.conversation[0].kind {
+                ConversationKind::CodeComment { replies, .. } => {
+                    replies.iter().map(|r| (r.id, r.body.clone())).collect()
+                }
+                _ => Vec::new(),
+            }
+        };
+        assert_eq!(
+            reply_bodies(&app),
+            vec![(2, "Edited".to_string()), (3, "Second".to_string())]
+        );
+
+        app.remove_review_comment(2);
+        assert_eq!(reply_bodies(&app), vec![(3, "Second".to_string())]);
+        assert_eq!(app.review.viewing_comments.len(), 2);
+
+        // 他人のコメントを選ぶと編集できない
+        app.handle_comment_view_mode(KeyCode::Char('K'));
+        app.handle_comment_view_mode(KeyCode::Char('K'));
+        assert_eq!(app.review.viewing_comment_cursor, 0);
+        app.handle_comment_view_mode(KeyCode::Char('e'));
+        assert_eq!(app.mode, AppMode::CommentView);
+        assert!(
+            app.status_message
+                .as_ref()
+                .is_some_and(|m| m.body.contains("not yours"))
+        );
+    }
 }
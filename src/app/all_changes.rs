@@ -0,0 +1,116 @@
+//! Commits ペイン末尾の All changes（PR 全体 base..head の差分をまとめてレビューする合成エントリ）
+
+use super::*;
+use crate::i18n::tr;
+
+/// All changes のファイル・viewed・保留コメントのキー（実在のコミット SHA とは衝突しない）
+pub(super) const ALL_CHANGES_KEY: &str = "all";
+
+impl App {
+    /// Commits ペインに All changes の行を出すか（読み込み済みか、API で取得できる場合）
+    pub(super) fn has_all_changes_row(&self) -> bool {
+        !self.commits.is_empty() && (self.pr_files.is_some() || self.client.is_some())
+    }
+
+    /// Commits ペインの行数（All changes の行を含む）
+    pub(super) fn commit_row_count(&self) -> usize {
+        self.commits.len() + usize::from(self.has_all_changes_row())
+    }
+
+    /// All changes の行を選択中か
+    pub(super) fn is_all_changes_selected(&self) -> bool {
+        self.has_all_changes_row() && self.commit_list_state.selected() == Some(self.commits.len())
+    }
+
+    /// Commits ペインの行のキー（コミット SHA、All changes の行なら ALL_CHANGES_KEY）
+    pub(super) fn commit_key_at(&self, index: usize) -> Option<&str> {
+        match self.commits.get(index) {
+            Some(commit) => Some(commit.sha.as_str()),
+            None if index == self.commits.len() && self.has_all_changes_row() => {
+                Some(ALL_CHANGES_KEY)
+            }
+            None => None,
+        }
+    }
+
+    /// コミット SHA（All changes なら ALL_CHANGES_KEY）の変更ファイル
+    pub(super) fn files_for(&self, sha: &str) -> Option<&Vec<DiffFile>> {
+        if sha == ALL_CHANGES_KEY {
+            self.pr_files.as_ref()
+        } else {
+            self.files_map.get(sha)
+        }
+    }
+
+    /// 選択中の差分の新しい側のコミット（All changes なら head、パーマリンク等に使う）
+    pub(super) fn current_revision(&self) -> Option<String> {
+        if self.is_all_changes_selected() {
+            return self.commits.last().map(|c| c.sha.clone());
+        }
+        self.current_commit_sha()
+    }
+
+    /// All changes を選択中で未読み込みか（draw 後に読み込む）
+    pub(super) fn needs_all_changes_files(&self) -> bool {
+        self.is_all_changes_selected() && self.pr_files.is_none() && !self.pr_files_failed
+    }
+
+    /// PR 全体の変更ファイルを取得し、Files ペインを初期化する（失敗したらリロードまで再試行しない）
+    pub(super) fn load_all_changes_files(&mut self) {
+        let (Some(client), Some((owner, repo))) = (self.client.clone(), self.parse_repo()) else {
+            self.pr_files_failed = true;
+            return;
+        };
+        let result = tokio::task::block_in_place(|| {
+            Handle::current().block_on(crate::github::files::fetch_pr_files(
+                &client,
+                owner,
+                repo,
+                self.pr_number,
+            ))
+        });
+        match result {
+            Ok(files) => {
+                if crate::github::files::pr_files_truncated(files.len()) {
+                    self.status_message = Some(StatusMessage::error(format!(
+                        "✗ GitHub returned only the first {} files — the rest are not shown",
+                        files.len()
+                    )));
+                }
+                self.pr_files = Some(files);
+                self.rebuild_visible_comment_cache();
+                self.reset_file_selection();
+                self.diff.highlight_cache.clear();
+            }
+            Err(e) => {
+                self.pr_files_failed = true;
+                self.status_message = Some(StatusMessage::error(format!(
+                    "✗ Failed to load all changes: {e} — press R to retry"
+                )));
+            }
+        }
+    }
+
+    /// All changes の行の表示（"◆ All changes (main..feature)"）
+    pub(super) fn all_changes_label(&self) -> String {
+        format!(
+            "◆ {} ({}..{})",
+            tr("All changes"),
+            self.pr_base_branch,
+            self.pr_head_branch
+        )
+    }
+
+    /// All changes を選択中の Commit ペインの本文
+    pub(super) fn all_changes_summary(&self) -> String {
+        let first = self.commits.first().map(|c| c.short_sha()).unwrap_or("");
+        let last = self.commits.last().map(|c| c.short_sha()).unwrap_or("");
+        format!(
+            "{}\n\n{} commits ({first}..{last})\n{} ← {}",
+            tr("All changes"),
+            self.commits.len(),
+            self.pr_base_branch,
+            self.pr_head_branch
+        )
+    }
+}
@@ -0,0 +1,283 @@
+//! コメントへの画像添付（ファイルかクリップボードの画像をアップロードしてマークダウンを挿入する）
+
+use super::*;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 添付できる画像の拡張子
+const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "gif", "webp"];
+/// クリップボードの画像のファイル名（貼るたびに新しいディレクトリに作る）
+const CLIPBOARD_IMAGE_FILE: &str = "prism-clipboard.png";
+
+/// 画像ファイルか（拡張子で判定）
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// 先頭の `~/` をホームディレクトリに展開する
+fn expand_home(input: &str) -> PathBuf {
+    if let Some(rest) = input.strip_prefix("~/")
+        && let Some(home) = std::env::var_os("HOME")
+    {
+        return PathBuf::from(home).join(rest);
+    }
+    PathBuf::from(input)
+}
+
+/// マークダウンの画像参照（alt は拡張子を除いたファイル名）
+pub(super) fn image_markdown(path: &Path, url: &str) -> String {
+    let alt = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "image".to_string());
+    format!("![{alt}]({url})")
+}
+
+/// アップロードコマンドの出力から URL を取り出す（最初の http(s) の行）
+pub(super) fn parse_upload_url(output: &str) -> Option<&str> {
+    output
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("https://") || line.starts_with("http://"))
+}
+
+/// アップロードコマンドを `sh -c` で実行する（画像のパスは $1 で渡す）
+fn run_upload_command(command: &str, path: &Path) -> Result<String, String> {
+    let output = crate::exec::command("sh")
+        .ok_or_else(|| "sh is not in allowed_commands".to_string())?
+        .args(["-c", &format!("{command} \"$1\""), "prism"])
+        .arg(path)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().next().unwrap_or("upload failed").to_string());
+    }
+    parse_upload_url(&String::from_utf8_lossy(&output.stdout))
+        .map(str::to_string)
+        .ok_or_else(|| "no URL in upload command output".to_string())
+}
+
+/// クリップボードの画像を置くディレクトリを新しく作る（0700。既にあれば他人が置いたものなので使わない）
+fn create_clipboard_dir() -> std::io::Result<PathBuf> {
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("prism-clipboard-{}-{stamp}", std::process::id()));
+    crate::platform::private_dir_builder().create(&dir)?;
+    Ok(dir)
+}
+
+/// 新しいファイルとして書き込む（既にあるファイルやシンボリックリンクは辿らずにエラーにする）
+fn write_new_file(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)?
+        .write_all(data)
+}
+
+/// クリップボードの画像をディレクトリごと消す
+pub(super) fn remove_clipboard_image(path: &Path) {
+    let _ = std::fs::remove_file(path);
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::remove_dir(dir);
+    }
+}
+
+/// クリップボードから保存した画像（手放したら消す。アップロードしないまま終了しても一時ディレクトリに残さない）
+#[derive(Debug)]
+pub(super) struct ClipboardImage(PathBuf);
+
+impl ClipboardImage {
+    pub(super) fn new(path: PathBuf) -> Self {
+        Self(path)
+    }
+
+    pub(super) fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ClipboardImage {
+    fn drop(&mut self) {
+        remove_clipboard_image(&self.0);
+    }
+}
+
+/// クリップボードの画像を一時ファイルに保存する
+fn save_clipboard_image() -> Result<PathBuf, &'static str> {
+    let program = crate::platform::clipboard_image_program();
+    let Some(mut cmd) = crate::exec::command(program) else {
+        return Err("✗ Clipboard image helper is not in allowed_commands");
+    };
+    let Ok(dir) = create_clipboard_dir() else {
+        return Err("✗ Failed to create a temporary directory for the image");
+    };
+    let path = dir.join(CLIPBOARD_IMAGE_FILE);
+    let saved = if program == "xclip" {
+        cmd.args(["-selection", "clipboard", "-t", "image/png", "-o"])
+            .stdin(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success() && !output.stdout.is_empty())
+            .is_some_and(|output| write_new_file(&path, &output.stdout).is_ok())
+    } else {
+        cmd.arg(&path)
+            .stdin(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    };
+    if saved {
+        Ok(path)
+    } else {
+        remove_clipboard_image(&path);
+        Err("✗ No image in clipboard")
+    }
+}
+
+impl App {
+    /// 画像のアップロードコマンドをセットする（未設定ならドラッグ＆ドロップを案内する）
+    pub fn set_attach_upload_command(&mut self, command: Option<String>) {
+        self.attach_upload_command = command.filter(|c| !c.trim().is_empty());
+    }
+
+    /// Ctrl+T: 画像の添付を始める（コメント・返信の入力中）
+    pub(super) fn open_attach_prompt(&mut self) {
+        self.attach_return_mode = self.mode;
+        self.attach_input.clear();
+        self.mode = AppMode::AttachImage;
+    }
+
+    /// Enter: 入力したパス（空ならクリップボード）の画像を添付する
+    pub(super) fn confirm_attach(&mut self) {
+        let input = self.attach_input.trim();
+        let path = if input.is_empty() {
+            // 前に貼った画像（ブラウザで添付するためのドラッグ元）はもう要らない
+            self.clipboard_image = None;
+            let pending = crate::exec::pending_count();
+            match save_clipboard_image() {
+                Ok(path) => {
+                    self.clipboard_image = Some(ClipboardImage::new(path.clone()));
+                    path
+                }
+                Err(msg) => {
+                    self.status_message = Some(StatusMessage::error(msg));
+                    self.retry_after_permission(pending, PermissionRetry::ClipboardImage);
+                    return;
+                }
+            }
+        } else {
+            expand_home(input)
+        };
+        if !path.is_file() {
+            self.status_message = Some(StatusMessage::error("✗ Image file not found"));
+            return;
+        }
+        if !is_image(&path) {
+            self.status_message =
+                Some(StatusMessage::error("✗ Not an image (png, jpg, gif, webp)"));
+            return;
+        }
+        self.mode = self.attach_return_mode;
+        if self.attach_upload_command.is_some() {
+            self.needs_attach_upload = Some(path);
+        } else {
+            self.insert_attach_placeholder(&path);
+        }
+    }
+
+    /// アップロードして画像参照を挿入する（draw 後に実行）
+    pub(super) fn execute_attach_upload(&mut self, path: PathBuf) {
+        let Some(command) = self.attach_upload_command.clone() else {
+            return;
+        };
+        let pending = crate::exec::pending_count();
+        match run_upload_command(&command, &path) {
+            Ok(url) => {
+                self.insert_into_comment(&image_markdown(&path, &url));
+                self.status_message = Some(StatusMessage::info("✓ Image attached"));
+                // アップロード済みのクリップボードの画像はもう要らない（ユーザーのファイルは消さない）
+                if self
+                    .clipboard_image
+                    .as_ref()
+                    .is_some_and(|image| image.path() == path)
+                {
+                    self.clipboard_image = None;
+                }
+            }
+            // sh の実行の確認を待つ間は参照を挿入しない（許可されたらアップロードし直す）
+            Err(_)
+                if self.retry_after_permission(
+                    pending,
+                    PermissionRetry::AttachUpload(path.clone()),
+                ) => {}
+            Err(e) => {
+                self.insert_attach_placeholder(&path);
+                self.status_message = Some(StatusMessage::error(format!("✗ Upload failed: {e}")));
+            }
+        }
+    }
+
+    /// 入力中のコメントのカーソル位置に挿入する
+    fn insert_into_comment(&mut self, text: &str) {
+        self.review.comment_editor.insert_text(text);
+        self.review
+            .comment_editor
+            .ensure_visible(editor::EDITOR_VISIBLE_HEIGHT);
+    }
+
+    /// アップロードできないときは URL が空の画像参照を挿入し、ブラウザでの添付を案内する
+    pub(super) fn insert_attach_placeholder(&mut self, path: &Path) {
+        self.insert_into_comment(&image_markdown(path, ""));
+        self.status_message = Some(StatusMessage::info(format!(
+            "Drag {} into a comment box on GitHub and paste the link into ()",
+            path.display()
+        )));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_helpers() {
+        assert!(is_image(Path::new("shot.PNG")));
+        assert!(!is_image(Path::new("notes.txt")));
+        assert_eq!(
+            image_markdown(Path::new("/tmp/screen shot.png"), "https://x/y.png"),
+            "![screen shot](https://x/y.png)"
+        );
+        assert_eq!(
+            parse_upload_url("uploading...\n  https://cdn.example/a.png \n"),
+            Some("https://cdn.example/a.png")
+        );
+        assert_eq!(parse_upload_url("error"), None);
+    }
+
+    #[test]
+    fn test_clipboard_dir_is_new_and_file_is_not_overwritten() {
+        let dir = create_clipboard_dir().unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+        let path = dir.join(CLIPBOARD_IMAGE_FILE);
+        write_new_file(&path, b"png").unwrap();
+        // 既にあるファイルには書かない
+        assert!(write_new_file(&path, b"other").is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), b"png");
+
+        remove_clipboard_image(&path);
+        assert!(!dir.exists());
+    }
+}
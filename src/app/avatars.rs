@@ -0,0 +1,142 @@
+//! Conversation のヘッダーに出す作者のアバター（画像プロトコルのある端末では画像、なければイニシャル）
+
+use super::*;
+use crate::github::media;
+use image::DynamicImage;
+use ratatui::{Frame, layout::Rect};
+use ratatui_image::StatefulImage;
+use ratatui_image::picker::ProtocolType;
+
+/// アバターの表示幅（セル）
+const AVATAR_WIDTH: u16 = 2;
+
+/// ログイン名のイニシャル（区切りがあれば各部分の頭文字、なければ先頭 2 文字。幅は常に 2）
+pub(super) fn avatar_initials(login: &str) -> String {
+    let name = login.trim_end_matches("[bot]");
+    let mut parts = name.split(['-', '_', '.']).filter(|p| !p.is_empty());
+    let initials: String = match (parts.next(), parts.next()) {
+        (Some(first), Some(second)) => first
+            .chars()
+            .take(1)
+            .chain(second.chars().take(1))
+            .collect(),
+        (Some(first), None) => first.chars().take(2).collect(),
+        _ => "?".to_string(),
+    };
+    format!("{:<2}", initials.to_ascii_uppercase())
+}
+
+impl App {
+    /// 画像のアバターを表示できるか（halfblocks では 2 セルに収まらないのでイニシャルにする）
+    fn avatar_images_supported(&self) -> bool {
+        self.picker
+            .as_ref()
+            .is_some_and(|picker| picker.protocol_type() != ProtocolType::Halfblocks)
+    }
+
+    /// Conversation の作者のうち未取得のアバターをバックグラウンドで取得する
+    pub(super) fn schedule_avatar_fetch(&mut self) {
+        if !crate::format::avatars()
+            || !self.avatar_images_supported()
+            || self.avatar_worker.is_some()
+        {
+            return;
+        }
+        let Ok(runtime) = Handle::try_current() else {
+            return;
+        };
+        let logins: Vec<String> = self
+            .conversation
+            .iter()
+            .map(|entry| entry.author.clone())
+            .filter(|login| self.avatar_requested.insert(login.clone()))
+            .collect();
+        if logins.is_empty() {
+            return;
+        }
+        self.avatar_worker = Some(runtime.spawn(async move {
+            let urls = logins
+                .iter()
+                .map(|login| media::avatar_url(login))
+                .collect();
+            let cache = media::download_media(urls).await;
+            (logins, cache)
+        }));
+    }
+
+    /// 取得が終わったアバターを回収する（失敗したものはイニシャルのまま）
+    pub(super) fn poll_avatar_worker(&mut self) {
+        if !self.avatar_worker.as_ref().is_some_and(|h| h.is_finished()) {
+            return;
+        }
+        let Some(handle) = self.avatar_worker.take() else {
+            return;
+        };
+        let Ok((logins, mut cache)) =
+            tokio::task::block_in_place(|| Handle::current().block_on(handle))
+        else {
+            return;
+        };
+        for login in logins {
+            if let Some(image) = cache.get(&media::avatar_url(&login)).cloned() {
+                self.avatar_images.insert(login, image);
+            }
+        }
+        // 取得中に作者が増えていたら続けて取りに行く
+        self.schedule_avatar_fetch();
+    }
+
+    /// 作者のアバターの描画プロトコル（初回に作って使い回す。画像がなければ None）
+    pub(super) fn avatar_protocol(&mut self, login: &str) -> Option<&mut StatefulProtocol> {
+        if !self.avatar_protocols.contains_key(login) {
+            let image: &DynamicImage = self.avatar_images.get(login)?;
+            let protocol = self.picker.as_ref()?.new_resize_protocol(image.clone());
+            self.avatar_protocols.insert(login.to_string(), protocol);
+        }
+        self.avatar_protocols.get_mut(login)
+    }
+
+    /// 画面内のヘッダーのイニシャルにアバター画像を重ねる（area は枠を含む Conversation ペイン）
+    pub(super) fn render_conversation_avatars(&mut self, frame: &mut Frame, area: Rect) {
+        if self.avatar_images.is_empty() {
+            return;
+        }
+        let inner_width = area.width.saturating_sub(2);
+        let (scroll, view_height) = (self.conversation_scroll, self.conversation_view_height);
+        let headers: Vec<(String, u16, u16)> = self
+            .conversation
+            .iter()
+            .zip(&self.conversation_avatar_cols)
+            .zip(&self.conversation_visual_offsets)
+            .filter_map(|((entry, col), &row)| Some((entry.author.clone(), (*col)?, row)))
+            .filter(|&(_, col, row)| {
+                col + AVATAR_WIDTH <= inner_width && row >= scroll && row < scroll + view_height
+            })
+            .collect();
+        for (login, col, row) in headers {
+            let rect = Rect {
+                x: area.x + 1 + col,
+                y: area.y + 1 + (row - scroll),
+                width: AVATAR_WIDTH,
+                height: 1,
+            };
+            if let Some(protocol) = self.avatar_protocol(&login) {
+                frame.render_stateful_widget(StatefulImage::default(), rect, protocol);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_avatar_initials() {
+        assert_eq!(avatar_initials("kawarimidoll"), "KA");
+        assert_eq!(avatar_initials("octo-cat"), "OC");
+        assert_eq!(avatar_initials("dependabot[bot]"), "DE");
+        assert_eq!(avatar_initials("x"), "X ");
+        assert_eq!(avatar_initials(""), "? ");
+    }
+}
@@ -0,0 +1,294 @@
+//! base ブランチの取り込み（GitHub の "Update branch"）・ローカルへのチェックアウト・自動マージ・PR の close / reopen
+
+use super::*;
+
+/// ブランチ更新後に新しい head コミットを待つ間隔
+const UPDATE_BRANCH_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+/// 新しい head コミットを待つ最大回数（超えたら手動リロードを案内）
+const UPDATE_BRANCH_POLL_ATTEMPTS: usize = 15;
+
+impl App {
+    /// head ブランチが base より遅れているかをセットする
+    pub fn set_behind_base(&mut self, behind: bool) {
+        self.behind_base = behind;
+    }
+
+    /// fork からの PR かどうかと、メンテナーによる push の可否をセットする
+    pub fn set_fork(&mut self, fork_owner: Option<String>, maintainer_can_modify: bool) {
+        self.fork_owner = fork_owner;
+        self.maintainer_can_modify = maintainer_can_modify;
+    }
+
+    /// head ブランチの表示名（fork なら "user:branch"）
+    pub(super) fn head_label(&self) -> String {
+        match &self.fork_owner {
+            Some(owner) => format!("{owner}:{}", self.pr_head_branch),
+            None => self.pr_head_branch.clone(),
+        }
+    }
+
+    /// fork の PR でメンテナーが head ブランチに push できないか（Update branch・suggestion の適用が不可）
+    pub(super) fn fork_push_blocked(&self) -> bool {
+        self.fork_owner.is_some() && !self.maintainer_can_modify
+    }
+
+    /// U: ブランチ更新の方式選択ダイアログを開く（base より遅れている Open な PR のみ）
+    pub(super) fn open_update_branch(&mut self) {
+        if self.pr_state != "Open" {
+            self.status_message = Some(StatusMessage::error("✗ PR is not open"));
+        } else if !self.behind_base {
+            self.status_message = Some(StatusMessage::info("Branch is up to date with base"));
+        } else if self.fork_push_blocked() {
+            self.status_message = Some(StatusMessage::error(
+                "✗ Fork does not allow edits from maintainers. Only the author can update the branch.",
+            ));
+        } else if self.pr_node_id.is_empty() {
+            self.status_message = Some(StatusMessage::error("✗ PR node ID not available"));
+        } else if !self.review.pending_comments.is_empty() {
+            // 更新後にリロードするため、未送信コメントがあると失われる
+            self.status_message = Some(StatusMessage::error(
+                "✗ Cannot update branch with pending comments. Submit or discard first.",
+            ));
+        } else {
+            self.mode = AppMode::UpdateBranch;
+        }
+    }
+
+    /// base を取り込み、新しい head コミットが現れるのをバックグラウンドで待つ（draw 後に呼ばれる。結果は AsyncData::BranchUpdated で届く）
+    pub(super) fn execute_update_branch(&mut self, method: UpdateMethod) {
+        let Some(client) = self.client.clone() else {
+            self.status_message = Some(StatusMessage::error("✗ No API client available"));
+            return;
+        };
+        let Some((owner, repo)) = self
+            .parse_repo()
+            .map(|(owner, repo)| (owner.to_string(), repo.to_string()))
+        else {
+            self.status_message = Some(StatusMessage::error("✗ Invalid repo format"));
+            return;
+        };
+        let Some(old_head) = self.commits.last().map(|c| c.sha.clone()) else {
+            return;
+        };
+
+        if let Err(e) = crate::github::pr::update_branch(&self.pr_node_id, &old_head, method) {
+            self.status_message = Some(StatusMessage::error(format!("✗ Failed: {}", e)));
+            return;
+        }
+
+        // 更新は GitHub 側で非同期に行われるため、head が変わるまでポーリングする
+        let pr_number = self.pr_number;
+        let spawned = self.spawn_task(async move {
+            for _ in 0..UPDATE_BRANCH_POLL_ATTEMPTS {
+                tokio::time::sleep(UPDATE_BRANCH_POLL_INTERVAL).await;
+                if let Ok(pr) = crate::github::pr::fetch_pr(&client, &owner, &repo, pr_number).await
+                    && pr.head.sha != old_head
+                {
+                    return crate::AsyncData::BranchUpdated {
+                        old_head,
+                        landed: true,
+                    };
+                }
+            }
+            crate::AsyncData::BranchUpdated {
+                old_head,
+                landed: false,
+            }
+        });
+        self.status_message = Some(StatusMessage::info(if spawned {
+            "Branch update requested — waiting for the new head commit..."
+        } else {
+            "✓ Branch update requested — press R to reload when it lands"
+        }));
+    }
+
+    /// ブランチ更新の結果を反映する（新しい head が現れたらリロード。待つ間に別の理由でリロード済みなら何もしない）
+    pub(super) fn apply_branch_updated(&mut self, old_head: &str, landed: bool) {
+        if self.commits.last().map(|c| c.sha.as_str()) != Some(old_head) {
+            return;
+        }
+        if landed {
+            self.behind_base = false;
+            self.needs_reload = true;
+        } else {
+            self.status_message = Some(StatusMessage::info(
+                "✓ Branch update requested — press R to reload when it lands",
+            ));
+        }
+    }
+
+    /// fork の PR で使うローカルブランチ名（fork の main などで同名のブランチを上書きしないよう owner で名前空間を分ける）
+    fn checkout_local_branch(&self) -> Option<String> {
+        self.fork_owner
+            .as_ref()
+            .map(|owner| format!("{owner}/{}", self.pr_head_branch))
+    }
+
+    /// チェックアウト先のローカルブランチ名
+    pub(super) fn checkout_branch_label(&self) -> String {
+        self.checkout_local_branch()
+            .unwrap_or_else(|| self.pr_head_branch.clone())
+    }
+
+    /// O: チェックアウトの確認ダイアログを開く（作業ツリーに未コミットの変更があれば開かない）
+    pub(super) fn open_checkout_confirm(&mut self) {
+        self.open_checkout_confirm_with(uncommitted_file_count());
+    }
+
+    /// 未コミットの変更があるファイル数を受け取って確認ダイアログを開く
+    pub(super) fn open_checkout_confirm_with(&mut self, uncommitted: usize) {
+        if uncommitted > 0 {
+            self.status_message = Some(StatusMessage::error(format!(
+                "✗ {uncommitted} uncommitted file(s) in the working tree. Commit or stash first."
+            )));
+        } else {
+            self.mode = AppMode::CheckoutConfirm;
+        }
+    }
+
+    /// gh pr checkout で PR のブランチをローカルにチェックアウトする（draw 後に呼ばれる）
+    /// fork の remote・push 先の設定は gh に任せる
+    pub(super) fn execute_checkout(&mut self) {
+        let number = self.pr_number.to_string();
+        let mut args = vec![
+            "pr",
+            "checkout",
+            number.as_str(),
+            "--repo",
+            self.repo.as_str(),
+        ];
+        let local_branch = self.checkout_local_branch();
+        if let Some(branch) = &local_branch {
+            args.extend(["--branch", branch.as_str()]);
+        }
+        self.status_message = Some(match crate::github::metrics::run_gh("pr checkout", &args) {
+            Ok(output) if output.status.success() => {
+                let branch = self.checkout_branch_label();
+                if self.fork_push_blocked() {
+                    StatusMessage::info(format!(
+                        "✓ Checked out {branch} (read-only: fork without maintainer edits)"
+                    ))
+                } else {
+                    StatusMessage::info(format!("✓ Checked out {branch}"))
+                }
+            }
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                StatusMessage::error(format!(
+                    "✗ Checkout failed: {}",
+                    stderr.lines().next().unwrap_or("").trim()
+                ))
+            }
+            Err(e) => StatusMessage::error(format!("✗ Failed: {}", e)),
+        });
+    }
+
+    /// 自動マージ状態の取得を予約する（Open な PR のみ、初回描画後に取得）
+    pub fn schedule_auto_merge_check(&mut self) {
+        self.needs_auto_merge_check = self.pr_state == "Open" && !self.pr_node_id.is_empty();
+    }
+
+    /// 現在の自動マージ設定をバックグラウンドで取得する（draw 後に呼ばれる。結果は AsyncData::AutoMerge で届く）
+    pub(super) fn execute_auto_merge_check(&mut self) {
+        let pr_node_id = self.pr_node_id.clone();
+        self.spawn_task(async move {
+            crate::AsyncData::AutoMerge(
+                Self::run_blocking(move || crate::github::pr::fetch_auto_merge(&pr_node_id)).await,
+            )
+        });
+    }
+
+    /// W: 自動マージの設定ダイアログを開く
+    pub(super) fn open_auto_merge(&mut self) {
+        if self.pr_state != "Open" {
+            self.status_message = Some(StatusMessage::error("✗ PR is not open"));
+        } else if self.pr_node_id.is_empty() {
+            self.status_message = Some(StatusMessage::error("✗ PR node ID not available"));
+        } else {
+            self.mode = AppMode::AutoMerge;
+        }
+    }
+
+    /// 自動マージを有効化・解除する（draw 後に呼ばれる）
+    pub(super) fn execute_auto_merge(&mut self, action: AutoMergeAction) {
+        let result = match action {
+            AutoMergeAction::Enable(method) => {
+                crate::github::pr::enable_auto_merge(&self.pr_node_id, method)
+            }
+            AutoMergeAction::Disable => crate::github::pr::disable_auto_merge(&self.pr_node_id),
+        };
+        self.status_message = Some(match (result, action) {
+            (Ok(()), AutoMergeAction::Enable(method)) => {
+                self.auto_merge = Some(method);
+                StatusMessage::info(format!("✓ Auto-merge enabled ({})", method.label()))
+            }
+            (Ok(()), AutoMergeAction::Disable) => {
+                self.auto_merge = None;
+                StatusMessage::info("✓ Auto-merge disabled")
+            }
+            (Err(e), _) => StatusMessage::error(format!("✗ Failed: {}", e)),
+        });
+    }
+
+    /// 今の状態から行う変更（Open なら閉じる、Closed なら開き直す）
+    pub(super) fn pr_state_change(&self) -> PrStateChange {
+        if self.pr_state == "Open" {
+            PrStateChange::Close
+        } else {
+            PrStateChange::Reopen
+        }
+    }
+
+    /// Z: PR を閉じる・開き直す確認ダイアログを開く（マージ済みの PR は対象外）
+    pub(super) fn open_pr_state_change(&mut self) {
+        if self.pr_state == "Merged" {
+            self.status_message = Some(StatusMessage::error("✗ PR is already merged"));
+        } else if self.pr_state.is_empty() {
+            self.status_message = Some(StatusMessage::error("✗ PR state not available"));
+        } else if self.pr_node_id.is_empty() {
+            self.status_message = Some(StatusMessage::error("✗ PR node ID not available"));
+        } else {
+            self.mode = AppMode::PrStateChange;
+        }
+    }
+
+    /// PR を閉じる・開き直す（draw 後に呼ばれる。権限がなければ GitHub のエラーを表示）
+    pub(super) fn execute_pr_state_change(&mut self, change: PrStateChange) {
+        let closed = change == PrStateChange::Close;
+        if let Err(e) = crate::github::pr::set_pr_closed(&self.pr_node_id, closed) {
+            self.status_message = Some(StatusMessage::error(format!("✗ Failed: {}", e)));
+            return;
+        }
+        if closed {
+            // 閉じると GitHub 側で自動マージも解除される
+            self.pr_state = "Closed".to_string();
+            self.auto_merge = None;
+            self.status_message = Some(StatusMessage::info(format!(
+                "✓ Closed PR #{}",
+                self.pr_number
+            )));
+        } else {
+            self.pr_state = "Open".to_string();
+            self.schedule_auto_merge_check();
+            self.status_message = Some(StatusMessage::info(format!(
+                "✓ Reopened PR #{}",
+                self.pr_number
+            )));
+        }
+    }
+}
+
+/// 作業ツリーで未コミットの変更があるファイル数（git が使えなければ 0。その場合は gh のエラーに任せる）
+fn uncommitted_file_count() -> usize {
+    crate::exec::command("git")
+        .and_then(|mut cmd| {
+            cmd.args(["status", "--porcelain", "--untracked-files=no"])
+                .stdin(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .output()
+                .ok()
+        })
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).lines().count())
+        .unwrap_or(0)
+}
@@ -0,0 +1,211 @@
+//! レビューチェックリスト（PR ごとに保存、レビュー本文への追記に対応。PR 本文のタスクリストも取り込む）
+
+use super::*;
+use crate::github::cache::{self, ChecklistCache};
+
+/// --checklist 未指定時のデフォルト項目
+pub const DEFAULT_CHECKLIST: [&str; 4] = ["Security", "Tests", "Docs", "Migrations"];
+
+/// チェックリストの1項目
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecklistItem {
+    pub label: String,
+    pub checked: bool,
+    /// PR 本文のタスクリストから取り込んだ項目か
+    pub from_pr: bool,
+}
+
+/// PR 本文のタスクリスト（"- [ ] ..." / "* [x] ..."）の項目。コードブロック内は除く
+pub(super) fn pr_task_labels(body: &str) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    let mut in_code = false;
+    for line in body.lines() {
+        let line = line.trim_start();
+        if line.starts_with("```") || line.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        let Some(item) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|bullet| line.strip_prefix(bullet))
+        else {
+            continue;
+        };
+        let Some(label) = ["[ ] ", "[x] ", "[X] "]
+            .iter()
+            .find_map(|checkbox| item.strip_prefix(checkbox))
+        else {
+            continue;
+        };
+        let label = label.trim();
+        if !label.is_empty() && !labels.iter().any(|l| l == label) {
+            labels.push(label.to_string());
+        }
+    }
+    labels
+}
+
+impl App {
+    /// チェックリスト項目（設定の項目 + PR 本文のタスクリスト）と保存済み状態をセットする
+    pub fn set_checklist(&mut self, labels: Vec<String>, saved: Option<ChecklistCache>) {
+        let saved = saved.unwrap_or_default();
+        self.checklist = labels
+            .into_iter()
+            .map(|label| ChecklistItem {
+                checked: saved.checked.get(&label).copied().unwrap_or(false),
+                label,
+                from_pr: false,
+            })
+            .collect();
+        self.import_pr_tasks(&saved.checked);
+        self.checklist_append = saved.append_to_review;
+        self.checklist_cursor = 0;
+    }
+
+    /// PR 本文が更新されたらタスクリストの項目を取り込み直す（チェック状態は引き継ぐ）
+    pub(super) fn refresh_pr_tasks(&mut self) {
+        let checked: HashMap<String, bool> = self
+            .checklist
+            .iter()
+            .map(|item| (item.label.clone(), item.checked))
+            .collect();
+        self.checklist.retain(|item| !item.from_pr);
+        self.import_pr_tasks(&checked);
+        self.checklist_cursor = self
+            .checklist_cursor
+            .min(self.checklist.len().saturating_sub(1));
+    }
+
+    /// PR 本文のタスクリストを設定の項目の後に加える（同じラベルの項目は加えない）
+    fn import_pr_tasks(&mut self, checked: &HashMap<String, bool>) {
+        for label in pr_task_labels(&self.pr_body) {
+            if self.checklist.iter().any(|item| item.label == label) {
+                continue;
+            }
+            self.checklist.push(ChecklistItem {
+                checked: checked.get(&label).copied().unwrap_or(false),
+                label,
+                from_pr: true,
+            });
+        }
+    }
+
+    /// チェックリストオーバーレイを開く
+    pub(super) fn open_checklist(&mut self) {
+        if self.checklist.is_empty() {
+            self.status_message = Some(StatusMessage::error("✗ No checklist items configured"));
+            return;
+        }
+        self.checklist_cursor = self.checklist_cursor.min(self.checklist.len() - 1);
+        self.mode = AppMode::Checklist;
+    }
+
+    /// カーソル位置の項目をトグルして保存する（すべてチェックしたらレビュー本文への追記を勧める）
+    pub(super) fn toggle_checklist_item(&mut self) {
+        if let Some(item) = self.checklist.get_mut(self.checklist_cursor) {
+            item.checked = !item.checked;
+            let checked = item.checked;
+            self.save_checklist();
+            if checked
+                && !self.checklist_append
+                && self.checklist_checked_count() == self.checklist.len()
+            {
+                self.status_message = Some(StatusMessage::info(
+                    "✓ Checklist complete — a: add it to the review body",
+                ));
+            }
+        }
+    }
+
+    /// レビュー本文への追記をトグルして保存する
+    pub(super) fn toggle_checklist_append(&mut self) {
+        self.checklist_append = !self.checklist_append;
+        self.save_checklist();
+    }
+
+    /// チェック済み項目数を返す
+    pub(super) fn checklist_checked_count(&self) -> usize {
+        self.checklist.iter().filter(|item| item.checked).count()
+    }
+
+    /// チェックリストを Markdown のタスクリストとして返す
+    pub(super) fn checklist_markdown(&self) -> String {
+        self.checklist
+            .iter()
+            .map(|item| {
+                format!(
+                    "- [{}] {}",
+                    if item.checked { "x" } else { " " },
+                    item.label
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// レビュー送信時の本文（追記が有効ならチェックリストを末尾に付与）
+    pub(super) fn review_body_text(&self) -> String {
+        let body = self.review.review_body_editor.text();
+        let outline = self.pending_outline_markdown();
+        let checklist = if !self.checklist_append || self.checklist.is_empty() {
+            String::new()
+        } else {
+            self.checklist_markdown()
+        };
+        // 本文 → 保留コメントの目次 → チェックリストの順に、空でないものを並べる
+        if outline.is_empty() && checklist.is_empty() {
+            return body;
+        }
+        [body, outline, checklist]
+            .into_iter()
+            .filter(|part| !part.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// チェックリスト状態を PR ごとのファイルに保存する
+    fn save_checklist(&mut self) {
+        let Some((owner, repo)) = self.parse_repo() else {
+            return;
+        };
+        let data = ChecklistCache {
+            checked: self
+                .checklist
+                .iter()
+                .map(|item| (item.label.clone(), item.checked))
+                .collect(),
+            append_to_review: self.checklist_append,
+        };
+        if let Err(e) =
+            cache::write_pr_json(owner, repo, self.pr_number, cache::CHECKLIST_SUFFIX, &data)
+        {
+            self.status_message = Some(StatusMessage::error(format!(
+                "✗ Failed to save checklist: {e}"
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pr_task_labels() {
+        let body = "\
+## Checklist
+- [x] Added tests
+  * [ ] Updated docs
+- [ ]  
+- plain item
+```
+- [ ] not a task
+```
++ [X] Added tests
+";
+        assert_eq!(pr_task_labels(body), vec!["Added tests", "Updated docs"]);
+    }
+}
@@ -0,0 +1,104 @@
+//! C: PR の head コミットの CI（check run / commit status）一覧と、ヘッダーのバッジ
+
+use super::*;
+use crate::github::checks::{self, Check, CheckState};
+
+impl App {
+    /// head コミットのチェックの取得を予約する（API クライアントがなければ取得しない）
+    pub fn schedule_checks_fetch(&mut self) {
+        self.needs_checks_fetch = self.client.is_some() && !self.commits.is_empty();
+    }
+
+    /// head コミットのチェックをバックグラウンドで取得する（draw 後に呼ばれる。結果は AsyncData::Checks で届く）
+    pub(super) fn execute_checks_fetch(&mut self) {
+        let (Some(client), Some((owner, repo))) = (self.client.clone(), self.parse_repo()) else {
+            return;
+        };
+        let Some(sha) = self.commits.last().map(|c| c.sha.clone()) else {
+            return;
+        };
+        let (owner, repo) = (owner.to_string(), repo.to_string());
+        self.checks_loading = self.spawn_task(async move {
+            let result = checks::fetch_checks(&client, &owner, &repo, &sha)
+                .await
+                .map_err(|e| e.to_string());
+            crate::AsyncData::Checks { sha, result }
+        });
+    }
+
+    /// 取得したチェックを反映する（取得中に head が変わっていれば捨てて取り直す。失敗したら前回の結果のまま）
+    pub(super) fn apply_checks(&mut self, sha: &str, result: Result<Vec<Check>, String>) {
+        self.checks_loading = false;
+        if self.commits.last().is_none_or(|c| c.sha != sha) {
+            self.schedule_checks_fetch();
+            return;
+        }
+        match result {
+            Ok(checks) => {
+                self.checks_cursor = self.checks_cursor.min(checks.len().saturating_sub(1));
+                self.checks = Some(checks);
+            }
+            Err(e) => {
+                self.status_message = Some(StatusMessage::error(format!(
+                    "✗ Failed to load checks: {e}"
+                )));
+            }
+        }
+    }
+
+    /// ヘッダーの CI バッジと色（初回の取得中は "…"、未取得・チェックなしなら None）
+    pub(super) fn checks_badge(&self) -> Option<(String, Color)> {
+        let Some(checks) = self.checks.as_deref() else {
+            return self
+                .checks_loading
+                .then(|| (" CI … ".to_string(), Color::DarkGray));
+        };
+        let color = match checks::overall(checks)? {
+            CheckState::Failure => Color::Red,
+            CheckState::Pending => Color::Yellow,
+            CheckState::Success => Color::Green,
+            CheckState::Neutral => Color::DarkGray,
+        };
+        Some((checks::badge(checks), color))
+    }
+
+    /// C: チェック一覧を開く（未取得なら取得してから表示）
+    pub(super) fn open_checks(&mut self) {
+        if self.client.is_none() {
+            self.status_message = Some(StatusMessage::error("✗ Checks need a GitHub connection"));
+            return;
+        }
+        if self.checks.is_none() {
+            self.schedule_checks_fetch();
+        }
+        self.checks_cursor = 0;
+        self.mode = AppMode::Checks;
+    }
+
+    /// 選択中のチェックの詳細ページをブラウザで開く
+    pub(super) fn open_selected_check(&mut self) {
+        let Some(check) = self
+            .checks
+            .as_ref()
+            .and_then(|checks| checks.get(self.checks_cursor))
+        else {
+            return;
+        };
+        let Some(url) = check.url.clone() else {
+            self.status_message = Some(StatusMessage::error("✗ No details URL for this check"));
+            return;
+        };
+        let name = check.name.clone();
+        self.open_in_browser(&url, &name);
+    }
+
+    /// 一覧の行数
+    pub(super) fn checks_len(&self) -> usize {
+        self.checks.as_ref().map_or(0, Vec::len)
+    }
+
+    /// 一覧に表示するチェック（未取得なら空）
+    pub(super) fn checks_list(&self) -> &[Check] {
+        self.checks.as_deref().unwrap_or_default()
+    }
+}
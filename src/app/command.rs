@@ -0,0 +1,292 @@
+//! `!` キーで実行する外部コマンド（テスト等）の実行と結果保持
+
+use super::*;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Stdio};
+use std::sync::mpsc::{self as std_mpsc, TryRecvError};
+
+/// 外部コマンドの実行結果
+#[derive(Debug, Clone)]
+pub struct CommandResult {
+    /// 実行したコマンド文字列
+    pub command: String,
+    /// stdout + stderr を出力順に連結した出力
+    pub output: String,
+    /// 終了コード（シグナル終了時・停止時は None）
+    pub exit_code: Option<i32>,
+    /// 実行中か（出力は届いた分だけ入っている）
+    pub running: bool,
+    /// 実行前の注意（ローカルの HEAD が PR の head と違う等）
+    pub warning: Option<String>,
+}
+
+impl CommandResult {
+    /// 正常終了したか
+    pub fn success(&self) -> bool {
+        !self.running && self.exit_code == Some(0)
+    }
+
+    /// ヘッダーバッジ用の短い表示
+    pub fn badge(&self) -> String {
+        if self.running {
+            return " [! …]".to_string();
+        }
+        match self.exit_code {
+            Some(0) => " [! ✓]".to_string(),
+            Some(code) => format!(" [! ✗ {code}]"),
+            None => " [! ✗ signal]".to_string(),
+        }
+    }
+}
+
+/// バックグラウンドで実行中のコマンド（出力は読み取りスレッドからチャネルで届く）
+pub(super) struct RunningCommand {
+    child: Child,
+    output_rx: std_mpsc::Receiver<String>,
+    /// 終了を回収したか（回収後は PID が再利用されうるのでシグナルを送らない）
+    reaped: bool,
+}
+
+impl RunningCommand {
+    /// 届いた出力を追記し、出力を読み終えてプロセスが終了していれば終了コードを返す（外側の None は実行中）
+    pub(super) fn poll(&mut self, output: &mut String) -> std::io::Result<Option<Option<i32>>> {
+        loop {
+            match self.output_rx.try_recv() {
+                Ok(chunk) => output.push_str(&chunk),
+                Err(TryRecvError::Empty) => return Ok(None),
+                Err(TryRecvError::Disconnected) => break,
+            }
+        }
+        let status = self.child.wait()?;
+        self.reaped = true;
+        Ok(Some(status.code()))
+    }
+}
+
+impl Drop for RunningCommand {
+    /// 再実行や終了で手放したら止める（sh が起動した子プロセスもプロセスグループごと止める）
+    fn drop(&mut self) {
+        if self.reaped {
+            return;
+        }
+        #[cfg(unix)]
+        {
+            use nix::sys::signal::{Signal, killpg};
+            use nix::unistd::Pid;
+            let _ = killpg(Pid::from_raw(self.child.id() as i32), Signal::SIGKILL);
+        }
+        #[cfg(not(unix))]
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// `sh -c` でコマンドを起動し、stderr を stdout にまとめて 1 行ずつ読み取る（stdin は閉じる）
+pub(super) fn spawn_shell_command(command: &str) -> std::io::Result<RunningCommand> {
+    let mut cmd = crate::exec::require("sh")?;
+    // 1 本のパイプにまとめて、端末で見るのと同じ順に出力を並べる
+    cmd.args(["-c", &format!("exec 2>&1\n{command}")])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    // 止めるときに子孫もまとめて止められるよう、sh を新しいプロセスグループのリーダーにする
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    let mut child = cmd.spawn()?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| std::io::Error::other("failed to capture stdout"))?;
+    let (tx, output_rx) = std_mpsc::channel();
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(stdout);
+        let mut line = Vec::new();
+        while let Ok(n) = reader.read_until(b'\n', &mut line) {
+            if n == 0
+                || tx
+                    .send(String::from_utf8_lossy(&line).into_owned())
+                    .is_err()
+            {
+                break;
+            }
+            line.clear();
+        }
+    });
+    Ok(RunningCommand {
+        child,
+        output_rx,
+        reaped: false,
+    })
+}
+
+/// ローカルの HEAD（取得できなければ None）
+fn local_head_sha() -> Option<String> {
+    let output = crate::exec::command("git")?
+        .args(["rev-parse", "HEAD"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// ローカルの HEAD が PR の head と違えば注意文を返す
+fn head_mismatch_warning(local: &str, pr_head: &str) -> Option<String> {
+    if local.is_empty() || pr_head.is_empty() || local == pr_head {
+        return None;
+    }
+    Some(format!(
+        "⚠ Local HEAD {} is not the PR head {} (results may not reflect this PR)",
+        crate::format::short_sha(local),
+        crate::format::short_sha(pr_head)
+    ))
+}
+
+impl App {
+    /// 実行するコマンドを設定する（--exec 指定時のみ）
+    pub fn set_run_command(&mut self, command: Option<String>) {
+        self.run_command = command;
+    }
+
+    /// 設定済みコマンドをバックグラウンドで起動して結果オーバーレイを開く（実行中なら止めてから起動し直す）
+    pub(super) fn execute_run_command(&mut self) {
+        let Some(command) = self.run_command.clone() else {
+            return;
+        };
+        self.running_command = None;
+        let warning =
+            local_head_sha().and_then(|local| head_mismatch_warning(&local, &self.head_sha));
+        let pending = crate::exec::pending_count();
+        match spawn_shell_command(&command) {
+            Ok(running) => {
+                self.running_command = Some(running);
+                self.status_message = Some(match &warning {
+                    Some(warning) => StatusMessage::error(warning.clone()),
+                    None => StatusMessage::info(format!("Running {command}...")),
+                });
+                self.command_result = Some(CommandResult {
+                    command,
+                    output: String::new(),
+                    exit_code: None,
+                    running: true,
+                    warning,
+                });
+                self.command_output_scroll = 0;
+                self.command_output_follow = true;
+                self.mode = AppMode::CommandOutput;
+            }
+            Err(e) => {
+                self.status_message = Some(StatusMessage::error(format!("✗ Failed to run: {e}")));
+                self.retry_after_permission(pending, PermissionRetry::RunCommand);
+            }
+        }
+    }
+
+    /// 実行中のコマンドの出力を取り込み、終了していれば結果を確定する
+    pub(super) fn poll_run_command(&mut self) {
+        let (Some(running), Some(result)) = (&mut self.running_command, &mut self.command_result)
+        else {
+            return;
+        };
+        let exit_code = match running.poll(&mut result.output) {
+            Ok(None) => return,
+            Ok(Some(code)) => code,
+            Err(_) => None,
+        };
+        self.running_command = None;
+        result.running = false;
+        result.exit_code = exit_code;
+        self.status_message = Some(if result.success() {
+            StatusMessage::info(format!("✓ {}", result.command))
+        } else {
+            StatusMessage::error(format!("✗ {}", result.command))
+        });
+    }
+
+    /// 実行中のコマンドを止める
+    pub(super) fn stop_run_command(&mut self) {
+        let Some(running) = self.running_command.take() else {
+            return;
+        };
+        drop(running);
+        if let Some(result) = &mut self.command_result {
+            result.running = false;
+            result.exit_code = None;
+            self.status_message = Some(StatusMessage::error(format!(
+                "✗ Stopped: {}",
+                result.command
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    /// 終了まで出力を集める
+    fn run_to_end(command: &str) -> (String, Option<i32>) {
+        let mut running = spawn_shell_command(command).unwrap();
+        let mut output = String::new();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            if let Some(code) = running.poll(&mut output).unwrap() {
+                return (output, code);
+            }
+            assert!(Instant::now() < deadline, "command did not finish");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_run_shell_command_captures_stdout_and_stderr() {
+        let (output, code) = run_to_end("echo out; echo err >&2; echo out2");
+        assert_eq!(code, Some(0));
+        assert_eq!(output, "out\nerr\nout2\n");
+    }
+
+    #[test]
+    fn test_run_shell_command_exit_code() {
+        let (_, code) = run_to_end("exit 3");
+        assert_eq!(code, Some(3));
+    }
+
+    #[test]
+    fn test_badge() {
+        let mut result = CommandResult {
+            command: "make test".to_string(),
+            output: String::new(),
+            exit_code: None,
+            running: true,
+            warning: None,
+        };
+        assert!(!result.success());
+        assert_eq!(result.badge(), " [! …]");
+        result.running = false;
+        assert_eq!(result.badge(), " [! ✗ signal]");
+        result.exit_code = Some(3);
+        assert_eq!(result.badge(), " [! ✗ 3]");
+        result.exit_code = Some(0);
+        assert!(result.success());
+        assert_eq!(result.badge(), " [! ✓]");
+    }
+
+    #[test]
+    fn test_head_mismatch_warning() {
+        assert_eq!(head_mismatch_warning("abc1234def", "abc1234def"), None);
+        assert_eq!(head_mismatch_warning("abc1234def", ""), None);
+        assert_eq!(
+            head_mismatch_warning("abc1234def", "9876543aaa").as_deref(),
+            Some(
+                "⚠ Local HEAD abc1234 is not the PR head 9876543 (results may not reflect this PR)"
+            )
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! CommentView の e / d: 送信済みの自分のレビューコメントの編集・削除
+
+use super::*;
+
+impl App {
+    /// スレッドを開いたときの選択（自分のコメントがあれば最新のもの、なければ最新のコメント）
+    pub(super) fn select_default_viewing_comment(&mut self) {
+        let comments = &self.review.viewing_comments;
+        self.review.viewing_comment_cursor = comments
+            .iter()
+            .rposition(|c| !self.current_user.is_empty() && c.user.login == self.current_user)
+            .unwrap_or(comments.len().saturating_sub(1));
+    }
+
+    /// J / K: 選択するコメントを動かし、その見出しまでスクロールする
+    pub(super) fn move_viewing_comment_cursor(&mut self, delta: isize) {
+        let last = self.review.viewing_comments.len().saturating_sub(1);
+        let cursor = self
+            .review
+            .viewing_comment_cursor
+            .saturating_add_signed(delta)
+            .min(last);
+        self.review.viewing_comment_cursor = cursor;
+        if let Some(&row) = self.review.viewing_comment_rows.get(cursor) {
+            self.review.viewing_comment_scroll = row.min(self.review.comment_view_max_scroll);
+        }
+    }
+
+    /// 選択中のコメント
+    pub(super) fn selected_viewing_comment(&self) -> Option<&ReviewComment> {
+        self.review
+            .viewing_comments
+            .get(self.review.viewing_comment_cursor)
+    }
+
+    /// 選択中のコメントが自分のものならそれを返す（編集・削除の対象）
+    pub(super) fn own_viewing_comment(&self) -> Option<&ReviewComment> {
+        if self.current_user.is_empty() {
+            return None;
+        }
+        self.selected_viewing_comment()
+            .filter(|c| c.user.login == self.current_user)
+    }
+
+    /// 選択中のコメントを編集・削除できないときのメッセージ
+    fn not_own_comment_message(&self) -> &'static str {
+        let any_own = !self.current_user.is_empty()
+            && self
+                .review
+                .viewing_comments
+                .iter()
+                .any(|c| c.user.login == self.current_user);
+        if any_own {
+            "✗ Selected comment is not yours (J/K to select)"
+        } else {
+            "✗ No comment of yours in this thread"
+        }
+    }
+
+    /// e: 選択中の自分のコメントの本文をエディタに入れて編集を始める
+    pub(super) fn start_comment_edit(&mut self) {
+        let Some((id, body)) = self.own_viewing_comment().map(|c| (c.id, c.body.clone())) else {
+            self.status_message = Some(StatusMessage::error(self.not_own_comment_message()));
+            return;
+        };
+        self.review.editing_comment_id = Some(id);
+        self.review.comment_editor.clear();
+        self.review.comment_editor.insert_text(&body);
+        self.mode = AppMode::CommentEditInput;
+    }
+
+    /// d: 選択中の自分のコメントの削除確認を開く（返信の付いたスレッドの先頭は消さない）
+    pub(super) fn request_comment_delete(&mut self) {
+        let Some(comment) = self.own_viewing_comment() else {
+            self.status_message = Some(StatusMessage::error(self.not_own_comment_message()));
+            return;
+        };
+        let id = comment.id;
+        let has_replies = comment.in_reply_to_id.is_none()
+            && self
+                .review
+                .review_comments
+                .iter()
+                .any(|c| c.in_reply_to_id == Some(id));
+        if has_replies {
+            self.status_message = Some(StatusMessage::error(
+                "✗ Cannot delete the first comment of a thread with replies",
+            ));
+            return;
+        }
+        self.review.editing_comment_id = Some(id);
+        self.mode = AppMode::CommentDeleteConfirm;
+    }
+
+    /// 編集・削除の対象のコメント（確認ダイアログの表示用）
+    pub(super) fn editing_comment(&self) -> Option<&ReviewComment> {
+        let id = self.review.editing_comment_id?;
+        self.review.review_comments.iter().find(|c| c.id == id)
+    }
+
+    /// 編集した本文を送信し、表示中のコメントと Conversation を差し替える（draw 後に呼ばれる）
+    pub(super) fn submit_comment_edit(&mut self) {
+        let Some(id) = self.review.editing_comment_id else {
+            return;
+        };
+        let body = self.review.comment_editor.text();
+        let Some(client) = &self.client else {
+            self.status_message = Some(StatusMessage::error("✗ No API client available"));
+            return;
+        };
+        let Some((owner, repo)) = self.parse_repo() else {
+            self.status_message = Some(StatusMessage::error("✗ Invalid repo format"));
+            return;
+        };
+        let result = tokio::task::block_in_place(|| {
+            Handle::current().block_on(comments::update_review_comment(
+                client, owner, repo, id, &body,
+            ))
+        });
+        match result {
+            Ok(updated) => {
+                self.replace_review_comment_body(id, &updated.body);
+                self.review.editing_comment_id = None;
+                self.review.comment_editor.clear();
+                self.status_message = Some(StatusMessage::info("✓ Comment updated"));
+            }
+            Err(e) => {
+                // 本文はエディタに残しているので e で開き直さずそのまま再編集できる
+                self.mode = AppMode::CommentEditInput;
+                self.status_message = Some(StatusMessage::error(format!("✗ Failed: {}", e)));
+            }
+        }
+    }
+
+    /// コメントを削除し、表示中のスレッドと Conversation から取り除く（draw 後に呼ばれる）
+    pub(super) fn submit_comment_delete(&mut self) {
+        let Some(id) = self.review.editing_comment_id.take() else {
+            return;
+        };
+        let Some(client) = &self.client else {
+            self.status_message = Some(StatusMessage::error("✗ No API client available"));
+            return;
+        };
+        let Some((owner, repo)) = self.parse_repo() else {
+            self.status_message = Some(StatusMessage::error("✗ Invalid repo format"));
+            return;
+        };
+        let result = tokio::task::block_in_place(|| {
+            Handle::current().block_on(comments::delete_review_comment(client, owner, repo, id))
+        });
+        match result {
+            Ok(()) => {
+                self.remove_review_comment(id);
+                self.status_message = Some(StatusMessage::info("✓ Comment deleted"));
+            }
+            Err(e) => {
+                self.status_message = Some(StatusMessage::error(format!("✗ Failed: {}", e)));
+            }
+        }
+    }
+
+    /// review_comments・表示中のスレッド・Conversation のコメント本文を書き換える
+    pub(super) fn replace_review_comment_body(&mut self, id: u64, body: &str) {
+        let Some(comment) = self.review.review_comments.iter_mut().find(|c| c.id == id) else {
+            return;
+        };
+        comment.body = body.to_string();
+        let comment = comment.clone();
+        for viewing in self
+            .review
+            .viewing_comments
+            .iter_mut()
+            .filter(|c| c.id == id)
+        {
+            viewing.body = body.to_string();
+        }
+        let root_id = comment.in_reply_to_id.unwrap_or(id);
+        for entry in &mut self.conversation {
+            let ConversationKind::CodeComment {
+                root_comment_id,
+                ref mut replies,
+                ..
+            } = entry.kind
+            else {
+                continue;
+            };
+            if root_comment_id != root_id {
+                continue;
+            }
+            if comment.in_reply_to_id.is_none() {
+                entry.body = body.to_string();
+            } else if let Some(reply) = replies.iter_mut().find(|r| r.id == id) {
+                reply.body = body.to_string();
+            }
+        }
+        self.conversation_rendered = None;
+    }
+
+    /// review_comments・表示中のスレッド・Conversation からコメントを取り除く
+    pub(super) fn remove_review_comment(&mut self, id: u64) {
+        let Some(index) = self.review.review_comments.iter().position(|c| c.id == id) else {
+            return;
+        };
+        let comment = self.review.review_comments.remove(index);
+        self.review.viewing_comments.retain(|c| c.id != id);
+        self.review.viewing_comment_cursor = self
+            .review
+            .viewing_comment_cursor
+            .min(self.review.viewing_comments.len().saturating_sub(1));
+        self.rebuild_visible_comment_cache();
+
+        match comment.in_reply_to_id {
+            Some(root_id) => {
+                for entry in &mut self.conversation {
+                    if let ConversationKind::CodeComment {
+                        root_comment_id,
+                        ref mut replies,
+                        ..
+                    } = entry.kind
+                        && root_comment_id == root_id
+                    {
+                        replies.retain(|r| r.id != id);
+                    }
+                }
+            }
+            // 返信のないスレッドの先頭ならエントリごと消す（後ろの折りたたみ状態は詰める）
+            None => {
+                if let Some(removed) = self.conversation.iter().position(|entry| {
+                    matches!(entry.kind, ConversationKind::CodeComment { root_comment_id, .. } if root_comment_id == id)
+                }) {
+                    self.conversation.remove(removed);
+                    self.conversation_folded = self
+                        .conversation_folded
+                        .iter()
+                        .filter(|&&i| i != removed)
+                        .map(|&i| if i > removed { i - 1 } else { i })
+                        .collect();
+                    self.conversation_cursor = self
+                        .conversation_cursor
+                        .min(self.conversation.len().saturating_sub(1));
+                }
+                self.review.thread_map.remove(&id);
+            }
+        }
+        self.conversation_rendered = None;
+
+        // スレッドが空になったら CommentView を閉じる
+        if self.review.viewing_comments.is_empty() {
+            self.review.viewing_comment_scroll = 0;
+            if self.mode == AppMode::CommentView {
+                self.mode = AppMode::Normal;
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Commits ペインの範囲選択（前回までに見たコミットをまとめて viewed にする）
+
+use super::*;
+
+impl App {
+    /// `v`: 選択中のコミットを起点に範囲選択を始める（選択中なら解除）
+    pub(super) fn toggle_commit_range(&mut self) {
+        self.commit_range_anchor = match self.commit_range_anchor {
+            Some(_) => None,
+            None => self.commit_list_state.selected(),
+        };
+    }
+
+    /// 範囲選択中のコミットの範囲（両端を含む）
+    pub(super) fn commit_range(&self) -> Option<(usize, usize)> {
+        let anchor = self.commit_range_anchor?;
+        let cursor = self.commit_list_state.selected()?;
+        Some((anchor.min(cursor), anchor.max(cursor)))
+    }
+
+    /// 範囲内のコミットの viewed をまとめて切り替える（すべて viewed なら解除、それ以外は viewed にする）
+    pub(super) fn toggle_commit_range_viewed(&mut self) {
+        let Some((start, end)) = self.commit_range() else {
+            return;
+        };
+        self.commit_range_anchor = None;
+        let shas: Vec<String> = self.commits[start..=end.min(self.commits.len() - 1)]
+            .iter()
+            .map(|c| c.sha.clone())
+            .filter(|sha| self.files_map.contains_key(sha))
+            .collect();
+        if shas.is_empty() {
+            return;
+        }
+        let viewed = !shas.iter().all(|sha| self.is_commit_viewed(sha));
+        for sha in &shas {
+            self.set_commit_viewed(sha, viewed);
+        }
+        self.status_message = Some(StatusMessage::info(format!(
+            "✓ Marked {} commit{} as {}",
+            shas.len(),
+            if shas.len() == 1 { "" } else { "s" },
+            if viewed { "viewed" } else { "not viewed" }
+        )));
+    }
+}
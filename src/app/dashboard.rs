@@ -0,0 +1,201 @@
+//! 自分の PR 向けダッシュボード（未解決スレッドとレビュアー状態の集約）
+
+use super::*;
+
+/// ダッシュボードに表示する未解決スレッド
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DashboardThread {
+    /// conversation 内のインデックス（ジャンプ用）
+    pub entry_index: usize,
+    pub path: String,
+    pub line: Option<usize>,
+    /// スレッド開始者
+    pub author: String,
+    /// スレッド先頭コメントの1行目
+    pub summary: String,
+    /// 最後の発言が自分なら対応済みとみなす
+    pub addressed: bool,
+    pub root_comment_id: u64,
+    pub thread_node_id: Option<String>,
+}
+
+impl App {
+    /// 未解決のコード行コメントスレッドを conversation 順に返す
+    pub(super) fn dashboard_threads(&self) -> Vec<DashboardThread> {
+        self.conversation
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, entry)| match &entry.kind {
+                ConversationKind::CodeComment {
+                    path,
+                    line,
+                    replies,
+                    is_resolved: false,
+                    thread_node_id,
+                    root_comment_id,
+                } => {
+                    let last_author = replies
+                        .last()
+                        .map(|r| r.author.as_str())
+                        .unwrap_or(&entry.author);
+                    Some(DashboardThread {
+                        entry_index: idx,
+                        path: path.clone(),
+                        line: *line,
+                        author: entry.author.clone(),
+                        summary: entry.body.lines().next().unwrap_or("").to_string(),
+                        addressed: !self.current_user.is_empty()
+                            && last_author == self.current_user,
+                        root_comment_id: *root_comment_id,
+                        thread_node_id: thread_node_id.clone(),
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// レビュアーごとの最新状態を返す（自分は除外、COMMENTED は判定済み状態を上書きしない）
+    pub(super) fn reviewer_states(&self) -> Vec<(String, String)> {
+        let mut states: Vec<(String, String)> = Vec::new();
+        for entry in &self.conversation {
+            let ConversationKind::Review { state } = &entry.kind else {
+                continue;
+            };
+            if entry.author == self.current_user || entry.author == self.pr_author {
+                continue;
+            }
+            match states
+                .iter_mut()
+                .find(|(author, _)| *author == entry.author)
+            {
+                Some((_, current)) => {
+                    if state != "COMMENTED" || current == "COMMENTED" {
+                        *current = state.clone();
+                    }
+                }
+                None => states.push((entry.author.clone(), state.clone())),
+            }
+        }
+        states
+    }
+
+    /// ダッシュボードを開く（自分の PR のみ）
+    pub(super) fn open_dashboard(&mut self) {
+        if !self.is_own_pr {
+            self.status_message = Some(StatusMessage::error(
+                "✗ Dashboard is available on your own PRs",
+            ));
+            return;
+        }
+        if self.loading.conversation == LoadPhase::Loading {
+            self.status_message =
+                Some(StatusMessage::error("✗ Conversation loading. Please wait."));
+            return;
+        }
+        self.dashboard_cursor = 0;
+        self.mode = AppMode::Dashboard;
+    }
+
+    /// 再レビュー依頼を実行（draw 後に呼ばれる）
+    pub(super) fn execute_rerequest_review(&mut self) {
+        let reviewers: Vec<String> = self
+            .reviewer_states()
+            .into_iter()
+            .map(|(author, _)| author)
+            .collect();
+        if reviewers.is_empty() {
+            self.status_message = Some(StatusMessage::error("✗ No reviewers to re-request"));
+            return;
+        }
+        let Some(client) = &self.client else {
+            self.status_message = Some(StatusMessage::error("✗ No API client available"));
+            return;
+        };
+        let Some((owner, repo)) = self.parse_repo() else {
+            self.status_message = Some(StatusMessage::error("✗ Invalid repo format"));
+            return;
+        };
+        let result = tokio::task::block_in_place(|| {
+            Handle::current().block_on(crate::github::pr::request_reviewers(
+                client,
+                owner,
+                repo,
+                self.pr_number,
+                &reviewers,
+            ))
+        });
+        self.status_message = Some(match result {
+            Ok(()) => StatusMessage::info(format!(
+                "✓ Re-requested review from {}",
+                reviewers.join(", ")
+            )),
+            Err(e) => StatusMessage::error(format!("✗ Failed: {}", e)),
+        });
+    }
+
+    /// 自分が所属するチームにレビュー依頼が来ているか
+    pub(super) fn team_review_requested(&self) -> bool {
+        self.my_requested_teams
+            .as_ref()
+            .is_some_and(|teams| !teams.is_empty())
+    }
+
+    /// 依頼中チームへの所属をバックグラウンドでまとめて確認する
+    /// （draw 後に呼ばれる。チームは PR の owner 組織に属し、結果は AsyncData::TeamMembership で届く）
+    pub(super) fn execute_team_check(&mut self) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(owner) = self.parse_repo().map(|(owner, _)| owner.to_string()) else {
+            return;
+        };
+        let requested = self.requested_teams.clone();
+        let user = self.current_user.clone();
+        self.spawn_task(async move {
+            let checks = requested
+                .iter()
+                .map(|slug| crate::github::pr::is_team_member(&client, &owner, slug, &user));
+            let member = futures::future::join_all(checks).await;
+            let mine = requested
+                .iter()
+                .zip(member)
+                .filter(|(_, member)| *member)
+                .map(|(slug, _)| slug.clone())
+                .collect();
+            crate::AsyncData::TeamMembership { requested, mine }
+        });
+    }
+
+    /// チームの所属を反映する（確認中にリロードで依頼中チームが変わっていれば捨てる）
+    pub(super) fn apply_team_membership(&mut self, requested: &[String], mine: Vec<String>) {
+        if self.requested_teams != requested {
+            return;
+        }
+        if !mine.is_empty()
+            && let Some((owner, _)) = self.parse_repo()
+        {
+            self.status_message = Some(StatusMessage::info(format!(
+                "✓ Review requested from your team: {}",
+                mine.iter()
+                    .map(|t| format!("@{owner}/{t}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        self.my_requested_teams = Some(mine);
+    }
+
+    /// Draft PR を Ready for review にする（draw 後に呼ばれる）
+    pub(super) fn execute_mark_ready(&mut self) {
+        let Some((owner, repo)) = self.parse_repo() else {
+            self.status_message = Some(StatusMessage::error("✗ Invalid repo format"));
+            return;
+        };
+        let result = crate::github::pr::mark_ready_for_review(owner, repo, self.pr_number);
+        self.status_message = Some(match result {
+            Ok(()) => StatusMessage::info("✓ Marked as ready for review"),
+            Err(e) => StatusMessage::error(format!("✗ Failed: {}", e)),
+        });
+    }
+}
@@ -0,0 +1,274 @@
+//! PR 本文の <details>/<summary> ブロックを折りたたみセクションとして描画する
+
+use super::*;
+
+/// 折りたたみ見出しの既定サマリー（<summary> がない場合）
+const DEFAULT_SUMMARY: &str = "Details";
+
+/// PR 本文を <details> で分割した断片
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum BodySegment {
+    Markdown(String),
+    Details {
+        summary: String,
+        body: String,
+        /// `<details open>` で初期展開指定されているか
+        open: bool,
+    },
+}
+
+/// 行内の `<details` 開始タグと `</details>` 終了タグの数（大文字小文字を区別しない）
+fn count_details_tags(line: &str) -> (usize, usize) {
+    let lower = line.to_ascii_lowercase();
+    let closes = lower.matches("</details>").count();
+    let opens = lower.matches("<details").count();
+    (opens, closes)
+}
+
+/// HTML タグを取り除く（<summary><b>Title</b></summary> → Title）
+fn strip_tags(text: &str) -> String {
+    let mut result = String::new();
+    let mut in_tag = false;
+    for ch in text.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => result.push(ch),
+            _ => {}
+        }
+    }
+    result.trim().to_string()
+}
+
+/// `<details ...>...</details>` ブロック全体を見出し・本文・初期展開状態に分解
+fn parse_details_block(block: &str) -> BodySegment {
+    let lower = block.to_ascii_lowercase();
+    let tag_start = lower.find("<details").unwrap_or(0);
+    let tag_end = lower[tag_start..]
+        .find('>')
+        .map_or(block.len(), |e| tag_start + e + 1);
+    let open = lower[tag_start..tag_end]
+        .split(|c: char| c.is_whitespace() || c == '>')
+        .any(|attr| attr == "open" || attr.starts_with("open="));
+    let inner_end = lower
+        .rfind("</details>")
+        .unwrap_or(block.len())
+        .max(tag_end);
+    let inner = &block[tag_end..inner_end];
+    let inner_lower = &lower[tag_end..inner_end];
+
+    let (summary, body) = match (inner_lower.find("<summary"), inner_lower.find("</summary>")) {
+        (Some(start), Some(end)) if start < end => {
+            let summary = strip_tags(&inner[start..end]);
+            (summary, &inner[end + "</summary>".len()..])
+        }
+        _ => (String::new(), inner),
+    };
+    let summary = if summary.is_empty() {
+        DEFAULT_SUMMARY.to_string()
+    } else {
+        summary
+    };
+    BodySegment::Details {
+        summary,
+        body: body.trim_matches('\n').to_string(),
+        open,
+    }
+}
+
+/// 本文を通常のマークダウンと最上位の <details> ブロックに分割する（コードフェンス内は対象外）
+pub(super) fn split_details(text: &str) -> Vec<BodySegment> {
+    let mut segments = Vec::new();
+    let mut markdown: Vec<&str> = Vec::new();
+    let mut block: Vec<&str> = Vec::new();
+    let mut depth = 0usize;
+    let mut in_code = false;
+
+    for line in text.lines() {
+        if depth == 0 {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code = !in_code;
+            }
+            if in_code || !trimmed.to_ascii_lowercase().starts_with("<details") {
+                markdown.push(line);
+                continue;
+            }
+            if !markdown.is_empty() {
+                segments.push(BodySegment::Markdown(markdown.join("\n")));
+                markdown.clear();
+            }
+        }
+        block.push(line);
+        let (opens, closes) = count_details_tags(line);
+        depth = (depth + opens).saturating_sub(closes);
+        if depth == 0 {
+            segments.push(parse_details_block(&block.join("\n")));
+            block.clear();
+        }
+    }
+    // 閉じタグがないまま終わった場合も折りたたみとして扱う
+    if !block.is_empty() {
+        segments.push(parse_details_block(&block.join("\n")));
+    }
+    if !markdown.is_empty() {
+        segments.push(BodySegment::Markdown(markdown.join("\n")));
+    }
+    segments
+}
+
+impl App {
+    /// PR 本文を折りたたみ状態に応じて描画し、見出し行の論理行位置を記録する
+    /// `offset` は本文より前に挿入される行数（タイトル等）
+    pub(super) fn render_pr_body_with_folds(
+        &mut self,
+        body: &str,
+        offset: usize,
+    ) -> Vec<Line<'static>> {
+        let segments = split_details(body);
+        let fold_count = segments
+            .iter()
+            .filter(|s| matches!(s, BodySegment::Details { .. }))
+            .count();
+        // 初回（または本文が変わった場合）は <details open> 指定に従って初期化
+        if self.pr_desc_fold_open.len() != fold_count {
+            self.pr_desc_fold_open = segments
+                .iter()
+                .filter_map(|s| match s {
+                    BodySegment::Details { open, .. } => Some(*open),
+                    BodySegment::Markdown(_) => None,
+                })
+                .collect();
+        }
+
+        let width = self.pr_desc_rendered_width as usize;
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        let mut fold_lines = Vec::with_capacity(fold_count);
+        for segment in segments {
+            match segment {
+                BodySegment::Markdown(text) => {
+                    lines.extend(markdown::render_markdown(&text, self.theme, width));
+                }
+                BodySegment::Details { summary, body, .. } => {
+                    let is_open = self.pr_desc_fold_open[fold_lines.len()];
+                    fold_lines.push(offset + lines.len());
+                    let marker = if is_open { "▼ " } else { "▶ " };
+                    lines.push(Line::styled(
+                        format!("{marker}{summary}"),
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                    if is_open && !body.is_empty() {
+                        let guide = Style::default().fg(Color::DarkGray);
+                        let inner_width = width.saturating_sub(2);
+                        for line in markdown::render_markdown(&body, self.theme, inner_width) {
+                            let mut spans = vec![Span::styled("│ ", guide)];
+                            spans.extend(line.spans);
+                            lines.push(Line::from(spans).style(line.style));
+                        }
+                    }
+                }
+            }
+        }
+        self.pr_desc_fold_lines = fold_lines;
+        lines
+    }
+
+    /// PR Description の表示範囲内で最初の折りたたみ見出しのインデックス
+    pub(super) fn visible_pr_desc_fold(&self) -> Option<usize> {
+        let top = self.pr_desc_scroll as usize;
+        let bottom = top + self.pr_desc_view_height as usize;
+        self.pr_desc_fold_lines
+            .iter()
+            .enumerate()
+            .map(|(idx, &line)| {
+                // render 後は Wrap 考慮の視覚行、render 前は論理行で判定
+                let row = self.pr_desc_fold_rows.get(idx).copied().unwrap_or(line);
+                (idx, row)
+            })
+            .find(|&(_, row)| row >= top && row < bottom)
+            .map(|(idx, _)| idx)
+    }
+
+    /// 表示中の折りたたみ見出しを開閉する（見出しがなければ false）
+    pub(super) fn toggle_pr_desc_fold(&mut self) -> bool {
+        let Some(idx) = self.visible_pr_desc_fold() else {
+            return false;
+        };
+        if let Some(open) = self.pr_desc_fold_open.get_mut(idx) {
+            *open = !*open;
+            self.pr_desc_rendered = None;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_details_basic() {
+        let text = "Intro\n<details>\n<summary>Logs</summary>\n\nline1\n</details>\nOutro";
+        assert_eq!(
+            split_details(text),
+            vec![
+                BodySegment::Markdown("Intro".to_string()),
+                BodySegment::Details {
+                    summary: "Logs".to_string(),
+                    body: "line1".to_string(),
+                    open: false,
+                },
+                BodySegment::Markdown("Outro".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_details_single_line_and_open_attr() {
+        let text = "<details open><summary><b>Screenshot</b></summary>body</details>";
+        assert_eq!(
+            split_details(text),
+            vec![BodySegment::Details {
+                summary: "Screenshot".to_string(),
+                body: "body".to_string(),
+                open: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_split_details_nested_is_kept_in_body() {
+        let text = "<details>\n<summary>A</summary>\n<details>\n<summary>B</summary>\nx\n</details>\n</details>";
+        let segments = split_details(text);
+        assert_eq!(segments.len(), 1);
+        let BodySegment::Details { summary, body, .. } = &segments[0] else {
+            panic!("expected details");
+        };
+        assert_eq!(summary, "A");
+        assert!(body.contains("<summary>B</summary>"));
+    }
+
+    #[test]
+    fn test_split_details_ignores_code_fence() {
+        let text = "```\n<details>\n```";
+        assert_eq!(
+            split_details(text),
+            vec![BodySegment::Markdown(text.to_string())]
+        );
+    }
+
+    #[test]
+    fn test_missing_summary_uses_default() {
+        let segments = split_details("<details>\nhidden\n</details>");
+        assert_eq!(
+            segments,
+            vec![BodySegment::Details {
+                summary: DEFAULT_SUMMARY.to_string(),
+                body: "hidden".to_string(),
+                open: false,
+            }]
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! Mermaid / PlantUML のコードブロックを検出し、プレースホルダー・ASCII 描画・kroki の URL に変換する
+
+use std::io::Write;
+use std::process::Stdio;
+
+/// 折りたたんだ図の行の先頭（markdown 側でこの行を装飾する）
+pub(super) const DIAGRAM_PLACEHOLDER: &str = "[📊 Diagram:";
+/// 図をブラウザで表示するための描画サービス
+const KROKI_BASE_URL: &str = "https://kroki.io";
+
+/// 図の種類（コードフェンスの info string で判定）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum DiagramKind {
+    Mermaid,
+    PlantUml,
+}
+
+impl DiagramKind {
+    fn from_info(info: &str) -> Option<Self> {
+        match info.split_whitespace().next()? {
+            "mermaid" => Some(Self::Mermaid),
+            "plantuml" | "puml" => Some(Self::PlantUml),
+            _ => None,
+        }
+    }
+
+    /// kroki の図の種類名（表示にも使う）
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            Self::Mermaid => "mermaid",
+            Self::PlantUml => "plantuml",
+        }
+    }
+
+    /// ASCII 描画に使う外部プログラムと引数（ソースは stdin で渡す）
+    fn ascii_command(self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            Self::Mermaid => ("mermaid-ascii", &[]),
+            Self::PlantUml => ("plantuml", &["-tutxt", "-pipe"]),
+        }
+    }
+}
+
+/// テキスト中の図のブロック
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct DiagramBlock {
+    pub kind: DiagramKind,
+    pub source: String,
+    /// 開きフェンスの行番号
+    pub start: usize,
+    /// 閉じフェンスの行番号（閉じていなければ最終行）
+    pub end: usize,
+}
+
+impl DiagramBlock {
+    /// ブラウザで描画結果を開く URL
+    pub(super) fn kroki_url(&self) -> String {
+        format!(
+            "{KROKI_BASE_URL}/{}/svg/{}",
+            self.kind.name(),
+            base64_url(&zlib_stored(self.source.as_bytes()))
+        )
+    }
+}
+
+/// 図のコードブロックを抽出する（```mermaid / ```plantuml / ```puml）
+pub(super) fn extract_diagrams(text: &str) -> Vec<DiagramBlock> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let trimmed = lines[i].trim_start();
+        let fence = if trimmed.starts_with("```") {
+            "```"
+        } else if trimmed.starts_with("~~~") {
+            "~~~"
+        } else {
+            i += 1;
+            continue;
+        };
+        let end = (i + 1..lines.len())
+            .find(|&j| lines[j].trim() == fence)
+            .unwrap_or(lines.len().saturating_sub(1));
+        if let Some(kind) = DiagramKind::from_info(&trimmed[fence.len()..]) {
+            let body_end = if end > i && lines[end].trim() == fence {
+                end
+            } else {
+                lines.len()
+            };
+            blocks.push(DiagramBlock {
+                kind,
+                source: lines[i + 1..body_end].join("\n"),
+                start: i,
+                end,
+            });
+        }
+        // 図以外のコードブロックの中身は見ない
+        i = end + 1;
+    }
+    blocks
+}
+
+/// 図のブロックを ASCII 描画（`ascii` が true で外部プログラムが使える場合）か 1 行のプレースホルダーに置き換える
+pub(super) fn collapse_diagrams(text: &str, ascii: bool) -> String {
+    let blocks = extract_diagrams(text);
+    if blocks.is_empty() {
+        return text.to_string();
+    }
+    let lines: Vec<&str> = text.lines().collect();
+    let mut out: Vec<String> = Vec::new();
+    let mut next = 0;
+    for block in &blocks {
+        out.extend(lines[next..block.start].iter().map(|l| l.to_string()));
+        out.push(format!(
+            "{DIAGRAM_PLACEHOLDER} {}] gl: open rendered",
+            block.kind.name()
+        ));
+        if let Some(art) = ascii.then(|| render_ascii(block)).flatten() {
+            // テーブル・タスクリストとして整形されないようコードフェンスで囲む
+            out.push("```".to_string());
+            out.extend(art);
+            out.push("```".to_string());
+        }
+        next = block.end + 1;
+    }
+    out.extend(lines[next.min(lines.len())..].iter().map(|l| l.to_string()));
+    out.join("\n")
+}
+
+/// 外部プログラムで ASCII 描画する（失敗・未許可なら None）
+fn render_ascii(block: &DiagramBlock) -> Option<Vec<String>> {
+    let (program, args) = block.kind.ascii_command();
+    let mut child = crate::exec::command(program)?
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    // パイプデッドロック回避は highlight_with_bat と同じ thread::spawn パターン
+    let mut stdin = child.stdin.take().expect("stdin was configured");
+    let source = block.source.clone().into_bytes();
+    let writer = std::thread::spawn(move || {
+        let _ = stdin.write_all(&source);
+    });
+    let output = child.wait_with_output().ok()?;
+    let _ = writer.join();
+    if !output.status.success() {
+        return None;
+    }
+    let art: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.trim_end().to_string())
+        .collect();
+    (!art.is_empty()).then_some(art)
+}
+
+/// 無圧縮（stored ブロック）の zlib ストリーム。kroki は deflate 済みであれば圧縮率を問わない
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    if data.is_empty() {
+        out.extend([0x01, 0x00, 0x00, 0xFF, 0xFF]);
+    }
+    let mut chunks = data.chunks(u16::MAX as usize).peekable();
+    while let Some(chunk) = chunks.next() {
+        out.push(u8::from(chunks.peek().is_none()));
+        let len = chunk.len() as u16;
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(chunk);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % MOD;
+        b = (b + a) % MOD;
+    }
+    (b << 16) | a
+}
+
+/// URL 用の base64（`-` と `_`、パディングなし）
+fn base64_url(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | (u32::from(b) << (16 - 8 * i)));
+        for i in 0..=chunk.len() {
+            out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3F) as usize] as char);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_diagrams() {
+        let text = "intro\n```mermaid\ngraph TD\n  A-->B\n```\n```rust\n```mermaid\n```\n~~~puml\n@startuml\n~~~";
+        let blocks = extract_diagrams(text);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].kind, DiagramKind::Mermaid);
+        assert_eq!(blocks[0].source, "graph TD\n  A-->B");
+        assert_eq!((blocks[0].start, blocks[0].end), (1, 4));
+        // rust ブロック内の ```mermaid は図として扱わない
+        assert_eq!(blocks[1].kind, DiagramKind::PlantUml);
+        assert_eq!(blocks[1].source, "@startuml");
+    }
+
+    #[test]
+    fn test_collapse_diagrams_to_placeholder() {
+        let text = "before\n```mermaid\ngraph TD\n```\nafter";
+        assert_eq!(
+            collapse_diagrams(text, false),
+            "before\n[📊 Diagram: mermaid] gl: open rendered\nafter"
+        );
+        assert_eq!(collapse_diagrams("plain", false), "plain");
+    }
+
+    #[test]
+    fn test_kroki_encoding() {
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+        assert_eq!(base64_url(b"\xfb\xff"), "-_8");
+        assert_eq!(base64_url(b"Man"), "TWFu");
+        assert_eq!(
+            zlib_stored(b"ab"),
+            [
+                0x78, 0x01, 0x01, 0x02, 0x00, 0xFD, 0xFF, b'a', b'b', 0x01, 0x26, 0x00, 0xC4
+            ]
+        );
+        let block = &extract_diagrams("```mermaid\nab\n```")[0];
+        assert_eq!(
+            block.kroki_url(),
+            "https://kroki.io/mermaid/svg/eAEBAgD9_2FiASYAxA"
+        );
+    }
+}
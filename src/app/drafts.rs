@@ -0,0 +1,127 @@
+//! コメントエディタのキャンセル確認と下書き
+
+use super::editor::TextEditor;
+use super::*;
+
+/// 下書きの保存先（エディタの種類と対象）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) enum DraftKey {
+    /// 行コメント（コミット, ファイル, 選択開始行）
+    Line {
+        commit_sha: String,
+        path: String,
+        anchor: usize,
+    },
+    /// スレッドへの返信（ルートコメント ID）
+    Reply(u64),
+    IssueComment,
+    ReviewBody,
+}
+
+impl App {
+    /// 指定モードのエディタに対応する下書きキー
+    fn draft_key(&self, mode: AppMode) -> Option<DraftKey> {
+        match mode {
+            AppMode::CommentInput => Some(DraftKey::Line {
+                commit_sha: self.current_commit_sha()?,
+                path: self.current_file()?.filename.clone(),
+                anchor: self.line_selection?.anchor,
+            }),
+            AppMode::ReplyInput => self.review.reply_to_comment_id.map(DraftKey::Reply),
+            AppMode::IssueCommentInput => Some(DraftKey::IssueComment),
+            AppMode::ReviewBodyInput => Some(DraftKey::ReviewBody),
+            _ => None,
+        }
+    }
+
+    /// 指定モードで編集中のエディタ
+    pub(super) fn editor_for(&mut self, mode: AppMode) -> &mut TextEditor {
+        if mode == AppMode::ReviewBodyInput {
+            &mut self.review.review_body_editor
+        } else {
+            &mut self.review.comment_editor
+        }
+    }
+
+    /// Esc: 入力があれば破棄確認ダイアログを出し、空ならそのまま閉じる
+    pub(super) fn request_cancel_editor(&mut self) {
+        let mode = self.mode;
+        if self.editor_for(mode).is_empty() {
+            self.cancel_editor(mode);
+        } else {
+            self.discard_return_mode = mode;
+            self.mode = AppMode::DiscardConfirm;
+        }
+    }
+
+    /// エディタの内容を破棄して閉じる（モードごとの後始末を行う）
+    pub(super) fn cancel_editor(&mut self, mode: AppMode) {
+        match mode {
+            AppMode::CommentInput => self.cancel_comment_input(),
+            AppMode::ReplyInput => {
+                self.review.comment_editor.clear();
+                self.review.reply_to_comment_id = None;
+                self.review.resolve_after_reply = false;
+                // CommentView から入った場合（viewing_comments が残っている）は CommentView に戻る
+                if !self.review.viewing_comments.is_empty() {
+                    self.mode = AppMode::CommentView;
+                } else {
+                    self.mode = AppMode::Normal;
+                }
+            }
+            AppMode::IssueCommentInput => {
+                self.review.comment_editor.clear();
+                self.mode = AppMode::Normal;
+                self.focused_panel = Panel::Conversation;
+            }
+            AppMode::ReviewBodyInput => {
+                self.review.review_body_editor.clear();
+                self.mode = AppMode::ReviewSubmit;
+            }
+            AppMode::CommentEditInput => {
+                self.review.comment_editor.clear();
+                self.review.editing_comment_id = None;
+                self.mode = AppMode::CommentView;
+            }
+            _ => self.mode = AppMode::Normal,
+        }
+    }
+
+    /// エディタの内容を下書きとして保存して閉じる（同じ対象で開き直すと復元される）
+    pub(super) fn save_draft_and_close(&mut self) {
+        let mode = self.discard_return_mode;
+        let Some(key) = self.draft_key(mode) else {
+            self.status_message = Some(StatusMessage::error("✗ Cannot save draft here"));
+            self.mode = mode;
+            return;
+        };
+        let text = self.editor_for(mode).text();
+        self.drafts.insert(key, text);
+        self.cancel_editor(mode);
+        self.status_message = Some(StatusMessage::info("✓ Draft saved"));
+    }
+
+    /// 編集中の内容を確認なしで下書きに退避して閉じる（空なら閉じるだけ）
+    pub(super) fn stash_draft_and_close(&mut self) {
+        let mode = self.mode;
+        if let Some(key) = self.draft_key(mode) {
+            let text = self.editor_for(mode).text();
+            if !text.is_empty() {
+                self.drafts.insert(key, text);
+                self.status_message = Some(StatusMessage::info("✓ Draft saved"));
+            }
+        }
+        self.cancel_editor(mode);
+    }
+
+    /// 現在のエディタに対応する下書きがあれば復元する（エディタを開いた直後に呼ぶ）
+    pub(super) fn restore_draft(&mut self) {
+        let mode = self.mode;
+        if let Some(text) = self
+            .draft_key(mode)
+            .and_then(|key| self.drafts.remove(&key))
+        {
+            self.editor_for(mode).insert_text(&text);
+        }
+    }
+}
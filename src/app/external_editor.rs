@@ -0,0 +1,83 @@
+//! Ctrl+E: 編集中のコメントを外部エディタ（$VISUAL / $EDITOR）で開き、保存した内容を読み戻す
+
+use super::*;
+use std::process::Stdio;
+
+/// 一時ファイルに書き出したテキストをエディタで編集し、保存後の内容を返す
+fn run_external_editor(text: &str) -> std::result::Result<String, String> {
+    let command = crate::exec::editor_command();
+    let Some((program, args)) = command.split_first() else {
+        return Err("✗ No editor configured — set $EDITOR".to_string());
+    };
+    let Some(mut cmd) = crate::exec::command(program) else {
+        return Err(format!("✗ `{program}` is not in allowed_commands"));
+    };
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis());
+    // .md にしてエディタの Markdown モードを効かせる
+    let path =
+        std::env::temp_dir().join(format!("prism-comment-{}-{millis}.md", std::process::id()));
+    std::fs::write(&path, text).map_err(|e| format!("✗ Failed to write temp file: {e}"))?;
+    let status = cmd
+        .args(args)
+        .arg(&path)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+    let edited = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => return Err(format!("✗ {program} exited with {status}")),
+        Err(e) => return Err(format!("✗ Failed to run {program}: {e}")),
+    }
+    let edited = edited.map_err(|e| format!("✗ Failed to read temp file: {e}"))?;
+    // エディタが付ける末尾の改行は落とす
+    Ok(edited.trim_end_matches(['\n', '\r']).to_string())
+}
+
+impl App {
+    /// Ctrl+E で外部エディタを開けるモードか
+    pub(super) fn is_editor_mode(mode: AppMode) -> bool {
+        matches!(
+            mode,
+            AppMode::CommentInput
+                | AppMode::ReplyInput
+                | AppMode::CommentEditInput
+                | AppMode::IssueCommentInput
+                | AppMode::NoteInput
+                | AppMode::ReviewBodyInput
+        )
+    }
+
+    /// TUI を一時停止して外部エディタで編集し、終了したら内容を反映して TUI を再初期化する
+    pub(super) fn edit_in_external_editor(&mut self) -> Result<DefaultTerminal> {
+        let mode = self.mode;
+        let text = self.editor_for(mode).text();
+
+        crate::mouse::disable(self.mouse_captured);
+        ratatui::restore();
+        let pending = crate::exec::pending_count();
+        let result = run_external_editor(&text);
+        let terminal = ratatui::init();
+        self.mouse_captured = crate::mouse::enable(self.mouse_captured);
+        // 編集中に端末サイズが変わっている可能性がある
+        self.handle_resize();
+
+        match result {
+            Ok(edited) => {
+                let target = self.editor_for(mode);
+                target.clear();
+                target.insert_text(&edited);
+                target.ensure_visible(editor::EDITOR_VISIBLE_HEIGHT);
+            }
+            Err(message) => {
+                self.status_message = Some(StatusMessage::error(message));
+                self.retry_after_permission(pending, PermissionRetry::ExternalEditor);
+            }
+        }
+        Ok(terminal)
+    }
+}
@@ -0,0 +1,154 @@
+//! ファイルを固定して PR 内の任意の2コミット間で比較する
+
+use super::*;
+use crate::git::diff::highlight_diff;
+use crate::github::files;
+
+/// 比較対象として固定したファイル
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePin {
+    pub filename: String,
+    pub commit_sha: String,
+}
+
+/// 2コミット間のファイル比較結果
+#[derive(Debug, Clone)]
+pub struct FileCompareView {
+    pub filename: String,
+    pub from_sha: String,
+    pub to_sha: String,
+    pub additions: usize,
+    pub deletions: usize,
+    /// ハイライト済み差分（取得時に一度だけ生成）
+    pub text: Text<'static>,
+}
+
+/// delta が使えない場合の手動色分け
+pub(super) fn colorize_patch(patch: &str) -> Text<'static> {
+    let lines: Vec<Line<'static>> = patch
+        .lines()
+        .map(|line| {
+            let style = match line.chars().next() {
+                Some('+') => Style::default().fg(Color::Green),
+                Some('-') => Style::default().fg(Color::Red),
+                Some('@') => Style::default().fg(Color::Cyan),
+                _ => Style::default(),
+            };
+            Line::styled(line.to_string(), style)
+        })
+        .collect();
+    Text::from(lines)
+}
+
+impl App {
+    /// Files ペインで選択中のファイルを比較用に固定（同じファイル・コミットなら解除）
+    pub(super) fn toggle_file_pin(&mut self) {
+        let Some(filename) = self.current_file().map(|f| f.filename.clone()) else {
+            return;
+        };
+        if self.is_all_changes_selected() {
+            self.status_message = Some(StatusMessage::error("✗ Select a commit to pin"));
+            return;
+        }
+        let Some(commit_sha) = self.current_commit_sha() else {
+            return;
+        };
+        let pin = FilePin {
+            filename,
+            commit_sha,
+        };
+        if self.file_pin.as_ref() == Some(&pin) {
+            self.file_pin = None;
+            self.status_message = Some(StatusMessage::info("✓ Unpinned file"));
+        } else {
+            self.status_message = Some(StatusMessage::info(format!(
+                "✓ Pinned {} — select another commit and press p",
+                truncate_path(&pin.filename, 30)
+            )));
+            self.file_pin = Some(pin);
+        }
+    }
+
+    /// Commits ペインで p: 固定したファイルを選択中のコミットと比較する
+    pub(super) fn request_file_compare(&mut self) {
+        let Some(pin) = &self.file_pin else {
+            self.status_message = Some(StatusMessage::error(
+                "✗ No pinned file (press p in Files first)",
+            ));
+            return;
+        };
+        if self.is_all_changes_selected()
+            || self.current_commit_sha().as_deref() == Some(pin.commit_sha.as_str())
+        {
+            self.status_message = Some(StatusMessage::error(
+                "✗ Select a different commit to compare",
+            ));
+            return;
+        }
+        self.needs_file_compare = true;
+    }
+
+    /// 固定コミットと選択コミットを時系列順に並べる（古い方が from）
+    pub(super) fn file_compare_range(&self) -> Option<(String, String)> {
+        let pin = self.file_pin.as_ref()?;
+        let selected = self.current_commit_sha()?;
+        let pin_idx = self.commits.iter().position(|c| c.sha == pin.commit_sha)?;
+        let sel_idx = self.commits.iter().position(|c| c.sha == selected)?;
+        if pin_idx <= sel_idx {
+            Some((pin.commit_sha.clone(), selected))
+        } else {
+            Some((selected, pin.commit_sha.clone()))
+        }
+    }
+
+    /// 比較 API を呼び出してオーバーレイを開く（draw 後に呼ばれる）
+    pub(super) fn execute_file_compare(&mut self) {
+        let Some(filename) = self.file_pin.as_ref().map(|p| p.filename.clone()) else {
+            return;
+        };
+        let Some((from_sha, to_sha)) = self.file_compare_range() else {
+            self.status_message = Some(StatusMessage::error("✗ Commit not found"));
+            return;
+        };
+        let Some(client) = &self.client else {
+            self.status_message = Some(StatusMessage::error("✗ No API client available"));
+            return;
+        };
+        let Some((owner, repo)) = self.parse_repo() else {
+            self.status_message = Some(StatusMessage::error("✗ Invalid repo format"));
+            return;
+        };
+
+        let result = tokio::task::block_in_place(|| {
+            Handle::current().block_on(files::fetch_compare_file(
+                client, owner, repo, &from_sha, &to_sha, &filename,
+            ))
+        });
+
+        match result {
+            Ok(Some(file)) => {
+                let patch = file.patch.unwrap_or_default();
+                let text = highlight_diff(&patch, &file.filename, &file.status)
+                    .unwrap_or_else(|| colorize_patch(&patch));
+                self.file_compare = Some(FileCompareView {
+                    filename: file.filename,
+                    from_sha,
+                    to_sha,
+                    additions: file.additions,
+                    deletions: file.deletions,
+                    text,
+                });
+                self.file_compare_scroll = 0;
+                self.mode = AppMode::FileCompare;
+            }
+            Ok(None) => {
+                self.status_message = Some(StatusMessage::info(
+                    "✓ No changes to the file between these commits",
+                ));
+            }
+            Err(e) => {
+                self.status_message = Some(StatusMessage::error(format!("✗ Failed: {}", e)));
+            }
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! gf / Ctrl+P: PR 内の全ファイル名のあいまい検索（選んだファイルへ Commits と Files の選択を移す）
+
+use super::*;
+
+/// 一覧に出す候補の上限
+const FILE_FINDER_MAX_RESULTS: usize = 200;
+
+/// あいまい検索の候補
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinderMatch {
+    pub filename: String,
+    /// マッチした文字の位置（char 単位、ハイライト用）
+    pub positions: Vec<usize>,
+    /// ファイルを変更したコミット数
+    pub commits: usize,
+}
+
+/// 区切り文字の直後か（単語の先頭へのマッチを優先する）
+fn is_word_start(chars: &[char], pos: usize) -> bool {
+    pos == 0 || matches!(chars[pos - 1], '/' | '_' | '-' | '.' | ' ')
+}
+
+/// クエリの文字が順に現れればマッチ（大文字を含むクエリのみ大文字小文字を区別）。
+/// 連続・単語の先頭・ファイル名部分へのマッチほどスコアが高い
+pub(super) fn fuzzy_match(query: &str, candidate: &str) -> Option<(i64, Vec<usize>)> {
+    let case_sensitive = query.chars().any(char::is_uppercase);
+    let fold = |c: char| {
+        if case_sensitive {
+            c
+        } else {
+            c.to_ascii_lowercase()
+        }
+    };
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(fold)
+        .collect();
+    let chars: Vec<char> = candidate.chars().collect();
+    let Some(&first) = query.first() else {
+        return Some((0, Vec::new()));
+    };
+    let basename_start = candidate
+        .rfind('/')
+        .map_or(0, |i| candidate[..=i].chars().count());
+    // 1 文字目の位置ごとに前から貪欲に当て、最もスコアの高いものを採る
+    (0..chars.len())
+        .filter(|&start| fold(chars[start]) == first)
+        .filter_map(|start| {
+            let mut positions = vec![start];
+            for &q in &query[1..] {
+                let next = positions.last().map_or(0, |&p| p + 1);
+                positions.push((next..chars.len()).find(|&i| fold(chars[i]) == q)?);
+            }
+            let score = positions
+                .iter()
+                .enumerate()
+                .map(|(i, &pos)| {
+                    let consecutive = i > 0 && positions[i - 1] + 1 == pos;
+                    1 + 5 * i64::from(consecutive)
+                        + 3 * i64::from(is_word_start(&chars, pos))
+                        + 2 * i64::from(pos >= basename_start)
+                })
+                .sum();
+            Some((score, positions))
+        })
+        .max_by_key(|(score, positions)| (*score, std::cmp::Reverse(positions[0])))
+}
+
+impl App {
+    /// gf / Ctrl+P: ファイル検索を開く
+    pub(super) fn open_file_finder(&mut self) {
+        if self.files_map.values().all(Vec::is_empty) && self.pr_files.is_none() {
+            self.status_message = Some(StatusMessage::error("✗ No files loaded yet"));
+            return;
+        }
+        self.file_finder_input.clear();
+        self.file_finder_cursor = 0;
+        self.mode = AppMode::FileFinder;
+    }
+
+    /// 入力に合う PR 内のファイル（スコア順、入力が空ならパス順）
+    pub(super) fn file_finder_matches(&self) -> Vec<FinderMatch> {
+        let mut commits: BTreeMap<&str, usize> = BTreeMap::new();
+        for files in self
+            .commits
+            .iter()
+            .filter_map(|c| self.files_map.get(&c.sha))
+        {
+            for file in files {
+                *commits.entry(file.filename.as_str()).or_default() += 1;
+            }
+        }
+        // 後から戻されたファイルはコミットにしか、読み込み前のコミットのファイルは PR 全体にしかない
+        for file in self.pr_files.iter().flatten() {
+            commits.entry(file.filename.as_str()).or_default();
+        }
+        let mut matches: Vec<(i64, FinderMatch)> = commits
+            .into_iter()
+            .filter_map(|(filename, count)| {
+                let (score, positions) = fuzzy_match(&self.file_finder_input, filename)?;
+                Some((
+                    score,
+                    FinderMatch {
+                        filename: filename.to_string(),
+                        positions,
+                        commits: count,
+                    },
+                ))
+            })
+            .collect();
+        // 入力が空ならパス順のまま、あればスコア順で同点なら短いパスを先に（BTreeMap 由来のパス順は stable sort で保たれる）
+        if !self.file_finder_input.is_empty() {
+            matches.sort_by_key(|(score, m)| (std::cmp::Reverse(*score), m.filename.len()));
+        }
+        matches
+            .into_iter()
+            .take(FILE_FINDER_MAX_RESULTS)
+            .map(|(_, m)| m)
+            .collect()
+    }
+
+    /// ファイルを開くコミット行とファイル位置（選択中のコミットにあればそのまま、なければ最新のコミット、どのコミットにもなければ All changes）
+    fn file_finder_target(&self, filename: &str) -> Option<(usize, usize)> {
+        let position_in = |row: usize| {
+            self.commit_key_at(row)
+                .and_then(|key| self.files_for(key))
+                .and_then(|files| files.iter().position(|f| f.filename == filename))
+                .map(|file| (row, file))
+        };
+        self.commit_list_state
+            .selected()
+            .and_then(position_in)
+            .or_else(|| (0..self.commits.len()).rev().find_map(position_in))
+            .or_else(|| position_in(self.commits.len()))
+    }
+
+    /// Enter: 選択中の候補のファイルを DiffView に開く
+    pub(super) fn open_file_finder_match(&mut self) {
+        let Some(filename) = self
+            .file_finder_matches()
+            .into_iter()
+            .nth(self.file_finder_cursor)
+            .map(|m| m.filename)
+        else {
+            return;
+        };
+        self.mode = AppMode::Normal;
+        let Some(position) = self.file_finder_target(&filename) else {
+            self.status_message = Some(StatusMessage::error(format!(
+                "✗ {} is not loaded yet",
+                truncate_str(&filename, 50)
+            )));
+            return;
+        };
+        self.select_file_position(position);
+        // 折りたたんだテストファイルを開いたら見出しも開く
+        if self.test_split == TestSplit::Collapsed
+            && !self.file_tree_rows().contains(&FileRow::File(position.1))
+        {
+            self.test_split = TestSplit::Grouped;
+        }
+        self.focused_panel = Panel::DiffView;
+    }
+
+    /// 候補のカーソルを動かす（端で止まる）
+    pub(super) fn move_file_finder_cursor(&mut self, forward: bool) {
+        if forward {
+            let len = self.file_finder_matches().len();
+            if self.file_finder_cursor + 1 < len {
+                self.file_finder_cursor += 1;
+            }
+        } else {
+            self.file_finder_cursor = self.file_finder_cursor.saturating_sub(1);
+        }
+    }
+
+    /// 入力を 1 文字足す（None なら 1 文字消す）。入力を変えたら先頭の候補を選ぶ
+    pub(super) fn edit_file_finder_input(&mut self, ch: Option<char>) {
+        match ch {
+            Some(ch) => self.file_finder_input.push(ch),
+            None => {
+                self.file_finder_input.pop();
+            }
+        }
+        self.file_finder_cursor = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(fuzzy_match("", "src/a.rs"), Some((0, Vec::new())));
+        assert_eq!(fuzzy_match("xyz", "src/app.rs"), None);
+        // ファイル名部分の連続したマッチを、ディレクトリ名の中のマッチより優先する
+        let (_, positions) = fuzzy_match("main", "src/domain/main.rs").unwrap();
+        assert_eq!(positions, vec![11, 12, 13, 14]);
+        // 単語の先頭を拾う
+        let (_, positions) = fuzzy_match("ff", "src/app/file_finder.rs").unwrap();
+        assert_eq!(positions, vec![8, 13]);
+        // 大文字を含むクエリは大文字小文字を区別する
+        assert!(fuzzy_match("readme", "README.md").is_some());
+        assert!(fuzzy_match("README", "readme.md").is_none());
+        let (exact, _) = fuzzy_match("app", "src/app.rs").unwrap();
+        let (scattered, _) = fuzzy_match("app", "src/a_p_p.rs").unwrap();
+        assert!(exact > scattered);
+    }
+}
@@ -0,0 +1,121 @@
+//! 選択ファイルの PR 内履歴（そのファイルを変更したコミットのパッチを時系列順にたどる）
+
+use super::file_compare::colorize_patch;
+use super::*;
+use crate::git::diff::highlight_diff;
+
+/// ファイル履歴ビューの状態
+#[derive(Debug, Clone)]
+pub struct FileHistoryView {
+    pub filename: String,
+    /// ファイルを変更したコミットのインデックス（古い順）
+    pub commits: Vec<usize>,
+    /// 表示中の位置（commits 内のインデックス）
+    pub index: usize,
+    pub scroll: u16,
+    /// 表示中パッチのハイライト済みテキスト（移動時に作り直す）
+    pub text: Text<'static>,
+}
+
+impl App {
+    /// ファイルを変更したコミットのインデックス（古い順）
+    fn commits_touching(&self, filename: &str) -> Vec<usize> {
+        self.commits
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| {
+                self.files_map
+                    .get(&c.sha)
+                    .is_some_and(|files| files.iter().any(|f| f.filename == filename))
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// 指定コミットでのファイルのパッチをハイライトする
+    fn file_history_text(&self, commit: usize, filename: &str) -> Text<'static> {
+        let Some(file) = self
+            .commits
+            .get(commit)
+            .and_then(|c| self.files_map.get(&c.sha))
+            .and_then(|files| files.iter().find(|f| f.filename == filename))
+        else {
+            return Text::default();
+        };
+        match file.patch.as_deref() {
+            Some(patch) => highlight_diff(patch, &file.filename, &file.status)
+                .unwrap_or_else(|| colorize_patch(patch)),
+            None => Text::from(Line::styled(
+                "(no patch: binary or too large)",
+                Style::default().fg(Color::DarkGray),
+            )),
+        }
+    }
+
+    /// H: 選択中のファイルの履歴を開く（選択中のコミットから表示）
+    pub(super) fn open_file_history(&mut self) {
+        let Some(filename) = self.current_file().map(|f| f.filename.clone()) else {
+            return;
+        };
+        let commits = self.commits_touching(&filename);
+        let selected = self.commit_list_state.selected().unwrap_or(0);
+        let index = commits
+            .iter()
+            .position(|&c| c == selected)
+            .unwrap_or(commits.len().saturating_sub(1));
+        let Some(&commit) = commits.get(index) else {
+            return;
+        };
+        let text = self.file_history_text(commit, &filename);
+        self.file_history = Some(FileHistoryView {
+            filename,
+            commits,
+            index,
+            scroll: 0,
+            text,
+        });
+        self.mode = AppMode::FileHistory;
+    }
+
+    /// n / N: 次 / 前のコミットのパッチへ移動する
+    pub(super) fn step_file_history(&mut self, forward: bool) {
+        let Some(view) = &self.file_history else {
+            return;
+        };
+        let index = if forward {
+            (view.index + 1).min(view.commits.len().saturating_sub(1))
+        } else {
+            view.index.saturating_sub(1)
+        };
+        if index == view.index {
+            return;
+        }
+        let text = self.file_history_text(view.commits[index], &view.filename);
+        if let Some(view) = &mut self.file_history {
+            view.index = index;
+            view.scroll = 0;
+            view.text = text;
+        }
+    }
+
+    /// Enter: 表示中のコミットでファイルを DiffView に開く
+    pub(super) fn open_file_history_entry(&mut self) {
+        let Some(view) = self.file_history.take() else {
+            return;
+        };
+        self.mode = AppMode::Normal;
+        let Some(&commit) = view.commits.get(view.index) else {
+            return;
+        };
+        let Some(file) = self
+            .commits
+            .get(commit)
+            .and_then(|c| self.files_map.get(&c.sha))
+            .and_then(|files| files.iter().position(|f| f.filename == view.filename))
+        else {
+            return;
+        };
+        self.select_file_position((commit, file));
+        self.focused_panel = Panel::DiffView;
+    }
+}
@@ -0,0 +1,51 @@
+//! Conversation エントリの折りたたみと、カーソル位置のエントリに対するアクション
+
+use super::*;
+
+impl App {
+    /// 折りたたみ変更後に再描画し、カーソルエントリを画面内へ戻す
+    fn refresh_conversation_folds(&mut self) {
+        self.conversation_rendered = None; // キャッシュ無効化
+        self.conversation_recenter = true;
+    }
+
+    /// za: カーソル位置のエントリを折りたたむ / 展開する
+    pub(super) fn toggle_conversation_fold(&mut self) {
+        if self.conversation.is_empty() {
+            return;
+        }
+        let cursor = self.conversation_cursor;
+        if !self.conversation_folded.remove(&cursor) {
+            self.conversation_folded.insert(cursor);
+        }
+        self.refresh_conversation_folds();
+    }
+
+    /// zm: 全エントリを折りたたむ / zr: 全エントリを展開する
+    pub(super) fn set_all_conversation_folds(&mut self, folded: bool) {
+        self.conversation_folded = if folded {
+            (0..self.conversation.len()).collect()
+        } else {
+            HashSet::new()
+        };
+        self.refresh_conversation_folds();
+    }
+
+    /// r: カーソル位置のスレッドの resolve / unresolve を切り替える
+    pub(super) fn toggle_conversation_resolve(&mut self) {
+        let Some(root_comment_id) =
+            self.conversation
+                .get(self.conversation_cursor)
+                .and_then(|entry| match entry.kind {
+                    ConversationKind::CodeComment {
+                        root_comment_id, ..
+                    } => Some(root_comment_id),
+                    _ => None,
+                })
+        else {
+            self.status_message = Some(StatusMessage::error("✗ Not a review thread"));
+            return;
+        };
+        self.request_resolve_toggle(root_comment_id);
+    }
+}
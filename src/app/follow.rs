@@ -0,0 +1,49 @@
+//! ファイル追従モード（コミットを切り替えても同じパスのファイルを選択し続ける）
+
+use super::*;
+
+impl App {
+    /// f: 選択中のファイルの追従を切り替える
+    pub(super) fn toggle_follow_file(&mut self) {
+        if self.follow_file.take().is_some() {
+            self.status_message = Some(StatusMessage::info("✓ Stopped following file"));
+            return;
+        }
+        let Some(file) = self.current_file() else {
+            return;
+        };
+        self.follow_file = Some(file.filename.clone());
+        self.status_message = Some(StatusMessage::info(
+            "✓ Following file across commits (f to stop)",
+        ));
+    }
+
+    /// 追従中にファイルを選び直したら、追従するパスも選んだファイルにする
+    pub(super) fn update_followed_file(&mut self) {
+        if self.follow_file.is_none() {
+            return;
+        }
+        if let Some(file) = self.current_file() {
+            self.follow_file = Some(file.filename.clone());
+        }
+    }
+
+    /// 追従中のパスが選択中のコミットの File Tree に表示されていればそのインデックス
+    pub(super) fn followed_file_index(&self) -> Option<usize> {
+        let path = self.follow_file.as_deref()?;
+        let idx = self
+            .current_files()
+            .iter()
+            .position(|f| f.filename == path)?;
+        self.file_tree_rows()
+            .contains(&FileRow::File(idx))
+            .then_some(idx)
+    }
+
+    /// 追従中のパスを選択中のコミットが変更していないか（ファイル読み込み中は false）
+    pub(super) fn followed_file_missing(&self) -> bool {
+        self.follow_file.is_some()
+            && !self.current_files().is_empty()
+            && self.followed_file_index().is_none()
+    }
+}
@@ -0,0 +1,140 @@
+//! GitHub がパッチを省略した大きなファイルの全文取得と新規ファイル表示
+
+use super::*;
+use crate::git::diff::compute_patch;
+use crate::github::{commits, files};
+
+impl App {
+    /// GitHub がパッチを省略したか（テキストの変更行があるのに patch がない）
+    pub(super) fn is_patch_omitted(file: &DiffFile) -> bool {
+        file.patch.is_none() && file.additions + file.deletions > 0
+    }
+
+    /// 現在のファイルが全文取得済みの新規ファイルか（新規ファイル表示モード）
+    pub(super) fn is_new_file_view(&self) -> bool {
+        let Some(file) = self.current_file() else {
+            return false;
+        };
+        file.status == "added"
+            && self.current_commit_sha().is_some_and(|sha| {
+                self.full_content_loaded
+                    .contains(&(sha, file.filename.clone()))
+            })
+    }
+
+    /// F: パッチが省略されたファイルの全文取得（新規）またはローカル diff 計算を要求する
+    pub(super) fn request_full_content(&mut self) {
+        let Some(file) = self.current_file() else {
+            return;
+        };
+        if !Self::is_patch_omitted(file) {
+            self.status_message = Some(StatusMessage::error("✗ Diff is already available"));
+            return;
+        }
+        if self.is_all_changes_selected() {
+            self.status_message = Some(StatusMessage::error(
+                "✗ Select a commit to load the full diff",
+            ));
+            return;
+        }
+        if self.reject_in_interdiff() {
+            return;
+        }
+        self.needs_full_content = true;
+    }
+
+    /// 全文を取得して合成パッチとして files_map に格納する（draw 後に呼ばれる）
+    pub(super) fn execute_full_content(&mut self) {
+        let Some(sha) = self.current_commit_sha() else {
+            return;
+        };
+        let Some((filename, status)) = self
+            .current_file()
+            .map(|f| (f.filename.clone(), f.status.clone()))
+        else {
+            return;
+        };
+        if status != "added" {
+            self.execute_local_diff(&sha, &filename, &status);
+            return;
+        }
+        let Some((owner, repo)) = self.parse_repo() else {
+            self.status_message = Some(StatusMessage::error("✗ Invalid repo format"));
+            return;
+        };
+
+        match files::fetch_file_content(owner, repo, &filename, &sha) {
+            Ok(Some(content)) => {
+                let patch = files::added_file_patch(&content);
+                self.set_file_patch(&sha, &filename, patch);
+                self.full_content_loaded.insert((sha, filename));
+                self.status_message = Some(StatusMessage::info("✓ Loaded full file content"));
+            }
+            Ok(None) => {
+                self.status_message = Some(StatusMessage::error("✗ File is binary"));
+            }
+            Err(e) => {
+                self.status_message = Some(StatusMessage::error(format!("✗ Failed: {}", e)));
+            }
+        }
+    }
+
+    /// 親コミットと対象コミットのファイル内容を取得し、diff をローカルで計算する
+    fn execute_local_diff(&mut self, sha: &str, filename: &str, status: &str) {
+        let Some((owner, repo)) = self.parse_repo() else {
+            self.status_message = Some(StatusMessage::error("✗ Invalid repo format"));
+            return;
+        };
+
+        let result = (|| -> Result<Option<String>> {
+            let parent = commits::fetch_parent_sha(owner, repo, sha)?;
+            // 削除ファイルは新側、追加ファイルは旧側が存在しない
+            let old = files::fetch_file_content(owner, repo, filename, &parent)?;
+            let new = if matches!(status, "removed" | "deleted") {
+                Some(String::new())
+            } else {
+                files::fetch_file_content(owner, repo, filename, sha)?
+            };
+            let (Some(old), Some(new)) = (old, new) else {
+                return Ok(None);
+            };
+            Ok(Some(compute_patch(&old, &new)?))
+        })();
+
+        match result {
+            Ok(Some(patch)) if patch.is_empty() => {
+                self.status_message = Some(StatusMessage::info("✓ No textual changes"));
+            }
+            Ok(Some(patch)) => {
+                self.set_file_patch(sha, filename, patch);
+                self.status_message = Some(StatusMessage::info("✓ Computed diff locally"));
+            }
+            Ok(None) => {
+                self.status_message = Some(StatusMessage::error("✗ File is binary"));
+            }
+            Err(e) => {
+                self.status_message = Some(StatusMessage::error(format!("✗ Failed: {}", e)));
+            }
+        }
+    }
+
+    /// files_map 内のファイルにパッチをセットし、diff 表示キャッシュを破棄する
+    pub(super) fn set_file_patch(&mut self, sha: &str, filename: &str, patch: String) {
+        if let Some(file) = self
+            .files_map
+            .get_mut(sha)
+            .and_then(|files| files.iter_mut().find(|f| f.filename == filename))
+        {
+            file.patch = Some(patch);
+            self.files_usage_stale = true;
+        }
+        self.diff.highlight_cache.clear();
+        self.diff.visual_offsets = None;
+        self.diff.cursor_line = 0;
+        self.diff.scroll = 0;
+        // パッチが変わったため保存位置と hunk 状態は使えない
+        let key = (sha.to_string(), filename.to_string());
+        self.diff.positions.remove(&key);
+        self.hunk_states.remove(&key);
+    }
+}
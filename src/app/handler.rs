@@ -22,7 +22,7 @@ impl App {
             Panel::CommitList => {
                 let relative_y = y.saturating_sub(self.layout.commit_list_rect.y + 1);
                 let idx = self.commit_list_state.offset() + relative_y as usize;
-                if idx < self.commits.len() {
+                if idx < self.commit_row_count() {
                     let old = self.commit_list_state.selected();
                     self.commit_list_state.select(Some(idx));
                     if old != Some(idx) {
@@ -32,10 +32,14 @@ impl App {
             }
             Panel::FileTree => {
                 let relative_y = y.saturating_sub(self.layout.file_tree_rect.y + 1);
-                let idx = self.file_list_state.offset() + relative_y as usize;
-                if idx < self.current_files().len() {
-                    self.file_list_state.select(Some(idx));
-                    self.reset_cursor();
+                let row = self.file_list_state.offset() + relative_y as usize;
+                match self.file_tree_rows().get(row) {
+                    Some(FileRow::File(idx)) => {
+                        self.file_list_state.select(Some(*idx));
+                        self.reset_cursor();
+                    }
+                    Some(FileRow::TestsHeader) => self.toggle_tests_collapsed(),
+                    None => {}
                 }
             }
             Panel::DiffView => {
@@ -93,7 +97,7 @@ impl App {
             Panel::CommitList if !self.commits.is_empty() => {
                 let current = self.commit_list_state.selected().unwrap_or(0);
                 let next = if down {
-                    (current + 1).min(self.commits.len() - 1)
+                    (current + 1).min(self.commit_row_count() - 1)
                 } else {
                     current.saturating_sub(1)
                 };
@@ -106,11 +110,7 @@ impl App {
                 let files_len = self.current_files().len();
                 if files_len > 0 {
                     let current = self.file_list_state.selected().unwrap_or(0);
-                    let next = if down {
-                        (current + 1).min(files_len - 1)
-                    } else {
-                        current.saturating_sub(1)
-                    };
+                    let next = self.adjacent_tree_file(current, down);
                     if next != current {
                         self.file_list_state.select(Some(next));
                         self.reset_cursor();
@@ -198,7 +198,47 @@ impl App {
             return Ok(());
         }
 
-        match event::read()? {
+        let event = event::read()?;
+        if let Some(perf) = &mut self.perf {
+            perf.record_input();
+        }
+        match event {
+            // 性能オーバーレイ（デバッグ用の隠しキー、全モード共通）
+            Event::Key(key) if key.kind == KeyEventKind::Press && key.code == KeyCode::F(12) => {
+                self.toggle_perf_overlay();
+            }
+            // raw mode では SIGTSTP が発生しないため Ctrl+Z を自前で処理（全モード共通）
+            Event::Key(key)
+                if key.kind == KeyEventKind::Press
+                    && key.code == KeyCode::Char('z')
+                    && key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                if cfg!(unix) {
+                    self.needs_suspend = true;
+                } else {
+                    self.status_message = Some(StatusMessage::error(
+                        "✗ Suspend is not supported on this platform",
+                    ));
+                }
+            }
+            // 編集中のコメントを外部エディタで開く（エディタのモード共通、行末移動は End）
+            Event::Key(key)
+                if key.kind == KeyEventKind::Press
+                    && key.code == KeyCode::Char('e')
+                    && key.modifiers.contains(KeyModifiers::CONTROL)
+                    && Self::is_editor_mode(self.mode) =>
+            {
+                self.needs_external_editor = true;
+            }
+            // レビュー送信（編集中のコメントは下書きに退避、送信・確認系のモード以外で共通）
+            Event::Key(key)
+                if key.kind == KeyEventKind::Press
+                    && key.code == KeyCode::Char('s')
+                    && key.modifiers.contains(KeyModifiers::ALT)
+                    && self.can_open_review_submit_globally() =>
+            {
+                self.open_review_submit_globally();
+            }
             Event::Key(key) if key.kind == KeyEventKind::Press => match self.mode {
                 AppMode::Normal => self.handle_normal_mode(key.code, key.modifiers),
                 AppMode::LineSelect => self.handle_line_select_mode(key.code),
@@ -207,14 +247,40 @@ impl App {
                     self.handle_issue_comment_input_mode(key.code, key.modifiers)
                 }
                 AppMode::ReplyInput => self.handle_reply_input_mode(key.code, key.modifiers),
+                AppMode::CommentEditInput => {
+                    self.handle_comment_edit_input_mode(key.code, key.modifiers)
+                }
+                AppMode::CommentDeleteConfirm => self.handle_comment_delete_confirm_mode(key.code),
                 AppMode::CommentView => self.handle_comment_view_mode(key.code),
                 AppMode::ReviewSubmit => self.handle_review_submit_mode(key.code),
                 AppMode::ReviewBodyInput => {
                     self.handle_review_body_input_mode(key.code, key.modifiers)
                 }
                 AppMode::QuitConfirm => self.handle_quit_confirm_mode(key.code),
+                AppMode::QuitWait => self.handle_quit_wait_mode(key.code),
                 AppMode::Help => self.handle_help_mode(key.code),
                 AppMode::MediaViewer => self.handle_media_viewer_mode(key.code),
+                AppMode::CommandOutput => self.handle_command_output_mode(key.code),
+                AppMode::Checklist => self.handle_checklist_mode(key.code),
+                AppMode::DiffSearch => self.handle_diff_search_mode(key.code, key.modifiers),
+                AppMode::Dashboard => self.handle_dashboard_mode(key.code),
+                AppMode::FileCompare => self.handle_file_compare_mode(key.code),
+                AppMode::FileHistory => self.handle_file_history_mode(key.code),
+                AppMode::LargePrSummary => self.handle_large_pr_summary_mode(key.code),
+                AppMode::LinkList => self.handle_link_list_mode(key.code),
+                AppMode::StatusHistory => self.handle_status_history_mode(key.code),
+                AppMode::Checks => self.handle_checks_mode(key.code),
+                AppMode::FileFinder => self.handle_file_finder_mode(key.code, key.modifiers),
+                AppMode::Stats => self.handle_stats_mode(key.code),
+                AppMode::NoteInput => self.handle_note_input_mode(key.code, key.modifiers),
+                AppMode::UpdateBranch => self.handle_update_branch_mode(key.code),
+                AppMode::AutoMerge => self.handle_auto_merge_mode(key.code),
+                AppMode::PrStateChange => self.handle_pr_state_change_mode(key.code),
+                AppMode::CheckoutConfirm => self.handle_checkout_confirm_mode(key.code),
+                AppMode::AllowCommand => self.handle_allow_command_mode(key.code),
+                AppMode::DiscardConfirm => self.handle_discard_confirm_mode(key.code),
+                AppMode::PendingComments => self.handle_pending_comments_mode(key.code),
+                AppMode::AttachImage => self.handle_attach_image_mode(key.code),
             },
             Event::Mouse(mouse) if self.mode == AppMode::Help => match mouse.kind {
                 MouseEventKind::ScrollDown => {
@@ -246,13 +312,99 @@ impl App {
                     _ => {}
                 }
             }
+            Event::Resize(_, _) => self.handle_resize(),
             _ => {}
         }
         Ok(())
     }
 
+    /// 端末リサイズ時に幅・高さ依存のキャッシュを無効化する（クランプは次の draw 後）
+    pub(super) fn handle_resize(&mut self) {
+        self.pr_desc_visual_total = 0;
+        self.commit_msg_visual_total = 0;
+        self.conversation_visual_total = 0;
+        self.commit_overview_visual_total = 0;
+        self.diff.visual_offsets = None;
+        self.needs_resize_clamp = true;
+    }
+
+    /// リサイズ後の draw で更新されたサイズを使ってスクロール位置・カーソルを補正する
+    pub(super) fn clamp_after_resize(&mut self) {
+        self.clamp_pr_desc_scroll();
+        self.clamp_commit_msg_scroll();
+        self.clamp_conversation_scroll();
+        self.clamp_commit_overview_scroll();
+        self.review.viewing_comment_scroll = self
+            .review
+            .viewing_comment_scroll
+            .min(self.review.comment_view_max_scroll);
+        self.ensure_cursor_visible();
+    }
+
     /// 通常モードのキー処理
     pub(super) fn handle_normal_mode(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        // emacs キーマップでは Ctrl+N / Ctrl+P を j / k として扱う
+        let (code, modifiers) = match (self.keymap, code) {
+            (Keymap::Emacs, KeyCode::Char('n')) if modifiers.contains(KeyModifiers::CONTROL) => {
+                (KeyCode::Char('j'), KeyModifiers::NONE)
+            }
+            (Keymap::Emacs, KeyCode::Char('p')) if modifiers.contains(KeyModifiers::CONTROL) => {
+                (KeyCode::Char('k'), KeyModifiers::NONE)
+            }
+            // PgDn / PgUp / End は Ctrl+F / Ctrl+B / G と同じ（Home は g と違い 2 文字目を待たない）
+            (_, KeyCode::PageDown) => (KeyCode::Char('f'), KeyModifiers::CONTROL),
+            (_, KeyCode::PageUp) => (KeyCode::Char('b'), KeyModifiers::CONTROL),
+            (_, KeyCode::End) => (KeyCode::Char('G'), KeyModifiers::NONE),
+            _ => (code, modifiers),
+        };
+        // gl: リンク一覧 / gm: メッセージ履歴 / gf: ファイル検索 / gd: スレッドの diff 位置へ（g 単体の先頭移動は実行済みなので、それ以外は通常処理へ）
+        if self.pending_key == Some('g') {
+            self.pending_key = None;
+            if code == KeyCode::Char('l') {
+                self.open_link_list();
+                return;
+            }
+            if code == KeyCode::Char('m') {
+                self.open_status_history();
+                return;
+            }
+            if code == KeyCode::Char('f') {
+                self.open_file_finder();
+                return;
+            }
+            // gd: g で先頭へ移動したカーソルを戻してからスレッドの diff 位置へ
+            if code == KeyCode::Char('d')
+                && let Some((cursor, scroll)) = self.conversation_g_origin.take()
+            {
+                self.conversation_cursor = cursor;
+                self.conversation_scroll = scroll;
+                self.jump_to_conversation_thread();
+                return;
+            }
+        }
+
+        // Esc: 表示中のエラーを先に消す（パネルごとの Esc はその次から）
+        if code == KeyCode::Esc && self.dismiss_error_message() {
+            return;
+        }
+
+        // zr / zm / za: Conversation の折りたたみ（z 単体のズームは実行済みなので取り消す）
+        if self.pending_key == Some('z') {
+            self.pending_key = None;
+            if matches!(code, KeyCode::Char('r' | 'm' | 'a')) {
+                self.toggle_zoom();
+                match (self.focused_panel, code) {
+                    (Panel::DiffView, KeyCode::Char('r')) => self.set_all_hunks_collapsed(false),
+                    (Panel::DiffView, KeyCode::Char('m')) => self.set_all_hunks_collapsed(true),
+                    (Panel::DiffView, _) => self.toggle_hunk_collapse(),
+                    (_, KeyCode::Char('r')) => self.set_all_conversation_folds(false),
+                    (_, KeyCode::Char('m')) => self.set_all_conversation_folds(true),
+                    _ => self.toggle_conversation_fold(),
+                }
+                return;
+            }
+        }
+
         // 2キーシーケンスの処理（] or [ の後の2文字目）
         if let Some(first) = self.pending_key.take() {
             if self.focused_panel == Panel::DiffView {
@@ -263,24 +415,34 @@ impl App {
                     ('[', KeyCode::Char('h')) => self.jump_to_prev_hunk(),
                     (']', KeyCode::Char('n')) => self.jump_to_next_comment(),
                     ('[', KeyCode::Char('n')) => self.jump_to_prev_comment(),
+                    (']', KeyCode::Char('s')) => self.jump_to_next_match(),
+                    ('[', KeyCode::Char('s')) => self.jump_to_prev_match(),
+                    (']', KeyCode::Char('f')) => self.jump_to_adjacent_file(true),
+                    ('[', KeyCode::Char('f')) => self.jump_to_adjacent_file(false),
                     _ => {} // 不明な2文字目は無視
                 }
             }
             return;
         }
 
-        if self.handle_global_keys(code, modifiers) {
-            return;
+        if !self.handle_global_keys(code, modifiers) {
+            match self.focused_panel {
+                Panel::PrDescription => self.handle_pr_desc_keys(code),
+                Panel::CommitList => self.handle_commit_list_keys(code),
+                Panel::FileTree => self.handle_file_tree_keys(code),
+                Panel::CommitMessage => self.handle_commit_msg_keys(code),
+                Panel::DiffView => self.handle_diff_view_keys(code),
+                Panel::Conversation => self.handle_conversation_keys(code),
+                Panel::CommitOverview => self.handle_commit_overview_keys(code),
+            }
         }
-
-        match self.focused_panel {
-            Panel::PrDescription => self.handle_pr_desc_keys(code),
-            Panel::CommitList => self.handle_commit_list_keys(code),
-            Panel::FileTree => self.handle_file_tree_keys(code),
-            Panel::CommitMessage => self.handle_commit_msg_keys(code),
-            Panel::DiffView => self.handle_diff_view_keys(code),
-            Panel::Conversation => self.handle_conversation_keys(code),
-            Panel::CommitOverview => self.handle_commit_overview_keys(code),
+        // 範囲選択は Commits ペインを離れたら解除する
+        if self.focused_panel != Panel::CommitList {
+            self.commit_range_anchor = None;
+        }
+        // カーソルが乗った Conversation エントリは既読にする
+        if self.focused_panel == Panel::Conversation {
+            self.mark_conversation_seen();
         }
     }
 
@@ -289,7 +451,7 @@ impl App {
         match code {
             KeyCode::Char('q') => {
                 if self.review.pending_comments.is_empty() {
-                    self.should_quit = true;
+                    self.request_quit();
                 } else {
                     self.mode = AppMode::QuitConfirm;
                 }
@@ -302,7 +464,7 @@ impl App {
             KeyCode::Tab | KeyCode::Char('l') | KeyCode::Right => self.next_panel(),
             KeyCode::BackTab | KeyCode::Char('h') | KeyCode::Left => self.prev_panel(),
             // 数字キーでペイン直接ジャンプ
-            KeyCode::Char('1') => self.focused_panel = Panel::PrDescription,
+            KeyCode::Char('1') => self.focused_panel = self.sidebar_top_panel(),
             KeyCode::Char('2') => self.focused_panel = Panel::CommitList,
             KeyCode::Char('3') => self.focused_panel = Panel::FileTree,
             KeyCode::Char('j') | KeyCode::Down => self.select_next(),
@@ -411,29 +573,14 @@ impl App {
                     _ => self.page_up(),
                 }
             }
-            KeyCode::Char('g') => match self.focused_panel {
-                Panel::PrDescription => {
-                    self.pr_desc_scroll = 0;
-                }
-                Panel::CommitList | Panel::CommitOverview => {
-                    self.commit_overview_scroll = 0;
-                }
-                Panel::CommitMessage => {
-                    self.commit_msg_scroll = 0;
-                }
-                Panel::Conversation => {
-                    self.conversation_cursor = 0;
-                    self.conversation_scroll = 0;
-                }
-                Panel::DiffView => {
-                    self.diff.cursor_line = 0;
-                    self.diff.scroll = 0;
-                    let max = self.current_diff_line_count();
-                    self.diff.cursor_line = self.skip_hunk_header_forward(0, max);
-                    self.review.viewing_comment_scroll = 0;
-                }
-                _ => {}
-            },
+            KeyCode::Char('g') => {
+                // gl / gm / gd 用に 2 文字目を待つ（g 単体の動作は即時実行）
+                self.pending_key = Some('g');
+                self.conversation_g_origin = (self.focused_panel == Panel::Conversation)
+                    .then_some((self.conversation_cursor, self.conversation_scroll));
+                self.scroll_to_top();
+            }
+            KeyCode::Home => self.scroll_to_top(),
             KeyCode::Char('G') => match self.focused_panel {
                 Panel::PrDescription => {
                     self.pr_desc_scroll = self.pr_desc_max_scroll();
@@ -454,30 +601,14 @@ impl App {
                 }
                 _ => {}
             },
-            KeyCode::Char('S') => {
-                // レビュー送信は conversation データに依存 → 個別フェーズチェック
-                if self.loading.conversation == LoadPhase::Loading {
-                    self.status_message =
-                        Some(StatusMessage::error("✗ Conversation loading. Please wait."));
-                } else {
-                    self.review.review_event_cursor = 0;
-                    self.mode = AppMode::ReviewSubmit;
-                }
-            }
+            KeyCode::Char('S') => self.open_review_submit(),
             KeyCode::Char('w') => {
-                if self.diff.wrap {
-                    // ON → OFF: 表示行→論理行に変換
-                    let logical = self.visual_to_logical_line(self.diff.scroll as usize);
-                    self.diff.wrap = false;
-                    self.diff.scroll = logical as u16;
-                } else {
-                    // OFF → ON: 論理行→表示行に変換
-                    let visual = self.visual_line_offset(self.diff.scroll as usize);
-                    self.diff.wrap = true;
-                    self.diff.scroll = visual as u16;
-                }
+                // 画面先頭の論理行を保ったまま切り替える（折りたたみ中は OFF でも表示行単位）
+                let top = self.visual_to_logical_line(self.diff.scroll as usize);
+                self.diff.wrap = !self.diff.wrap;
                 // 次の render で再計算されるまでの1フレームの不整合を防ぐ
                 self.diff.visual_offsets = None;
+                self.diff.scroll = self.visual_line_offset(top) as u16;
                 self.ensure_cursor_visible();
             }
             KeyCode::Char('n') => {
@@ -486,11 +617,24 @@ impl App {
                 self.ensure_cursor_visible();
             }
             KeyCode::Char('z') => {
-                self.zoomed = !self.zoomed;
-                // zoom 切替で描画幅が変わり、Wrap 済み視覚行数も変わる
-                self.pr_desc_visual_total = 0;
-                self.commit_msg_visual_total = 0;
-                self.conversation_visual_total = 0;
+                // Conversation / DiffView では zr / zm / za 用に 2 文字目を待つ（z 単体の動作は即時実行）
+                if matches!(self.focused_panel, Panel::Conversation | Panel::DiffView) {
+                    self.pending_key = Some('z');
+                }
+                self.toggle_zoom();
+            }
+            KeyCode::Char('V') => self.toggle_conversation_in_sidebar(),
+            KeyCode::Char('X') => self.export_report(),
+            KeyCode::Char('L') => self.open_checklist(),
+            KeyCode::Char('P') => self.open_pending_comments(),
+            KeyCode::Char('D') => self.open_dashboard(),
+            KeyCode::Char('!') => {
+                if self.run_command.is_none() {
+                    self.status_message =
+                        Some(StatusMessage::error("✗ No command configured (use --exec)"));
+                } else {
+                    self.needs_run_command = true;
+                }
             }
             KeyCode::Char('R') => {
                 // リロードは全データに依存 → いずれかの Phase が Loading なら拒否
@@ -508,8 +652,24 @@ impl App {
                     self.needs_reload = true;
                 }
             }
+            KeyCode::Char('I') => {
+                self.mode = AppMode::Stats;
+            }
+            KeyCode::Char('C') => self.open_checks(),
+            // emacs キーマップでは Ctrl+P は k になっているので gf を使う
+            KeyCode::Char('p') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_file_finder();
+            }
+            KeyCode::Char('T') => self.toggle_time_format(),
+            KeyCode::Char('U') => self.open_update_branch(),
+            KeyCode::Char('O') => self.open_checkout_confirm(),
+            KeyCode::Char('B') => self.open_large_pr_summary(),
+            KeyCode::Char('W') => self.open_auto_merge(),
+            KeyCode::Char('Z') => self.open_pr_state_change(),
             KeyCode::Char('?') => {
                 self.help_scroll = 0;
+                self.help_filter.clear();
+                self.help_filter_input = false;
                 self.help_context_panel = self.focused_panel;
                 self.mode = AppMode::Help;
             }
@@ -521,15 +681,109 @@ impl App {
         true
     }
 
+    /// フォーカス中パネルの先頭へ移動（g）
+    fn scroll_to_top(&mut self) {
+        match self.focused_panel {
+            Panel::PrDescription => {
+                self.pr_desc_scroll = 0;
+            }
+            Panel::CommitList | Panel::CommitOverview => {
+                self.commit_overview_scroll = 0;
+            }
+            Panel::CommitMessage => {
+                self.commit_msg_scroll = 0;
+            }
+            Panel::Conversation => {
+                self.conversation_cursor = 0;
+                self.conversation_scroll = 0;
+            }
+            Panel::DiffView => {
+                self.diff.cursor_line = 0;
+                self.diff.scroll = 0;
+                let max = self.current_diff_line_count();
+                self.diff.cursor_line = self.skip_hunk_header_forward(0, max);
+                self.review.viewing_comment_scroll = 0;
+            }
+            _ => {}
+        }
+    }
+
+    /// S: レビュー送信ダイアログを開く
+    fn open_review_submit(&mut self) {
+        // レビュー送信は conversation データに依存 → 個別フェーズチェック
+        if self.loading.conversation == LoadPhase::Loading {
+            self.status_message =
+                Some(StatusMessage::error("✗ Conversation loading. Please wait."));
+        } else {
+            self.review.review_event_cursor = 0;
+            self.mode = AppMode::ReviewSubmit;
+        }
+    }
+
+    /// Alt+S を受け付けるモードか（送信中・確認ダイアログ・メモや検索の入力中は除く）
+    pub(super) fn can_open_review_submit_globally(&self) -> bool {
+        !matches!(
+            self.mode,
+            AppMode::ReviewSubmit
+                | AppMode::ReviewBodyInput
+                | AppMode::QuitConfirm
+                | AppMode::QuitWait
+                | AppMode::DiscardConfirm
+                | AppMode::UpdateBranch
+                | AppMode::AutoMerge
+                | AppMode::PrStateChange
+                | AppMode::CheckoutConfirm
+                | AppMode::AllowCommand
+                | AppMode::CommentEditInput
+                | AppMode::CommentDeleteConfirm
+                | AppMode::NoteInput
+                | AppMode::AttachImage
+                | AppMode::DiffSearch
+                | AppMode::MediaViewer
+        )
+    }
+
+    /// Alt+S: どのモードからでもレビュー送信ダイアログを開く
+    pub(super) fn open_review_submit_globally(&mut self) {
+        if self.loading.conversation == LoadPhase::Loading {
+            self.status_message =
+                Some(StatusMessage::error("✗ Conversation loading. Please wait."));
+            return;
+        }
+        match self.mode {
+            AppMode::CommentInput | AppMode::ReplyInput | AppMode::IssueCommentInput => {
+                self.stash_draft_and_close();
+            }
+            AppMode::LineSelect => self.line_selection = None,
+            _ => {}
+        }
+        self.review.viewing_comments.clear();
+        self.review.viewing_comment_scroll = 0;
+        self.open_review_submit();
+    }
+
+    /// z: ペインのズームを切り替える
+    fn toggle_zoom(&mut self) {
+        self.zoomed = !self.zoomed;
+        // zoom 切替で描画幅が変わり、Wrap 済み視覚行数も変わる
+        self.pr_desc_visual_total = 0;
+        self.commit_msg_visual_total = 0;
+        self.conversation_visual_total = 0;
+    }
+
     /// PR Description パネルのキー処理
     fn handle_pr_desc_keys(&mut self, code: KeyCode) {
         match code {
-            KeyCode::Enter => {
+            // 表示中の <details> があれば開閉、なければ Conversation へ
+            KeyCode::Enter if !self.toggle_pr_desc_fold() => {
                 self.focused_panel = Panel::Conversation;
             }
             KeyCode::Char('o') => {
                 self.enter_media_viewer();
             }
+            KeyCode::Esc if self.conversation_in_sidebar => {
+                self.focused_panel = Panel::Conversation;
+            }
             _ => {}
         }
     }
@@ -544,10 +798,19 @@ impl App {
     /// Commit List パネルのキー処理
     fn handle_commit_list_keys(&mut self, code: KeyCode) {
         match code {
+            // All changes は単一コミットの概要がないので Files へ
+            KeyCode::Enter if self.is_all_changes_selected() => {
+                self.focused_panel = Panel::FileTree;
+            }
             KeyCode::Enter => {
                 self.focused_panel = Panel::CommitOverview;
             }
+            KeyCode::Char('x') if self.commit_range_anchor.is_some() => {
+                self.toggle_commit_range_viewed();
+            }
             KeyCode::Char('x') => self.toggle_commit_viewed(),
+            KeyCode::Char('v') if !self.is_all_changes_selected() => self.toggle_commit_range(),
+            KeyCode::Esc => self.commit_range_anchor = None,
             KeyCode::Char('y') => {
                 if let Some(idx) = self.commit_list_state.selected()
                     && let Some(commit) = self.commits.get(idx)
@@ -564,6 +827,8 @@ impl App {
                     self.copy_to_clipboard(&msg, "message");
                 }
             }
+            KeyCode::Char('p') => self.request_file_compare(),
+            KeyCode::Char('m') => self.toggle_interdiff_base(),
             _ => {}
         }
     }
@@ -579,6 +844,14 @@ impl App {
                     self.copy_to_clipboard(&path, "path");
                 }
             }
+            KeyCode::Char('p') => self.toggle_file_pin(),
+            KeyCode::Char('H') => self.open_file_history(),
+            KeyCode::Char('N') => self.open_note_editor(),
+            KeyCode::Char('E') => self.export_notes(),
+            KeyCode::Char('t') => self.cycle_test_split(),
+            KeyCode::Char('f') => self.toggle_follow_file(),
+            KeyCode::Char('}') => self.jump_to_unviewed_file(true),
+            KeyCode::Char('{') => self.jump_to_unviewed_file(false),
             _ => {}
         }
     }
@@ -591,7 +864,10 @@ impl App {
                 let comments = self.comments_at_diff_line(self.diff.cursor_line);
                 if !comments.is_empty() {
                     self.review.viewing_comments = comments;
+                    self.review.quote_select = None;
+                    self.select_default_viewing_comment();
                     self.mode = AppMode::CommentView;
+                    self.scroll_thread_into_view();
                 }
             }
             KeyCode::Esc => {
@@ -616,8 +892,58 @@ impl App {
                     });
                     self.review.comment_editor.clear();
                     self.mode = AppMode::CommentInput;
+                    self.restore_draft();
                 }
             }
+            KeyCode::Char('/') => {
+                self.search_input = self.diff.search_query.clone();
+                self.mode = AppMode::DiffSearch;
+            }
+            KeyCode::Char('F') => self.request_full_content(),
+            KeyCode::Char('x') => self.toggle_hunk_reviewed(),
+            KeyCode::Char('}') => self.jump_to_unviewed_file(true),
+            KeyCode::Char('{') => self.jump_to_unviewed_file(false),
+            KeyCode::Char('A') => {
+                if self.reject_in_interdiff() {
+                    return;
+                }
+                if self.loading.conversation == LoadPhase::Loading {
+                    self.status_message =
+                        Some(StatusMessage::error("✗ Conversation loading. Please wait."));
+                    return;
+                }
+                let matches = self.search_match_lines();
+                let Some(&first) = matches.first() else {
+                    self.status_message = Some(StatusMessage::error(
+                        "✗ No search matches (use / to search)",
+                    ));
+                    return;
+                };
+                // カーソルがマッチ行になければ最初のマッチへ移動
+                if !matches.contains(&self.diff.cursor_line) {
+                    self.diff.cursor_line = first;
+                    self.ensure_cursor_visible();
+                }
+                self.line_selection = Some(LineSelection {
+                    anchor: self.diff.cursor_line,
+                });
+                self.queue_all_matches = true;
+                self.review.comment_editor.clear();
+                self.mode = AppMode::CommentInput;
+                self.restore_draft();
+            }
+            KeyCode::Char('i') => {
+                if self.coverage.is_none() {
+                    self.status_message = Some(StatusMessage::error(
+                        "✗ No coverage report (use --coverage)",
+                    ));
+                    return;
+                }
+                self.diff.show_coverage = !self.diff.show_coverage;
+                let state = if self.diff.show_coverage { "on" } else { "off" };
+                self.status_message =
+                    Some(StatusMessage::info(format!("✓ Coverage overlay {state}")));
+            }
             KeyCode::Tab | KeyCode::BackTab => {
                 self.focused_panel = Panel::CommitMessage;
             }
@@ -641,7 +967,8 @@ impl App {
     /// Conversation パネルのキー処理
     fn handle_conversation_keys(&mut self, code: KeyCode) {
         match code {
-            KeyCode::Esc => {
+            // 入れ替え中はサイドバーにいるので戻り先がない
+            KeyCode::Esc if !self.conversation_in_sidebar => {
                 self.focused_panel = Panel::PrDescription;
             }
             KeyCode::Char('c') => {
@@ -660,11 +987,18 @@ impl App {
                     self.review.reply_to_comment_id = Some(root_comment_id);
                     self.review.comment_editor.clear();
                     self.mode = AppMode::ReplyInput;
+                    self.restore_draft();
                     return;
                 }
                 self.review.comment_editor.clear();
                 self.mode = AppMode::IssueCommentInput;
+                self.restore_draft();
             }
+            KeyCode::Enter => self.jump_to_conversation_thread(),
+            KeyCode::Char('r') => self.toggle_conversation_resolve(),
+            KeyCode::Char('m') => self.toggle_thread_mute(),
+            KeyCode::Char('M') => self.request_pr_mute_toggle(),
+            KeyCode::Char('u') => self.jump_to_first_unread(),
             _ => {}
         }
     }
@@ -673,14 +1007,7 @@ impl App {
     pub(super) fn handle_reply_input_mode(&mut self, code: KeyCode, modifiers: KeyModifiers) {
         match code {
             KeyCode::Esc => {
-                self.review.comment_editor.clear();
-                self.review.reply_to_comment_id = None;
-                // CommentView から入った場合（viewing_comments が残っている）は CommentView に戻る
-                if !self.review.viewing_comments.is_empty() {
-                    self.mode = AppMode::CommentView;
-                } else {
-                    self.mode = AppMode::Normal;
-                }
+                self.request_cancel_editor();
                 return;
             }
             KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) => {
@@ -693,6 +1020,45 @@ impl App {
                 self.mode = AppMode::Normal;
                 return;
             }
+            KeyCode::Char('r') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.cycle_quick_reply();
+            }
+            KeyCode::Char('l') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.insert_commit_link();
+            }
+            KeyCode::Char('x') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.toggle_resolve_after_reply();
+            }
+            KeyCode::Char('t') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_attach_prompt();
+                return;
+            }
+            _ => {
+                self.review.comment_editor.handle_key(code, modifiers);
+            }
+        }
+        self.review
+            .comment_editor
+            .ensure_visible(editor::EDITOR_VISIBLE_HEIGHT);
+    }
+
+    /// メモ入力モードのキー処理
+    pub(super) fn handle_note_input_mode(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        match code {
+            KeyCode::Esc => {
+                self.review.comment_editor.clear();
+                self.note_target = None;
+                self.mode = AppMode::Normal;
+                return;
+            }
+            KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.save_note();
+                return;
+            }
+            KeyCode::Char('p') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.promote_note();
+                return;
+            }
             _ => {
                 self.review.comment_editor.handle_key(code, modifiers);
             }
@@ -709,6 +1075,12 @@ impl App {
             KeyCode::Char('j') | KeyCode::Down => self.extend_selection_down(),
             KeyCode::Char('k') | KeyCode::Up => self.extend_selection_up(),
             KeyCode::Char('c') => self.enter_comment_input_mode(),
+            // Ctrl+S も同じ（修飾キーは見ない）
+            KeyCode::Char('s') => self.enter_suggestion_input_mode(),
+            KeyCode::Char('y') => self.copy_permalink(),
+            KeyCode::Char('Y') => self.copy_diff_snippet(),
+            KeyCode::Char('o') => self.copy_side_text(crate::github::review::Side::Left),
+            KeyCode::Char('n') => self.copy_side_text(crate::github::review::Side::Right),
             _ => {}
         }
     }
@@ -716,13 +1088,17 @@ impl App {
     /// コメント入力モードのキー処理
     pub(super) fn handle_comment_input_mode(&mut self, code: KeyCode, modifiers: KeyModifiers) {
         match code {
-            KeyCode::Esc => self.cancel_comment_input(),
+            KeyCode::Esc => self.request_cancel_editor(),
             KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) => {
                 self.confirm_comment();
             }
             KeyCode::Char('g') if modifiers.contains(KeyModifiers::CONTROL) => {
                 self.insert_suggestion();
             }
+            KeyCode::Char('t') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_attach_prompt();
+                return;
+            }
             _ => {
                 self.review.comment_editor.handle_key(code, modifiers);
             }
@@ -740,9 +1116,7 @@ impl App {
     ) {
         match code {
             KeyCode::Esc => {
-                self.review.comment_editor.clear();
-                self.mode = AppMode::Normal;
-                self.focused_panel = Panel::Conversation;
+                self.request_cancel_editor();
                 return;
             }
             KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) => {
@@ -752,10 +1126,15 @@ impl App {
                     return;
                 }
                 self.needs_issue_comment_submit = true;
+                self.append_posting_issue_comment();
                 self.mode = AppMode::Normal;
                 self.focused_panel = Panel::Conversation;
                 return;
             }
+            KeyCode::Char('t') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_attach_prompt();
+                return;
+            }
             _ => {
                 self.review.comment_editor.handle_key(code, modifiers);
             }
@@ -767,6 +1146,10 @@ impl App {
 
     /// コメントペイン（フォーカス状態）のキー処理
     pub(super) fn handle_comment_view_mode(&mut self, code: KeyCode) {
+        if self.review.quote_select.is_some() {
+            self.handle_quote_select(code);
+            return;
+        }
         match code {
             KeyCode::Esc | KeyCode::Char('q') => {
                 self.review.viewing_comments.clear();
@@ -782,9 +1165,27 @@ impl App {
                 self.review.viewing_comment_scroll =
                     self.review.viewing_comment_scroll.saturating_sub(1);
             }
+            KeyCode::PageDown => {
+                self.review.viewing_comment_scroll = self
+                    .review
+                    .viewing_comment_scroll
+                    .saturating_add(self.review.comment_view_height)
+                    .min(self.review.comment_view_max_scroll);
+            }
+            KeyCode::PageUp => {
+                self.review.viewing_comment_scroll = self
+                    .review
+                    .viewing_comment_scroll
+                    .saturating_sub(self.review.comment_view_height);
+            }
+            KeyCode::Char('g') | KeyCode::Home => self.review.viewing_comment_scroll = 0,
+            KeyCode::Char('G') | KeyCode::End => {
+                self.review.viewing_comment_scroll = self.review.comment_view_max_scroll;
+            }
             KeyCode::Char('r') => {
                 self.toggle_resolve_thread();
             }
+            KeyCode::Char('y') => self.copy_permalink(),
             KeyCode::Char('c') => {
                 // viewing_comments からルートコメント ID を取得して返信モードへ
                 if let Some(root_id) =
@@ -793,12 +1194,75 @@ impl App {
                     self.review.reply_to_comment_id = Some(root_id);
                     self.review.comment_editor.clear();
                     self.mode = AppMode::ReplyInput;
+                    self.restore_draft();
+                }
+            }
+            KeyCode::Char('J') => self.move_viewing_comment_cursor(1),
+            KeyCode::Char('K') => self.move_viewing_comment_cursor(-1),
+            KeyCode::Char('>') => self.start_quote_select(),
+            KeyCode::Char('e') => self.start_comment_edit(),
+            KeyCode::Char('d') => self.request_comment_delete(),
+            _ => {}
+        }
+    }
+
+    /// 自分のコメントの編集中のキー処理
+    pub(super) fn handle_comment_edit_input_mode(
+        &mut self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) {
+        match code {
+            KeyCode::Esc => {
+                self.request_cancel_editor();
+                return;
+            }
+            KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) => {
+                if self.review.comment_editor.text().trim().is_empty() {
+                    self.status_message = Some(StatusMessage::error("Comment is empty"));
+                    return;
                 }
+                self.needs_comment_edit = true;
+                self.mode = AppMode::CommentView;
+                return;
+            }
+            _ => {
+                self.review.comment_editor.handle_key(code, modifiers);
+            }
+        }
+        self.review
+            .comment_editor
+            .ensure_visible(editor::EDITOR_VISIBLE_HEIGHT);
+    }
+
+    /// コメント削除の確認中のキー処理
+    pub(super) fn handle_comment_delete_confirm_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('y') => {
+                self.needs_comment_delete = true;
+                self.mode = AppMode::CommentView;
+            }
+            KeyCode::Char('c' | 'n') | KeyCode::Esc => {
+                self.review.editing_comment_id = None;
+                self.mode = AppMode::CommentView;
             }
             _ => {}
         }
     }
 
+    /// 引用する行の選択中のキー処理（CommentView 内）
+    fn handle_quote_select(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.review.quote_select = None,
+            KeyCode::Char('j') | KeyCode::Down => self.move_quote_cursor(1, false),
+            KeyCode::Char('k') | KeyCode::Up => self.move_quote_cursor(-1, false),
+            KeyCode::Char('J') => self.move_quote_cursor(1, true),
+            KeyCode::Char('K') => self.move_quote_cursor(-1, true),
+            KeyCode::Enter | KeyCode::Char('>') => self.reply_with_quote(),
+            _ => {}
+        }
+    }
+
     /// レビュー送信ダイアログのキー処理
     pub(super) fn handle_review_submit_mode(&mut self, code: KeyCode) {
         match code {
@@ -826,8 +1290,25 @@ impl App {
                     self.mode = AppMode::Normal;
                     return;
                 }
-                self.review.review_body_editor.clear();
-                self.mode = AppMode::ReviewBodyInput;
+                self.enter_review_body_input(event);
+            }
+            KeyCode::Char(c) => {
+                let Some(event) = ReviewEvent::ALL.into_iter().find(|e| e.key() == c) else {
+                    return;
+                };
+                // c/a/r: イベントを直接選ぶ（選べない場合は理由を表示してダイアログに留まる）
+                if let Some(reason) = self.review_event_disabled_reason(event) {
+                    self.status_message = Some(StatusMessage::error(format!(
+                        "✗ {}: {}",
+                        event.label(),
+                        crate::i18n::tr(reason)
+                    )));
+                    return;
+                }
+                if let Some(i) = self.available_events().iter().position(|e| *e == event) {
+                    self.review.review_event_cursor = i;
+                }
+                self.enter_review_body_input(event);
             }
             _ => {}
         }
@@ -836,10 +1317,7 @@ impl App {
     /// レビュー本文入力モードのキー処理
     pub(super) fn handle_review_body_input_mode(&mut self, code: KeyCode, modifiers: KeyModifiers) {
         match code {
-            KeyCode::Esc => {
-                self.review.review_body_editor.clear();
-                self.mode = AppMode::ReviewSubmit;
-            }
+            KeyCode::Esc => self.request_cancel_editor(),
             KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) => {
                 let event = self.available_events()[self.review.review_event_cursor];
                 self.review.needs_submit = Some(event);
@@ -865,8 +1343,8 @@ impl App {
             }
             KeyCode::Char('n') => {
                 // 破棄して終了
-                self.review.pending_comments.clear();
-                self.should_quit = true;
+                self.clear_pending_comments();
+                self.request_quit();
             }
             KeyCode::Char('c') | KeyCode::Esc => {
                 // キャンセル
@@ -876,9 +1354,131 @@ impl App {
         }
     }
 
+    /// バックグラウンド処理の終了待ちダイアログのキー処理
+    pub(super) fn handle_quit_wait_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('w') => self.quit_when_idle = true,
+            KeyCode::Char('q') => self.should_quit = true,
+            KeyCode::Char('c') | KeyCode::Esc => {
+                self.quit_when_idle = false;
+                self.mode = AppMode::Normal;
+            }
+            _ => {}
+        }
+    }
+
+    /// エディタ破棄確認ダイアログのキー処理
+    pub(super) fn handle_discard_confirm_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('d') => self.cancel_editor(self.discard_return_mode),
+            KeyCode::Char('s') => self.save_draft_and_close(),
+            KeyCode::Char('k') | KeyCode::Esc => self.mode = self.discard_return_mode,
+            _ => {}
+        }
+    }
+
+    /// ブランチ更新の方式選択モードのキー処理
+    pub(super) fn handle_update_branch_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('m') => {
+                self.needs_update_branch = Some(UpdateMethod::Merge);
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('r') => {
+                self.needs_update_branch = Some(UpdateMethod::Rebase);
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('c') | KeyCode::Esc => {
+                self.mode = AppMode::Normal;
+            }
+            _ => {}
+        }
+    }
+
+    /// 自動マージ設定モードのキー処理
+    pub(super) fn handle_auto_merge_mode(&mut self, code: KeyCode) {
+        let action = match code {
+            KeyCode::Char('m') => AutoMergeAction::Enable(MergeMethod::Merge),
+            KeyCode::Char('s') => AutoMergeAction::Enable(MergeMethod::Squash),
+            KeyCode::Char('r') => AutoMergeAction::Enable(MergeMethod::Rebase),
+            KeyCode::Char('d') if self.auto_merge.is_some() => AutoMergeAction::Disable,
+            KeyCode::Char('c') | KeyCode::Esc => {
+                self.mode = AppMode::Normal;
+                return;
+            }
+            _ => return,
+        };
+        self.needs_auto_merge = Some(action);
+        self.mode = AppMode::Normal;
+    }
+
+    /// PR を閉じる・開き直す確認モードのキー処理
+    pub(super) fn handle_pr_state_change_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('y') => {
+                self.needs_pr_state_change = Some(self.pr_state_change());
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('c' | 'n') | KeyCode::Esc => {
+                self.mode = AppMode::Normal;
+            }
+            _ => {}
+        }
+    }
+
+    /// チェックアウト確認モードのキー処理
+    pub(super) fn handle_checkout_confirm_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('y') => {
+                self.needs_checkout = true;
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('c' | 'n') | KeyCode::Esc => {
+                self.mode = AppMode::Normal;
+            }
+            _ => {}
+        }
+    }
+
+    /// 外部プログラムの実行確認のキー処理（y: 今回だけ、a: 常に許可、n / Esc: 許可しない）
+    pub(super) fn handle_allow_command_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('y') => self.answer_command_permission(crate::exec::Answer::Once),
+            KeyCode::Char('a') => self.answer_command_permission(crate::exec::Answer::Always),
+            KeyCode::Char('n') | KeyCode::Esc => {
+                self.answer_command_permission(crate::exec::Answer::Deny);
+            }
+            _ => {}
+        }
+    }
+
     /// ヘルプ表示モードのキー処理
     pub(super) fn handle_help_mode(&mut self, code: KeyCode) {
+        // 絞り込み入力中は文字をクエリに追加（Enter で確定、Esc で解除）
+        if self.help_filter_input {
+            match code {
+                KeyCode::Esc => {
+                    self.help_filter.clear();
+                    self.help_filter_input = false;
+                }
+                KeyCode::Enter => self.help_filter_input = false,
+                KeyCode::Backspace => {
+                    self.help_filter.pop();
+                }
+                KeyCode::Char(c) => self.help_filter.push(c),
+                _ => {}
+            }
+            self.help_scroll = 0;
+            return;
+        }
         match code {
+            KeyCode::Char('/') => {
+                self.help_filter_input = true;
+            }
+            KeyCode::Esc if !self.help_filter.is_empty() => {
+                self.help_filter.clear();
+                self.help_scroll = 0;
+            }
             KeyCode::Char('?') | KeyCode::Esc | KeyCode::Char('q') => {
                 self.mode = AppMode::Normal;
             }
@@ -888,6 +1488,379 @@ impl App {
             KeyCode::Char('k') | KeyCode::Up => {
                 self.help_scroll = self.help_scroll.saturating_sub(1);
             }
+            // 末尾を超えた分は render でクランプされる
+            KeyCode::PageDown => {
+                self.help_scroll = self.help_scroll.saturating_add(self.help_view_height);
+            }
+            KeyCode::PageUp => {
+                self.help_scroll = self.help_scroll.saturating_sub(self.help_view_height);
+            }
+            KeyCode::Char('g') | KeyCode::Home => self.help_scroll = 0,
+            KeyCode::Char('G') | KeyCode::End => self.help_scroll = u16::MAX,
+            _ => {}
+        }
+    }
+
+    /// ダッシュボードのキー処理
+    pub(super) fn handle_dashboard_mode(&mut self, code: KeyCode) {
+        let threads = self.dashboard_threads();
+        let count = threads.len();
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('D') => {
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('j') | KeyCode::Down if count > 0 => {
+                self.dashboard_cursor = (self.dashboard_cursor + 1).min(count - 1);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.dashboard_cursor = self.dashboard_cursor.saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                // Conversation ペインの該当スレッドへジャンプ
+                if let Some(thread) = threads.get(self.dashboard_cursor) {
+                    self.focused_panel = Panel::Conversation;
+                    self.conversation_cursor = thread.entry_index;
+                    self.center_conversation_on_cursor();
+                    self.mode = AppMode::Normal;
+                }
+            }
+            KeyCode::Char('c') => {
+                if let Some(thread) = threads.get(self.dashboard_cursor) {
+                    // 返信対象のスレッドを Conversation ペインで見えるようにする
+                    self.focused_panel = Panel::Conversation;
+                    self.conversation_cursor = thread.entry_index;
+                    self.review.reply_to_comment_id = Some(thread.root_comment_id);
+                    self.review.viewing_comments.clear();
+                    self.review.comment_editor.clear();
+                    self.mode = AppMode::ReplyInput;
+                    self.restore_draft();
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Some(thread) = threads.get(self.dashboard_cursor) {
+                    let Some(node_id) = thread.thread_node_id.clone() else {
+                        self.status_message =
+                            Some(StatusMessage::error("Thread info not available"));
+                        return;
+                    };
+                    self.review.needs_resolve_toggle = Some(ResolveToggleRequest {
+                        thread_node_id: node_id,
+                        should_resolve: true,
+                        root_comment_id: thread.root_comment_id,
+                    });
+                    // 解決後にリストが縮むのでカーソルを補正
+                    self.dashboard_cursor = self.dashboard_cursor.min(count.saturating_sub(2));
+                }
+            }
+            KeyCode::Char('Q') => self.needs_rerequest_review = true,
+            KeyCode::Char('M') => self.needs_mark_ready = true,
+            _ => {}
+        }
+    }
+
+    /// DiffView 検索入力モードのキー処理
+    pub(super) fn handle_diff_search_mode(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        match code {
+            KeyCode::Esc => {
+                self.search_input.clear();
+                self.mode = AppMode::Normal;
+            }
+            // Alt+c / Alt+w / Alt+r: 大文字小文字・単語単位・正規表現の切替
+            KeyCode::Char(c @ ('c' | 'w' | 'r')) if modifiers.contains(KeyModifiers::ALT) => {
+                self.toggle_search_option(c);
+            }
+            KeyCode::Enter => {
+                if let Some(err) = self.search_input_error() {
+                    // 不正な正規表現は確定せず入力を続けさせる
+                    self.status_message =
+                        Some(StatusMessage::error(format!("✗ Invalid regex: {err}")));
+                    return;
+                }
+                self.diff.search_query = std::mem::take(&mut self.search_input);
+                self.mode = AppMode::Normal;
+                if self.diff.search_query.is_empty() {
+                    return;
+                }
+                let matches = self.search_match_lines();
+                // カーソル位置以降の最初のマッチ、なければ先頭のマッチへ
+                match matches
+                    .iter()
+                    .find(|&&idx| idx >= self.diff.cursor_line)
+                    .or(matches.first())
+                {
+                    Some(&target) => {
+                        self.diff.cursor_line = target;
+                        self.ensure_cursor_visible();
+                    }
+                    None => {
+                        self.status_message = Some(StatusMessage::error(format!(
+                            "✗ No matches for \"{}\"",
+                            self.diff.search_query
+                        )));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.search_input.pop();
+            }
+            KeyCode::Char(c) => self.search_input.push(c),
+            _ => {}
+        }
+    }
+
+    /// チェックリストオーバーレイのキー処理
+    pub(super) fn handle_checklist_mode(&mut self, code: KeyCode) {
+        let count = self.checklist.len();
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('L') => {
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('j') | KeyCode::Down if count > 0 => {
+                self.checklist_cursor = (self.checklist_cursor + 1) % count;
+            }
+            KeyCode::Char('k') | KeyCode::Up if count > 0 => {
+                self.checklist_cursor = (self.checklist_cursor + count - 1) % count;
+            }
+            KeyCode::Char(' ') | KeyCode::Char('x') | KeyCode::Enter => {
+                self.toggle_checklist_item();
+            }
+            KeyCode::Char('a') => self.toggle_checklist_append(),
+            _ => {}
+        }
+    }
+
+    /// コマンド出力オーバーレイのキー処理
+    pub(super) fn handle_command_output_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.command_output_follow = false;
+                self.command_output_scroll = self.command_output_scroll.saturating_add(1);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.command_output_follow = false;
+                self.command_output_scroll = self.command_output_scroll.saturating_sub(1);
+            }
+            KeyCode::Char('g') => {
+                self.command_output_follow = false;
+                self.command_output_scroll = 0;
+            }
+            KeyCode::Char('G') => {
+                // render 時に末尾へクランプされる
+                self.command_output_follow = true;
+                self.command_output_scroll = u16::MAX;
+            }
+            KeyCode::Char('r') => {
+                self.needs_run_command = true;
+            }
+            KeyCode::Char('x') => self.stop_run_command(),
+            _ => {}
+        }
+    }
+
+    /// API 統計オーバーレイのキー処理
+    pub(super) fn handle_stats_mode(&mut self, code: KeyCode) {
+        if matches!(code, KeyCode::Esc | KeyCode::Char('q' | 'I')) {
+            self.mode = AppMode::Normal;
+        }
+    }
+
+    /// リンク一覧オーバーレイのキー処理
+    pub(super) fn handle_link_list_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('j') | KeyCode::Down
+                if self.link_list_cursor + 1 < self.link_list.len() =>
+            {
+                self.link_list_cursor += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.link_list_cursor = self.link_list_cursor.saturating_sub(1);
+            }
+            KeyCode::Enter => self.open_link_at(self.link_list_cursor),
+            KeyCode::Char(ch) if ch.is_ascii_digit() => {
+                if let Some(index) = self.link_index_for_digit(ch) {
+                    self.open_link_at(index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// CI チェック一覧オーバーレイのキー処理
+    pub(super) fn handle_checks_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q' | 'C') => {
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('j') | KeyCode::Down if self.checks_cursor + 1 < self.checks_len() => {
+                self.checks_cursor += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.checks_cursor = self.checks_cursor.saturating_sub(1);
+            }
+            KeyCode::Enter | KeyCode::Char('o') => self.open_selected_check(),
+            KeyCode::Char('r') => self.schedule_checks_fetch(),
+            _ => {}
+        }
+    }
+
+    /// ファイル検索オーバーレイのキー処理（文字はすべて入力になるので、移動は矢印キーと Ctrl+N / Ctrl+P）
+    pub(super) fn handle_file_finder_mode(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+        match code {
+            KeyCode::Esc => self.mode = AppMode::Normal,
+            KeyCode::Enter => self.open_file_finder_match(),
+            KeyCode::Down | KeyCode::Tab => self.move_file_finder_cursor(true),
+            KeyCode::Up | KeyCode::BackTab => self.move_file_finder_cursor(false),
+            KeyCode::Char('n') if ctrl => self.move_file_finder_cursor(true),
+            KeyCode::Char('p') if ctrl => self.move_file_finder_cursor(false),
+            KeyCode::Char('u') if ctrl => {
+                self.file_finder_input.clear();
+                self.file_finder_cursor = 0;
+            }
+            KeyCode::Backspace => self.edit_file_finder_input(None),
+            KeyCode::Char(c) if !ctrl => self.edit_file_finder_input(Some(c)),
+            _ => {}
+        }
+    }
+
+    /// メッセージ履歴オーバーレイのキー処理
+    pub(super) fn handle_status_history_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('j') | KeyCode::Down
+                if self.status_history_scroll + 1 < self.status_history.len() =>
+            {
+                self.status_history_scroll += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.status_history_scroll = self.status_history_scroll.saturating_sub(1);
+            }
+            _ => {}
+        }
+    }
+
+    /// ファイル比較オーバーレイのキー処理
+    pub(super) fn handle_file_compare_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.file_compare_scroll = self.file_compare_scroll.saturating_add(1);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.file_compare_scroll = self.file_compare_scroll.saturating_sub(1);
+            }
+            KeyCode::Char('g') => {
+                self.file_compare_scroll = 0;
+            }
+            KeyCode::Char('G') => {
+                // render 時に末尾へクランプされる
+                self.file_compare_scroll = u16::MAX;
+            }
+            _ => {}
+        }
+    }
+
+    /// ファイル履歴オーバーレイのキー処理
+    pub(super) fn handle_file_history_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('H') => {
+                self.file_history = None;
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('n') | KeyCode::Char('l') | KeyCode::Right => {
+                self.step_file_history(true);
+            }
+            KeyCode::Char('N') | KeyCode::Char('h') | KeyCode::Left => {
+                self.step_file_history(false);
+            }
+            KeyCode::Enter => self.open_file_history_entry(),
+            _ => {
+                let Some(view) = &mut self.file_history else {
+                    return;
+                };
+                match code {
+                    KeyCode::Char('j') | KeyCode::Down => {
+                        view.scroll = view.scroll.saturating_add(1);
+                    }
+                    KeyCode::Char('k') | KeyCode::Up => {
+                        view.scroll = view.scroll.saturating_sub(1);
+                    }
+                    KeyCode::Char('g') => view.scroll = 0,
+                    // render 時に末尾へクランプされる
+                    KeyCode::Char('G') => view.scroll = u16::MAX,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// 大きな PR の概要画面のキー処理
+    pub(super) fn handle_large_pr_summary_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('B') => {
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('j') | KeyCode::Down => self.move_summary_cursor(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_summary_cursor(-1),
+            KeyCode::Char('g') => self.move_summary_cursor(isize::MIN),
+            KeyCode::Char('G') => self.move_summary_cursor(isize::MAX),
+            KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => self.activate_summary_row(),
+            KeyCode::Char('h') | KeyCode::Left => self.collapse_summary_dir(),
+            _ => {}
+        }
+    }
+
+    /// 保留コメントプレビューのキー処理
+    pub(super) fn handle_pending_comments_mode(&mut self, code: KeyCode) {
+        // 見出しの入力中は文字を追加（Enter で確定、Esc で取消）
+        if let Some(input) = &mut self.pending_header_input {
+            match code {
+                KeyCode::Esc => self.pending_header_input = None,
+                KeyCode::Enter => self.commit_pending_header(),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return;
+        }
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('P') => {
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('j') | KeyCode::Down => self.move_pending_cursor(true),
+            KeyCode::Char('k') | KeyCode::Up => self.move_pending_cursor(false),
+            KeyCode::Char('J') => self.move_pending_comment(true),
+            KeyCode::Char('K') => self.move_pending_comment(false),
+            KeyCode::Char('h') => self.start_pending_header_input(),
+            KeyCode::Char('g') | KeyCode::Home => self.pending_cursor = 0,
+            KeyCode::Char('G') | KeyCode::End => {
+                self.pending_cursor = self.review.pending_comments.len().saturating_sub(1);
+            }
+            _ => {}
+        }
+    }
+
+    /// 画像添付のパス入力のキー処理
+    pub(super) fn handle_attach_image_mode(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => self.mode = self.attach_return_mode,
+            KeyCode::Enter => self.confirm_attach(),
+            KeyCode::Backspace => {
+                self.attach_input.pop();
+            }
+            KeyCode::Char(c) => self.attach_input.push(c),
             _ => {}
         }
     }
@@ -920,9 +1893,10 @@ impl App {
                     .media_ref_at(self.media_viewer_index)
                     .map(|r| r.url.clone())
                 {
-                    open_url_in_browser(&url);
+                    self.open_in_browser(&url, &url);
                 }
             }
+            KeyCode::Char('c') => self.comment_on_media(),
             _ => {}
         }
     }
@@ -0,0 +1,62 @@
+//! Files ペインのレビュー活動ヒートマップ（既存コメント・未解決スレッド・保留コメントの多いファイルほど濃く表示）
+
+use super::*;
+
+/// 活動量の段階ごとの記号と色（最も活発なファイルに対する割合で選ぶ）
+const HEAT_LEVELS: [(&str, Color); 4] = [
+    ("▂", Color::Yellow),
+    ("▄", Color::LightRed),
+    ("▆", Color::Red),
+    ("█", Color::Red),
+];
+
+/// 最大値に対する割合から段階を選ぶ（活動がなければ None）
+pub(super) fn heat_level(score: usize, max: usize) -> Option<(&'static str, Color)> {
+    if score == 0 || max == 0 {
+        return None;
+    }
+    let index = (score * HEAT_LEVELS.len()).div_ceil(max) - 1;
+    Some(HEAT_LEVELS[index.min(HEAT_LEVELS.len() - 1)])
+}
+
+impl App {
+    /// ファイルごとのレビュー活動量（コメント数 + 未解決スレッド数 + 保留コメント数、PR 全体で数える）
+    pub(super) fn file_activity(&self) -> HashMap<String, usize> {
+        let mut activity: HashMap<String, usize> = HashMap::new();
+        for comment in &self.review.review_comments {
+            *activity.entry(comment.path.clone()).or_default() += 1;
+        }
+        // 未解決のスレッドはルートコメントのファイルにもう 1 つ加える
+        for thread in self.review.thread_map.values() {
+            if thread.is_resolved {
+                continue;
+            }
+            if let Some(root) = self
+                .review
+                .review_comments
+                .iter()
+                .find(|c| c.id == thread.root_comment_database_id)
+            {
+                *activity.entry(root.path.clone()).or_default() += 1;
+            }
+        }
+        for pending in &self.review.pending_comments {
+            *activity.entry(pending.file_path.clone()).or_default() += 1;
+        }
+        activity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heat_level() {
+        assert_eq!(heat_level(0, 10), None);
+        assert_eq!(heat_level(1, 10).map(|(s, _)| s), Some("▂"));
+        assert_eq!(heat_level(5, 10).map(|(s, _)| s), Some("▄"));
+        assert_eq!(heat_level(6, 10).map(|(s, _)| s), Some("▆"));
+        assert_eq!(heat_level(10, 10).map(|(s, _)| s), Some("█"));
+    }
+}
@@ -0,0 +1,361 @@
+//! ヘルプダイアログの内容（ペインごとのキーバインド一覧と絞り込み）
+
+use super::*;
+use crate::i18n::tr;
+
+/// ヘルプの1セクション（見出し + (キー, 説明) の一覧）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HelpSection {
+    pub title: &'static str,
+    pub entries: Vec<(&'static str, &'static str)>,
+}
+
+/// ヘルプの対象になる全ペイン（表示順）
+const ALL_PANELS: [Panel; 7] = [
+    Panel::PrDescription,
+    Panel::CommitList,
+    Panel::FileTree,
+    Panel::CommitMessage,
+    Panel::DiffView,
+    Panel::Conversation,
+    Panel::CommitOverview,
+];
+
+/// 全ペイン共通のキーバインド（emacs プリセットでは Ctrl+N / Ctrl+P が加わる）
+fn navigation_section(keymap: Keymap) -> HelpSection {
+    let mut section = HelpSection {
+        title: "Navigation",
+        entries: vec![
+            ("j / ↓", "Move down"),
+            ("k / ↑", "Move up"),
+            ("l / → / Tab", "Next pane"),
+            ("h / ← / BackTab", "Previous pane"),
+            ("1 / 2 / 3", "Jump to pane"),
+            ("Esc", "Back to parent pane (dismisses an error first)"),
+            ("z", "Toggle zoom"),
+            ("R", "Reload PR data"),
+            ("S", "Submit review"),
+            ("Alt+S", "Submit review from any mode (keeps drafts)"),
+            ("!", "Run command (--exec)"),
+            ("L", "Review checklist"),
+            ("P", "Preview pending comments"),
+            ("D", "Author dashboard (own PR)"),
+            ("gl", "Links in description/conversation"),
+            ("gm", "Message history"),
+            ("gf / Ctrl+P", "Find file across all commits"),
+            ("I", "API call stats"),
+            ("C", "CI checks for the head commit"),
+            ("T", "Toggle relative / absolute times"),
+            ("V", "Swap description / conversation in sidebar"),
+            ("X", "Export review report (--export-format)"),
+            ("U", "Update branch from base"),
+            ("O", "Check out PR branch locally (gh pr checkout)"),
+            ("W", "Enable / disable auto-merge"),
+            ("Z", "Close / reopen the PR"),
+            ("B", "Large PR summary (lazy loading)"),
+            ("?", "This help"),
+            ("Ctrl+Z", "Suspend (resume with fg)"),
+            ("q", "Quit"),
+        ],
+    };
+    if keymap == Keymap::Emacs {
+        section
+            .entries
+            .insert(2, ("Ctrl+N / Ctrl+P", "Move down / up (emacs keymap)"));
+        // emacs キーマップの Ctrl+P は上移動なのでファイル検索は gf だけ
+        for entry in &mut section.entries {
+            if entry.0 == "gf / Ctrl+P" {
+                entry.0 = "gf";
+            }
+        }
+    }
+    section
+}
+
+/// スクロール可能なペイン共通のキーバインド
+fn scroll_section() -> HelpSection {
+    HelpSection {
+        title: "Scroll",
+        entries: vec![
+            ("Ctrl+d / Ctrl+u", "Half page down / up"),
+            ("Ctrl+f / Ctrl+b, PgDn / PgUp", "Full page down / up"),
+            ("g / G, Home / End", "Top / Bottom"),
+        ],
+    }
+}
+
+/// ペイン固有のキーバインド
+fn panel_section(panel: Panel) -> HelpSection {
+    let entries = match panel {
+        Panel::PrDescription => vec![
+            ("Enter", "Toggle <details> / open conversation"),
+            ("o", "Open media viewer"),
+        ],
+        Panel::CommitList => vec![
+            ("x", "Toggle viewed"),
+            ("v", "Select range (x marks all viewed)"),
+            ("y", "Copy SHA"),
+            ("Y", "Copy commit message"),
+            ("p", "Compare pinned file with this commit"),
+            (
+                "m",
+                "Mark as interdiff base (diff from it to the selected commit)",
+            ),
+        ],
+        Panel::FileTree => vec![
+            ("Enter", "Open diff"),
+            ("x", "Toggle viewed"),
+            ("y", "Copy file path"),
+            ("p", "Pin file for commit compare"),
+            ("H", "File history across commits"),
+            ("} / {", "Next / prev unviewed file"),
+            ("N", "Edit private note for file"),
+            ("E", "Export notes to markdown"),
+            ("t", "Group / collapse / inline test files"),
+            ("f", "Follow file across commits"),
+        ],
+        Panel::CommitMessage => vec![("Tab", "Switch to diff view"), ("Esc", "Back to file tree")],
+        Panel::DiffView => vec![
+            ("Tab", "Switch to commit message"),
+            ("n", "Toggle line numbers"),
+            ("za", "Fold / unfold hunk"),
+            ("zm / zr", "Fold / unfold all hunks"),
+            ("x", "Mark hunk reviewed"),
+            ("w", "Toggle line wrap"),
+            ("i", "Toggle coverage overlay"),
+            ("]c / [c", "Next / prev change block"),
+            ("]h / [h", "Next / prev hunk"),
+            ("]n / [n", "Next / prev comment"),
+            ("]f / [f", "Next / prev file (across commits)"),
+            ("} / {", "Next / prev unviewed file"),
+            ("/", "Search in diff"),
+            (
+                "Alt+c / Alt+w / Alt+r (in search)",
+                "Toggle case / whole word / regex",
+            ),
+            ("F", "Load omitted patch (full file / local diff)"),
+            ("]s / [s", "Next / prev search match"),
+            ("A", "Comment on all search matches"),
+            ("v", "Enter line select mode"),
+            ("y (in select)", "Copy permalink of selected lines"),
+            ("Y (in select)", "Copy selected lines as diff snippet"),
+            (
+                "o / n (in select)",
+                "Copy old / new version of selected lines",
+            ),
+            ("c", "Comment on line"),
+            ("s (in select)", "Suggest change for selected lines"),
+            ("Enter", "View comment on line"),
+            ("c (in view)", "Reply to thread"),
+            (
+                "> (in view)",
+                "Select lines to quote in reply (J/K: extend)",
+            ),
+            ("J/K (in view)", "Select comment to edit or delete"),
+            ("e (in view)", "Edit selected comment (yours)"),
+            ("d (in view)", "Delete selected comment (yours)"),
+            ("Ctrl+R", "Quick reply (in reply)"),
+            ("Ctrl+L", "Link selected commit (in reply)"),
+            ("Ctrl+X", "Resolve after reply (in reply)"),
+            ("y (in view)", "Copy permalink of thread lines"),
+            ("r", "Resolve/unresolve thread"),
+            ("Ctrl+G", "Insert suggestion"),
+            ("Ctrl+T", "Attach image (in comment)"),
+            ("Ctrl+E", "Edit in $EDITOR (in comment)"),
+            ("Ctrl+S", "Submit comment"),
+        ],
+        Panel::Conversation => vec![
+            ("j / k", "Next / prev entry"),
+            ("c", "Reply / comment on PR"),
+            ("Enter / gd", "Jump to commented code"),
+            ("r", "Resolve/unresolve thread"),
+            ("za", "Fold / unfold entry"),
+            ("zm / zr", "Fold / unfold all entries"),
+            ("m", "Mute / unmute thread"),
+            ("M", "Mute / unmute PR notifications"),
+            ("u", "Jump to first unread entry"),
+            ("Ctrl+R", "Quick reply (in reply)"),
+            ("Ctrl+L", "Link selected commit (in reply)"),
+            ("Ctrl+X", "Resolve after reply (in reply)"),
+            ("Ctrl+T", "Attach image (in comment)"),
+            ("Ctrl+E", "Edit in $EDITOR (in comment)"),
+            ("Ctrl+S", "Submit comment"),
+            ("Esc", "Back to PR description"),
+        ],
+        Panel::CommitOverview => vec![
+            ("j / k", "Scroll down / up"),
+            ("Esc", "Back to commit list"),
+        ],
+    };
+    let title = match panel {
+        Panel::PrDescription => "PR Description",
+        Panel::CommitList => "Commit List",
+        Panel::FileTree => "File Tree",
+        Panel::CommitMessage => "Commit Message",
+        Panel::DiffView => "Diff View",
+        Panel::Conversation => "Conversation",
+        Panel::CommitOverview => "Commit Overview",
+    };
+    HelpSection { title, entries }
+}
+
+/// ヘルプに表示するセクション一覧。
+/// 絞り込みなしでは現在のペインに関係するものだけ、絞り込み中は全ペインを対象にする
+pub(super) fn help_sections(panel: Panel, filter: &str, keymap: Keymap) -> Vec<HelpSection> {
+    let scrollable = !matches!(panel, Panel::FileTree);
+
+    if filter.is_empty() {
+        let mut sections = vec![navigation_section(keymap)];
+        if scrollable {
+            sections.push(scroll_section());
+        }
+        sections.push(panel_section(panel));
+        return sections;
+    }
+
+    // 現在のペインを先頭に、残りのペインを続ける
+    let mut sections = vec![
+        navigation_section(keymap),
+        scroll_section(),
+        panel_section(panel),
+    ];
+    sections.extend(
+        ALL_PANELS
+            .iter()
+            .filter(|p| **p != panel)
+            .map(|p| panel_section(*p)),
+    );
+
+    let query = filter.to_lowercase();
+    let matches = |text: &str| {
+        text.to_lowercase().contains(&query) || tr(text).to_lowercase().contains(&query)
+    };
+    sections
+        .into_iter()
+        .filter_map(|mut section| {
+            // 見出しに一致したらセクション全体を残す
+            if !matches(section.title) {
+                section
+                    .entries
+                    .retain(|(key, desc)| key.to_lowercase().contains(&query) || matches(desc));
+            }
+            (!section.entries.is_empty()).then_some(section)
+        })
+        .collect()
+}
+
+/// キーバインド一覧の出力形式（`prism keys --format`）
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum KeymapFormat {
+    #[default]
+    Markdown,
+    Json,
+}
+
+/// 有効なキーバインドの一覧（共通 + 全ペイン）をヘルプと同じ表から書き出す
+pub fn export_keymap(keymap: Keymap, format: KeymapFormat) -> String {
+    let mut sections = vec![navigation_section(keymap), scroll_section()];
+    sections.extend(ALL_PANELS.iter().map(|p| panel_section(*p)));
+    match format {
+        KeymapFormat::Markdown => keymap_markdown(&sections),
+        KeymapFormat::Json => keymap_json(&sections),
+    }
+}
+
+/// セクションごとの見出し + 表（チームの wiki やチートシート用）
+fn keymap_markdown(sections: &[HelpSection]) -> String {
+    let cell = |text: &str| tr(text).replace('|', "\\|");
+    let mut out = String::from("# prism key bindings\n");
+    for section in sections {
+        out.push_str(&format!(
+            "\n## {}\n\n| Key | Action |\n| --- | --- |\n",
+            tr(section.title)
+        ));
+        for (key, desc) in &section.entries {
+            out.push_str(&format!(
+                "| `{}` | {} |\n",
+                key.replace('|', "\\|"),
+                cell(desc)
+            ));
+        }
+    }
+    out
+}
+
+/// `[{ "section": ..., "bindings": [{ "key": ..., "action": ... }] }]`
+fn keymap_json(sections: &[HelpSection]) -> String {
+    let value: Vec<serde_json::Value> = sections
+        .iter()
+        .map(|section| {
+            let bindings: Vec<serde_json::Value> = section
+                .entries
+                .iter()
+                .map(|(key, desc)| serde_json::json!({ "key": key, "action": tr(desc) }))
+                .collect();
+            serde_json::json!({ "section": tr(section.title), "bindings": bindings })
+        })
+        .collect();
+    serde_json::to_string_pretty(&value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_help_sections_for_panel() {
+        let titles: Vec<&str> = help_sections(Panel::DiffView, "", Keymap::Vim)
+            .iter()
+            .map(|s| s.title)
+            .collect();
+        assert_eq!(titles, vec!["Navigation", "Scroll", "Diff View"]);
+
+        // File Tree はスクロールしない
+        let titles: Vec<&str> = help_sections(Panel::FileTree, "", Keymap::Vim)
+            .iter()
+            .map(|s| s.title)
+            .collect();
+        assert_eq!(titles, vec!["Navigation", "File Tree"]);
+    }
+
+    #[test]
+    fn test_help_filter_searches_all_panels() {
+        let sections = help_sections(Panel::PrDescription, "toggle viewed", Keymap::Vim);
+        let titles: Vec<&str> = sections.iter().map(|s| s.title).collect();
+        assert_eq!(titles, vec!["Commit List", "File Tree"]);
+        assert!(
+            sections
+                .iter()
+                .all(|s| s.entries == vec![("x", "Toggle viewed")])
+        );
+    }
+
+    #[test]
+    fn test_help_filter_matches_key_and_section_title() {
+        let sections = help_sections(Panel::PrDescription, "ctrl+g", Keymap::Vim);
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].entries, vec![("Ctrl+G", "Insert suggestion")]);
+
+        // 見出しに一致したらセクション全体
+        let sections = help_sections(Panel::PrDescription, "commit overview", Keymap::Vim);
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].entries.len(), 2);
+
+        assert!(help_sections(Panel::PrDescription, "no such command", Keymap::Vim).is_empty());
+    }
+
+    #[test]
+    fn test_export_keymap() {
+        let markdown = export_keymap(Keymap::Vim, KeymapFormat::Markdown);
+        assert!(markdown.contains("## Diff View\n\n| Key | Action |\n| --- | --- |\n"));
+        assert!(markdown.contains("| `/` | Search in diff |"));
+        assert!(!markdown.contains("Ctrl+N"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&export_keymap(Keymap::Emacs, KeymapFormat::Json)).unwrap();
+        let sections = json.as_array().unwrap();
+        assert_eq!(sections.len(), 2 + ALL_PANELS.len());
+        assert_eq!(sections[0]["section"], "Navigation");
+        assert_eq!(sections[0]["bindings"][2]["key"], "Ctrl+N / Ctrl+P");
+    }
+}
@@ -2,19 +2,26 @@ use super::*;
 
 use unicode_width::UnicodeWidthStr;
 
-/// ISO 8601 日時文字列をシステムタイムゾーンのローカル時刻に変換して返す
-/// 入力例: "2024-01-15T09:30:00Z" → "2024-01-15 18:30 +0900"（JST の場合）
-pub(super) fn format_datetime(iso: &str) -> String {
-    chrono::DateTime::parse_from_rfc3339(iso)
-        .map(|dt| {
-            dt.with_timezone(&chrono::Local)
-                .format("%Y-%m-%d %H:%M %z")
-                .to_string()
-        })
-        .unwrap_or_else(|_| iso.to_string())
-}
+pub(super) use crate::format::bytes as format_bytes;
 
 impl App {
+    /// 設定に応じて日時を相対表記またはローカル時刻で整形する（全ペインで共通）
+    pub(super) fn format_time(&self, iso: &str) -> String {
+        match self.time_format {
+            TimeFormat::Relative => crate::format::relative(iso, chrono::Utc::now()),
+            TimeFormat::Absolute => crate::format::datetime(iso),
+        }
+    }
+
+    /// T: 相対表記と絶対表記を切り替える
+    pub(super) fn toggle_time_format(&mut self) {
+        self.time_format = match self.time_format {
+            TimeFormat::Relative => TimeFormat::Absolute,
+            TimeFormat::Absolute => TimeFormat::Relative,
+        };
+        self.conversation_rendered = None; // キャッシュ無効化
+    }
+
     /// @@ hunk header を整形表示用の Line に変換
     /// `@@ -10,5 +12,7 @@ fn main()` → `─── L10-14 → L12-18 ─── fn main() ────`
     pub(super) fn format_hunk_header(raw: &str, width: u16, style: Style) -> Line<'static> {
@@ -84,16 +91,6 @@ impl App {
     }
 }
 
-/// URL をシステムのデフォルトブラウザで開く
-pub(super) fn open_url_in_browser(url: &str) {
-    #[cfg(target_os = "macos")]
-    let cmd = "open";
-    #[cfg(target_os = "linux")]
-    let cmd = "xdg-open";
-    #[cfg(any(target_os = "macos", target_os = "linux"))]
-    let _ = std::process::Command::new(cmd).arg(url).spawn();
-}
-
 /// 文字列を最大表示幅に収まるように末尾を省略する（unicode-width 対応）
 /// 例: "prism - repo#1: Long PR title" → "prism - repo#1: Lo…"
 pub(super) fn truncate_str(s: &str, max_width: usize) -> String {
@@ -139,3 +136,81 @@ pub(super) fn truncate_path(path: &str, max_width: usize) -> String {
         format!("...{}", tail)
     }
 }
+
+/// 所要時間を表示用に整形（1 秒未満は ms、以上は小数1桁の秒）
+pub(super) fn format_duration(duration: std::time::Duration) -> String {
+    let ms = duration.as_millis();
+    if ms < 1000 {
+        format!("{} ms", ms)
+    } else {
+        format!("{:.1} s", duration.as_secs_f64())
+    }
+}
+
+/// CommentView に表示する diff_hunk の末尾の行数（コメント対象行がこれより多ければ全て表示）
+const DIFF_HUNK_PREVIEW_LINES: usize = 6;
+
+/// レビューコメントの diff_hunk の末尾を、コメント対象行に印を付けて返す（パッチがずれても元の文脈が分かるように）
+pub(super) fn diff_hunk_preview(
+    comment: &crate::github::comments::ReviewComment,
+) -> Vec<Line<'static>> {
+    let Some(hunk) = comment.diff_hunk.as_deref() else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = hunk.lines().collect();
+    // 複数行コメントは start_line..=line（同じ side の場合のみ行数が分かる）
+    let commented = match (comment.start_line, comment.line) {
+        (Some(start), Some(end))
+            if comment.start_side.is_none() || comment.start_side == comment.side =>
+        {
+            end.saturating_sub(start) + 1
+        }
+        _ => 1,
+    }
+    .min(lines.len());
+    let shown = DIFF_HUNK_PREVIEW_LINES.max(commented).min(lines.len());
+    let first_commented = lines.len() - commented;
+    lines[lines.len() - shown..]
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let style = match line.chars().next() {
+                Some('+') => Style::default().fg(Color::Green),
+                Some('-') => Style::default().fg(Color::Red),
+                Some('@') => Style::default().fg(Color::Cyan),
+                _ => Style::default(),
+            };
+            let marker = if lines.len() - shown + i >= first_commented {
+                Span::styled("▎", Style::default().fg(Color::Yellow))
+            } else {
+                Span::raw(" ")
+            };
+            Line::from(vec![marker, Span::styled(line.to_string(), style)])
+        })
+        .collect()
+}
+
+/// 作者の色パレット（状態表示に使う Red / Green / DarkGray は避ける）
+const AUTHOR_PALETTE: [Color; 10] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Yellow,
+    Color::Blue,
+    Color::LightCyan,
+    Color::LightMagenta,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightRed,
+    Color::LightGreen,
+];
+
+/// ログイン名のハッシュ（FNV-1a）からパレットの色を選ぶ（実行をまたいでも同じ色）
+pub(super) fn author_color(login: &str) -> Color {
+    let hash = login
+        .to_lowercase()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        });
+    AUTHOR_PALETTE[(hash % AUTHOR_PALETTE.len() as u64) as usize]
+}
@@ -0,0 +1,231 @@
+//! hunk 単位の折りたたみと確認済み状態（全 hunk が済んだらファイルを viewed にする）
+
+use super::*;
+use std::ops::Range;
+use unicode_width::UnicodeWidthStr;
+
+/// ファイルごとの hunk 状態（hunk header の行インデックスで管理）
+#[derive(Debug, Default, Clone)]
+pub struct HunkState {
+    pub collapsed: HashSet<usize>,
+    pub reviewed: HashSet<usize>,
+}
+
+/// hunk header の行インデックスと、その hunk の本体の行範囲
+fn hunk_ranges(patch: &str) -> Vec<(usize, Range<usize>)> {
+    let lines: Vec<&str> = patch.lines().collect();
+    let headers: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.starts_with("@@"))
+        .map(|(i, _)| i)
+        .collect();
+    headers
+        .iter()
+        .enumerate()
+        .map(|(n, &h)| {
+            let end = headers.get(n + 1).copied().unwrap_or(lines.len());
+            (h, h + 1..end)
+        })
+        .collect()
+}
+
+/// hunk header の先頭に状態ラベルを入れる（右端の罫線を削って幅を保つ）
+pub(super) fn label_hunk_header(line: &mut Line<'static>, label: &str, style: Style) {
+    let label_width = UnicodeWidthStr::width(label);
+    if let Some(last) = line.spans.last_mut() {
+        let content = last.content.to_string();
+        let trimmed = content.trim_end_matches('─');
+        let fill = content.chars().count() - trimmed.chars().count();
+        last.content = format!("{trimmed}{}", "─".repeat(fill.saturating_sub(label_width))).into();
+    }
+    line.spans.insert(0, Span::styled(label.to_string(), style));
+}
+
+impl App {
+    /// 選択中ファイルの hunk 一覧（header, 本体範囲）
+    fn current_hunks(&self) -> Vec<(usize, Range<usize>)> {
+        self.current_file()
+            .and_then(|f| f.patch.as_deref())
+            .map(hunk_ranges)
+            .unwrap_or_default()
+    }
+
+    /// 選択中ファイルの hunk 状態
+    fn current_hunk_state(&self) -> Option<&HunkState> {
+        if self.hunk_states.is_empty() {
+            return None;
+        }
+        self.hunk_states.get(&self.diff_position_key()?)
+    }
+
+    /// 折りたたまれた hunk（header, 本体範囲）。本体の行は表示されない
+    pub(super) fn collapsed_hunks(&self) -> Vec<(usize, Range<usize>)> {
+        let Some(state) = self
+            .current_hunk_state()
+            .filter(|s| !s.collapsed.is_empty())
+        else {
+            return Vec::new();
+        };
+        self.current_hunks()
+            .into_iter()
+            .filter(|(h, _)| state.collapsed.contains(h))
+            .collect()
+    }
+
+    /// 確認済みの hunk（header, 本体範囲）
+    pub(super) fn reviewed_hunks(&self) -> Vec<(usize, Range<usize>)> {
+        let Some(state) = self.current_hunk_state().filter(|s| !s.reviewed.is_empty()) else {
+            return Vec::new();
+        };
+        self.current_hunks()
+            .into_iter()
+            .filter(|(h, _)| state.reviewed.contains(h))
+            .collect()
+    }
+
+    /// 表示行オフセットで描画・移動するか（wrap 時か、折りたたみで行が隠れている時）
+    pub(super) fn diff_uses_visual_offsets(&self) -> bool {
+        self.diff.wrap
+            || self
+                .current_hunk_state()
+                .is_some_and(|s| !s.collapsed.is_empty())
+    }
+
+    /// カーソルが止まらない行か（hunk header と折りたたまれた本体。折りたたみ中の header には止まる）
+    pub(super) fn is_cursor_skipped_line(
+        &self,
+        line: usize,
+        collapsed: &[(usize, Range<usize>)],
+    ) -> bool {
+        if collapsed.iter().any(|(_, body)| body.contains(&line)) {
+            return true;
+        }
+        self.is_hunk_header(line) && !collapsed.iter().any(|(h, _)| *h == line)
+    }
+
+    /// カーソル行を含む hunk の header
+    fn hunk_at_cursor(&self) -> Option<usize> {
+        self.current_hunks()
+            .into_iter()
+            .find(|(h, body)| *h == self.diff.cursor_line || body.contains(&self.diff.cursor_line))
+            .map(|(h, _)| h)
+    }
+
+    /// 折りたたみ変更後に表示を作り直し、カーソルを表示行に合わせる
+    fn refresh_hunk_folds(&mut self) {
+        self.diff.visual_offsets = None;
+        let max = self.current_diff_line_count();
+        self.diff.cursor_line = self.skip_hunk_header_forward(self.diff.cursor_line, max);
+        self.ensure_cursor_visible();
+    }
+
+    /// za: カーソル位置の hunk を折りたたむ / 展開する
+    pub(super) fn toggle_hunk_collapse(&mut self) {
+        let (Some(key), Some(header)) = (self.diff_position_key(), self.hunk_at_cursor()) else {
+            return;
+        };
+        let state = self.hunk_states.entry(key).or_default();
+        if state.collapsed.remove(&header) {
+            self.diff.cursor_line = header + 1;
+        } else {
+            state.collapsed.insert(header);
+            self.diff.cursor_line = header;
+        }
+        self.refresh_hunk_folds();
+        self.mark_viewed_if_hunks_done();
+    }
+
+    /// zm: 全 hunk を折りたたむ / zr: 全 hunk を展開する
+    pub(super) fn set_all_hunks_collapsed(&mut self, collapsed: bool) {
+        let Some(key) = self.diff_position_key() else {
+            return;
+        };
+        let headers: HashSet<usize> = self.current_hunks().into_iter().map(|(h, _)| h).collect();
+        let cursor_hunk = self.hunk_at_cursor();
+        let state = self.hunk_states.entry(key).or_default();
+        if collapsed {
+            state.collapsed = headers;
+            if let Some(header) = cursor_hunk {
+                self.diff.cursor_line = header;
+            }
+        } else {
+            state.collapsed.clear();
+        }
+        self.refresh_hunk_folds();
+        self.mark_viewed_if_hunks_done();
+    }
+
+    /// x: カーソル位置の hunk を確認済みにする / 戻す
+    pub(super) fn toggle_hunk_reviewed(&mut self) {
+        if self.reject_in_interdiff() {
+            return;
+        }
+        let (Some(key), Some(header)) = (self.diff_position_key(), self.hunk_at_cursor()) else {
+            return;
+        };
+        let state = self.hunk_states.entry(key).or_default();
+        if !state.reviewed.remove(&header) {
+            state.reviewed.insert(header);
+        }
+        self.mark_viewed_if_hunks_done();
+    }
+
+    /// 全 hunk が折りたたみ済みか確認済みならファイルを viewed にする
+    fn mark_viewed_if_hunks_done(&mut self) {
+        let Some((sha, filename)) = self.diff_position_key() else {
+            return;
+        };
+        let hunks = self.current_hunks();
+        let Some(state) = self.current_hunk_state() else {
+            return;
+        };
+        let done = !hunks.is_empty()
+            && hunks
+                .iter()
+                .all(|(h, _)| state.collapsed.contains(h) || state.reviewed.contains(h));
+        if done && !self.is_file_viewed(&sha, &filename) {
+            self.set_files_viewed(&sha, vec![filename], true);
+            self.status_message = Some(StatusMessage::info("✓ All hunks done — marked viewed"));
+        }
+    }
+
+    /// カーソルが折りたたまれた本体に入ったら（検索・ジャンプ等）その hunk を展開する
+    pub(super) fn reveal_cursor_hunk(&mut self) {
+        let cursor = self.diff.cursor_line;
+        let Some((header, _)) = self
+            .collapsed_hunks()
+            .into_iter()
+            .find(|(_, body)| body.contains(&cursor))
+        else {
+            return;
+        };
+        if let Some(key) = self.diff_position_key()
+            && let Some(state) = self.hunk_states.get_mut(&key)
+        {
+            state.collapsed.remove(&header);
+            self.diff.visual_offsets = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hunk_ranges() {
+        let patch = "@@ -1,2 +1,2 @@\n-a\n+b\n@@ -10,1 +10,2 @@\n c\n+d";
+        assert_eq!(hunk_ranges(patch), vec![(0, 1..3), (3, 4..6)]);
+        assert!(hunk_ranges("+no header").is_empty());
+    }
+
+    #[test]
+    fn test_label_hunk_header_keeps_width() {
+        let mut line = Line::raw("─── L1 → L2 ──────────");
+        let before = UnicodeWidthStr::width(line.to_string().as_str());
+        label_hunk_header(&mut line, "▸ 3 ", Style::default());
+        assert!(line.to_string().starts_with("▸ 3 ─── L1"));
+        assert_eq!(UnicodeWidthStr::width(line.to_string().as_str()), before);
+    }
+}
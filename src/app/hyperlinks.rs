@@ -0,0 +1,278 @@
+//! OSC 8 ハイパーリンク: 描画したリンクのセルを draw の後にエスケープシーケンスで囲んで描き直し、対応端末ではクリックで開けるようにする（設定の `display.hyperlinks` で無効化）
+
+use super::*;
+use crate::format;
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    buffer::{Buffer, Cell},
+    crossterm::{
+        cursor::{RestorePosition, SavePosition},
+        queue,
+    },
+    layout::Rect,
+};
+use std::io::Stdout;
+use std::sync::OnceLock;
+use unicode_width::UnicodeWidthStr;
+
+/// 本文中の URL の末尾から外す記号（文末の句読点や括弧）
+const URL_TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?', ')', ']', '\'', '"'];
+
+/// PR のページの URL
+pub(super) fn pr_url(repo: &str, pr_number: u64) -> String {
+    format!("https://github.com/{repo}/pull/{pr_number}")
+}
+
+/// OSC 8 を出すか（設定で無効化されているか、対応しない端末なら出さない）
+pub(super) fn enabled() -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+    format::hyperlinks()
+        && *SUPPORTED.get_or_init(|| {
+            // Linux コンソールなどはエスケープシーケンスをそのまま表示してしまう
+            !matches!(std::env::var("TERM").as_deref(), Ok("linux" | "dumb"))
+        })
+}
+
+/// 描画したリンクの 1 行分の領域
+#[derive(Debug, Clone)]
+pub(super) struct Hyperlink {
+    area: Rect,
+    url: String,
+    /// 登録した時点のセルの文字（後からダイアログが重なったら囲まない）
+    text: String,
+}
+
+/// 領域のセルの文字を連結する
+fn cells_text(buf: &Buffer, area: Rect) -> String {
+    (area.left()..area.right())
+        .map(|x| buf[(x, area.y)].symbol())
+        .collect()
+}
+
+impl App {
+    /// 描画したリンクの領域を登録する（1 行分。draw の後に OSC 8 で囲む）
+    pub(super) fn link_area(&mut self, buf: &Buffer, area: Rect, url: impl Into<String>) {
+        let area = area.intersection(buf.area);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let area = Rect { height: 1, ..area };
+        self.hyperlinks.push(Hyperlink {
+            area,
+            url: url.into(),
+            text: cells_text(buf, area),
+        });
+    }
+
+    /// スクロールするダイアログの各行のリンクを登録する（行番号・列・幅・URL、スクロールで隠れた行は除く）
+    pub(super) fn link_dialog_rows(
+        &mut self,
+        buf: &Buffer,
+        dialog: Rect,
+        scroll: u16,
+        rows: Vec<(usize, u16, u16, String)>,
+    ) {
+        let inner = Rect {
+            x: dialog.x + 1,
+            y: dialog.y + 1,
+            width: dialog.width.saturating_sub(2),
+            height: dialog.height.saturating_sub(2),
+        };
+        for (line, x, width, url) in rows {
+            let Some(row) = (line as u16).checked_sub(scroll) else {
+                continue;
+            };
+            if row >= inner.height {
+                continue;
+            }
+            let area = Rect::new(inner.x + x, inner.y + row, width, 1).intersection(inner);
+            self.link_area(buf, area, url);
+        }
+    }
+}
+
+/// 登録した領域と本文にそのまま出ている URL（省略されていないもの）のセルを、OSC 8 で囲んだ複製にして返す
+///
+/// ratatui のバッファにはエスケープシーケンスを入れない（差分描画がシンボルの表示幅で後ろのセルを飛ばすため）
+pub(super) fn link_cells(buf: &Buffer, links: &[Hyperlink]) -> Vec<(u16, u16, Cell)> {
+    let area = buf.area;
+    let mut cells = Vec::new();
+    let mut linked: HashSet<(u16, u16)> = HashSet::new();
+    for link in links {
+        let rect = link.area.intersection(area);
+        if rect.is_empty() || cells_text(buf, rect) != link.text {
+            continue;
+        }
+        cells.extend(wrap_cells(
+            buf,
+            rect.y,
+            rect.left(),
+            rect.right(),
+            &link.url,
+        ));
+        linked.extend((rect.left()..rect.right()).map(|x| (x, rect.y)));
+    }
+    for y in area.top()..area.bottom() {
+        for (start, end, url) in bare_urls(buf, y) {
+            if (start..end).all(|x| !linked.contains(&(x, y))) {
+                cells.extend(wrap_cells(buf, y, start, end, &url));
+            }
+        }
+    }
+    cells
+}
+
+/// draw の後にリンクのセルだけを描き直す（カーソル位置は描画前に戻す）
+pub(super) fn emit(
+    backend: &mut CrosstermBackend<Stdout>,
+    cells: &[(u16, u16, Cell)],
+) -> std::io::Result<()> {
+    if cells.is_empty() {
+        return Ok(());
+    }
+    queue!(backend, SavePosition)?;
+    backend.draw(cells.iter().map(|(x, y, cell)| (*x, *y, cell)))?;
+    queue!(backend, RestorePosition)?;
+    Backend::flush(backend)
+}
+
+/// 行の中で URL に見えるセルの範囲（開始列・終了列・URL）
+fn bare_urls(buf: &Buffer, y: u16) -> Vec<(u16, u16, String)> {
+    let area = buf.area;
+    let symbols: Vec<&str> = (area.left()..area.right())
+        .map(|x| buf[(x, y)].symbol())
+        .collect();
+    let mut urls = Vec::new();
+    let mut i = 0;
+    while i < symbols.len() {
+        let rest: String = symbols[i..].iter().take(8).copied().collect();
+        if !rest.starts_with("https://") && !rest.starts_with("http://") {
+            i += 1;
+            continue;
+        }
+        // 1 セル 1 文字の ASCII が続く間を URL とみなす
+        let mut end = i;
+        while end < symbols.len() {
+            let s = symbols[end];
+            if s.len() != 1 || s.trim().is_empty() || "<>\"`".contains(s) {
+                break;
+            }
+            end += 1;
+        }
+        let mut url: String = symbols[i..end].concat();
+        while url.ends_with(URL_TRAILING_PUNCTUATION) {
+            url.pop();
+        }
+        let url_end = i + url.len();
+        // 幅に収まらず省略された URL（末尾が …）はリンク先が分からないので囲まない
+        let truncated = symbols.get(end).is_some_and(|s| *s == "…");
+        if !truncated && url.len() > "https://".len() {
+            urls.push((area.left() + i as u16, area.left() + url_end as u16, url));
+        }
+        i = end.max(i + 1);
+    }
+    urls
+}
+
+/// start..end のセルを複製し、先頭のセルの前で OSC 8 を開いて最後のセルの後で閉じる（全角文字の続きのセルは除く）
+fn wrap_cells(buf: &Buffer, y: u16, start: u16, end: u16, url: &str) -> Vec<(u16, u16, Cell)> {
+    let mut cells: Vec<(u16, u16, Cell)> = Vec::new();
+    let mut x = start;
+    while x < end {
+        let cell = buf[(x, y)].clone();
+        let width = (cell.symbol().width() as u16).max(1);
+        cells.push((x, y, cell));
+        x += width;
+    }
+    if let Some((_, _, first)) = cells.first_mut() {
+        let symbol = format!("\x1b]8;;{url}\x1b\\{}", first.symbol());
+        first.set_symbol(&symbol);
+    }
+    if let Some((_, _, last)) = cells.last_mut() {
+        let symbol = format!("{}\x1b]8;;\x1b\\", last.symbol());
+        last.set_symbol(&symbol);
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(text: &str) -> Buffer {
+        Buffer::with_lines([text])
+    }
+
+    fn hyperlink(buf: &Buffer, area: Rect, url: &str) -> Hyperlink {
+        Hyperlink {
+            area,
+            url: url.to_string(),
+            text: cells_text(buf, area),
+        }
+    }
+
+    fn symbols(cells: &[(u16, u16, Cell)]) -> Vec<(u16, &str)> {
+        cells.iter().map(|(x, _, c)| (*x, c.symbol())).collect()
+    }
+
+    #[test]
+    fn test_wraps_registered_area() {
+        let buf = row(" owner/repo#12 ");
+        let url = pr_url("owner/repo", 12);
+        let link = hyperlink(&buf, Rect::new(1, 0, 13, 1), &url);
+        let cells = link_cells(&buf, &[link]);
+        assert_eq!(cells.len(), 13);
+        assert_eq!(cells[0].0, 1);
+        assert_eq!(cells[0].2.symbol(), format!("\x1b]8;;{url}\x1b\\o"));
+        assert_eq!(cells[1].2.symbol(), "w");
+        assert_eq!(cells[12].2.symbol(), "2\x1b]8;;\x1b\\");
+    }
+
+    #[test]
+    fn test_skips_wide_char_continuation() {
+        let buf = row(" 💬 Review ");
+        let cells = link_cells(
+            &buf,
+            &[hyperlink(&buf, Rect::new(1, 0, 9, 1), "https://x.test")],
+        );
+        assert_eq!(cells[0].0, 1);
+        assert_eq!(cells[1].0, 3);
+        assert_eq!(cells.len(), 8);
+    }
+
+    #[test]
+    fn test_skips_area_covered_later() {
+        let mut buf = row(" owner/repo#12 ");
+        let link = hyperlink(&buf, Rect::new(1, 0, 13, 1), &pr_url("owner/repo", 12));
+        // 登録した後にダイアログが重なった
+        buf.set_string(3, 0, "Delete?", Style::default());
+        assert!(link_cells(&buf, &[link]).is_empty());
+    }
+
+    #[test]
+    fn test_detects_bare_urls() {
+        let buf = row("see https://example.com/a). and https://exam…");
+        let urls = bare_urls(&buf, 0);
+        assert_eq!(urls.len(), 1);
+        assert_eq!(urls[0], (4, 25, "https://example.com/a".to_string()));
+    }
+
+    #[test]
+    fn test_link_cells_keep_buffer_diff_intact() {
+        let prev = row("see https://example.com/a now");
+        let cells = link_cells(&prev, &[]);
+        assert_eq!(cells.len(), 21);
+        assert_eq!(
+            symbols(&cells)[0],
+            (4, "\x1b]8;;https://example.com/a\x1b\\h")
+        );
+        // バッファにはエスケープシーケンスを入れないので、URL の隣が変わっても差分描画から漏れない
+        let next = row("see https://example.com/a yes");
+        let updates: Vec<(u16, &str)> = prev
+            .diff(&next)
+            .into_iter()
+            .map(|(x, _, c)| (x, c.symbol()))
+            .collect();
+        assert_eq!(updates, vec![(26, "y"), (27, "e"), (28, "s")]);
+    }
+}
@@ -0,0 +1,118 @@
+//! Commits ペインの m: 基準にしたコミットの次から選択中のコミットまでをまとめた差分（interdiff、比較 API で取得）
+
+use super::*;
+use crate::github::files;
+use std::cmp::Ordering;
+
+impl App {
+    /// m: 選択中のコミットを interdiff の基準にする（基準のコミットで押すと解除）
+    pub(super) fn toggle_interdiff_base(&mut self) {
+        if self.is_all_changes_selected() {
+            self.status_message = Some(StatusMessage::error("✗ Select a commit as the base"));
+            return;
+        }
+        let Some(selected) = self.commit_list_state.selected() else {
+            return;
+        };
+        if self.interdiff_base == Some(selected) {
+            self.interdiff_base = None;
+            self.status_message = Some(StatusMessage::info("✓ Cleared interdiff base"));
+        } else {
+            self.interdiff_base = Some(selected);
+            self.status_message = Some(StatusMessage::info(format!(
+                "✓ Comparing from {} — select another commit",
+                self.commits[selected].short_sha()
+            )));
+        }
+        self.reset_file_selection();
+        self.diff.highlight_cache.clear();
+    }
+
+    /// 表示中の interdiff の範囲（古い方と新しい方のコミット。基準そのものや All changes では None）
+    pub(super) fn interdiff_range(&self) -> Option<(usize, usize)> {
+        let base = self.interdiff_base?;
+        let selected = self.commit_list_state.selected()?;
+        if base >= self.commits.len() || selected >= self.commits.len() {
+            return None;
+        }
+        match base.cmp(&selected) {
+            Ordering::Less => Some((base, selected)),
+            Ordering::Greater => Some((selected, base)),
+            Ordering::Equal => None,
+        }
+    }
+
+    /// DiffView が interdiff を表示中か
+    pub(super) fn is_interdiff_active(&self) -> bool {
+        self.interdiff_range().is_some()
+    }
+
+    /// interdiff の変更ファイル（未取得なら空）
+    pub(super) fn interdiff_files(&self, range: (usize, usize)) -> &[DiffFile] {
+        self.interdiff_files.get(&range).map_or(&[], Vec::as_slice)
+    }
+
+    /// 表示中の interdiff が未取得か（draw 後に読み込む）
+    pub(super) fn needs_interdiff_files(&self) -> bool {
+        self.interdiff_range().is_some_and(|range| {
+            !self.interdiff_files.contains_key(&range) && !self.interdiff_failed.contains(&range)
+        })
+    }
+
+    /// 比較 API で interdiff の変更ファイルを取得する（失敗した範囲はリロードまで再試行しない）
+    pub(super) fn load_interdiff_files(&mut self) {
+        let Some(range) = self.interdiff_range() else {
+            return;
+        };
+        let (Some(client), Some((owner, repo))) = (self.client.clone(), self.parse_repo()) else {
+            self.interdiff_failed.insert(range);
+            self.status_message = Some(StatusMessage::error("✗ No API client available"));
+            return;
+        };
+        let (from, to) = (&self.commits[range.0].sha, &self.commits[range.1].sha);
+        let result = tokio::task::block_in_place(|| {
+            Handle::current().block_on(files::fetch_compare_files(&client, owner, repo, from, to))
+        });
+        match result {
+            Ok(files) => {
+                self.interdiff_files.insert(range, files);
+                self.reset_file_selection();
+                self.diff.highlight_cache.clear();
+            }
+            Err(e) => {
+                self.interdiff_failed.insert(range);
+                self.status_message = Some(StatusMessage::error(format!(
+                    "✗ Failed to load interdiff: {e} — press R to retry"
+                )));
+            }
+        }
+    }
+
+    /// interdiff の表示中はコメント等を受け付けない（行が PR のどのコミットの差分にも対応しないため）
+    pub(super) fn reject_in_interdiff(&mut self) -> bool {
+        if !self.is_interdiff_active() {
+            return false;
+        }
+        self.status_message = Some(StatusMessage::error(
+            "✗ Not available in interdiff view (m on the base commit clears it)",
+        ));
+        true
+    }
+
+    /// Files ペインのタイトルに出す interdiff の範囲（"Δ abc1234..def5678"）
+    pub(super) fn interdiff_label(&self) -> Option<String> {
+        let (from, to) = self.interdiff_range()?;
+        Some(format!(
+            "Δ {}..{}",
+            self.commits[from].short_sha(),
+            self.commits[to].short_sha()
+        ))
+    }
+
+    /// リロードでコミットが入れ替わったら基準と取得済みの差分を捨てる
+    pub(super) fn reset_interdiff(&mut self) {
+        self.interdiff_base = None;
+        self.interdiff_files.clear();
+        self.interdiff_failed.clear();
+    }
+}
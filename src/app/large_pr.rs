@@ -0,0 +1,295 @@
+//! 大きな PR の概要画面と遅延読み込み（開いたコミット・パスのファイルだけを取得する）
+
+use super::*;
+use std::collections::BTreeMap;
+
+/// ルート直下のファイルをまとめるディレクトリ名
+const ROOT_DIR: &str = "(root)";
+
+/// 大きな PR の概要（PR 全体の変更ファイルの統計）
+#[derive(Debug, Clone, Default)]
+pub struct LargePrSummary {
+    /// パッチを持たないファイル統計（パス順）
+    pub files: Vec<DiffFile>,
+    /// 展開中のトップレベルディレクトリ
+    pub expanded: HashSet<String>,
+    pub cursor: usize,
+    pub scroll: u16,
+}
+
+/// 概要画面の 1 行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SummaryRow {
+    Dir {
+        name: String,
+        files: usize,
+        additions: usize,
+        deletions: usize,
+        expanded: bool,
+    },
+    /// LargePrSummary::files のインデックス
+    File(usize),
+}
+
+/// ファイルパスのトップレベルディレクトリ
+fn top_level_dir(path: &str) -> &str {
+    path.split_once('/').map_or(ROOT_DIR, |(dir, _)| dir)
+}
+
+impl LargePrSummary {
+    fn new(mut files: Vec<DiffFile>) -> Self {
+        files.sort_by(|a, b| a.filename.cmp(&b.filename));
+        Self {
+            files,
+            ..Self::default()
+        }
+    }
+
+    /// 表示行（トップレベルディレクトリごとの合計と、展開中ならその配下のファイル）
+    pub fn rows(&self) -> Vec<SummaryRow> {
+        let mut dirs: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (i, file) in self.files.iter().enumerate() {
+            dirs.entry(top_level_dir(&file.filename))
+                .or_default()
+                .push(i);
+        }
+        let mut rows = Vec::new();
+        for (name, indices) in dirs {
+            let expanded = self.expanded.contains(name);
+            rows.push(SummaryRow::Dir {
+                name: name.to_string(),
+                files: indices.len(),
+                additions: indices.iter().map(|&i| self.files[i].additions).sum(),
+                deletions: indices.iter().map(|&i| self.files[i].deletions).sum(),
+                expanded,
+            });
+            if expanded {
+                rows.extend(indices.into_iter().map(SummaryRow::File));
+            }
+        }
+        rows
+    }
+}
+
+impl App {
+    /// 大きな PR として、ファイルをコミットごとに必要になった時点で読み込む
+    pub fn set_lazy_files(&mut self, lazy: bool) {
+        self.lazy_files = lazy;
+    }
+
+    /// 概要データを受け取って概要画面を開く
+    pub(super) fn apply_file_stats(&mut self, stats: Vec<DiffFile>) {
+        self.large_pr_summary = Some(LargePrSummary::new(stats));
+        self.loading.files = LoadPhase::Done;
+        if self.mode == AppMode::Normal {
+            self.mode = AppMode::LargePrSummary;
+        }
+    }
+
+    /// B: 概要画面を開く（大きな PR のみ）
+    pub(super) fn open_large_pr_summary(&mut self) {
+        if self.large_pr_summary.is_some() {
+            self.mode = AppMode::LargePrSummary;
+        } else {
+            self.status_message = Some(StatusMessage::error(
+                "✗ Summary is only available for large PRs",
+            ));
+        }
+    }
+
+    /// 選択中のコミットのファイルが未読み込みか（draw 後に読み込む）
+    pub(super) fn needs_commit_files(&self) -> bool {
+        self.lazy_files
+            && self.loading.files == LoadPhase::Done
+            && !self.is_all_changes_selected()
+            && self.current_commit_sha().is_some_and(|sha| {
+                !self.files_map.contains_key(&sha) && !self.lazy_failed.contains(&sha)
+            })
+    }
+
+    /// コミットのファイルを取得して files_map に追加する（失敗したコミットは再試行しない）
+    fn load_commit_files(&mut self, commit: usize) -> bool {
+        let Some(sha) = self.commits.get(commit).map(|c| c.sha.clone()) else {
+            return false;
+        };
+        if self.files_map.contains_key(&sha) {
+            return true;
+        }
+        let (Some(client), Some((owner, repo))) = (self.client.clone(), self.parse_repo()) else {
+            return false;
+        };
+        let result = tokio::task::block_in_place(|| {
+            Handle::current().block_on(crate::github::files::fetch_commit_files(
+                &client, owner, repo, &sha,
+            ))
+        });
+        match result {
+            Ok(files) => {
+                self.files_evicted.remove(&sha);
+                self.files_map.insert(sha, files);
+                self.files_usage_stale = true;
+                self.rebuild_visible_comment_cache();
+                true
+            }
+            Err(e) => {
+                self.lazy_failed.insert(sha);
+                self.status_message = Some(StatusMessage::error(format!(
+                    "✗ Failed to load files: {e} — press R to retry"
+                )));
+                false
+            }
+        }
+    }
+
+    /// 選択中のコミットのファイルを読み込み、Files ペインを初期化する
+    pub(super) fn load_selected_commit_files(&mut self) {
+        if let Some(commit) = self.commit_list_state.selected()
+            && self.load_commit_files(commit)
+        {
+            self.reset_file_selection();
+            self.diff.highlight_cache.clear();
+        }
+    }
+
+    /// Enter: ディレクトリの展開 / 折りたたみ、ファイルなら読み込んで DiffView に開く
+    pub(super) fn activate_summary_row(&mut self) {
+        let Some(summary) = &mut self.large_pr_summary else {
+            return;
+        };
+        match summary.rows().get(summary.cursor) {
+            Some(SummaryRow::Dir { name, .. }) if !summary.expanded.remove(name) => {
+                summary.expanded.insert(name.clone());
+            }
+            Some(SummaryRow::File(i)) => {
+                self.needs_path_load = Some(summary.files[*i].filename.clone());
+            }
+            _ => {}
+        }
+    }
+
+    /// h / ←: カーソル位置のディレクトリ（ファイルなら親ディレクトリ）を折りたたむ
+    pub(super) fn collapse_summary_dir(&mut self) {
+        let Some(summary) = &mut self.large_pr_summary else {
+            return;
+        };
+        let rows = summary.rows();
+        let name = match rows.get(summary.cursor) {
+            Some(SummaryRow::Dir { name, .. }) => name.clone(),
+            Some(SummaryRow::File(i)) => top_level_dir(&summary.files[*i].filename).to_string(),
+            None => return,
+        };
+        if summary.expanded.remove(&name) {
+            // 折りたたんだディレクトリの行へカーソルを戻す
+            summary.cursor = summary
+                .rows()
+                .iter()
+                .position(|r| matches!(r, SummaryRow::Dir { name: n, .. } if *n == name))
+                .unwrap_or(0);
+        }
+    }
+
+    /// j / k: カーソル移動
+    pub(super) fn move_summary_cursor(&mut self, delta: isize) {
+        let Some(summary) = &mut self.large_pr_summary else {
+            return;
+        };
+        let last = summary.rows().len().saturating_sub(1);
+        summary.cursor = summary.cursor.saturating_add_signed(delta).min(last);
+    }
+
+    /// パスを変更した最新のコミットを探し、そのコミットのファイルだけを読み込んで開く（draw 後に呼ばれる）
+    pub(super) fn execute_path_load(&mut self, path: String) {
+        let (Some(client), Some((owner, repo))) = (self.client.clone(), self.parse_repo()) else {
+            self.status_message = Some(StatusMessage::error("✗ No API client available"));
+            return;
+        };
+        let result = tokio::task::block_in_place(|| {
+            Handle::current().block_on(crate::github::files::fetch_path_commits(
+                &client,
+                owner,
+                repo,
+                &self.head_sha,
+                &path,
+            ))
+        });
+        let shas = match result {
+            Ok(shas) => shas,
+            Err(e) => {
+                self.status_message = Some(StatusMessage::error(format!("✗ Failed: {e}")));
+                return;
+            }
+        };
+        // 新しい順に返るため、最初に見つかった PR 内のコミットが最新
+        let Some(commit) = shas
+            .iter()
+            .find_map(|sha| self.commits.iter().position(|c| c.sha == *sha))
+        else {
+            self.status_message = Some(StatusMessage::error(format!(
+                "✗ No commit in this PR changes {path}"
+            )));
+            return;
+        };
+        if !self.load_commit_files(commit) {
+            return;
+        }
+        let Some(file) = self
+            .files_map
+            .get(&self.commits[commit].sha)
+            .and_then(|files| files.iter().position(|f| f.filename == path))
+        else {
+            return;
+        };
+        self.mode = AppMode::Normal;
+        self.select_file_position((commit, file));
+        self.focused_panel = Panel::DiffView;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stat(filename: &str, additions: usize, deletions: usize) -> DiffFile {
+        DiffFile {
+            filename: filename.to_string(),
+            status: "modified".to_string(),
+            additions,
+            deletions,
+            patch: None,
+        }
+    }
+
+    #[test]
+    fn test_summary_rows_group_by_top_level_dir() {
+        let mut summary = LargePrSummary::new(vec![
+            stat("src/b.rs", 3, 1),
+            stat("README.md", 1, 0),
+            stat("src/a/c.rs", 2, 2),
+        ]);
+        assert_eq!(
+            summary.rows(),
+            vec![
+                SummaryRow::Dir {
+                    name: ROOT_DIR.to_string(),
+                    files: 1,
+                    additions: 1,
+                    deletions: 0,
+                    expanded: false,
+                },
+                SummaryRow::Dir {
+                    name: "src".to_string(),
+                    files: 2,
+                    additions: 5,
+                    deletions: 3,
+                    expanded: false,
+                },
+            ]
+        );
+        summary.expanded.insert("src".to_string());
+        let rows = summary.rows();
+        assert_eq!(rows.len(), 4);
+        // ファイルはパス順
+        assert_eq!(rows[2], SummaryRow::File(1));
+        assert_eq!(summary.files[1].filename, "src/a/c.rs");
+    }
+}
@@ -0,0 +1,95 @@
+//! レビュー待ちの経過時間（依頼からの時間・作成者の最後の活動）と SLA 超過の判定
+
+use super::*;
+use crate::config::ReviewConfig;
+use crate::github::review::ReviewRequestTimes;
+
+/// 経過時間の表示（"3d ago" → "3d"）
+pub fn elapsed_label(iso: &str, now: chrono::DateTime<chrono::Utc>) -> String {
+    let relative = crate::format::relative(iso, now);
+    relative
+        .strip_suffix(" ago")
+        .map_or(relative.clone(), str::to_string)
+}
+
+impl App {
+    /// レビュー待ちの SLA をセットする
+    pub fn set_review_sla(&mut self, config: ReviewConfig) {
+        self.review_sla_hours = config.sla_hours;
+    }
+
+    /// タイムラインから集計したレビュー依頼の日時を反映する
+    pub(super) fn apply_review_requests(&mut self, times: ReviewRequestTimes) {
+        self.review_request_times = times;
+    }
+
+    /// 自分（または所属チーム）にレビューが依頼された日時
+    pub(super) fn my_review_requested_at(&self) -> Option<&str> {
+        if self.current_user.is_empty() {
+            return None;
+        }
+        if self.requested_reviewers.contains(&self.current_user) {
+            return self
+                .review_request_times
+                .users
+                .get(&self.current_user)
+                .map(String::as_str);
+        }
+        // 日時はすべて UTC の ISO 8601（Z 付き）なので文字列比較で古い順に並ぶ
+        self.my_requested_teams
+            .iter()
+            .flatten()
+            .filter_map(|team| self.review_request_times.teams.get(team))
+            .map(String::as_str)
+            .min()
+    }
+
+    /// レビュー待ちの起点（自分への依頼を優先し、なければ依頼中のうち最も古いもの）
+    pub(super) fn review_waiting_since(&self) -> Option<&str> {
+        self.my_review_requested_at().or_else(|| {
+            let users = self
+                .requested_reviewers
+                .iter()
+                .filter_map(|u| self.review_request_times.users.get(u));
+            let teams = self
+                .requested_teams
+                .iter()
+                .filter_map(|t| self.review_request_times.teams.get(t));
+            users.chain(teams).map(String::as_str).min()
+        })
+    }
+
+    /// 作成者の最後の活動（コミット・コメント・レビュー・スレッドへの返信）
+    pub(super) fn author_last_active(&self) -> Option<&str> {
+        let commits = self
+            .commits
+            .iter()
+            .filter_map(|c| c.commit.author.as_ref().map(|a| a.date.as_str()));
+        let comments = self
+            .conversation
+            .iter()
+            .filter(|e| e.author == self.pr_author)
+            .map(|e| e.created_at.as_str());
+        let replies = self.conversation.iter().flat_map(|e| match &e.kind {
+            ConversationKind::CodeComment { replies, .. } => replies
+                .iter()
+                .filter(|r| r.author == self.pr_author)
+                .map(|r| r.created_at.as_str())
+                .collect::<Vec<_>>(),
+            _ => Vec::new(),
+        });
+        commits.chain(comments).chain(replies).max()
+    }
+
+    /// 自分への依頼が SLA を超えて待たせているか
+    pub(super) fn review_overdue(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        if self.review_sla_hours == 0 {
+            return false;
+        }
+        self.my_review_requested_at()
+            .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok())
+            .is_some_and(|at| {
+                (now - at.with_timezone(&chrono::Utc)).num_hours() >= self.review_sla_hours as i64
+            })
+    }
+}
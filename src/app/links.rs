@@ -0,0 +1,179 @@
+//! PR 本文と Conversation からリンクを抽出してブラウザで開く
+
+use super::*;
+
+/// 数字キーで直接開けるリンク数（1〜9）
+pub(super) const LINK_LIST_NUMBERED: usize = 9;
+
+/// 抽出したリンク
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkEntry {
+    /// マークダウンリンクのテキスト（素の URL の場合は None）
+    pub title: Option<String>,
+    pub url: String,
+}
+
+/// URL の末尾から除去する文字（文末の句読点や括弧）
+fn trim_url_end(url: &str) -> &str {
+    url.trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '>', '"', '\''])
+}
+
+/// http:// と https:// のうち先に現れる位置
+fn find_url_start(text: &str) -> Option<usize> {
+    match (text.find("http://"), text.find("https://")) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// テキストからリンクを抽出する（`[title](url)` と素の http(s) URL。画像リンクは除外）
+pub(super) fn extract_links(text: &str) -> Vec<LinkEntry> {
+    let mut links = Vec::new();
+    let mut rest = text;
+    while let Some(pos) = find_url_start(rest) {
+        let before = &rest[..pos];
+        let end = rest[pos..]
+            .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | '\'' | '`'))
+            .map_or(rest.len(), |e| pos + e);
+        let raw = &rest[pos..end];
+
+        if let Some(label_end) = before.strip_suffix("](")
+            && let Some(label_start) = label_end.rfind('[')
+        {
+            // マークダウンリンク: 閉じ括弧までが URL
+            let url = raw.split(')').next().unwrap_or(raw);
+            let is_image = label_end[..label_start].ends_with('!');
+            if !is_image {
+                let title = label_end[label_start + 1..].trim();
+                links.push(LinkEntry {
+                    title: (!title.is_empty()).then(|| title.to_string()),
+                    url: url.to_string(),
+                });
+            }
+            rest = &rest[pos + url.len()..];
+            continue;
+        }
+
+        let url = trim_url_end(raw);
+        // <img src="..."> 内の URL は画像なので除外
+        if !before.ends_with("src=\"") && !url.is_empty() {
+            links.push(LinkEntry {
+                title: None,
+                url: url.to_string(),
+            });
+        }
+        rest = &rest[end..];
+    }
+    links
+}
+
+impl App {
+    /// PR 本文と Conversation 全体からリンクを収集（URL 重複は先勝ちで除去）
+    pub(super) fn collect_links(&self) -> Vec<LinkEntry> {
+        let mut bodies: Vec<&str> = vec![&self.pr_body];
+        for entry in &self.conversation {
+            bodies.push(&entry.body);
+            if let ConversationKind::CodeComment { replies, .. } = &entry.kind {
+                bodies.extend(replies.iter().map(|r| r.body.as_str()));
+            }
+        }
+
+        // 図のブロックは kroki で描画した結果を開けるようにする
+        let diagrams = bodies.iter().flat_map(|body| {
+            super::diagram::extract_diagrams(body)
+                .into_iter()
+                .map(|block| LinkEntry {
+                    title: Some(format!("📊 {} diagram", block.kind.name())),
+                    url: block.kroki_url(),
+                })
+        });
+
+        let mut seen = HashSet::new();
+        let mut links: Vec<LinkEntry> = Vec::new();
+        for link in bodies
+            .iter()
+            .flat_map(|body| extract_links(body))
+            .chain(diagrams)
+        {
+            if seen.insert(link.url.clone()) {
+                links.push(link);
+            } else if link.title.is_some()
+                && let Some(existing) = links.iter_mut().find(|l| l.url == link.url)
+                && existing.title.is_none()
+            {
+                // 後から現れたマークダウンリンクのタイトルで補完
+                existing.title = link.title;
+            }
+        }
+        links
+    }
+
+    /// リンク一覧オーバーレイを開く
+    pub(super) fn open_link_list(&mut self) {
+        let links = self.collect_links();
+        if links.is_empty() {
+            self.status_message = Some(StatusMessage::info("✓ No links found"));
+            return;
+        }
+        self.link_list = links;
+        self.link_list_cursor = 0;
+        self.mode = AppMode::LinkList;
+    }
+
+    /// 指定インデックスのリンクをブラウザで開く
+    pub(super) fn open_link_at(&mut self, index: usize) {
+        let Some(link) = self.link_list.get(index) else {
+            return;
+        };
+        let url = link.url.clone();
+        self.open_in_browser(&url, &url);
+        self.mode = AppMode::Normal;
+    }
+
+    /// 数字キーに対応するリンクのインデックス（1 始まり）
+    pub(super) fn link_index_for_digit(&self, ch: char) -> Option<usize> {
+        let n = ch.to_digit(10)? as usize;
+        (1..=LINK_LIST_NUMBERED.min(self.link_list.len()))
+            .contains(&n)
+            .then_some(n - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(title: Option<&str>, url: &str) -> LinkEntry {
+        LinkEntry {
+            title: title.map(str::to_string),
+            url: url.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_extract_markdown_and_bare_links() {
+        let text = "See [Design doc](https://example.com/doc) and https://jira.example.com/T-1.";
+        assert_eq!(
+            extract_links(text),
+            vec![
+                link(Some("Design doc"), "https://example.com/doc"),
+                link(None, "https://jira.example.com/T-1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_links_skips_images() {
+        let text = "![shot](https://example.com/a.png)\n<img src=\"https://example.com/b.png\">";
+        assert!(extract_links(text).is_empty());
+    }
+
+    #[test]
+    fn test_extract_links_autolink_and_parens() {
+        let text = "(<http://dash.example.com/x>)";
+        assert_eq!(
+            extract_links(text),
+            vec![link(None, "http://dash.example.com/x")]
+        );
+    }
+}
@@ -1,17 +1,27 @@
 use super::ThemeMode;
+use super::diagram::{self, DIAGRAM_PLACEHOLDER};
+use super::helpers::truncate_str;
 use crate::git::diff::ansi_to_text;
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use std::io::Write;
-use std::process::{Command, Stdio};
+use std::process::Stdio;
 use std::sync::OnceLock;
 
 static BAT_AVAILABLE: OnceLock<bool> = OnceLock::new();
 
-/// bat の可用性を起動時に1回だけチェック（OnceLock でキャッシュ）
+/// テーブルセル間の区切り
+const TABLE_CELL_SEPARATOR: &str = " │ ";
+/// 幅が足りない場合でも各列に確保する最小幅
+const TABLE_MIN_COLUMN_WIDTH: usize = 3;
+
+/// bat の可用性を1回だけチェック（OnceLock でキャッシュ。未許可の間はキャッシュせず、許可されてから確かめる）
 fn has_bat() -> bool {
+    let Some(mut cmd) = crate::exec::command("bat") else {
+        return false;
+    };
     *BAT_AVAILABLE.get_or_init(|| {
-        Command::new("bat")
-            .arg("--version")
+        cmd.arg("--version")
             .stdout(Stdio::null())
             .stderr(Stdio::null())
             .status()
@@ -35,7 +45,7 @@ fn highlight_with_bat(text: &str, theme: ThemeMode) -> Option<Vec<Line<'static>>
         return None;
     }
 
-    let mut child = Command::new("bat")
+    let mut child = crate::exec::command("bat")?
         .args([
             "--language=markdown",
             "--color=always",
@@ -86,12 +96,283 @@ fn highlight_with_bat(text: &str, theme: ThemeMode) -> Option<Vec<Line<'static>>
 
 /// マークダウンテキストを ratatui Line に変換する。
 /// bat が利用可能なら bat でシンタックスハイライト、なければ生テキストをそのまま表示。
-pub(super) fn render_markdown(text: &str, theme: ThemeMode) -> Vec<Line<'static>> {
-    if let Some(lines) = highlight_with_bat(text, theme) {
+/// テーブルとタスクリストは bat の結果を上書きして整形する（行数は入力と一致させる）。
+/// `max_width` はテーブルを収める幅（0 なら制限なし）。
+/// Mermaid / PlantUML のブロックは 1 行のプレースホルダー（設定によっては ASCII 描画）に置き換える。
+pub(super) fn render_markdown(
+    text: &str,
+    theme: ThemeMode,
+    max_width: usize,
+) -> Vec<Line<'static>> {
+    let text = &diagram::collapse_diagrams(text, crate::format::ascii_diagrams());
+    let mut lines = highlight_with_bat(text, theme)
+        // bat が利用不可の場合は生テキストをそのまま表示
+        .unwrap_or_else(|| text.lines().map(|l| Line::raw(l.to_string())).collect());
+
+    let source: Vec<&str> = text.lines().collect();
+    // bat が行数を変えた場合は上書き位置がずれるため整形しない
+    if lines.len() != source.len() {
         return lines;
     }
-    // bat が利用不可の場合は生テキストをそのまま表示
-    text.lines().map(|l| Line::raw(l.to_string())).collect()
+
+    let outside_code = outside_code_fence(&source);
+    let mut i = 0;
+    while i < source.len() {
+        if outside_code[i]
+            && let Some(len) = table_block_len(&source[i..], &outside_code[i..])
+        {
+            for (offset, line) in render_table(&source[i..i + len], max_width)
+                .into_iter()
+                .enumerate()
+            {
+                lines[i + offset] = line;
+            }
+            i += len;
+            continue;
+        }
+        if outside_code[i]
+            && let Some(line) = render_task_item(source[i])
+        {
+            lines[i] = line;
+        }
+        if outside_code[i] && source[i].starts_with(DIAGRAM_PLACEHOLDER) {
+            lines[i] = Line::styled(source[i].to_string(), Style::default().fg(Color::Cyan));
+        }
+        i += 1;
+    }
+    lines
+}
+
+/// 各行がコードフェンスの外側にあるか（フェンス行自体は内側扱い）
+fn outside_code_fence(lines: &[&str]) -> Vec<bool> {
+    let mut in_code = false;
+    lines
+        .iter()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code = !in_code;
+                return false;
+            }
+            !in_code
+        })
+        .collect()
+}
+
+/// タスクリスト項目を解析する（インデント, チェック済みか, 本文）
+fn parse_task_item(line: &str) -> Option<(&str, bool, &str)> {
+    let body = line.trim_start();
+    let indent = &line[..line.len() - body.len()];
+    let rest = body
+        .strip_prefix("- ")
+        .or_else(|| body.strip_prefix("* "))
+        .or_else(|| body.strip_prefix("+ "))?;
+    let (checked, rest) = if let Some(rest) = rest.strip_prefix("[ ]") {
+        (false, rest)
+    } else if let Some(rest) = rest
+        .strip_prefix("[x]")
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        (true, rest)
+    } else {
+        return None;
+    };
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some((indent, checked, rest.trim_start()))
+}
+
+/// タスクリスト項目をチェックボックスグリフ付きの行に変換
+fn render_task_item(line: &str) -> Option<Line<'static>> {
+    let (indent, checked, content) = parse_task_item(line)?;
+    let (glyph, glyph_style, content_style) = if checked {
+        (
+            "☑ ",
+            Style::default().fg(Color::Green),
+            Style::default().fg(Color::DarkGray),
+        )
+    } else {
+        ("☐ ", Style::default().fg(Color::Yellow), Style::default())
+    };
+    Some(Line::from(vec![
+        Span::raw(indent.to_string()),
+        Span::styled(glyph, glyph_style),
+        Span::styled(content.to_string(), content_style),
+    ]))
+}
+
+/// タスクリストの進捗（完了数, 総数）。タスクがなければ None
+pub(super) fn task_progress(text: &str) -> Option<(usize, usize)> {
+    let source: Vec<&str> = text.lines().collect();
+    let outside_code = outside_code_fence(&source);
+    let (done, total) = source
+        .iter()
+        .zip(&outside_code)
+        .filter(|(_, outside)| **outside)
+        .filter_map(|(line, _)| parse_task_item(line))
+        .fold((0, 0), |(done, total), (_, checked, _)| {
+            (done + usize::from(checked), total + 1)
+        });
+    (total > 0).then_some((done, total))
+}
+
+/// 列の揃え方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnAlign {
+    Left,
+    Center,
+    Right,
+}
+
+/// テーブル行をセルに分割（先頭・末尾のパイプは除去、`\|` はエスケープとして扱う）
+fn split_table_row(line: &str) -> Vec<String> {
+    let trimmed = line.trim();
+    let trimmed = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let trimmed = trimmed.strip_suffix('|').unwrap_or(trimmed);
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut chars = trimmed.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if chars.peek() == Some(&'|') => {
+                current.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(ch),
+        }
+    }
+    cells.push(current.trim().to_string());
+    cells
+}
+
+/// 区切り行（`| --- | :-: |`）を解析して各列の揃え方を返す
+fn parse_delimiter_row(line: &str) -> Option<Vec<ColumnAlign>> {
+    if !line.contains('-') {
+        return None;
+    }
+    split_table_row(line)
+        .iter()
+        .map(|cell| {
+            let inner = cell.trim_start_matches(':').trim_end_matches(':');
+            if inner.is_empty() || !inner.chars().all(|c| c == '-') {
+                return None;
+            }
+            Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+                (true, true) => ColumnAlign::Center,
+                (false, true) => ColumnAlign::Right,
+                _ => ColumnAlign::Left,
+            })
+        })
+        .collect()
+}
+
+/// 先頭からテーブルが始まる場合、その行数を返す（ヘッダー + 区切り行 + 本文行）
+fn table_block_len(lines: &[&str], outside_code: &[bool]) -> Option<usize> {
+    let header = lines.first()?;
+    if !header.contains('|') {
+        return None;
+    }
+    let aligns = parse_delimiter_row(lines.get(1)?)?;
+    if aligns.len() != split_table_row(header).len() {
+        return None;
+    }
+    let body_len = lines[2..]
+        .iter()
+        .zip(&outside_code[2..])
+        .take_while(|(line, outside)| **outside && line.contains('|') && !line.trim().is_empty())
+        .count();
+    Some(2 + body_len)
+}
+
+/// 合計幅が `max_width` に収まるよう、最も広い列から順に縮める
+fn fit_column_widths(widths: &mut [usize], max_width: usize) {
+    if max_width == 0 || widths.is_empty() {
+        return;
+    }
+    let separators = TABLE_CELL_SEPARATOR.chars().count() * (widths.len() - 1);
+    let budget = max_width.saturating_sub(separators);
+    while widths.iter().sum::<usize>() > budget {
+        let Some(widest) = widths
+            .iter_mut()
+            .filter(|w| **w > TABLE_MIN_COLUMN_WIDTH)
+            .max_by_key(|w| **w)
+        else {
+            break;
+        };
+        *widest -= 1;
+    }
+}
+
+/// セルを指定幅・揃え方でパディング（はみ出す場合は省略記号で切り詰め）
+fn pad_cell(cell: &str, width: usize, align: ColumnAlign) -> String {
+    let cell = truncate_str(cell, width);
+    let gap = width.saturating_sub(unicode_width::UnicodeWidthStr::width(cell.as_str()));
+    let (left, right) = match align {
+        ColumnAlign::Left => (0, gap),
+        ColumnAlign::Right => (gap, 0),
+        ColumnAlign::Center => (gap / 2, gap - gap / 2),
+    };
+    format!("{}{}{}", " ".repeat(left), cell, " ".repeat(right))
+}
+
+/// テーブルブロックを列揃えした行に変換（区切り行は罫線になる）
+fn render_table(block: &[&str], max_width: usize) -> Vec<Line<'static>> {
+    let aligns = parse_delimiter_row(block[1]).unwrap_or_default();
+    let columns = aligns.len();
+    let rows: Vec<Vec<String>> = block
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != 1)
+        .map(|(_, line)| {
+            let mut cells = split_table_row(line);
+            cells.resize(columns, String::new());
+            cells
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = (0..columns)
+        .map(|col| {
+            rows.iter()
+                .map(|row| unicode_width::UnicodeWidthStr::width(row[col].as_str()))
+                .max()
+                .unwrap_or(0)
+                .max(1)
+        })
+        .collect();
+    fit_column_widths(&mut widths, max_width);
+
+    let border_style = Style::default().fg(Color::DarkGray);
+    let render_row = |row: &[String], style: Style| -> Line<'static> {
+        let mut spans = Vec::new();
+        for (col, cell) in row.iter().enumerate() {
+            if col > 0 {
+                spans.push(Span::styled(TABLE_CELL_SEPARATOR, border_style));
+            }
+            spans.push(Span::styled(
+                pad_cell(cell, widths[col], aligns[col]),
+                style,
+            ));
+        }
+        Line::from(spans)
+    };
+
+    let mut lines = Vec::with_capacity(block.len());
+    lines.push(render_row(
+        &rows[0],
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+    let rule = widths
+        .iter()
+        .map(|w| "─".repeat(*w))
+        .collect::<Vec<_>>()
+        .join("─┼─");
+    lines.push(Line::styled(rule, border_style));
+    for row in &rows[1..] {
+        lines.push(render_row(row, Style::default()));
+    }
+    lines
 }
 
 #[cfg(test)]
@@ -101,7 +382,7 @@ mod tests {
     #[test]
     fn test_render_markdown_returns_lines() {
         let text = "# Title\n\nSome text\n\n| a | b |\n| - | - |\n| 1 | 2 |";
-        let lines = render_markdown(text, ThemeMode::Dark);
+        let lines = render_markdown(text, ThemeMode::Dark, 0);
         assert!(!lines.is_empty());
         // bat の有無にかかわらず入力行数と出力行数が一致する
         // (bat はハイライトのみで行数を変えない)
@@ -111,7 +392,7 @@ mod tests {
     #[test]
     fn test_render_markdown_preserves_content() {
         let text = "Hello world\n\nSecond line";
-        let lines = render_markdown(text, ThemeMode::Dark);
+        let lines = render_markdown(text, ThemeMode::Dark, 0);
         let text_content: String = lines
             .iter()
             .map(|l| {
@@ -125,4 +406,67 @@ mod tests {
         assert!(text_content.contains("Hello world"));
         assert!(text_content.contains("Second line"));
     }
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_render_markdown_aligns_table_columns() {
+        let text = "| Name | Qty |\n| :-- | --: |\n| apple | 3 |\n| kiwi | 12 |";
+        let lines = render_markdown(text, ThemeMode::Dark, 0);
+        let rendered: Vec<String> = lines.iter().map(line_text).collect();
+        assert_eq!(rendered[0], "Name  │ Qty");
+        assert_eq!(rendered[1], "──────┼────");
+        assert_eq!(rendered[2], "apple │   3");
+        assert_eq!(rendered[3], "kiwi  │  12");
+    }
+
+    #[test]
+    fn test_render_markdown_table_fits_width() {
+        let text = "| a | b |\n| - | - |\n| very long cell content | x |";
+        let lines = render_markdown(text, ThemeMode::Dark, 16);
+        for line in &lines {
+            assert!(unicode_width::UnicodeWidthStr::width(line_text(line).as_str()) <= 16);
+        }
+        assert!(line_text(&lines[2]).contains('…'));
+    }
+
+    #[test]
+    fn test_render_markdown_task_list_glyphs() {
+        let text = "- [ ] todo\n  - [x] done\n- not a task";
+        let lines = render_markdown(text, ThemeMode::Dark, 0);
+        assert_eq!(line_text(&lines[0]), "☐ todo");
+        assert_eq!(line_text(&lines[1]), "  ☑ done");
+        assert!(line_text(&lines[2]).contains("not a task"));
+    }
+
+    #[test]
+    fn test_code_fence_is_not_reformatted() {
+        let text = "```\n- [ ] todo\n| a | b |\n| - | - |\n```";
+        assert_eq!(task_progress(text), None);
+        let lines = render_markdown(text, ThemeMode::Dark, 0);
+        assert!(line_text(&lines[1]).contains("[ ]"));
+        assert!(line_text(&lines[3]).contains("| - | - |"));
+    }
+
+    #[test]
+    fn test_diagram_block_is_collapsed() {
+        let text = "Flow:\n```mermaid\ngraph TD\n  A-->B\n```\n- [ ] done?";
+        let lines = render_markdown(text, ThemeMode::Dark, 0);
+        assert_eq!(lines.len(), 3);
+        assert!(line_text(&lines[1]).starts_with("[📊 Diagram: mermaid]"));
+        assert_eq!(line_text(&lines[2]), "☐ done?");
+    }
+
+    #[test]
+    fn test_task_progress_counts() {
+        assert_eq!(task_progress("- [x] a\n- [ ] b\n* [X] c"), Some((2, 3)));
+        assert_eq!(task_progress("no tasks"), None);
+    }
+
+    #[test]
+    fn test_escaped_pipe_in_table_cell() {
+        assert_eq!(split_table_row(r"| a \| b | c |"), vec!["a | b", "c"]);
+    }
 }
@@ -269,6 +269,22 @@ fn extract_html_attr(tag: &str, attr_name: &str) -> Option<String> {
     Some(rest[..end].to_string())
 }
 
+/// コメントに入れるメディアへの参照（alt テキストがなければ種別と番号、リンクなので画像は再掲しない）
+pub(super) fn media_reference(media: &MediaRef, index: usize, total: usize) -> String {
+    let kind = match media.media_type {
+        MediaType::Image => "Image",
+        MediaType::Video => "Video",
+    };
+    // alt がないときは抽出時に種別名が入っている
+    let label = if media.alt == kind {
+        format!("{kind} {} of {total}", index + 1)
+    } else {
+        format!("{kind}: {}", media.alt)
+    };
+    let label = label.replace('[', "\\[").replace(']', "\\]");
+    format!("> [{label}]({})\n\n", media.url)
+}
+
 impl App {
     /// メディアビューアモードに入る（メディアがある場合のみ）
     pub(super) fn enter_media_viewer(&mut self) {
@@ -283,6 +299,29 @@ impl App {
         self.mode = AppMode::MediaViewer;
     }
 
+    /// c: 表示中の画像・動画への参照を入れて PR コメントを書き始める（下書きがあれば末尾に足す）
+    pub(super) fn comment_on_media(&mut self) {
+        if self.loading.conversation == LoadPhase::Loading {
+            self.status_message =
+                Some(StatusMessage::error("✗ Conversation loading. Please wait."));
+            return;
+        }
+        let Some(reference) = self
+            .media_ref_at(self.media_viewer_index)
+            .map(|media| media_reference(media, self.media_viewer_index, self.media_count()))
+        else {
+            return;
+        };
+        self.review.comment_editor.clear();
+        self.focused_panel = Panel::Conversation;
+        self.mode = AppMode::IssueCommentInput;
+        self.restore_draft();
+        if !self.review.comment_editor.is_empty() {
+            self.review.comment_editor.insert_text("\n\n");
+        }
+        self.review.comment_editor.insert_text(&reference);
+    }
+
     /// 完了したバックグラウンドワーカーの結果をキャッシュに回収する。
     pub(super) fn poll_media_protocol_worker(&mut self) {
         if self
@@ -292,7 +331,12 @@ impl App {
             && let Some(handle) = self.media_protocol_worker.take()
             && let Ok((url, protocol)) = handle.join()
         {
-            self.media_protocol_cache.insert(url, protocol);
+            // プロトコルは元画像と同程度のサイズの画像を保持する
+            let size = self
+                .media_cache
+                .get(&url)
+                .map_or(0, |img| img.as_bytes().len());
+            self.media_protocol_cache.insert(url, protocol, size);
         }
     }
 
@@ -306,7 +350,7 @@ impl App {
             .media_ref_at(self.media_viewer_index)
             .map(|r| (r.media_type.clone(), r.url.clone()));
         if let Some((media_type, url)) = info {
-            if media_type == MediaType::Video || self.media_protocol_cache.contains_key(&url) {
+            if media_type == MediaType::Video || self.media_protocol_cache.contains(&url) {
                 return;
             }
             if let Some(picker) = self.picker.clone()
@@ -397,4 +441,27 @@ mod tests {
         assert_eq!(refs[0].media_type, MediaType::Video);
         assert!(result.contains("[🎬 Video]"));
     }
+
+    #[test]
+    fn test_media_reference() {
+        let image = |alt: &str, url: &str| MediaRef {
+            media_type: MediaType::Image,
+            url: url.to_string(),
+            alt: alt.to_string(),
+        };
+        assert_eq!(
+            media_reference(
+                &image("login [old] form", "https://example.com/a.png"),
+                0,
+                2
+            ),
+            "> [Image: login \\[old\\] form](https://example.com/a.png)\n\n"
+        );
+        // alt のない画像は抽出時に "Image" が入る
+        let (_, refs) = preprocess_pr_body("![](https://example.com/b.png)");
+        assert_eq!(
+            media_reference(&refs[0], 1, 2),
+            "> [Image 2 of 2](https://example.com/b.png)\n\n"
+        );
+    }
 }
@@ -0,0 +1,110 @@
+//! キャッシュのメモリ予算（画像・ハイライト済み diff・コミットごとのファイル差分）
+
+use super::*;
+
+/// ハイライト済みテキストのおおよそのサイズ
+pub(super) fn text_size(text: &Text) -> usize {
+    text.lines
+        .iter()
+        .map(|line| {
+            std::mem::size_of::<Line>()
+                + line
+                    .spans
+                    .iter()
+                    .map(|span| std::mem::size_of::<Span>() + span.content.len())
+                    .sum::<usize>()
+        })
+        .sum()
+}
+
+/// コミット 1 つ分のファイル差分のおおよそのサイズ
+fn files_size(files: &[DiffFile]) -> usize {
+    files
+        .iter()
+        .map(|f| {
+            std::mem::size_of::<DiffFile>()
+                + f.filename.len()
+                + f.status.len()
+                + f.patch.as_ref().map_or(0, String::len)
+        })
+        .sum()
+}
+
+impl App {
+    /// 設定のメモリ予算を各キャッシュに適用する
+    pub fn set_memory_limits(&mut self, memory: MemoryConfig) {
+        self.memory = memory;
+        self.media_cache.set_budget(memory.media_bytes());
+        self.media_protocol_cache.set_budget(memory.media_bytes());
+        self.diff
+            .highlight_cache
+            .set_budget(memory.highlight_bytes());
+    }
+
+    /// ダウンロード済み画像を受け取り、予算を超えた分は捨てる
+    pub(super) fn apply_media_cache(&mut self, mut media_cache: MediaCache) {
+        media_cache.set_budget(self.memory.media_bytes());
+        self.media_cache = media_cache;
+    }
+
+    /// files_map を丸ごと差し替えた（リロード・バックグラウンド読み込み）。捨てたコミットの記録も無効になる
+    pub(super) fn files_map_replaced(&mut self) {
+        self.files_evicted.clear();
+        self.files_usage_stale = true;
+    }
+
+    /// files_usage を files_map に合わせる（なくなったコミットは外し、増えた・サイズが変わったものは入れ直す）
+    fn sync_files_usage(&mut self) {
+        let files_map = &self.files_map;
+        self.files_usage.retain(|sha| files_map.contains_key(sha));
+        for (sha, files) in files_map {
+            let size = files_size(files);
+            if self.files_usage.size(sha) != Some(size) {
+                self.files_usage.insert(sha.clone(), size);
+            }
+        }
+    }
+
+    /// files_map が予算を超えたら古いコミットのファイルを捨てる（選択時に遅延読み込みで再取得する）
+    pub(super) fn enforce_files_budget(&mut self) {
+        // 読み込み中はキャッシュ書き込み前なので捨てない
+        if self.async_rx.is_some() {
+            return;
+        }
+        // files_map が変わっていれば追跡を合わせ直し、選択中のコミットを最新にして予算を適用する
+        self.files_usage.set_budget(usize::MAX);
+        if self.files_usage_stale {
+            self.files_usage_stale = false;
+            self.sync_files_usage();
+        }
+        if let Some(sha) = self.current_commit_sha() {
+            self.files_usage.touch(&sha);
+        }
+        let evicted = self.files_usage.set_budget(self.memory.files_bytes());
+        if evicted.is_empty() {
+            return;
+        }
+        for sha in evicted {
+            self.files_map.remove(&sha);
+            self.files_evicted.insert(sha);
+        }
+        // 捨てたコミットは選択時に読み込み直す（キャッシュも一部のコミットしか持たないため書かない）
+        self.lazy_files = true;
+        self.rebuild_visible_comment_cache();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_size_counts_span_content() {
+        let empty = text_size(&Text::from(vec![Line::from(Vec::<Span>::new())]));
+        let text = Text::from(vec![Line::from(vec![Span::raw("abc"), Span::raw("de")])]);
+        assert_eq!(
+            text_size(&text),
+            empty + 2 * std::mem::size_of::<Span>() + 5
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//! スレッド・PR 単位のミュート（ポーリングや通知でアラートしない対象の管理）
+
+use super::*;
+use crate::github::cache::{self, MuteCache};
+
+impl App {
+    /// PR ノード ID と保存済みのミュート状態をセットする
+    pub fn set_mutes(&mut self, pr_node_id: String, saved: Option<MuteCache>) {
+        let saved = saved.unwrap_or_default();
+        self.pr_node_id = pr_node_id;
+        self.muted_threads = saved.threads;
+        self.pr_muted = saved.pr;
+    }
+
+    /// m: カーソル位置のスレッドのミュートを切り替える（GitHub にスレッド単位の購読はないためローカル保存）
+    pub(super) fn toggle_thread_mute(&mut self) {
+        let Some(root_comment_id) =
+            self.conversation
+                .get(self.conversation_cursor)
+                .and_then(|entry| match entry.kind {
+                    ConversationKind::CodeComment {
+                        root_comment_id, ..
+                    } => Some(root_comment_id),
+                    _ => None,
+                })
+        else {
+            self.status_message = Some(StatusMessage::error("✗ Not a review thread"));
+            return;
+        };
+        let muted = if self.muted_threads.remove(&root_comment_id) {
+            false
+        } else {
+            self.muted_threads.insert(root_comment_id);
+            true
+        };
+        self.conversation_rendered = None; // キャッシュ無効化
+        if self.save_mutes() {
+            self.status_message = Some(StatusMessage::info(if muted {
+                "✓ Thread muted"
+            } else {
+                "✓ Thread unmuted"
+            }));
+        }
+    }
+
+    /// M: PR 全体のミュート切替を要求する
+    pub(super) fn request_pr_mute_toggle(&mut self) {
+        if self.pr_node_id.is_empty() {
+            self.status_message = Some(StatusMessage::error("✗ PR node ID not available"));
+            return;
+        }
+        self.needs_pr_mute = true;
+    }
+
+    /// GitHub の subscription を更新して PR 全体のミュートを切り替える（draw 後に呼ばれる）
+    pub(super) fn execute_pr_mute(&mut self) {
+        match crate::github::pr::set_pr_subscription(&self.pr_node_id, !self.pr_muted) {
+            Ok(muted) => {
+                self.pr_muted = muted;
+                if self.save_mutes() {
+                    self.status_message = Some(StatusMessage::info(if muted {
+                        "✓ PR muted (notifications ignored)"
+                    } else {
+                        "✓ PR unmuted (subscribed)"
+                    }));
+                }
+            }
+            Err(e) => {
+                self.status_message = Some(StatusMessage::error(format!("✗ Failed: {}", e)));
+            }
+        }
+    }
+
+    /// ミュート状態を保存する（失敗時はステータスバーに表示して false）
+    fn save_mutes(&mut self) -> bool {
+        let Some((owner, repo)) = self.parse_repo() else {
+            return false;
+        };
+        let data = MuteCache {
+            threads: self.muted_threads.clone(),
+            pr: self.pr_muted,
+        };
+        if let Err(e) = cache::write_pr_json(owner, repo, self.pr_number, cache::MUTE_SUFFIX, &data)
+        {
+            self.status_message =
+                Some(StatusMessage::error(format!("✗ Failed to save mutes: {e}")));
+            return false;
+        }
+        true
+    }
+}
@@ -17,23 +17,25 @@ impl App {
             .is_some_and(|line| line.starts_with("@@"))
     }
 
-    /// hunk header をスキップして次の非 @@ 行に進む（下方向）
+    /// hunk header（と折りたたまれた行）をスキップして次の行に進む（下方向）
     pub(super) fn skip_hunk_header_forward(&self, line: usize, max: usize) -> usize {
+        let collapsed = self.collapsed_hunks();
         let mut l = line;
-        while l < max && self.is_hunk_header(l) {
+        while l < max && self.is_cursor_skipped_line(l, &collapsed) {
             l += 1;
         }
         if l >= max { line } else { l }
     }
 
-    /// hunk header をスキップして前の非 @@ 行に戻る（上方向）
+    /// hunk header（と折りたたまれた行）をスキップして前の行に戻る（上方向）
     pub(super) fn skip_hunk_header_backward(&self, line: usize, max: usize) -> usize {
+        let collapsed = self.collapsed_hunks();
         let mut l = line;
-        while l > 0 && self.is_hunk_header(l) {
+        while l > 0 && self.is_cursor_skipped_line(l, &collapsed) {
             l -= 1;
         }
         // 行 0 が @@ の場合は下方向にスキップ
-        if self.is_hunk_header(l) {
+        if self.is_cursor_skipped_line(l, &collapsed) {
             self.skip_hunk_header_forward(l, max)
         } else {
             l
@@ -68,7 +70,7 @@ impl App {
             }
             Panel::CommitList if !self.commits.is_empty() => {
                 let current = self.commit_list_state.selected().unwrap_or(0);
-                let next = (current + 1).min(self.commits.len() - 1);
+                let next = (current + 1).min(self.commit_row_count() - 1);
                 self.commit_list_state.select(Some(next));
                 if next != current {
                     self.reset_file_selection();
@@ -78,7 +80,7 @@ impl App {
                 let files_len = self.current_files().len();
                 if files_len > 0 {
                     let current = self.file_list_state.selected().unwrap_or(0);
-                    let next = (current + 1).min(files_len - 1);
+                    let next = self.adjacent_tree_file(current, true);
                     self.file_list_state.select(Some(next));
                     if next != current {
                         self.reset_cursor();
@@ -120,7 +122,7 @@ impl App {
                 let files_len = self.current_files().len();
                 if files_len > 0 {
                     let current = self.file_list_state.selected().unwrap_or(0);
-                    let prev = current.saturating_sub(1);
+                    let prev = self.adjacent_tree_file(current, false);
                     self.file_list_state.select(Some(prev));
                     if prev != current {
                         self.reset_cursor();
@@ -187,7 +189,7 @@ impl App {
 
     /// カーソルエントリを画面中央に配置（j で入った時 = 先頭から表示）
     /// 先頭/末尾エントリは clamp により自然にエリア端にフィットする
-    fn center_conversation_on_cursor(&mut self) {
+    pub(super) fn center_conversation_on_cursor(&mut self) {
         let offsets = &self.conversation_visual_offsets;
         let cursor = self.conversation_cursor;
         if offsets.len() <= cursor + 1 {
@@ -246,13 +248,245 @@ impl App {
         self.conversation_cursor = cursor;
     }
 
-    /// カーソルをリセット（先頭の @@ 行をスキップ）
+    /// カーソルをリセット（前回表示時の位置があれば復元、なければ先頭の @@ 行をスキップ）
     pub(super) fn reset_cursor(&mut self) {
+        self.update_followed_file();
+        self.review.viewing_comment_scroll = 0;
+        if self.swap_diff_position() {
+            return;
+        }
         self.diff.cursor_line = 0;
         self.diff.scroll = 0;
         let max = self.current_diff_line_count();
         self.diff.cursor_line = self.skip_hunk_header_forward(0, max);
-        self.review.viewing_comment_scroll = 0;
+    }
+
+    /// 選択中の (コミット SHA, ファイル名)
+    pub(super) fn diff_position_key(&self) -> Option<(String, String)> {
+        Some((
+            self.current_commit_sha()?,
+            self.current_file()?.filename.clone(),
+        ))
+    }
+
+    /// 直前に表示していたファイルの位置を保存し、選択中のファイルの保存位置を復元する（未保存なら false）
+    pub(super) fn swap_diff_position(&mut self) -> bool {
+        if let Some(key) = self.diff.position_key.take() {
+            // visual_offsets は直前の render（= 直前のファイル）のもの
+            let position = DiffPosition {
+                cursor_line: self.diff.cursor_line,
+                scroll: self.diff.scroll,
+                top_line: self.visual_to_logical_line(self.diff.scroll as usize),
+                wrap: self.diff.wrap,
+                view_width: self.diff.view_width,
+            };
+            self.diff.positions.insert(key, position);
+        }
+        self.diff.position_key = self.diff_position_key();
+        self.diff.visual_offsets = None;
+        let Some(saved) = self
+            .diff
+            .position_key
+            .as_ref()
+            .and_then(|key| self.diff.positions.get(key))
+            .copied()
+        else {
+            return false;
+        };
+        let last = self.current_diff_line_count().saturating_sub(1);
+        self.diff.cursor_line = saved.cursor_line.min(last);
+        self.diff.scroll =
+            if saved.wrap == self.diff.wrap && saved.view_width == self.diff.view_width {
+                saved.scroll
+            } else {
+                self.visual_line_offset(saved.top_line.min(last)) as u16
+            };
+        self.ensure_cursor_visible();
+        true
+    }
+
+    /// (コミット, ファイル) 位置の次/前のファイル位置（コミット境界をまたぎ、ファイルのないコミットは飛ばす）
+    fn adjacent_file_position(
+        &self,
+        (commit, file): (usize, usize),
+        forward: bool,
+    ) -> Option<(usize, usize)> {
+        let file_count = |c: usize| {
+            self.commit_key_at(c)
+                .and_then(|sha| self.files_for(sha))
+                .map_or(0, Vec::len)
+        };
+        // All changes はコミットをまたがず、その中だけで移動する
+        if commit >= self.commits.len() {
+            return if forward {
+                (file + 1 < file_count(commit)).then_some((commit, file + 1))
+            } else {
+                file.checked_sub(1).map(|f| (commit, f))
+            };
+        }
+        if forward {
+            if file + 1 < file_count(commit) {
+                return Some((commit, file + 1));
+            }
+            (commit + 1..self.commits.len())
+                .find(|&c| file_count(c) > 0)
+                .map(|c| (c, 0))
+        } else {
+            if file > 0 {
+                return Some((commit, file - 1));
+            }
+            (0..commit)
+                .rev()
+                .find(|&c| file_count(c) > 0)
+                .map(|c| (c, file_count(c) - 1))
+        }
+    }
+
+    /// 指定位置のファイルを選択し、コミット選択と Files ペインを同期する
+    pub(super) fn select_file_position(&mut self, (commit, file): (usize, usize)) {
+        if self.commit_list_state.selected() != Some(commit) {
+            self.commit_list_state.select(Some(commit));
+            self.reset_file_selection();
+        }
+        self.file_list_state.select(Some(file));
+        self.reset_cursor();
+    }
+
+    /// コミット内のファイルで、指定側・ファイル行に対応する diff 行インデックス
+    fn diff_line_for(
+        &self,
+        commit: usize,
+        file: usize,
+        side: review::Side,
+        line: usize,
+    ) -> Option<usize> {
+        let patch = self
+            .files_map
+            .get(&self.commits.get(commit)?.sha)?
+            .get(file)?
+            .patch
+            .as_deref()?;
+        review::parse_patch_line_map(patch)
+            .iter()
+            .position(|info| info.is_some_and(|i| i.side == side && i.file_line == line))
+    }
+
+    /// Enter / gd: Conversation のコードコメントが指すファイル・行へ DiffView で移動する
+    /// （commit_id のコミットを優先し、その diff に行がなければ新しいコミットから探す）
+    pub(super) fn jump_to_conversation_thread(&mut self) {
+        let Some(ConversationKind::CodeComment {
+            root_comment_id, ..
+        }) = self
+            .conversation
+            .get(self.conversation_cursor)
+            .map(|e| &e.kind)
+        else {
+            return;
+        };
+        let Some(root) = self
+            .review
+            .review_comments
+            .iter()
+            .find(|c| c.id == *root_comment_id)
+        else {
+            self.status_message = Some(StatusMessage::error("Thread info not available"));
+            return;
+        };
+        let root = root.clone();
+        let path = root.path.clone();
+        let line = root.line;
+        let side = review::Side::from_api(root.side.as_deref());
+        let file_index = |commit: usize| {
+            self.commits
+                .get(commit)
+                .and_then(|c| self.files_map.get(&c.sha))
+                .and_then(|files| files.iter().position(|f| f.filename == path))
+        };
+        let preferred = self.commits.iter().position(|c| c.sha == root.commit_id);
+        let candidates: Vec<usize> = preferred
+            .into_iter()
+            .chain((0..self.commits.len()).rev())
+            .filter_map(|commit| file_index(commit).map(|_| commit))
+            .collect();
+        let Some(&fallback) = candidates.first() else {
+            self.status_message = Some(StatusMessage::error("✗ File not found in PR commits"));
+            return;
+        };
+
+        let target = line.and_then(|line| {
+            candidates.iter().find_map(|&commit| {
+                let file = file_index(commit)?;
+                self.diff_line_for(commit, file, side, line)
+                    .map(|diff_line| (commit, file, diff_line))
+            })
+        });
+        self.focused_panel = Panel::DiffView;
+        match target {
+            Some((commit, file, diff_line)) => {
+                self.select_file_position((commit, file));
+                self.diff.cursor_line = diff_line;
+                // 複数行コメントは範囲の先頭から見えるようにする
+                let start = self
+                    .current_file()
+                    .and_then(|f| f.patch.as_deref())
+                    .map(review::parse_patch_line_map)
+                    .and_then(|line_map| Self::comment_diff_range(&root, &line_map))
+                    .map_or(diff_line, |(start, _)| start);
+                self.scroll_range_into_view(start, diff_line);
+            }
+            None => {
+                let file = file_index(fallback).unwrap_or(0);
+                self.select_file_position((fallback, file));
+                self.status_message =
+                    Some(StatusMessage::info("Commented line is not in the diff"));
+            }
+        }
+    }
+
+    /// ]f / [f: DiffView のまま次/前のファイルへ移動する
+    pub(super) fn jump_to_adjacent_file(&mut self, forward: bool) {
+        let Some(commit) = self.commit_list_state.selected() else {
+            return;
+        };
+        let file = self.file_list_state.selected().unwrap_or(0);
+        match self.adjacent_file_position((commit, file), forward) {
+            Some(pos) => self.select_file_position(pos),
+            None => {
+                self.status_message = Some(StatusMessage::info(if forward {
+                    "No next file"
+                } else {
+                    "No previous file"
+                }));
+            }
+        }
+    }
+
+    /// } / {: 次/前の未確認（viewed でない）ファイルへ移動する（コミット境界をまたぐ）
+    pub(super) fn jump_to_unviewed_file(&mut self, forward: bool) {
+        let Some(commit) = self.commit_list_state.selected() else {
+            return;
+        };
+        let mut pos = (commit, self.file_list_state.selected().unwrap_or(0));
+        while let Some(next) = self.adjacent_file_position(pos, forward) {
+            let (c, f) = next;
+            let Some(sha) = self.commit_key_at(c) else {
+                break;
+            };
+            let viewed = self
+                .files_for(sha)
+                .and_then(|files| files.get(f))
+                .is_some_and(|file| self.is_file_viewed(sha, &file.filename));
+            if !viewed {
+                self.select_file_position(next);
+                return;
+            }
+            pos = next;
+        }
+        self.status_message = Some(StatusMessage::info(if forward {
+            "✓ No unviewed files ahead"
+        } else {
+            "✓ No unviewed files behind"
+        }));
     }
 
     /// カーソルを下に移動（@@ 行をスキップ）
@@ -278,7 +512,7 @@ impl App {
 
     /// 行番号プレフィックスの表示幅を返す
     pub(super) fn line_number_prefix_width(&self) -> u16 {
-        if !self.diff.show_line_numbers {
+        if !self.diff.show_line_numbers && !self.is_new_file_view() {
             return 0;
         }
         let file_status = self.current_file().map(|f| f.status.as_str()).unwrap_or("");
@@ -288,12 +522,42 @@ impl App {
         }
     }
 
-    /// wrap 有効時に論理行の表示行オフセットを計算する。
+    /// フォールバック計算での 1 論理行の表示行数（折りたたまれた行は 0）
+    fn fallback_line_rows(
+        &self,
+        idx: usize,
+        line: &str,
+        prefix_width: usize,
+        collapsed: &[(usize, std::ops::Range<usize>)],
+    ) -> usize {
+        if collapsed.iter().any(|(_, body)| body.contains(&idx)) {
+            return 0;
+        }
+        let width = self.diff.view_width;
+        if !self.diff.wrap || width == 0 {
+            return 1;
+        }
+        // @@ 行はプレフィックスなし、それ以外はプレフィックス幅分を加味
+        if line.starts_with("@@") || prefix_width == 0 {
+            Paragraph::new(line)
+                .wrap(Wrap { trim: false })
+                .line_count(width)
+                .max(1)
+        } else {
+            let padded = format!("{}{}", " ".repeat(prefix_width), line);
+            Paragraph::new(padded.as_str())
+                .wrap(Wrap { trim: false })
+                .line_count(width)
+                .max(1)
+        }
+    }
+
+    /// wrap 有効時（または hunk 折りたたみ時）に論理行の表示行オフセットを計算する。
     /// 論理行 `logical_line` が始まる表示行番号を返す。
     /// `logical_line == line_count` のとき、合計表示行数を返す。
     /// render 時に計算したキャッシュを優先し、未計算時は patch テキストからフォールバック。
     pub(super) fn visual_line_offset(&self, logical_line: usize) -> usize {
-        if !self.diff.wrap {
+        if !self.diff_uses_visual_offsets() {
             return logical_line;
         }
         // キャッシュがあればそれを使う（レンダリングと同じデータソース）
@@ -304,8 +568,7 @@ impl App {
                 .unwrap_or_else(|| offsets.last().copied().unwrap_or(logical_line));
         }
         // フォールバック: patch テキストから計算（初回 render 前・テスト用）
-        let width = self.diff.view_width;
-        if width == 0 {
+        if self.diff.wrap && self.diff.view_width == 0 {
             return logical_line;
         }
         let prefix_width = self.line_number_prefix_width() as usize;
@@ -313,45 +576,35 @@ impl App {
             Some(p) => p,
             None => return logical_line,
         };
-        let mut visual = 0;
-        for (i, line) in patch.lines().enumerate() {
-            if i >= logical_line {
-                break;
-            }
-            // @@ 行はプレフィックスなし、それ以外はプレフィックス幅分を加味
-            let count = if line.starts_with("@@") || prefix_width == 0 {
-                Paragraph::new(line)
-                    .wrap(Wrap { trim: false })
-                    .line_count(width)
-                    .max(1)
-            } else {
-                let padded = format!("{}{}", " ".repeat(prefix_width), line);
-                Paragraph::new(padded.as_str())
-                    .wrap(Wrap { trim: false })
-                    .line_count(width)
-                    .max(1)
-            };
-            visual += count;
-        }
-        visual
+        let collapsed = self.collapsed_hunks();
+        patch
+            .lines()
+            .enumerate()
+            .take(logical_line)
+            .map(|(i, line)| self.fallback_line_rows(i, line, prefix_width, &collapsed))
+            .sum()
+    }
+
+    /// 論理行が占める表示行の範囲（wrap で折り返した行をすべて含む。折りたたみ中は空）
+    pub(super) fn visual_rows(&self, logical_line: usize) -> std::ops::Range<usize> {
+        self.visual_line_offset(logical_line)..self.visual_line_offset(logical_line + 1)
     }
 
-    /// wrap 有効時に表示行位置から論理行を逆引きする
+    /// wrap 有効時（または hunk 折りたたみ時）に表示行位置から論理行を逆引きする
     pub(super) fn visual_to_logical_line(&self, visual_target: usize) -> usize {
-        if !self.diff.wrap {
+        if !self.diff_uses_visual_offsets() {
             return visual_target;
         }
         // キャッシュがあればそれを使う
         if let Some(offsets) = &self.diff.visual_offsets {
-            // offsets[i] = 論理行 i の開始表示行。visual_target 以下で最大の i を探す。
-            return match offsets.binary_search(&visual_target) {
-                Ok(i) => i,
-                Err(i) => i.saturating_sub(1),
-            };
+            // offsets[i] = 論理行 i の開始表示行。visual_target 以下で最大の i を探す
+            // （折りたたまれた行はオフセットが重複するため、その次の表示行を選ぶ）
+            return offsets
+                .partition_point(|&o| o <= visual_target)
+                .saturating_sub(1);
         }
         // フォールバック: patch テキストから計算
-        let width = self.diff.view_width;
-        if width == 0 {
+        if self.diff.wrap && self.diff.view_width == 0 {
             return visual_target;
         }
         let prefix_width = self.line_number_prefix_width() as usize;
@@ -359,20 +612,10 @@ impl App {
             Some(p) => p,
             None => return visual_target,
         };
+        let collapsed = self.collapsed_hunks();
         let mut visual = 0;
         for (i, line) in patch.lines().enumerate() {
-            let count = if line.starts_with("@@") || prefix_width == 0 {
-                Paragraph::new(line)
-                    .wrap(Wrap { trim: false })
-                    .line_count(width)
-                    .max(1)
-            } else {
-                let padded = format!("{}{}", " ".repeat(prefix_width), line);
-                Paragraph::new(padded.as_str())
-                    .wrap(Wrap { trim: false })
-                    .line_count(width)
-                    .max(1)
-            };
+            let count = self.fallback_line_rows(i, line, prefix_width, &collapsed);
             if visual + count > visual_target {
                 return i;
             }
@@ -383,12 +626,13 @@ impl App {
 
     /// カーソルが画面内に収まるようスクロールを調整
     pub(super) fn ensure_cursor_visible(&mut self) {
+        self.reveal_cursor_hunk();
         let visible_lines = self.diff.view_height as usize;
         if visible_lines == 0 {
             return;
         }
 
-        if self.diff.wrap {
+        if self.diff_uses_visual_offsets() {
             let cursor_visual = self.visual_line_offset(self.diff.cursor_line);
             let cursor_visual_end = self.visual_line_offset(self.diff.cursor_line + 1);
             let scroll = self.diff.scroll as usize;
@@ -407,6 +651,31 @@ impl App {
         }
     }
 
+    /// スレッドの対象行範囲が画面に収まるようスクロールする（収まらない場合はカーソル優先）
+    pub(super) fn scroll_thread_into_view(&mut self) {
+        let Some((start, end)) = self.viewing_thread_range() else {
+            return;
+        };
+        self.scroll_range_into_view(start, end);
+    }
+
+    /// diff 行範囲が画面に収まるならその先頭から見えるようにスクロールする（カーソルは常に表示）
+    pub(super) fn scroll_range_into_view(&mut self, start: usize, end: usize) {
+        let visible_lines = self.diff.view_height as usize;
+        let (start_visual, end_visual) = if self.diff_uses_visual_offsets() {
+            (
+                self.visual_line_offset(start),
+                self.visual_line_offset(end + 1),
+            )
+        } else {
+            (start, end + 1)
+        };
+        if end_visual - start_visual <= visible_lines && start_visual < self.diff.scroll as usize {
+            self.diff.scroll = start_visual as u16;
+        }
+        self.ensure_cursor_visible();
+    }
+
     /// 現在の diff の行数を取得
     pub(super) fn current_diff_line_count(&self) -> usize {
         self.current_file()
@@ -422,7 +691,7 @@ impl App {
         }
         let half = (self.diff.view_height as usize) / 2;
         let line_count = self.current_diff_line_count();
-        if self.diff.wrap {
+        if self.diff_uses_visual_offsets() {
             let target_visual = self.visual_line_offset(self.diff.cursor_line) + half;
             self.diff.cursor_line = self
                 .visual_to_logical_line(target_visual)
@@ -442,7 +711,7 @@ impl App {
         }
         let half = (self.diff.view_height as usize) / 2;
         let line_count = self.current_diff_line_count();
-        if self.diff.wrap {
+        if self.diff_uses_visual_offsets() {
             let cur_visual = self.visual_line_offset(self.diff.cursor_line);
             let target_visual = cur_visual.saturating_sub(half);
             self.diff.cursor_line = self.visual_to_logical_line(target_visual);
@@ -471,7 +740,7 @@ impl App {
         }
         let page = self.diff.view_height as usize;
         let line_count = self.current_diff_line_count();
-        if self.diff.wrap {
+        if self.diff_uses_visual_offsets() {
             let target_visual = self.visual_line_offset(self.diff.cursor_line) + page;
             self.diff.cursor_line = self
                 .visual_to_logical_line(target_visual)
@@ -491,7 +760,7 @@ impl App {
         }
         let page = self.diff.view_height as usize;
         let line_count = self.current_diff_line_count();
-        if self.diff.wrap {
+        if self.diff_uses_visual_offsets() {
             let cur_visual = self.visual_line_offset(self.diff.cursor_line);
             let target_visual = cur_visual.saturating_sub(page);
             self.diff.cursor_line = self.visual_to_logical_line(target_visual);
@@ -623,6 +892,31 @@ impl App {
         }
     }
 
+    /// 次の検索マッチ行にジャンプ
+    pub(super) fn jump_to_next_match(&mut self) {
+        if let Some(&target) = self
+            .search_match_lines()
+            .iter()
+            .find(|&&idx| idx > self.diff.cursor_line)
+        {
+            self.diff.cursor_line = target;
+            self.ensure_cursor_visible();
+        }
+    }
+
+    /// 前の検索マッチ行にジャンプ
+    pub(super) fn jump_to_prev_match(&mut self) {
+        if let Some(&target) = self
+            .search_match_lines()
+            .iter()
+            .rev()
+            .find(|&&idx| idx < self.diff.cursor_line)
+        {
+            self.diff.cursor_line = target;
+            self.ensure_cursor_visible();
+        }
+    }
+
     /// スクリーン上の相対 Y 座標（DiffView 内部、ボーダー除外済み）から
     /// 論理 diff 行番号に変換する。hunk header はスキップ。
     pub(super) fn diff_line_at_y(&self, relative_y: u16) -> Option<usize> {
@@ -632,8 +926,8 @@ impl App {
         if line_count == 0 || logical_line >= line_count {
             return None;
         }
-        // hunk header をスキップ
-        let line = if self.is_hunk_header(logical_line) {
+        // hunk header をスキップ（折りたたまれた hunk の header には止まる）
+        let line = if self.is_cursor_skipped_line(logical_line, &self.collapsed_hunks()) {
             let forward = self.skip_hunk_header_forward(logical_line, line_count);
             if forward >= line_count {
                 self.skip_hunk_header_backward(logical_line, line_count)
@@ -646,39 +940,55 @@ impl App {
         if line >= line_count { None } else { Some(line) }
     }
 
+    /// サイドバー上段のペイン（入れ替え中は Conversation）
+    pub(super) fn sidebar_top_panel(&self) -> Panel {
+        if self.conversation_in_sidebar {
+            Panel::Conversation
+        } else {
+            Panel::PrDescription
+        }
+    }
+
+    /// V: サイドバー上段の PR Description と Conversation を入れ替える
+    pub(super) fn toggle_conversation_in_sidebar(&mut self) {
+        let top = self.sidebar_top_panel();
+        self.conversation_in_sidebar = !self.conversation_in_sidebar;
+        // サイドバー上段にいたならフォーカスも上段に追従させる
+        if self.focused_panel == top {
+            self.focused_panel = self.sidebar_top_panel();
+        }
+        self.status_message = Some(StatusMessage::info(if self.conversation_in_sidebar {
+            "✓ Conversation in sidebar"
+        } else {
+            "✓ Description in sidebar"
+        }));
+    }
+
     pub(super) fn next_panel(&mut self) {
-        // DiffView / CommitMessage / Conversation / CommitOverview は Tab 巡回の対象外
-        if matches!(
-            self.focused_panel,
-            Panel::DiffView | Panel::CommitMessage | Panel::Conversation | Panel::CommitOverview
-        ) {
+        // DiffView / CommitMessage / CommitOverview と右カラムの Conversation（入れ替え中は PR Description）は Tab 巡回の対象外
+        let top = self.sidebar_top_panel();
+        if !matches!(self.focused_panel, Panel::CommitList | Panel::FileTree)
+            && self.focused_panel != top
+        {
             return;
         }
         self.focused_panel = match self.focused_panel {
-            Panel::PrDescription => Panel::CommitList,
             Panel::CommitList => Panel::FileTree,
-            Panel::FileTree => Panel::PrDescription,
-            Panel::CommitMessage
-            | Panel::DiffView
-            | Panel::Conversation
-            | Panel::CommitOverview => unreachable!(),
+            Panel::FileTree => top,
+            _ => Panel::CommitList,
         }
     }
     pub(super) fn prev_panel(&mut self) {
-        if matches!(
-            self.focused_panel,
-            Panel::DiffView | Panel::CommitMessage | Panel::Conversation | Panel::CommitOverview
-        ) {
+        let top = self.sidebar_top_panel();
+        if !matches!(self.focused_panel, Panel::CommitList | Panel::FileTree)
+            && self.focused_panel != top
+        {
             return;
         }
         self.focused_panel = match self.focused_panel {
-            Panel::PrDescription => Panel::FileTree,
-            Panel::CommitList => Panel::PrDescription,
+            Panel::CommitList => top,
             Panel::FileTree => Panel::CommitList,
-            Panel::CommitMessage
-            | Panel::DiffView
-            | Panel::Conversation
-            | Panel::CommitOverview => unreachable!(),
+            _ => Panel::FileTree,
         }
     }
 }
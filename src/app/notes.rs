@@ -0,0 +1,107 @@
+//! ファイルごとの非公開レビューメモ（ローカル保存のみ、GitHub には送信しない）
+
+use super::*;
+use crate::github::cache::{self, NotesCache};
+
+impl App {
+    /// 保存済みのメモをセットする
+    pub fn set_notes(&mut self, saved: Option<NotesCache>) {
+        self.file_notes = saved.unwrap_or_default().notes;
+    }
+
+    /// N: 選択中ファイルのメモ編集を開始する（既存のメモを読み込む）
+    pub(super) fn open_note_editor(&mut self) {
+        let Some(filename) = self.current_file().map(|f| f.filename.clone()) else {
+            return;
+        };
+        self.review.comment_editor.clear();
+        if let Some(note) = self.file_notes.get(&filename) {
+            self.review.comment_editor.insert_text(note);
+        }
+        self.note_target = Some(filename);
+        self.mode = AppMode::NoteInput;
+    }
+
+    /// 編集中のメモを保存する（空なら削除）
+    pub(super) fn save_note(&mut self) {
+        let Some(filename) = self.note_target.take() else {
+            return;
+        };
+        let text = self.review.comment_editor.text();
+        self.review.comment_editor.clear();
+        self.mode = AppMode::Normal;
+        let message = if text.trim().is_empty() {
+            self.file_notes.remove(&filename);
+            "✓ Note deleted"
+        } else {
+            self.file_notes.insert(filename, text);
+            "✓ Note saved (local only)"
+        };
+        if self.save_notes() {
+            self.status_message = Some(StatusMessage::info(message));
+        }
+    }
+
+    /// Ctrl+P: メモを保存し、その内容で PR コメントの下書きを開く
+    pub(super) fn promote_note(&mut self) {
+        let Some(filename) = self.note_target.clone() else {
+            return;
+        };
+        let text = self.review.comment_editor.text();
+        if text.trim().is_empty() {
+            self.status_message = Some(StatusMessage::error("✗ Note is empty"));
+            return;
+        }
+        self.save_note();
+        self.review.comment_editor.clear();
+        self.review
+            .comment_editor
+            .insert_text(&format!("`{}`\n\n{}", filename, text.trim_end()));
+        self.focused_panel = Panel::Conversation;
+        self.mode = AppMode::IssueCommentInput;
+    }
+
+    /// 全メモをマークダウンにまとめる
+    pub(super) fn notes_markdown(&self) -> String {
+        let mut out = format!("# Review notes: {}#{}\n", self.repo, self.pr_number);
+        for (path, note) in &self.file_notes {
+            out.push_str(&format!("\n## `{}`\n\n{}\n", path, note.trim_end()));
+        }
+        out
+    }
+
+    /// E: 全メモをカレントディレクトリのマークダウンファイルに書き出す
+    pub(super) fn export_notes(&mut self) {
+        if self.file_notes.is_empty() {
+            self.status_message = Some(StatusMessage::error("✗ No notes to export"));
+            return;
+        }
+        let path = format!("prism-notes-pr{}.md", self.pr_number);
+        self.status_message = Some(match std::fs::write(&path, self.notes_markdown()) {
+            Ok(()) => StatusMessage::info(format!(
+                "✓ Exported {} note(s) to {}",
+                self.file_notes.len(),
+                path
+            )),
+            Err(e) => StatusMessage::error(format!("✗ Failed to export notes: {e}")),
+        });
+    }
+
+    /// メモを PR ごとのファイルに保存する（失敗時はステータスバーに表示して false）
+    fn save_notes(&mut self) -> bool {
+        let Some((owner, repo)) = self.parse_repo() else {
+            return false;
+        };
+        let data = NotesCache {
+            notes: self.file_notes.clone(),
+        };
+        if let Err(e) =
+            cache::write_pr_json(owner, repo, self.pr_number, cache::NOTES_SUFFIX, &data)
+        {
+            self.status_message =
+                Some(StatusMessage::error(format!("✗ Failed to save notes: {e}")));
+            return false;
+        }
+        true
+    }
+}
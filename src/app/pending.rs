@@ -0,0 +1,233 @@
+//! 送信前の保留コメント一覧（コメント対象のコードと本文を並べて確認する）
+
+use super::*;
+
+/// 送信確認ダイアログに並べる保留コメントの上限（超えた分は件数だけ表示）
+pub(super) const PENDING_SUMMARY_MAX: usize = 6;
+
+impl App {
+    /// P: 保留コメントのプレビューを開く
+    pub(super) fn open_pending_comments(&mut self) {
+        if self.review.pending_comments.is_empty() {
+            self.status_message = Some(StatusMessage::error("No pending comments"));
+            return;
+        }
+        self.pending_comments_scroll = 0;
+        self.pending_cursor = self
+            .pending_cursor
+            .min(self.review.pending_comments.len() - 1);
+        self.pending_header_input = None;
+        self.mode = AppMode::PendingComments;
+    }
+
+    /// 保留コメントの対象ファイルのパッチ
+    fn pending_patch(&self, pending: &PendingComment) -> Option<&str> {
+        self.files_for(&pending.commit_sha)
+            .and_then(|files| files.iter().find(|f| f.filename == pending.file_path))
+            .and_then(|f| f.patch.as_deref())
+    }
+
+    /// 保留コメントの対象行（L12 / L12–L15、パッチに見つからなければ L?）
+    fn pending_line_range(&self, pending: &PendingComment) -> String {
+        let line_map = self
+            .pending_patch(pending)
+            .map(review::parse_patch_line_map)
+            .unwrap_or_default();
+        let file_line = |idx: usize| {
+            line_map
+                .get(idx)
+                .and_then(|info| info.as_ref())
+                .map(|info| info.file_line)
+        };
+        match (file_line(pending.start_line), file_line(pending.end_line)) {
+            (Some(start), Some(end)) if start != end => format!("L{start}–L{end}"),
+            (_, Some(end)) => format!("L{end}"),
+            _ => "L?".to_string(),
+        }
+    }
+
+    /// 送信確認用の保留コメントの要約（file:L12 本文の 1 行目）
+    pub(super) fn pending_comment_summaries(&self) -> Vec<String> {
+        self.review
+            .pending_comments
+            .iter()
+            .map(|pending| {
+                format!(
+                    "{}:{} {}",
+                    pending.file_path,
+                    self.pending_line_range(pending),
+                    pending.body.lines().next().unwrap_or("")
+                )
+            })
+            .collect()
+    }
+
+    /// 保留コメントごとに、見出し・対象ファイル・行範囲・コード・本文を行へ展開する（各コメントの先頭行の位置も返す）
+    pub(super) fn pending_comment_preview(&self) -> (Vec<Line<'static>>, Vec<usize>) {
+        let header_style = Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD);
+        let mut lines = Vec::new();
+        let mut starts = Vec::new();
+        for (i, pending) in self.review.pending_comments.iter().enumerate() {
+            starts.push(lines.len());
+            if let Some(title) = self.review.pending_headers.get(&i) {
+                lines.push(Line::styled(format!("▍ {title}"), header_style));
+            }
+            let code: Vec<&str> = self
+                .pending_patch(pending)
+                .map(|p| {
+                    p.lines()
+                        .skip(pending.start_line)
+                        .take(pending.end_line + 1 - pending.start_line)
+                        .collect()
+                })
+                .unwrap_or_default();
+            let range = self.pending_line_range(pending);
+
+            let number_style = if i == self.pending_cursor {
+                Style::default().fg(Color::Black).bg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("#{} ", i + 1), number_style),
+                Span::styled(
+                    pending.file_path.clone(),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(
+                    " {range} @ {}",
+                    crate::format::short_sha(&pending.commit_sha)
+                )),
+            ]));
+            if code.is_empty() {
+                lines.push(Line::styled(
+                    "  (commented lines not found in patch)",
+                    Style::default().fg(Color::Red),
+                ));
+            }
+            for line in code {
+                let style = if line.starts_with('+') {
+                    Style::default().fg(Color::Green)
+                } else if line.starts_with('-') {
+                    Style::default().fg(Color::Red)
+                } else {
+                    Style::default()
+                };
+                lines.push(Line::styled(format!("  │ {line}"), style));
+            }
+            for (j, body_line) in pending.body.lines().enumerate() {
+                let prefix = if j == 0 { "💬" } else { "  " };
+                lines.push(Line::styled(
+                    format!("  {prefix} {body_line}"),
+                    Style::default().fg(Color::Cyan),
+                ));
+            }
+            lines.push(Line::raw(""));
+        }
+        // 末尾の見出し（まだコメントのないグループ、次に追加したコメントが入る）
+        if let Some(title) = self
+            .review
+            .pending_headers
+            .get(&self.review.pending_comments.len())
+        {
+            lines.push(Line::styled(format!("▍ {title}"), header_style));
+        }
+        (lines, starts)
+    }
+
+    /// j / k: 選択する保留コメントを移す
+    pub(super) fn move_pending_cursor(&mut self, forward: bool) {
+        let last = self.review.pending_comments.len().saturating_sub(1);
+        self.pending_cursor = if forward {
+            (self.pending_cursor + 1).min(last)
+        } else {
+            self.pending_cursor.saturating_sub(1)
+        };
+    }
+
+    /// J / K: 選択中のコメントを 1 つ後ろ / 前へ動かす（見出しの境界では隣のグループに移るだけ）
+    pub(super) fn move_pending_comment(&mut self, forward: bool) {
+        let i = self.pending_cursor;
+        let len = self.review.pending_comments.len();
+        let headers = &mut self.review.pending_headers;
+        if forward {
+            // 次のグループの見出しを自分の前に移して、そのグループの先頭に入る
+            if !headers.contains_key(&i)
+                && let Some(title) = headers.remove(&(i + 1))
+            {
+                headers.insert(i, title);
+            } else if i + 1 < len {
+                self.review.pending_comments.swap(i, i + 1);
+                self.pending_cursor = i + 1;
+            }
+        } else if headers.contains_key(&i) && !headers.contains_key(&(i + 1)) {
+            // グループの先頭なら見出しを自分の後ろに移して、前のグループの末尾に入る
+            if let Some(title) = headers.remove(&i) {
+                headers.insert(i + 1, title);
+            }
+        } else if i > 0 {
+            self.review.pending_comments.swap(i - 1, i);
+            self.pending_cursor = i - 1;
+        }
+    }
+
+    /// h: 選択中のコメントの前に置く見出しの入力を始める（既存の見出しは編集）
+    pub(super) fn start_pending_header_input(&mut self) {
+        let current = self
+            .review
+            .pending_headers
+            .get(&self.pending_cursor)
+            .cloned()
+            .unwrap_or_default();
+        self.pending_header_input = Some(current);
+    }
+
+    /// 見出しを確定する（空なら見出しを外す）
+    pub(super) fn commit_pending_header(&mut self) {
+        let Some(title) = self.pending_header_input.take() else {
+            return;
+        };
+        let title = title.trim();
+        if title.is_empty() {
+            self.review.pending_headers.remove(&self.pending_cursor);
+        } else {
+            self.review
+                .pending_headers
+                .insert(self.pending_cursor, title.to_string());
+        }
+    }
+
+    /// 見出しごとの保留コメントの目次（レビュー本文に添える。見出しがなければ空）
+    pub(super) fn pending_outline_markdown(&self) -> String {
+        if self.review.pending_headers.is_empty() {
+            return String::new();
+        }
+        let mut out = String::new();
+        for (i, pending) in self.review.pending_comments.iter().enumerate() {
+            if let Some(title) = self.review.pending_headers.get(&i) {
+                if !out.is_empty() {
+                    out.push('\n');
+                }
+                out.push_str(&format!("**{title}**\n"));
+            }
+            out.push_str(&format!(
+                "- `{}` {}: {}\n",
+                pending.file_path,
+                self.pending_line_range(pending),
+                pending.body.lines().next().unwrap_or("")
+            ));
+        }
+        out.trim_end().to_string()
+    }
+
+    /// 保留コメントを全て捨てる（見出しと選択位置も戻す）
+    pub(super) fn clear_pending_comments(&mut self) {
+        self.review.pending_comments.clear();
+        self.review.pending_headers.clear();
+        self.pending_cursor = 0;
+    }
+}
@@ -0,0 +1,118 @@
+//! 描画性能のデバッグ表示（F12 で切替、ヘルプには載せない隠し機能）
+
+use super::*;
+use std::time::{Duration, Instant};
+
+/// フレーム描画時間と入力から描画までの遅延の計測値
+#[derive(Debug, Default)]
+pub struct PerfStats {
+    frames: u64,
+    last_frame: Duration,
+    /// 指数移動平均（直近のフレームほど重い）
+    avg_frame: Duration,
+    max_frame: Duration,
+    /// 入力を受け取ってから次のフレームを描き終えるまで
+    last_latency: Option<Duration>,
+    max_latency: Duration,
+    /// まだ描画に反映されていない最初の入力の時刻
+    input_at: Option<Instant>,
+}
+
+impl PerfStats {
+    /// 描画 1 回分の時間を記録する（入力待ちがあれば遅延も確定する）
+    pub fn record_frame(&mut self, elapsed: Duration) {
+        self.avg_frame = if self.frames == 0 {
+            elapsed
+        } else {
+            (self.avg_frame * 7 + elapsed) / 8
+        };
+        self.frames += 1;
+        self.last_frame = elapsed;
+        self.max_frame = self.max_frame.max(elapsed);
+        if let Some(at) = self.input_at.take() {
+            let latency = at.elapsed();
+            self.last_latency = Some(latency);
+            self.max_latency = self.max_latency.max(latency);
+        }
+    }
+
+    /// 入力を受け取った時刻を記録する
+    pub fn record_input(&mut self) {
+        self.input_at.get_or_insert_with(Instant::now);
+    }
+}
+
+/// 時間をミリ秒（小数 1 桁）で表示する
+fn ms(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// バイト数を MB（小数 1 桁）で表示する
+fn mb(bytes: usize) -> String {
+    format!("{:.1}MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+impl App {
+    /// F12: 性能オーバーレイの表示を切り替える（非表示にすると計測値も捨てる）
+    pub(super) fn toggle_perf_overlay(&mut self) {
+        self.perf = match self.perf {
+            Some(_) => None,
+            None => Some(PerfStats::default()),
+        };
+    }
+
+    /// オーバーレイに表示する行（計測値と主なキャッシュのおおよそのサイズ）
+    pub(super) fn perf_lines(&self) -> Vec<String> {
+        let Some(perf) = &self.perf else {
+            return Vec::new();
+        };
+        let latency = perf.last_latency.map_or_else(|| "-".to_string(), ms);
+        vec![
+            format!(
+                "frame   {} avg {} max {} (#{})",
+                ms(perf.last_frame),
+                ms(perf.avg_frame),
+                ms(perf.max_frame),
+                perf.frames
+            ),
+            format!("input   {latency} max {}", ms(perf.max_latency)),
+            format!(
+                "diff    {} highlighted, {}",
+                self.diff.highlight_cache.len(),
+                mb(self.diff.highlight_cache.total())
+            ),
+            format!(
+                "files   {} commits, {}",
+                self.files_map.len(),
+                mb(self.files_usage.total())
+            ),
+            format!(
+                "media   {} images {}, {} protocols {}",
+                self.media_cache.len(),
+                mb(self.media_cache.total()),
+                self.media_protocol_cache.len(),
+                mb(self.media_protocol_cache.total())
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_frame_tracks_average_and_latency() {
+        let mut perf = PerfStats::default();
+        perf.record_frame(Duration::from_millis(8));
+        assert_eq!(perf.avg_frame, Duration::from_millis(8));
+        assert!(perf.last_latency.is_none());
+
+        perf.record_input();
+        perf.record_frame(Duration::from_millis(16));
+        assert_eq!(perf.avg_frame, Duration::from_millis(9));
+        assert_eq!(perf.max_frame, Duration::from_millis(16));
+        assert!(perf.last_latency.is_some());
+        assert!(perf.input_at.is_none());
+    }
+}
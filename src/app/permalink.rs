@@ -0,0 +1,123 @@
+//! 行範囲付きの GitHub パーマリンク（blob/<sha>/path#L10-L20）
+
+use super::*;
+use crate::github::review::Side;
+
+/// blob パーマリンクを組み立てる（単一行なら #L10、範囲なら #L10-L20）
+fn permalink_url(repo: &str, sha: &str, path: &str, start: usize, end: usize) -> String {
+    let anchor = if start == end {
+        format!("L{start}")
+    } else {
+        format!("L{start}-L{end}")
+    };
+    format!("https://github.com/{repo}/blob/{sha}/{path}#{anchor}")
+}
+
+impl App {
+    /// 親コミットの SHA（PR 内の直前コミット、先頭コミットなら API で取得）
+    fn parent_sha(&self, sha: &str) -> Option<String> {
+        if let Some(idx) = self.commits.iter().position(|c| c.sha == sha)
+            && idx > 0
+        {
+            return Some(self.commits[idx - 1].sha.clone());
+        }
+        let (owner, repo) = self.parse_repo()?;
+        crate::github::commits::fetch_parent_sha(owner, repo, sha).ok()
+    }
+
+    /// 指定側の行範囲のパーマリンク（LEFT は変更前なので親コミットの blob を指す）
+    fn side_permalink(
+        &self,
+        sha: &str,
+        path: &str,
+        side: Side,
+        start: usize,
+        end: usize,
+    ) -> Option<String> {
+        let sha = match side {
+            Side::Right => sha.to_string(),
+            Side::Left => self.parent_sha(sha)?,
+        };
+        Some(permalink_url(&self.repo, &sha, path, start, end))
+    }
+
+    /// 行選択範囲のパーマリンク（追加・変更後の行を優先し、削除行のみなら変更前を指す）
+    pub(super) fn selection_permalink(&self) -> Option<String> {
+        let (side, first, last) = self.selection_file_lines()?;
+        let sha = self.current_revision()?;
+        let file = self.current_file()?;
+        self.side_permalink(&sha, &file.filename, side, first, last)
+    }
+
+    /// 行選択範囲の実ファイル行番号（変更後を優先し、削除行のみなら変更前）
+    pub(super) fn selection_file_lines(&self) -> Option<(Side, usize, usize)> {
+        let (start, end) = self.line_selection?.range(self.diff.cursor_line);
+        let file = self.current_file()?;
+        let line_map = review::parse_patch_line_map(file.patch.as_deref()?);
+        let infos: Vec<_> = line_map
+            .get(start..=end.min(line_map.len().saturating_sub(1)))?
+            .iter()
+            .flatten()
+            .collect();
+        let side = if infos.iter().any(|info| info.side == Side::Right) {
+            Side::Right
+        } else {
+            Side::Left
+        };
+        let lines: Vec<usize> = infos
+            .iter()
+            .filter(|info| info.side == side)
+            .map(|info| info.file_line)
+            .collect();
+        Some((side, *lines.iter().min()?, *lines.iter().max()?))
+    }
+
+    /// 表示中のスレッドが指す行範囲のパーマリンク（コメント時点のコミット）
+    pub(super) fn thread_permalink(&self) -> Option<String> {
+        let root = self
+            .review
+            .viewing_comments
+            .iter()
+            .find(|c| c.in_reply_to_id.is_none())
+            .or_else(|| self.review.viewing_comments.first())?;
+        let end = root.line?;
+        let start = root.start_line.unwrap_or(end);
+        let side = match root.side.as_deref() {
+            Some("LEFT") => Side::Left,
+            _ => Side::Right,
+        };
+        self.side_permalink(&root.commit_id, &root.path, side, start, end)
+    }
+
+    /// y: パーマリンクをクリップボードにコピーする（行選択・コメント表示中）
+    pub(super) fn copy_permalink(&mut self) {
+        let url = if self.mode == AppMode::CommentView {
+            self.thread_permalink()
+        } else {
+            self.selection_permalink()
+        };
+        match url {
+            Some(url) => self.copy_to_clipboard(&url, "permalink"),
+            None => {
+                self.status_message = Some(StatusMessage::error("✗ No permalink for this line"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permalink_url() {
+        assert_eq!(
+            permalink_url("owner/repo", "abc123", "src/main.rs", 10, 20),
+            "https://github.com/owner/repo/blob/abc123/src/main.rs#L10-L20"
+        );
+        assert_eq!(
+            permalink_url("owner/repo", "abc123", "src/main.rs", 7, 7),
+            "https://github.com/owner/repo/blob/abc123/src/main.rs#L7"
+        );
+    }
+}
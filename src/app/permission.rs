@@ -0,0 +1,93 @@
+//! 外部プログラムの実行確認（未許可のプログラムを初めて使うときにダイアログで聞く）
+
+use super::*;
+use crate::exec::{self, Answer};
+use std::path::PathBuf;
+
+/// 実行の確認を待って止まった操作（許可されたらやり直す）
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum PermissionRetry {
+    /// `!` のコマンド
+    RunCommand,
+    /// 外部エディタでの編集
+    ExternalEditor,
+    /// クリップボードの画像の保存（添付ダイアログの確定）
+    ClipboardImage,
+    /// 画像のアップロード
+    AttachUpload(PathBuf),
+    /// クリップボードへのコピー
+    Copy { text: String, label: String },
+    /// ブラウザで開く
+    OpenUrl { url: String, label: String },
+}
+
+impl App {
+    /// 操作の間に実行の確認が増えていれば、許可されたらやり直すよう覚えておく（true なら確認待ち）
+    pub(super) fn retry_after_permission(
+        &mut self,
+        pending_before: usize,
+        retry: PermissionRetry,
+    ) -> bool {
+        let asked = exec::pending_count() > pending_before;
+        if asked {
+            self.permission_retry = Some(retry);
+        }
+        asked
+    }
+
+    /// 確認待ちのプログラムがあればダイアログを開く（ループの先頭で呼ぶ。確認中なら次の回答を待つ）
+    pub(super) fn poll_command_permission(&mut self) {
+        if self.mode == AppMode::AllowCommand {
+            return;
+        }
+        let Some(program) = exec::take_pending() else {
+            return;
+        };
+        self.allow_command = program;
+        self.allow_return_mode = self.mode;
+        self.mode = AppMode::AllowCommand;
+    }
+
+    /// 回答を反映して元のモードに戻り、止まっていた操作をやり直す（描画で使う delta / bat は描画し直す）
+    pub(super) fn answer_command_permission(&mut self, answer: Answer) {
+        let program = std::mem::take(&mut self.allow_command);
+        self.mode = self.allow_return_mode;
+        if let Err(e) = exec::answer(&program, answer) {
+            self.status_message = Some(StatusMessage::error(format!(
+                "✗ Failed to save allowed commands: {e}"
+            )));
+        } else if answer == Answer::Deny {
+            self.status_message = Some(StatusMessage::info(format!(
+                "`{program}` is not allowed in this session"
+            )));
+        } else {
+            self.diff.highlight_cache.clear();
+            self.pr_desc_rendered = None;
+            self.conversation_rendered = None;
+            self.status_message = Some(StatusMessage::info(format!("✓ Allowed `{program}`")));
+        }
+
+        if answer == Answer::Deny {
+            // 代わりのプログラムの確認が続くなら、その回答までやり直しを待つ
+            if exec::pending_count() == 0
+                && let Some(PermissionRetry::AttachUpload(path)) = self.permission_retry.take()
+            {
+                self.insert_attach_placeholder(&path);
+            }
+        } else if let Some(retry) = self.permission_retry.take() {
+            self.run_permission_retry(retry);
+        }
+    }
+
+    /// 止まっていた操作をやり直す（draw 後に実行するものはフラグを立てる）
+    fn run_permission_retry(&mut self, retry: PermissionRetry) {
+        match retry {
+            PermissionRetry::RunCommand => self.needs_run_command = true,
+            PermissionRetry::ExternalEditor => self.needs_external_editor = true,
+            PermissionRetry::ClipboardImage => self.confirm_attach(),
+            PermissionRetry::AttachUpload(path) => self.needs_attach_upload = Some(path),
+            PermissionRetry::Copy { text, label } => self.copy_to_clipboard(&text, &label),
+            PermissionRetry::OpenUrl { url, label } => self.open_in_browser(&url, &label),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! スレッド返信の補助（定型文・対応コミットのリンク）
+
+use super::*;
+
+/// 定型返信（`{sha}`: 最新コミットの短縮 SHA, `{author}`: スレッド開始者）
+pub const QUICK_REPLIES: [&str; 4] = [
+    "Done",
+    "Fixed in {sha}",
+    "Good catch, will address in a follow-up",
+    "Thanks @{author}!",
+];
+
+/// 定型返信のプレースホルダーを展開する
+pub fn expand_quick_reply(template: &str, sha: &str, author: &str) -> String {
+    template.replace("{sha}", sha).replace("{author}", author)
+}
+
+impl App {
+    /// 返信先スレッドの開始者を返す
+    fn reply_thread_author(&self) -> String {
+        let Some(root_id) = self.review.reply_to_comment_id else {
+            return String::new();
+        };
+        self.review
+            .review_comments
+            .iter()
+            .find(|c| c.id == root_id)
+            .map(|c| c.user.login.clone())
+            .unwrap_or_default()
+    }
+
+    /// 次の定型返信を挿入する。直前に挿入した定型文が未編集のまま残っていれば置き換える
+    pub(super) fn cycle_quick_reply(&mut self) {
+        let sha = self
+            .commits
+            .last()
+            .map(|c| c.short_sha().to_string())
+            .unwrap_or_default();
+        let author = self.reply_thread_author();
+        let expand = |idx: usize| expand_quick_reply(QUICK_REPLIES[idx], &sha, &author);
+
+        let editor_text = self.review.comment_editor.text();
+        let next = match self.quick_reply_index {
+            Some(idx) if editor_text == expand(idx) => {
+                self.review.comment_editor.clear();
+                (idx + 1) % QUICK_REPLIES.len()
+            }
+            _ => 0,
+        };
+        self.review.comment_editor.insert_text(&expand(next));
+        self.quick_reply_index = Some(next);
+    }
+
+    /// Commits ペインで選択中のコミットを「対応コミット」として返信に挿入する
+    pub(super) fn insert_commit_link(&mut self) {
+        let Some(commit) = self
+            .commit_list_state
+            .selected()
+            .and_then(|idx| self.commits.get(idx))
+        else {
+            self.status_message = Some(StatusMessage::error("✗ No commit selected"));
+            return;
+        };
+        // GitHub は本文中の SHA をコミットへのリンクに自動変換する
+        let text = format!("Addressed in {} ({})", commit.sha, commit.message_summary());
+        self.review.comment_editor.insert_text(&text);
+    }
+
+    /// 返信送信後にスレッドを resolve するかを切り替える
+    pub(super) fn toggle_resolve_after_reply(&mut self) {
+        self.review.resolve_after_reply = !self.review.resolve_after_reply;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_quick_reply_placeholders() {
+        assert_eq!(
+            expand_quick_reply("Fixed in {sha}", "abc1234", "alice"),
+            "Fixed in abc1234"
+        );
+        assert_eq!(
+            expand_quick_reply("Thanks @{author}!", "abc1234", "alice"),
+            "Thanks @alice!"
+        );
+        assert_eq!(expand_quick_reply("Done", "abc1234", "alice"), "Done");
+    }
+}
@@ -0,0 +1,42 @@
+//! 終了時の確認（バックグラウンドの読み込み・同期が残っていれば一覧を出し、待つ・やめる・そのまま終了するを選ぶ）
+
+use super::*;
+
+impl App {
+    /// 実行中のバックグラウンド処理（終了すると失われるもの）
+    pub(super) fn background_tasks(&self) -> Vec<&'static str> {
+        let mut tasks = Vec::new();
+        if self.loading.files == LoadPhase::Loading {
+            // 読み込み完了時に PR キャッシュを書くため、途中で終えると次回も取り直しになる
+            tasks.push("Loading changed files");
+        }
+        if self.loading.conversation == LoadPhase::Loading {
+            tasks.push("Loading conversation");
+        }
+        if self.loading.media == LoadPhase::Loading {
+            tasks.push("Loading images");
+        }
+        // 送れていない変更は保存されて次回送り直すので、待つのは送信中のものだけ
+        if self.viewed_sync_running {
+            tasks.push("Syncing viewed files to GitHub");
+        }
+        tasks
+    }
+
+    /// 終了する（バックグラウンド処理が残っていれば確認ダイアログを出す）
+    pub(super) fn request_quit(&mut self) {
+        if self.background_tasks().is_empty() {
+            self.should_quit = true;
+        } else {
+            self.quit_when_idle = false;
+            self.mode = AppMode::QuitWait;
+        }
+    }
+
+    /// 待つを選んだ後、残りの処理が終わったら終了する（ループの先頭で呼ぶ）
+    pub(super) fn quit_if_idle(&mut self) {
+        if self.quit_when_idle && self.background_tasks().is_empty() {
+            self.should_quit = true;
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! スレッドへの引用返信（コメントの一部を `> ` 付きで返信に入れる）
+
+use super::*;
+
+/// 行を GitHub の引用形式にする（末尾に本文用の空行を残す）
+pub fn quote_lines(lines: &[&str]) -> String {
+    let quoted: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            if line.is_empty() {
+                ">".to_string()
+            } else {
+                format!("> {line}")
+            }
+        })
+        .collect();
+    format!("{}\n\n", quoted.join("\n"))
+}
+
+impl App {
+    /// 表示中のコメント本文の行（コメント順に通しで並べる）
+    pub(super) fn quote_source_lines(&self) -> Vec<&str> {
+        self.review
+            .viewing_comments
+            .iter()
+            .flat_map(|c| c.body.lines())
+            .collect()
+    }
+
+    /// `>`: 引用する行の選択を始める（返信先になる最新コメントの先頭行から）
+    pub(super) fn start_quote_select(&mut self) {
+        let total = self.quote_source_lines().len();
+        if total == 0 {
+            return;
+        }
+        let last_len = self
+            .review
+            .viewing_comments
+            .last()
+            .map_or(0, |c| c.body.lines().count());
+        let start = total - last_len.max(1);
+        self.review.quote_select = Some(QuoteSelection {
+            anchor: start,
+            cursor: start,
+        });
+    }
+
+    /// 選択中の行を動かす（`extend` が true なら範囲を広げる）
+    pub(super) fn move_quote_cursor(&mut self, delta: isize, extend: bool) {
+        let total = self.quote_source_lines().len();
+        let Some(sel) = self.review.quote_select.as_mut() else {
+            return;
+        };
+        sel.cursor = sel
+            .cursor
+            .saturating_add_signed(delta)
+            .min(total.saturating_sub(1));
+        if !extend {
+            sel.anchor = sel.cursor;
+        }
+    }
+
+    /// 選択した行を引用して返信を始める
+    pub(super) fn reply_with_quote(&mut self) {
+        let Some(sel) = self.review.quote_select.take() else {
+            return;
+        };
+        let Some(root_id) = crate::github::comments::root_comment_id(&self.review.viewing_comments)
+        else {
+            return;
+        };
+        let (start, end) = sel.range();
+        let quote = {
+            let lines = self.quote_source_lines();
+            let end = end.min(lines.len().saturating_sub(1));
+            quote_lines(lines.get(start..=end).unwrap_or_default())
+        };
+        self.review.reply_to_comment_id = Some(root_id);
+        self.review.comment_editor.clear();
+        self.review.comment_editor.insert_text(&quote);
+        self.mode = AppMode::ReplyInput;
+        self.restore_draft();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_lines() {
+        assert_eq!(quote_lines(&["a", "", "b"]), "> a\n>\n> b\n\n");
+    }
+}
@@ -0,0 +1,85 @@
+//! 自分と PR の関係（作成者・担当者・レビュー依頼・自分のレビュー）をヘッダーに表示する
+
+use super::*;
+use crate::i18n::tr;
+
+/// 自分と PR の関係
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Relationship {
+    pub author: bool,
+    pub assignee: bool,
+    /// 自分か所属チームにレビュー依頼が来ている
+    pub review_requested: bool,
+    /// 自分の最新レビューの状態（APPROVED / CHANGES_REQUESTED / COMMENTED / DISMISSED）
+    pub reviewed: Option<String>,
+}
+
+impl Relationship {
+    /// 自分に対応が求められているか（レビュー依頼が来ている、または担当なのに未レビュー）
+    pub fn action_expected(&self) -> bool {
+        self.review_requested || (self.assignee && !self.author && self.reviewed.is_none())
+    }
+}
+
+/// 自分のレビュー状態の表示
+fn reviewed_label(state: &str) -> &'static str {
+    match state {
+        "APPROVED" => "you approved",
+        "CHANGES_REQUESTED" => "you requested changes",
+        "DISMISSED" => "your review was dismissed",
+        _ => "you commented",
+    }
+}
+
+impl App {
+    /// PR の担当者をセットする
+    pub fn set_assignees(&mut self, assignees: Vec<String>) {
+        self.assignees = assignees;
+    }
+
+    /// 自分と PR の関係を集計する（ログインユーザーが不明なら None）
+    pub(super) fn my_relationship(&self) -> Option<Relationship> {
+        if self.current_user.is_empty() {
+            return None;
+        }
+        let me = &self.current_user;
+        let reviewed = self
+            .conversation
+            .iter()
+            .rev()
+            .find_map(|entry| match &entry.kind {
+                ConversationKind::Review { state } if &entry.author == me => Some(state.clone()),
+                _ => None,
+            });
+        Some(Relationship {
+            author: self.is_own_pr,
+            assignee: self.assignees.contains(me),
+            review_requested: self.requested_reviewers.contains(me) || self.team_review_requested(),
+            reviewed,
+        })
+    }
+
+    /// ヘッダーのバッジ（" 👤 assignee · review requested · you approved "）。関係がなければ空
+    pub(super) fn relationship_badge(&self) -> String {
+        let Some(rel) = self.my_relationship() else {
+            return String::new();
+        };
+        let mut parts = Vec::new();
+        if rel.author {
+            parts.push(tr("author"));
+        }
+        if rel.assignee {
+            parts.push(tr("assignee"));
+        }
+        if rel.review_requested {
+            parts.push(tr("review requested"));
+        }
+        if let Some(state) = &rel.reviewed {
+            parts.push(tr(reviewed_label(state)));
+        }
+        if parts.is_empty() {
+            return String::new();
+        }
+        format!(" 👤 {} ", parts.join(" · "))
+    }
+}
@@ -1,17 +1,31 @@
 use super::*;
 
-use crate::git::diff::highlight_diff;
+use super::heatmap;
+use super::help;
+use super::helpers::{diff_hunk_preview, format_bytes, format_duration};
+use super::hunks::label_hunk_header;
+use super::hyperlinks;
+use super::large_pr::SummaryRow;
+use super::latency;
+use super::memory::text_size;
+use super::pending::PENDING_SUMMARY_MAX;
+use super::search;
+use crate::git::diff::{expand_tabs, highlight_diff};
+use crate::git::workflow;
+use crate::github::checks::CheckState;
+use crate::i18n::tr;
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, HorizontalAlignment, Layout, Position, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
     widgets::{
-        Block, Borders, Clear, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation,
-        ScrollbarState, Wrap,
+        Block, Borders, Clear, List, ListItem, ListState, Paragraph, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Wrap,
     },
 };
 use ratatui_image::StatefulImage;
+use std::ops::Range;
 use unicode_width::UnicodeWidthStr;
 
 /// コミットメッセージペインの高さ（ボーダー上下 2 + 内容 4 行）
@@ -31,15 +45,41 @@ const HINT_MEDIA: &str = " o: media ";
 const HINT_VIEWED: &str = " x: viewed ";
 const HINT_COMMENT: &str = " c: comment ";
 const HINT_SELECT_COMMENT: &str = " v: select | c: comment ";
+const HINT_SUGGEST: &str = " c: comment | s: suggest ";
 
 // --- ダイアログサイズ ---
-const REVIEW_DIALOG_WIDTH: u16 = 36;
-const REVIEW_DIALOG_HEIGHT: u16 = 7;
+const REVIEW_DIALOG_WIDTH: u16 = 44;
+const REVIEW_DIALOG_HEIGHT: u16 = 8;
+/// 保留コメントを並べるときのレビュー送信ダイアログの幅
+const REVIEW_DIALOG_WIDE_WIDTH: u16 = 72;
+const CHECKLIST_DIALOG_MIN_WIDTH: u16 = 36;
+const STATS_DIALOG_WIDTH: u16 = 52;
 const QUIT_DIALOG_WIDTH: u16 = 38;
 const QUIT_DIALOG_HEIGHT: u16 = 9;
+const QUIT_WAIT_DIALOG_WIDTH: u16 = 44;
+const DISCARD_DIALOG_WIDTH: u16 = 38;
+const DISCARD_DIALOG_HEIGHT: u16 = 7;
+const ATTACH_DIALOG_WIDTH: u16 = 60;
+const ATTACH_DIALOG_HEIGHT: u16 = 5;
+const UPDATE_BRANCH_DIALOG_WIDTH: u16 = 44;
+const UPDATE_BRANCH_DIALOG_HEIGHT: u16 = 9;
+/// コメント削除の確認ダイアログのサイズ
+const COMMENT_DELETE_DIALOG_WIDTH: u16 = 50;
+const COMMENT_DELETE_DIALOG_HEIGHT: u16 = 7;
+/// PR を閉じる・開き直す確認ダイアログのサイズ
+const PR_STATE_DIALOG_WIDTH: u16 = 50;
+const PR_STATE_DIALOG_HEIGHT: u16 = 8;
+const CHECKOUT_DIALOG_WIDTH: u16 = 50;
+const CHECKOUT_DIALOG_HEIGHT: u16 = 8;
+/// 外部プログラムの実行確認ダイアログのサイズ
+const ALLOW_COMMAND_DIALOG_WIDTH: u16 = 50;
+const ALLOW_COMMAND_DIALOG_HEIGHT: u16 = 9;
+const AUTO_MERGE_DIALOG_WIDTH: u16 = 44;
+const AUTO_MERGE_DIALOG_HEIGHT: u16 = 10;
 const HELP_DIALOG_WIDTH: u16 = 60;
 const HELP_DIALOG_MIN_HEIGHT: u16 = 20;
 const HELP_KEY_COLUMN_WIDTH: usize = 20;
+const HELP_MAX_COLUMNS: u16 = 3;
 
 // --- 行番号フォーマット ---
 const LINE_NUM_WIDTH: usize = 4;
@@ -51,9 +91,15 @@ const CURSOR_BG_DARK: Color = Color::DarkGray;
 const CURSOR_BG_LIGHT: Color = Color::Indexed(254);
 const PENDING_BG_DARK: Color = Color::Indexed(22);
 const PENDING_BG_LIGHT: Color = Color::Indexed(151);
+const SEARCH_BG_DARK: Color = Color::Indexed(58);
+const SEARCH_BG_LIGHT: Color = Color::Indexed(229);
+const COVERED_BG_DARK: Color = Color::Indexed(23);
+const COVERED_BG_LIGHT: Color = Color::Indexed(195);
+const UNCOVERED_BG_DARK: Color = Color::Indexed(52);
+const UNCOVERED_BG_LIGHT: Color = Color::Indexed(224);
 
 /// ローディング中 / エラー時のプレースホルダー描画
-/// `LoadPhase::Loading` なら "Loading..." 表示、`Error` なら "Failed to load" 表示
+/// `LoadPhase::Loading` なら "Loading..." 表示、`Error` ならエラーの種類と対処のヒントを表示
 /// 描画した場合は `true` を返す（呼び出し元は early return に使用）
 fn render_load_phase(
     frame: &mut Frame,
@@ -77,15 +123,26 @@ fn render_load_phase(
             frame.render_widget(text, area);
             true
         }
-        LoadPhase::Error => {
+        LoadPhase::Error(kind) => {
             let block = Block::default()
                 .borders(Borders::ALL)
                 .title(format!(" {title} "))
                 .border_style(border_style);
-            let text = Paragraph::new(Line::styled(
-                " Failed to load — press R to retry",
-                Style::default().fg(Color::Red),
-            ))
+            let text = Paragraph::new(vec![
+                Line::styled(
+                    format!(" ✗ {}", tr(kind.label())),
+                    Style::default().fg(Color::Red),
+                ),
+                Line::styled(
+                    format!(" {}", tr(kind.hint())),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Line::styled(
+                    format!(" {}", tr("R: retry")),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])
+            .wrap(Wrap { trim: false })
             .block(block);
             frame.render_widget(text, area);
             true
@@ -95,8 +152,39 @@ fn render_load_phase(
 }
 
 impl App {
+    /// ヘッダーのコメント集計（💬 コメント数 · 未解決スレッド数 · 保留コメント数、0 の項目は省く）
+    pub(super) fn comment_summary_badge(&self) -> String {
+        let total = self.review.review_comments.len();
+        let unresolved = self
+            .review
+            .thread_map
+            .values()
+            .filter(|t| !t.is_resolved)
+            .count();
+        let pending = self.review.pending_comments.len();
+        let mut parts = Vec::new();
+        if total > 0 {
+            parts.push(format!("💬 {total}"));
+        }
+        if unresolved > 0 {
+            parts.push(format!("{unresolved} {}", tr("unresolved")));
+        }
+        if pending > 0 {
+            parts.push(format!("{pending} {}", tr("pending")));
+        }
+        // 送信できず再送待ちの resolve / unresolve
+        if !self.resolve_queue.is_empty() {
+            parts.push(format!("⟳ {} {}", self.resolve_queue.len(), tr("queued")));
+        }
+        if parts.is_empty() {
+            return String::new();
+        }
+        format!(" {} ", parts.join(" · "))
+    }
+
     pub(super) fn render(&mut self, frame: &mut Frame) {
         let area = frame.area();
+        self.hyperlinks.clear();
 
         // ReviewBodyInput のみ全幅エディタパネルを下部に表示
         let main_layout = if self.mode == AppMode::ReviewBodyInput {
@@ -120,37 +208,85 @@ impl App {
             AppMode::LineSelect => " [LINE SELECT] ",
             AppMode::CommentInput | AppMode::IssueCommentInput => " [COMMENT] ",
             AppMode::ReplyInput => " [REPLY] ",
+            AppMode::CommentEditInput => " [EDIT] ",
+            AppMode::CommentDeleteConfirm => " [CONFIRM] ",
             AppMode::CommentView => " [VIEWING] ",
             AppMode::ReviewSubmit => " [REVIEW] ",
             AppMode::ReviewBodyInput => " [REVIEW] ",
             AppMode::QuitConfirm => " [CONFIRM] ",
+            AppMode::QuitWait => " [CONFIRM] ",
             AppMode::Help => " [HELP] ",
             AppMode::MediaViewer => " [MEDIA] ",
+            AppMode::CommandOutput => " [OUTPUT] ",
+            AppMode::Checklist => " [CHECKLIST] ",
+            AppMode::DiffSearch => " [SEARCH] ",
+            AppMode::Dashboard => " [DASHBOARD] ",
+            AppMode::FileCompare => " [COMPARE] ",
+            AppMode::FileHistory => " [HISTORY] ",
+            AppMode::LargePrSummary => " [SUMMARY] ",
+            AppMode::LinkList => " [LINKS] ",
+            AppMode::Stats => " [STATS] ",
+            AppMode::NoteInput => " [NOTE] ",
+            AppMode::UpdateBranch => " [CONFIRM] ",
+            AppMode::AutoMerge => " [AUTO-MERGE] ",
+            AppMode::DiscardConfirm => " [CONFIRM] ",
+            AppMode::PendingComments => " [PENDING] ",
+            AppMode::AttachImage => " [ATTACH] ",
+            AppMode::StatusHistory => " [MESSAGES] ",
+            AppMode::Checks => " [CHECKS] ",
+            AppMode::FileFinder => " [FIND FILE] ",
+            AppMode::PrStateChange => " [CONFIRM] ",
+            AppMode::CheckoutConfirm => " [CONFIRM] ",
+            AppMode::AllowCommand => " [CONFIRM] ",
         };
 
-        let comments_badge = if self.review.pending_comments.is_empty() {
-            String::new()
-        } else {
-            format!(" [{}💬]", self.review.pending_comments.len())
-        };
+        let comments_badge = self.comment_summary_badge();
 
         let header_bg = match self.mode {
             AppMode::Normal => Color::Blue,
             AppMode::LineSelect => Color::Magenta,
-            AppMode::CommentInput | AppMode::IssueCommentInput | AppMode::ReplyInput => {
-                Color::Green
-            }
+            AppMode::CommentInput
+            | AppMode::IssueCommentInput
+            | AppMode::ReplyInput
+            | AppMode::CommentEditInput => Color::Green,
+            AppMode::CommentDeleteConfirm => Color::Red,
             AppMode::CommentView => Color::Yellow,
             AppMode::ReviewSubmit => Color::Cyan,
             AppMode::ReviewBodyInput => Color::Green,
             AppMode::QuitConfirm => Color::Red,
+            AppMode::QuitWait => Color::Red,
             AppMode::Help => Color::DarkGray,
             AppMode::MediaViewer => Color::DarkGray,
+            AppMode::CommandOutput => Color::DarkGray,
+            AppMode::Checklist => Color::Cyan,
+            AppMode::DiffSearch => Color::Magenta,
+            AppMode::Dashboard => Color::DarkGray,
+            AppMode::FileCompare => Color::DarkGray,
+            AppMode::FileHistory => Color::DarkGray,
+            AppMode::LargePrSummary => Color::DarkGray,
+            AppMode::LinkList => Color::DarkGray,
+            AppMode::Stats => Color::DarkGray,
+            AppMode::NoteInput => Color::Green,
+            AppMode::UpdateBranch => Color::Red,
+            AppMode::AutoMerge => Color::Cyan,
+            AppMode::DiscardConfirm => Color::Red,
+            AppMode::PendingComments => Color::DarkGray,
+            AppMode::AttachImage => Color::Green,
+            AppMode::StatusHistory => Color::DarkGray,
+            AppMode::Checks => Color::DarkGray,
+            AppMode::FileFinder => Color::Magenta,
+            AppMode::PrStateChange => Color::Red,
+            AppMode::CheckoutConfirm => Color::Red,
+            AppMode::AllowCommand => Color::Yellow,
         };
         // CommentView / ReviewSubmit は明るい bg なので常に Black。
         // 他のモードはテーマに応じて White / Black を切り替え。
         let header_fg = match self.mode {
-            AppMode::CommentView | AppMode::ReviewSubmit | AppMode::ReviewBodyInput => Color::Black,
+            AppMode::CommentView
+            | AppMode::ReviewSubmit
+            | AppMode::ReviewBodyInput
+            | AppMode::Checklist
+            | AppMode::AutoMerge => Color::Black,
             _ => match self.theme {
                 ThemeMode::Dark => Color::White,
                 ThemeMode::Light => Color::Black,
@@ -171,9 +307,48 @@ impl App {
         if !zoom_indicator.is_empty() {
             right_spans.push(Span::styled(zoom_indicator, header_style));
         }
+        // 自分へのレビュー依頼が SLA を超えている
+        let now = chrono::Utc::now();
+        if self.review_overdue(now)
+            && let Some(since) = self.my_review_requested_at()
+        {
+            right_spans.push(Span::styled(
+                format!(" ⏰ waiting on you {} ", latency::elapsed_label(since, now)),
+                Style::default().bg(Color::Yellow).fg(Color::Black),
+            ));
+        }
+        let relationship_badge = self.relationship_badge();
+        if !relationship_badge.is_empty() {
+            // 自分の対応待ちなら目立たせる
+            let style = if self.my_relationship().is_some_and(|r| r.action_expected()) {
+                header_style.fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                header_style
+            };
+            right_spans.push(Span::styled(&relationship_badge, style));
+        }
         if !comments_badge.is_empty() {
             right_spans.push(Span::styled(&comments_badge, header_style));
         }
+        if let Some((badge, color)) = self.checks_badge() {
+            right_spans.push(Span::styled(badge, header_style.fg(color)));
+        }
+        if let Some(method) = self.auto_merge {
+            right_spans.push(Span::styled(
+                format!(" ⚡auto-merge: {} ", method.label()),
+                header_style.fg(Color::Green),
+            ));
+        }
+        if let Some(ref result) = self.command_result {
+            let badge_style = if result.running {
+                header_style.fg(Color::Yellow)
+            } else if result.success() {
+                header_style.fg(Color::Green)
+            } else {
+                header_style.fg(Color::Red)
+            };
+            right_spans.push(Span::styled(result.badge(), badge_style));
+        }
         if let Some(ref msg) = self.status_message {
             let status_style = match msg.level {
                 StatusLevel::Info => Style::default().bg(Color::Green).fg(Color::Black),
@@ -205,6 +380,19 @@ impl App {
             Paragraph::new(Line::from(spans)).style(header_style),
             main_layout[0],
         );
+        // "owner/repo#123" を PR のページへのリンクにする
+        let prefix_width = " prism - ".width() as u16;
+        let pr_label_width = format!("{}#{}", self.repo, self.pr_number).width() as u16;
+        let pr_label = Rect {
+            x: main_layout[0].x + prefix_width,
+            width: pr_label_width.min((left_used as u16).saturating_sub(prefix_width)),
+            ..main_layout[0]
+        };
+        self.link_area(
+            frame.buffer_mut(),
+            pr_label,
+            hyperlinks::pr_url(&self.repo, self.pr_number),
+        );
 
         if self.zoomed {
             // Zoom: フォーカスペインのみ全画面表示
@@ -303,12 +491,24 @@ impl App {
             let comment_area = right_layout[2];
 
             // マウスヒットテスト用に各ペインの Rect を記録
-            self.layout.pr_desc_rect = sidebar_layout[0];
+            // （入れ替え中はサイドバー上段が Conversation、PR Description は右カラムに出る）
+            let swapped = self.conversation_in_sidebar;
+            if swapped {
+                self.layout.pr_desc_rect = Rect::default();
+                self.layout.conversation_rect = sidebar_layout[0];
+            } else {
+                self.layout.pr_desc_rect = sidebar_layout[0];
+                self.layout.conversation_rect = Rect::default();
+            }
             self.layout.commit_list_rect = sidebar_layout[1];
             self.layout.file_tree_rect = sidebar_layout[2];
 
             // サイドバー3ペイン描画
-            self.render_pr_description(frame, sidebar_layout[0]);
+            if swapped {
+                self.render_conversation_pane(frame, sidebar_layout[0]);
+            } else {
+                self.render_pr_description(frame, sidebar_layout[0]);
+            }
             self.render_commit_list_stateful(frame, sidebar_layout[1]);
             self.render_file_tree(frame, sidebar_layout[2]);
 
@@ -319,14 +519,19 @@ impl App {
             ) || self.mode == AppMode::IssueCommentInput;
 
             if show_conversation {
-                // PrDescription / Conversation → Info + Conversation + Comment
+                // PrDescription / Conversation → Info + Conversation（入れ替え中は PR Description）+ Comment
                 self.layout.commit_msg_rect = Rect::default();
                 self.layout.diff_view_rect = Rect::default();
                 self.layout.commit_overview_rect = Rect::default();
-                self.layout.conversation_rect = diff_area;
 
                 self.render_info_pane(frame, commit_msg_area);
-                self.render_conversation_pane(frame, diff_area);
+                if swapped {
+                    self.layout.pr_desc_rect = diff_area;
+                    self.render_pr_description(frame, diff_area);
+                } else {
+                    self.layout.conversation_rect = diff_area;
+                    self.render_conversation_pane(frame, diff_area);
+                }
                 // コメントペイン
                 if self.mode != AppMode::ReviewBodyInput {
                     self.render_editor_panel(frame, comment_area);
@@ -338,7 +543,6 @@ impl App {
                 // CommitList → Commit Overview（右カラム全体）
                 self.layout.commit_msg_rect = Rect::default();
                 self.layout.diff_view_rect = Rect::default();
-                self.layout.conversation_rect = Rect::default();
                 self.layout.commit_overview_rect = body_layout[1];
 
                 self.render_commit_overview(frame, body_layout[1]);
@@ -346,7 +550,6 @@ impl App {
                 // FileTree / CommitMessage / DiffView → CommitMsg + Diff + Comment
                 self.layout.commit_msg_rect = commit_msg_area;
                 self.layout.diff_view_rect = diff_area;
-                self.layout.conversation_rect = Rect::default();
                 self.layout.commit_overview_rect = Rect::default();
 
                 self.render_commit_message(frame, commit_msg_area);
@@ -367,17 +570,71 @@ impl App {
         match self.mode {
             AppMode::ReviewSubmit => self.render_review_submit_dialog(frame, area),
             AppMode::QuitConfirm => self.render_quit_confirm_dialog(frame, area),
+            AppMode::QuitWait => self.render_quit_wait_dialog(frame, area),
+            AppMode::UpdateBranch => self.render_update_branch_dialog(frame, area),
+            AppMode::AutoMerge => self.render_auto_merge_dialog(frame, area),
+            AppMode::PrStateChange => self.render_pr_state_change_dialog(frame, area),
+            AppMode::CheckoutConfirm => self.render_checkout_dialog(frame, area),
+            AppMode::AllowCommand => self.render_allow_command_dialog(frame, area),
+            AppMode::CommentDeleteConfirm => self.render_comment_delete_dialog(frame, area),
+            AppMode::DiscardConfirm => self.render_discard_confirm_dialog(frame, area),
+            AppMode::PendingComments => self.render_pending_comments_overlay(frame, area),
             AppMode::Help => self.render_help_dialog(frame, area),
             AppMode::MediaViewer => self.render_media_viewer_overlay(frame, area),
+            AppMode::CommandOutput => self.render_command_output_overlay(frame, area),
+            AppMode::Checklist => self.render_checklist_dialog(frame, area),
+            AppMode::Dashboard => self.render_dashboard_overlay(frame, area),
+            AppMode::FileCompare => self.render_file_compare_overlay(frame, area),
+            AppMode::FileHistory => self.render_file_history_overlay(frame, area),
+            AppMode::LargePrSummary => self.render_large_pr_summary_overlay(frame, area),
+            AppMode::LinkList => self.render_link_list_dialog(frame, area),
+            AppMode::StatusHistory => self.render_status_history_dialog(frame, area),
+            AppMode::Checks => self.render_checks_dialog(frame, area),
+            AppMode::FileFinder => self.render_file_finder_dialog(frame, area),
+            AppMode::Stats => self.render_stats_dialog(frame, area),
+            AppMode::AttachImage => self.render_attach_prompt(frame, area),
             _ => {}
         }
 
         // ブロッキング操作中ダイアログ（最前面に描画）
         if let Some(msg) = self.blocking_operation_message() {
-            Self::render_blocking_dialog(frame, area, msg);
+            Self::render_blocking_dialog(frame, area, tr(msg));
+        }
+
+        if self.perf.is_some() {
+            self.render_perf_overlay(frame, area);
         }
     }
 
+    /// 性能オーバーレイを右下に描画する（F12）
+    fn render_perf_overlay(&self, frame: &mut Frame, area: Rect) {
+        let lines = self.perf_lines();
+        let width = lines
+            .iter()
+            .map(|l| UnicodeWidthStr::width(l.as_str()) as u16)
+            .max()
+            .unwrap_or(0)
+            .saturating_add(2)
+            .min(area.width);
+        let height = (lines.len() as u16).saturating_add(2).min(area.height);
+        let rect = Rect::new(
+            area.x + area.width - width,
+            area.y + area.height - height,
+            width,
+            height,
+        );
+        Self::clear_wide_safe(frame, rect, area);
+        let paragraph = Paragraph::new(lines.into_iter().map(Line::from).collect::<Vec<_>>())
+            .style(Style::default().fg(Color::DarkGray))
+            .block(
+                Block::default()
+                    .title(" perf (F12) ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            );
+        frame.render_widget(paragraph, rect);
+    }
+
     /// ブロッキング操作のメッセージを返す（フラグが立っていればダイアログ描画に使う）
     /// 評価順序は `run()` ループの実行順序と一致させている
     pub(super) fn blocking_operation_message(&self) -> Option<&'static str> {
@@ -390,19 +647,67 @@ impl App {
         if self.needs_reply_submit {
             return Some("Submitting reply...");
         }
+        if self.needs_comment_edit {
+            return Some("Updating comment...");
+        }
+        if self.needs_comment_delete {
+            return Some("Deleting comment...");
+        }
         if self.needs_reload {
             return Some("Reloading PR data...");
         }
         if self.review.needs_resolve_toggle.is_some() {
             return Some("Updating thread...");
         }
+        if self.needs_resolve_replay {
+            return Some("Syncing queued thread actions...");
+        }
+        if self.needs_rerequest_review {
+            return Some("Requesting review...");
+        }
+        if self.needs_mark_ready {
+            return Some("Marking ready for review...");
+        }
+        if self.needs_file_compare {
+            return Some("Comparing commits...");
+        }
+        if self.needs_full_content {
+            return Some("Loading full file...");
+        }
+        if self.needs_pr_mute {
+            return Some("Updating subscription...");
+        }
+        if self.needs_checkout {
+            return Some("Checking out...");
+        }
+        if self.needs_path_load.is_some()
+            || self.needs_commit_files()
+            || self.needs_all_changes_files()
+            || self.needs_interdiff_files()
+        {
+            return Some("Loading files...");
+        }
+        if self.needs_update_branch.is_some() {
+            return Some("Updating branch...");
+        }
+        if self.needs_auto_merge.is_some() {
+            return Some("Updating auto-merge...");
+        }
+        match self.needs_pr_state_change {
+            Some(PrStateChange::Close) => return Some("Closing PR..."),
+            Some(PrStateChange::Reopen) => return Some("Reopening PR..."),
+            None => {}
+        }
+        if self.needs_attach_upload.is_some() {
+            return Some("Uploading image...");
+        }
         None
     }
 
     /// ブロッキング操作中のダイアログを画面中央に描画する
     fn render_blocking_dialog(frame: &mut Frame, area: Rect, message: &str) {
         // ボーダー左右 (2) + 先頭スペース (1) + ⏳ 幅 (2) + スペース (1) = 6
-        let width = (message.len() as u16) + 6;
+        let width = (UnicodeWidthStr::width(message) as u16) + 6;
         let height = 3;
         let dialog = Self::centered_rect(width, height, area);
         Self::clear_wide_safe(frame, dialog, area);
@@ -432,24 +737,52 @@ impl App {
             Style::default()
         };
 
+        // テーブルは描画幅に合わせて整形するため、幅が変わったら再生成
+        if self.pr_desc_rendered_width != inner_width {
+            self.pr_desc_rendered_width = inner_width;
+            self.pr_desc_rendered = None;
+        }
         self.ensure_pr_desc_rendered();
 
         // Paragraph::new は Text をムーブするため clone が必要
         let text = self.pr_desc_rendered.as_ref().unwrap().clone();
 
+        // <details> 見出しの視覚行位置を計算（Enter での開閉対象の判定に使用）
+        self.pr_desc_fold_rows.clear();
+        let mut visual_row = 0;
+        let mut fold_iter = self.pr_desc_fold_lines.iter().peekable();
+        for (i, line) in text.lines.iter().enumerate() {
+            let Some(&&fold_line) = fold_iter.peek() else {
+                break;
+            };
+            if fold_line == i {
+                self.pr_desc_fold_rows.push(visual_row);
+                fold_iter.next();
+            }
+            visual_row += Paragraph::new(line.clone())
+                .wrap(Wrap { trim: false })
+                .line_count(inner_width)
+                .max(1);
+        }
+
         // block なしで line_count を計算（block 付きだとボーダー行が加算されてしまう）
         let paragraph = Paragraph::new(text).wrap(Wrap { trim: false });
         self.pr_desc_visual_total = paragraph.line_count(inner_width) as u16;
         // zoom 切替等で描画幅が変わった場合にスクロール位置をクランプ
         self.clamp_pr_desc_scroll();
 
+        // タスクリストがあれば進捗をタイトルに表示
+        let title = match markdown::task_progress(&self.pr_body) {
+            Some((done, total)) => format!(" PR Description [{}/{} ☑] ", done, total),
+            None => " PR Description ".to_string(),
+        };
         let mut block = Block::default()
-            .title(" PR Description ")
+            .title(title)
             .borders(Borders::ALL)
             .border_style(style);
         if self.focused_panel == Panel::PrDescription {
-            block =
-                block.title_bottom(Line::from(HINT_MEDIA).alignment(HorizontalAlignment::Right));
+            block = block
+                .title_bottom(Line::from(tr(HINT_MEDIA)).alignment(HorizontalAlignment::Right));
         }
         let paragraph = paragraph.block(block).scroll((self.pr_desc_scroll, 0));
 
@@ -471,63 +804,95 @@ impl App {
             Style::default()
         };
 
-        let items: Vec<ListItem> = self
+        let range = self
+            .commit_range()
+            .filter(|_| self.focused_panel == Panel::CommitList);
+        // ボーダー左右 (2) を除いた内部幅
+        let inner = area.width.saturating_sub(2) as usize;
+        let commit_item = |sha: &str, label: String, in_range: bool| {
+            let viewed = self.is_commit_viewed(sha);
+            let marker = if viewed { "✓ " } else { "  " };
+            let mut item_style = if viewed {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            // 範囲選択中のコミットは反転表示
+            if in_range {
+                item_style = item_style.add_modifier(Modifier::REVERSED);
+            }
+            // キャッシュから可視コメント数を取得 + pending を加算
+            let comment_count = self
+                .files_for(sha)
+                .map(|files| {
+                    let mut count = 0usize;
+                    for f in files {
+                        count += self.cached_visible_comment_count(sha, &f.filename);
+                        count += self
+                            .review
+                            .pending_comments
+                            .iter()
+                            .filter(|pc| pc.commit_sha == sha && pc.file_path == f.filename)
+                            .count();
+                    }
+                    count
+                })
+                .unwrap_or(0);
+            let left_part = format!("{}{}", marker, label);
+            if comment_count > 0 {
+                let badge = format!("💬 {} ", comment_count);
+                let badge_width = UnicodeWidthStr::width(badge.as_str());
+                let text_max = inner.saturating_sub(badge_width);
+                let left_text = truncate_str(&left_part, text_max);
+                let left_width = UnicodeWidthStr::width(left_text.as_str());
+                let pad = inner.saturating_sub(left_width + badge_width);
+                ListItem::new(Line::from(vec![
+                    Span::styled(left_text, item_style),
+                    Span::styled(" ".repeat(pad), item_style),
+                    Span::styled(badge, Style::default().fg(Color::Yellow)),
+                ]))
+            } else {
+                let left_text = truncate_str(&left_part, inner);
+                ListItem::new(Line::from(vec![Span::styled(left_text, item_style)]))
+            }
+        };
+        let mut items: Vec<ListItem> = self
             .commits
             .iter()
-            .map(|c| {
-                let viewed = self.is_commit_viewed(&c.sha);
-                let marker = if viewed { "✓ " } else { "  " };
-                let item_style = if viewed {
-                    Style::default().fg(Color::DarkGray)
+            .enumerate()
+            .map(|(i, c)| {
+                // interdiff の基準には印を付ける
+                let base = if self.interdiff_base == Some(i) {
+                    "[base] "
                 } else {
-                    Style::default()
+                    ""
                 };
-                // キャッシュから可視コメント数を取得 + pending を加算
-                let comment_count = self
-                    .files_map
-                    .get(&c.sha)
-                    .map(|files| {
-                        let mut count = 0usize;
-                        for f in files {
-                            count += self.cached_visible_comment_count(&c.sha, &f.filename);
-                            count += self
-                                .review
-                                .pending_comments
-                                .iter()
-                                .filter(|pc| pc.commit_sha == c.sha && pc.file_path == f.filename)
-                                .count();
-                        }
-                        count
-                    })
-                    .unwrap_or(0);
-                let left_part = format!("{}{} {}", marker, c.short_sha(), c.message_summary());
-                // ボーダー左右 (2) を除いた内部幅
-                let inner = area.width.saturating_sub(2) as usize;
-                if comment_count > 0 {
-                    let badge = format!("💬 {} ", comment_count);
-                    let badge_width = UnicodeWidthStr::width(badge.as_str());
-                    let text_max = inner.saturating_sub(badge_width);
-                    let left_text = truncate_str(&left_part, text_max);
-                    let left_width = UnicodeWidthStr::width(left_text.as_str());
-                    let pad = inner.saturating_sub(left_width + badge_width);
-                    ListItem::new(Line::from(vec![
-                        Span::styled(left_text, item_style),
-                        Span::styled(" ".repeat(pad), item_style),
-                        Span::styled(badge, Style::default().fg(Color::Yellow)),
-                    ]))
-                } else {
-                    let left_text = truncate_str(&left_part, inner);
-                    ListItem::new(Line::from(vec![Span::styled(left_text, item_style)]))
-                }
+                commit_item(
+                    &c.sha,
+                    format!("{} {base}{}", c.short_sha(), c.message_summary()),
+                    range.is_some_and(|(start, end)| (start..=end).contains(&i)),
+                )
             })
             .collect();
+        // 末尾に PR 全体の差分（All changes）
+        if self.has_all_changes_row() {
+            items.push(commit_item(
+                ALL_CHANGES_KEY,
+                self.all_changes_label(),
+                false,
+            ));
+        }
 
         let viewed_count = self.viewed_commit_count();
-        let selected = self
-            .commit_list_state
-            .selected()
-            .map(|i| i + 1)
-            .unwrap_or(0);
+        let selected = if self.is_all_changes_selected() {
+            tr("All").to_string()
+        } else {
+            self.commit_list_state
+                .selected()
+                .map(|i| i + 1)
+                .unwrap_or(0)
+                .to_string()
+        };
         let title = format!(
             " Commits {}/{} ✓{} ",
             selected,
@@ -538,15 +903,22 @@ impl App {
             .title(title)
             .borders(Borders::ALL)
             .border_style(style);
-        if self.focused_panel == Panel::CommitList {
-            block =
-                block.title_bottom(Line::from(HINT_VIEWED).alignment(HorizontalAlignment::Right));
+        if let Some((start, end)) = range {
+            let hint = format!(
+                " {} {} ",
+                end - start + 1,
+                tr("x: toggle viewed | Esc: cancel")
+            );
+            block = block.title_bottom(Line::from(hint).alignment(HorizontalAlignment::Right));
+        } else if self.focused_panel == Panel::CommitList {
+            block = block
+                .title_bottom(Line::from(tr(HINT_VIEWED)).alignment(HorizontalAlignment::Right));
         }
         let list = List::new(items)
             .block(block)
             .highlight_style(self.highlight_style());
 
-        let total = self.commits.len();
+        let total = self.commit_row_count();
         frame.render_stateful_widget(list, area, &mut self.commit_list_state);
 
         let offset = self.commit_list_state.offset();
@@ -574,101 +946,183 @@ impl App {
 
         let files = self.current_files();
         let current_sha = self.current_commit_sha();
-        let viewed_count = files
+        let is_viewed = |f: &DiffFile| {
+            current_sha
+                .as_ref()
+                .is_some_and(|sha| self.is_file_viewed(sha, &f.filename))
+        };
+        let counted: Vec<&DiffFile> = files
             .iter()
-            .filter(|f| {
-                current_sha
-                    .as_ref()
-                    .is_some_and(|sha| self.is_file_viewed(sha, &f.filename))
-            })
-            .count();
-        let items: Vec<ListItem> = files
+            .filter(|f| self.counts_for_progress(&f.filename))
+            .collect();
+        let viewed_count = counted.iter().filter(|&&f| is_viewed(f)).count();
+        // 一覧中で最も活発なファイルを基準にヒートマップの濃さを決める
+        let activity = self.file_activity();
+        let max_activity = files
             .iter()
-            .map(|f| {
-                let is_viewed = current_sha
-                    .as_ref()
-                    .is_some_and(|sha| self.is_file_viewed(sha, &f.filename));
-                let status = f.status_char();
-                let status_color = if is_viewed {
-                    Color::DarkGray
-                } else {
-                    match status {
-                        'A' => Color::Green,
-                        'M' => Color::Yellow,
-                        'D' => Color::Red,
-                        'R' => Color::Cyan,
-                        _ => Color::White,
-                    }
-                };
-                let text_style = if is_viewed {
-                    Style::default().fg(Color::DarkGray)
-                } else {
-                    Style::default()
-                };
-                let marker = if is_viewed { "✓ " } else { "  " };
-                // キャッシュから可視コメント数を取得 + 当該コミットの pending を加算
-                let visible_existing = current_sha
-                    .as_deref()
-                    .map(|sha| self.cached_visible_comment_count(sha, &f.filename))
-                    .unwrap_or(0);
-                let visible_pending = self
-                    .review
-                    .pending_comments
-                    .iter()
-                    .filter(|pc| {
-                        pc.file_path == f.filename
-                            && current_sha
-                                .as_deref()
-                                .is_some_and(|sha| sha == pc.commit_sha)
-                    })
-                    .count();
-                let comment_count = visible_existing + visible_pending;
-                // ボーダー左右 (2) を除いた内部幅
-                let inner = area.width.saturating_sub(2) as usize;
-                let status_str = String::from(status);
-                let prefix_width = UnicodeWidthStr::width(marker)
-                    + UnicodeWidthStr::width(status_str.as_str())
-                    + 1; // space before filename
-                let (badge, badge_width) = if comment_count > 0 {
-                    let b = format!("💬 {} ", comment_count);
-                    let w = UnicodeWidthStr::width(b.as_str());
-                    (Some(b), w)
-                } else {
-                    (None, 0)
-                };
-                let filename_max = inner.saturating_sub(prefix_width + badge_width);
-                let truncated = truncate_str(&f.filename, filename_max);
-                let mut spans = vec![
-                    Span::styled(marker, text_style),
-                    Span::styled(status_str, Style::default().fg(status_color)),
-                    Span::styled(format!(" {}", truncated), text_style),
-                ];
-                if let Some(badge) = badge {
-                    let left_width = prefix_width + UnicodeWidthStr::width(truncated.as_str());
-                    let pad = inner.saturating_sub(left_width + badge_width);
-                    spans.push(Span::styled(" ".repeat(pad), text_style));
-                    spans.push(Span::styled(badge, Style::default().fg(Color::Yellow)));
+            .filter_map(|f| activity.get(&f.filename))
+            .copied()
+            .max()
+            .unwrap_or(0);
+        let file_item = |f: &DiffFile| {
+            let is_viewed = is_viewed(f);
+            let status = f.status_char();
+            let status_color = if is_viewed {
+                Color::DarkGray
+            } else {
+                match status {
+                    'A' => Color::Green,
+                    'M' => Color::Yellow,
+                    'D' => Color::Red,
+                    'R' => Color::Cyan,
+                    _ => Color::White,
+                }
+            };
+            let text_style = if is_viewed {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            let marker = if is_viewed { "✓ " } else { "  " };
+            // キャッシュから可視コメント数を取得 + 当該コミットの pending を加算
+            let visible_existing = current_sha
+                .as_deref()
+                .map(|sha| self.cached_visible_comment_count(sha, &f.filename))
+                .unwrap_or(0);
+            let visible_pending = self
+                .review
+                .pending_comments
+                .iter()
+                .filter(|pc| {
+                    pc.file_path == f.filename
+                        && current_sha
+                            .as_deref()
+                            .is_some_and(|sha| sha == pc.commit_sha)
+                })
+                .count();
+            let comment_count = visible_existing + visible_pending;
+            // ボーダー左右 (2) を除いた内部幅
+            let inner = area.width.saturating_sub(2) as usize;
+            let status_str = String::from(status);
+            let prefix_width =
+                UnicodeWidthStr::width(marker) + UnicodeWidthStr::width(status_str.as_str()) + 1; // space before filename
+            let mut badge_text = String::new();
+            if self.file_notes.contains_key(&f.filename) {
+                badge_text.push_str("📝 ");
+            }
+            if comment_count > 0 {
+                badge_text.push_str(&format!("💬 {} ", comment_count));
+            }
+            let heat = heatmap::heat_level(
+                activity.get(&f.filename).copied().unwrap_or(0),
+                max_activity,
+            );
+            let (badge, badge_width) = if badge_text.is_empty() && heat.is_none() {
+                (None, 0)
+            } else {
+                let w = UnicodeWidthStr::width(badge_text.as_str()) + usize::from(heat.is_some());
+                (Some(badge_text), w)
+            };
+            let filename_max = inner.saturating_sub(prefix_width + badge_width);
+            let truncated = truncate_str(&f.filename, filename_max);
+            let mut spans = vec![
+                Span::styled(marker, text_style),
+                Span::styled(status_str, Style::default().fg(status_color)),
+                Span::styled(format!(" {}", truncated), text_style),
+            ];
+            if let Some(badge) = badge {
+                let left_width = prefix_width + UnicodeWidthStr::width(truncated.as_str());
+                let pad = inner.saturating_sub(left_width + badge_width);
+                spans.push(Span::styled(" ".repeat(pad), text_style));
+                spans.push(Span::styled(badge, Style::default().fg(Color::Yellow)));
+                if let Some((glyph, color)) = heat {
+                    spans.push(Span::styled(glyph, Style::default().fg(color)));
+                }
+            }
+            ListItem::new(Line::from(spans))
+        };
+        let rows = self.file_tree_rows();
+        let tests: Vec<&DiffFile> = files
+            .iter()
+            .filter(|f| self.is_test_file(&f.filename))
+            .collect();
+        let items: Vec<ListItem> = rows
+            .iter()
+            .map(|row| match row {
+                FileRow::File(i) => file_item(&files[*i]),
+                FileRow::TestsHeader => {
+                    let collapsed = self.test_split == TestSplit::Collapsed;
+                    let viewed = tests.iter().filter(|&&f| is_viewed(f)).count();
+                    ListItem::new(Line::styled(
+                        format!(
+                            "{} {} ({}, ✓{})",
+                            if collapsed { "▸" } else { "▾" },
+                            tr("Tests"),
+                            tests.len(),
+                            viewed
+                        ),
+                        Style::default()
+                            .fg(Color::DarkGray)
+                            .add_modifier(Modifier::BOLD),
+                    ))
                 }
-                ListItem::new(Line::from(spans))
             })
             .collect();
 
         let selected = self.file_list_state.selected().map(|i| i + 1).unwrap_or(0);
         let total = items.len();
-        let title = format!(" Files {}/{} ✓{} ", selected, files.len(), viewed_count);
+        let title = if self.tests_excluded_from_progress() {
+            format!(
+                " Files {}/{} ✓{}/{} ",
+                selected,
+                files.len(),
+                viewed_count,
+                counted.len()
+            )
+        } else {
+            format!(" Files {}/{} ✓{} ", selected, files.len(), viewed_count)
+        };
         let mut block = Block::default()
             .title(title)
             .borders(Borders::ALL)
             .border_style(style);
+        if let Some(label) = self.interdiff_label() {
+            block = block.title(
+                Line::styled(format!(" {label} "), Style::default().fg(Color::Magenta))
+                    .alignment(HorizontalAlignment::Right),
+            );
+        }
+        // 追従中のファイル（このコミットで変更されていなければ灰色で「-」付き）
+        if let Some(path) = &self.follow_file {
+            let name = path.rsplit('/').next().unwrap_or(path);
+            let (text, color) = if self.followed_file_missing() {
+                (format!(" ⇢ {name} - "), Color::DarkGray)
+            } else {
+                (format!(" ⇢ {name} "), Color::Cyan)
+            };
+            block = block.title(
+                Line::styled(text, Style::default().fg(color))
+                    .alignment(HorizontalAlignment::Right),
+            );
+        }
         if self.focused_panel == Panel::FileTree {
-            block =
-                block.title_bottom(Line::from(HINT_VIEWED).alignment(HorizontalAlignment::Right));
+            block = block
+                .title_bottom(Line::from(tr(HINT_VIEWED)).alignment(HorizontalAlignment::Right));
         }
         let list = List::new(items)
             .block(block)
             .highlight_style(self.highlight_style());
 
-        frame.render_stateful_widget(list, area, &mut self.file_list_state);
+        // 選択はファイルのインデックスで持つため、表示行の位置に変換して描画する
+        let selected_row = self
+            .file_list_state
+            .selected()
+            .and_then(|i| rows.iter().position(|row| *row == FileRow::File(i)));
+        let mut state = ListState::default()
+            .with_offset(self.file_list_state.offset())
+            .with_selected(selected_row);
+        frame.render_stateful_widget(list, area, &mut state);
+        *self.file_list_state.offset_mut() = state.offset();
 
         let offset = self.file_list_state.offset();
         let vh = area.height.saturating_sub(2) as usize;
@@ -686,12 +1140,15 @@ impl App {
         self.commit_msg_view_height = area.height.saturating_sub(2);
         let inner_width = area.width.saturating_sub(2);
 
-        let commit_msg = self
-            .commit_list_state
-            .selected()
-            .and_then(|idx| self.commits.get(idx))
-            .map(|c| c.commit.message.clone())
-            .unwrap_or_default();
+        let commit_msg = if self.is_all_changes_selected() {
+            self.all_changes_summary()
+        } else {
+            self.commit_list_state
+                .selected()
+                .and_then(|idx| self.commits.get(idx))
+                .map(|c| c.commit.message.clone())
+                .unwrap_or_default()
+        };
 
         // block なしで line_count を計算（block 付きだとボーダー行が加算されてしまう）
         let paragraph = Paragraph::new(commit_msg).wrap(Wrap { trim: false });
@@ -728,45 +1185,129 @@ impl App {
                 "Closed" => Color::Red,
                 _ => Color::White,
             };
-            lines.push(Line::from(vec![
+            let mut spans = vec![
                 Span::raw(" Status:  "),
                 Span::styled(&self.pr_state, Style::default().fg(state_color)),
-            ]));
+            ];
+            // レビュー待ちの経過時間（自分への依頼が SLA を超えていれば強調）
+            let now = chrono::Utc::now();
+            if let Some(since) = self.review_waiting_since() {
+                let target = if self.my_review_requested_at().is_some() {
+                    "you"
+                } else {
+                    "review"
+                };
+                let style = if self.review_overdue(now) {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                spans.push(Span::styled(
+                    format!(
+                        " · waiting on {target} {}",
+                        latency::elapsed_label(since, now)
+                    ),
+                    style,
+                ));
+            }
+            lines.push(Line::from(spans));
         }
 
         // Author
-        lines.push(Line::from(vec![
+        let mut spans = vec![
             Span::raw(" Author:  "),
             Span::styled(
                 format!("@{}", self.pr_author),
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(author_color(&self.pr_author)),
             ),
-        ]));
+        ];
+        if let Some(active) = self.author_last_active() {
+            spans.push(Span::styled(
+                format!(
+                    " · active {}",
+                    crate::format::relative(active, chrono::Utc::now())
+                ),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        lines.push(Line::from(spans));
 
         // Branch
         if !self.pr_base_branch.is_empty() || !self.pr_head_branch.is_empty() {
-            lines.push(Line::from(vec![
+            let mut spans = vec![
                 Span::raw(" Branch:  "),
                 Span::raw(&self.pr_base_branch),
                 Span::raw(" ← "),
-                Span::styled(&self.pr_head_branch, Style::default().fg(Color::Green)),
-            ]));
+                Span::styled(self.head_label(), Style::default().fg(Color::Green)),
+            ];
+            if self.behind_base {
+                spans.push(Span::styled(
+                    if self.fork_push_blocked() {
+                        " (behind)"
+                    } else {
+                        " (behind, U: update)"
+                    },
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            if self.fork_push_blocked() {
+                spans.push(Span::styled(
+                    " (fork: no maintainer edits)",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            lines.push(Line::from(spans));
         }
 
         // Date
         if !self.pr_created_at.is_empty() {
             lines.push(Line::from(vec![
                 Span::raw(" Date:    "),
-                Span::raw(&self.pr_created_at),
+                Span::raw(self.format_time(&self.pr_created_at)),
             ]));
         }
 
-        let paragraph = Paragraph::new(lines).block(
-            Block::default()
-                .title(" Info ")
-                .borders(Borders::ALL)
-                .border_style(Style::default()),
-        );
+        let mut block = Block::default()
+            .title(" Info ")
+            .borders(Borders::ALL)
+            .border_style(Style::default());
+        if self.team_review_requested() {
+            block = block.title_top(
+                Line::styled(
+                    " ◆ Team review requested ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .alignment(HorizontalAlignment::Right),
+            );
+        }
+        // 依頼中のレビュアー（チームは org/slug 表記、自分の所属チームを強調）
+        if !self.requested_reviewers.is_empty() || !self.requested_teams.is_empty() {
+            let owner = self.repo.split('/').next().unwrap_or_default();
+            let mine = self.my_requested_teams.as_deref().unwrap_or_default();
+            let mut spans = vec![Span::raw(" Requested: ")];
+            for user in &self.requested_reviewers {
+                spans.push(Span::styled(
+                    format!("@{user} "),
+                    Style::default().fg(author_color(user)),
+                ));
+            }
+            for team in &self.requested_teams {
+                let style = if mine.contains(team) {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Magenta)
+                };
+                spans.push(Span::styled(format!("@{owner}/{team} "), style));
+            }
+            block = block.title_bottom(Line::from(spans));
+        }
+        let paragraph = Paragraph::new(lines).block(block);
         frame.render_widget(paragraph, area);
     }
 
@@ -816,7 +1357,7 @@ impl App {
         if !date_str.is_empty() {
             lines.push(Line::from(vec![
                 Span::raw("Date:   "),
-                Span::raw(format_datetime(date_str)),
+                Span::raw(self.format_time(date_str)),
             ]));
         }
 
@@ -944,6 +1485,10 @@ impl App {
             return;
         }
 
+        if self.conversation_rendered_width != inner_width {
+            self.conversation_rendered_width = inner_width;
+            self.conversation_rendered = None;
+        }
         self.ensure_conversation_rendered();
         let lines = self.conversation_rendered.as_ref().unwrap().clone();
 
@@ -975,11 +1520,16 @@ impl App {
         let cursor_idx = self
             .conversation_cursor
             .min(self.conversation.len().saturating_sub(1));
+        let muted = if self.pr_muted { "🔇 " } else { "" };
+        let unread = match self.unread_count() {
+            0 => String::new(),
+            n => format!("● {n} new "),
+        };
         let title = if self.conversation.is_empty() {
-            " Conversation (0) ".to_string()
+            format!(" Conversation (0) {muted}")
         } else {
             format!(
-                " Conversation ({}/{}) ",
+                " Conversation ({}/{}) {unread}{muted}",
                 cursor_idx + 1,
                 self.conversation.len()
             )
@@ -988,14 +1538,17 @@ impl App {
         let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
         self.conversation_visual_total = paragraph.line_count(inner_width) as u16;
         self.clamp_conversation_scroll();
+        if std::mem::take(&mut self.conversation_recenter) {
+            self.center_conversation_on_cursor();
+        }
 
         let mut block = Block::default()
             .title(title)
             .borders(Borders::ALL)
             .border_style(border_style);
         if self.focused_panel == Panel::Conversation {
-            block =
-                block.title_bottom(Line::from(HINT_COMMENT).alignment(HorizontalAlignment::Right));
+            block = block
+                .title_bottom(Line::from(tr(HINT_COMMENT)).alignment(HorizontalAlignment::Right));
         }
         let paragraph = paragraph.block(block).scroll((self.conversation_scroll, 0));
         frame.render_widget(paragraph, area);
@@ -1030,6 +1583,7 @@ impl App {
                 buf.set_style(row_rect, Style::default().bg(cursor_bg));
             }
         }
+        self.render_conversation_avatars(frame, area);
 
         Self::render_scrollbar(
             frame,
@@ -1097,12 +1651,33 @@ impl App {
             let selection_suffix = match (&self.mode, &self.line_selection) {
                 (AppMode::LineSelect | AppMode::CommentInput, Some(sel)) => {
                     let count = sel.count(self.diff.cursor_line);
+                    let file_lines = self
+                        .selection_file_lines()
+                        .map(|(side, first, last)| {
+                            let old = if side == review::Side::Left {
+                                "old "
+                            } else {
+                                ""
+                            };
+                            if first == last {
+                                format!(" ({old}L{first})")
+                            } else {
+                                format!(" ({old}L{first}–L{last})")
+                            }
+                        })
+                        .unwrap_or_default();
                     format!(
-                        " - {} line{} selected",
+                        " - {} line{} selected{}",
                         count,
-                        if count == 1 { "" } else { "s" }
+                        if count == 1 { "" } else { "s" },
+                        file_lines
                     )
                 }
+                // 検索中はカーソル位置のマッチ番号と総数
+                _ if !self.diff.search_query.is_empty() => match self.search_position() {
+                    Some((current, total)) => format!(" - match {current}/{total}"),
+                    None => " - no matches".to_string(),
+                },
                 _ => String::new(),
             };
 
@@ -1147,24 +1722,65 @@ impl App {
         if self.focused_panel == Panel::DiffView
             && !matches!(
                 self.mode,
-                AppMode::CommentInput | AppMode::CommentView | AppMode::ReplyInput
+                AppMode::CommentInput
+                    | AppMode::CommentView
+                    | AppMode::ReplyInput
+                    | AppMode::CommentEditInput
+                    | AppMode::CommentDeleteConfirm
             )
         {
-            let hint = if self.mode == AppMode::LineSelect {
-                HINT_COMMENT
+            let hint = tr(if self.mode == AppMode::LineSelect {
+                HINT_SUGGEST
             } else {
                 HINT_SELECT_COMMENT
-            };
+            });
             block = block.title_bottom(Line::from(hint).alignment(HorizontalAlignment::Right));
         }
+        // 検索入力中はカーソル付きで、確定済みクエリはマッチ数付きで左下に表示
+        let options = search::search_options_label(self.diff.search_options);
+        let options = if options.is_empty() {
+            String::new()
+        } else {
+            format!("[{options}] ")
+        };
+        if self.mode == AppMode::DiffSearch {
+            let mut spans = vec![Span::styled(
+                format!(" /{}█ {options}", self.search_input),
+                Style::default().fg(Color::Magenta),
+            )];
+            if let Some(err) = self.search_input_error() {
+                spans.push(Span::styled(
+                    format!("✗ {err} "),
+                    Style::default().fg(Color::Red),
+                ));
+            }
+            block = block.title_bottom(Line::from(spans));
+        } else if !self.diff.search_query.is_empty() {
+            let count = self.search_match_lines().len();
+            block = block.title_bottom(Line::styled(
+                format!(" /{} ({}) {options}", self.diff.search_query, count),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
 
         // バイナリファイルまたは diff がない場合
         if has_file && !has_patch {
-            let paragraph = Paragraph::new(Line::styled(
-                "Binary file or no diff available",
-                Style::default().fg(Color::DarkGray),
-            ))
-            .block(block);
+            let omitted = self.current_file().filter(|f| Self::is_patch_omitted(f));
+            let message = match omitted {
+                Some(_) if file_status == "added" => {
+                    "Patch omitted by GitHub (file too large) — press F to load the full file"
+                        .to_string()
+                }
+                // REST API はサイズを返さないため変更行数で規模を示す
+                Some(f) => format!(
+                    "Patch truncated by GitHub (+{} -{} lines) — press F to compute the diff locally",
+                    f.additions, f.deletions
+                ),
+                None => "Binary file or no diff available".to_string(),
+            };
+            let paragraph =
+                Paragraph::new(Line::styled(message, Style::default().fg(Color::DarkGray)))
+                    .block(block);
             frame.render_widget(paragraph, area);
             return;
         }
@@ -1173,19 +1789,30 @@ impl App {
 
         self.update_diff_highlight_cache(&patch, &filename, &file_status);
         let mut text = self.prepare_diff_text(&patch, &file_status, inner_width);
+        self.emphasize_search_matches(&mut text, &patch);
         let bg_lines = self.collect_diff_bg_lines(&mut text, &filename);
+        let collapsed = self.collapsed_hunks();
+        self.apply_workflow_hints(&mut text, &patch, &filename);
+        self.apply_hunk_states(&mut text, &collapsed);
+        let is_hidden = |idx: usize| collapsed.iter().any(|(_, body)| body.contains(&idx));
 
-        // Wrap 有効時、レンダリングに使う実テキストから視覚行オフセットを計算してキャッシュ。
+        // Wrap 有効時（または hunk 折りたたみ時）、レンダリングに使う実テキストから視覚行オフセットを計算してキャッシュ。
         // visual_line_offset / visual_to_logical_line はこのキャッシュを参照する。
-        if self.diff.wrap {
+        if self.diff_uses_visual_offsets() {
             let mut offsets = Vec::with_capacity(text.lines.len() + 1);
             let mut visual = 0usize;
             offsets.push(0);
-            for line in &text.lines {
-                let count = Paragraph::new(line.clone())
-                    .wrap(Wrap { trim: false })
-                    .line_count(inner_width)
-                    .max(1);
+            for (idx, line) in text.lines.iter().enumerate() {
+                let count = if is_hidden(idx) {
+                    0
+                } else if self.diff.wrap {
+                    Paragraph::new(line.clone())
+                        .wrap(Wrap { trim: false })
+                        .line_count(inner_width)
+                        .max(1)
+                } else {
+                    1
+                };
                 visual += count;
                 offsets.push(visual);
             }
@@ -1195,6 +1822,15 @@ impl App {
         }
 
         let line_count = text.lines.len();
+        // 折りたたまれた hunk の本体は描画しない（オフセット上は 0 行）
+        if !collapsed.is_empty() {
+            text.lines = std::mem::take(&mut text.lines)
+                .into_iter()
+                .enumerate()
+                .filter(|(idx, _)| !is_hidden(*idx))
+                .map(|(_, line)| line)
+                .collect();
+        }
         let paragraph = Paragraph::new(text)
             .block(block)
             .scroll((self.diff.scroll, 0));
@@ -1217,62 +1853,112 @@ impl App {
         );
     }
 
-    /// delta 出力をキャッシュ（ファイル選択が変わったときだけ再実行）
-    fn update_diff_highlight_cache(&mut self, patch: &str, filename: &str, file_status: &str) {
-        let commit_idx = self.commit_list_state.selected().unwrap_or(usize::MAX);
-        let file_idx = self.file_list_state.selected().unwrap_or(usize::MAX);
+    /// ワークフローファイルの hunk header に変更されたジョブ・ステップ名を付ける
+    fn apply_workflow_hints(&self, text: &mut Text<'static>, patch: &str, filename: &str) {
+        if !workflow::is_workflow_file(filename) || !crate::git::diff::workflow_hints() {
+            return;
+        }
+        let style = self.hunk_header_style().add_modifier(Modifier::ITALIC);
+        for (header, label) in workflow::workflow_hunk_labels(patch) {
+            if let Some(line) = text.lines.get_mut(header) {
+                label_hunk_header(line, &label, style);
+            }
+        }
+    }
 
-        let cache_hit = matches!(
-            &self.diff.highlight_cache,
-            Some((ci, fi, _)) if *ci == commit_idx && *fi == file_idx
-        );
+    /// hunk の状態を表示に反映する（折りたたみは header にラベル、確認済みは本体を減光）
+    fn apply_hunk_states(&self, text: &mut Text<'static>, collapsed: &[(usize, Range<usize>)]) {
+        let label_style = self.hunk_header_style().add_modifier(Modifier::BOLD);
+        let reviewed = self.reviewed_hunks();
+        for (header, body) in &reviewed {
+            for line in text.lines.get_mut(body.clone()).into_iter().flatten() {
+                for span in &mut line.spans {
+                    span.style = span.style.add_modifier(Modifier::DIM);
+                }
+            }
+            if let Some(line) = text.lines.get_mut(*header) {
+                label_hunk_header(line, "✓ ", label_style);
+            }
+        }
+        for (header, body) in collapsed {
+            if let Some(line) = text.lines.get_mut(*header) {
+                let label = format!(
+                    "▸ {} line{} ",
+                    body.len(),
+                    if body.len() == 1 { "" } else { "s" }
+                );
+                label_hunk_header(line, &label, label_style);
+            }
+        }
+    }
+
+    /// 表示中の diff のハイライトキャッシュのキー
+    fn highlight_cache_key(&self) -> (usize, usize) {
+        (
+            self.commit_list_state.selected().unwrap_or(usize::MAX),
+            self.file_list_state.selected().unwrap_or(usize::MAX),
+        )
+    }
+
+    /// delta 出力をキャッシュ（未キャッシュのファイルを開いたときだけ再実行）
+    fn update_diff_highlight_cache(&mut self, patch: &str, filename: &str, file_status: &str) {
+        let key = self.highlight_cache_key();
+        let cache_hit = self.diff.highlight_cache.get(&key).is_some();
 
         if !cache_hit {
             let is_whole_file = matches!(file_status, "added" | "removed" | "deleted");
-            let base_text = if let Some(highlighted) = highlight_diff(patch, filename, file_status)
-            {
-                highlighted
-            } else {
-                // delta 未使用: 手動色分け
-                let lines: Vec<Line> = patch
-                    .lines()
-                    .map(|line| {
-                        if is_whole_file {
-                            // 全行追加/削除: +/- を除去してデフォルトスタイルで表示
-                            let content = if (line.starts_with('+') || line.starts_with('-'))
-                                && line.len() > 1
-                            {
-                                &line[1..]
-                            } else if line.starts_with('+') || line.starts_with('-') {
-                                ""
+            let mut base_text =
+                if let Some(highlighted) = highlight_diff(patch, filename, file_status) {
+                    highlighted
+                } else {
+                    // delta 未使用: 手動色分け
+                    let lines: Vec<Line> = patch
+                        .lines()
+                        .map(|line| {
+                            if is_whole_file {
+                                // 全行追加/削除: +/- を除去してデフォルトスタイルで表示
+                                let content = if (line.starts_with('+') || line.starts_with('-'))
+                                    && line.len() > 1
+                                {
+                                    &line[1..]
+                                } else if line.starts_with('+') || line.starts_with('-') {
+                                    ""
+                                } else {
+                                    line
+                                };
+                                Line::styled(content.to_string(), Style::default())
                             } else {
-                                line
-                            };
-                            Line::styled(content.to_string(), Style::default())
-                        } else {
-                            let style = match line.chars().next() {
-                                Some('+') => Style::default().fg(Color::Green),
-                                Some('-') => Style::default().fg(Color::Red),
-                                Some('@') => Style::default().fg(Color::Cyan),
-                                _ => Style::default(),
-                            };
-                            Line::styled(line.to_string(), style)
-                        }
-                    })
-                    .collect();
-                Text::from(lines)
-            };
-            self.diff.highlight_cache = Some((commit_idx, file_idx, base_text));
+                                let style = match line.chars().next() {
+                                    Some('+') => Style::default().fg(Color::Green),
+                                    Some('-') => Style::default().fg(Color::Red),
+                                    Some('@') => Style::default().fg(Color::Cyan),
+                                    _ => Style::default(),
+                                };
+                                Line::styled(line.to_string(), style)
+                            }
+                        })
+                        .collect();
+                    Text::from(lines)
+                };
+            expand_tabs(&mut base_text, crate::git::diff::tab_width());
+            let size = text_size(&base_text);
+            self.diff.highlight_cache.insert(key, base_text, size);
         }
     }
 
     /// キャッシュからクローンして Hunk ヘッダー整形・Wrap 空行修正・行番号プレフィックスを適用。
     /// `update_diff_highlight_cache` が事前に呼ばれている必要がある。
     fn prepare_diff_text(&self, patch: &str, file_status: &str, inner_width: u16) -> Text<'static> {
-        let mut text = self.diff.highlight_cache.as_ref().unwrap().2.clone();
+        let mut text = self
+            .diff
+            .highlight_cache
+            .peek(&self.highlight_cache_key())
+            .unwrap()
+            .clone();
 
         // Hunk ヘッダーを整形表示に置換
         let patch_lines: Vec<&str> = patch.lines().collect();
+        let new_file_view = self.is_new_file_view();
 
         // delta 出力の余分な末尾行を除去（patch 行数と一致させる）
         text.lines.truncate(patch_lines.len());
@@ -1280,7 +1966,15 @@ impl App {
             if let Some(raw) = patch_lines.get(idx)
                 && raw.starts_with("@@")
             {
-                *line = Self::format_hunk_header(raw, inner_width, self.hunk_header_style());
+                *line = if new_file_view {
+                    // 新規ファイル表示: 行範囲は冗長なので行数のみのラベルにする
+                    Line::styled(
+                        format!("─── new file · {} lines ───", patch_lines.len() - 1),
+                        self.hunk_header_style(),
+                    )
+                } else {
+                    Self::format_hunk_header(raw, inner_width, self.hunk_header_style())
+                };
             }
         }
 
@@ -1295,8 +1989,8 @@ impl App {
             }
         }
 
-        // 行番号プレフィックスを各行の先頭に挿入
-        if self.diff.show_line_numbers {
+        // 行番号プレフィックスを各行の先頭に挿入（新規ファイル表示では常に表示）
+        if self.diff.show_line_numbers || new_file_view {
             use crate::github::review::parse_hunk_header;
 
             let line_num_style = Style::default().fg(Color::DarkGray);
@@ -1357,6 +2051,7 @@ impl App {
         let show_cursor = self.focused_panel == Panel::DiffView;
         let has_selection = self.mode == AppMode::LineSelect || self.mode == AppMode::CommentInput;
         let existing_counts = self.existing_comment_counts();
+        let range_lines = self.commented_range_lines();
         let cursor_bg = match self.theme {
             ThemeMode::Dark => CURSOR_BG_DARK,
             ThemeMode::Light => CURSOR_BG_LIGHT,
@@ -1365,7 +2060,24 @@ impl App {
             ThemeMode::Dark => PENDING_BG_DARK,
             ThemeMode::Light => PENDING_BG_LIGHT,
         };
-
+        let (covered_bg, uncovered_bg) = match self.theme {
+            ThemeMode::Dark => (COVERED_BG_DARK, UNCOVERED_BG_DARK),
+            ThemeMode::Light => (COVERED_BG_LIGHT, UNCOVERED_BG_LIGHT),
+        };
+        let coverage_status = self.coverage_line_status();
+        let search_bg = match self.theme {
+            ThemeMode::Dark => SEARCH_BG_DARK,
+            ThemeMode::Light => SEARCH_BG_LIGHT,
+        };
+        let search_matches: HashSet<usize> = self.search_match_lines().into_iter().collect();
+
+        // CommentView 中はスレッドの対象行を強調し、それ以外を薄く表示する
+        let thread_range = if self.mode == AppMode::CommentView {
+            self.viewing_thread_range()
+        } else {
+            None
+        };
+
         // 背景色が必要な論理行を収集（render 後に Buffer で適用）
         let mut bg_lines: Vec<(usize, Color)> = Vec::new();
 
@@ -1382,15 +2094,29 @@ impl App {
                 .iter()
                 .any(|c| c.file_path == filename && idx >= c.start_line && idx <= c.end_line);
             let existing_count = existing_counts.get(&idx).copied().unwrap_or(0);
+            let in_thread = thread_range.is_some_and(|(start, end)| idx >= start && idx <= end);
+
+            if thread_range.is_some() && !in_thread {
+                for span in &mut line.spans {
+                    span.style = span.style.add_modifier(Modifier::DIM);
+                }
+            }
 
-            if is_selected || is_cursor {
+            if is_selected || is_cursor || in_thread {
                 bg_lines.push((idx, cursor_bg));
             } else if is_pending {
                 bg_lines.push((idx, pending_bg));
+            } else if search_matches.contains(&idx) {
+                bg_lines.push((idx, search_bg));
+            } else if let Some(&covered) = coverage_status.get(&idx) {
+                // カバレッジオーバーレイ（追加行のみ）
+                bg_lines.push((idx, if covered { covered_bg } else { uncovered_bg }));
             }
 
-            // 既存コメント行は下線で表示（背景色だとテーマ依存で文字が見えなくなるため）
-            if existing_count > 0 && !is_selected && !is_cursor && !is_pending {
+            // 既存コメント行（複数行コメントは範囲全体）は下線で表示（背景色だとテーマ依存で文字が見えなくなるため）
+            let in_comment_range = range_lines.contains(&idx);
+            if (existing_count > 0 || in_comment_range) && !is_selected && !is_cursor && !is_pending
+            {
                 for span in &mut line.spans {
                     span.style = span.style.add_modifier(Modifier::UNDERLINED);
                 }
@@ -1405,6 +2131,10 @@ impl App {
                 };
                 line.spans
                     .push(Span::styled(marker, Style::default().fg(Color::Yellow)));
+            } else if in_comment_range {
+                // 複数行コメントの途中の行（💬 は末尾行に付く）
+                line.spans
+                    .push(Span::styled(" ┆", Style::default().fg(Color::Yellow)));
             }
 
             // 💭 マーカー（pending コメント行の末尾に付与）
@@ -1437,9 +2167,8 @@ impl App {
         let scroll = self.diff.scroll as usize;
         let buf = frame.buffer_mut();
         for &(logical_line, bg_color) in bg_lines {
-            let vis_start = self.visual_line_offset(logical_line);
-            let vis_end = self.visual_line_offset(logical_line + 1);
-            for vis_row in vis_start..vis_end {
+            // 折り返した行も含め、行番号・マーカー列を含む内部幅全体を塗る
+            for vis_row in self.visual_rows(logical_line) {
                 if vis_row < scroll {
                     continue;
                 }
@@ -1463,8 +2192,12 @@ impl App {
     /// それ以外は薄いグレーのボーダーで空のコメント欄を表示。
     /// ReviewBodyInput は呼び出し側で全幅パネルとして別途呼び出す。
     fn render_editor_panel(&mut self, frame: &mut Frame, area: Rect) {
-        // CommentView モード: viewing_comments をペインに表示（フォーカス状態）
-        if self.mode == AppMode::CommentView && !self.review.viewing_comments.is_empty() {
+        // CommentView モード: viewing_comments をペインに表示（フォーカス状態、削除確認中も）
+        if matches!(
+            self.mode,
+            AppMode::CommentView | AppMode::CommentDeleteConfirm
+        ) && !self.review.viewing_comments.is_empty()
+        {
             // render_cursor_comments が &mut self を取るため clone で借用を分離
             let comments = self.review.viewing_comments.clone();
             self.render_cursor_comments(frame, area, &comments, true);
@@ -1477,7 +2210,9 @@ impl App {
             AppMode::CommentInput
                 | AppMode::IssueCommentInput
                 | AppMode::ReplyInput
+                | AppMode::CommentEditInput
                 | AppMode::ReviewBodyInput
+                | AppMode::NoteInput
         ) && self.layout.diff_view_rect.width > 0
         {
             let comments = self.comments_at_diff_line(self.diff.cursor_line);
@@ -1489,7 +2224,15 @@ impl App {
 
         let (title, help_text, editor, show_cursor) = match self.mode {
             AppMode::CommentInput => {
-                let title = if let Some(selection) = self.line_selection {
+                let title = if self.queue_all_matches {
+                    let count = self.search_match_lines().len();
+                    format!(
+                        " Comment on {} match{} of \"{}\" ",
+                        count,
+                        if count == 1 { "" } else { "es" },
+                        self.diff.search_query
+                    )
+                } else if let Some(selection) = self.line_selection {
                     let (start, end) = selection.range(self.diff.cursor_line);
                     format!(" Comment L{}–L{} ", start + 1, end + 1)
                 } else {
@@ -1497,20 +2240,30 @@ impl App {
                 };
                 (
                     title,
-                    " Ctrl+G: suggestion | Ctrl+S: submit ",
+                    " Ctrl+G: suggestion | Ctrl+E: $EDITOR | Ctrl+S: submit ",
                     &mut self.review.comment_editor,
                     true,
                 )
             }
             AppMode::IssueCommentInput => (
                 " Comment (PR) ".to_string(),
-                " Ctrl+S: submit ",
+                " Ctrl+E: $EDITOR | Ctrl+S: submit ",
                 &mut self.review.comment_editor,
                 true,
             ),
             AppMode::ReplyInput => (
-                " Reply ".to_string(),
-                " Ctrl+S: submit ",
+                if self.review.resolve_after_reply {
+                    " Reply [resolve on send] ".to_string()
+                } else {
+                    " Reply ".to_string()
+                },
+                " Ctrl+R: quick reply | Ctrl+L: link commit | Ctrl+X: resolve | Ctrl+S: submit ",
+                &mut self.review.comment_editor,
+                true,
+            ),
+            AppMode::CommentEditInput => (
+                " Edit comment ".to_string(),
+                " Ctrl+E: $EDITOR | Ctrl+S: save ",
                 &mut self.review.comment_editor,
                 true,
             ),
@@ -1518,11 +2271,20 @@ impl App {
                 let event = self.available_events()[self.review.review_event_cursor];
                 (
                     format!(" Review Body ({}) ", event.label()),
-                    " Ctrl+S: submit ",
+                    " Ctrl+E: $EDITOR | Ctrl+S: submit ",
                     &mut self.review.review_body_editor,
                     true,
                 )
             }
+            AppMode::NoteInput => (
+                format!(
+                    " Note: {} (private) ",
+                    self.note_target.as_deref().unwrap_or_default()
+                ),
+                " Ctrl+P: promote to PR comment | Ctrl+S: save ",
+                &mut self.review.comment_editor,
+                true,
+            ),
             _ => (
                 " Comment ".to_string(),
                 "",
@@ -1593,20 +2355,67 @@ impl App {
         }
 
         let mut lines = Vec::new();
+        // フォーカス時はスレッドのルートコメント時点の hunk を先頭に表示する
+        if focused
+            && let Some(root) = comments
+                .iter()
+                .find(|c| c.in_reply_to_id.is_none())
+                .or(comments.first())
+        {
+            lines.extend(diff_hunk_preview(root));
+            if !lines.is_empty() {
+                lines.push(Line::styled(
+                    "─".repeat(area.width.saturating_sub(2) as usize),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+        }
+        // 引用する行の選択（フォーカス時のみ）と、カーソル行の描画位置
+        let quote_range = self
+            .review
+            .quote_select
+            .filter(|_| focused)
+            .map(|sel| (sel.range(), sel.cursor));
+        let mut body_idx = 0;
+        let mut quote_cursor_row = None;
+        // 複数コメントのスレッドでは e / d の対象になる選択中のコメントに印を付ける
+        let selected =
+            (focused && comments.len() > 1).then_some(self.review.viewing_comment_cursor);
+        let mut header_rows = Vec::with_capacity(comments.len());
         for (i, comment) in comments.iter().enumerate() {
             if i > 0 {
                 lines.push(Line::raw(""));
             }
-            lines.push(Line::styled(
-                format!(
-                    "@{} ({})",
-                    comment.user.login,
-                    format_datetime(&comment.created_at)
+            header_rows.push(lines.len() as u16);
+            let mut header = Vec::new();
+            if selected == Some(i) {
+                header.push(Span::styled("▶ ", Style::default().fg(Color::Yellow)));
+            }
+            header.extend([
+                Span::styled(
+                    format!("@{}", comment.user.login),
+                    Style::default().fg(author_color(&comment.user.login)),
                 ),
-                Style::default().fg(Color::Cyan),
-            ));
+                Span::styled(
+                    format!(" ({})", self.format_time(&comment.created_at)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]);
+            lines.push(Line::from(header));
             for body_line in comment.body.lines() {
-                lines.push(Line::raw(body_line.to_string()));
+                match quote_range {
+                    Some(((start, end), cursor)) if (start..=end).contains(&body_idx) => {
+                        if body_idx == cursor {
+                            quote_cursor_row = Some(lines.len());
+                        }
+                        lines.push(Line::styled(
+                            format!("> {body_line}"),
+                            Style::default().bg(Color::DarkGray),
+                        ));
+                    }
+                    _ => lines.push(Line::raw(body_line.to_string())),
+                }
+                body_idx += 1;
             }
         }
 
@@ -1615,10 +2424,22 @@ impl App {
             .and_then(|id| self.review.thread_map.get(&id))
             .is_some_and(|t| t.is_resolved);
 
+        // 複数行コメントは対象の行範囲を見出しに出す
+        let range = comments
+            .iter()
+            .find(|c| c.in_reply_to_id.is_none())
+            .or(comments.first())
+            .filter(|root| root.start_line.is_some_and(|s| Some(s) != root.line))
+            .and_then(|root| root.line_range_label())
+            .map(|label| format!(" {label}"))
+            .unwrap_or_default();
         let title = if is_resolved {
-            format!(" 💬 Review Comments ({}) [Resolved] ", comments.len())
+            format!(
+                " 💬 Review Comments ({}){range} [Resolved] ",
+                comments.len()
+            )
         } else {
-            format!(" 💬 Review Comments ({}) ", comments.len())
+            format!(" 💬 Review Comments ({}){range} ", comments.len())
         };
         let (help_text, border_color) = if focused {
             let resolve_label = if is_resolved {
@@ -1626,12 +2447,32 @@ impl App {
             } else {
                 "r: resolve"
             };
-            (format!(" c: reply | {resolve_label} "), Color::Yellow)
+            if quote_range.is_some() {
+                (
+                    " j/k: line | J/K: extend | Enter: quote reply | Esc: cancel ".to_string(),
+                    Color::Yellow,
+                )
+            } else {
+                let select = if comments.len() > 1 {
+                    " | J/K: select"
+                } else {
+                    ""
+                };
+                let own = if self.own_viewing_comment().is_some() {
+                    " | e: edit | d: delete"
+                } else {
+                    ""
+                };
+                (
+                    format!(" c: reply | >: quote | {resolve_label}{select}{own} "),
+                    Color::Yellow,
+                )
+            }
         } else {
             (String::new(), Color::DarkGray)
         };
         let mut block = Block::default()
-            .title(title)
+            .title(title.as_str())
             .borders(Borders::ALL)
             .border_style(Style::default().fg(border_color));
         if !help_text.is_empty() {
@@ -1645,12 +2486,36 @@ impl App {
         let visual_total = paragraph.line_count(inner_width);
         self.review.comment_view_max_scroll =
             (visual_total as u16).saturating_sub(visible_height as u16);
+        self.review.comment_view_height = visible_height as u16;
+        if focused {
+            self.review.viewing_comment_rows = header_rows;
+        }
+        // 選択中の行が見えるようにスクロールする（折り返しは考慮しない近似）
+        if let Some(row) = quote_cursor_row.map(|r| r as u16) {
+            let scroll = &mut self.review.viewing_comment_scroll;
+            if row < *scroll {
+                *scroll = row;
+            } else if row >= *scroll + visible_height as u16 {
+                *scroll =
+                    (row + 1 - visible_height as u16).min(self.review.comment_view_max_scroll);
+            }
+        }
 
         let paragraph = paragraph
             .block(block)
             .scroll((self.review.viewing_comment_scroll, 0));
 
         frame.render_widget(paragraph, area);
+        // フォーカス中のスレッドの見出しをパーマリンクにする
+        if focused && let Some(url) = self.thread_permalink() {
+            let title_area = Rect {
+                x: area.x + 1,
+                width: (title.width() as u16).min(area.width.saturating_sub(2)),
+                height: 1,
+                ..area
+            };
+            self.link_area(frame.buffer_mut(), title_area, url);
+        }
 
         if visual_total > visible_height {
             Self::render_scrollbar(
@@ -1742,7 +2607,21 @@ impl App {
     }
 
     fn render_review_submit_dialog(&self, frame: &mut Frame, area: Rect) {
-        let dialog = Self::centered_rect(REVIEW_DIALOG_WIDTH, REVIEW_DIALOG_HEIGHT, area);
+        let show_checklist = self.checklist_append && !self.checklist.is_empty();
+        // 送信に含まれる保留コメントを並べる（多い場合は残りの件数だけ）
+        let summaries = self.pending_comment_summaries();
+        let listed = summaries.len().min(PENDING_SUMMARY_MAX);
+        let more = summaries.len() - listed;
+        let height = REVIEW_DIALOG_HEIGHT
+            + u16::from(show_checklist)
+            + u16::from(self.is_own_pr)
+            + (listed + usize::from(more > 0)) as u16;
+        let width = if summaries.is_empty() {
+            REVIEW_DIALOG_WIDTH
+        } else {
+            REVIEW_DIALOG_WIDE_WIDTH
+        };
+        let dialog = Self::centered_rect(width, height, area);
         Self::clear_wide_safe(frame, dialog, area);
 
         let comments_info = if self.review.pending_comments.is_empty() {
@@ -1753,217 +2632,1512 @@ impl App {
 
         let mut lines = vec![Line::raw("")];
 
-        for (i, event) in self.available_events().iter().enumerate() {
-            let marker = if i == self.review.review_event_cursor {
+        // 選べないイベントも灰色で表示する（自分の PR の場合は理由を下にまとめて出す）
+        let selected = self.available_events()[self.review.review_event_cursor];
+        for event in ReviewEvent::ALL {
+            let marker = if event == selected { "▶ " } else { "  " };
+            let label = format!("{}[{}] {}", marker, event.key(), event.label());
+            let line = match self.review_event_disabled_reason(event) {
+                Some(_) if !self.available_events().contains(&event) => {
+                    Line::styled(label, Style::default().fg(Color::DarkGray))
+                }
+                Some(reason) => Line::styled(
+                    format!("{} ({})", label, tr(reason)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                None if event == selected => {
+                    Line::styled(label, Style::default().fg(Color::Yellow))
+                }
+                None => Line::raw(label),
+            };
+            lines.push(line);
+        }
+        if self.is_own_pr {
+            lines.push(Line::styled(
+                format!("  {}", tr("You cannot approve your own PR")),
+                Style::default().fg(Color::Red),
+            ));
+        }
+
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            format!("  {}", comments_info),
+            Style::default().fg(Color::DarkGray),
+        ));
+        let summary_width = (dialog.width as usize).saturating_sub(7);
+        for summary in summaries.iter().take(listed) {
+            lines.push(Line::raw(format!(
+                "   • {}",
+                truncate_str(summary, summary_width)
+            )));
+        }
+        if more > 0 {
+            lines.push(Line::styled(
+                format!("   … +{more}"),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        if show_checklist {
+            lines.push(Line::styled(
+                format!(
+                    "  Checklist {}/{} appended",
+                    self.checklist_checked_count(),
+                    self.checklist.len()
+                ),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr(" Submit Review "))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+        frame.render_widget(paragraph, dialog);
+    }
+
+    /// 自分の PR 向けダッシュボードを描画する
+    fn render_dashboard_overlay(&self, frame: &mut Frame, area: Rect) {
+        let dialog_width = (area.width * 4 / 5).max(area.width.min(HELP_DIALOG_WIDTH));
+        let dialog_height = (area.height * 4 / 5).max(area.height.min(HELP_DIALOG_MIN_HEIGHT));
+        let dialog = Self::centered_rect(dialog_width, dialog_height, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let threads = self.dashboard_threads();
+        let reviewers = self.reviewer_states();
+        let addressed = threads.iter().filter(|t| t.addressed).count();
+        let s = Style::default().fg(Color::Yellow);
+        let dim = Style::default().fg(Color::DarkGray);
+
+        let mut lines: Vec<Line> = vec![
+            Line::raw(""),
+            Line::from(vec![
+                Span::styled("  Unresolved threads: ", s),
+                Span::raw(format!(
+                    "{} ({} addressed / {} awaiting reply)",
+                    threads.len(),
+                    addressed,
+                    threads.len() - addressed
+                )),
+            ]),
+            Line::raw(""),
+            Line::styled("  Reviewers", s),
+        ];
+        if reviewers.is_empty() {
+            lines.push(Line::styled("    (no reviews yet)", dim));
+        }
+        for (author, state) in &reviewers {
+            let (label, color) = match state.as_str() {
+                "APPROVED" => ("APPROVED", Color::Green),
+                "CHANGES_REQUESTED" => ("CHANGES REQUESTED", Color::Red),
+                "DISMISSED" => ("DISMISSED", Color::DarkGray),
+                _ => ("COMMENTED", Color::Cyan),
+            };
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(
+                    format!("@{author}"),
+                    Style::default().fg(author_color(author)),
+                ),
+                Span::raw(" "),
+                Span::styled(label, Style::default().fg(color)),
+            ]));
+        }
+
+        lines.push(Line::raw(""));
+        lines.push(Line::styled("  Threads", s));
+        if threads.is_empty() {
+            lines.push(Line::styled("    (all threads resolved)", dim));
+        }
+        // ボーダー (2) + マーカー等のプレフィックス分を除いた幅で要約を切り詰める
+        let summary_width = (dialog_width as usize).saturating_sub(8);
+        for (i, thread) in threads.iter().enumerate() {
+            let marker = if i == self.dashboard_cursor {
                 "▶ "
             } else {
                 "  "
             };
-            let style = if i == self.review.review_event_cursor {
-                Style::default().fg(Color::Yellow)
+            let (icon, icon_color) = if thread.addressed {
+                ("✓", Color::Green)
+            } else {
+                ("●", Color::Yellow)
+            };
+            let location = match thread.line {
+                Some(line) => format!("{}:{}", thread.path, line),
+                None => thread.path.clone(),
+            };
+            let row_style = if i == self.dashboard_cursor {
+                Style::default().add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
             };
-            lines.push(Line::styled(format!("{}{}", marker, event.label()), style));
+            let head = format!("{location} ");
+            let author = format!("@{}", thread.author);
+            let detail = truncate_str(&format!("{head}{author} {}", thread.summary), summary_width);
+            let mut spans = vec![
+                Span::styled(format!("  {marker}"), row_style),
+                Span::styled(format!("{icon} "), Style::default().fg(icon_color)),
+            ];
+            // 作者名だけ色付け（切り詰めで作者名が欠けた場合はそのまま表示）
+            if let Some(rest) = detail.strip_prefix(&head)
+                && let Some(tail) = rest.strip_prefix(&author)
+            {
+                spans.push(Span::styled(head, row_style));
+                spans.push(Span::styled(
+                    author,
+                    row_style.fg(author_color(&thread.author)),
+                ));
+                spans.push(Span::styled(tail.to_string(), row_style));
+            } else {
+                spans.push(Span::styled(detail, row_style));
+            }
+            lines.push(Line::from(spans));
         }
 
+        // カーソル行が見えるようにスクロール（ヘッダー部分の行数 + カーソル）
+        let inner_height = dialog_height.saturating_sub(2) as usize;
+        let cursor_row = lines.len() - threads.len() + self.dashboard_cursor;
+        let scroll = (cursor_row + 1).saturating_sub(inner_height) as u16;
+
+        let k = Style::default().fg(Color::Cyan);
+        let hint = Line::from(vec![
+            Span::styled(" Enter ", k),
+            Span::raw("Jump  "),
+            Span::styled("c ", k),
+            Span::raw("Reply  "),
+            Span::styled("r ", k),
+            Span::raw("Resolve  "),
+            Span::styled("Q ", k),
+            Span::raw("Re-request  "),
+            Span::styled("M ", k),
+            Span::raw("Ready  "),
+            Span::styled("Esc ", k),
+            Span::raw("Close "),
+        ])
+        .alignment(HorizontalAlignment::Right);
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(format!(" Dashboard ({}#{}) ", self.repo, self.pr_number))
+                    .title_bottom(hint)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .scroll((scroll, 0));
+        frame.render_widget(paragraph, dialog);
+    }
+
+    fn render_checklist_dialog(&self, frame: &mut Frame, area: Rect) {
+        let label_width = self
+            .checklist
+            .iter()
+            .map(|item| item.label.width())
+            .max()
+            .unwrap_or(0) as u16;
+        // ボーダー (2) + マーカー・チェックボックス (8) + 余白 (2)
+        let width = (label_width + 12)
+            .max(CHECKLIST_DIALOG_MIN_WIDTH)
+            .min(area.width.saturating_sub(4));
+        // PR 本文から取り込んだ項目の前に見出しを 1 行入れる
+        let pr_heading = self.checklist.iter().any(|item| item.from_pr);
+        // ボーダー (2) + 上下余白 (2) + 追記設定 (1) + 見出し + 項目
+        let height = (self.checklist.len() as u16 + 5 + u16::from(pr_heading))
+            .min(area.height.saturating_sub(2));
+        let dialog = Self::centered_rect(width, height, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let mut lines = vec![Line::raw("")];
+        for (i, item) in self.checklist.iter().enumerate() {
+            if item.from_pr && !self.checklist[..i].iter().any(|prev| prev.from_pr) {
+                lines.push(Line::styled(
+                    format!("  {}", tr("From the PR description:")),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            let marker = if i == self.checklist_cursor {
+                "▶ "
+            } else {
+                "  "
+            };
+            let checkbox = if item.checked { "[x] " } else { "[ ] " };
+            let style = if i == self.checklist_cursor {
+                Style::default().fg(Color::Yellow)
+            } else if item.checked {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::styled(
+                format!("{marker}{checkbox}{}", item.label),
+                style,
+            ));
+        }
         lines.push(Line::raw(""));
         lines.push(Line::styled(
-            format!("  {}", comments_info),
+            format!(
+                "  a: append to review [{}]",
+                if self.checklist_append { "on" } else { "off" }
+            ),
             Style::default().fg(Color::DarkGray),
         ));
 
+        let title = format!(
+            " Checklist ({}/{}) ",
+            self.checklist_checked_count(),
+            self.checklist.len()
+        );
         let paragraph = Paragraph::new(lines).block(
             Block::default()
-                .title(" Submit Review ")
+                .title(title)
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan)),
         );
         frame.render_widget(paragraph, dialog);
     }
 
-    fn render_quit_confirm_dialog(&self, frame: &mut Frame, area: Rect) {
-        let dialog = Self::centered_rect(QUIT_DIALOG_WIDTH, QUIT_DIALOG_HEIGHT, area);
-        Self::clear_wide_safe(frame, dialog, area);
+    /// API 呼び出し統計ダイアログを描画する
+    fn render_stats_dialog(&self, frame: &mut Frame, area: Rect) {
+        let metrics = crate::github::metrics::snapshot();
+        let total = metrics.total();
+        let label = Style::default().fg(Color::Cyan);
+        let dim = Style::default().fg(Color::DarkGray);
 
-        let lines = vec![
+        let mut lines = vec![
             Line::raw(""),
-            Line::styled(
-                format!(
-                    "  {} unsent comment(s).",
-                    self.review.pending_comments.len()
-                ),
-                Style::default().fg(Color::Yellow),
-            ),
-            Line::styled("  Submit before quitting?", Style::default()),
+            Line::from(vec![
+                Span::styled("  API calls   ", label),
+                Span::raw(format!(
+                    "{}  ({}, {})",
+                    total.count,
+                    format_duration(total.total),
+                    format_bytes(total.bytes)
+                )),
+            ]),
+            Line::from(vec![
+                Span::styled("  Cache       ", label),
+                Span::raw(format!(
+                    "{} hit / {} miss",
+                    metrics.cache_hits, metrics.cache_misses
+                )),
+            ]),
+            Line::from(vec![
+                Span::styled("  Rate limit  ", label),
+                Span::raw(match metrics.rate_limit {
+                    Some(rl) => format!("{} / {} remaining", rl.remaining, rl.limit),
+                    None => "unknown".to_string(),
+                }),
+            ]),
             Line::raw(""),
-            Line::styled("  y: submit & quit", Style::default().fg(Color::Green)),
-            Line::styled("  n: discard & quit", Style::default().fg(Color::Red)),
-            Line::styled("  c: cancel", Style::default().fg(Color::DarkGray)),
+            Line::styled("  Load phases", label),
         ];
+        if metrics.phases.is_empty() {
+            lines.push(Line::styled("    (none)", dim));
+        }
+        for (name, elapsed) in &metrics.phases {
+            lines.push(Line::raw(format!(
+                "    {:<18}{:>10}",
+                name,
+                format_duration(*elapsed)
+            )));
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            format!(
+                "  {:<20}{:>6}{:>10}{:>10}",
+                "Endpoint", "calls", "time", "bytes"
+            ),
+            label,
+        ));
+        for (name, stat) in &metrics.calls {
+            lines.push(Line::raw(format!(
+                "  {:<20}{:>6}{:>10}{:>10}",
+                name,
+                stat.count,
+                format_duration(stat.total),
+                format_bytes(stat.bytes)
+            )));
+        }
 
+        let width = STATS_DIALOG_WIDTH.min(area.width.saturating_sub(4));
+        let height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+        let dialog = Self::centered_rect(width, height, area);
+        Self::clear_wide_safe(frame, dialog, area);
         let paragraph = Paragraph::new(lines).block(
             Block::default()
-                .title(" Quit Confirmation ")
+                .title(" API Stats ")
+                .title_bottom(Line::from(" Esc: close ").alignment(HorizontalAlignment::Right))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red)),
+                .border_style(Style::default().fg(Color::Cyan)),
         );
         frame.render_widget(paragraph, dialog);
     }
 
-    fn render_help_dialog(&mut self, frame: &mut Frame, area: Rect) {
-        let dialog_height = (area.height * 2 / 3)
-            .max(HELP_DIALOG_MIN_HEIGHT)
-            .min(area.height.saturating_sub(4));
-        let dialog_width = HELP_DIALOG_WIDTH.min(area.width.saturating_sub(4));
-        let dialog = Self::centered_rect(dialog_width, dialog_height, area);
+    /// リンク一覧ダイアログを描画する
+    fn render_link_list_dialog(&mut self, frame: &mut Frame, area: Rect) {
+        let width = (area.width * 4 / 5).max(area.width.min(HELP_DIALOG_WIDTH));
+        // ボーダー (2) + 上下余白 (2) + 項目
+        let height = (self.link_list.len() as u16 + 4).min(area.height.saturating_sub(2));
+        let dialog = Self::centered_rect(width, height, area);
         Self::clear_wide_safe(frame, dialog, area);
 
-        let s = Style::default().fg(Color::Yellow); // section header
-        let k = Style::default().fg(Color::Cyan); // key
-        let d = Style::default(); // description
-        // ボーダー左右 (2) + インデント (2) + 余白 (2) を引いた幅でセパレータ生成
-        let sep_width = (HELP_DIALOG_WIDTH as usize).saturating_sub(6);
-        let sep: String = format!("  {}", "─".repeat(sep_width));
-
-        let panel = self.help_context_panel;
-
-        // --- 共通セクション (Global) ---
-        let mut entries: Vec<(&str, &str)> = vec![
-            ("", "Navigation"),
-            ("j / ↓", "Move down"),
-            ("k / ↑", "Move up"),
-            ("l / → / Tab", "Next pane"),
-            ("h / ← / BackTab", "Previous pane"),
-            ("1 / 2 / 3", "Jump to pane"),
-            ("Esc", "Back to parent pane"),
-            ("z", "Toggle zoom"),
-            ("R", "Reload PR data"),
-            ("S", "Submit review"),
-            ("?", "This help"),
-            ("q", "Quit"),
-        ];
-
-        // --- Scroll セクション (PrDescription, CommitList, CommitMessage, Conversation, DiffView) ---
-        if matches!(
-            panel,
-            Panel::PrDescription
-                | Panel::CommitList
-                | Panel::CommitMessage
-                | Panel::Conversation
-                | Panel::DiffView
-                | Panel::CommitOverview
-        ) {
-            entries.extend_from_slice(&[
-                ("", "Scroll"),
-                ("Ctrl+d / Ctrl+u", "Half page down / up"),
-                ("Ctrl+f / Ctrl+b", "Full page down / up"),
-                ("g / G", "Top / Bottom"),
-            ]);
-        }
-
-        // --- ペイン固有セクション ---
-        match panel {
-            Panel::PrDescription => {
-                entries.extend_from_slice(&[
-                    ("", "PR Description"),
-                    ("Enter", "Open conversation"),
-                    ("o", "Open media viewer"),
-                ]);
-            }
-            Panel::CommitList => {
-                entries.extend_from_slice(&[
-                    ("", "Commit List"),
-                    ("x", "Toggle viewed"),
-                    ("y", "Copy SHA"),
-                    ("Y", "Copy commit message"),
-                ]);
-            }
-            Panel::FileTree => {
-                entries.extend_from_slice(&[
-                    ("", "File Tree"),
-                    ("Enter", "Open diff"),
-                    ("x", "Toggle viewed"),
-                    ("y", "Copy file path"),
-                ]);
-            }
-            Panel::CommitMessage => {
-                entries.extend_from_slice(&[
-                    ("", "Commit Message"),
-                    ("Tab", "Switch to diff view"),
-                    ("Esc", "Back to file tree"),
-                ]);
-            }
-            Panel::DiffView => {
-                entries.extend_from_slice(&[
-                    ("", "Diff View"),
-                    ("Tab", "Switch to commit message"),
-                    ("n", "Toggle line numbers"),
-                    ("w", "Toggle line wrap"),
-                    ("]c / [c", "Next / prev change block"),
-                    ("]h / [h", "Next / prev hunk"),
-                    ("]n / [n", "Next / prev comment"),
-                    ("v", "Enter line select mode"),
-                    ("c", "Comment on line"),
-                    ("Enter", "View comment on line"),
-                    ("c (in view)", "Reply to thread"),
-                    ("r", "Resolve/unresolve thread"),
-                    ("Ctrl+G", "Insert suggestion"),
-                    ("Ctrl+S", "Submit comment"),
-                ]);
-            }
-            Panel::Conversation => {
-                entries.extend_from_slice(&[
-                    ("", "Conversation"),
-                    ("j / k", "Next / prev entry"),
-                    ("c", "Reply / comment on PR"),
-                    ("Ctrl+S", "Submit comment"),
-                    ("Esc", "Back to PR description"),
-                ]);
-            }
-            Panel::CommitOverview => {
-                entries.extend_from_slice(&[
-                    ("", "Commit Overview"),
-                    ("j / k", "Scroll down / up"),
-                    ("Esc", "Back to commit list"),
-                ]);
-            }
-        }
-
-        let mut lines: Vec<Line> = vec![];
-        for (key, desc) in &entries {
-            if key.is_empty() {
-                // セクションヘッダー
-                lines.push(Line::raw(""));
-                lines.push(Line::styled(format!("  {desc}"), s));
-                lines.push(Line::styled(sep.as_str(), s));
+        // ボーダー (2) + カーソル (2) + 番号 (3) + 余白 (1)
+        let text_width = (width as usize).saturating_sub(8);
+        let mut lines = vec![Line::raw("")];
+        // 各行のタイトル（なければ URL）の行・表示幅と URL
+        let mut link_cells = Vec::new();
+        for (i, link) in self.link_list.iter().enumerate() {
+            let selected = i == self.link_list_cursor;
+            let marker = if selected { "▶ " } else { "  " };
+            // 1〜9 のみ数字キーで開ける
+            let number = if i < links::LINK_LIST_NUMBERED {
+                format!("{} ", i + 1)
             } else {
-                lines.push(Line::from(vec![
-                    Span::styled(format!("  {key:<HELP_KEY_COLUMN_WIDTH$}"), k),
-                    Span::styled(*desc, d),
-                ]));
+                "  ".to_string()
+            };
+            let mut spans = vec![
+                Span::raw(marker),
+                Span::styled(number, Style::default().fg(Color::Cyan)),
+            ];
+            let url_style = Style::default().fg(Color::DarkGray);
+            match &link.title {
+                Some(title) => {
+                    let title = truncate_str(title, text_width / 2);
+                    let url_width = text_width.saturating_sub(title.width() + 1);
+                    let title_style = if selected {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default()
+                    };
+                    link_cells.push((lines.len(), title.width() as u16, link.url.clone()));
+                    spans.push(Span::styled(title, title_style));
+                    spans.push(Span::styled(
+                        format!(" {}", truncate_str(&link.url, url_width)),
+                        url_style,
+                    ));
+                }
+                None => {
+                    let style = if selected {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default()
+                    };
+                    let text = truncate_str(&link.url, text_width);
+                    link_cells.push((lines.len(), text.width() as u16, link.url.clone()));
+                    spans.push(Span::styled(text, style));
+                }
             }
+            lines.push(Line::from(spans));
         }
-        lines.push(Line::raw(""));
-        lines.push(Line::styled(
-            "  ?/Esc/q: close",
-            Style::default().fg(Color::DarkGray),
-        ));
 
-        // コンテンツ末尾を超えてスクロールしないようにクランプ
-        let content_height = lines.len() as u16;
-        let inner_height = dialog_height.saturating_sub(2); // ボーダー上下分
-        let max_scroll = content_height.saturating_sub(inner_height);
-        let scroll = self.help_scroll.min(max_scroll);
-        // 内部状態も同期して、スクロールアップ時のラグを防ぐ
-        self.help_scroll = scroll;
+        // カーソル行が見えるようにスクロール
+        let inner_height = height.saturating_sub(2) as usize;
+        let scroll = (self.link_list_cursor + 2).saturating_sub(inner_height) as u16;
 
+        let k = Style::default().fg(Color::Cyan);
+        let hint = Line::from(vec![
+            Span::styled(" 1-9/Enter ", k),
+            Span::raw("Open  "),
+            Span::styled("Esc ", k),
+            Span::raw("Close "),
+        ])
+        .alignment(HorizontalAlignment::Right);
         let paragraph = Paragraph::new(lines)
             .block(
                 Block::default()
-                    .title(format!(" Help ({panel}) "))
+                    .title(format!(" Links ({}) ", self.link_list.len()))
+                    .title_bottom(hint)
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::DarkGray)),
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .scroll((scroll, 0));
+        frame.render_widget(paragraph, dialog);
+        // カーソル (2) + 番号 (2) の後ろがリンク
+        let rows = link_cells
+            .into_iter()
+            .map(|(line, width, url)| (line, 4, width, url))
+            .collect();
+        self.link_dialog_rows(frame.buffer_mut(), dialog, scroll, rows);
+    }
+
+    /// CI チェック一覧ダイアログを描画する
+    fn render_checks_dialog(&mut self, frame: &mut Frame, area: Rect) {
+        let checks = self.checks_list();
+        let width = (area.width * 4 / 5).max(area.width.min(HELP_DIALOG_WIDTH));
+        // ボーダー (2) + 上下余白 (2) + 項目
+        let height = (checks.len().max(1) as u16 + 4).min(area.height.saturating_sub(2));
+        let dialog = Self::centered_rect(width, height, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        // ボーダー (2) + カーソル (2) + 記号 (2)
+        let text_width = (width as usize).saturating_sub(6);
+        let mut lines = vec![Line::raw("")];
+        if self.checks.is_none() {
+            let message = if self.checks_loading {
+                "Loading checks..."
+            } else {
+                "Checks could not be loaded — press r to retry"
+            };
+            lines.push(Line::styled(
+                format!("  {}", tr(message)),
+                Style::default().fg(Color::DarkGray),
+            ));
+        } else if checks.is_empty() {
+            lines.push(Line::styled(
+                format!("  {}", tr("No checks for the head commit")),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        // 詳細ページのあるチェックの行・名前の位置と URL
+        let mut link_rows = Vec::new();
+        for (i, check) in checks.iter().enumerate() {
+            let selected = i == self.checks_cursor;
+            let color = match check.state {
+                CheckState::Failure => Color::Red,
+                CheckState::Pending => Color::Yellow,
+                CheckState::Success => Color::Green,
+                CheckState::Neutral => Color::DarkGray,
+            };
+            let name = truncate_str(&check.name, text_width / 2);
+            let detail_width = text_width.saturating_sub(name.width() + 1);
+            let name_style = if selected {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            };
+            let symbol = format!("{} ", check.state.symbol());
+            if let Some(url) = &check.url {
+                let x = 2 + symbol.width() as u16;
+                link_rows.push((lines.len(), x, name.width() as u16, url.clone()));
+            }
+            lines.push(Line::from(vec![
+                Span::raw(if selected { "▶ " } else { "  " }),
+                Span::styled(symbol, Style::default().fg(color)),
+                Span::styled(name, name_style),
+                Span::styled(
+                    format!(" {}", truncate_str(&check.detail, detail_width)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+
+        // カーソル行が見えるようにスクロール
+        let inner_height = height.saturating_sub(2) as usize;
+        let scroll = (self.checks_cursor + 2).saturating_sub(inner_height) as u16;
+
+        let k = Style::default().fg(Color::Cyan);
+        let hint = Line::from(vec![
+            Span::styled(" Enter ", k),
+            Span::raw("Details  "),
+            Span::styled("r ", k),
+            Span::raw("Refresh  "),
+            Span::styled("Esc ", k),
+            Span::raw("Close "),
+        ])
+        .alignment(HorizontalAlignment::Right);
+        let mut title = match self.checks_badge() {
+            Some((badge, _)) => format!(" Checks{}", badge.trim_start_matches(" CI")),
+            None => " Checks ".to_string(),
+        };
+        if self.checks_loading && self.checks.is_some() {
+            title.push_str(tr("(refreshing) "));
+        }
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(title)
+                    .title_bottom(hint)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .scroll((scroll, 0));
+        frame.render_widget(paragraph, dialog);
+        self.link_dialog_rows(frame.buffer_mut(), dialog, scroll, link_rows);
+    }
+
+    /// ファイル検索ダイアログを描画する（入力行 + 候補、マッチした文字を強調）
+    fn render_file_finder_dialog(&self, frame: &mut Frame, area: Rect) {
+        let matches = self.file_finder_matches();
+        let width = (area.width * 4 / 5).max(area.width.min(HELP_DIALOG_WIDTH));
+        // ボーダー (2) + 入力行と余白 (3) + 候補
+        let height = (matches.len().max(1) as u16 + 5).min(area.height.saturating_sub(2));
+        let dialog = Self::centered_rect(width, height, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        // ボーダー (2) + カーソル (2)
+        let text_width = (width as usize).saturating_sub(4);
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(" > ", Style::default().fg(Color::Magenta)),
+                Span::raw(self.file_finder_input.as_str()),
+                Span::styled("█", Style::default().fg(Color::Magenta)),
+            ]),
+            Line::raw(""),
+        ];
+        if matches.is_empty() {
+            lines.push(Line::styled(
+                format!("  {}", tr("No matching files")),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        for (i, m) in matches.iter().enumerate() {
+            let selected = i == self.file_finder_cursor;
+            let commits = match m.commits {
+                0 => String::new(),
+                1 => " 1 commit".to_string(),
+                n => format!(" {n} commits"),
+            };
+            let name_width = text_width.saturating_sub(commits.width());
+            // 長いパスは末尾を省く（マッチ位置が先頭からの文字数なので、先頭は省かない）
+            let name = truncate_str(&m.filename, name_width);
+            let base = if selected {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            };
+            let highlight = base.fg(Color::Magenta).add_modifier(Modifier::BOLD);
+            let mut spans = vec![Span::raw(if selected { "▶ " } else { "  " })];
+            spans.extend(name.chars().enumerate().map(|(pos, ch)| {
+                let style = if m.positions.contains(&pos) {
+                    highlight
+                } else {
+                    base
+                };
+                Span::styled(ch.to_string(), style)
+            }));
+            spans.push(Span::styled(commits, Style::default().fg(Color::DarkGray)));
+            lines.push(Line::from(spans));
+        }
+
+        // カーソル行が見えるようにスクロール（入力行は固定せず、候補と一緒に流す）
+        let inner_height = height.saturating_sub(2) as usize;
+        let scroll = (self.file_finder_cursor + 3).saturating_sub(inner_height) as u16;
+
+        let k = Style::default().fg(Color::Cyan);
+        let hint = Line::from(vec![
+            Span::styled(" Enter ", k),
+            Span::raw("Open  "),
+            Span::styled("↑↓ ", k),
+            Span::raw("Select  "),
+            Span::styled("Esc ", k),
+            Span::raw("Close "),
+        ])
+        .alignment(HorizontalAlignment::Right);
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(format!(" Find file ({}) ", matches.len()))
+                    .title_bottom(hint)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Magenta)),
+            )
+            .scroll((scroll, 0));
+        frame.render_widget(paragraph, dialog);
+    }
+
+    /// メッセージ履歴ダイアログを描画する（新しい順）
+    fn render_status_history_dialog(&self, frame: &mut Frame, area: Rect) {
+        let width = (area.width * 4 / 5).max(area.width.min(HELP_DIALOG_WIDTH));
+        // ボーダー (2) + 上下余白 (2) + 項目
+        let height = (self.status_history.len() as u16 + 4).min(area.height.saturating_sub(2));
+        let dialog = Self::centered_rect(width, height, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        // ボーダー (2) + 左右余白 (2)
+        let text_width = (width as usize).saturating_sub(4);
+        let mut lines = vec![Line::raw("")];
+        for (level, text) in self.status_history_lines() {
+            let style = match level {
+                StatusLevel::Info => Style::default(),
+                StatusLevel::Error => Style::default().fg(Color::Red),
+            };
+            lines.push(Line::styled(
+                format!(" {}", truncate_str(&text, text_width)),
+                style,
+            ));
+        }
+
+        let k = Style::default().fg(Color::Cyan);
+        let hint = Line::from(vec![
+            Span::styled(" j/k ", k),
+            Span::raw("Scroll  "),
+            Span::styled("Esc ", k),
+            Span::raw("Close "),
+        ])
+        .alignment(HorizontalAlignment::Right);
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(format!(" Messages ({}) ", self.status_history.len()))
+                    .title_bottom(hint)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .scroll((self.status_history_scroll as u16, 0));
+        frame.render_widget(paragraph, dialog);
+    }
+
+    fn render_quit_confirm_dialog(&self, frame: &mut Frame, area: Rect) {
+        let dialog = Self::centered_rect(QUIT_DIALOG_WIDTH, QUIT_DIALOG_HEIGHT, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let lines = vec![
+            Line::raw(""),
+            Line::styled(
+                format!(
+                    "  {}",
+                    tr("{n} unsent comment(s).")
+                        .replace("{n}", &self.review.pending_comments.len().to_string())
+                ),
+                Style::default().fg(Color::Yellow),
+            ),
+            Line::styled(
+                format!("  {}", tr("Submit before quitting?")),
+                Style::default(),
+            ),
+            Line::raw(""),
+            Line::styled(
+                format!("  {}", tr("y: submit & quit")),
+                Style::default().fg(Color::Green),
+            ),
+            Line::styled(
+                format!("  {}", tr("n: discard & quit")),
+                Style::default().fg(Color::Red),
+            ),
+            Line::styled(
+                format!("  {}", tr("c: cancel")),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr(" Quit Confirmation "))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red)),
+        );
+        frame.render_widget(paragraph, dialog);
+    }
+
+    /// バックグラウンド処理の終了待ちダイアログを描画する
+    fn render_quit_wait_dialog(&self, frame: &mut Frame, area: Rect) {
+        let tasks = self.background_tasks();
+        // ボーダー (2) + 余白 (2) + 見出し (1) + 選択肢 (3) + 処理
+        let height = tasks.len().max(1) as u16 + 8;
+        let dialog = Self::centered_rect(QUIT_WAIT_DIALOG_WIDTH, height, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let mut lines = vec![
+            Line::raw(""),
+            Line::styled(
+                format!("  {}", tr("Still running:")),
+                Style::default().fg(Color::Yellow),
+            ),
+        ];
+        if tasks.is_empty() {
+            lines.push(Line::styled(
+                format!("  {}", tr("✓ All done")),
+                Style::default().fg(Color::Green),
+            ));
+        }
+        for task in tasks {
+            lines.push(Line::raw(format!("  • {}", tr(task))));
+        }
+        lines.push(Line::raw(""));
+        lines.push(if self.quit_when_idle {
+            Line::styled(
+                format!("  {}", tr("Waiting… quits when done")),
+                Style::default().fg(Color::Cyan),
+            )
+        } else {
+            Line::styled(
+                format!("  {}", tr("w: wait, then quit")),
+                Style::default().fg(Color::Green),
+            )
+        });
+        lines.push(Line::styled(
+            format!("  {}", tr("q: quit anyway")),
+            Style::default().fg(Color::Red),
+        ));
+        lines.push(Line::styled(
+            format!("  {}", tr("c: cancel")),
+            Style::default().fg(Color::DarkGray),
+        ));
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr(" Quit Confirmation "))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red)),
+        );
+        frame.render_widget(paragraph, dialog);
+    }
+
+    /// エディタ破棄確認ダイアログを描画する
+    fn render_discard_confirm_dialog(&self, frame: &mut Frame, area: Rect) {
+        let dialog = Self::centered_rect(DISCARD_DIALOG_WIDTH, DISCARD_DIALOG_HEIGHT, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let lines = vec![
+            Line::raw(""),
+            Line::styled(
+                format!("  {}", tr("d: discard")),
+                Style::default().fg(Color::Red),
+            ),
+            Line::styled(
+                format!("  {}", tr("k: keep editing")),
+                Style::default().fg(Color::Green),
+            ),
+            Line::styled(
+                format!("  {}", tr("s: save as draft")),
+                Style::default().fg(Color::Yellow),
+            ),
+        ];
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr(" Discard unsent text? "))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red)),
+        );
+        frame.render_widget(paragraph, dialog);
+    }
+
+    /// 画像添付のパス入力ダイアログを描画する
+    fn render_attach_prompt(&self, frame: &mut Frame, area: Rect) {
+        let dialog = Self::centered_rect(ATTACH_DIALOG_WIDTH, ATTACH_DIALOG_HEIGHT, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let input_width = (dialog.width as usize).saturating_sub(6);
+        let lines = vec![
+            Line::raw(""),
+            Line::raw(format!(
+                "  > {}█",
+                truncate_str(&self.attach_input, input_width)
+            )),
+            Line::styled(
+                format!("  {}", tr("Enter: attach (empty: clipboard) | Esc: cancel")),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr(" Attach image "))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green)),
+        );
+        frame.render_widget(paragraph, dialog);
+    }
+
+    /// ブランチ更新の方式選択ダイアログを描画する
+    fn render_update_branch_dialog(&self, frame: &mut Frame, area: Rect) {
+        let dialog = Self::centered_rect(
+            UPDATE_BRANCH_DIALOG_WIDTH,
+            UPDATE_BRANCH_DIALOG_HEIGHT,
+            area,
+        );
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let lines = vec![
+            Line::raw(""),
+            Line::styled(
+                format!("  {}", tr("Branch is behind base.")),
+                Style::default().fg(Color::Yellow),
+            ),
+            Line::raw(format!("  {} ← {}", self.head_label(), self.pr_base_branch)),
+            Line::raw(""),
+            Line::styled(
+                format!("  {}", tr("m: merge base into branch")),
+                Style::default().fg(Color::Green),
+            ),
+            Line::styled(
+                format!("  {}", tr("r: rebase onto base")),
+                Style::default().fg(Color::Green),
+            ),
+            Line::styled(
+                format!("  {}", tr("c: cancel")),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr(" Update Branch "))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red)),
+        );
+        frame.render_widget(paragraph, dialog);
+    }
+
+    /// 自分のコメントの削除確認ダイアログを描画する（本文の先頭行を添える）
+    fn render_comment_delete_dialog(&self, frame: &mut Frame, area: Rect) {
+        let dialog = Self::centered_rect(
+            COMMENT_DELETE_DIALOG_WIDTH,
+            COMMENT_DELETE_DIALOG_HEIGHT,
+            area,
+        );
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let preview_width = COMMENT_DELETE_DIALOG_WIDTH.saturating_sub(4) as usize;
+        let preview = self
+            .editing_comment()
+            .and_then(|c| c.body.lines().find(|l| !l.trim().is_empty()))
+            .map(|line| truncate_str(line, preview_width))
+            .unwrap_or_default();
+        let lines = vec![
+            Line::raw(""),
+            Line::styled(
+                format!("  {}", tr("Delete your comment?")),
+                Style::default().fg(Color::Yellow),
+            ),
+            Line::styled(format!("  {preview}"), Style::default().fg(Color::DarkGray)),
+            Line::styled(
+                format!("  {}", tr("y: delete")),
+                Style::default().fg(Color::Red),
+            ),
+            Line::styled(
+                format!("  {}", tr("c: cancel")),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr(" Delete Comment "))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red)),
+        );
+        frame.render_widget(paragraph, dialog);
+    }
+
+    /// PR を閉じる・開き直す確認ダイアログを描画する
+    fn render_pr_state_change_dialog(&self, frame: &mut Frame, area: Rect) {
+        let dialog = Self::centered_rect(PR_STATE_DIALOG_WIDTH, PR_STATE_DIALOG_HEIGHT, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let (title, question, confirm) = match self.pr_state_change() {
+            PrStateChange::Close => (" Close PR ", "Close this pull request?", "y: close PR"),
+            PrStateChange::Reopen => (" Reopen PR ", "Reopen this pull request?", "y: reopen PR"),
+        };
+        let title_width = PR_STATE_DIALOG_WIDTH.saturating_sub(4) as usize;
+        let mut lines = vec![
+            Line::raw(""),
+            Line::styled(
+                format!("  {}", tr(question)),
+                Style::default().fg(Color::Yellow),
+            ),
+            Line::raw(format!(
+                "  {}",
+                truncate_str(
+                    &format!("#{} {}", self.pr_number, self.pr_title),
+                    title_width
+                )
+            )),
+        ];
+        // 閉じても送信待ちのコメントは残るが、送り忘れに気づけるよう添える
+        let pending = self.review.pending_comments.len();
+        if pending > 0 {
+            lines.push(Line::styled(
+                format!("  {} {pending}", tr("Unsent pending comments:")),
+                Style::default().fg(Color::Red),
+            ));
+        } else {
+            lines.push(Line::raw(""));
+        }
+        lines.push(Line::styled(
+            format!("  {}", tr(confirm)),
+            Style::default().fg(Color::Green),
+        ));
+        lines.push(Line::styled(
+            format!("  {}", tr("c: cancel")),
+            Style::default().fg(Color::DarkGray),
+        ));
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr(title))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red)),
+        );
+        frame.render_widget(paragraph, dialog);
+    }
+
+    /// ローカルへのチェックアウトの確認ダイアログを描画する
+    fn render_checkout_dialog(&self, frame: &mut Frame, area: Rect) {
+        let dialog = Self::centered_rect(CHECKOUT_DIALOG_WIDTH, CHECKOUT_DIALOG_HEIGHT, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let width = CHECKOUT_DIALOG_WIDTH.saturating_sub(4) as usize;
+        let lines = vec![
+            Line::raw(""),
+            Line::styled(
+                format!("  {}", tr("Check out this PR locally?")),
+                Style::default().fg(Color::Yellow),
+            ),
+            Line::raw(format!(
+                "  {}",
+                truncate_str(&format!("#{} {}", self.pr_number, self.pr_title), width)
+            )),
+            Line::styled(
+                format!(
+                    "  {}",
+                    truncate_str(&format!("→ {}", self.checkout_branch_label()), width)
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Line::styled(
+                format!("  {}", tr("y: checkout")),
+                Style::default().fg(Color::Green),
+            ),
+            Line::styled(
+                format!("  {}", tr("c: cancel")),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr(" Checkout "))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red)),
+        );
+        frame.render_widget(paragraph, dialog);
+    }
+
+    /// 外部プログラムの実行確認ダイアログを描画する
+    fn render_allow_command_dialog(&self, frame: &mut Frame, area: Rect) {
+        let dialog = Self::centered_rect(
+            ALLOW_COMMAND_DIALOG_WIDTH,
+            ALLOW_COMMAND_DIALOG_HEIGHT,
+            area,
+        );
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let width = ALLOW_COMMAND_DIALOG_WIDTH.saturating_sub(4) as usize;
+        let lines = vec![
+            Line::raw(""),
+            Line::styled(
+                format!("  {}", tr("Allow prism to run this program?")),
+                Style::default().fg(Color::Yellow),
+            ),
+            Line::raw(format!(
+                "  {}",
+                truncate_str(&format!("`{}`", self.allow_command), width)
+            )),
+            Line::styled(
+                format!("  {}", tr("y: this session")),
+                Style::default().fg(Color::Green),
+            ),
+            Line::styled(
+                format!("  {}", tr("a: always (saved to allowed_commands)")),
+                Style::default().fg(Color::Green),
+            ),
+            Line::styled(
+                format!("  {}", tr("n: not in this session")),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr(" Run external program "))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+        frame.render_widget(paragraph, dialog);
+    }
+
+    /// 自動マージの設定ダイアログを描画する
+    fn render_auto_merge_dialog(&self, frame: &mut Frame, area: Rect) {
+        let dialog = Self::centered_rect(AUTO_MERGE_DIALOG_WIDTH, AUTO_MERGE_DIALOG_HEIGHT, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let current = match self.auto_merge {
+            Some(method) => format!("{} ({})", tr("Auto-merge is enabled"), method.label()),
+            None => tr("Auto-merge is disabled").to_string(),
+        };
+        let mut lines = vec![
+            Line::raw(""),
+            Line::styled(format!("  {current}"), Style::default().fg(Color::Yellow)),
+            Line::raw(""),
+            Line::styled(
+                format!("  {}", tr("m: merge commit")),
+                Style::default().fg(Color::Green),
+            ),
+            Line::styled(
+                format!("  {}", tr("s: squash and merge")),
+                Style::default().fg(Color::Green),
+            ),
+            Line::styled(
+                format!("  {}", tr("r: rebase and merge")),
+                Style::default().fg(Color::Green),
+            ),
+        ];
+        if self.auto_merge.is_some() {
+            lines.push(Line::styled(
+                format!("  {}", tr("d: disable auto-merge")),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        lines.push(Line::styled(
+            format!("  {}", tr("c: cancel")),
+            Style::default().fg(Color::DarkGray),
+        ));
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr(" Auto-merge "))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+        frame.render_widget(paragraph, dialog);
+    }
+
+    /// ヘルプダイアログを描画する（幅に応じて複数列に流し込み、/ で絞り込み）
+    fn render_help_dialog(&mut self, frame: &mut Frame, area: Rect) {
+        let dialog_height = (area.height * 2 / 3)
+            .max(HELP_DIALOG_MIN_HEIGHT)
+            .min(area.height.saturating_sub(4));
+        let max_width = area.width.saturating_sub(4);
+        let columns = (max_width / HELP_DIALOG_WIDTH).clamp(1, HELP_MAX_COLUMNS);
+        let dialog_width = (HELP_DIALOG_WIDTH * columns).min(max_width);
+        let dialog = Self::centered_rect(dialog_width, dialog_height, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let s = Style::default().fg(Color::Yellow); // section header
+        let k = Style::default().fg(Color::Cyan); // key
+        let d = Style::default(); // description
+        let column_width = (dialog_width.saturating_sub(2) / columns) as usize;
+        // 狭い端末ではキー列を縮めて説明を優先する
+        let key_width = HELP_KEY_COLUMN_WIDTH.min(column_width / 2);
+        // インデント (2) + 余白 (2) を引いた幅でセパレータ生成
+        let sep = format!("  {}", "─".repeat(column_width.saturating_sub(4)));
+
+        let panel = self.help_context_panel;
+        let sections = help::help_sections(panel, &self.help_filter, self.keymap);
+
+        // セクション単位の行ブロック
+        let blocks: Vec<Vec<Line>> = sections
+            .iter()
+            .map(|section| {
+                let mut lines = vec![
+                    Line::raw(""),
+                    Line::styled(format!("  {}", tr(section.title)), s),
+                    Line::styled(sep.clone(), s),
+                ];
+                for (key, desc) in &section.entries {
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("  {key:<key_width$}"), k),
+                        Span::styled(tr(desc), d),
+                    ]));
+                }
+                lines
+            })
+            .collect();
+
+        // 列の高さが揃うようにセクションを順に割り振る（セクションは列をまたがない）
+        let total: usize = blocks.iter().map(Vec::len).sum();
+        let target = total.div_ceil(columns as usize);
+        let mut cols: Vec<Vec<Line>> = vec![Vec::new()];
+        for block in blocks {
+            let col_count = cols.len();
+            let current = cols.last_mut().expect("cols is never empty");
+            if !current.is_empty()
+                && current.len() + block.len() > target
+                && col_count < columns as usize
+            {
+                cols.push(block);
+            } else {
+                current.extend(block);
+            }
+        }
+        if total == 0 {
+            cols[0].push(Line::raw(""));
+            cols[0].push(Line::styled(
+                format!("  {}", tr("No matching keys")),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+
+        // コンテンツ末尾を超えてスクロールしないようにクランプ
+        let content_height = cols.iter().map(Vec::len).max().unwrap_or(0) as u16;
+        let inner_height = dialog_height.saturating_sub(2); // ボーダー上下分
+        let max_scroll = content_height.saturating_sub(inner_height);
+        let scroll = self.help_scroll.min(max_scroll);
+        self.help_view_height = inner_height;
+        // 内部状態も同期して、スクロールアップ時のラグを防ぐ
+        self.help_scroll = scroll;
+
+        let title = if self.help_filter_input {
+            format!(" Help /{}█ ", self.help_filter)
+        } else if !self.help_filter.is_empty() {
+            format!(" Help /{} ", self.help_filter)
+        } else {
+            format!(" Help ({panel}) ")
+        };
+        let block = Block::default()
+            .title(title)
+            .title_bottom(Line::styled(
+                format!(" {}  {} ", tr("/: filter"), tr("?/Esc/q: close")),
+                Style::default().fg(Color::DarkGray),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+        let inner = block.inner(dialog);
+        frame.render_widget(block, dialog);
+
+        let column_areas = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, columns as u32); columns as usize])
+            .split(inner);
+        for (lines, column_area) in cols.into_iter().zip(column_areas.iter()) {
+            frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), *column_area);
+        }
+    }
+
+    /// コマンド出力オーバーレイを描画する
+    fn render_command_output_overlay(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(result) = &self.command_result else {
+            return;
+        };
+        let dialog_width = (area.width * 4 / 5).max(area.width.min(HELP_DIALOG_WIDTH));
+        let dialog_height = (area.height * 4 / 5).max(area.height.min(HELP_DIALOG_MIN_HEIGHT));
+        let dialog = Self::centered_rect(dialog_width, dialog_height, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        // ANSI カラー付き出力はそのまま色付けして表示
+        let mut text = crate::git::diff::ansi_to_text(&result.output)
+            .unwrap_or_else(|_| Text::raw(result.output.clone()));
+        if let Some(warning) = &result.warning {
+            text.lines.insert(
+                0,
+                Line::styled(warning.clone(), Style::default().fg(Color::Yellow)),
+            );
+        }
+
+        let inner_height = dialog_height.saturating_sub(2);
+        let max_scroll = (text.lines.len() as u16).saturating_sub(inner_height);
+        let scroll = if self.command_output_follow {
+            max_scroll
+        } else {
+            self.command_output_scroll.min(max_scroll)
+        };
+        self.command_output_scroll = scroll;
+
+        let (status, status_color) = match result.exit_code {
+            _ if result.running => ("running…".to_string(), Color::Yellow),
+            Some(0) => ("exit 0".to_string(), Color::Green),
+            Some(code) => (format!("exit {code}"), Color::Red),
+            None => ("killed by signal".to_string(), Color::Red),
+        };
+        let running = result.running;
+        let title = Line::from(vec![
+            Span::raw(format!(" $ {} ", result.command)),
+            Span::styled(format!("[{status}] "), Style::default().fg(status_color)),
+        ]);
+        let k = Style::default().fg(Color::Cyan);
+        let mut hint_spans = vec![
+            Span::styled(" j/k ", k),
+            Span::raw("Scroll  "),
+            Span::styled("r ", k),
+            Span::raw("Rerun  "),
+        ];
+        if running {
+            hint_spans.extend([Span::styled("x ", k), Span::raw("Stop  ")]);
+        }
+        hint_spans.extend([Span::styled("Esc ", k), Span::raw("Close ")]);
+        let hint = Line::from(hint_spans).alignment(HorizontalAlignment::Right);
+
+        let paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(title)
+                    .title_bottom(hint)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(status_color)),
+            )
+            .scroll((scroll, 0));
+        frame.render_widget(paragraph, dialog);
+    }
+
+    /// 2コミット間のファイル比較オーバーレイを描画する
+    fn render_file_compare_overlay(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(view) = &self.file_compare else {
+            return;
+        };
+        let dialog_width = (area.width * 4 / 5).max(area.width.min(HELP_DIALOG_WIDTH));
+        let dialog_height = (area.height * 4 / 5).max(area.height.min(HELP_DIALOG_MIN_HEIGHT));
+        let dialog = Self::centered_rect(dialog_width, dialog_height, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let inner_height = dialog_height.saturating_sub(2);
+        let max_scroll = (view.text.lines.len() as u16).saturating_sub(inner_height);
+        let scroll = self.file_compare_scroll.min(max_scroll);
+        self.file_compare_scroll = scroll;
+
+        let title = Line::from(vec![
+            Span::raw(format!(
+                " {} {}..{} ",
+                truncate_path(&view.filename, 40),
+                crate::format::short_sha(&view.from_sha),
+                crate::format::short_sha(&view.to_sha)
+            )),
+            Span::styled(
+                format!("+{} ", view.additions),
+                Style::default().fg(Color::Green),
+            ),
+            Span::styled(
+                format!("-{} ", view.deletions),
+                Style::default().fg(Color::Red),
+            ),
+        ]);
+        let k = Style::default().fg(Color::Cyan);
+        let hint = Line::from(vec![
+            Span::styled(" j/k ", k),
+            Span::raw("Scroll  "),
+            Span::styled("g/G ", k),
+            Span::raw("Top/Bottom  "),
+            Span::styled("Esc ", k),
+            Span::raw("Close "),
+        ])
+        .alignment(HorizontalAlignment::Right);
+
+        let paragraph = Paragraph::new(view.text.clone())
+            .block(
+                Block::default()
+                    .title(title)
+                    .title_bottom(hint)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .scroll((scroll, 0));
+        frame.render_widget(paragraph, dialog);
+    }
+
+    /// ファイル履歴オーバーレイを描画する（表示中コミットのパッチ）
+    fn render_file_history_overlay(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(view) = &self.file_history else {
+            return;
+        };
+        let dialog_width = (area.width * 4 / 5).max(area.width.min(HELP_DIALOG_WIDTH));
+        let dialog_height = (area.height * 4 / 5).max(area.height.min(HELP_DIALOG_MIN_HEIGHT));
+        let dialog = Self::centered_rect(dialog_width, dialog_height, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let inner_height = dialog_height.saturating_sub(2);
+        let max_scroll = (view.text.lines.len() as u16).saturating_sub(inner_height);
+        let scroll = view.scroll.min(max_scroll);
+
+        let commit = view
+            .commits
+            .get(view.index)
+            .and_then(|&i| self.commits.get(i));
+        let file = commit
+            .and_then(|c| self.files_map.get(&c.sha))
+            .and_then(|files| files.iter().find(|f| f.filename == view.filename));
+        let mut title = vec![Span::raw(format!(
+            " {} [{}/{}] {} {} ",
+            truncate_path(&view.filename, 40),
+            view.index + 1,
+            view.commits.len(),
+            commit.map(|c| c.short_sha()).unwrap_or_default(),
+            truncate_str(commit.map_or("", |c| c.message_summary()), 40),
+        ))];
+        if let Some(file) = file {
+            title.push(Span::styled(
+                format!("+{} ", file.additions),
+                Style::default().fg(Color::Green),
+            ));
+            title.push(Span::styled(
+                format!("-{} ", file.deletions),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        let k = Style::default().fg(Color::Cyan);
+        let hint = Line::from(vec![
+            Span::styled(" n/N ", k),
+            Span::raw("Next/Prev commit  "),
+            Span::styled("j/k ", k),
+            Span::raw("Scroll  "),
+            Span::styled("Enter ", k),
+            Span::raw("Open diff  "),
+            Span::styled("Esc ", k),
+            Span::raw("Close "),
+        ])
+        .alignment(HorizontalAlignment::Right);
+
+        let paragraph = Paragraph::new(view.text.clone())
+            .block(
+                Block::default()
+                    .title(Line::from(title))
+                    .title_bottom(hint)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .scroll((scroll, 0));
+        frame.render_widget(paragraph, dialog);
+        if let Some(view) = &mut self.file_history {
+            view.scroll = scroll;
+        }
+    }
+
+    /// 大きな PR の概要オーバーレイを描画する（トップレベルディレクトリごとの変更量）
+    fn render_large_pr_summary_overlay(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(summary) = &self.large_pr_summary else {
+            return;
+        };
+        let dialog_width = (area.width * 4 / 5).max(area.width.min(HELP_DIALOG_WIDTH));
+        let dialog_height = (area.height * 4 / 5).max(area.height.min(HELP_DIALOG_MIN_HEIGHT));
+        let dialog = Self::centered_rect(dialog_width, dialog_height, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let rows = summary.rows();
+        let lines: Vec<Line> = rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut spans = match row {
+                    SummaryRow::Dir {
+                        name,
+                        files,
+                        additions,
+                        deletions,
+                        expanded,
+                    } => vec![
+                        Span::raw(format!(
+                            "{} {name}/ ({files}) ",
+                            if *expanded { "▾" } else { "▸" }
+                        )),
+                        Span::styled(format!("+{additions} "), Style::default().fg(Color::Green)),
+                        Span::styled(format!("-{deletions}"), Style::default().fg(Color::Red)),
+                    ],
+                    SummaryRow::File(index) => {
+                        let file = &summary.files[*index];
+                        vec![
+                            Span::raw(format!("    {} ", truncate_path(&file.filename, 60))),
+                            Span::styled(
+                                format!("+{} ", file.additions),
+                                Style::default().fg(Color::Green),
+                            ),
+                            Span::styled(
+                                format!("-{}", file.deletions),
+                                Style::default().fg(Color::Red),
+                            ),
+                        ]
+                    }
+                };
+                if i == summary.cursor {
+                    spans = spans
+                        .into_iter()
+                        .map(|s| s.patch_style(Style::default().add_modifier(Modifier::REVERSED)))
+                        .collect();
+                }
+                Line::from(spans)
+            })
+            .collect();
+
+        // カーソル行が見えるようにスクロール
+        let inner_height = dialog_height.saturating_sub(2);
+        let cursor = summary.cursor as u16;
+        let mut scroll = summary.scroll;
+        if cursor < scroll {
+            scroll = cursor;
+        } else if inner_height > 0 && cursor >= scroll + inner_height {
+            scroll = cursor + 1 - inner_height;
+        }
+
+        let (additions, deletions) = summary
+            .files
+            .iter()
+            .fold((0, 0), |(a, d), f| (a + f.additions, d + f.deletions));
+        let title = format!(
+            " {} ({} files, +{additions} -{deletions}) ",
+            tr("Large PR summary"),
+            summary.files.len()
+        );
+        let k = Style::default().fg(Color::Cyan);
+        let hint = Line::from(vec![
+            Span::styled(" j/k ", k),
+            Span::raw("Move  "),
+            Span::styled("Enter ", k),
+            Span::raw("Expand/Open  "),
+            Span::styled("h ", k),
+            Span::raw("Collapse  "),
+            Span::styled("Esc ", k),
+            Span::raw("Close "),
+        ])
+        .alignment(HorizontalAlignment::Right);
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(title)
+                    .title_bottom(hint)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .scroll((scroll, 0));
+        frame.render_widget(paragraph, dialog);
+        if let Some(summary) = &mut self.large_pr_summary {
+            summary.scroll = scroll;
+        }
+    }
+
+    /// 保留コメントのプレビューを描画する（コメント対象のコードと本文）
+    fn render_pending_comments_overlay(&mut self, frame: &mut Frame, area: Rect) {
+        let dialog_width = (area.width * 4 / 5).max(area.width.min(HELP_DIALOG_WIDTH));
+        let dialog_height = (area.height * 4 / 5).max(area.height.min(HELP_DIALOG_MIN_HEIGHT));
+        let dialog = Self::centered_rect(dialog_width, dialog_height, area);
+        Self::clear_wide_safe(frame, dialog, area);
+
+        let (lines, starts) = self.pending_comment_preview();
+        let inner_height = dialog_height.saturating_sub(2);
+        let max_scroll = (lines.len() as u16).saturating_sub(inner_height);
+        // 選択中のコメントの先頭行が見えるようにスクロールする
+        let mut scroll = self.pending_comments_scroll;
+        if let Some(&start) = starts.get(self.pending_cursor) {
+            let start = start as u16;
+            if start < scroll {
+                scroll = start;
+            } else if start >= scroll + inner_height {
+                scroll = start + 1 - inner_height;
+            }
+        }
+        let scroll = scroll.min(max_scroll);
+        self.pending_comments_scroll = scroll;
+
+        let k = Style::default().fg(Color::Cyan);
+        let hint = match &self.pending_header_input {
+            Some(input) => Line::from(vec![
+                Span::styled(format!(" {}: ", tr("Header")), k),
+                Span::raw(format!("{input}█ ")),
+            ]),
+            None => Line::from(vec![
+                Span::styled(" j/k ", k),
+                Span::raw("Select  "),
+                Span::styled("J/K ", k),
+                Span::raw("Move  "),
+                Span::styled("h ", k),
+                Span::raw("Header  "),
+                Span::styled("Esc ", k),
+                Span::raw("Close "),
+            ]),
+        }
+        .alignment(HorizontalAlignment::Right);
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(format!(
+                        " {} ({}) ",
+                        tr("Pending Comments"),
+                        self.review.pending_comments.len()
+                    ))
+                    .title_bottom(hint)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
             )
             .scroll((scroll, 0));
         frame.render_widget(paragraph, dialog);
@@ -1989,11 +4163,18 @@ impl App {
             Span::raw("Navigate  "),
             Span::styled("o ", k),
             Span::raw("Open in browser  "),
+            Span::styled("c ", k),
+            Span::raw("Comment  "),
             Span::styled("Esc ", k),
             Span::raw("Close "),
         ])
         .alignment(HorizontalAlignment::Right);
 
+        // 見出しを表示中のメディアの URL へのリンクにする
+        let title_link = current.map(|r| {
+            let width = (title.width() as u16).min(area.width.saturating_sub(2));
+            (Rect::new(area.x + 1, area.y, width, 1), r.url.clone())
+        });
         let block = Block::default()
             .title(title)
             .title_bottom(hint)
@@ -2001,6 +4182,10 @@ impl App {
             .border_style(Style::default().fg(Color::Cyan));
         let inner = block.inner(area);
         frame.render_widget(block, area);
+        let title_url = title_link.map(|(title_area, url)| {
+            self.link_area(frame.buffer_mut(), title_area, url.clone());
+            url
+        });
 
         let content_area = inner;
 
@@ -2013,7 +4198,7 @@ impl App {
             .alignment(Alignment::Center);
             let centered = Self::centered_rect(45, 3, content_area);
             frame.render_widget(msg, centered);
-        } else if let Some(url) = current.map(|r| r.url.clone()) {
+        } else if let Some(url) = title_url {
             if let Some(protocol) = self.media_protocol_cache.get_mut(&url) {
                 let widget = StatefulImage::default();
                 frame.render_stateful_widget(widget, content_area, protocol);
@@ -0,0 +1,115 @@
+//! 読み込み済みの PR をレビューレポート（Markdown / HTML）としてファイルに書き出す
+
+use super::*;
+use crate::github::export::{self, ReportFormat, ReportPending, ReportThread, ReviewReport};
+
+/// 行範囲の表示（"L10" / "L10–L12"）
+fn line_range_label(start: usize, end: usize) -> String {
+    if start == end {
+        format!("L{end}")
+    } else {
+        format!("L{start}–L{end}")
+    }
+}
+
+impl App {
+    /// レポートの出力形式をセットする（--export-format）
+    pub fn set_report_format(&mut self, format: ReportFormat) {
+        self.report_format = format;
+    }
+
+    /// レビュースレッド（ルートコメントの順に、返信を続ける）
+    fn report_threads(&self) -> Vec<ReportThread<'_>> {
+        let comments = &self.review.review_comments;
+        comments
+            .iter()
+            .filter(|c| c.in_reply_to_id.is_none())
+            .map(|root| {
+                let lines = root
+                    .line_range_label()
+                    .unwrap_or_else(|| "outdated".to_string());
+                let mut thread: Vec<&ReviewComment> = vec![root];
+                thread.extend(
+                    comments
+                        .iter()
+                        .filter(|c| c.in_reply_to_id == Some(root.id)),
+                );
+                ReportThread {
+                    path: &root.path,
+                    lines,
+                    resolved: self
+                        .review
+                        .thread_map
+                        .get(&root.id)
+                        .is_some_and(|t| t.is_resolved),
+                    comments: thread,
+                }
+            })
+            .collect()
+    }
+
+    /// 保留コメント（diff 上の位置を実ファイルの行番号に直す）
+    fn report_pending(&self) -> Vec<ReportPending<'_>> {
+        self.review
+            .pending_comments
+            .iter()
+            .map(|pc| {
+                let line_map = self
+                    .files_for(&pc.commit_sha)
+                    .and_then(|files| files.iter().find(|f| f.filename == pc.file_path))
+                    .and_then(|f| f.patch.as_deref())
+                    .map(review::parse_patch_line_map)
+                    .unwrap_or_default();
+                let file_line = |idx: usize| line_map.get(idx).copied().flatten();
+                let lines = match (file_line(pc.start_line), file_line(pc.end_line)) {
+                    (Some(start), Some(end)) => line_range_label(start.file_line, end.file_line),
+                    _ => String::new(),
+                };
+                ReportPending {
+                    path: &pc.file_path,
+                    lines,
+                    commit: &pc.commit_sha,
+                    body: &pc.body,
+                }
+            })
+            .collect()
+    }
+
+    /// レポートの本文を組み立てる
+    pub(super) fn review_report(&self) -> String {
+        let report = ReviewReport {
+            repo: &self.repo,
+            pr_number: self.pr_number,
+            title: &self.pr_title,
+            author: &self.pr_author,
+            state: &self.pr_state,
+            base_branch: &self.pr_base_branch,
+            head_branch: &self.pr_head_branch,
+            body: &self.pr_body,
+            commits: &self.commits,
+            files_map: &self.files_map,
+            threads: self.report_threads(),
+            pending: self.report_pending(),
+        };
+        export::render_report(&report, self.report_format)
+    }
+
+    /// X: レビューレポートをカレントディレクトリに書き出す
+    pub(super) fn export_report(&mut self) {
+        if self.is_async_loading() {
+            self.status_message = Some(StatusMessage::error(
+                "✗ Initial loading in progress. Please wait.",
+            ));
+            return;
+        }
+        let path = format!(
+            "prism-review-pr{}.{}",
+            self.pr_number,
+            self.report_format.extension()
+        );
+        self.status_message = Some(match std::fs::write(&path, self.review_report()) {
+            Ok(()) => StatusMessage::info(format!("✓ Exported review report to {path}")),
+            Err(e) => StatusMessage::error(format!("✗ Failed to export report: {e}")),
+        });
+    }
+}
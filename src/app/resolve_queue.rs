@@ -0,0 +1,154 @@
+//! 送信できなかったスレッドの resolve / unresolve（画面には先に反映し、接続が戻ったら再送する）
+
+use super::*;
+use crate::github::cache::{self, QueuedResolve, ResolveQueueCache};
+
+impl App {
+    /// 保存済みの再送待ち操作をセットし、画面に反映して再送を予約する
+    pub fn set_resolve_queue(&mut self, saved: Option<ResolveQueueCache>) {
+        self.resolve_queue = saved.unwrap_or_default().actions;
+        self.apply_resolve_queue();
+        self.needs_resolve_replay = !self.resolve_queue.is_empty();
+    }
+
+    /// 再送待ちの操作をスレッドの状態に反映する（PR データの再取得で上書きされた後にも呼ぶ）
+    pub(super) fn apply_resolve_queue(&mut self) {
+        for action in self.resolve_queue.clone() {
+            self.set_thread_resolved(
+                action.root_comment_id,
+                &action.thread_node_id,
+                action.resolve,
+            );
+        }
+    }
+
+    /// thread_map と conversation のスレッドの resolve 状態を更新する
+    pub(super) fn set_thread_resolved(
+        &mut self,
+        root_comment_id: u64,
+        thread_node_id: &str,
+        resolved: bool,
+    ) {
+        if let Some(thread) = self.review.thread_map.get_mut(&root_comment_id) {
+            thread.is_resolved = resolved;
+        }
+        for entry in &mut self.conversation {
+            if let ConversationKind::CodeComment {
+                ref mut is_resolved,
+                thread_node_id: ref node_id,
+                ..
+            } = entry.kind
+                && node_id.as_deref() == Some(thread_node_id)
+            {
+                *is_resolved = resolved;
+            }
+        }
+        self.conversation_rendered = None; // キャッシュ無効化
+    }
+
+    /// 送信に失敗した resolve 操作をキューに積み、画面には先に反映する
+    pub(super) fn queue_resolve(&mut self, req: ResolveToggleRequest) {
+        // 同じスレッドへの操作は最後のものだけ残す
+        self.resolve_queue
+            .retain(|a| a.root_comment_id != req.root_comment_id);
+        self.resolve_queue.push(QueuedResolve {
+            thread_node_id: req.thread_node_id.clone(),
+            root_comment_id: req.root_comment_id,
+            resolve: req.should_resolve,
+        });
+        self.set_thread_resolved(req.root_comment_id, &req.thread_node_id, req.should_resolve);
+        if self.save_resolve_queue() {
+            self.status_message = Some(StatusMessage::error(format!(
+                "⚠ Offline: thread {} queued ({} pending)",
+                if req.should_resolve {
+                    "resolve"
+                } else {
+                    "unresolve"
+                },
+                self.resolve_queue.len()
+            )));
+        }
+    }
+
+    /// 再送待ちの操作を順に送る。一時的な失敗ならそこで止めて残りを次の機会に回し、
+    /// 権限不足・スレッド削除などの失敗はキューから外して表示を戻す（draw 後に呼ばれる）
+    pub(super) fn execute_resolve_replay(&mut self) {
+        let mut sent = 0;
+        let mut rejected = None;
+        while let Some(action) = self.resolve_queue.first().cloned() {
+            let result = if action.resolve {
+                comments::resolve_review_thread(&action.thread_node_id)
+            } else {
+                comments::unresolve_review_thread(&action.thread_node_id)
+            };
+            match result {
+                Ok(is_resolved) => {
+                    // 実際の状態に合わせる（他の人が先に変えていた場合など）
+                    self.set_thread_resolved(
+                        action.root_comment_id,
+                        &action.thread_node_id,
+                        is_resolved,
+                    );
+                    self.resolve_queue.remove(0);
+                    sent += 1;
+                }
+                Err(e) if crate::github::retry::is_transient(&e.to_string()) => break,
+                Err(e) => {
+                    self.drop_queued_resolve(0);
+                    rejected = Some(e);
+                }
+            }
+        }
+        if (sent == 0 && rejected.is_none()) || !self.save_resolve_queue() {
+            return;
+        }
+        self.status_message = Some(match rejected {
+            Some(e) => StatusMessage::error(format!("✗ Queued thread action rejected: {e}")),
+            None => StatusMessage::info(format!(
+                "✓ Synced {sent} queued thread action{}",
+                if sent == 1 { "" } else { "s" }
+            )),
+        });
+    }
+
+    /// 再送できない操作をキューから外し、先に反映していたスレッドの状態を戻す
+    pub(super) fn drop_queued_resolve(&mut self, index: usize) {
+        if index >= self.resolve_queue.len() {
+            return;
+        }
+        let action = self.resolve_queue.remove(index);
+        self.set_thread_resolved(
+            action.root_comment_id,
+            &action.thread_node_id,
+            !action.resolve,
+        );
+    }
+
+    /// 再送待ちの操作を保存する（失敗時はステータスバーに表示して false）
+    fn save_resolve_queue(&mut self) -> bool {
+        let Some((owner, repo)) = self.parse_repo() else {
+            return false;
+        };
+        let result = if self.resolve_queue.is_empty() {
+            cache::remove_pr_json(owner, repo, self.pr_number, cache::RESOLVE_QUEUE_SUFFIX)
+        } else {
+            let data = ResolveQueueCache {
+                actions: self.resolve_queue.clone(),
+            };
+            cache::write_pr_json(
+                owner,
+                repo,
+                self.pr_number,
+                cache::RESOLVE_QUEUE_SUFFIX,
+                &data,
+            )
+        };
+        if let Err(e) = result {
+            self.status_message = Some(StatusMessage::error(format!(
+                "✗ Failed to save queued resolves: {e}"
+            )));
+            return false;
+        }
+        true
+    }
+}
@@ -0,0 +1,34 @@
+//! レビュー本文のテンプレート（送信イベントごとに設定した本文の初期値）
+
+use super::*;
+use crate::config::ReviewTemplates;
+
+impl App {
+    /// レビュー本文のテンプレートをセットする
+    pub fn set_review_templates(&mut self, templates: ReviewTemplates) {
+        self.review_templates = templates;
+    }
+
+    /// イベントのテンプレート
+    fn review_template(&self, event: ReviewEvent) -> &str {
+        match event {
+            ReviewEvent::Comment => &self.review_templates.comment,
+            ReviewEvent::Approve => &self.review_templates.approve,
+            ReviewEvent::RequestChanges => &self.review_templates.request_changes,
+        }
+    }
+
+    /// レビュー本文の入力を始める（下書きがなければイベントのテンプレートを入れる）
+    pub(super) fn enter_review_body_input(&mut self, event: ReviewEvent) {
+        self.review.review_body_editor.clear();
+        self.mode = AppMode::ReviewBodyInput;
+        self.restore_draft();
+        if self.review.review_body_editor.is_empty() {
+            let template = self.review_template(event).to_string();
+            self.review.review_body_editor.insert_text(&template);
+            self.review
+                .review_body_editor
+                .ensure_visible(editor::EDITOR_VISIBLE_HEIGHT);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//! diff 内検索（大文字小文字・単語単位・正規表現の切替とマッチ箇所の強調）
+
+use super::*;
+use regex::{Regex, RegexBuilder};
+
+/// マッチ箇所の強調（行全体の背景色は Buffer 側で上書きされるため反転で示す）
+const MATCH_MODIFIER: Modifier = Modifier::REVERSED.union(Modifier::BOLD);
+
+/// クエリとオプションから検索用の正規表現を組み立てる
+pub(super) fn build_search_regex(query: &str, options: SearchOptions) -> Result<Regex, String> {
+    let pattern = if options.regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+    let pattern = if options.whole_word {
+        format!(r"\b(?:{pattern})\b")
+    } else {
+        pattern
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!options.case_sensitive)
+        .build()
+        .map_err(|e| match e {
+            // 構文エラーはパターンとキャレットを含む複数行なので最後の説明だけ使う
+            regex::Error::Syntax(msg) => msg
+                .lines()
+                .rev()
+                .find(|l| !l.trim().is_empty())
+                .unwrap_or_default()
+                .trim_start_matches("error: ")
+                .to_string(),
+            other => other.to_string(),
+        })
+}
+
+/// 有効なオプションの表示（"Aa W .*"）。何も有効でなければ空
+pub(super) fn search_options_label(options: SearchOptions) -> String {
+    [
+        (options.case_sensitive, "Aa"),
+        (options.whole_word, "W"),
+        (options.regex, ".*"),
+    ]
+    .iter()
+    .filter(|(on, _)| *on)
+    .map(|(_, label)| *label)
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// 行の中で `ranges`（バイト位置）にあたる部分を強調する
+fn emphasize_ranges(line: &mut Line<'static>, ranges: &[std::ops::Range<usize>]) {
+    let mut spans = Vec::with_capacity(line.spans.len());
+    let mut offset = 0;
+    for span in line.spans.drain(..) {
+        let content = span.content.to_string();
+        let end = offset + content.len();
+        let mut cuts = vec![0, content.len()];
+        for range in ranges {
+            for bound in [range.start, range.end] {
+                if bound > offset && bound < end {
+                    cuts.push(bound - offset);
+                }
+            }
+        }
+        cuts.sort_unstable();
+        cuts.dedup();
+        for pair in cuts.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let hit = ranges
+                .iter()
+                .any(|r| r.start <= offset + from && offset + to <= r.end);
+            let style = if hit {
+                span.style.add_modifier(MATCH_MODIFIER)
+            } else {
+                span.style
+            };
+            spans.push(Span::styled(content[from..to].to_string(), style));
+        }
+        offset = end;
+    }
+    line.spans = spans;
+}
+
+impl App {
+    /// 確定済みクエリの正規表現（クエリが空か不正なら None）
+    fn search_regex(&self) -> Option<Regex> {
+        if self.diff.search_query.is_empty() {
+            return None;
+        }
+        build_search_regex(&self.diff.search_query, self.diff.search_options).ok()
+    }
+
+    /// 入力中のクエリのエラー（正規表現として不正な場合）
+    pub(super) fn search_input_error(&self) -> Option<String> {
+        if self.search_input.is_empty() {
+            return None;
+        }
+        build_search_regex(&self.search_input, self.diff.search_options).err()
+    }
+
+    /// 検索オプションを切り替える（確定済みクエリのマッチにも即反映される）
+    pub(super) fn toggle_search_option(&mut self, key: char) {
+        let options = &mut self.diff.search_options;
+        match key {
+            'c' => options.case_sensitive = !options.case_sensitive,
+            'w' => options.whole_word = !options.whole_word,
+            'r' => options.regex = !options.regex,
+            _ => {}
+        }
+    }
+
+    /// 検索クエリにマッチする diff 行（hunk header を除く）
+    pub(super) fn search_match_lines(&self) -> Vec<usize> {
+        let Some(re) = self.search_regex() else {
+            return Vec::new();
+        };
+        let Some(patch) = self.current_file().and_then(|f| f.patch.as_deref()) else {
+            return Vec::new();
+        };
+        patch
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.starts_with("@@"))
+            // 先頭の +/-/空白 を除いた本文で比較
+            .filter(|(_, line)| re.is_match(line.get(1..).unwrap_or("")))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// カーソル位置のマッチが何番目か（1 始まり）と総数。カーソルがマッチより前なら 0 番目
+    pub(super) fn search_position(&self) -> Option<(usize, usize)> {
+        let matches = self.search_match_lines();
+        if matches.is_empty() {
+            return None;
+        }
+        let current = matches.partition_point(|&idx| idx <= self.diff.cursor_line);
+        Some((current, matches.len()))
+    }
+
+    /// 表示範囲内のマッチ箇所を強調する（`text` は行番号プレフィックス挿入済み）
+    pub(super) fn emphasize_search_matches(&self, text: &mut Text<'static>, patch: &str) {
+        let Some(re) = self.search_regex() else {
+            return;
+        };
+        let first = self.visual_to_logical_line(self.diff.scroll as usize);
+        let last =
+            self.visual_to_logical_line(self.diff.scroll as usize + self.diff.view_height as usize);
+        let prefix_width = self.line_number_prefix_width() as usize;
+        for (idx, raw) in patch.lines().enumerate().take(last + 1).skip(first) {
+            if raw.starts_with("@@") {
+                continue;
+            }
+            let Some(line) = text.lines.get_mut(idx) else {
+                break;
+            };
+            let rendered: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+            // 行番号と +/- マーカーの後ろだけを対象にする
+            let marker = raw.get(..1).unwrap_or_default();
+            let body_start = if rendered
+                .get(prefix_width..)
+                .is_some_and(|r| r.starts_with(marker))
+            {
+                prefix_width + marker.len()
+            } else {
+                prefix_width
+            };
+            let Some(body) = rendered.get(body_start..) else {
+                continue;
+            };
+            let ranges: Vec<_> = re
+                .find_iter(body)
+                .filter(|m| !m.is_empty())
+                .map(|m| body_start + m.start()..body_start + m.end())
+                .collect();
+            if !ranges.is_empty() {
+                emphasize_ranges(line, &ranges);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opts(case_sensitive: bool, whole_word: bool, regex: bool) -> SearchOptions {
+        SearchOptions {
+            case_sensitive,
+            whole_word,
+            regex,
+        }
+    }
+
+    #[test]
+    fn test_build_search_regex() {
+        let re = build_search_regex("Foo", opts(false, false, false)).unwrap();
+        assert!(re.is_match("a foobar"));
+        let re = build_search_regex("Foo", opts(true, true, false)).unwrap();
+        assert!(!re.is_match("Foobar"));
+        assert!(re.is_match("let Foo = 1"));
+        // 正規表現でなければ記号はそのまま探す
+        let re = build_search_regex("a.b", opts(false, false, false)).unwrap();
+        assert!(!re.is_match("axb"));
+        let re = build_search_regex("a.b", opts(false, false, true)).unwrap();
+        assert!(re.is_match("axb"));
+        assert_eq!(
+            build_search_regex("(a", opts(false, false, true)).unwrap_err(),
+            "unclosed group"
+        );
+        assert_eq!(search_options_label(opts(true, false, true)), "Aa .*");
+    }
+
+    #[test]
+    fn test_emphasize_ranges_splits_spans() {
+        let mut line = Line::from(vec![Span::raw("ab"), Span::raw("cd")]);
+        emphasize_ranges(&mut line, std::slice::from_ref(&(1..3)));
+        let parts: Vec<(&str, bool)> = line
+            .spans
+            .iter()
+            .map(|s| {
+                (
+                    s.content.as_ref(),
+                    s.style.add_modifier.contains(Modifier::REVERSED),
+                )
+            })
+            .collect();
+        assert_eq!(
+            parts,
+            vec![("a", false), ("b", true), ("c", true), ("d", false)]
+        );
+    }
+}
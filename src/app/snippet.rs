@@ -0,0 +1,148 @@
+//! 行選択範囲を ```diff コードブロックや変更前後の片側のテキストとしてコピーする
+
+use super::*;
+use crate::github::review::Side;
+
+/// 選択範囲の前後に含めるコンテキスト行数（hunk をまたがない）
+const SNIPPET_CONTEXT_LINES: usize = 3;
+
+/// ファイル名・hunk header・前後のコンテキスト・選択行を ```diff ブロックにまとめる
+fn diff_snippet(filename: &str, patch: &str, start: usize, end: usize) -> Option<String> {
+    let lines: Vec<&str> = patch.lines().collect();
+    if start > end || end >= lines.len() {
+        return None;
+    }
+    let header = (0..=start).rev().find(|&i| lines[i].starts_with("@@"));
+    let hunk_start = header.map_or(0, |h| h + 1);
+    let hunk_end = (end + 1..lines.len())
+        .find(|&i| lines[i].starts_with("@@"))
+        .unwrap_or(lines.len());
+    let from = start.saturating_sub(SNIPPET_CONTEXT_LINES).max(hunk_start);
+    let to = (end + 1 + SNIPPET_CONTEXT_LINES).min(hunk_end);
+
+    let mut out = format!("{filename}\n```diff\n");
+    for line in header
+        .map(|h| lines[h])
+        .into_iter()
+        .chain(lines[from..to].iter().copied())
+    {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push_str("```\n");
+    Some(out)
+}
+
+/// 選択範囲のうち片側（Left: 変更前, Right: 変更後）に存在する行だけを +/- を外して返す
+fn side_text(patch: &str, start: usize, end: usize, side: Side) -> Option<String> {
+    let lines: Vec<&str> = patch.lines().collect();
+    if start > end || end >= lines.len() {
+        return None;
+    }
+    let absent = match side {
+        Side::Left => '+',
+        Side::Right => '-',
+    };
+    let mut out = String::new();
+    for line in &lines[start..=end] {
+        if line.starts_with("@@") || line.starts_with('\\') || line.starts_with(absent) {
+            continue;
+        }
+        out.push_str(line.get(1..).unwrap_or(""));
+        out.push('\n');
+    }
+    Some(out)
+}
+
+impl App {
+    /// o / n: 選択行の変更前（o）または変更後（n）のテキストだけをコピーする
+    pub(super) fn copy_side_text(&mut self, side: Side) {
+        let (start, end) = self
+            .line_selection
+            .map_or((self.diff.cursor_line, self.diff.cursor_line), |sel| {
+                sel.range(self.diff.cursor_line)
+            });
+        let label = match side {
+            Side::Left => "old version",
+            Side::Right => "new version",
+        };
+        let text = self
+            .current_file()
+            .and_then(|file| side_text(file.patch.as_deref()?, start, end, side));
+        let Some(text) = text.filter(|t| !t.is_empty()) else {
+            self.status_message =
+                Some(StatusMessage::error(format!("✗ No {label} lines selected")));
+            return;
+        };
+        self.copy_to_clipboard(&text, label);
+        if self
+            .status_message
+            .as_ref()
+            .is_some_and(|m| m.level == StatusLevel::Info)
+        {
+            let count = text.lines().count();
+            self.status_message = Some(StatusMessage::info(format!(
+                "✓ Copied {label} ({count} line{})",
+                if count == 1 { "" } else { "s" }
+            )));
+        }
+    }
+
+    /// Y: 選択行（未選択ならカーソル行）を前後のコンテキスト付き diff としてコピーする
+    pub(super) fn copy_diff_snippet(&mut self) {
+        let (start, end) = self
+            .line_selection
+            .map_or((self.diff.cursor_line, self.diff.cursor_line), |sel| {
+                sel.range(self.diff.cursor_line)
+            });
+        let Some(snippet) = self
+            .current_file()
+            .and_then(|file| diff_snippet(&file.filename, file.patch.as_deref()?, start, end))
+        else {
+            self.status_message = Some(StatusMessage::error("✗ No diff to copy"));
+            return;
+        };
+        self.copy_to_clipboard(&snippet, "diff");
+        // 本文は複数行になるため、ステータスには行数だけ表示する
+        if self
+            .status_message
+            .as_ref()
+            .is_some_and(|m| m.level == StatusLevel::Info)
+        {
+            let count = end - start + 1;
+            self.status_message = Some(StatusMessage::info(format!(
+                "✓ Copied diff snippet ({count} line{})",
+                if count == 1 { "" } else { "s" }
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_snippet() {
+        let patch = "@@ -1,8 +1,8 @@\n a\n b\n c\n d\n-e\n+E\n f\n g\n h\n i\n@@ -20 +20 @@\n+z";
+        let snippet = diff_snippet("src/lib.rs", patch, 5, 6).unwrap();
+        assert_eq!(
+            snippet,
+            "src/lib.rs\n```diff\n@@ -1,8 +1,8 @@\n b\n c\n d\n-e\n+E\n f\n g\n h\n```\n"
+        );
+        // コンテキストは hunk の境界で止まる
+        let snippet = diff_snippet("src/lib.rs", patch, 1, 1).unwrap();
+        assert!(snippet.contains("@@ -1,8 +1,8 @@\n a\n b\n c\n d\n```"));
+        assert!(diff_snippet("src/lib.rs", patch, 3, 99).is_none());
+    }
+
+    #[test]
+    fn test_side_text() {
+        let patch = "@@ -1,3 +1,3 @@\n a\n-old\n+new\n\\ No newline at end of file\n b";
+        assert_eq!(side_text(patch, 0, 5, Side::Left).unwrap(), "a\nold\nb\n");
+        assert_eq!(side_text(patch, 0, 5, Side::Right).unwrap(), "a\nnew\nb\n");
+        // 片側にない行だけを選ぶと空になる
+        assert_eq!(side_text(patch, 3, 3, Side::Left).unwrap(), "");
+        assert!(side_text(patch, 2, 9, Side::Left).is_none());
+    }
+}
@@ -0,0 +1,81 @@
+//! ステータスメッセージの履歴（gm で一覧表示）
+
+use super::*;
+use std::time::Instant;
+
+/// 履歴に残したステータスメッセージ
+#[derive(Debug, Clone)]
+pub struct StatusHistoryEntry {
+    pub at: chrono::DateTime<chrono::Local>,
+    pub level: StatusLevel,
+    pub body: String,
+    /// 記録済みかの判定に使う（同じメッセージを二重に記録しない）
+    created_at: Instant,
+}
+
+impl App {
+    /// 表示中のステータスメッセージを履歴に記録する（未記録のものだけ、古いものから上限を超えた分を捨てる）
+    pub(super) fn record_status_message(&mut self) {
+        let Some(message) = &self.status_message else {
+            return;
+        };
+        if self
+            .status_history
+            .back()
+            .is_some_and(|last| last.created_at == message.created_at && last.body == message.body)
+        {
+            return;
+        }
+        let limit = crate::format::status_history();
+        if limit == 0 {
+            return;
+        }
+        self.status_history.push_back(StatusHistoryEntry {
+            at: chrono::Local::now(),
+            level: message.level,
+            body: message.body.clone(),
+            created_at: message.created_at,
+        });
+        while self.status_history.len() > limit {
+            self.status_history.pop_front();
+        }
+    }
+
+    /// 表示中のエラーを消す（エラーは時間では消えない）
+    pub(super) fn dismiss_error_message(&mut self) -> bool {
+        if self
+            .status_message
+            .as_ref()
+            .is_some_and(|m| m.level == StatusLevel::Error)
+        {
+            self.status_message = None;
+            return true;
+        }
+        false
+    }
+
+    /// メッセージ履歴オーバーレイを開く（新しい順に表示）
+    pub(super) fn open_status_history(&mut self) {
+        self.record_status_message();
+        if self.status_history.is_empty() {
+            self.status_message = Some(StatusMessage::info("✓ No messages yet"));
+            return;
+        }
+        self.status_history_scroll = 0;
+        self.mode = AppMode::StatusHistory;
+    }
+
+    /// 履歴の各行（新しい順、"12:34:56 ✗ body"）
+    pub(super) fn status_history_lines(&self) -> Vec<(StatusLevel, String)> {
+        self.status_history
+            .iter()
+            .rev()
+            .map(|entry| {
+                (
+                    entry.level,
+                    format!("{} {}", entry.at.format("%H:%M:%S"), entry.body),
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,175 @@
+//! テストファイルのまとめ表示（本体のコードを先にレビューし、テストは後でまとめて見る）
+
+use super::*;
+use crate::config::TestFilesConfig;
+
+/// File Tree でのテストファイルの表示方法（t で順に切替）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TestSplit {
+    /// 通常どおり API の順に並べる
+    #[default]
+    Off,
+    /// 本体のファイルの後にテストファイルをまとめる
+    Grouped,
+    /// テストファイルのセクションを折りたたむ
+    Collapsed,
+}
+
+/// File Tree の 1 行
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileRow {
+    /// current_files() のインデックス
+    File(usize),
+    /// テストファイルのセクション見出し
+    TestsHeader,
+}
+
+/// パスが glob にマッチするか（`**` は 0 個以上の階層、`*` と `?` は 1 階層内）
+pub(super) fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+    match_segments(&pattern, &path)
+}
+
+fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| match_segments(rest, &path[skip..])),
+        Some((segment, rest)) => path.split_first().is_some_and(|(name, path_rest)| {
+            match_segment(segment.as_bytes(), name.as_bytes()) && match_segments(rest, path_rest)
+        }),
+    }
+}
+
+fn match_segment(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| match_segment(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && match_segment(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && match_segment(rest, &name[1..]),
+    }
+}
+
+impl App {
+    /// テストファイルの判定パターンと初期表示をセットする
+    pub fn set_test_files(&mut self, tests: TestFilesConfig) {
+        self.test_split = if tests.group {
+            TestSplit::Grouped
+        } else {
+            TestSplit::Off
+        };
+        self.test_patterns = tests.patterns;
+        self.tests_exclude_from_progress = tests.exclude_from_progress;
+    }
+
+    /// テストファイルか（設定の glob のどれかにマッチ）
+    pub(super) fn is_test_file(&self, path: &str) -> bool {
+        self.test_patterns.iter().any(|p| glob_match(p, path))
+    }
+
+    /// まとめ表示中でテストファイルを進捗に数えないか
+    pub(super) fn tests_excluded_from_progress(&self) -> bool {
+        self.tests_exclude_from_progress && self.test_split != TestSplit::Off
+    }
+
+    /// 進捗に数えるファイルか
+    pub(super) fn counts_for_progress(&self, path: &str) -> bool {
+        !self.tests_excluded_from_progress() || !self.is_test_file(path)
+    }
+
+    /// File Tree の表示行（まとめ表示中は本体 → 見出し → テストの順）
+    pub(super) fn file_tree_rows(&self) -> Vec<FileRow> {
+        let files = self.current_files();
+        if self.test_split == TestSplit::Off {
+            return (0..files.len()).map(FileRow::File).collect();
+        }
+        let (tests, production): (Vec<usize>, Vec<usize>) =
+            (0..files.len()).partition(|&i| self.is_test_file(&files[i].filename));
+        let mut rows: Vec<FileRow> = production.into_iter().map(FileRow::File).collect();
+        if !tests.is_empty() {
+            rows.push(FileRow::TestsHeader);
+            if self.test_split == TestSplit::Grouped {
+                rows.extend(tests.into_iter().map(FileRow::File));
+            }
+        }
+        rows
+    }
+
+    /// File Tree の表示順で隣のファイル（端なら現在のファイル）
+    pub(super) fn adjacent_tree_file(&self, current: usize, forward: bool) -> usize {
+        let order: Vec<usize> = self
+            .file_tree_rows()
+            .into_iter()
+            .filter_map(|row| match row {
+                FileRow::File(i) => Some(i),
+                FileRow::TestsHeader => None,
+            })
+            .collect();
+        let Some(pos) = order.iter().position(|&i| i == current) else {
+            return order.first().copied().unwrap_or(current);
+        };
+        let next = if forward {
+            (pos + 1).min(order.len() - 1)
+        } else {
+            pos.saturating_sub(1)
+        };
+        order[next]
+    }
+
+    /// t: テストファイルのまとめ表示を切り替える（通常 → まとめる → 折りたたむ → 通常）
+    pub(super) fn cycle_test_split(&mut self) {
+        self.test_split = match self.test_split {
+            TestSplit::Off => TestSplit::Grouped,
+            TestSplit::Grouped => TestSplit::Collapsed,
+            TestSplit::Collapsed => TestSplit::Off,
+        };
+        self.status_message = Some(StatusMessage::info(match self.test_split {
+            TestSplit::Off => "Test files: inline",
+            TestSplit::Grouped => "Test files: grouped last",
+            TestSplit::Collapsed => "Test files: collapsed",
+        }));
+        self.reveal_selected_file();
+    }
+
+    /// 見出しのクリック: テストファイルのセクションを開閉する
+    pub(super) fn toggle_tests_collapsed(&mut self) {
+        self.test_split = match self.test_split {
+            TestSplit::Grouped => TestSplit::Collapsed,
+            TestSplit::Collapsed => TestSplit::Grouped,
+            TestSplit::Off => TestSplit::Off,
+        };
+        self.reveal_selected_file();
+    }
+
+    /// 折りたたまれたテストファイルを選択中なら、表示中の最初のファイルに移る
+    fn reveal_selected_file(&mut self) {
+        let Some(selected) = self.file_list_state.selected() else {
+            return;
+        };
+        let rows = self.file_tree_rows();
+        if rows.contains(&FileRow::File(selected)) {
+            return;
+        }
+        if let Some(FileRow::File(first)) = rows.first() {
+            self.file_list_state.select(Some(*first));
+            self.reset_cursor();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("**/tests/**", "tests/a.rs"));
+        assert!(glob_match("**/tests/**", "crates/x/tests/deep/a.rs"));
+        assert!(!glob_match("**/tests/**", "src/testsuite.rs"));
+        assert!(glob_match("**/*_test.*", "pkg/server_test.go"));
+        assert!(glob_match("**/*.test.*", "web/App.test.tsx"));
+        assert!(!glob_match("**/*.test.*", "web/App.tsx"));
+        assert!(glob_match("src/?.rs", "src/a.rs"));
+        assert!(!glob_match("src/*.rs", "src/a/b.rs"));
+    }
+}
@@ -1,8 +1,11 @@
 use super::editor::TextEditor;
+use crate::config::MemoryConfig;
+use crate::github::error::ApiErrorKind;
+use crate::github::pr::MergeMethod;
+use crate::lru::SizedLru;
 use ratatui::layout::Rect;
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
-const STATUS_MSG_TTL_SECS: u64 = 3;
 const DEFAULT_DIFF_VIEW_HEIGHT: u16 = 20;
 const DEFAULT_DIFF_VIEW_WIDTH: u16 = 80;
 
@@ -49,11 +52,49 @@ pub enum AppMode {
     IssueCommentInput,
     CommentView,
     ReplyInput,
+    CommentEditInput,
+    CommentDeleteConfirm,
     ReviewSubmit,
     ReviewBodyInput,
     QuitConfirm,
+    QuitWait,
     Help,
     MediaViewer,
+    CommandOutput,
+    Checklist,
+    DiffSearch,
+    Dashboard,
+    FileCompare,
+    FileHistory,
+    LargePrSummary,
+    LinkList,
+    Stats,
+    NoteInput,
+    UpdateBranch,
+    AutoMerge,
+    DiscardConfirm,
+    PendingComments,
+    AttachImage,
+    StatusHistory,
+    Checks,
+    FileFinder,
+    PrStateChange,
+    CheckoutConfirm,
+    AllowCommand,
+}
+
+/// 自動マージ設定の変更要求
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AutoMergeAction {
+    Enable(MergeMethod),
+    Disable,
+}
+
+/// PR の状態変更要求（GitHub の "Close pull request" / "Reopen pull request"）
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PrStateChange {
+    Close,
+    Reopen,
 }
 
 /// レビューイベントタイプ
@@ -86,6 +127,15 @@ impl ReviewEvent {
             ReviewEvent::RequestChanges => "Request Changes",
         }
     }
+
+    /// 送信ダイアログで直接選択するキー
+    pub fn key(&self) -> char {
+        match self {
+            ReviewEvent::Comment => 'c',
+            ReviewEvent::Approve => 'a',
+            ReviewEvent::RequestChanges => 'r',
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -102,9 +152,10 @@ pub struct StatusMessage {
 }
 
 impl StatusMessage {
+    /// 登録済みの定型メッセージは現在のロケールに翻訳される
     pub fn info(body: impl Into<String>) -> Self {
         Self {
-            body: body.into(),
+            body: crate::i18n::tr(&body.into()).to_string(),
             level: StatusLevel::Info,
             created_at: Instant::now(),
         }
@@ -112,14 +163,16 @@ impl StatusMessage {
 
     pub fn error(body: impl Into<String>) -> Self {
         Self {
-            body: body.into(),
+            body: crate::i18n::tr(&body.into()).to_string(),
             level: StatusLevel::Error,
             created_at: Instant::now(),
         }
     }
 
+    /// 表示期限を過ぎたか（エラーは Esc で消すまで残す）
     pub fn is_expired(&self) -> bool {
-        self.created_at.elapsed() >= Duration::from_secs(STATUS_MSG_TTL_SECS)
+        self.level == StatusLevel::Info
+            && self.created_at.elapsed() >= crate::format::status_duration()
     }
 }
 
@@ -170,16 +223,38 @@ pub struct ResolveToggleRequest {
     pub root_comment_id: u64,
 }
 
+/// CommentView で引用する行の範囲（表示中コメントの本文行を通しで数えた位置）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuoteSelection {
+    pub anchor: usize,
+    pub cursor: usize,
+}
+
+impl QuoteSelection {
+    /// 選択範囲（両端を含む）
+    pub fn range(&self) -> (usize, usize) {
+        (self.anchor.min(self.cursor), self.anchor.max(self.cursor))
+    }
+}
+
 /// レビュー・コメント関連の状態
 #[derive(Debug, Default)]
 pub struct ReviewState {
     /// CommentInput（diff 行コメント）と IssueCommentInput（PR 全体コメント）で共有
     pub comment_editor: TextEditor,
     pub pending_comments: Vec<crate::github::review::PendingComment>,
+    /// 保留コメントのグループ見出し（キーはグループ先頭のコメントの位置。末尾は次に追加するコメント）
+    pub pending_headers: std::collections::BTreeMap<usize, String>,
     pub review_comments: Vec<crate::github::comments::ReviewComment>,
     pub viewing_comments: Vec<crate::github::comments::ReviewComment>,
+    /// CommentView で選択中のコメントの位置（J / K で移動、e / d の対象）
+    pub viewing_comment_cursor: usize,
+    /// 各コメントの見出しの表示行（render で更新、J / K でスクロールする先）
+    pub viewing_comment_rows: Vec<u16>,
     pub viewing_comment_scroll: u16,
     pub comment_view_max_scroll: u16,
+    /// コメントペインの表示行数（render で更新、PgDn / PgUp の移動量）
+    pub comment_view_height: u16,
     pub review_event_cursor: usize,
     pub review_body_editor: TextEditor,
     pub needs_submit: Option<ReviewEvent>,
@@ -187,6 +262,23 @@ pub struct ReviewState {
     pub thread_map: std::collections::HashMap<u64, crate::github::comments::ReviewThread>,
     pub needs_resolve_toggle: Option<ResolveToggleRequest>,
     pub reply_to_comment_id: Option<u64>,
+    /// 編集・削除する自分のコメント（CommentView の e / d）
+    pub editing_comment_id: Option<u64>,
+    /// 返信送信後にスレッドを resolve する（Ctrl+X で切替）
+    pub resolve_after_reply: bool,
+    /// 引用返信の行選択（`>` で開始）
+    pub quote_select: Option<QuoteSelection>,
+}
+
+/// diff 内検索のオプション（検索入力中に Alt+c / Alt+w / Alt+r で切替）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// 大文字小文字を区別する
+    pub case_sensitive: bool,
+    /// 単語単位で一致させる
+    pub whole_word: bool,
+    /// クエリを正規表現として扱う
+    pub regex: bool,
 }
 
 /// DiffView パネルの表示状態
@@ -198,8 +290,29 @@ pub struct DiffViewState {
     pub view_width: u16,
     pub wrap: bool,
     pub show_line_numbers: bool,
+    /// カバレッジオーバーレイを表示するか（レポート未指定時は無効）
+    pub show_coverage: bool,
+    /// 確定済みの検索クエリ（空なら検索なし）
+    pub search_query: String,
+    pub search_options: SearchOptions,
     pub visual_offsets: Option<Vec<usize>>,
-    pub highlight_cache: Option<(usize, usize, ratatui::text::Text<'static>)>,
+    /// (コミット, ファイル) ごとのハイライト済み diff（メモリ予算付き LRU）
+    pub highlight_cache: SizedLru<(usize, usize), ratatui::text::Text<'static>>,
+    /// (コミット SHA, ファイル名) ごとの保存位置（ファイルに戻ったときに復元する）
+    pub positions: std::collections::HashMap<(String, String), DiffPosition>,
+    /// 現在の cursor_line / scroll がどのファイルのものか
+    pub position_key: Option<(String, String)>,
+}
+
+/// ファイルごとに保存する DiffView のカーソル・スクロール位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffPosition {
+    pub cursor_line: usize,
+    pub scroll: u16,
+    /// 画面先頭の論理行（wrap や幅が変わっていたときはここから表示行位置を再計算する）
+    pub top_line: usize,
+    pub wrap: bool,
+    pub view_width: u16,
 }
 
 /// 各ペインの描画領域キャッシュ（マウスヒットテスト用、render 時に更新）
@@ -217,6 +330,8 @@ pub struct LayoutCache {
 /// コード行コメントスレッドのリプライ
 #[derive(Debug, Clone)]
 pub struct CodeCommentReply {
+    /// レビューコメントの ID（編集・削除の反映に使う）
+    pub id: u64,
     pub author: String,
     pub body: String,
     pub created_at: String,
@@ -255,7 +370,7 @@ pub enum LoadPhase {
     #[default]
     Loading,
     Done,
-    Error,
+    Error(ApiErrorKind),
 }
 
 /// 各データの非同期ロード状態
@@ -291,8 +406,13 @@ impl Default for DiffViewState {
             view_width: DEFAULT_DIFF_VIEW_WIDTH,
             wrap: false,
             show_line_numbers: false,
+            show_coverage: true,
+            search_query: String::new(),
+            search_options: SearchOptions::default(),
             visual_offsets: None,
-            highlight_cache: None,
+            highlight_cache: SizedLru::new(MemoryConfig::default().highlight_bytes()),
+            positions: std::collections::HashMap::new(),
+            position_key: None,
         }
     }
 }
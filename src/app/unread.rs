@@ -0,0 +1,92 @@
+//! Conversation エントリの既読管理（PR ごとにローカル保存し、新着にバッジを付ける）
+
+use super::*;
+use crate::github::cache::{self, SeenCache};
+
+/// 既読判定に使うエントリのキー（スレッドは最新返信の日時を含め、返信が付くと未読に戻す）
+fn entry_key(entry: &ConversationEntry) -> String {
+    match &entry.kind {
+        ConversationKind::Review { .. } => {
+            format!("review:{}:{}", entry.author, entry.created_at)
+        }
+        ConversationKind::IssueComment => {
+            format!("comment:{}:{}", entry.author, entry.created_at)
+        }
+        ConversationKind::CodeComment {
+            replies,
+            root_comment_id,
+            ..
+        } => {
+            let latest = replies
+                .iter()
+                .map(|reply| reply.created_at.as_str())
+                .chain(std::iter::once(entry.created_at.as_str()))
+                .max()
+                .unwrap_or_default();
+            format!("thread:{root_comment_id}:{latest}")
+        }
+    }
+}
+
+impl App {
+    /// 保存済みの既読状態をセットする
+    pub fn set_seen_entries(&mut self, saved: Option<SeenCache>) {
+        self.seen_entries = saved.unwrap_or_default().entries;
+    }
+
+    /// エントリが未読か
+    pub(super) fn is_entry_unread(&self, index: usize) -> bool {
+        self.conversation
+            .get(index)
+            .is_some_and(|entry| !self.seen_entries.contains(&entry_key(entry)))
+    }
+
+    /// 未読エントリの数
+    pub(super) fn unread_count(&self) -> usize {
+        (0..self.conversation.len())
+            .filter(|&i| self.is_entry_unread(i))
+            .count()
+    }
+
+    /// カーソル位置のエントリを既読にする（変化があれば保存して再描画）
+    pub(super) fn mark_conversation_seen(&mut self) {
+        let Some(key) = self
+            .conversation
+            .get(self.conversation_cursor)
+            .map(entry_key)
+        else {
+            return;
+        };
+        if !self.seen_entries.insert(key) {
+            return;
+        }
+        self.conversation_rendered = None; // キャッシュ無効化
+        self.save_seen_entries();
+    }
+
+    /// u: 最初の未読エントリへジャンプする
+    pub(super) fn jump_to_first_unread(&mut self) {
+        let Some(index) = (0..self.conversation.len()).find(|&i| self.is_entry_unread(i)) else {
+            self.status_message = Some(StatusMessage::info("No unread entries"));
+            return;
+        };
+        self.conversation_cursor = index;
+        self.center_conversation_on_cursor();
+    }
+
+    /// 既読状態をキャッシュに保存する（失敗時はステータスにエラーを出す）
+    fn save_seen_entries(&mut self) {
+        let Some((owner, repo)) = self.parse_repo() else {
+            return;
+        };
+        let data = SeenCache {
+            entries: self.seen_entries.clone(),
+        };
+        if let Err(e) = cache::write_pr_json(owner, repo, self.pr_number, cache::SEEN_SUFFIX, &data)
+        {
+            self.status_message = Some(StatusMessage::error(format!(
+                "✗ Failed to save read state: {e}"
+            )));
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! viewed 状態の保存と、GitHub の "Viewed" チェックとの同期（All changes の viewed が対応する）
+
+use super::*;
+use crate::github::cache::{self, ViewedCache};
+
+impl App {
+    /// 保存済みの viewed 状態と、GitHub に送れていない変更をセットする
+    pub fn set_viewed_files(&mut self, saved: Option<ViewedCache>) {
+        let saved = saved.unwrap_or_default();
+        self.viewed_files = saved.files;
+        self.viewed_sync = saved.pending;
+    }
+
+    /// GitHub の "Viewed" の取得を予約する（PR ノード ID がなければ同期しない）
+    pub fn schedule_viewed_fetch(&mut self) {
+        self.needs_viewed_fetch = !self.pr_node_id.is_empty();
+    }
+
+    /// GitHub の "Viewed" をバックグラウンドで取得する（draw 後に呼ばれる。結果は AsyncData::ViewedFiles で届く）
+    pub(super) fn execute_viewed_fetch(&mut self) {
+        let pr_node_id = self.pr_node_id.clone();
+        self.spawn_task(async move {
+            crate::AsyncData::ViewedFiles(
+                Self::run_blocking(move || crate::github::pr::fetch_viewed_files(&pr_node_id))
+                    .await,
+            )
+        });
+    }
+
+    /// GitHub の "Viewed" で All changes の viewed を置き換え、送れていない変更を送り直す
+    /// （Web UI で外したものや、push 後に GitHub が外したものはローカルでも外れる。失敗したら保存済みの状態のまま）
+    pub(super) fn apply_viewed_files(&mut self, result: Result<HashSet<String>, String>) {
+        if let Ok(mut remote) = result {
+            // まだ GitHub に届いていない変更だけはローカルの状態を優先する
+            for (path, viewed) in &self.viewed_sync {
+                if *viewed {
+                    remote.insert(path.clone());
+                } else {
+                    remote.remove(path);
+                }
+            }
+            self.viewed_files
+                .insert(ALL_CHANGES_KEY.to_string(), remote);
+            self.save_viewed_files();
+        }
+        self.needs_viewed_sync = !self.viewed_sync.is_empty();
+    }
+
+    /// ファイルの viewed を変更して保存し、All changes の変更は GitHub への反映を予約する
+    pub(super) fn set_files_viewed(&mut self, sha: &str, filenames: Vec<String>, viewed: bool) {
+        let set = self.viewed_files.entry(sha.to_string()).or_default();
+        let changed: Vec<String> = filenames
+            .into_iter()
+            .filter(|name| {
+                if viewed {
+                    set.insert(name.clone())
+                } else {
+                    set.remove(name)
+                }
+            })
+            .collect();
+        if changed.is_empty() {
+            return;
+        }
+        if sha == ALL_CHANGES_KEY && !self.pr_node_id.is_empty() {
+            for name in changed {
+                // 送る前に切り替え直したファイルは最後の状態だけ送る
+                self.viewed_sync.retain(|(path, _)| *path != name);
+                self.viewed_sync.push((name, viewed));
+            }
+            self.needs_viewed_sync = true;
+        }
+        self.save_viewed_files();
+    }
+
+    /// 送れていない "Viewed" の変更をバックグラウンドで GitHub に送る
+    /// （draw 後に呼ばれる。失敗したファイルも止めずに残りを送り、結果は AsyncData::ViewedSynced で届く）
+    pub(super) fn execute_viewed_sync(&mut self) {
+        if self.pr_node_id.is_empty() || self.viewed_sync.is_empty() {
+            return;
+        }
+        let pr_node_id = self.pr_node_id.clone();
+        let changes = self.viewed_sync.clone();
+        self.viewed_sync_running = self.spawn_task(async move {
+            let mut synced = Vec::new();
+            let mut failed = Vec::new();
+            for (path, viewed) in changes {
+                let (pr_node_id, target) = (pr_node_id.clone(), path.clone());
+                let result = Self::run_blocking(move || {
+                    crate::github::pr::set_file_viewed(&pr_node_id, &target, viewed)
+                })
+                .await;
+                match result {
+                    Ok(()) => synced.push((path, viewed)),
+                    Err(e) => failed.push((path, e)),
+                }
+            }
+            crate::AsyncData::ViewedSynced { synced, failed }
+        });
+    }
+
+    /// 同期の結果を反映する（送れた変更を消し、送れなかったものは保存して次の同期で送り直す）
+    pub(super) fn apply_viewed_synced(
+        &mut self,
+        synced: Vec<(String, bool)>,
+        failed: Vec<(String, String)>,
+    ) {
+        self.viewed_sync_running = false;
+        // 送信中に切り替え直したファイルは新しい状態を送るために残す
+        self.viewed_sync.retain(|change| !synced.contains(change));
+        self.save_viewed_files();
+        if let Some((path, e)) = failed.first() {
+            self.status_message = Some(StatusMessage::error(format!(
+                "✗ Failed to sync viewed state of {} file(s) ({path}): {e} — will retry on the next sync",
+                failed.len()
+            )));
+        }
+    }
+
+    /// viewed 状態を保存する（force-push で消えたコミットの分は落とす。失敗時はステータスバーに表示）
+    fn save_viewed_files(&mut self) {
+        let Some((owner, repo)) = self.parse_repo() else {
+            return;
+        };
+        let files = self
+            .viewed_files
+            .iter()
+            .filter(|(sha, files)| {
+                !files.is_empty()
+                    && (sha.as_str() == ALL_CHANGES_KEY
+                        || self.commits.iter().any(|c| &c.sha == *sha))
+            })
+            .map(|(sha, files)| (sha.clone(), files.clone()))
+            .collect();
+        if let Err(e) = cache::write_pr_json(
+            owner,
+            repo,
+            self.pr_number,
+            cache::VIEWED_SUFFIX,
+            &ViewedCache {
+                files,
+                pending: self.viewed_sync.clone(),
+            },
+        ) {
+            self.status_message = Some(StatusMessage::error(format!(
+                "✗ Failed to save viewed files: {e}"
+            )));
+        }
+    }
+}
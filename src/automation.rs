@@ -0,0 +1,183 @@
+//! TUI を使わずにレビューを送信する自動化モード（`prism review`）
+//!
+//! 結果は stdout に 1 行の JSON で出力し、終了コードで成否を区別する。
+
+use crate::github::review::{self, ReviewContext};
+use octocrab::Octocrab;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 終了コード（clap の引数エラーは 2、それ以外の想定外エラーは 1）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// レビューを送信した
+    Submitted = 0,
+    /// 本文が空・ファイルが読めないなど入力が不正
+    InvalidInput = 2,
+    /// PR が Open でない、自分の PR への Approve など GitHub が受け付けない操作
+    NotAllowed = 3,
+    /// GitHub API 呼び出しの失敗
+    ApiError = 4,
+}
+
+/// stdout に出力する結果
+#[derive(Debug, Serialize)]
+pub struct Outcome {
+    pub ok: bool,
+    pub exit_code: i32,
+    pub repo: String,
+    pub pr_number: u64,
+    pub event: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head_sha: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 送信するレビュー
+pub struct ReviewRequest<'a> {
+    pub owner: &'a str,
+    pub repo: &'a str,
+    pub pr_number: u64,
+    /// "APPROVE" / "REQUEST_CHANGES" / "COMMENT"
+    pub event: &'static str,
+    pub body: Option<String>,
+    pub body_file: Option<&'a Path>,
+}
+
+impl ReviewRequest<'_> {
+    fn outcome(
+        &self,
+        status: ExitStatus,
+        head_sha: Option<String>,
+        error: Option<String>,
+    ) -> Outcome {
+        Outcome {
+            ok: status == ExitStatus::Submitted,
+            exit_code: status as i32,
+            repo: format!("{}/{}", self.owner, self.repo),
+            pr_number: self.pr_number,
+            event: self.event,
+            head_sha,
+            error,
+        }
+    }
+}
+
+/// --body / --comment-file から本文を決める（Approve 以外は空を許さない）
+fn resolve_body(
+    event: &str,
+    body: Option<&str>,
+    body_file: Option<&Path>,
+) -> Result<String, String> {
+    let body = match (body, body_file) {
+        (Some(body), _) => body.to_string(),
+        (None, Some(path)) => std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?,
+        (None, None) => String::new(),
+    };
+    let body = body.trim().to_string();
+    if body.is_empty() && event != "APPROVE" {
+        return Err(format!("{event} requires --body or --comment-file"));
+    }
+    Ok(body)
+}
+
+/// GitHub が受け付けない操作を送信前に弾く
+fn check_allowed(event: &str, pr_open: bool, is_own_pr: bool) -> Result<(), &'static str> {
+    if !pr_open {
+        Err("PR is not open")
+    } else if is_own_pr && event == "APPROVE" {
+        Err("You cannot approve your own PR")
+    } else if is_own_pr && event == "REQUEST_CHANGES" {
+        Err("You cannot request changes on your own PR")
+    } else {
+        Ok(())
+    }
+}
+
+/// レビューを送信して結果を返す（エラーも Outcome として返す）
+pub async fn submit(client: &Octocrab, req: &ReviewRequest<'_>, current_user: &str) -> Outcome {
+    let body = match resolve_body(req.event, req.body.as_deref(), req.body_file) {
+        Ok(body) => body,
+        Err(e) => return req.outcome(ExitStatus::InvalidInput, None, Some(e)),
+    };
+
+    let pr = match crate::github::pr::fetch_pr(client, req.owner, req.repo, req.pr_number).await {
+        Ok(pr) => pr,
+        Err(e) => return req.outcome(ExitStatus::ApiError, None, Some(format!("{e:#}"))),
+    };
+    let head_sha = pr.head.sha.clone();
+    let pr_open = pr.merged_at.is_none() && pr.state == Some(octocrab::models::IssueState::Open);
+    let is_own_pr =
+        !current_user.is_empty() && pr.user.as_ref().is_some_and(|u| u.login == current_user);
+    if let Err(e) = check_allowed(req.event, pr_open, is_own_pr) {
+        return req.outcome(ExitStatus::NotAllowed, Some(head_sha), Some(e.to_string()));
+    }
+
+    let ctx = ReviewContext {
+        client,
+        owner: req.owner,
+        repo: req.repo,
+        pr_number: req.pr_number,
+    };
+    match review::submit_review(&ctx, &head_sha, &[], &HashMap::new(), req.event, &body).await {
+        Ok(()) => req.outcome(ExitStatus::Submitted, Some(head_sha), None),
+        Err(e) => req.outcome(ExitStatus::ApiError, Some(head_sha), Some(format!("{e:#}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_body() {
+        assert_eq!(resolve_body("APPROVE", None, None), Ok(String::new()));
+        assert_eq!(
+            resolve_body("COMMENT", Some("  LGTM\n"), None),
+            Ok("LGTM".to_string())
+        );
+        assert!(resolve_body("COMMENT", None, None).is_err());
+        assert!(resolve_body("REQUEST_CHANGES", Some(" "), None).is_err());
+        assert!(resolve_body("COMMENT", None, Some(Path::new("/nonexistent/review.md"))).is_err());
+    }
+
+    #[test]
+    fn test_check_allowed() {
+        assert!(check_allowed("APPROVE", true, false).is_ok());
+        assert!(check_allowed("COMMENT", true, true).is_ok());
+        assert_eq!(
+            check_allowed("APPROVE", true, true),
+            Err("You cannot approve your own PR")
+        );
+        assert!(check_allowed("REQUEST_CHANGES", true, true).is_err());
+        assert_eq!(
+            check_allowed("COMMENT", false, false),
+            Err("PR is not open")
+        );
+    }
+
+    #[test]
+    fn test_outcome_json() {
+        let req = ReviewRequest {
+            owner: "octo",
+            repo: "demo",
+            pr_number: 7,
+            event: "APPROVE",
+            body: None,
+            body_file: None,
+        };
+        let json = serde_json::to_string(&req.outcome(
+            ExitStatus::NotAllowed,
+            None,
+            Some("PR is not open".to_string()),
+        ))
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"ok":false,"exit_code":3,"repo":"octo/demo","pr_number":7,"event":"APPROVE","error":"PR is not open"}"#
+        );
+    }
+}
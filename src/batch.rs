@@ -0,0 +1,310 @@
+//! 小さな PR を連続してレビューするバッチモード（dependabot などの依存更新 PR 向け）
+
+use crate::github::files::DiffFile;
+use crate::github::review::ReviewContext;
+use crate::i18n::tr;
+use color_eyre::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use octocrab::Octocrab;
+use ratatui::{
+    DefaultTerminal, Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+/// バッチ対象の PR（起動時にまとめて取得）
+pub struct BatchPr {
+    pub number: u64,
+    pub title: String,
+    pub head_sha: String,
+    pub files: Vec<DiffFile>,
+}
+
+/// 各 PR に対して取ったアクション
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchAction {
+    Approved,
+    Skipped,
+    Failed(String),
+}
+
+/// 1 PR 分の差分をコンパクトに行へ展開する（ファイル見出し + パッチ本文）
+fn compact_diff_lines(files: &[DiffFile]) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for file in files {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} {}", file.status_char(), file.filename),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(" +{}", file.additions),
+                Style::default().fg(Color::Green),
+            ),
+            Span::styled(
+                format!(" -{}", file.deletions),
+                Style::default().fg(Color::Red),
+            ),
+        ]));
+        let Some(patch) = &file.patch else {
+            lines.push(Line::styled(
+                "  (no textual diff)",
+                Style::default().fg(Color::DarkGray),
+            ));
+            continue;
+        };
+        for line in patch.lines() {
+            let style = if line.starts_with("@@") {
+                Style::default().fg(Color::Cyan)
+            } else if line.starts_with('+') {
+                Style::default().fg(Color::Green)
+            } else if line.starts_with('-') {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::styled(format!("  {line}"), style));
+        }
+        lines.push(Line::raw(""));
+    }
+    lines
+}
+
+/// アクション結果のサマリー（未処理の PR は "Not reviewed" として数える）
+pub fn summary_lines(prs: &[BatchPr], actions: &[BatchAction]) -> Vec<String> {
+    let numbers = |pred: &dyn Fn(&BatchAction) -> bool| -> String {
+        prs.iter()
+            .zip(actions)
+            .filter(|(_, a)| pred(a))
+            .map(|(pr, _)| format!("#{}", pr.number))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let mut lines = vec![
+        format!(
+            "{} {}",
+            tr("Approved:"),
+            numbers(&|a| *a == BatchAction::Approved)
+        ),
+        format!(
+            "{} {}",
+            tr("Skipped:"),
+            numbers(&|a| *a == BatchAction::Skipped)
+        ),
+    ];
+    for (pr, action) in prs.iter().zip(actions) {
+        if let BatchAction::Failed(e) = action {
+            lines.push(format!("{} #{}: {}", tr("Failed:"), pr.number, e));
+        }
+    }
+    if actions.len() < prs.len() {
+        let rest: Vec<String> = prs[actions.len()..]
+            .iter()
+            .map(|pr| format!("#{}", pr.number))
+            .collect();
+        lines.push(format!("{} {}", tr("Not reviewed:"), rest.join(" ")));
+    }
+    lines
+}
+
+/// バッチレビューの画面状態
+struct BatchView<'a> {
+    prs: &'a [BatchPr],
+    actions: Vec<BatchAction>,
+    scroll: u16,
+}
+
+impl BatchView<'_> {
+    fn render(&self, frame: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(frame.area());
+        let header_style = Style::default().bg(Color::Blue).fg(Color::White);
+
+        let Some(pr) = self.prs.get(self.actions.len()) else {
+            // 全件処理済み: サマリー
+            frame.render_widget(
+                Paragraph::new(format!(" prism batch - {}", tr("Summary"))).style(header_style),
+                chunks[0],
+            );
+            let lines: Vec<Line> = summary_lines(self.prs, &self.actions)
+                .into_iter()
+                .map(|l| Line::raw(format!(" {l}")))
+                .collect();
+            frame.render_widget(
+                Paragraph::new(lines).block(Block::default().borders(Borders::ALL)),
+                chunks[1],
+            );
+            frame.render_widget(
+                Paragraph::new(tr(" Press any key to exit ")).style(header_style),
+                chunks[2],
+            );
+            return;
+        };
+
+        frame.render_widget(
+            Paragraph::new(format!(
+                " prism batch [{}/{}] #{} {}",
+                self.actions.len() + 1,
+                self.prs.len(),
+                pr.number,
+                pr.title
+            ))
+            .style(header_style),
+            chunks[0],
+        );
+        let mut block = Block::default().borders(Borders::ALL);
+        // 直前の PR の承認失敗を表示（サマリーにも残る）
+        if let Some(BatchAction::Failed(e)) = self.actions.last() {
+            let prev = self.prs[self.actions.len() - 1].number;
+            block = block.title_bottom(Line::styled(
+                format!(" ✗ #{prev}: {e} "),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        frame.render_widget(
+            Paragraph::new(compact_diff_lines(&pr.files))
+                .block(block)
+                .scroll((self.scroll, 0)),
+            chunks[1],
+        );
+        frame.render_widget(
+            Paragraph::new(tr(" a: approve | s: skip | j/k: scroll | q: finish "))
+                .style(header_style),
+            chunks[2],
+        );
+    }
+}
+
+/// バッチレビューの TUI を実行し、各 PR へのアクションを返す
+pub fn run_batch(
+    mut terminal: DefaultTerminal,
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    prs: &[BatchPr],
+) -> Result<Vec<BatchAction>> {
+    let mut view = BatchView {
+        prs,
+        actions: Vec::new(),
+        scroll: 0,
+    };
+    loop {
+        terminal.draw(|frame| view.render(frame))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let Some(pr) = prs.get(view.actions.len()) else {
+            break;
+        };
+        match key.code {
+            KeyCode::Char('a') => {
+                let ctx = ReviewContext {
+                    client,
+                    owner,
+                    repo,
+                    pr_number: pr.number,
+                };
+                let result = tokio::task::block_in_place(|| {
+                    tokio::runtime::Handle::current().block_on(
+                        crate::github::review::submit_review(
+                            &ctx,
+                            &pr.head_sha,
+                            &[],
+                            &Default::default(),
+                            "APPROVE",
+                            "",
+                        ),
+                    )
+                });
+                view.actions.push(match result {
+                    Ok(()) => BatchAction::Approved,
+                    Err(e) => BatchAction::Failed(e.to_string()),
+                });
+                view.scroll = 0;
+            }
+            KeyCode::Char('s') => {
+                view.actions.push(BatchAction::Skipped);
+                view.scroll = 0;
+            }
+            KeyCode::Char('j') | KeyCode::Down => view.scroll = view.scroll.saturating_add(1),
+            KeyCode::Char('k') | KeyCode::Up => view.scroll = view.scroll.saturating_sub(1),
+            KeyCode::Char('d') => view.scroll = view.scroll.saturating_add(10),
+            KeyCode::Char('u') => view.scroll = view.scroll.saturating_sub(10),
+            KeyCode::Char('q') => break,
+            _ => {}
+        }
+    }
+    Ok(view.actions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(number: u64) -> BatchPr {
+        BatchPr {
+            number,
+            title: format!("Bump dep {number}"),
+            head_sha: String::new(),
+            files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_summary_lines() {
+        let prs = vec![pr(1), pr(2), pr(3), pr(4)];
+        let actions = vec![
+            BatchAction::Approved,
+            BatchAction::Skipped,
+            BatchAction::Failed("Not Found".to_string()),
+        ];
+        assert_eq!(
+            summary_lines(&prs, &actions),
+            vec![
+                "Approved: #1",
+                "Skipped: #2",
+                "Failed: #3: Not Found",
+                "Not reviewed: #4",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compact_diff_lines() {
+        let files = vec![
+            DiffFile {
+                filename: "Cargo.lock".to_string(),
+                status: "modified".to_string(),
+                additions: 1,
+                deletions: 1,
+                patch: Some("@@ -1,1 +1,1 @@\n-version = \"1.0\"\n+version = \"1.1\"".to_string()),
+            },
+            DiffFile {
+                filename: "logo.png".to_string(),
+                status: "added".to_string(),
+                additions: 0,
+                deletions: 0,
+                patch: None,
+            },
+        ];
+        let lines = compact_diff_lines(&files);
+        // 見出し + パッチ 3 行 + 空行、バイナリは見出し + 注記
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0].to_string(), "M Cargo.lock +1 -1");
+        assert_eq!(lines[3].to_string(), "  +version = \"1.1\"");
+        assert_eq!(lines[6].to_string(), "  (no textual diff)");
+    }
+}
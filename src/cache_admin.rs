@@ -0,0 +1,253 @@
+//! ローカルキャッシュの確認と整理（`prism cache list|clear|prune`）
+//!
+//! clear / prune はファイルをすぐには消さず、キャッシュ直下の .archive に移す。
+//! アーカイブしたファイルは、以降の prune で期限を過ぎたものから削除する。
+
+use crate::github::cache::{self, CacheEntry};
+use color_eyre::Result;
+use octocrab::Octocrab;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// `prism cache` のサブコマンド
+#[derive(Debug, clap::Subcommand)]
+pub enum CacheAction {
+    /// List cached PRs per repository with their size and age
+    List,
+    /// Archive the cache of the given PRs (all PRs of the repository when none are given)
+    Clear {
+        /// Pull Request numbers
+        pr_numbers: Vec<u64>,
+    },
+    /// Archive entries not used for N days (and of merged PRs with --merged),
+    /// then delete archived files older than N days
+    Prune {
+        /// Age in days
+        #[arg(long, value_name = "DAYS", default_value_t = 30)]
+        older_than: u64,
+
+        /// Also archive PRs that have been merged (asks GitHub)
+        #[arg(long)]
+        merged: bool,
+    },
+}
+
+/// 一覧の 1 行にまとめる単位（PR、または repo ごとの API レスポンス）
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Group {
+    files: usize,
+    size: u64,
+    /// 最後に書き込まれた時刻
+    modified: Option<SystemTime>,
+}
+
+/// (owner/repo, PR 番号) ごとに集計する
+fn group_entries(entries: &[CacheEntry]) -> BTreeMap<(String, Option<u64>), Group> {
+    let mut groups: BTreeMap<(String, Option<u64>), Group> = BTreeMap::new();
+    for entry in entries {
+        let group = groups
+            .entry((format!("{}/{}", entry.owner, entry.repo), entry.pr_number))
+            .or_default();
+        group.files += 1;
+        group.size += entry.size;
+        group.modified = group.modified.max(Some(entry.modified));
+    }
+    groups
+}
+
+/// 経過時間の表示（"3d ago"）
+fn age(modified: Option<SystemTime>, now: chrono::DateTime<chrono::Utc>) -> String {
+    modified.map_or_else(String::new, |time| {
+        let time: chrono::DateTime<chrono::Utc> = time.into();
+        crate::format::relative(&time.to_rfc3339(), now)
+    })
+}
+
+/// 一覧の表示（repo ごとに PR を並べる）
+fn list_lines(entries: &[CacheEntry], now: chrono::DateTime<chrono::Utc>) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current_repo = None;
+    for ((repo, pr_number), group) in group_entries(entries) {
+        if current_repo.as_ref() != Some(&repo) {
+            lines.push(repo.clone());
+            current_repo = Some(repo);
+        }
+        let label = pr_number.map_or_else(|| "API responses".to_string(), |n| format!("#{n}"));
+        lines.push(format!(
+            "  {label:<14} {:>4} files {:>10}  {}",
+            group.files,
+            crate::format::bytes(group.size),
+            age(group.modified, now)
+        ));
+    }
+    lines
+}
+
+/// 件数とサイズ（"3 files, 1.2 KB"）
+fn totals(entries: &[&CacheEntry]) -> String {
+    let size = entries.iter().map(|e| e.size).sum();
+    format!("{} files, {}", entries.len(), crate::format::bytes(size))
+}
+
+/// エントリをアーカイブに移す（失敗したファイルは警告して続ける）
+fn archive(entries: &[&CacheEntry]) {
+    let (root, archive) = (cache::cache_root(), cache::archive_root());
+    for entry in entries {
+        if let Err(e) = cache::archive_entry(&root, &archive, &entry.path) {
+            eprintln!("Warning: failed to archive {}: {e}", entry.path.display());
+        }
+    }
+}
+
+/// prism cache list
+pub fn list() -> Result<()> {
+    let entries = cache::scan_entries(&cache::cache_root());
+    let now = chrono::Utc::now();
+    if entries.is_empty() {
+        println!("No cached PRs in {}", cache::cache_root().display());
+    }
+    for line in list_lines(&entries, now) {
+        println!("{line}");
+    }
+    let archived = cache::scan_entries(&cache::archive_root());
+    if !archived.is_empty() {
+        println!(
+            "\nArchived: {} in {} (deleted by `prism cache prune`)",
+            totals(&archived.iter().collect::<Vec<_>>()),
+            cache::archive_root().display()
+        );
+    }
+    Ok(())
+}
+
+/// prism cache clear
+pub fn clear(owner: &str, repo: &str, pr_numbers: &[u64]) -> Result<()> {
+    let entries = cache::scan_entries(&cache::cache_root());
+    let targets: Vec<&CacheEntry> = entries
+        .iter()
+        .filter(|e| e.owner == owner && e.repo == repo)
+        .filter(|e| pr_numbers.is_empty() || e.pr_number.is_some_and(|n| pr_numbers.contains(&n)))
+        .collect();
+    if targets.is_empty() {
+        println!("No cached entries for {owner}/{repo}");
+        return Ok(());
+    }
+    archive(&targets);
+    println!(
+        "Archived {} to {}",
+        totals(&targets),
+        cache::archive_root().display()
+    );
+    Ok(())
+}
+
+/// prism cache prune（client があればマージ済みの PR もアーカイブする）
+pub async fn prune(client: Option<&Octocrab>, days: u64) -> Result<()> {
+    let cutoff = SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60);
+    let entries = cache::scan_entries(&cache::cache_root());
+    let groups = group_entries(&entries);
+
+    let mut stale: HashSet<(String, Option<u64>)> = groups
+        .iter()
+        .filter(|(_, group)| group.modified.is_some_and(|m| m < cutoff))
+        .map(|(key, _)| key.clone())
+        .collect();
+    if let Some(client) = client {
+        for (repo, pr_number) in groups.keys() {
+            let Some(number) = *pr_number else {
+                continue;
+            };
+            if stale.contains(&(repo.clone(), Some(number))) {
+                continue;
+            }
+            let Some((owner, name)) = repo.split_once('/') else {
+                continue;
+            };
+            match crate::github::pr::fetch_pr(client, owner, name, number).await {
+                Ok(pr) if pr.merged_at.is_some() => {
+                    stale.insert((repo.clone(), Some(number)));
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Warning: failed to check {repo}#{number}: {e}"),
+            }
+        }
+    }
+    let targets: Vec<&CacheEntry> = entries
+        .iter()
+        .filter(|e| stale.contains(&(format!("{}/{}", e.owner, e.repo), e.pr_number)))
+        .collect();
+    if !targets.is_empty() {
+        archive(&targets);
+    }
+    println!("Archived {}", totals(&targets));
+
+    let archived = cache::scan_entries(&cache::archive_root());
+    let expired: Vec<&CacheEntry> = archived.iter().filter(|e| e.modified < cutoff).collect();
+    for entry in &expired {
+        if let Err(e) = std::fs::remove_file(&entry.path) {
+            eprintln!("Warning: failed to delete {}: {e}", entry.path.display());
+        }
+    }
+    remove_empty_dirs(&cache::archive_root());
+    println!("Deleted {} from the archive", totals(&expired));
+    Ok(())
+}
+
+/// 空になったディレクトリを消す（アーカイブ直下から再帰的に）
+fn remove_empty_dirs(dir: &Path) {
+    let Ok(read) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in read.flatten() {
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            remove_empty_dirs(&entry.path());
+            // 中身が残っていれば失敗するだけ
+            let _ = std::fs::remove_dir(entry.path());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn entry(repo: &str, pr_number: Option<u64>, size: u64, days_ago: u64) -> CacheEntry {
+        CacheEntry {
+            path: PathBuf::from(format!("/tmp/{repo}/{size}")),
+            owner: "octo".to_string(),
+            repo: repo.to_string(),
+            pr_number,
+            size,
+            modified: SystemTime::now() - Duration::from_secs(days_ago * 24 * 60 * 60),
+        }
+    }
+
+    #[test]
+    fn test_list_lines() {
+        let entries = vec![
+            entry("app", Some(12), 1024, 3),
+            entry("app", Some(12), 1024, 1),
+            entry("app", None, 10, 0),
+            entry("lib", Some(3), 5, 40),
+        ];
+        let groups = group_entries(&entries);
+        assert_eq!(groups[&("octo/app".to_string(), Some(12))].files, 2);
+        assert_eq!(groups[&("octo/app".to_string(), Some(12))].size, 2048);
+
+        let lines = list_lines(&entries, chrono::Utc::now());
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "octo/app");
+        assert!(lines[1].starts_with("  API responses"));
+        assert!(lines[2].starts_with("  #12 "));
+        assert!(lines[2].contains("2 files"));
+        assert!(lines[2].contains("2.0 KB"));
+        assert!(lines[2].ends_with("1d ago"));
+        assert_eq!(lines[3], "octo/lib");
+        assert_eq!(
+            totals(&entries.iter().collect::<Vec<_>>()),
+            "4 files, 2.0 KB"
+        );
+    }
+}
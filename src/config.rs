@@ -0,0 +1,476 @@
+use crate::i18n::Language;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// テーマ設定（auto は起動時にターミナル背景色から判定）
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreference {
+    #[default]
+    Auto,
+    Light,
+    Dark,
+}
+
+/// キーバインドのプリセット（emacs は Ctrl+N / Ctrl+P を上下移動に追加）
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Keymap {
+    #[default]
+    Vim,
+    Emacs,
+}
+
+/// 日時の表示形式（relative は "3h ago"、absolute はローカル時刻）
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeFormat {
+    #[default]
+    Relative,
+    Absolute,
+}
+
+/// ネットワーク操作のタイムアウトとリトライ（REST・GraphQL・画像ダウンロード共通）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// 1 回の試行のタイムアウト（秒）
+    pub timeout_secs: u64,
+    /// 一時的な失敗（タイムアウト・接続エラー・5xx）のリトライ回数
+    pub retries: u32,
+    /// 最初のリトライまでの待ち時間（ミリ秒、以降は倍々）
+    pub backoff_ms: u64,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 30,
+            retries: 2,
+            backoff_ms: 500,
+        }
+    }
+}
+
+/// 日時と SHA の表示形式（ヘッダー・Commits・Conversation・CommentView 共通）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// 絶対表記の日時形式（chrono の strftime 書式）
+    pub datetime_format: String,
+    /// 短縮 SHA の桁数（4〜40）
+    pub sha_length: usize,
+    /// Mermaid / PlantUML の図を外部プログラム（mermaid-ascii / plantuml）で ASCII 描画する
+    pub ascii_diagrams: bool,
+    /// ステータスメッセージ（情報）を表示しておく秒数（エラーは Esc で消すまで残る）
+    pub status_secs: u64,
+    /// gm で見返せるステータスメッセージの件数
+    pub status_history: usize,
+    /// Conversation の作者の横にアバター（画像プロトコルのない端末ではイニシャル）を表示する
+    pub avatars: bool,
+    /// URL やリンクを OSC 8 で囲み、対応端末でクリックして開けるようにする（崩れる端末では false に）
+    pub hyperlinks: bool,
+}
+
+impl DisplayConfig {
+    pub const DEFAULT_DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M %z";
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            datetime_format: Self::DEFAULT_DATETIME_FORMAT.to_string(),
+            sha_length: 7,
+            ascii_diagrams: false,
+            status_secs: 3,
+            status_history: 50,
+            avatars: false,
+            hyperlinks: true,
+        }
+    }
+}
+
+/// diff アルゴリズム（ローカルで計算する diff に使う git diff --diff-algorithm）
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffAlgorithm {
+    #[default]
+    Myers,
+    Minimal,
+    Patience,
+    Histogram,
+}
+
+impl DiffAlgorithm {
+    pub fn as_git_arg(self) -> &'static str {
+        match self {
+            DiffAlgorithm::Myers => "myers",
+            DiffAlgorithm::Minimal => "minimal",
+            DiffAlgorithm::Patience => "patience",
+            DiffAlgorithm::Histogram => "histogram",
+        }
+    }
+}
+
+/// diff の表示設定（CLI フラグが優先）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiffConfig {
+    /// ローカルで計算する diff（F で読み込む大きなファイル）のアルゴリズム
+    pub algorithm: DiffAlgorithm,
+    /// ローカルで計算する diff の前後の行数
+    pub context_lines: u32,
+    /// タブの表示幅（0 ならそのまま）
+    pub tab_width: usize,
+    /// delta に追加で渡す引数（--color-only のため行の構造を変えるオプションは効かない）
+    pub delta_args: Vec<String>,
+    /// ワークフローファイルの hunk header に変更されたジョブ・ステップ名を付ける
+    pub workflow_hints: bool,
+}
+
+impl Default for DiffConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: DiffAlgorithm::default(),
+            context_lines: 3,
+            tab_width: 4,
+            delta_args: Vec::new(),
+            workflow_hints: true,
+        }
+    }
+}
+
+/// 大きな PR の判定しきい値（どれかを超えたら全コミットのファイルを先読みせず概要から開く）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LargePrConfig {
+    /// 変更ファイル数
+    pub max_files: u64,
+    /// コミット数
+    pub max_commits: u64,
+    /// 追加・削除行数の合計
+    pub max_lines: u64,
+}
+
+impl Default for LargePrConfig {
+    fn default() -> Self {
+        Self {
+            max_files: 300,
+            max_commits: 100,
+            max_lines: 20_000,
+        }
+    }
+}
+
+impl LargePrConfig {
+    /// しきい値のどれかを超えているか
+    pub fn exceeded_by(&self, files: u64, commits: u64, lines: u64) -> bool {
+        files > self.max_files || commits > self.max_commits || lines > self.max_lines
+    }
+}
+
+/// キャッシュのメモリ予算（MB、おおよその値。超えたら古いものから捨てる）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoryConfig {
+    /// デコード済み画像
+    pub media_mb: usize,
+    /// ハイライト済みの diff
+    pub highlight_mb: usize,
+    /// コミットごとのファイル差分（超えたら古いコミットを捨て、選択時に再取得する）
+    pub files_mb: usize,
+}
+
+impl Default for MemoryConfig {
+    fn default() -> Self {
+        Self {
+            media_mb: 256,
+            highlight_mb: 64,
+            files_mb: 512,
+        }
+    }
+}
+
+impl MemoryConfig {
+    pub fn media_bytes(&self) -> usize {
+        self.media_mb.saturating_mul(1024 * 1024)
+    }
+
+    pub fn highlight_bytes(&self) -> usize {
+        self.highlight_mb.saturating_mul(1024 * 1024)
+    }
+
+    pub fn files_bytes(&self) -> usize {
+        self.files_mb.saturating_mul(1024 * 1024)
+    }
+}
+
+/// テストファイルの判定と File Tree でのまとめ表示（t で切替）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TestFilesConfig {
+    /// テストファイルとみなすパスの glob（`**` は任意の階層、`*` と `?` は 1 階層内）
+    pub patterns: Vec<String>,
+    /// 起動時からテストファイルをまとめて表示する
+    pub group: bool,
+    /// まとめ表示中はテストファイルを viewed の進捗に数えない
+    pub exclude_from_progress: bool,
+}
+
+impl Default for TestFilesConfig {
+    fn default() -> Self {
+        Self {
+            patterns: [
+                "**/tests/**",
+                "**/test/**",
+                "**/__tests__/**",
+                "**/spec/**",
+                "**/*_test.*",
+                "**/*.test.*",
+                "**/*.spec.*",
+                "**/test_*.py",
+            ]
+            .map(String::from)
+            .to_vec(),
+            group: false,
+            exclude_from_progress: false,
+        }
+    }
+}
+
+/// コメントへの画像添付（Ctrl+T）
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AttachConfig {
+    /// 画像をアップロードして URL を出力するコマンド（画像のパスは $1。未設定ならドラッグ＆ドロップを案内）
+    pub upload_command: Option<String>,
+}
+
+/// レビュー待ち時間の目安（自分への依頼がこれを超えたら強調する）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReviewConfig {
+    /// 依頼から何時間で期限切れとみなすか（0 で強調しない）
+    pub sla_hours: u64,
+    /// レビュー本文の初期値（送信イベントごと）
+    pub templates: ReviewTemplates,
+}
+
+impl Default for ReviewConfig {
+    fn default() -> Self {
+        Self {
+            sla_hours: 24,
+            templates: ReviewTemplates::default(),
+        }
+    }
+}
+
+/// レビュー送信時に本文へ入れておくテンプレート（空なら何も入れない）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReviewTemplates {
+    pub comment: String,
+    pub approve: String,
+    pub request_changes: String,
+}
+
+/// ユーザー設定（prism init で生成）
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub theme: ThemePreference,
+    #[serde(default)]
+    pub keymap: Keymap,
+    /// 起動時に新しいリリースを確認するか（opt-in）
+    #[serde(default)]
+    pub check_update: bool,
+    #[serde(default)]
+    pub time_format: TimeFormat,
+    /// 表示言語（GH_PRISM_LANG 環境変数が優先）
+    #[serde(default)]
+    pub language: Language,
+    /// tmux 内ではペイン変数 @prism_pr に owner/repo#番号 をセットする（opt-in）
+    #[serde(default)]
+    pub tmux_status: bool,
+    /// マウスキャプチャを無効にする（端末のテキスト選択を使う）
+    #[serde(default)]
+    pub no_mouse: bool,
+    /// 確認なしで実行してよい外部プログラム（"*" で全て許可）
+    #[serde(default)]
+    pub allowed_commands: Vec<String>,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
+    #[serde(default)]
+    pub large_pr: LargePrConfig,
+    #[serde(default)]
+    pub memory: MemoryConfig,
+    #[serde(default)]
+    pub diff: DiffConfig,
+    #[serde(default)]
+    pub tests: TestFilesConfig,
+    #[serde(default)]
+    pub attach: AttachConfig,
+    #[serde(default)]
+    pub review: ReviewConfig,
+}
+
+/// 設定ファイルのパス（$XDG_CONFIG_HOME/gh-prism/config.json、未設定なら ~/.config 配下）
+pub fn config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("gh-prism").join("config.json"))
+}
+
+/// 設定ファイルを読み込む（存在しない・壊れている場合はデフォルト）
+pub fn read_config() -> Config {
+    config_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+/// 設定ファイルを書き込み、書き込んだパスを返す
+pub fn write_config(config: &Config) -> std::io::Result<PathBuf> {
+    let path = config_path().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "could not determine config directory",
+        )
+    })?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(config).map_err(std::io::Error::other)?;
+    std::fs::write(&path, json)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_roundtrip() {
+        let config = Config {
+            theme: ThemePreference::Light,
+            keymap: Keymap::Emacs,
+            check_update: true,
+            time_format: TimeFormat::Absolute,
+            language: Language::Ja,
+            tmux_status: true,
+            no_mouse: true,
+            allowed_commands: vec!["delta".to_string()],
+            network: NetworkConfig {
+                timeout_secs: 10,
+                retries: 0,
+                backoff_ms: 250,
+            },
+            display: DisplayConfig {
+                datetime_format: "%m/%d %H:%M".to_string(),
+                sha_length: 10,
+                ascii_diagrams: true,
+                status_secs: 5,
+                status_history: 20,
+                avatars: true,
+                hyperlinks: false,
+            },
+            large_pr: LargePrConfig {
+                max_files: 50,
+                max_commits: 20,
+                max_lines: 1000,
+            },
+            memory: MemoryConfig {
+                media_mb: 64,
+                highlight_mb: 16,
+                files_mb: 128,
+            },
+            diff: DiffConfig {
+                algorithm: DiffAlgorithm::Patience,
+                context_lines: 5,
+                tab_width: 8,
+                delta_args: vec!["--syntax-theme=Nord".to_string()],
+                workflow_hints: false,
+            },
+            tests: TestFilesConfig {
+                patterns: vec!["**/*_spec.lua".to_string()],
+                group: true,
+                exclude_from_progress: true,
+            },
+            attach: AttachConfig {
+                upload_command: Some("imgup".to_string()),
+            },
+            review: ReviewConfig {
+                sla_hours: 48,
+                templates: ReviewTemplates {
+                    comment: String::new(),
+                    approve: "LGTM".to_string(),
+                    request_changes: "Blocking:\n".to_string(),
+                },
+            },
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            json,
+            r#"{"theme":"light","keymap":"emacs","check_update":true,"time_format":"absolute","language":"ja","tmux_status":true,"no_mouse":true,"allowed_commands":["delta"],"network":{"timeout_secs":10,"retries":0,"backoff_ms":250},"display":{"datetime_format":"%m/%d %H:%M","sha_length":10,"ascii_diagrams":true,"status_secs":5,"status_history":20,"avatars":true,"hyperlinks":false},"large_pr":{"max_files":50,"max_commits":20,"max_lines":1000},"memory":{"media_mb":64,"highlight_mb":16,"files_mb":128},"diff":{"algorithm":"patience","context_lines":5,"tab_width":8,"delta_args":["--syntax-theme=Nord"],"workflow_hints":false},"tests":{"patterns":["**/*_spec.lua"],"group":true,"exclude_from_progress":true},"attach":{"upload_command":"imgup"},"review":{"sla_hours":48,"templates":{"comment":"","approve":"LGTM","request_changes":"Blocking:\n"}}}"#
+        );
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+    }
+
+    #[test]
+    fn test_config_missing_fields_use_defaults() {
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.theme, ThemePreference::Auto);
+        assert_eq!(config.keymap, Keymap::Vim);
+        assert!(!config.check_update);
+        assert_eq!(config.time_format, TimeFormat::Relative);
+        assert_eq!(config.language, Language::Auto);
+        assert!(!config.tmux_status);
+        assert!(!config.no_mouse);
+        assert!(config.allowed_commands.is_empty());
+        assert_eq!(config.network, NetworkConfig::default());
+        assert_eq!(config.display, DisplayConfig::default());
+        assert_eq!(config.large_pr, LargePrConfig::default());
+        assert_eq!(config.memory, MemoryConfig::default());
+        assert_eq!(config.diff, DiffConfig::default());
+        assert_eq!(config.tests, TestFilesConfig::default());
+        assert_eq!(config.attach.upload_command, None);
+        assert_eq!(config.review.sla_hours, 24);
+        assert_eq!(config.review.templates, ReviewTemplates::default());
+    }
+
+    #[test]
+    fn test_network_config_partial_fields() {
+        let config: Config = serde_json::from_str(r#"{"network":{"retries":5}}"#).unwrap();
+        assert_eq!(config.network.retries, 5);
+        assert_eq!(config.network.timeout_secs, 30);
+        assert_eq!(config.network.backoff_ms, 500);
+    }
+
+    #[test]
+    fn test_display_config_partial_fields() {
+        let config: Config = serde_json::from_str(r#"{"display":{"sha_length":12}}"#).unwrap();
+        assert_eq!(config.display.sha_length, 12);
+        assert_eq!(
+            config.display.datetime_format,
+            DisplayConfig::DEFAULT_DATETIME_FORMAT
+        );
+        assert!(!config.display.ascii_diagrams);
+        assert_eq!(config.display.status_secs, 3);
+        assert!(!config.display.avatars);
+        assert!(config.display.hyperlinks);
+    }
+
+    #[test]
+    fn test_large_pr_exceeded_by() {
+        let large_pr = LargePrConfig::default();
+        assert!(!large_pr.exceeded_by(300, 100, 20_000));
+        assert!(large_pr.exceeded_by(301, 1, 1));
+        assert!(large_pr.exceeded_by(1, 101, 1));
+        assert!(large_pr.exceeded_by(1, 1, 20_001));
+    }
+}
@@ -0,0 +1,164 @@
+use color_eyre::{Result, eyre::eyre};
+use std::collections::HashMap;
+
+/// LCOV レポートのダウンロードタイムアウト（秒）
+const COVERAGE_FETCH_TIMEOUT_SECS: u64 = 10;
+
+/// カバレッジレポート（ファイルパス → 行番号 → 実行回数）
+#[derive(Debug, Default, Clone)]
+pub struct CoverageReport {
+    files: HashMap<String, HashMap<usize, u64>>,
+}
+
+impl CoverageReport {
+    /// LCOV 形式のテキストをパースする（SF / DA / end_of_record のみ解釈）
+    pub fn parse_lcov(text: &str) -> Self {
+        let mut files: HashMap<String, HashMap<usize, u64>> = HashMap::new();
+        let mut current: Option<String> = None;
+
+        for line in text.lines() {
+            let line = line.trim();
+            if let Some(path) = line.strip_prefix("SF:") {
+                current = Some(normalize_path(path));
+            } else if let Some(rest) = line.strip_prefix("DA:")
+                && let Some(path) = &current
+            {
+                // DA:<line>,<hits>[,<checksum>]
+                let mut parts = rest.split(',');
+                if let (Some(line_no), Some(hits)) = (parts.next(), parts.next())
+                    && let (Ok(line_no), Ok(hits)) = (line_no.parse(), hits.parse::<u64>())
+                {
+                    let entry = files.entry(path.clone()).or_default();
+                    // 同一行が複数回出現する場合は合算
+                    *entry.entry(line_no).or_insert(0) += hits;
+                }
+            } else if line == "end_of_record" {
+                current = None;
+            }
+        }
+
+        Self { files }
+    }
+
+    /// レポートが空か判定
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// 指定ファイルの行データを取得（SF が絶対パスの場合に備えて末尾一致でも探す）
+    pub fn file_lines(&self, filename: &str) -> Option<&HashMap<usize, u64>> {
+        if let Some(lines) = self.files.get(filename) {
+            return Some(lines);
+        }
+        let suffix = format!("/{}", filename);
+        self.files
+            .iter()
+            .filter(|(path, _)| path.ends_with(&suffix))
+            // 複数候補がある場合は最短パスを優先（決定的にするため）
+            .min_by_key(|(path, _)| path.len())
+            .map(|(_, lines)| lines)
+    }
+
+    /// 指定行がカバーされているか（データなしの行は None）
+    #[cfg(test)]
+    pub fn is_covered(&self, filename: &str, line: usize) -> Option<bool> {
+        self.file_lines(filename)
+            .and_then(|lines| lines.get(&line))
+            .map(|&hits| hits > 0)
+    }
+}
+
+/// SF のパスを正規化（"./" 接頭辞と Windows 区切り文字を除去）
+fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_string()
+}
+
+/// ローカルファイルパスまたは URL から LCOV レポートを読み込む
+pub async fn load_coverage(source: &str) -> Result<CoverageReport> {
+    let text = if source.starts_with("http://") || source.starts_with("https://") {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(COVERAGE_FETCH_TIMEOUT_SECS))
+            .build()?;
+        let start = std::time::Instant::now();
+        let resp = client.get(source).send().await?;
+        if !resp.status().is_success() {
+            return Err(eyre!("HTTP {}", resp.status()));
+        }
+        let text = resp.text().await?;
+        crate::github::metrics::record_call("coverage", start.elapsed(), text.len());
+        text
+    } else {
+        std::fs::read_to_string(source)?
+    };
+    Ok(CoverageReport::parse_lcov(&text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_LCOV: &str = "\
+TN:
+SF:src/main.rs
+DA:1,1
+DA:2,0
+DA:3,5
+end_of_record
+SF:/home/user/project/src/lib.rs
+DA:10,0
+DA:11,2
+end_of_record
+";
+
+    #[test]
+    fn test_parse_lcov_basic() {
+        let report = CoverageReport::parse_lcov(SAMPLE_LCOV);
+        assert!(!report.is_empty());
+        assert_eq!(report.is_covered("src/main.rs", 1), Some(true));
+        assert_eq!(report.is_covered("src/main.rs", 2), Some(false));
+        assert_eq!(report.is_covered("src/main.rs", 3), Some(true));
+        assert_eq!(report.is_covered("src/main.rs", 4), None);
+    }
+
+    #[test]
+    fn test_absolute_path_matches_by_suffix() {
+        let report = CoverageReport::parse_lcov(SAMPLE_LCOV);
+        assert_eq!(report.is_covered("src/lib.rs", 10), Some(false));
+        assert_eq!(report.is_covered("src/lib.rs", 11), Some(true));
+        // 部分一致はしない
+        assert_eq!(report.is_covered("b.rs", 11), None);
+    }
+
+    #[test]
+    fn test_unknown_file_returns_none() {
+        let report = CoverageReport::parse_lcov(SAMPLE_LCOV);
+        assert_eq!(report.is_covered("src/other.rs", 1), None);
+    }
+
+    #[test]
+    fn test_dot_slash_prefix_is_normalized() {
+        let report = CoverageReport::parse_lcov("SF:./src/a.rs\nDA:1,3\nend_of_record\n");
+        assert_eq!(report.is_covered("src/a.rs", 1), Some(true));
+    }
+
+    #[test]
+    fn test_duplicate_lines_are_summed() {
+        let lcov = "SF:a.rs\nDA:1,0\nend_of_record\nSF:a.rs\nDA:1,2\nend_of_record\n";
+        let report = CoverageReport::parse_lcov(lcov);
+        assert_eq!(report.is_covered("a.rs", 1), Some(true));
+    }
+
+    #[test]
+    fn test_malformed_lines_are_ignored() {
+        let lcov = "DA:1,1\nSF:a.rs\nDA:x,1\nDA:2\nDA:3,1,abc\nend_of_record\n";
+        let report = CoverageReport::parse_lcov(lcov);
+        assert_eq!(report.is_covered("a.rs", 3), Some(true));
+        assert_eq!(report.file_lines("a.rs").map(|l| l.len()), Some(1));
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert!(CoverageReport::parse_lcov("").is_empty());
+    }
+}
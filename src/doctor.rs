@@ -0,0 +1,378 @@
+//! `prism doctor`: 起動に必要な環境を確認し、問題ごとに対処法を表示する
+
+use crate::config;
+use crate::exec;
+use crate::github;
+use color_eyre::Result;
+use ratatui_image::picker::{Picker, ProtocolType};
+use std::io::IsTerminal;
+
+/// 必須のトークンスコープ（private リポジトリの PR 取得とレビュー送信）
+const REQUIRED_SCOPES: [&str; 1] = ["repo"];
+/// あると便利なトークンスコープ（チームへのレビュー依頼の表示）
+const RECOMMENDED_SCOPES: [&str; 1] = ["read:org"];
+/// レイアウトが崩れない最小の端末サイズ
+const MIN_TERMINAL_SIZE: (u16, u16) = (80, 24);
+
+/// チェック結果の重要度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    /// 動作はするが一部の機能が使えない
+    Warn,
+    /// prism が起動しない・PR を取得できない
+    Fail,
+}
+
+impl Status {
+    fn mark(self) -> &'static str {
+        match self {
+            Status::Ok => "✓",
+            Status::Warn => "!",
+            Status::Fail => "✗",
+        }
+    }
+}
+
+/// 1 項目のチェック結果
+#[derive(Debug, Clone)]
+struct Check {
+    name: &'static str,
+    status: Status,
+    detail: String,
+    /// 問題がある場合の対処法
+    fix: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn problem(
+        name: &'static str,
+        status: Status,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// コマンドの stdout と stderr を連結して返す（許可されていないか起動できなければ None）
+fn command_output(program: &str, args: &[&str]) -> Option<(bool, String)> {
+    let output = exec::command(program)?.args(args).output().ok()?;
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Some((output.status.success(), text))
+}
+
+/// `gh auth status` の出力からトークンスコープを取り出す（fine-grained token などで表示がなければ None）
+fn parse_token_scopes(auth_status: &str) -> Option<Vec<String>> {
+    let line = auth_status.lines().find(|l| l.contains("Token scopes:"))?;
+    let scopes = line.split_once("Token scopes:")?.1;
+    Some(
+        scopes
+            .split(',')
+            .map(|s| s.trim().trim_matches(|c| c == '\'' || c == '"').to_string())
+            .filter(|s| !s.is_empty() && s != "none")
+            .collect(),
+    )
+}
+
+fn check_gh() -> Check {
+    match command_output("gh", &["--version"]) {
+        Some((true, text)) => Check::ok("gh", text.lines().next().unwrap_or("installed").trim()),
+        _ => Check::problem(
+            "gh",
+            Status::Fail,
+            "not found",
+            "Install GitHub CLI: https://cli.github.com",
+        ),
+    }
+}
+
+fn check_auth() -> Check {
+    let Some((logged_in, text)) = command_output("gh", &["auth", "status"]) else {
+        return Check::problem(
+            "gh auth",
+            Status::Fail,
+            "gh not available",
+            "Install gh first",
+        );
+    };
+    if !logged_in {
+        return Check::problem(
+            "gh auth",
+            Status::Fail,
+            "not logged in",
+            "Run `gh auth login`",
+        );
+    }
+    let Some(scopes) = parse_token_scopes(&text) else {
+        return Check::ok("gh auth", "logged in (token scopes not reported)");
+    };
+    let missing = |wanted: &[&str]| -> Vec<String> {
+        wanted
+            .iter()
+            .filter(|w| !scopes.iter().any(|s| s == *w))
+            .map(|w| w.to_string())
+            .collect()
+    };
+    let (required, recommended) = (missing(&REQUIRED_SCOPES), missing(&RECOMMENDED_SCOPES));
+    let detail = format!("logged in, scopes: {}", scopes.join(", "));
+    let refresh = |scopes: &[String]| format!("Run `gh auth refresh -s {}`", scopes.join(","));
+    if !required.is_empty() {
+        Check::problem("gh auth", Status::Fail, detail, refresh(&required))
+    } else if !recommended.is_empty() {
+        Check::problem(
+            "gh auth",
+            Status::Warn,
+            detail,
+            format!("{} to show team review requests", refresh(&recommended)),
+        )
+    } else {
+        Check::ok("gh auth", detail)
+    }
+}
+
+fn check_api() -> Check {
+    let args = [
+        "api",
+        "rate_limit",
+        "-q",
+        r#".resources.core | "\(.remaining)/\(.limit)""#,
+    ];
+    match github::metrics::run_gh("rate limit", &args) {
+        Ok(output) if output.status.success() => {
+            let remaining = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            Check::ok(
+                "API",
+                format!("reachable, rate limit remaining {remaining}"),
+            )
+        }
+        Ok(output) => Check::problem(
+            "API",
+            Status::Fail,
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .next()
+                .unwrap_or("request failed")
+                .trim()
+                .to_string(),
+            "Check `gh auth status` and your network / proxy settings",
+        ),
+        Err(e) => Check::problem(
+            "API",
+            Status::Fail,
+            e.to_string(),
+            "Check your network or raise network.timeout_secs in the config",
+        ),
+    }
+}
+
+fn check_images() -> Check {
+    match Picker::from_query_stdio().map(|p| p.protocol_type()) {
+        Ok(ProtocolType::Halfblocks) => Check::problem(
+            "images",
+            Status::Warn,
+            "halfblocks only",
+            "Use a terminal with kitty, sixel or iTerm2 graphics for sharp images",
+        ),
+        Ok(protocol) => Check::ok("images", format!("{protocol:?} protocol")),
+        Err(_) => Check::problem(
+            "images",
+            Status::Warn,
+            "could not query terminal",
+            "Run doctor directly in your terminal (not piped); the media viewer may be limited",
+        ),
+    }
+}
+
+fn check_delta() -> Check {
+    // 許可されていないプログラムは、バージョン確認のためでも起動しない
+    if !exec::is_allowed("delta") {
+        return Check::problem(
+            "delta",
+            Status::Warn,
+            "not in allowed_commands (not checked)",
+            "Answer `a` when prism asks to run it or add \"delta\" to allowed_commands",
+        );
+    }
+    let Some((true, text)) = command_output("delta", &["--version"]) else {
+        return Check::problem(
+            "delta",
+            Status::Warn,
+            "not found (diffs use basic coloring)",
+            "Install delta: https://github.com/dandavison/delta",
+        );
+    };
+    Check::ok("delta", text.lines().next().unwrap_or("installed").trim())
+}
+
+fn check_cache_dir() -> Check {
+    let dir = github::cache::cache_root();
+    let probe = dir.join(".doctor");
+    let result = std::fs::create_dir_all(&dir)
+        .and_then(|()| std::fs::write(&probe, b"ok"))
+        .and_then(|()| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => Check::ok("cache", format!("{} is writable", dir.display())),
+        Err(e) => Check::problem(
+            "cache",
+            Status::Warn,
+            format!("{}: {e}", dir.display()),
+            "Fix the directory permissions or set TMPDIR to a writable directory (use --no-cache meanwhile)",
+        ),
+    }
+}
+
+fn check_state_dir() -> Check {
+    let Some(dir) = github::cache::state_root() else {
+        return Check::problem(
+            "state",
+            Status::Warn,
+            "no state directory (notes, checklists and viewed files are not saved)",
+            "Set XDG_STATE_HOME or HOME",
+        );
+    };
+    let probe = dir.join(".doctor");
+    let result = crate::platform::private_dir_builder()
+        .recursive(true)
+        .create(&dir)
+        .and_then(|()| std::fs::write(&probe, b"ok"))
+        .and_then(|()| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => Check::ok("state", format!("{} is writable", dir.display())),
+        Err(e) => Check::problem(
+            "state",
+            Status::Warn,
+            format!("{}: {e}", dir.display()),
+            "Fix the directory permissions or set XDG_STATE_HOME to a writable directory",
+        ),
+    }
+}
+
+fn check_terminal() -> Check {
+    if !std::io::stdout().is_terminal() {
+        return Check::problem(
+            "terminal",
+            Status::Fail,
+            "stdout is not a terminal",
+            "Run prism in an interactive terminal",
+        );
+    }
+    let truecolor =
+        std::env::var("COLORTERM").is_ok_and(|v| matches!(v.as_str(), "truecolor" | "24bit"));
+    let colors = if truecolor { "truecolor" } else { "256 colors" };
+    match crossterm::terminal::size() {
+        Ok((cols, rows)) if cols < MIN_TERMINAL_SIZE.0 || rows < MIN_TERMINAL_SIZE.1 => {
+            Check::problem(
+                "terminal",
+                Status::Warn,
+                format!("{cols}x{rows}, {colors}"),
+                format!(
+                    "Enlarge the window to at least {}x{}",
+                    MIN_TERMINAL_SIZE.0, MIN_TERMINAL_SIZE.1
+                ),
+            )
+        }
+        Ok((cols, rows)) => Check::ok("terminal", format!("{cols}x{rows}, {colors}")),
+        Err(e) => Check::problem(
+            "terminal",
+            Status::Warn,
+            format!("size unknown: {e}"),
+            "Check that TERM is set correctly",
+        ),
+    }
+}
+
+/// チェック結果を表にする（名前の列をそろえ、対処法は次の行に表示）
+fn render_table(checks: &[Check]) -> String {
+    let width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for check in checks {
+        out.push_str(&format!(
+            "{} {:width$}  {}\n",
+            check.status.mark(),
+            check.name,
+            check.detail
+        ));
+        if let Some(fix) = &check.fix {
+            out.push_str(&format!("  {:width$}  → {fix}\n", ""));
+        }
+    }
+    out
+}
+
+/// `prism doctor`: 全項目を確認して表を表示する（起動できない問題があれば終了コード 1）
+pub fn run_doctor() -> Result<()> {
+    let config = config::read_config();
+    exec::init(config.allowed_commands.iter().cloned().collect(), false);
+    github::retry::init(config.network);
+    let gh = check_gh();
+    let gh_ok = gh.status == Status::Ok;
+    let mut checks = vec![gh];
+    if gh_ok {
+        checks.push(check_auth());
+        checks.push(check_api());
+    }
+    checks.extend([
+        check_images(),
+        check_delta(),
+        check_cache_dir(),
+        check_state_dir(),
+        check_terminal(),
+    ]);
+    print!("{}", render_table(&checks));
+    if checks.iter().any(|c| c.status == Status::Fail) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_token_scopes() {
+        let status = "github.com\n  ✓ Logged in to github.com account octocat (keyring)\n  - Token scopes: 'gist', 'read:org', 'repo'\n";
+        assert_eq!(
+            parse_token_scopes(status).unwrap(),
+            vec!["gist", "read:org", "repo"]
+        );
+        assert_eq!(
+            parse_token_scopes("  - Token scopes: none").unwrap(),
+            Vec::<String>::new()
+        );
+        assert!(parse_token_scopes("  ✓ Logged in to github.com").is_none());
+    }
+
+    #[test]
+    fn test_render_table() {
+        let checks = [
+            Check::ok("gh", "gh version 2.60.0"),
+            Check::problem(
+                "gh auth",
+                Status::Fail,
+                "not logged in",
+                "Run `gh auth login`",
+            ),
+        ];
+        assert_eq!(
+            render_table(&checks),
+            "✓ gh       gh version 2.60.0\n✗ gh auth  not logged in\n           → Run `gh auth login`\n"
+        );
+    }
+}
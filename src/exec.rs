@@ -0,0 +1,191 @@
+//! 外部コマンド実行の窓口（許可リストと初回確認）
+//!
+//! gh / git 以外の外部プログラムは、設定の `allowed_commands` にあるか
+//! 使おうとしたときの確認で許可されたものだけを実行する。信頼できないリポジトリで
+//! prism を開いても、想定外の実行ファイルが起動されないようにするため。
+
+use crate::config;
+use std::collections::HashSet;
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+
+/// 常に許可するプログラム（prism の動作に必須）
+const CORE_PROGRAMS: [&str; 2] = ["gh", "git"];
+/// allowed_commands でこれを指定すると全プログラムを許可する
+const ALLOW_ALL: &str = "*";
+
+/// 実行の許可状態
+#[derive(Debug, Default)]
+struct Permissions {
+    /// 許可されたプログラム
+    allowed: HashSet<String>,
+    /// 今回の起動で拒否されたプログラム（もう確認しない）
+    denied: HashSet<String>,
+    /// 未許可のまま呼ばれ、確認を待っているプログラム（呼ばれた順）
+    pending: Vec<String>,
+    /// 未許可のプログラムを確認するか（TUI のみ。batch や doctor は許可リストだけで判断する）
+    ask: bool,
+}
+
+impl Permissions {
+    /// プログラムを実行してよいか（未許可で確認できるものは確認待ちに積む）
+    fn check(&mut self, program: &str) -> bool {
+        if self.allowed.contains(ALLOW_ALL) || self.allowed.contains(program) {
+            return true;
+        }
+        // インストールされていないものは聞いても使えないので確認しない
+        if self.ask
+            && !self.denied.contains(program)
+            && !self.pending.iter().any(|p| p == program)
+            && on_path(program)
+        {
+            self.pending.push(program.to_string());
+        }
+        false
+    }
+
+    /// 確認を待っているプログラムを古い順に 1 つ取り出す
+    fn take_pending(&mut self) -> Option<String> {
+        (!self.pending.is_empty()).then(|| self.pending.remove(0))
+    }
+
+    /// 回答を反映する（拒否したものは今回の起動ではもう確認しない）
+    fn apply(&mut self, program: &str, answer: Answer) {
+        if answer == Answer::Deny {
+            self.denied.insert(program.to_string());
+        } else {
+            self.allowed.insert(program.to_string());
+        }
+    }
+}
+
+/// 今回の起動の許可状態（未初期化なら制限しない: テストや init サブコマンド）
+static PERMISSIONS: OnceLock<Mutex<Permissions>> = OnceLock::new();
+
+/// 許可状態をロックして操作する（未初期化なら None）
+fn with_permissions<T>(f: impl FnOnce(&mut Permissions) -> T) -> Option<T> {
+    let permissions = PERMISSIONS.get()?;
+    Some(f(&mut permissions
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())))
+}
+
+/// 許可されたプログラムをセットする（起動時に 1 回だけ。ask なら未許可のものは使うときに確認する）
+pub fn init(allowed: HashSet<String>, ask: bool) {
+    let _ = PERMISSIONS.set(Mutex::new(Permissions {
+        allowed,
+        ask,
+        ..Permissions::default()
+    }));
+}
+
+/// プログラムの実行が許可されているか（未許可で確認できるものは確認待ちに積む）
+pub fn is_allowed(program: &str) -> bool {
+    CORE_PROGRAMS.contains(&program)
+        || with_permissions(|permissions| permissions.check(program)).unwrap_or(true)
+}
+
+/// 許可されていれば Command を返す（外部プログラムはすべてここを経由して起動する）
+pub fn command(program: &str) -> Option<Command> {
+    is_allowed(program).then(|| Command::new(program))
+}
+
+/// 許可されていれば Command を返し、されていなければ PermissionDenied のエラーにする
+pub fn require(program: &str) -> std::io::Result<Command> {
+    command(program).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("{program} is not in allowed_commands"),
+        )
+    })
+}
+
+/// 確認を待っているプログラムの数（操作の前後で比べて、確認が必要になったかを知る）
+pub fn pending_count() -> usize {
+    with_permissions(|permissions| permissions.pending.len()).unwrap_or(0)
+}
+
+/// 確認を待っているプログラムを 1 つ取り出す（TUI がダイアログで確認する）
+pub fn take_pending() -> Option<String> {
+    with_permissions(Permissions::take_pending).flatten()
+}
+
+/// 確認への回答
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    Once,
+    Always,
+    Deny,
+}
+
+/// 確認への回答を反映する（"always" は config の allowed_commands に保存し、次回から確認しない）
+pub fn answer(program: &str, answer: Answer) -> std::io::Result<()> {
+    with_permissions(|permissions| permissions.apply(program, answer));
+    if answer == Answer::Always {
+        let mut config = config::read_config();
+        if !config.allowed_commands.iter().any(|p| p == program) {
+            config.allowed_commands.push(program.to_string());
+            config::write_config(&config)?;
+        }
+    }
+    Ok(())
+}
+
+/// PATH 上に実行ファイルがあるか（確認前なので実行はしない。Windows では .exe 付きも探す）
+fn on_path(program: &str) -> bool {
+    let with_suffix = format!("{program}{}", std::env::consts::EXE_SUFFIX);
+    std::env::var_os("PATH").is_some_and(|paths| {
+        std::env::split_paths(&paths)
+            .any(|dir| dir.join(program).is_file() || dir.join(&with_suffix).is_file())
+    })
+}
+
+/// コメントの編集に使うエディタ（$VISUAL → $EDITOR → vi、"code --wait" のような引数付きも可）
+pub fn editor_command() -> Vec<String> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unapproved_program_is_asked_once() {
+        let mut permissions = Permissions {
+            allowed: ["delta".to_string()].into(),
+            ask: true,
+            ..Permissions::default()
+        };
+        assert!(permissions.check("delta"));
+        // PATH にあるものだけ確認待ちに積み、何度呼ばれても 1 回だけ聞く
+        assert!(!permissions.check("sh"));
+        assert!(!permissions.check("sh"));
+        assert!(!permissions.check("prism-not-installed"));
+        assert_eq!(permissions.take_pending(), Some("sh".to_string()));
+        assert_eq!(permissions.take_pending(), None);
+
+        permissions.apply("sh", Answer::Deny);
+        assert!(!permissions.check("sh"));
+        assert_eq!(permissions.take_pending(), None);
+
+        permissions.apply("sh", Answer::Once);
+        assert!(permissions.check("sh"));
+    }
+
+    #[test]
+    fn test_unapproved_program_is_not_asked_without_prompt() {
+        let mut permissions = Permissions::default();
+        assert!(!permissions.check("sh"));
+        assert_eq!(permissions.take_pending(), None);
+
+        permissions.allowed.insert(ALLOW_ALL.to_string());
+        assert!(permissions.check("sh"));
+    }
+}
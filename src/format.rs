@@ -0,0 +1,138 @@
+//! 日時・SHA・サイズ・ステータスメッセージの表示（設定の `display` で調整、ヘッダー・Commits・Conversation・CommentView 共通）
+
+use crate::config::DisplayConfig;
+use std::sync::OnceLock;
+
+static SETTINGS: OnceLock<DisplayConfig> = OnceLock::new();
+
+/// SHA の表示桁数の範囲（短すぎると衝突しやすく、40 を超えると意味がない）
+const SHA_LENGTH_RANGE: std::ops::RangeInclusive<usize> = 4..=40;
+
+/// 設定をセットする（起動時に 1 回だけ、未設定ならデフォルト値）
+pub fn init(settings: DisplayConfig) {
+    let _ = SETTINGS.set(settings);
+}
+
+fn settings() -> &'static DisplayConfig {
+    static DEFAULT: OnceLock<DisplayConfig> = OnceLock::new();
+    SETTINGS
+        .get()
+        .unwrap_or_else(|| DEFAULT.get_or_init(DisplayConfig::default))
+}
+
+/// Mermaid / PlantUML の図を ASCII 描画するか
+pub fn ascii_diagrams() -> bool {
+    settings().ascii_diagrams
+}
+
+/// Conversation の作者の横にアバターを表示するか
+pub fn avatars() -> bool {
+    settings().avatars
+}
+
+/// リンクを OSC 8 ハイパーリンクとして出力するか
+pub fn hyperlinks() -> bool {
+    settings().hyperlinks
+}
+
+/// 情報のステータスメッセージを表示しておく時間
+pub fn status_duration() -> std::time::Duration {
+    std::time::Duration::from_secs(settings().status_secs)
+}
+
+/// ステータスメッセージの履歴の件数
+pub fn status_history() -> usize {
+    settings().status_history
+}
+
+/// SHA を設定の桁数に短縮する
+pub fn short_sha(sha: &str) -> &str {
+    short_sha_with(sha, settings().sha_length)
+}
+
+fn short_sha_with(sha: &str, length: usize) -> &str {
+    let length = length.clamp(*SHA_LENGTH_RANGE.start(), *SHA_LENGTH_RANGE.end());
+    sha.get(..length).unwrap_or(sha)
+}
+
+/// ISO 8601 日時文字列をシステムタイムゾーンのローカル時刻に変換して返す
+/// 入力例: "2024-01-15T09:30:00Z" → "2024-01-15 18:30 +0900"（JST・デフォルト形式の場合）
+pub fn datetime(iso: &str) -> String {
+    datetime_with(iso, &settings().datetime_format)
+}
+
+fn datetime_with(iso: &str, format: &str) -> String {
+    let Ok(dt) = chrono::DateTime::parse_from_rfc3339(iso) else {
+        return iso.to_string();
+    };
+    let local = dt.with_timezone(&chrono::Local);
+    // 不正な書式指定子は Display 時にエラーになるため、デフォルト形式に戻す
+    let mut out = String::new();
+    if std::fmt::write(&mut out, format_args!("{}", local.format(format))).is_ok() {
+        return out;
+    }
+    local
+        .format(DisplayConfig::DEFAULT_DATETIME_FORMAT)
+        .to_string()
+}
+
+/// ISO 8601 日時文字列を now からの相対表記にする（"just now" / "5m ago" / "3h ago" / "2d ago" / "4mo ago" / "1y ago"）
+pub fn relative(iso: &str, now: chrono::DateTime<chrono::Utc>) -> String {
+    let Ok(dt) = chrono::DateTime::parse_from_rfc3339(iso) else {
+        return iso.to_string();
+    };
+    let secs = (now - dt.with_timezone(&chrono::Utc)).num_seconds();
+    match secs {
+        ..60 => "just now".to_string(),
+        60..3_600 => format!("{}m ago", secs / 60),
+        3_600..86_400 => format!("{}h ago", secs / 3_600),
+        86_400..2_592_000 => format!("{}d ago", secs / 86_400),
+        2_592_000..31_536_000 => format!("{}mo ago", secs / 2_592_000),
+        _ => format!("{}y ago", secs / 31_536_000),
+    }
+}
+
+/// バイト数を表示用に整形（B / KB / MB）
+pub fn bytes(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    if bytes < KB {
+        format!("{} B", bytes)
+    } else if bytes < MB {
+        format!("{:.1} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_sha_with() {
+        let sha = "abc1234def5678";
+        assert_eq!(short_sha_with(sha, 7), "abc1234");
+        assert_eq!(short_sha_with(sha, 10), "abc1234def");
+        // 範囲外は丸める・SHA より長ければそのまま
+        assert_eq!(short_sha_with(sha, 1), "abc1");
+        assert_eq!(short_sha_with(sha, 40), sha);
+    }
+
+    #[test]
+    fn test_datetime_with_custom_format() {
+        let iso = "2024-01-15T09:30:00Z";
+        let expected = chrono::DateTime::parse_from_rfc3339(iso)
+            .unwrap()
+            .with_timezone(&chrono::Local)
+            .format("%m/%d %H:%M")
+            .to_string();
+        assert_eq!(datetime_with(iso, "%m/%d %H:%M"), expected);
+        assert_eq!(datetime_with("not a date", "%m/%d"), "not a date");
+        // 不正な書式はデフォルト形式になる
+        assert_eq!(
+            datetime_with(iso, "%Q"),
+            datetime_with(iso, DisplayConfig::DEFAULT_DATETIME_FORMAT)
+        );
+    }
+}
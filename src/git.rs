@@ -1 +1,3 @@
 pub mod diff;
+pub mod language;
+pub mod workflow;
@@ -1,15 +1,43 @@
+use crate::config::DiffConfig;
 use color_eyre::Result;
 use ratatui::text::Text;
 use std::io::Write;
-use std::process::{Command, Stdio};
+use std::process::Stdio;
+use std::sync::OnceLock;
+use unicode_width::UnicodeWidthChar;
+
+static SETTINGS: OnceLock<DiffConfig> = OnceLock::new();
+
+/// 設定をセットする（起動時に 1 回だけ、未設定ならデフォルト値）
+pub fn init(settings: DiffConfig) {
+    let _ = SETTINGS.set(settings);
+}
+
+fn settings() -> &'static DiffConfig {
+    static DEFAULT: OnceLock<DiffConfig> = OnceLock::new();
+    SETTINGS
+        .get()
+        .unwrap_or_else(|| DEFAULT.get_or_init(DiffConfig::default))
+}
+
+/// タブの表示幅（0 ならタブを展開しない）
+pub fn tab_width() -> usize {
+    settings().tab_width
+}
+
+/// ワークフローファイルの hunk にジョブ・ステップ名を付けるか
+pub fn workflow_hints() -> bool {
+    settings().workflow_hints
+}
 
 /// delta コマンドが利用可能かチェック
 pub fn has_delta() -> bool {
-    Command::new("delta")
-        .arg("--version")
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
+    crate::exec::command("delta").is_some_and(|mut cmd| {
+        cmd.arg("--version")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    })
 }
 
 /// delta を使って diff をシンタックスハイライト
@@ -17,16 +45,20 @@ pub fn has_delta() -> bool {
 ///
 /// delta を使って diff をシンタックスハイライト
 /// --no-gitconfig でユーザー設定を無視し、--color-only で装飾を抑制する。
+/// `language` はファイル名から言語を推定できない場合の既定言語（--default-language）。
 /// hunk ヘッダーのスタイリングは app.rs 側で独自に行うため、delta には raw 出力させる。
 /// 注: app.rs 側で delta 出力をキャッシュするため、ファイル選択変更時のみ呼ばれる。
-pub fn highlight_with_delta(diff: &str) -> Result<String> {
-    let mut child = Command::new("delta")
+pub fn highlight_with_delta(diff: &str, language: Option<&str>) -> Result<String> {
+    let mut child = crate::exec::command("delta")
+        .ok_or_else(|| color_eyre::eyre::eyre!("delta is not allowed"))?
         .args([
             "--no-gitconfig",
             "--paging=never",
             "--color-only",
             "--hunk-header-style=raw",
         ])
+        .args(language.map(|language| format!("--default-language={language}")))
+        .args(&settings().delta_args)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
@@ -61,7 +93,7 @@ fn create_diff_header(filename: &str) -> String {
 
 /// diff をハイライト付きで Text に変換
 /// delta が利用可能なら使用、なければ None を返す
-/// filename を渡すことで delta が言語を検出できる
+/// filename を渡すことで delta が言語を検出できる（拡張子のないファイルはファイル名と shebang から判定する）
 /// file_status が "added"/"removed"/"deleted" の場合、差分色を抑制してシンタックスハイライトのみ適用
 /// 出力はパッチ行のみ（言語検出用に追加した diff ヘッダーは除去済み）
 pub fn highlight_diff(diff: &str, filename: &str, file_status: &str) -> Option<Text<'static>> {
@@ -94,7 +126,8 @@ pub fn highlight_diff(diff: &str, filename: &str, file_status: &str) -> Option<T
 
     let full_diff = format!("{}{}", header, body);
 
-    highlight_with_delta(&full_diff)
+    let language = super::language::detect_language(filename, diff);
+    highlight_with_delta(&full_diff, language)
         .ok()
         .and_then(|highlighted| ansi_to_text(&highlighted).ok())
         .map(|mut text| {
@@ -154,10 +187,158 @@ pub fn highlight_diff(diff: &str, filename: &str, file_status: &str) -> Option<T
         })
 }
 
+/// タブを次のタブ位置までの空白に展開する（端末ではタブ幅が不定で、ratatui は幅 0 として扱うため）
+pub fn expand_tabs(text: &mut Text, width: usize) {
+    if width == 0 {
+        return;
+    }
+    for line in &mut text.lines {
+        let mut column = 0;
+        for span in &mut line.spans {
+            if !span.content.contains('\t') {
+                column += span
+                    .content
+                    .chars()
+                    .filter_map(|c| c.width())
+                    .sum::<usize>();
+                continue;
+            }
+            let mut expanded = String::with_capacity(span.content.len());
+            for c in span.content.chars() {
+                if c == '\t' {
+                    let spaces = width - column % width;
+                    expanded.extend(std::iter::repeat_n(' ', spaces));
+                    column += spaces;
+                } else {
+                    expanded.push(c);
+                    column += c.width().unwrap_or(0);
+                }
+            }
+            span.content = expanded.into();
+        }
+    }
+}
+
+/// compute_patch で比較するファイルを置く一時ディレクトリ（他のユーザーから読めず、Drop で中身ごと消す）
+struct PatchDir(std::path::PathBuf);
+
+impl PatchDir {
+    /// 一意な名前で新しく作る（既にあるパスは使わない）
+    fn create() -> std::io::Result<Self> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!(
+            "prism-diff-{}-{stamp}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        crate::platform::private_dir_builder().create(&dir)?;
+        Ok(Self(dir))
+    }
+
+    /// 新しいファイルとして書き込む（シンボリックリンクは辿らない）
+    fn write(&self, name: &str, content: &str) -> std::io::Result<std::path::PathBuf> {
+        use std::io::Write;
+        let path = self.0.join(name);
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?
+            .write_all(content.as_bytes())?;
+        Ok(path)
+    }
+}
+
+impl Drop for PatchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// 2つのファイル内容から unified diff のパッチ部分（最初の @@ 以降）を計算する。
+/// GitHub がパッチを省略した大きなファイル用に `git diff --no-index` をローカルで実行する。
+/// アルゴリズムと前後の行数は設定の `diff` に従う。
+pub fn compute_patch(old: &str, new: &str) -> Result<String> {
+    let dir = PatchDir::create()?;
+    let old_path = dir.write("old", old)?;
+    let new_path = dir.write("new", new)?;
+
+    let settings = settings();
+    let output = crate::exec::require("git")?
+        .args(["diff", "--no-index", "--no-color", "--no-ext-diff"])
+        .arg(format!("-U{}", settings.context_lines))
+        .arg(format!(
+            "--diff-algorithm={}",
+            settings.algorithm.as_git_arg()
+        ))
+        .arg(&old_path)
+        .arg(&new_path)
+        .output()?;
+
+    // --no-index は差分ありで終了コード 1 を返す
+    if !matches!(output.status.code(), Some(0 | 1)) {
+        return Err(color_eyre::eyre::eyre!(
+            "git diff failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .lines()
+        .skip_while(|line| !line.starts_with("@@"))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_patch_dir_is_private_and_removed_on_drop() {
+        let dir = PatchDir::create().unwrap();
+        let path = dir.0.clone();
+        dir.write("old", "a\n").unwrap();
+        // 既にあるファイルは上書きしない
+        assert!(dir.write("old", "b\n").is_err());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+        drop(dir);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_compute_patch() {
+        let patch = compute_patch("a\nb\nc\n", "a\nB\nc\n").unwrap();
+        assert_eq!(patch, "@@ -1,3 +1,3 @@\n a\n-b\n+B\n c");
+    }
+
+    #[test]
+    fn test_expand_tabs_to_next_stop() {
+        use ratatui::text::{Line, Span};
+        let mut text = Text::from(vec![Line::from(vec![Span::raw("+"), Span::raw("\tx\ty")])]);
+        expand_tabs(&mut text, 4);
+        assert_eq!(text.lines[0].spans[1].content, "   x   y");
+
+        let mut kept = Text::raw("\tz");
+        expand_tabs(&mut kept, 0);
+        assert_eq!(kept.lines[0].spans[0].content, "\tz");
+    }
+
+    #[test]
+    fn test_compute_patch_identical() {
+        assert_eq!(compute_patch("same\n", "same\n").unwrap(), "");
+    }
+
     /// 変更パッチの行数が入力と一致することを確認
     #[test]
     fn test_highlight_diff_line_count_matches_patch() {
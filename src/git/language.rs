@@ -0,0 +1,131 @@
+//! 拡張子のないファイルの言語判定（ファイル名と shebang から、シンタックスハイライト用）
+
+/// ファイル名で言語が決まるファイル（拡張子の有無に関わらず優先する）
+const FILENAME_LANGUAGES: &[(&str, &str)] = &[
+    ("Dockerfile", "dockerfile"),
+    ("Containerfile", "dockerfile"),
+    ("Makefile", "makefile"),
+    ("GNUmakefile", "makefile"),
+    ("makefile", "makefile"),
+    ("CMakeLists.txt", "cmake"),
+    ("Jenkinsfile", "groovy"),
+    ("Gemfile", "ruby"),
+    ("Rakefile", "ruby"),
+    ("Vagrantfile", "ruby"),
+    ("Podfile", "ruby"),
+    ("Brewfile", "ruby"),
+    ("BUILD", "python"),
+    ("WORKSPACE", "python"),
+    ("PKGBUILD", "bash"),
+    (".bashrc", "bash"),
+    (".bash_profile", "bash"),
+    (".profile", "bash"),
+    (".zshrc", "bash"),
+    (".zprofile", "bash"),
+];
+
+/// shebang のインタプリタ → 言語
+const INTERPRETER_LANGUAGES: &[(&str, &str)] = &[
+    ("sh", "bash"),
+    ("bash", "bash"),
+    ("zsh", "bash"),
+    ("ksh", "bash"),
+    ("dash", "bash"),
+    ("ash", "bash"),
+    ("python", "python"),
+    ("node", "javascript"),
+    ("nodejs", "javascript"),
+    ("deno", "javascript"),
+    ("bun", "javascript"),
+    ("ruby", "ruby"),
+    ("perl", "perl"),
+    ("php", "php"),
+    ("lua", "lua"),
+    ("Rscript", "r"),
+    ("awk", "awk"),
+    ("gawk", "awk"),
+    ("tclsh", "tcl"),
+];
+
+/// ファイル名と（ファイル先頭を含む）パッチから言語を判定する（拡張子で判定できるファイルは None）
+pub fn detect_language(filename: &str, patch: &str) -> Option<&'static str> {
+    let basename = filename.rsplit('/').next().unwrap_or(filename);
+    if let Some(&(_, language)) = FILENAME_LANGUAGES.iter().find(|(name, _)| {
+        basename == *name
+            // Dockerfile.dev のような派生ファイル
+            || basename
+                .strip_prefix(name)
+                .is_some_and(|rest| rest.starts_with('.'))
+    }) {
+        return Some(language);
+    }
+    if basename.trim_start_matches('.').contains('.') {
+        return None;
+    }
+    shebang_language(first_file_line(patch)?)
+}
+
+/// パッチがファイル先頭から始まる場合の 1 行目（+/-/空白のプレフィックスを除く）
+fn first_file_line(patch: &str) -> Option<&str> {
+    let mut lines = patch.lines();
+    let header = lines.next()?;
+    // "@@ -0,0 +1,5 @@" / "@@ -1,3 +1,4 @@" のように新旧どちらかが 1 行目（新規なら 0）から始まる
+    let starts_at_top = header.split_whitespace().skip(1).take(2).any(|range| {
+        matches!(
+            range.get(1..).and_then(|r| r.split(',').next()),
+            Some("0" | "1")
+        )
+    });
+    if !header.starts_with("@@") || !starts_at_top {
+        return None;
+    }
+    lines.next().map(|line| line.get(1..).unwrap_or(""))
+}
+
+/// shebang 行（"#!/usr/bin/env python3" 等）から言語を判定する
+fn shebang_language(line: &str) -> Option<&'static str> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        // env -S 等のオプションは読み飛ばす
+        program = words.find(|w| !w.starts_with('-'))?;
+    }
+    // python3.12 → python
+    let program = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    INTERPRETER_LANGUAGES
+        .iter()
+        .find(|(name, _)| *name == program)
+        .map(|&(_, language)| language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(detect_language("Dockerfile", ""), Some("dockerfile"));
+        assert_eq!(
+            detect_language("docker/Dockerfile.dev", ""),
+            Some("dockerfile")
+        );
+        assert_eq!(detect_language("Makefile", ""), Some("makefile"));
+        assert_eq!(
+            detect_language("src/main.rs", "@@ -0,0 +1 @@\n+#!/bin/sh"),
+            None
+        );
+
+        let script = "@@ -0,0 +1,2 @@\n+#!/usr/bin/env -S python3.12 -u\n+print(1)";
+        assert_eq!(detect_language("bin/tool", script), Some("python"));
+        assert_eq!(
+            detect_language("bin/run", "@@ -1,2 +1,2 @@\n #!/bin/bash\n-a\n+b"),
+            Some("bash")
+        );
+        // ファイル先頭を含まないパッチでは shebang を判定しない
+        assert_eq!(
+            detect_language("bin/run", "@@ -10,2 +10,2 @@\n #!/bin/bash\n-a"),
+            None
+        );
+        assert_eq!(detect_language("LICENSE", "@@ -0,0 +1 @@\n+MIT"), None);
+    }
+}
@@ -0,0 +1,261 @@
+//! GitHub Actions のワークフローファイルの diff から、変更されたジョブ・ステップを読み取る
+
+/// ラベルに並べるステップ名の上限（超えた分は "+N" にまとめる）
+const MAX_STEPS: usize = 3;
+
+/// .github/workflows/ 以下の YAML か
+pub fn is_workflow_file(filename: &str) -> bool {
+    filename
+        .strip_prefix(".github/workflows/")
+        .is_some_and(|name| name.ends_with(".yml") || name.ends_with(".yaml"))
+}
+
+/// "key: value" の行のキーと値（リスト項目の "- " は除いて渡す）
+fn split_key(text: &str) -> Option<(&str, &str)> {
+    let (key, value) = text.split_once(':')?;
+    let key = key.trim();
+    if key.is_empty() || key.starts_with('#') || key.contains(' ') {
+        return None;
+    }
+    let value = value.split(" #").next().unwrap_or("").trim();
+    Some((key, value.trim_matches(|c| c == '"' || c == '\'')))
+}
+
+/// hunk 内で読み取った位置（トップレベルのキー・ジョブ・ステップ）
+#[derive(Default)]
+struct Cursor {
+    top: Option<String>,
+    jobs_indent: Option<usize>,
+    job: Option<String>,
+    steps_indent: Option<usize>,
+    item_indent: Option<usize>,
+    /// 現在のステップ（hunk 内の steps の添字）
+    step: Option<usize>,
+}
+
+/// hunk で変更された箇所（ジョブ、またはトップレベルのキーごとのステップ名）
+#[derive(Default)]
+struct HunkChanges {
+    /// (ジョブ名かトップレベルのキー, 変更されたステップの添字)
+    targets: Vec<(String, Vec<usize>)>,
+    /// ステップ名（name があれば name、なければ uses）
+    steps: Vec<Option<String>>,
+    cursor: Cursor,
+}
+
+impl HunkChanges {
+    /// hunk header の関数コンテキスト（git はトップレベルのキーを付ける）から始める
+    fn new(header: &str) -> Self {
+        let mut changes = Self::default();
+        if let Some(context) = header.splitn(3, "@@").nth(2) {
+            changes.advance(context.trim());
+            if changes.cursor.top.as_deref() == Some("jobs") {
+                // jobs: の行が hunk の外にあるときは慣例の 2 スペースとみなす
+                changes.cursor.jobs_indent = Some(2);
+            }
+        }
+        changes
+    }
+
+    /// 1 行読んで位置を更新する
+    fn advance(&mut self, text: &str) {
+        let indent = text.len() - text.trim_start_matches(' ').len();
+        let content = text.trim();
+        if content.is_empty() || content.starts_with('#') {
+            return;
+        }
+        let cursor = &mut self.cursor;
+        if indent == 0 {
+            *cursor = Cursor {
+                top: split_key(content).map(|(key, _)| key.to_string()),
+                ..Cursor::default()
+            };
+            return;
+        }
+        if cursor.top.as_deref() != Some("jobs") {
+            return;
+        }
+        let jobs_indent = *cursor.jobs_indent.get_or_insert(indent);
+        if indent <= jobs_indent {
+            cursor.job = split_key(content).map(|(key, _)| key.to_string());
+            cursor.steps_indent = None;
+            cursor.item_indent = None;
+            cursor.step = None;
+            return;
+        }
+        if let Some(steps_indent) = cursor.steps_indent
+            && indent <= steps_indent
+            && !content.starts_with("- ")
+        {
+            // steps の後の別のキー
+            cursor.steps_indent = None;
+            cursor.item_indent = None;
+            cursor.step = None;
+        }
+        if cursor.steps_indent.is_none() {
+            if split_key(content).is_some_and(|(key, _)| key == "steps") {
+                cursor.steps_indent = Some(indent);
+            }
+            return;
+        }
+        let (item, key_indent) = match content.strip_prefix("- ") {
+            Some(item) if cursor.item_indent.is_none_or(|i| i == indent) => {
+                cursor.item_indent = Some(indent);
+                cursor.step = Some(self.steps.len());
+                self.steps.push(None);
+                (item, indent + 2)
+            }
+            _ => (content, indent),
+        };
+        let (Some(step), Some((key, value))) = (cursor.step, split_key(item)) else {
+            return;
+        };
+        // ステップ直下のキーだけを見る（with: の中の name 等は無視）
+        if cursor.item_indent.is_some_and(|i| key_indent != i + 2) || value.is_empty() {
+            return;
+        }
+        let name = &mut self.steps[step];
+        match key {
+            "name" => *name = Some(value.to_string()),
+            "uses" if name.is_none() => *name = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    /// 変更行を現在の位置に記録する
+    fn record(&mut self) {
+        let cursor = &self.cursor;
+        let Some(target) = cursor.job.as_ref().or(cursor.top.as_ref()) else {
+            return;
+        };
+        // jobs の中でジョブが分からない変更は付けない
+        if cursor.job.is_none() && target == "jobs" {
+            return;
+        }
+        let index = match self.targets.iter().position(|(name, _)| name == target) {
+            Some(index) => index,
+            None => {
+                self.targets.push((target.clone(), Vec::new()));
+                self.targets.len() - 1
+            }
+        };
+        let steps = &mut self.targets[index].1;
+        if let Some(step) = cursor.step.filter(|_| cursor.job.is_some())
+            && !steps.contains(&step)
+        {
+            steps.push(step);
+        }
+    }
+
+    /// "⚙ build › Run tests, Lint · deploy "
+    fn label(&self) -> Option<String> {
+        let parts: Vec<String> = self
+            .targets
+            .iter()
+            .map(|(target, steps)| {
+                let names: Vec<&str> = steps
+                    .iter()
+                    .filter_map(|&i| self.steps[i].as_deref())
+                    .collect();
+                if names.is_empty() {
+                    return target.clone();
+                }
+                let mut shown = names[..names.len().min(MAX_STEPS)].join(", ");
+                if names.len() > MAX_STEPS {
+                    shown.push_str(&format!(" +{}", names.len() - MAX_STEPS));
+                }
+                format!("{target} › {shown}")
+            })
+            .collect();
+        (!parts.is_empty()).then(|| format!("⚙ {} ", parts.join(" · ")))
+    }
+}
+
+/// hunk header の行インデックス → 変更されたジョブ・ステップのラベル
+///
+/// hunk に含まれる行だけから YAML の構造を推測するため、ジョブのキーが hunk の外にある変更には付かない。
+pub fn workflow_hunk_labels(patch: &str) -> Vec<(usize, String)> {
+    let mut labels = Vec::new();
+    let mut current: Option<(usize, HunkChanges)> = None;
+    for (idx, line) in patch.lines().enumerate() {
+        if line.starts_with("@@") {
+            if let Some((header, changes)) = current.take()
+                && let Some(label) = changes.label()
+            {
+                labels.push((header, label));
+            }
+            current = Some((idx, HunkChanges::new(line)));
+            continue;
+        }
+        // "\\ No newline at end of file"
+        if line.starts_with('\\') {
+            continue;
+        }
+        let Some((_, changes)) = current.as_mut() else {
+            continue;
+        };
+        let (prefix, text) = line.split_at(line.len().min(1));
+        changes.advance(text);
+        if matches!(prefix, "+" | "-") {
+            changes.record();
+        }
+    }
+    if let Some((header, changes)) = current
+        && let Some(label) = changes.label()
+    {
+        labels.push((header, label));
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_workflow_file() {
+        assert!(is_workflow_file(".github/workflows/ci.yml"));
+        assert!(is_workflow_file(".github/workflows/release.yaml"));
+        assert!(!is_workflow_file(".github/dependabot.yml"));
+        assert!(!is_workflow_file("docs/.github/workflows/ci.yml"));
+    }
+
+    #[test]
+    fn test_workflow_hunk_labels() {
+        let patch = "\
+@@ -1,4 +1,4 @@
+ on:
+-  push:
++  pull_request:
+ jobs:
+@@ -10,12 +10,13 @@ jobs:
+   build:
+     runs-on: ubuntu-latest
+     steps:
+       - uses: actions/checkout@v4
+       - name: \"Run tests\"
+-        run: cargo test
++        run: cargo test --workspace
+       - uses: actions/cache@v4
+         with:
+-          name: old
++          name: new
+   deploy:
+-    needs: test
++    needs: build
+@@ -40,2 +41,2 @@ jobs:
+       run: echo
+-      x: 1
++      x: 2";
+        assert_eq!(
+            workflow_hunk_labels(patch),
+            vec![
+                (0, "⚙ on ".to_string()),
+                (
+                    5,
+                    "⚙ build › Run tests, actions/cache@v4 · deploy ".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -1,8 +1,13 @@
 pub mod cache;
+pub mod checks;
 pub mod client;
 pub mod comments;
 pub mod commits;
+pub mod error;
+pub mod export;
 pub mod files;
 pub mod media;
+pub mod metrics;
 pub mod pr;
+pub mod retry;
 pub mod review;
@@ -1,6 +1,7 @@
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use super::comments::ReviewThread;
 use super::files::DiffFile;
@@ -18,8 +19,37 @@ pub struct PrCache {
     pub review_threads: Vec<ReviewThread>,
 }
 
+/// キャッシュの保存先（OS の一時ディレクトリ配下。消えても取り直せるものだけを置く）
+pub fn cache_root() -> PathBuf {
+    std::env::temp_dir().join("gh-prism")
+}
+
+/// ユーザーの保存状態（メモ・チェックリスト・viewed など）の保存先
+/// （$XDG_STATE_HOME/gh-prism、未設定なら ~/.local/state 配下。再起動や `prism cache` では消えない）
+pub fn state_root() -> Option<PathBuf> {
+    // テストで実際の保存状態を書き換えないよう一時ディレクトリに置く
+    if cfg!(test) {
+        return Some(std::env::temp_dir().join("gh-prism-test-state"));
+    }
+    let base = std::env::var_os("XDG_STATE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("state"))
+        })?;
+    Some(base.join("gh-prism"))
+}
+
+/// 保存先を決められないときのエラー（HOME も XDG_STATE_HOME もない）
+fn no_state_root() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "no state directory (set XDG_STATE_HOME or HOME)",
+    )
+}
+
 fn cache_dir(owner: &str, repo: &str) -> PathBuf {
-    std::env::temp_dir().join("gh-prism").join(owner).join(repo)
+    cache_root().join(owner).join(repo)
 }
 
 fn cache_path(owner: &str, repo: &str, pr_number: u64) -> PathBuf {
@@ -53,6 +83,309 @@ pub fn write_cache(owner: &str, repo: &str, pr_number: u64, cache: &PrCache) {
     }
 }
 
+/// PR ごとの JSON ファイル（"pr-12-{suffix}.json"）のパス
+fn pr_json_path(root: &Path, owner: &str, repo: &str, pr_number: u64, suffix: &str) -> PathBuf {
+    root.join(owner)
+        .join(repo)
+        .join(format!("pr-{pr_number}-{suffix}.json"))
+}
+
+fn read_pr_json_in<T: DeserializeOwned>(
+    root: &Path,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    suffix: &str,
+) -> Option<T> {
+    let data = std::fs::read_to_string(pr_json_path(root, owner, repo, pr_number, suffix)).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+fn write_pr_json_in<T: Serialize>(
+    root: &Path,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    suffix: &str,
+    value: &T,
+) -> std::io::Result<()> {
+    let path = pr_json_path(root, owner, repo, pr_number, suffix);
+    if let Some(parent) = path.parent() {
+        // 他のユーザーから読めないよう 0700 で作る
+        crate::platform::private_dir_builder()
+            .recursive(true)
+            .create(parent)?;
+    }
+    let json = serde_json::to_string(value)?;
+    std::fs::write(&path, json)
+}
+
+fn remove_pr_json_in(
+    root: &Path,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    suffix: &str,
+) -> std::io::Result<()> {
+    match std::fs::remove_file(pr_json_path(root, owner, repo, pr_number, suffix)) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// PR ごとの保存状態を読む（ファイルがないか壊れていれば None）
+/// 以前はキャッシュと同じ一時ディレクトリに置いていたため、保存先になければそちらも見る
+pub fn read_pr_json<T: DeserializeOwned>(
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    suffix: &str,
+) -> Option<T> {
+    state_root()
+        .and_then(|root| read_pr_json_in(&root, owner, repo, pr_number, suffix))
+        .or_else(|| read_pr_json_in(&cache_root(), owner, repo, pr_number, suffix))
+}
+
+/// PR ごとの保存状態を書く（TUI 実行中に呼ばれるため eprintln せずエラーを返す）
+pub fn write_pr_json<T: Serialize>(
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    suffix: &str,
+    value: &T,
+) -> std::io::Result<()> {
+    let root = state_root().ok_or_else(no_state_root)?;
+    write_pr_json_in(&root, owner, repo, pr_number, suffix, value)
+}
+
+/// PR ごとの保存状態を消す（ファイルがなければ何もしない。以前の保存先に残ったものも消す）
+pub fn remove_pr_json(
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    suffix: &str,
+) -> std::io::Result<()> {
+    if let Some(root) = state_root() {
+        remove_pr_json_in(&root, owner, repo, pr_number, suffix)?;
+    }
+    remove_pr_json_in(&cache_root(), owner, repo, pr_number, suffix)
+}
+
+/// レビューチェックリストの保存状態（PR ごと、head SHA に依存しないため PrCache とは別ファイル）
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ChecklistCache {
+    /// 項目ラベル → チェック済みか
+    #[serde(default)]
+    pub checked: HashMap<String, bool>,
+    /// レビュー送信時に本文へ追記するか
+    #[serde(default)]
+    pub append_to_review: bool,
+}
+
+pub const CHECKLIST_SUFFIX: &str = "checklist";
+
+/// ファイルごとの非公開レビューメモ（GitHub には送信しない）
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NotesCache {
+    /// ファイルパス → メモ本文（エクスポート順を安定させるため BTreeMap）
+    #[serde(default)]
+    pub notes: BTreeMap<String, String>,
+}
+
+pub const NOTES_SUFFIX: &str = "notes";
+
+/// 既読にした Conversation エントリ（PR ごと、新着バッジの判定に使う）
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SeenCache {
+    /// エントリのキー（スレッドは最新返信の日時を含むため、返信が付くと未読に戻る）
+    #[serde(default)]
+    pub entries: HashSet<String>,
+}
+
+pub const SEEN_SUFFIX: &str = "seen";
+
+/// スレッドと PR のミュート状態（PR ごと、ポーリング・通知系の抑制に使う）
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MuteCache {
+    /// ミュートしたスレッドのルートコメント ID
+    #[serde(default)]
+    pub threads: HashSet<u64>,
+    /// PR 全体の通知を無視しているか（GitHub の subscription と同期）
+    #[serde(default)]
+    pub pr: bool,
+}
+
+pub const MUTE_SUFFIX: &str = "mute";
+
+/// viewed にしたファイル（PR ごと、All changes の分は GitHub の "Viewed" と同期する）
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ViewedCache {
+    /// コミット SHA（All changes は "all"）→ ファイル名
+    #[serde(default)]
+    pub files: HashMap<String, HashSet<String>>,
+    /// GitHub にまだ送れていない All changes の変更（ファイル名, viewed）。次に同期するときに送り直す
+    #[serde(default)]
+    pub pending: Vec<(String, bool)>,
+}
+
+pub const VIEWED_SUFFIX: &str = "viewed";
+
+/// 送信できなかったスレッドの resolve / unresolve（接続が戻ったら順に再送する）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedResolve {
+    pub thread_node_id: String,
+    pub root_comment_id: u64,
+    pub resolve: bool,
+}
+
+/// 再送待ちの resolve 操作（PR ごと、空になったらファイルを消す）
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ResolveQueueCache {
+    #[serde(default)]
+    pub actions: Vec<QueuedResolve>,
+}
+
+pub const RESOLVE_QUEUE_SUFFIX: &str = "resolve-queue";
+
+/// 条件付きリクエスト用に保存する GET レスポンス（ETag / Last-Modified と本文）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConditionalEntry {
+    /// ハッシュ衝突時に取り違えないよう URL も保存する
+    pub url: String,
+    #[serde(default)]
+    pub etag: Option<String>,
+    #[serde(default)]
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+/// "/repos/{owner}/{repo}/..." の URL ごとのファイル（並列取得でも競合しないよう 1 URL 1 ファイル）
+fn conditional_path(url: &str) -> Option<PathBuf> {
+    use std::hash::{Hash, Hasher};
+    let mut parts = url.strip_prefix("/repos/")?.splitn(3, '/');
+    let (owner, repo) = (parts.next()?, parts.next()?);
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    url.hash(&mut hasher);
+    Some(
+        cache_dir(owner, repo)
+            .join("conditional")
+            .join(format!("{:016x}.json", hasher.finish())),
+    )
+}
+
+pub fn read_conditional(url: &str) -> Option<ConditionalEntry> {
+    let data = std::fs::read_to_string(conditional_path(url)?).ok()?;
+    let entry: ConditionalEntry = serde_json::from_str(&data).ok()?;
+    (entry.url == url).then_some(entry)
+}
+
+/// 読み込み中にも呼ばれるため eprintln せずエラーを返す
+pub fn write_conditional(entry: &ConditionalEntry) -> std::io::Result<()> {
+    let path = conditional_path(&entry.url).ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "not a repository URL")
+    })?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string(entry)?;
+    std::fs::write(&path, json)
+}
+
+/// キャッシュ内の 1 ファイル（`prism cache` の一覧・整理の単位）
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub path: PathBuf,
+    pub owner: String,
+    pub repo: String,
+    /// PR ごとのファイルなら PR 番号（conditional/ 以下の API レスポンスは None）
+    pub pr_number: Option<u64>,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+/// clear / prune で退避したファイルの保存先（キャッシュ直下、一覧の対象外）
+pub fn archive_root() -> PathBuf {
+    cache_root().join(".archive")
+}
+
+/// "pr-12.json" / "pr-12-notes.json" の PR 番号
+fn pr_number_of(file_name: &str) -> Option<u64> {
+    let stem = file_name.strip_prefix("pr-")?.strip_suffix(".json")?;
+    stem.split('-').next()?.parse().ok()
+}
+
+/// ディレクトリ直下のサブディレクトリ（"." で始まるものは除く）
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(read) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    read.flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .map(|e| e.path())
+        .collect()
+}
+
+/// ディレクトリ直下のファイル（パス, サイズ, 更新日時）
+fn files_in(dir: &Path) -> Vec<(PathBuf, u64, SystemTime)> {
+    let Ok(read) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    read.flatten()
+        .filter_map(|e| {
+            let meta = e.metadata().ok().filter(|m| m.is_file())?;
+            Some((e.path(), meta.len(), meta.modified().ok()?))
+        })
+        .collect()
+}
+
+/// root/owner/repo 以下のキャッシュファイルを列挙する（diff 計算用の一時ディレクトリ等は含まない）
+pub fn scan_entries(root: &Path) -> Vec<CacheEntry> {
+    let mut entries = Vec::new();
+    for owner_dir in subdirs(root) {
+        let owner = owner_dir.file_name().unwrap_or_default().to_string_lossy();
+        for repo_dir in subdirs(&owner_dir) {
+            let repo = repo_dir.file_name().unwrap_or_default().to_string_lossy();
+            let pr_files = files_in(&repo_dir).into_iter().filter_map(|file| {
+                let name = file.0.file_name()?.to_string_lossy().into_owned();
+                Some((pr_number_of(&name)?, file))
+            });
+            let conditional = files_in(&repo_dir.join("conditional"));
+            let files = pr_files
+                .map(|(number, file)| (Some(number), file))
+                .chain(conditional.into_iter().map(|file| (None, file)));
+            for (pr_number, (path, size, modified)) in files {
+                entries.push(CacheEntry {
+                    path,
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    pr_number,
+                    size,
+                    modified,
+                });
+            }
+        }
+    }
+    entries.sort_by(|a, b| {
+        (&a.owner, &a.repo, a.pr_number, &a.path).cmp(&(&b.owner, &b.repo, b.pr_number, &b.path))
+    });
+    entries
+}
+
+/// ファイルを root からの相対パスのままアーカイブへ移す（期限の判定のため更新日時を移した時刻にする）
+pub fn archive_entry(root: &Path, archive: &Path, path: &Path) -> std::io::Result<()> {
+    let relative = path.strip_prefix(root).map_err(std::io::Error::other)?;
+    let target = archive.join(relative);
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::rename(path, &target)?;
+    std::fs::File::options()
+        .write(true)
+        .open(&target)?
+        .set_modified(SystemTime::now())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,4 +441,93 @@ mod tests {
         let result = read_cache("nonexistent", "repo", 0);
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_pr_json_round_trip() {
+        let root = std::env::temp_dir().join("gh-prism-test-pr-json");
+        let _ = std::fs::remove_dir_all(&root);
+
+        let mut mutes = MuteCache::default();
+        mutes.threads.insert(42);
+        mutes.pr = true;
+        write_pr_json_in(&root, "octo", "app", 7, MUTE_SUFFIX, &mutes).unwrap();
+        assert!(root.join("octo/app/pr-7-mute.json").exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(root.join("octo/app"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+
+        let loaded: MuteCache = read_pr_json_in(&root, "octo", "app", 7, MUTE_SUFFIX).unwrap();
+        assert!(loaded.threads.contains(&42));
+        assert!(loaded.pr);
+        // 別の PR・別の種類のファイルは読まない
+        assert!(read_pr_json_in::<MuteCache>(&root, "octo", "app", 8, MUTE_SUFFIX).is_none());
+        assert!(read_pr_json_in::<SeenCache>(&root, "octo", "app", 7, SEEN_SUFFIX).is_none());
+
+        remove_pr_json_in(&root, "octo", "app", 7, MUTE_SUFFIX).unwrap();
+        assert!(read_pr_json_in::<MuteCache>(&root, "octo", "app", 7, MUTE_SUFFIX).is_none());
+        // 既にない場合も成功する
+        remove_pr_json_in(&root, "octo", "app", 7, MUTE_SUFFIX).unwrap();
+
+        // cleanup
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_conditional_round_trip() {
+        let entry = ConditionalEntry {
+            url: "/repos/test-owner/test-repo-conditional/pulls/1/reviews".to_string(),
+            etag: Some("W/\"abc\"".to_string()),
+            last_modified: None,
+            body: "[]".to_string(),
+        };
+        write_conditional(&entry).unwrap();
+
+        assert_eq!(read_conditional(&entry.url), Some(entry.clone()));
+        assert_eq!(
+            read_conditional("/repos/test-owner/test-repo-conditional/pulls/2/reviews"),
+            None
+        );
+        assert!(conditional_path("https://example.com/x").is_none());
+
+        // cleanup
+        let _ = std::fs::remove_file(conditional_path(&entry.url).unwrap());
+    }
+
+    #[test]
+    fn test_scan_and_archive_entries() {
+        let root = std::env::temp_dir().join("gh-prism-test-scan");
+        let archive = root.join(".archive");
+        let _ = std::fs::remove_dir_all(&root);
+        let repo_dir = root.join("octo").join("app");
+        std::fs::create_dir_all(repo_dir.join("conditional")).unwrap();
+        std::fs::write(repo_dir.join("pr-12.json"), "{}").unwrap();
+        std::fs::write(repo_dir.join("pr-12-notes.json"), "{}").unwrap();
+        std::fs::write(repo_dir.join("pr-3-seen.json"), "{}").unwrap();
+        std::fs::write(repo_dir.join("conditional").join("ab.json"), "{}").unwrap();
+        // diff 計算用の一時ディレクトリはキャッシュではない
+        std::fs::create_dir_all(root.join("diff-1-0")).unwrap();
+        std::fs::write(root.join("diff-1-0").join("old"), "x").unwrap();
+
+        let entries = scan_entries(&root);
+        let numbers: Vec<Option<u64>> = entries.iter().map(|e| e.pr_number).collect();
+        assert_eq!(numbers, vec![None, Some(3), Some(12), Some(12)]);
+        assert!(entries.iter().all(|e| e.owner == "octo" && e.repo == "app"));
+        assert_eq!(entries[1].size, 2);
+
+        archive_entry(&root, &archive, &entries[1].path).unwrap();
+        assert!(!repo_dir.join("pr-3-seen.json").exists());
+        assert_eq!(scan_entries(&root).len(), 3);
+        let archived = scan_entries(&archive);
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].pr_number, Some(3));
+
+        // cleanup
+        let _ = std::fs::remove_dir_all(&root);
+    }
 }
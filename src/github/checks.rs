@@ -0,0 +1,224 @@
+//! PR の head コミットの CI 状態（Checks API の check run と、commit status API の status をまとめる）
+
+use color_eyre::Result;
+use octocrab::Octocrab;
+use serde::Deserialize;
+
+use super::metrics;
+
+/// チェックの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckState {
+    Failure,
+    Pending,
+    Success,
+    /// skipped / neutral / cancelled（成否に数えない）
+    Neutral,
+}
+
+impl CheckState {
+    /// 一覧とバッジの記号
+    pub fn symbol(self) -> &'static str {
+        match self {
+            CheckState::Failure => "✗",
+            CheckState::Pending => "●",
+            CheckState::Success => "✓",
+            CheckState::Neutral => "○",
+        }
+    }
+
+    /// check run の status / conclusion から
+    fn from_check_run(status: &str, conclusion: Option<&str>) -> Self {
+        if status != "completed" {
+            return CheckState::Pending;
+        }
+        match conclusion {
+            Some("success") => CheckState::Success,
+            Some("failure" | "timed_out" | "action_required" | "startup_failure") => {
+                CheckState::Failure
+            }
+            _ => CheckState::Neutral,
+        }
+    }
+
+    /// commit status の state から
+    fn from_status(state: &str) -> Self {
+        match state {
+            "success" => CheckState::Success,
+            "failure" | "error" => CheckState::Failure,
+            _ => CheckState::Pending,
+        }
+    }
+}
+
+/// 1 つのチェック（check run か commit status）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub name: String,
+    pub state: CheckState,
+    /// 結果の説明（check run は conclusion / status、commit status は description）
+    pub detail: String,
+    /// 詳細ページの URL
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRunsResponse {
+    check_runs: Vec<CheckRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRun {
+    name: String,
+    status: String,
+    conclusion: Option<String>,
+    html_url: Option<String>,
+    details_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CombinedStatus {
+    statuses: Vec<CommitStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitStatus {
+    context: String,
+    state: String,
+    description: Option<String>,
+    target_url: Option<String>,
+}
+
+impl From<CheckRun> for Check {
+    fn from(run: CheckRun) -> Self {
+        let state = CheckState::from_check_run(&run.status, run.conclusion.as_deref());
+        Check {
+            name: run.name,
+            state,
+            detail: run.conclusion.unwrap_or(run.status).replace('_', " "),
+            url: run.details_url.or(run.html_url),
+        }
+    }
+}
+
+impl From<CommitStatus> for Check {
+    fn from(status: CommitStatus) -> Self {
+        Check {
+            name: status.context,
+            state: CheckState::from_status(&status.state),
+            detail: status.description.unwrap_or(status.state),
+            url: status.target_url,
+        }
+    }
+}
+
+/// head コミットの check run と commit status を取得する（失敗・実行中を先に、名前順）
+pub async fn fetch_checks(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    sha: &str,
+) -> Result<Vec<Check>> {
+    let runs_url = format!("/repos/{owner}/{repo}/commits/{sha}/check-runs?per_page=100");
+    let status_url = format!("/repos/{owner}/{repo}/commits/{sha}/status?per_page=100");
+    let (runs, status) = tokio::try_join!(
+        metrics::get_json::<CheckRunsResponse>(client, "check runs", runs_url),
+        metrics::get_json::<CombinedStatus>(client, "commit status", status_url),
+    )?;
+    let mut checks: Vec<Check> = runs
+        .check_runs
+        .into_iter()
+        .map(Check::from)
+        .chain(status.statuses.into_iter().map(Check::from))
+        .collect();
+    checks.sort_by(|a, b| (a.state, &a.name).cmp(&(b.state, &b.name)));
+    Ok(checks)
+}
+
+/// 全体の状態（失敗があれば失敗、実行中があれば実行中。チェックがなければ None）
+pub fn overall(checks: &[Check]) -> Option<CheckState> {
+    checks
+        .iter()
+        .map(|c| c.state)
+        .filter(|&s| s != CheckState::Neutral)
+        .min()
+        .or_else(|| (!checks.is_empty()).then_some(CheckState::Neutral))
+}
+
+/// ヘッダーのバッジ（" CI ✗1 ●2 ✓5 "、0 件の状態は省く）
+pub fn badge(checks: &[Check]) -> String {
+    let counts: Vec<String> = [
+        CheckState::Failure,
+        CheckState::Pending,
+        CheckState::Success,
+    ]
+    .into_iter()
+    .filter_map(|state| {
+        let n = checks.iter().filter(|c| c.state == state).count();
+        (n > 0).then(|| format!("{}{n}", state.symbol()))
+    })
+    .collect();
+    format!(" CI {} ", counts.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(name: &str, state: CheckState) -> Check {
+        Check {
+            name: name.to_string(),
+            state,
+            detail: String::new(),
+            url: None,
+        }
+    }
+
+    #[test]
+    fn test_check_state_mapping() {
+        assert_eq!(
+            CheckState::from_check_run("in_progress", None),
+            CheckState::Pending
+        );
+        assert_eq!(
+            CheckState::from_check_run("completed", Some("timed_out")),
+            CheckState::Failure
+        );
+        assert_eq!(
+            CheckState::from_check_run("completed", Some("skipped")),
+            CheckState::Neutral
+        );
+        assert_eq!(CheckState::from_status("error"), CheckState::Failure);
+        assert_eq!(CheckState::from_status("pending"), CheckState::Pending);
+
+        let run: Check = CheckRun {
+            name: "build".to_string(),
+            status: "completed".to_string(),
+            conclusion: Some("action_required".to_string()),
+            html_url: Some("https://github.com/run".to_string()),
+            details_url: None,
+        }
+        .into();
+        assert_eq!(run.detail, "action required");
+        assert_eq!(run.url.as_deref(), Some("https://github.com/run"));
+    }
+
+    #[test]
+    fn test_overall_and_badge() {
+        assert_eq!(overall(&[]), None);
+        let checks = vec![
+            check("lint", CheckState::Success),
+            check("docs", CheckState::Neutral),
+        ];
+        assert_eq!(overall(&checks), Some(CheckState::Success));
+        assert_eq!(badge(&checks), " CI ✓1 ");
+
+        let checks = vec![
+            check("lint", CheckState::Success),
+            check("test", CheckState::Pending),
+            check("build", CheckState::Failure),
+        ];
+        assert_eq!(overall(&checks), Some(CheckState::Failure));
+        assert_eq!(badge(&checks), " CI ✗1 ●1 ✓1 ");
+    }
+}
@@ -1,13 +1,14 @@
 use color_eyre::{Result, eyre::eyre};
 use octocrab::Octocrab;
-use std::process::Command;
 
 fn get_token() -> Result<String> {
     if let Ok(token) = std::env::var("GITHUB_TOKEN") {
         return Ok(token);
     }
 
-    let output = Command::new("gh").args(["auth", "token"]).output()?;
+    let output = crate::exec::require("gh")?
+        .args(["auth", "token"])
+        .output()?;
 
     if !output.status.success() {
         return Err(eyre!(
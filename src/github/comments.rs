@@ -2,6 +2,8 @@ use color_eyre::Result;
 use octocrab::Octocrab;
 use serde::{Deserialize, Serialize};
 
+use super::metrics;
+
 const REVIEW_THREADS_PAGE_SIZE: u32 = 100;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,8 +44,9 @@ pub fn fetch_review_threads(owner: &str, repo: &str, pr_number: u64) -> Result<V
         REVIEW_THREADS_PAGE_SIZE
     );
 
-    let output = std::process::Command::new("gh")
-        .args([
+    let output = metrics::run_gh(
+        "review threads",
+        &[
             "api",
             "graphql",
             "-f",
@@ -54,8 +57,8 @@ pub fn fetch_review_threads(owner: &str, repo: &str, pr_number: u64) -> Result<V
             &format!("repo={repo}"),
             "-F",
             &format!("pr={pr_number}"),
-        ])
-        .output()?;
+        ],
+    )?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -111,16 +114,17 @@ fn toggle_review_thread(thread_node_id: &str, resolve: bool) -> Result<bool> {
 }}"#
     );
 
-    let output = std::process::Command::new("gh")
-        .args([
+    let output = metrics::run_gh(
+        "thread resolve",
+        &[
             "api",
             "graphql",
             "-f",
             &format!("query={query}"),
             "-F",
             &format!("threadId={thread_node_id}"),
-        ])
-        .output()?;
+        ],
+    )?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -167,6 +171,27 @@ pub struct ReviewComment {
     pub user: ReviewCommentUser,
     pub created_at: String,
     pub in_reply_to_id: Option<u64>,
+    /// コメント時点の hunk（末尾の行がコメント対象行）
+    #[serde(default)]
+    pub diff_hunk: Option<String>,
+}
+
+impl ReviewComment {
+    /// コメント対象の行範囲（start_line〜line）。outdated なら None
+    pub fn line_range(&self) -> Option<(usize, usize)> {
+        let end = self.line?;
+        Some((self.start_line.unwrap_or(end), end))
+    }
+
+    /// 行範囲の表示（"L12" / "L10–L12"）
+    pub fn line_range_label(&self) -> Option<String> {
+        let (start, end) = self.line_range()?;
+        Some(if start == end {
+            format!("L{end}")
+        } else {
+            format!("L{start}–L{end}")
+        })
+    }
 }
 
 pub async fn fetch_review_comments(
@@ -176,7 +201,8 @@ pub async fn fetch_review_comments(
     pr_number: u64,
 ) -> Result<Vec<ReviewComment>> {
     let url = format!("/repos/{}/{}/pulls/{}/comments", owner, repo, pr_number);
-    let comments: Vec<ReviewComment> = client.get(url, None::<&()>).await?;
+    let comments: Vec<ReviewComment> =
+        metrics::get_json_conditional(client, "review comments", url).await?;
     Ok(comments)
 }
 
@@ -212,6 +238,34 @@ pub async fn post_reply_comment(
     Ok(comment)
 }
 
+/// Pull Request Review Comments API で自分のコメントの本文を書き換える
+pub async fn update_review_comment(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    comment_id: u64,
+    body: &str,
+) -> Result<ReviewComment> {
+    let url = format!("/repos/{}/{}/pulls/comments/{}", owner, repo, comment_id);
+    let comment: ReviewComment = client
+        .patch(url, Some(&serde_json::json!({ "body": body })))
+        .await?;
+    Ok(comment)
+}
+
+/// Pull Request Review Comments API で自分のコメントを削除する（成功時は 204 で本文なし）
+pub async fn delete_review_comment(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    comment_id: u64,
+) -> Result<()> {
+    let url = format!("/repos/{}/{}/pulls/comments/{}", owner, repo, comment_id);
+    let response = client._delete(url.as_str(), None::<&()>).await?;
+    octocrab::map_github_error(response).await?;
+    Ok(())
+}
+
 /// Issue Comments API で PR に一般コメントを投稿
 pub async fn post_issue_comment(
     client: &Octocrab,
@@ -235,6 +289,7 @@ pub async fn fetch_issue_comments(
     pr_number: u64,
 ) -> Result<Vec<IssueComment>> {
     let url = format!("/repos/{}/{}/issues/{}/comments", owner, repo, pr_number);
-    let comments: Vec<IssueComment> = client.get(url, None::<&()>).await?;
+    let comments: Vec<IssueComment> =
+        metrics::get_json_conditional(client, "issue comments", url).await?;
     Ok(comments)
 }
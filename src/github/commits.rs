@@ -22,9 +22,9 @@ pub struct CommitDetail {
 }
 
 impl CommitInfo {
-    /// 短いSHA（7文字）を返す
+    /// 短いSHA（設定の桁数、デフォルト 7 文字）を返す
     pub fn short_sha(&self) -> &str {
-        &self.sha[..7.min(self.sha.len())]
+        crate::format::short_sha(&self.sha)
     }
 
     /// コミットメッセージの1行目を返す
@@ -57,6 +57,20 @@ pub async fn fetch_commits(
     pr_number: u64,
 ) -> Result<Vec<CommitInfo>> {
     let url = format!("/repos/{}/{}/pulls/{}/commits", owner, repo, pr_number);
-    let commits: Vec<CommitInfo> = client.get(url, None::<&()>).await?;
+    let commits: Vec<CommitInfo> = super::metrics::get_json(client, "commits", url).await?;
     Ok(commits)
 }
+
+/// コミットの最初の親 SHA を取得（パッチ省略ファイルのローカル diff 用）
+pub fn fetch_parent_sha(owner: &str, repo: &str, sha: &str) -> Result<String> {
+    let endpoint = format!("repos/{}/{}/commits/{}", owner, repo, sha);
+    let output = super::metrics::run_gh(
+        "commit parent",
+        &["api", &endpoint, "-q", ".parents[0].sha"],
+    )?;
+    let parent = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || parent.is_empty() {
+        return Err(color_eyre::eyre::eyre!("Could not find parent of {}", sha));
+    }
+    Ok(parent)
+}
@@ -0,0 +1,135 @@
+//! API エラーの分類（パネルごとに原因と対処のヒントを表示する）
+
+use std::io::ErrorKind as IoErrorKind;
+
+/// API エラーの種類（エラーチェーン内の型と HTTP ステータスから判定）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    /// 401、または権限・スコープ不足の 403
+    Auth,
+    NotFound,
+    RateLimit,
+    /// タイムアウト・接続エラー・5xx
+    Network,
+    /// レスポンスの JSON が想定と違う
+    Parse,
+    Other,
+}
+
+impl ApiErrorKind {
+    /// パネルに表示する短い説明
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Auth => "Not authorized",
+            Self::NotFound => "Not found",
+            Self::RateLimit => "Rate limit exceeded",
+            Self::Network => "Network error",
+            Self::Parse => "Unexpected API response",
+            Self::Other => "Failed to load",
+        }
+    }
+
+    /// 対処のヒント
+    pub fn hint(self) -> &'static str {
+        match self {
+            Self::Auth => "Run `gh auth refresh -s repo,read:org` to grant the required scopes",
+            Self::NotFound => {
+                "Check the PR number and repository (private repos need the repo scope)"
+            }
+            Self::RateLimit => "Wait for the rate limit to reset (I: API stats)",
+            Self::Network => "Check your connection or raise network.timeout_secs in the config",
+            Self::Parse => "The API returned data prism could not read; please report this",
+            Self::Other => "See the status line for details",
+        }
+    }
+}
+
+/// HTTP ステータス（と GitHub のエラーメッセージ）から分類する
+fn from_status(status: u16, message: &str) -> ApiErrorKind {
+    match status {
+        401 => ApiErrorKind::Auth,
+        // 403 はレート制限（secondary rate limit 含む）と権限不足の両方で返る
+        403 if message.to_ascii_lowercase().contains("rate limit") => ApiErrorKind::RateLimit,
+        403 => ApiErrorKind::Auth,
+        404 => ApiErrorKind::NotFound,
+        429 => ApiErrorKind::RateLimit,
+        500..=599 => ApiErrorKind::Network,
+        _ => ApiErrorKind::Other,
+    }
+}
+
+/// エラーチェーンをたどって分類する
+pub fn classify(error: &color_eyre::Report) -> ApiErrorKind {
+    for cause in error.chain() {
+        if let Some(e) = cause.downcast_ref::<octocrab::Error>() {
+            return match e {
+                octocrab::Error::GitHub { source, .. } => {
+                    from_status(source.status_code.as_u16(), &source.message)
+                }
+                octocrab::Error::Serde { .. } | octocrab::Error::Json { .. } => ApiErrorKind::Parse,
+                octocrab::Error::Hyper { .. }
+                | octocrab::Error::Service { .. }
+                | octocrab::Error::Http { .. } => ApiErrorKind::Network,
+                _ => ApiErrorKind::Other,
+            };
+        }
+        if cause.is::<serde_json::Error>() {
+            return ApiErrorKind::Parse;
+        }
+        if cause.is::<tokio::time::error::Elapsed>() || cause.is::<reqwest::Error>() {
+            return ApiErrorKind::Network;
+        }
+        if let Some(e) = cause.downcast_ref::<std::io::Error>()
+            && matches!(
+                e.kind(),
+                IoErrorKind::TimedOut
+                    | IoErrorKind::ConnectionRefused
+                    | IoErrorKind::ConnectionReset
+                    | IoErrorKind::ConnectionAborted
+                    | IoErrorKind::NotConnected
+            )
+        {
+            return ApiErrorKind::Network;
+        }
+    }
+    ApiErrorKind::Other
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_status() {
+        assert_eq!(from_status(401, "Bad credentials"), ApiErrorKind::Auth);
+        assert_eq!(
+            from_status(403, "API rate limit exceeded for user"),
+            ApiErrorKind::RateLimit
+        );
+        assert_eq!(
+            from_status(403, "Resource not accessible by integration"),
+            ApiErrorKind::Auth
+        );
+        assert_eq!(from_status(404, "Not Found"), ApiErrorKind::NotFound);
+        assert_eq!(from_status(429, ""), ApiErrorKind::RateLimit);
+        assert_eq!(from_status(502, "Bad Gateway"), ApiErrorKind::Network);
+        assert_eq!(from_status(422, "Validation Failed"), ApiErrorKind::Other);
+    }
+
+    #[test]
+    fn test_classify_error_chain() {
+        let parse = serde_json::from_str::<u32>("{").unwrap_err();
+        let report = color_eyre::Report::new(parse).wrap_err("Failed to load files");
+        assert_eq!(classify(&report), ApiErrorKind::Parse);
+
+        let timeout = std::io::Error::new(IoErrorKind::TimedOut, "gh timed out after 30s");
+        assert_eq!(
+            classify(&color_eyre::Report::new(timeout)),
+            ApiErrorKind::Network
+        );
+        assert_eq!(
+            classify(&color_eyre::eyre::eyre!("something else")),
+            ApiErrorKind::Other
+        );
+    }
+}
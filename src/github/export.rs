@@ -0,0 +1,383 @@
+//! 読み込み済みの PR（メタデータ・コミット・diff・レビュースレッド・保留コメント）をレポートに書き出す
+
+use super::comments::ReviewComment;
+use super::commits::CommitInfo;
+use super::files::DiffFile;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// レポートの出力形式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    /// 出力ファイルの拡張子
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// レビュースレッド（ルートコメントと返信）
+pub struct ReportThread<'a> {
+    pub path: &'a str,
+    /// 対象行の表示（"L10–L12"、outdated なら "outdated"）
+    pub lines: String,
+    pub resolved: bool,
+    pub comments: Vec<&'a ReviewComment>,
+}
+
+/// 未送信の保留コメント
+pub struct ReportPending<'a> {
+    pub path: &'a str,
+    pub lines: String,
+    pub commit: &'a str,
+    pub body: &'a str,
+}
+
+/// レポートに載せる PR のデータ
+pub struct ReviewReport<'a> {
+    pub repo: &'a str,
+    pub pr_number: u64,
+    pub title: &'a str,
+    pub author: &'a str,
+    pub state: &'a str,
+    pub base_branch: &'a str,
+    pub head_branch: &'a str,
+    pub body: &'a str,
+    pub commits: &'a [CommitInfo],
+    /// コミット SHA → 変更ファイル（未取得のコミットは含まれない）
+    pub files_map: &'a HashMap<String, Vec<DiffFile>>,
+    pub threads: Vec<ReportThread<'a>>,
+    pub pending: Vec<ReportPending<'a>>,
+}
+
+/// レポートを指定の形式で組み立てる
+pub fn render_report(report: &ReviewReport, format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Markdown => render_markdown(report),
+        ReportFormat::Html => render_html(report),
+    }
+}
+
+/// 本文中のバッククォートより長いコードフェンス（3 個以上）
+pub fn code_fence(text: &str) -> String {
+    let longest = text
+        .split(|c: char| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+/// ファイルの見出し（"src/main.rs (M +3 -1)"）
+fn file_label(file: &DiffFile) -> String {
+    format!(
+        "{} ({} +{} -{})",
+        file.filename,
+        file.status_char(),
+        file.additions,
+        file.deletions
+    )
+}
+
+fn render_markdown(report: &ReviewReport) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# PR #{}: {}\n", report.pr_number, report.title);
+    let _ = writeln!(out, "- Repository: {}", report.repo);
+    let _ = writeln!(out, "- Author: @{}", report.author);
+    let _ = writeln!(out, "- State: {}", report.state);
+    let _ = writeln!(
+        out,
+        "- Branch: `{}` ← `{}`",
+        report.base_branch, report.head_branch
+    );
+
+    let _ = writeln!(out, "\n## Description\n");
+    let body = report.body.trim();
+    let _ = writeln!(
+        out,
+        "{}",
+        if body.is_empty() {
+            "_No description._"
+        } else {
+            body
+        }
+    );
+
+    let _ = writeln!(out, "\n## Commits ({})\n", report.commits.len());
+    for commit in report.commits {
+        let _ = writeln!(
+            out,
+            "- `{}` {}",
+            commit.short_sha(),
+            commit.message_summary()
+        );
+    }
+
+    let _ = writeln!(out, "\n## Files");
+    for commit in report.commits {
+        let Some(files) = report.files_map.get(&commit.sha) else {
+            continue;
+        };
+        let _ = writeln!(
+            out,
+            "\n### `{}` {}",
+            commit.short_sha(),
+            commit.message_summary()
+        );
+        for file in files {
+            let _ = writeln!(out, "\n#### {}\n", file_label(file));
+            match file.patch.as_deref() {
+                Some(patch) => {
+                    let fence = code_fence(patch);
+                    let _ = writeln!(out, "{fence}diff\n{patch}\n{fence}");
+                }
+                None => {
+                    let _ = writeln!(out, "_Binary file or no diff available._");
+                }
+            }
+        }
+    }
+
+    let _ = writeln!(out, "\n## Review threads ({})", report.threads.len());
+    for thread in &report.threads {
+        let resolved = if thread.resolved { " (resolved)" } else { "" };
+        let _ = writeln!(out, "\n### {} {}{}\n", thread.path, thread.lines, resolved);
+        for comment in &thread.comments {
+            let _ = writeln!(
+                out,
+                "**@{}** · {}\n",
+                comment.user.login, comment.created_at
+            );
+            let _ = writeln!(out, "{}\n", comment.body.trim());
+        }
+    }
+
+    let _ = writeln!(out, "\n## My pending comments ({})", report.pending.len());
+    for pending in &report.pending {
+        let _ = writeln!(
+            out,
+            "\n### {} {} (`{}`)\n",
+            pending.path,
+            pending.lines,
+            crate::format::short_sha(pending.commit)
+        );
+        let _ = writeln!(out, "{}", pending.body.trim());
+    }
+    out
+}
+
+/// HTML の特殊文字をエスケープする
+fn html_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// diff を行ごとに色分けした <pre>
+fn html_patch(patch: &str) -> String {
+    let lines: Vec<String> = patch
+        .lines()
+        .map(|line| {
+            let class = match line.chars().next() {
+                Some('+') => "add",
+                Some('-') => "del",
+                _ if line.starts_with("@@") => "hunk",
+                _ => "ctx",
+            };
+            format!("<span class=\"{class}\">{}</span>", html_escape(line))
+        })
+        .collect();
+    format!("<pre class=\"diff\">{}</pre>", lines.join("\n"))
+}
+
+/// 本文（markdown 原文）は整形せず改行を保ったまま載せる
+fn html_text(text: &str) -> String {
+    format!("<div class=\"text\">{}</div>", html_escape(text.trim()))
+}
+
+const HTML_STYLE: &str = "body{font-family:sans-serif;max-width:960px;margin:auto;padding:1em}\
+.text{white-space:pre-wrap}\
+pre.diff{background:#f6f8fa;padding:.5em;overflow-x:auto}\
+.add{background:#e6ffec}.del{background:#ffebe9}.hunk{color:#0969da}\
+.resolved{color:#57606a}";
+
+fn render_html(report: &ReviewReport) -> String {
+    let mut out = String::new();
+    let title = format!("PR #{}: {}", report.pr_number, report.title);
+    let _ = writeln!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>",
+        html_escape(&title)
+    );
+    let _ = writeln!(out, "<h1>{}</h1>", html_escape(&title));
+    let _ = writeln!(
+        out,
+        "<ul>\n<li>Repository: {}</li>\n<li>Author: @{}</li>\n<li>State: {}</li>\n<li>Branch: <code>{}</code> ← <code>{}</code></li>\n</ul>",
+        html_escape(report.repo),
+        html_escape(report.author),
+        html_escape(report.state),
+        html_escape(report.base_branch),
+        html_escape(report.head_branch)
+    );
+
+    let _ = writeln!(out, "<h2>Description</h2>\n{}", html_text(report.body));
+
+    let _ = writeln!(out, "<h2>Commits ({})</h2>\n<ul>", report.commits.len());
+    for commit in report.commits {
+        let _ = writeln!(
+            out,
+            "<li><code>{}</code> {}</li>",
+            html_escape(commit.short_sha()),
+            html_escape(commit.message_summary())
+        );
+    }
+    let _ = writeln!(out, "</ul>\n<h2>Files</h2>");
+    for commit in report.commits {
+        let Some(files) = report.files_map.get(&commit.sha) else {
+            continue;
+        };
+        let _ = writeln!(
+            out,
+            "<h3><code>{}</code> {}</h3>",
+            html_escape(commit.short_sha()),
+            html_escape(commit.message_summary())
+        );
+        for file in files {
+            let _ = writeln!(out, "<h4>{}</h4>", html_escape(&file_label(file)));
+            let _ = writeln!(
+                out,
+                "{}",
+                file.patch.as_deref().map_or_else(
+                    || "<p><em>Binary file or no diff available.</em></p>".to_string(),
+                    html_patch
+                )
+            );
+        }
+    }
+
+    let _ = writeln!(out, "<h2>Review threads ({})</h2>", report.threads.len());
+    for thread in &report.threads {
+        let (class, resolved) = if thread.resolved {
+            (" class=\"resolved\"", " (resolved)")
+        } else {
+            ("", "")
+        };
+        let _ = writeln!(
+            out,
+            "<h3{class}>{} {}{resolved}</h3>",
+            html_escape(thread.path),
+            html_escape(&thread.lines)
+        );
+        for comment in &thread.comments {
+            let _ = writeln!(
+                out,
+                "<p><strong>@{}</strong> · {}</p>\n{}",
+                html_escape(&comment.user.login),
+                html_escape(&comment.created_at),
+                html_text(&comment.body)
+            );
+        }
+    }
+
+    let _ = writeln!(
+        out,
+        "<h2>My pending comments ({})</h2>",
+        report.pending.len()
+    );
+    for pending in &report.pending {
+        let _ = writeln!(
+            out,
+            "<h3>{} {} (<code>{}</code>)</h3>\n{}",
+            html_escape(pending.path),
+            html_escape(&pending.lines),
+            html_escape(crate::format::short_sha(pending.commit)),
+            html_text(pending.body)
+        );
+    }
+    let _ = writeln!(out, "</body>\n</html>");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::commits::CommitDetail;
+
+    fn sample_report<'a>(
+        commits: &'a [CommitInfo],
+        files_map: &'a HashMap<String, Vec<DiffFile>>,
+    ) -> ReviewReport<'a> {
+        ReviewReport {
+            repo: "owner/repo",
+            pr_number: 7,
+            title: "Add <feature>",
+            author: "alice",
+            state: "open",
+            base_branch: "main",
+            head_branch: "feature",
+            body: "",
+            commits,
+            files_map,
+            threads: Vec::new(),
+            pending: vec![ReportPending {
+                path: "src/lib.rs",
+                lines: "L3".to_string(),
+                commit: "abcdef1234567",
+                body: "nit",
+            }],
+        }
+    }
+
+    #[test]
+    fn test_render_report() {
+        let commits = vec![CommitInfo {
+            sha: "abcdef1234567".to_string(),
+            commit: CommitDetail {
+                message: "Initial\n\nbody".to_string(),
+                author: None,
+            },
+        }];
+        let mut files_map = HashMap::new();
+        files_map.insert(
+            "abcdef1234567".to_string(),
+            vec![DiffFile {
+                filename: "src/lib.rs".to_string(),
+                status: "modified".to_string(),
+                additions: 1,
+                deletions: 0,
+                patch: Some("@@ -1 +1,2 @@\n a\n+```b".to_string()),
+            }],
+        );
+        let report = sample_report(&commits, &files_map);
+
+        let markdown = render_report(&report, ReportFormat::Markdown);
+        assert!(markdown.starts_with("# PR #7: Add <feature>\n"));
+        assert!(markdown.contains("- `abcdef1` Initial\n"));
+        assert!(markdown.contains("#### src/lib.rs (M +1 -0)\n\n````diff\n"));
+        assert!(
+            markdown
+                .contains("## My pending comments (1)\n\n### src/lib.rs L3 (`abcdef1`)\n\nnit\n")
+        );
+
+        let html = render_report(&report, ReportFormat::Html);
+        assert!(html.contains("<h1>PR #7: Add &lt;feature&gt;</h1>"));
+        assert!(html.contains("<span class=\"add\">+```b</span>"));
+        assert_eq!(ReportFormat::Html.extension(), "html");
+    }
+}
@@ -39,6 +39,183 @@ pub async fn fetch_commit_files(
         files: Option<Vec<DiffFile>>,
     }
 
-    let response: CommitResponse = client.get(url, None::<&()>).await?;
+    let response: CommitResponse =
+        super::metrics::get_json_conditional(client, "commit files", url).await?;
     Ok(response.files.unwrap_or_default())
 }
+
+/// PR ファイル一覧の 1 ページの件数（API の上限）
+const PR_FILES_PER_PAGE: u64 = 100;
+/// PR ファイル一覧の最大ページ数（API は 3000 ファイルまでしか返さない）
+const PR_FILES_MAX_PAGES: u64 = 30;
+
+/// PR 全体（base...head）の変更ファイル一覧をページを辿って取得（API の上限の 3000 ファイルまで）
+pub async fn fetch_pr_files(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+) -> Result<Vec<DiffFile>> {
+    let mut files = Vec::new();
+    for page in 1..=PR_FILES_MAX_PAGES {
+        let url = format!(
+            "/repos/{}/{}/pulls/{}/files?per_page={}&page={}",
+            owner, repo, pr_number, PR_FILES_PER_PAGE, page
+        );
+        let page_files: Vec<DiffFile> =
+            super::metrics::get_json_conditional(client, "pr files", url).await?;
+        let last_page = (page_files.len() as u64) < PR_FILES_PER_PAGE;
+        files.extend(page_files);
+        if last_page {
+            break;
+        }
+    }
+    Ok(files)
+}
+
+/// 取得したファイル数が API の上限に達していて、それ以降のファイルが欠けている可能性があるか
+pub fn pr_files_truncated(count: usize) -> bool {
+    count as u64 >= PR_FILES_PER_PAGE * PR_FILES_MAX_PAGES
+}
+
+/// 大きな PR の概要用に、PR 全体の変更ファイルの統計だけを取得する（パッチは保持しない）
+pub async fn fetch_pr_file_stats(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    changed_files: u64,
+) -> Result<Vec<DiffFile>> {
+    let pages = changed_files
+        .div_ceil(PR_FILES_PER_PAGE)
+        .clamp(1, PR_FILES_MAX_PAGES);
+    let mut stats = Vec::new();
+    for page in 1..=pages {
+        let url = format!(
+            "/repos/{}/{}/pulls/{}/files?per_page={}&page={}",
+            owner, repo, pr_number, PR_FILES_PER_PAGE, page
+        );
+        let files: Vec<DiffFile> = super::metrics::get_json(client, "pr file stats", url).await?;
+        let last_page = (files.len() as u64) < PR_FILES_PER_PAGE;
+        stats.extend(files.into_iter().map(|f| DiffFile { patch: None, ..f }));
+        if last_page {
+            break;
+        }
+    }
+    Ok(stats)
+}
+
+/// head から辿って指定パスを変更したコミットの SHA を取得する（PR 外のコミットも含む、新しい順）
+pub async fn fetch_path_commits(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    head: &str,
+    path: &str,
+) -> Result<Vec<String>> {
+    let url = format!(
+        "/repos/{}/{}/commits?sha={}&path={}&per_page=100",
+        owner,
+        repo,
+        head,
+        encode_path(path)
+    );
+
+    #[derive(Deserialize)]
+    struct CommitRef {
+        sha: String,
+    }
+
+    let commits: Vec<CommitRef> = super::metrics::get_json(client, "path commits", url).await?;
+    Ok(commits.into_iter().map(|c| c.sha).collect())
+}
+
+/// 2コミット間（base...head）の比較の変更ファイル一覧を取得
+pub async fn fetch_compare_files(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    base: &str,
+    head: &str,
+) -> Result<Vec<DiffFile>> {
+    let url = format!("/repos/{}/{}/compare/{}...{}", owner, repo, base, head);
+
+    #[derive(Deserialize)]
+    struct CompareResponse {
+        files: Option<Vec<DiffFile>>,
+    }
+
+    let response: CompareResponse = super::metrics::get_json(client, "compare", url).await?;
+    Ok(response.files.unwrap_or_default())
+}
+
+/// 2コミット間（base...head）の比較から指定ファイルの差分を取得
+pub async fn fetch_compare_file(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    base: &str,
+    head: &str,
+    filename: &str,
+) -> Result<Option<DiffFile>> {
+    Ok(fetch_compare_files(client, owner, repo, base, head)
+        .await?
+        .into_iter()
+        .find(|f| f.filename == filename))
+}
+
+/// URL パス用にファイルパスをパーセントエンコード（`/` は区切りとして残す）
+fn encode_path(path: &str) -> String {
+    path.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// 指定 ref 時点のファイル内容を取得（raw メディアタイプ、バイナリの場合は None）
+pub fn fetch_file_content(
+    owner: &str,
+    repo: &str,
+    path: &str,
+    git_ref: &str,
+) -> Result<Option<String>> {
+    let endpoint = format!(
+        "repos/{}/{}/contents/{}?ref={}",
+        owner,
+        repo,
+        encode_path(path),
+        git_ref
+    );
+    let output = super::metrics::run_gh(
+        "file content",
+        &["api", "-H", "Accept: application/vnd.github.raw", &endpoint],
+    )?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(color_eyre::eyre::eyre!(
+            "Failed to fetch {}: {}",
+            path,
+            stderr.trim()
+        ));
+    }
+    // NUL バイトを含む場合はバイナリとみなす
+    if output.stdout.contains(&0) {
+        return Ok(None);
+    }
+    Ok(String::from_utf8(output.stdout).ok())
+}
+
+/// ファイル全体を追加行とするパッチを生成（新規ファイルの全文表示用）
+pub fn added_file_patch(content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut patch = format!("@@ -0,0 +1,{} @@", lines.len());
+    for line in lines {
+        patch.push_str("\n+");
+        patch.push_str(line);
+    }
+    patch
+}
@@ -1,34 +1,67 @@
+use crate::lru::SizedLru;
 use image::DynamicImage;
-use std::collections::HashMap;
 
-/// ダウンロード済み画像のキャッシュ（URL → デコード済み画像）
-#[derive(Default)]
+/// ダウンロード済み画像のキャッシュ（URL → デコード済み画像、予算を超えたら古いものから捨てる）
 pub struct MediaCache {
-    images: HashMap<String, DynamicImage>,
+    images: SizedLru<String, DynamicImage>,
+}
+
+impl Default for MediaCache {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl MediaCache {
+    /// 予算なし（App に渡したときに設定の予算を適用する）
     pub fn new() -> Self {
         Self {
-            images: HashMap::new(),
+            images: SizedLru::new(usize::MAX),
         }
     }
 
-    pub fn insert(&mut self, url: String, image: DynamicImage) {
-        self.images.insert(url, image);
+    /// 追加して、予算を超えて捨てた URL を返す
+    pub fn insert(&mut self, url: String, image: DynamicImage) -> Vec<String> {
+        let size = image.as_bytes().len();
+        self.images.insert(url, image, size)
+    }
+
+    pub fn get(&mut self, url: &str) -> Option<&DynamicImage> {
+        self.images.get(&url.to_string())
+    }
+
+    pub fn len(&self) -> usize {
+        self.images.len()
+    }
+
+    /// デコード済み画像の合計サイズ
+    pub fn total(&self) -> usize {
+        self.images.total()
     }
 
-    pub fn get(&self, url: &str) -> Option<&DynamicImage> {
-        self.images.get(url)
+    /// 予算を設定して、超えて捨てた URL を返す
+    pub fn set_budget(&mut self, bytes: usize) -> Vec<String> {
+        self.images.set_budget(bytes)
     }
 }
 
+/// アバター画像の一辺のピクセル数（Conversation のヘッダーに 2 セルで表示する）
+const AVATAR_SIZE: u32 = 32;
+
+/// ユーザーのアバター画像の URL（bot は "[bot]" を外した同名アカウントの画像になる）
+pub fn avatar_url(login: &str) -> String {
+    format!(
+        "https://github.com/{}.png?size={AVATAR_SIZE}",
+        login.trim_end_matches("[bot]")
+    )
+}
+
 /// GitHub トークンを取得する（環境変数 or gh auth token）
 fn get_token() -> Option<String> {
     if let Ok(token) = std::env::var("GITHUB_TOKEN") {
         return Some(token);
     }
-    std::process::Command::new("gh")
+    crate::exec::command("gh")?
         .args(["auth", "token"])
         .output()
         .ok()
@@ -49,7 +82,7 @@ pub async fn download_media(urls: Vec<String>) -> MediaCache {
 
     let token = get_token();
     let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
+        .timeout(super::retry::timeout())
         .build()
         .unwrap_or_default();
 
@@ -59,7 +92,10 @@ pub async fn download_media(urls: Vec<String>) -> MediaCache {
             let token = token.clone();
             let client = client.clone();
             async move {
-                let result = download_single_image(&client, &url, token.as_deref()).await;
+                let (client_ref, url_ref, token) = (&client, url.as_str(), token.as_deref());
+                let result =
+                    super::retry::with_retry(|| download_single_image(client_ref, url_ref, token))
+                        .await;
                 (url, result)
             }
         })
@@ -90,8 +126,10 @@ async fn download_single_image(
         request = request.header("Authorization", format!("token {}", token));
     }
 
+    let start = std::time::Instant::now();
     let response = request.send().await?.error_for_status()?;
     let bytes = response.bytes().await?;
+    super::metrics::record_call("media", start.elapsed(), bytes.len());
     let img = image::load_from_memory(&bytes)?;
     Ok(img)
 }
@@ -0,0 +1,281 @@
+use color_eyre::Result;
+use octocrab::Octocrab;
+use reqwest::StatusCode;
+use reqwest::header::{
+    ETAG, HeaderMap, HeaderName, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// エンドポイント種別ごとの集計
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CallStat {
+    pub count: u64,
+    pub total: Duration,
+    pub bytes: u64,
+}
+
+/// GitHub API のレート制限（最後に観測したレスポンスヘッダーの値）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub remaining: u64,
+    pub limit: u64,
+}
+
+/// API 呼び出しとロード時間の計測値
+#[derive(Debug, Default, Clone)]
+pub struct Metrics {
+    /// 名前 → 集計（表示順を安定させるため BTreeMap）
+    pub calls: BTreeMap<&'static str, CallStat>,
+    /// ロードフェーズ名と所要時間（記録順）
+    pub phases: Vec<(&'static str, Duration)>,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl Metrics {
+    const fn new() -> Self {
+        Self {
+            calls: BTreeMap::new(),
+            phases: Vec::new(),
+            cache_hits: 0,
+            cache_misses: 0,
+            rate_limit: None,
+        }
+    }
+
+    /// 全エンドポイントの合計
+    pub fn total(&self) -> CallStat {
+        self.calls
+            .values()
+            .fold(CallStat::default(), |acc, s| CallStat {
+                count: acc.count + s.count,
+                total: acc.total + s.total,
+                bytes: acc.bytes + s.bytes,
+            })
+    }
+
+    fn record_call(&mut self, name: &'static str, elapsed: Duration, bytes: usize) {
+        let stat = self.calls.entry(name).or_default();
+        stat.count += 1;
+        stat.total += elapsed;
+        stat.bytes += bytes as u64;
+    }
+}
+
+static METRICS: Mutex<Metrics> = Mutex::new(Metrics::new());
+
+/// 計測値を更新する（ロック失敗時は計測を諦める）
+fn with_metrics(f: impl FnOnce(&mut Metrics)) {
+    if let Ok(mut metrics) = METRICS.lock() {
+        f(&mut metrics);
+    }
+}
+
+/// API 呼び出し1回分を記録
+pub fn record_call(name: &'static str, elapsed: Duration, bytes: usize) {
+    with_metrics(|m| m.record_call(name, elapsed, bytes));
+}
+
+/// ロードフェーズの所要時間を記録
+pub fn record_phase(name: &'static str, elapsed: Duration) {
+    with_metrics(|m| m.phases.push((name, elapsed)));
+}
+
+/// ファイルキャッシュのヒット/ミスを記録
+pub fn record_cache(hit: bool) {
+    with_metrics(|m| {
+        if hit {
+            m.cache_hits += 1;
+        } else {
+            m.cache_misses += 1;
+        }
+    });
+}
+
+/// 現在の計測値のスナップショット
+pub fn snapshot() -> Metrics {
+    METRICS.lock().map(|m| m.clone()).unwrap_or_default()
+}
+
+/// レスポンスヘッダーからレート制限を読み取る
+fn parse_rate_limit(headers: &HeaderMap) -> Option<RateLimit> {
+    let get = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+    };
+    Some(RateLimit {
+        remaining: get("x-ratelimit-remaining")?,
+        limit: get("x-ratelimit-limit")?,
+    })
+}
+
+/// REST API の GET を計測付きで実行して JSON をデシリアライズする
+pub async fn get_json<T: DeserializeOwned>(
+    client: &Octocrab,
+    name: &'static str,
+    url: String,
+) -> Result<T> {
+    let start = Instant::now();
+    let url = url.as_str();
+    let (rate_limit, body) = super::retry::with_retry(|| async move {
+        let response = client._get(url).await?;
+        let response = octocrab::map_github_error(response).await?;
+        let rate_limit = parse_rate_limit(response.headers());
+        let body = client.body_to_string(response).await?;
+        Ok::<_, color_eyre::Report>((rate_limit, body))
+    })
+    .await?;
+    with_metrics(|m| {
+        m.record_call(name, start.elapsed(), body.len());
+        if rate_limit.is_some() {
+            m.rate_limit = rate_limit;
+        }
+    });
+    Ok(serde_json::from_str(&body)?)
+}
+
+/// 保存済みレスポンスから条件付きリクエストのヘッダーを作る
+fn conditional_headers(entry: Option<&super::cache::ConditionalEntry>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let Some(entry) = entry else {
+        return headers;
+    };
+    if let Some(etag) = entry.etag.as_deref().and_then(|v| v.parse().ok()) {
+        headers.insert(IF_NONE_MATCH, etag);
+    }
+    if let Some(date) = entry.last_modified.as_deref().and_then(|v| v.parse().ok()) {
+        headers.insert(IF_MODIFIED_SINCE, date);
+    }
+    headers
+}
+
+/// 条件付き GET（ETag / Last-Modified）。304 なら保存済みの本文を使い、キャッシュヒットとして記録する。
+/// 304 はレート制限を消費しないため、リロードやポーリングで繰り返し取得するエンドポイントに使う
+pub async fn get_json_conditional<T: DeserializeOwned>(
+    client: &Octocrab,
+    name: &'static str,
+    url: String,
+) -> Result<T> {
+    let start = Instant::now();
+    let cached = super::cache::read_conditional(&url);
+    let headers = conditional_headers(cached.as_ref());
+    let path = url.as_str();
+    let (rate_limit, fresh) = super::retry::with_retry(|| {
+        let headers = headers.clone();
+        async move {
+            let response = client._get_with_headers(path, Some(headers)).await?;
+            if response.status() == StatusCode::NOT_MODIFIED {
+                return Ok::<_, color_eyre::Report>((parse_rate_limit(response.headers()), None));
+            }
+            let response = octocrab::map_github_error(response).await?;
+            let rate_limit = parse_rate_limit(response.headers());
+            let header = |name: HeaderName| {
+                response
+                    .headers()
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string)
+            };
+            let (etag, last_modified) = (header(ETAG), header(LAST_MODIFIED));
+            let body = client.body_to_string(response).await?;
+            Ok((rate_limit, Some((etag, last_modified, body))))
+        }
+    })
+    .await?;
+
+    let body = match (fresh, cached) {
+        (Some((etag, last_modified, body)), _) => {
+            if etag.is_some() || last_modified.is_some() {
+                // 保存に失敗しても次回が通常の GET になるだけなので無視する
+                let _ = super::cache::write_conditional(&super::cache::ConditionalEntry {
+                    url: url.clone(),
+                    etag,
+                    last_modified,
+                    body: body.clone(),
+                });
+            }
+            record_cache(false);
+            body
+        }
+        (None, Some(cached)) => {
+            record_cache(true);
+            cached.body
+        }
+        // 条件ヘッダーなしで 304 は返らないはずだが、念のため通常の GET に戻す
+        (None, None) => return get_json(client, name, url).await,
+    };
+    with_metrics(|m| {
+        m.record_call(name, start.elapsed(), body.len());
+        if rate_limit.is_some() {
+            m.rate_limit = rate_limit;
+        }
+    });
+    Ok(serde_json::from_str(&body)?)
+}
+
+/// gh コマンドを計測付きで実行する（バイト数は stdout の長さ、タイムアウトとリトライは retry 参照）
+pub fn run_gh(name: &'static str, args: &[&str]) -> std::io::Result<std::process::Output> {
+    let start = Instant::now();
+    let output = super::retry::run_gh_with_retry(args)?;
+    record_call(name, start.elapsed(), output.stdout.len());
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_total() {
+        let mut metrics = Metrics::new();
+        metrics.record_call("a", Duration::from_millis(10), 100);
+        metrics.record_call("a", Duration::from_millis(20), 50);
+        metrics.record_call("b", Duration::from_millis(5), 1);
+        assert_eq!(
+            metrics.calls["a"],
+            CallStat {
+                count: 2,
+                total: Duration::from_millis(30),
+                bytes: 150,
+            }
+        );
+        let total = metrics.total();
+        assert_eq!(total.count, 3);
+        assert_eq!(total.bytes, 151);
+    }
+
+    #[test]
+    fn test_parse_rate_limit() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", "4990".parse().unwrap());
+        headers.insert("x-ratelimit-limit", "5000".parse().unwrap());
+        assert_eq!(
+            parse_rate_limit(&headers),
+            Some(RateLimit {
+                remaining: 4990,
+                limit: 5000,
+            })
+        );
+        assert_eq!(parse_rate_limit(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_conditional_headers() {
+        assert!(conditional_headers(None).is_empty());
+        let entry = super::super::cache::ConditionalEntry {
+            url: "/repos/o/r/pulls/1/reviews".to_string(),
+            etag: Some("\"abc\"".to_string()),
+            last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+            body: "[]".to_string(),
+        };
+        let headers = conditional_headers(Some(&entry));
+        assert_eq!(headers[IF_NONE_MATCH], "\"abc\"");
+        assert_eq!(headers[IF_MODIFIED_SINCE], "Wed, 21 Oct 2015 07:28:00 GMT");
+    }
+}
@@ -8,6 +8,354 @@ pub async fn fetch_pr(
     repo: &str,
     pr_number: u64,
 ) -> Result<PullRequest> {
-    let pr = client.pulls(owner, repo).get(pr_number).await?;
-    Ok(pr)
+    let url = format!("/repos/{}/{}/pulls/{}", owner, repo, pr_number);
+    super::metrics::get_json(client, "pull request", url).await
+}
+
+/// 指定ユーザーが作成した Open な PR の番号一覧を取得する（gh pr list 経由、古い順）
+pub fn list_open_prs_by_author(owner: &str, repo: &str, author: &str) -> Result<Vec<u64>> {
+    let output = super::metrics::run_gh(
+        "pr list",
+        &[
+            "pr",
+            "list",
+            "--repo",
+            &format!("{}/{}", owner, repo),
+            "--author",
+            author,
+            "--state",
+            "open",
+            "--limit",
+            "100",
+            "--json",
+            "number",
+            "--jq",
+            ".[].number",
+        ],
+    )?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(color_eyre::eyre::eyre!(
+            "gh pr list failed: {}",
+            stderr.trim()
+        ));
+    }
+    let mut numbers: Vec<u64> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|l| l.trim().parse().ok())
+        .collect();
+    numbers.sort_unstable();
+    Ok(numbers)
+}
+
+/// 指定ユーザーにレビューを（再）依頼する
+pub async fn request_reviewers(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+    reviewers: &[String],
+) -> Result<()> {
+    let url = format!(
+        "/repos/{}/{}/pulls/{}/requested_reviewers",
+        owner, repo, pr_number
+    );
+    let _: serde_json::Value = client
+        .post(url, Some(&serde_json::json!({ "reviewers": reviewers })))
+        .await?;
+    Ok(())
+}
+
+/// ユーザーがチームのアクティブなメンバーか（権限不足や非メンバーの 404 は false）
+pub async fn is_team_member(client: &Octocrab, org: &str, team_slug: &str, user: &str) -> bool {
+    let url = format!("/orgs/{}/teams/{}/memberships/{}", org, team_slug, user);
+    super::metrics::get_json::<serde_json::Value>(client, "team membership", url)
+        .await
+        .is_ok_and(|v| v["state"] == "active")
+}
+
+/// GraphQL updateSubscription で PR の通知を無視（IGNORED）または購読（SUBSCRIBED）にする。
+/// 戻り値は更新後に無視状態かどうか。
+pub fn set_pr_subscription(pr_node_id: &str, mute: bool) -> Result<bool> {
+    let state = if mute { "IGNORED" } else { "SUBSCRIBED" };
+    let query = r#"mutation($id: ID!, $state: SubscriptionState!) {
+  updateSubscription(input: {subscribableId: $id, state: $state}) {
+    subscribable {
+      viewerSubscription
+    }
+  }
+}"#;
+    let data = run_graphql(
+        "pr subscription",
+        query,
+        &[("id", pr_node_id), ("state", state)],
+    )?;
+    data["updateSubscription"]["subscribable"]["viewerSubscription"]
+        .as_str()
+        .map(|s| s == "IGNORED")
+        .ok_or_else(|| color_eyre::eyre::eyre!("Unexpected response format"))
+}
+
+/// ブランチ更新の方式（GitHub の "Update branch" ボタンと同じ選択肢）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateMethod {
+    Merge,
+    Rebase,
+}
+
+impl UpdateMethod {
+    fn as_graphql(self) -> &'static str {
+        match self {
+            UpdateMethod::Merge => "MERGE",
+            UpdateMethod::Rebase => "REBASE",
+        }
+    }
+}
+
+/// GraphQL updatePullRequestBranch で head ブランチに base の変更を取り込む。
+/// expected_head_sha と異なる head に対しては GitHub 側で拒否される
+pub fn update_branch(
+    pr_node_id: &str,
+    expected_head_sha: &str,
+    method: UpdateMethod,
+) -> Result<()> {
+    let query = r#"mutation($id: ID!, $head: GitObjectID!, $method: PullRequestBranchUpdateMethod!) {
+  updatePullRequestBranch(input: {pullRequestId: $id, expectedHeadOid: $head, updateMethod: $method}) {
+    pullRequest {
+      id
+    }
+  }
+}"#;
+    run_graphql(
+        "update branch",
+        query,
+        &[
+            ("id", pr_node_id),
+            ("head", expected_head_sha),
+            ("method", method.as_graphql()),
+        ],
+    )?;
+    Ok(())
+}
+
+/// 自動マージ時のマージ方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeMethod {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl MergeMethod {
+    fn as_graphql(self) -> &'static str {
+        match self {
+            MergeMethod::Merge => "MERGE",
+            MergeMethod::Squash => "SQUASH",
+            MergeMethod::Rebase => "REBASE",
+        }
+    }
+
+    fn from_graphql(s: &str) -> Option<Self> {
+        match s {
+            "MERGE" => Some(MergeMethod::Merge),
+            "SQUASH" => Some(MergeMethod::Squash),
+            "REBASE" => Some(MergeMethod::Rebase),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MergeMethod::Merge => "merge",
+            MergeMethod::Squash => "squash",
+            MergeMethod::Rebase => "rebase",
+        }
+    }
+}
+
+/// `gh api graphql` を実行して data を返す（変数はすべて文字列として渡す）
+fn run_graphql(
+    label: &'static str,
+    query: &str,
+    vars: &[(&str, &str)],
+) -> Result<serde_json::Value> {
+    let mut args = vec![
+        "api".to_string(),
+        "graphql".to_string(),
+        "-f".to_string(),
+        format!("query={query}"),
+    ];
+    for (name, value) in vars {
+        // -F だとファイルパスの "123" 等が数値になるため -f で文字列のまま渡す
+        args.push("-f".to_string());
+        args.push(format!("{name}={value}"));
+    }
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = super::metrics::run_gh(label, &args)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(color_eyre::eyre::eyre!(
+            "{} failed: {}",
+            label,
+            stderr.trim()
+        ));
+    }
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    Ok(json["data"].clone())
+}
+
+/// 現在の自動マージ設定を取得する（未設定なら None）
+pub fn fetch_auto_merge(pr_node_id: &str) -> Result<Option<MergeMethod>> {
+    let query = r#"query($id: ID!) {
+  node(id: $id) {
+    ... on PullRequest {
+      autoMergeRequest {
+        mergeMethod
+      }
+    }
+  }
+}"#;
+    let data = run_graphql("auto-merge state", query, &[("id", pr_node_id)])?;
+    Ok(data["node"]["autoMergeRequest"]["mergeMethod"]
+        .as_str()
+        .and_then(MergeMethod::from_graphql))
+}
+
+/// GitHub で "Viewed" にチェックされたファイル（チェック後に変更されて外れた DISMISSED は含めない）
+pub fn fetch_viewed_files(pr_node_id: &str) -> Result<std::collections::HashSet<String>> {
+    let query = r#"query($id: ID!, $after: String) {
+  node(id: $id) {
+    ... on PullRequest {
+      files(first: 100, after: $after) {
+        pageInfo {
+          hasNextPage
+          endCursor
+        }
+        nodes {
+          path
+          viewerViewedState
+        }
+      }
+    }
+  }
+}"#;
+    let mut viewed = std::collections::HashSet::new();
+    let mut after: Option<String> = None;
+    loop {
+        let data = match &after {
+            Some(cursor) => run_graphql(
+                "viewed files",
+                query,
+                &[("id", pr_node_id), ("after", cursor)],
+            )?,
+            None => run_graphql("viewed files", query, &[("id", pr_node_id)])?,
+        };
+        let files = &data["node"]["files"];
+        for node in files["nodes"].as_array().into_iter().flatten() {
+            if node["viewerViewedState"].as_str() == Some("VIEWED")
+                && let Some(path) = node["path"].as_str()
+            {
+                viewed.insert(path.to_string());
+            }
+        }
+        match files["pageInfo"]["endCursor"].as_str() {
+            Some(cursor) if files["pageInfo"]["hasNextPage"].as_bool() == Some(true) => {
+                after = Some(cursor.to_string());
+            }
+            _ => break,
+        }
+    }
+    Ok(viewed)
+}
+
+/// GraphQL markFileAsViewed / unmarkFileAsViewed でファイルの "Viewed" チェックを切り替える
+pub fn set_file_viewed(pr_node_id: &str, path: &str, viewed: bool) -> Result<()> {
+    let mutation = if viewed {
+        "markFileAsViewed"
+    } else {
+        "unmarkFileAsViewed"
+    };
+    let query = format!(
+        r#"mutation($id: ID!, $path: String!) {{
+  {mutation}(input: {{pullRequestId: $id, path: $path}}) {{
+    pullRequest {{
+      id
+    }}
+  }}
+}}"#
+    );
+    run_graphql("file viewed", &query, &[("id", pr_node_id), ("path", path)])?;
+    Ok(())
+}
+
+/// GraphQL enablePullRequestAutoMerge で、必要な条件を満たしたら自動マージするよう設定する
+pub fn enable_auto_merge(pr_node_id: &str, method: MergeMethod) -> Result<()> {
+    let query = r#"mutation($id: ID!, $method: PullRequestMergeMethod!) {
+  enablePullRequestAutoMerge(input: {pullRequestId: $id, mergeMethod: $method}) {
+    pullRequest {
+      id
+    }
+  }
+}"#;
+    run_graphql(
+        "enable auto-merge",
+        query,
+        &[("id", pr_node_id), ("method", method.as_graphql())],
+    )?;
+    Ok(())
+}
+
+/// GraphQL disablePullRequestAutoMerge で自動マージを解除する
+pub fn disable_auto_merge(pr_node_id: &str) -> Result<()> {
+    let query = r#"mutation($id: ID!) {
+  disablePullRequestAutoMerge(input: {pullRequestId: $id}) {
+    pullRequest {
+      id
+    }
+  }
+}"#;
+    run_graphql("disable auto-merge", query, &[("id", pr_node_id)])?;
+    Ok(())
+}
+
+/// GraphQL closePullRequest / reopenPullRequest で PR を閉じる・開き直す
+pub fn set_pr_closed(pr_node_id: &str, closed: bool) -> Result<()> {
+    let (mutation, label) = if closed {
+        ("closePullRequest", "close pull request")
+    } else {
+        ("reopenPullRequest", "reopen pull request")
+    };
+    let query = format!(
+        r#"mutation($id: ID!) {{
+  {mutation}(input: {{pullRequestId: $id}}) {{
+    pullRequest {{
+      id
+    }}
+  }}
+}}"#
+    );
+    run_graphql(label, &query, &[("id", pr_node_id)])?;
+    Ok(())
+}
+
+/// gh pr ready で Draft PR を Ready for review にする
+pub fn mark_ready_for_review(owner: &str, repo: &str, pr_number: u64) -> Result<()> {
+    let output = super::metrics::run_gh(
+        "pr ready",
+        &[
+            "pr",
+            "ready",
+            &pr_number.to_string(),
+            "--repo",
+            &format!("{}/{}", owner, repo),
+        ],
+    )?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(color_eyre::eyre::eyre!(
+            "gh pr ready failed: {}",
+            stderr.trim()
+        ));
+    }
+    Ok(())
 }
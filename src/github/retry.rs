@@ -0,0 +1,235 @@
+//! ネットワーク操作のタイムアウトとリトライ（設定の `network` で調整）
+//!
+//! REST の GET・gh 経由の読み取り（GraphQL query 含む）・画像ダウンロードに共通で適用する。
+//! 書き込み系（mutation や POST）は二重送信を避けるためタイムアウトのみでリトライしない。
+
+use crate::config::NetworkConfig;
+use std::future::Future;
+use std::io::Read;
+use std::process::{Command, Output, Stdio};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+static SETTINGS: OnceLock<NetworkConfig> = OnceLock::new();
+
+/// gh プロセスの終了を確認する間隔
+const GH_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// 設定をセットする（起動時に 1 回だけ、未設定ならデフォルト値）
+pub fn init(settings: NetworkConfig) {
+    let _ = SETTINGS.set(settings);
+}
+
+fn settings() -> NetworkConfig {
+    SETTINGS.get().copied().unwrap_or_default()
+}
+
+/// 1 回の試行のタイムアウト
+pub fn timeout() -> Duration {
+    Duration::from_secs(settings().timeout_secs)
+}
+
+/// n 回目（0 始まり）の失敗後の待ち時間（指数バックオフ）
+fn backoff(settings: &NetworkConfig, attempt: u32) -> Duration {
+    Duration::from_millis(settings.backoff_ms.saturating_mul(1 << attempt.min(16)))
+}
+
+/// 一時的な失敗か（タイムアウト・接続エラー・5xx）。404 や認証エラーはリトライしない
+pub fn is_transient(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    [
+        "timed out",
+        "timeout",
+        "deadline has elapsed",
+        "connection",
+        "error sending request",
+        "502",
+        "503",
+        "504",
+        "bad gateway",
+        "service unavailable",
+        "server error",
+    ]
+    .iter()
+    .any(|pattern| message.contains(pattern))
+}
+
+/// 非同期操作をタイムアウト付きで実行し、一時的な失敗ならバックオフしてリトライする
+pub async fn with_retry<T, E, F, Fut>(mut op: F) -> Result<T, E>
+where
+    E: std::fmt::Display + From<tokio::time::error::Elapsed>,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let settings = settings();
+    let mut attempt = 0;
+    loop {
+        let result = match tokio::time::timeout(timeout(), op()).await {
+            Ok(result) => result,
+            Err(elapsed) => Err(E::from(elapsed)),
+        };
+        match result {
+            Err(e) if attempt < settings.retries && is_transient(&e.to_string()) => {
+                tokio::time::sleep(backoff(&settings, attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// gh の引数が読み取り専用の操作か（リトライしてよいか）
+fn is_read_only(args: &[&str]) -> bool {
+    match args {
+        ["api", "graphql", rest @ ..] => !rest.iter().any(|a| a.contains("mutation")),
+        ["api", rest @ ..] => !rest.iter().any(|a| {
+            matches!(
+                *a,
+                "-X" | "--method" | "-f" | "-F" | "--field" | "--raw-field" | "--input"
+            )
+        }),
+        ["pr", "list" | "view", ..] => true,
+        _ => false,
+    }
+}
+
+/// タイムアウト付きでプロセスを実行する（超過したら kill して TimedOut を返す）
+fn output_with_timeout(cmd: &mut Command, timeout: Duration) -> std::io::Result<Output> {
+    let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    // パイプが詰まらないよう並行して読み取る
+    let mut stdout = child.stdout.take().expect("stdout was configured");
+    let mut stderr = child.stderr.take().expect("stderr was configured");
+    let stdout_reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stdout.read_to_end(&mut buf);
+        buf
+    });
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stderr.read_to_end(&mut buf);
+        buf
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("gh timed out after {}s", timeout.as_secs()),
+            ));
+        }
+        std::thread::sleep(GH_POLL_INTERVAL);
+    };
+    Ok(Output {
+        status,
+        stdout: stdout_reader.join().unwrap_or_default(),
+        stderr: stderr_reader.join().unwrap_or_default(),
+    })
+}
+
+/// gh をタイムアウト付きで実行し、読み取り操作なら一時的な失敗をリトライする
+pub fn run_gh_with_retry(args: &[&str]) -> std::io::Result<Output> {
+    let settings = settings();
+    let retries = if is_read_only(args) {
+        settings.retries
+    } else {
+        0
+    };
+    let mut attempt = 0;
+    loop {
+        let result = crate::exec::require("gh")
+            .and_then(|mut cmd| output_with_timeout(cmd.args(args), timeout()));
+        let transient = match &result {
+            Ok(output) => {
+                !output.status.success() && is_transient(&String::from_utf8_lossy(&output.stderr))
+            }
+            Err(e) => e.kind() == std::io::ErrorKind::TimedOut,
+        };
+        if !transient || attempt >= retries {
+            return result;
+        }
+        std::thread::sleep(backoff(&settings, attempt));
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_read_only() {
+        assert!(is_read_only(&[
+            "api",
+            "graphql",
+            "-f",
+            "query=query { viewer { login } }"
+        ]));
+        assert!(!is_read_only(&[
+            "api",
+            "graphql",
+            "-f",
+            "query=mutation { resolveReviewThread }"
+        ]));
+        assert!(is_read_only(&[
+            "api",
+            "repos/o/r/commits/abc",
+            "-q",
+            ".sha"
+        ]));
+        assert!(!is_read_only(&[
+            "api",
+            "repos/o/r/issues/1/comments",
+            "-f",
+            "body=hi"
+        ]));
+        assert!(is_read_only(&["pr", "list", "--author", "app/dependabot"]));
+        assert!(!is_read_only(&["pr", "ready", "1"]));
+    }
+
+    #[test]
+    fn test_is_transient() {
+        assert!(is_transient("error sending request: connection reset"));
+        assert!(is_transient("HTTP 502: Bad Gateway"));
+        assert!(!is_transient("HTTP 404: Not Found"));
+        assert!(!is_transient("Bad credentials"));
+        assert!(is_transient(
+            "error connecting to api.github.com\ncheck your internet connection"
+        ));
+        assert!(!is_transient("Resource not accessible by integration"));
+    }
+
+    #[test]
+    fn test_backoff_is_exponential() {
+        let settings = NetworkConfig {
+            timeout_secs: 30,
+            retries: 3,
+            backoff_ms: 200,
+        };
+        assert_eq!(backoff(&settings, 0), Duration::from_millis(200));
+        assert_eq!(backoff(&settings, 2), Duration::from_millis(800));
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_retries_transient_errors() {
+        let mut calls = 0;
+        let result: Result<u32, color_eyre::Report> = with_retry(|| {
+            calls += 1;
+            let n = calls;
+            async move {
+                if n < 2 {
+                    Err(color_eyre::eyre::eyre!("connection reset"))
+                } else {
+                    Ok(n)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 2);
+    }
+}
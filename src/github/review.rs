@@ -23,6 +23,16 @@ pub enum Side {
     Right,
 }
 
+impl Side {
+    /// API の side（"LEFT" / "RIGHT"）から変換する（未指定は RIGHT）
+    pub fn from_api(side: Option<&str>) -> Self {
+        match side {
+            Some("LEFT") => Self::Left,
+            _ => Self::Right,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct DiffLineInfo {
     pub file_line: usize,
@@ -105,10 +115,87 @@ pub async fn fetch_reviews(
     pr_number: u64,
 ) -> Result<Vec<ReviewSummary>> {
     let url = format!("/repos/{}/{}/pulls/{}/reviews", owner, repo, pr_number);
-    let reviews: Vec<ReviewSummary> = client.get(url, None::<&()>).await?;
+    let reviews: Vec<ReviewSummary> =
+        super::metrics::get_json_conditional(client, "reviews", url).await?;
     Ok(reviews)
 }
 
+/// タイムラインの 1 ページの件数（API の上限）
+const TIMELINE_PER_PAGE: usize = 100;
+/// タイムラインの最大ページ数（イベントの多い PR でも取得を打ち切る）
+const TIMELINE_MAX_PAGES: usize = 10;
+
+#[derive(Debug, Clone, Deserialize)]
+struct TimelineTeam {
+    slug: String,
+}
+
+/// タイムラインイベント（レビュー依頼の集計に必要なフィールドのみ）
+#[derive(Debug, Clone, Deserialize)]
+struct TimelineEvent {
+    event: Option<String>,
+    created_at: Option<String>,
+    requested_reviewer: Option<ReviewCommentUser>,
+    requested_team: Option<TimelineTeam>,
+}
+
+/// 依頼中のレビューの依頼日時（ユーザー login / チーム slug → 最後に依頼された日時）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReviewRequestTimes {
+    pub users: HashMap<String, String>,
+    pub teams: HashMap<String, String>,
+}
+
+/// タイムライン（古い順）から依頼中のレビューの依頼日時を集計する（取り下げられた依頼は除く）
+fn collect_review_requests(events: &[TimelineEvent]) -> ReviewRequestTimes {
+    let mut times = ReviewRequestTimes::default();
+    for e in events {
+        let requested = match e.event.as_deref() {
+            Some("review_requested") => true,
+            Some("review_request_removed") => false,
+            _ => continue,
+        };
+        let (map, key) = match (&e.requested_reviewer, &e.requested_team) {
+            (Some(user), _) => (&mut times.users, user.login.clone()),
+            (None, Some(team)) => (&mut times.teams, team.slug.clone()),
+            (None, None) => continue,
+        };
+        match (requested, &e.created_at) {
+            (true, Some(at)) => {
+                map.insert(key, at.clone());
+            }
+            _ => {
+                map.remove(&key);
+            }
+        }
+    }
+    times
+}
+
+/// Timeline API からレビュー依頼の日時を取得する
+pub async fn fetch_review_requests(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    pr_number: u64,
+) -> Result<ReviewRequestTimes> {
+    let mut events = Vec::new();
+    for page in 1..=TIMELINE_MAX_PAGES {
+        let url = format!(
+            "/repos/{}/{}/issues/{}/timeline?per_page={}&page={}",
+            owner, repo, pr_number, TIMELINE_PER_PAGE, page
+        );
+        let page_events: Vec<TimelineEvent> =
+            super::metrics::get_json(client, "timeline", url).await?;
+        let last_page = page_events.len() < TIMELINE_PER_PAGE;
+        events.extend(page_events);
+        if last_page {
+            break;
+        }
+    }
+    Ok(collect_review_requests(&events))
+}
+
 #[derive(Debug, Serialize)]
 struct ReviewComment {
     path: String,
@@ -236,6 +323,31 @@ pub async fn submit_review(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_collect_review_requests() {
+        let events: Vec<TimelineEvent> = serde_json::from_str(
+            r#"[
+                {"event":"committed","sha":"abc"},
+                {"event":"review_requested","created_at":"2024-01-01T00:00:00Z","requested_reviewer":{"login":"alice"}},
+                {"event":"review_requested","created_at":"2024-01-01T00:00:00Z","requested_reviewer":{"login":"bob"}},
+                {"event":"review_requested","created_at":"2024-01-02T00:00:00Z","requested_team":{"slug":"core"}},
+                {"event":"review_request_removed","created_at":"2024-01-03T00:00:00Z","requested_reviewer":{"login":"bob"}},
+                {"event":"review_requested","created_at":"2024-01-04T00:00:00Z","requested_reviewer":{"login":"alice"}}
+            ]"#,
+        )
+        .unwrap();
+        let times = collect_review_requests(&events);
+        assert_eq!(
+            times.users.get("alice").map(String::as_str),
+            Some("2024-01-04T00:00:00Z")
+        );
+        assert!(!times.users.contains_key("bob"));
+        assert_eq!(
+            times.teams.get("core").map(String::as_str),
+            Some("2024-01-02T00:00:00Z")
+        );
+    }
+
     #[test]
     fn test_parse_hunk_header_basic() {
         let result = parse_hunk_header("@@ -1,5 +1,7 @@");
@@ -394,6 +506,29 @@ mod tests {
         assert_eq!(comment.start_side, Some(Side::Right));
     }
 
+    #[test]
+    fn test_build_review_comment_keeps_suggestion_verbatim() {
+        let files = vec![DiffFile {
+            filename: "src/main.rs".to_string(),
+            status: "added".to_string(),
+            additions: 2,
+            deletions: 0,
+            patch: Some("@@ -0,0 +1,2 @@\n+let a = 1;\n+let b = 2;".to_string()),
+        }];
+        let body = "Rename:\n\n```suggestion\nlet x = 1;  \n\tlet y = 2;\n```\n";
+        let pending = PendingComment {
+            file_path: "src/main.rs".to_string(),
+            start_line: 1,
+            end_line: 2,
+            body: body.to_string(),
+            commit_sha: "abc123".to_string(),
+        };
+
+        let comment = build_review_comment(&pending, &files).unwrap();
+        let json = serde_json::to_value(&comment).unwrap();
+        assert_eq!(json["body"], body);
+    }
+
     #[test]
     fn test_build_review_comment_hunk_header_error() {
         let files = vec![DiffFile {
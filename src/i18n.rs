@@ -0,0 +1,728 @@
+//! UI 文字列の翻訳（英語の原文をキーにした簡易テーブル。未登録の文字列は英語のまま表示）
+
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// 表示言語の設定（auto は環境変数 LANG などから判定）
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    Auto,
+    En,
+    Ja,
+}
+
+/// 実際に使うロケール
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    En,
+    Ja,
+}
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// 起動時に一度だけロケールを確定する
+pub fn init(locale: Locale) {
+    let _ = LOCALE.set(locale);
+}
+
+/// 現在のロケール（未初期化なら英語）
+pub fn locale() -> Locale {
+    LOCALE.get().copied().unwrap_or_default()
+}
+
+/// 設定と環境変数からロケールを決める（GH_PRISM_LANG > 設定ファイル > LC_ALL / LC_MESSAGES / LANG）
+pub fn detect_locale(pref: Language) -> Locale {
+    let env = |key: &str| std::env::var(key).ok().filter(|v| !v.is_empty());
+    let system = env("LC_ALL")
+        .or_else(|| env("LC_MESSAGES"))
+        .or_else(|| env("LANG"));
+    resolve_locale(pref, env("GH_PRISM_LANG").as_deref(), system.as_deref())
+}
+
+fn resolve_locale(pref: Language, override_lang: Option<&str>, system: Option<&str>) -> Locale {
+    let from_tag = |tag: &str| {
+        if tag.to_lowercase().starts_with("ja") {
+            Locale::Ja
+        } else {
+            Locale::En
+        }
+    };
+    if let Some(tag) = override_lang {
+        return from_tag(tag);
+    }
+    match pref {
+        Language::En => Locale::En,
+        Language::Ja => Locale::Ja,
+        Language::Auto => system.map(from_tag).unwrap_or_default(),
+    }
+}
+
+/// 現在のロケールで文字列を翻訳する
+pub fn tr(text: &str) -> &str {
+    tr_in(locale(), text)
+}
+
+/// 指定ロケールで文字列を翻訳する（未登録なら原文）
+pub fn tr_in(locale: Locale, text: &str) -> &str {
+    let table = match locale {
+        Locale::En => return text,
+        Locale::Ja => JA,
+    };
+    table
+        .iter()
+        .find(|(en, _)| *en == text)
+        .map_or(text, |&(_, translated)| translated)
+}
+
+/// 日本語テーブル（英語の原文 → 訳文）
+const JA: &[(&str, &str)] = &[
+    // ヘルプ: セクション
+    ("Navigation", "ナビゲーション"),
+    ("Scroll", "スクロール"),
+    ("PR Description", "PR 説明"),
+    ("Commit List", "コミット一覧"),
+    ("File Tree", "ファイル一覧"),
+    ("Commit Message", "コミットメッセージ"),
+    ("Diff View", "差分"),
+    ("Conversation", "会話"),
+    ("Commit Overview", "コミット概要"),
+    // ヘルプ: 共通
+    ("Move down", "下へ移動"),
+    ("Move up", "上へ移動"),
+    ("Next pane", "次のペイン"),
+    ("Previous pane", "前のペイン"),
+    ("Jump to pane", "ペインへ移動"),
+    (
+        "Back to parent pane (dismisses an error first)",
+        "親ペインへ戻る（エラー表示中はまず消す）",
+    ),
+    ("Toggle zoom", "ズーム切替"),
+    ("Reload PR data", "PR データを再読み込み"),
+    ("Submit review", "レビューを送信"),
+    (
+        "Submit review from any mode (keeps drafts)",
+        "どのモードからでもレビューを送信（入力中の内容は下書きに保存）",
+    ),
+    ("Run command (--exec)", "コマンド実行 (--exec)"),
+    ("Review checklist", "レビューチェックリスト"),
+    ("Preview pending comments", "保留中のコメントを確認"),
+    (
+        "Author dashboard (own PR)",
+        "作者ダッシュボード（自分の PR）",
+    ),
+    (
+        "Links in description/conversation",
+        "説明・会話内のリンク一覧",
+    ),
+    ("Message history", "メッセージ履歴"),
+    (
+        "Find file across all commits",
+        "全コミットからファイルを検索",
+    ),
+    ("API call stats", "API 呼び出し統計"),
+    (
+        "CI checks for the head commit",
+        "head コミットの CI チェック",
+    ),
+    ("Toggle relative / absolute times", "相対 / 絶対時刻の切替"),
+    (
+        "Swap description / conversation in sidebar",
+        "サイドバーの説明 / 会話を入れ替え",
+    ),
+    (
+        "Export review report (--export-format)",
+        "レビューレポートを書き出し（--export-format）",
+    ),
+    ("Update branch from base", "base の変更をブランチに取り込む"),
+    (
+        "Check out PR branch locally (gh pr checkout)",
+        "PR のブランチをローカルにチェックアウト（gh pr checkout）",
+    ),
+    (
+        "✗ Fork does not allow edits from maintainers. Only the author can update the branch.",
+        "✗ fork でメンテナーの編集が許可されていません。ブランチを更新できるのは作成者のみです",
+    ),
+    (
+        "Fork without maintainer edits: only the author can apply suggestions",
+        "メンテナーの編集が許可されていない fork です。suggestion を適用できるのは作成者のみです",
+    ),
+    ("Enable / disable auto-merge", "自動マージの有効化 / 解除"),
+    ("Close / reopen the PR", "PR を閉じる / 開き直す"),
+    (
+        "Large PR summary (lazy loading)",
+        "大きな PR の概要（遅延読み込み）",
+    ),
+    (
+        "Group / collapse / inline test files",
+        "テストファイルをまとめる / 折りたたむ / 通常表示",
+    ),
+    ("Tests", "テスト"),
+    (
+        "Mouse capture unavailable: use the keyboard",
+        "マウスを使えません。キーボードで操作してください",
+    ),
+    ("Test files: inline", "テストファイル: 通常表示"),
+    (
+        "Follow file across commits",
+        "コミットをまたいでファイルを追従",
+    ),
+    ("unresolved", "件未解決"),
+    ("Header", "見出し"),
+    ("Attach image (in comment)", "画像を添付（コメント入力中）"),
+    (
+        "Edit in $EDITOR (in comment)",
+        "$EDITOR で編集（コメント入力中）",
+    ),
+    (
+        "✗ No editor configured — set $EDITOR",
+        "✗ エディタが未設定です — $EDITOR を設定してください",
+    ),
+    (" Attach image ", " 画像を添付 "),
+    (
+        "Enter: attach (empty: clipboard) | Esc: cancel",
+        "Enter: 添付（空ならクリップボード） | Esc: 取消",
+    ),
+    ("Uploading image...", "画像をアップロード中..."),
+    ("✓ Image attached", "✓ 画像を添付しました"),
+    ("✗ Image file not found", "✗ 画像ファイルが見つかりません"),
+    (
+        "✗ Not an image (png, jpg, gif, webp)",
+        "✗ 画像ではありません（png, jpg, gif, webp）",
+    ),
+    (
+        "✗ No image in clipboard",
+        "✗ クリップボードに画像がありません",
+    ),
+    (
+        "✗ Clipboard image helper is not in allowed_commands",
+        "✗ クリップボード画像の取得プログラムが allowed_commands にありません",
+    ),
+    ("pending", "件保留"),
+    ("queued", "件再送待ち"),
+    ("author", "作成者"),
+    ("assignee", "担当者"),
+    ("review requested", "レビュー依頼あり"),
+    ("you approved", "承認済み"),
+    ("you requested changes", "変更を要求済み"),
+    ("your review was dismissed", "レビューが却下済み"),
+    ("you commented", "コメント済み"),
+    ("✓ Stopped following file", "✓ ファイルの追従を止めました"),
+    (
+        "✓ Following file across commits (f to stop)",
+        "✓ コミットをまたいでファイルを追従します（f で解除）",
+    ),
+    (
+        "Followed file is not changed in this commit",
+        "追従中のファイルはこのコミットで変更されていません",
+    ),
+    (
+        "Test files: grouped last",
+        "テストファイル: 最後にまとめて表示",
+    ),
+    ("Test files: collapsed", "テストファイル: 折りたたみ"),
+    ("This help", "このヘルプ"),
+    ("Suspend (resume with fg)", "一時停止（fg で再開）"),
+    ("Quit", "終了"),
+    ("Half page down / up", "半ページ下 / 上"),
+    ("Full page down / up", "1ページ下 / 上"),
+    ("Top / Bottom", "先頭 / 末尾"),
+    // ヘルプ: ペイン固有
+    (
+        "Toggle <details> / open conversation",
+        "<details> 開閉 / 会話を開く",
+    ),
+    ("Open media viewer", "メディアビューアを開く"),
+    ("Toggle viewed", "確認済みの切替"),
+    ("Copy SHA", "SHA をコピー"),
+    (
+        "Select range (x marks all viewed)",
+        "範囲選択（x でまとめて確認済み）",
+    ),
+    ("Copy commit message", "コミットメッセージをコピー"),
+    (
+        "Compare pinned file with this commit",
+        "固定したファイルをこのコミットと比較",
+    ),
+    (
+        "Mark as interdiff base (diff from it to the selected commit)",
+        "interdiff の基準にする（そこから選択中のコミットまでの差分）",
+    ),
+    ("Open diff", "差分を開く"),
+    ("Copy file path", "ファイルパスをコピー"),
+    (
+        "Pin file for commit compare",
+        "コミット比較用にファイルを固定",
+    ),
+    (
+        "File history across commits",
+        "コミットをまたいだファイル履歴",
+    ),
+    ("Edit private note for file", "ファイルの非公開メモを編集"),
+    ("Export notes to markdown", "メモを Markdown に書き出す"),
+    ("Switch to diff view", "差分へ切替"),
+    ("Back to file tree", "ファイル一覧へ戻る"),
+    ("Switch to commit message", "コミットメッセージへ切替"),
+    ("Toggle line numbers", "行番号の切替"),
+    ("Toggle line wrap", "折り返しの切替"),
+    ("Toggle coverage overlay", "カバレッジ表示の切替"),
+    ("Next / prev change block", "次 / 前の変更ブロック"),
+    ("Next / prev hunk", "次 / 前のハンク"),
+    ("Next / prev comment", "次 / 前のコメント"),
+    (
+        "Next / prev file (across commits)",
+        "次 / 前のファイル（コミットをまたぐ）",
+    ),
+    ("Next / prev unviewed file", "次 / 前の未確認ファイル"),
+    ("Search in diff", "差分内を検索"),
+    (
+        "Toggle case / whole word / regex",
+        "大文字小文字 / 単語単位 / 正規表現の切替",
+    ),
+    (
+        "Load omitted patch (full file / local diff)",
+        "省略されたパッチを読み込む（全文 / ローカル差分）",
+    ),
+    ("Next / prev search match", "次 / 前の検索結果"),
+    ("Comment on all search matches", "全検索結果にコメント"),
+    ("Enter line select mode", "行選択モードに入る"),
+    ("Jump to commented code", "コメント対象のコードへ移動"),
+    ("Fold / unfold entry", "エントリを折りたたむ / 展開する"),
+    (
+        "Fold / unfold all entries",
+        "全エントリを折りたたむ / 展開する",
+    ),
+    ("Fold / unfold hunk", "hunk を折りたたむ / 展開する"),
+    ("Fold / unfold all hunks", "全 hunk を折りたたむ / 展開する"),
+    ("Mark hunk reviewed", "hunk を確認済みにする"),
+    (
+        "Copy permalink of selected lines",
+        "選択行のパーマリンクをコピー",
+    ),
+    (
+        "Copy selected lines as diff snippet",
+        "選択行を diff スニペットとしてコピー",
+    ),
+    (
+        "Copy old / new version of selected lines",
+        "選択行の変更前 / 変更後のテキストをコピー",
+    ),
+    (
+        "Copy permalink of thread lines",
+        "スレッド対象行のパーマリンクをコピー",
+    ),
+    ("Comment on line", "行にコメント"),
+    ("View comment on line", "行のコメントを表示"),
+    ("Reply to thread", "スレッドに返信"),
+    ("Quick reply (in reply)", "定型返信（返信中）"),
+    (
+        "Select lines to quote in reply (J/K: extend)",
+        "返信に引用する行を選択（J/K: 範囲を広げる）",
+    ),
+    (
+        "Select comment to edit or delete",
+        "編集・削除するコメントを選択",
+    ),
+    (
+        "Edit selected comment (yours)",
+        "選択中の自分のコメントを編集",
+    ),
+    (
+        "Delete selected comment (yours)",
+        "選択中の自分のコメントを削除",
+    ),
+    (
+        "Link selected commit (in reply)",
+        "選択中コミットのリンク（返信中）",
+    ),
+    ("Resolve after reply (in reply)", "返信後に解決（返信中）"),
+    ("Resolve/unresolve thread", "スレッドの解決 / 未解決"),
+    ("Insert suggestion", "suggestion を挿入"),
+    ("Submit comment", "コメントを送信"),
+    ("Next / prev entry", "次 / 前のエントリ"),
+    ("Reply / comment on PR", "返信 / PR にコメント"),
+    ("Mute / unmute thread", "スレッドのミュート切替"),
+    ("Mute / unmute PR notifications", "PR 通知のミュート切替"),
+    ("Jump to first unread entry", "最初の未読エントリへジャンプ"),
+    ("All changes", "すべての変更"),
+    ("All", "全体"),
+    (
+        "✗ Select a commit to pin",
+        "✗ 固定するコミットを選択してください",
+    ),
+    (
+        "✗ Select a commit to load the full diff",
+        "✗ 全文を取得するコミットを選択してください",
+    ),
+    ("No unread entries", "未読のエントリはありません"),
+    ("Back to PR description", "PR 説明へ戻る"),
+    ("Scroll down / up", "下 / 上へスクロール"),
+    ("Back to commit list", "コミット一覧へ戻る"),
+    ("?/Esc/q: close", "?/Esc/q: 閉じる"),
+    ("/: filter", "/: 絞り込み"),
+    ("No matching keys", "一致するキーはありません"),
+    // パネルキーヒント
+    (" o: media ", " o: メディア "),
+    (" x: viewed ", " x: 確認済み "),
+    (
+        "x: toggle viewed | Esc: cancel",
+        "x: 確認済みを切替 | Esc: キャンセル",
+    ),
+    (" c: comment ", " c: コメント "),
+    (" v: select | c: comment ", " v: 選択 | c: コメント "),
+    (" c: comment | s: suggest ", " c: コメント | s: 修正提案 "),
+    (
+        "Suggest change for selected lines",
+        "選択行の修正提案を書く",
+    ),
+    // ダイアログ
+    (" Quit Confirmation ", " 終了確認 "),
+    (
+        "{n} unsent comment(s).",
+        "未送信のコメントが {n} 件あります。",
+    ),
+    ("Submit before quitting?", "終了前に送信しますか？"),
+    ("y: submit & quit", "y: 送信して終了"),
+    ("n: discard & quit", "n: 破棄して終了"),
+    ("c: cancel", "c: キャンセル"),
+    ("Still running:", "実行中の処理:"),
+    ("✓ All done", "✓ すべて完了しました"),
+    ("Loading changed files", "変更ファイルの読み込み"),
+    ("Loading conversation", "会話の読み込み"),
+    ("Loading images", "画像の読み込み"),
+    (
+        "Syncing viewed files to GitHub",
+        "viewed の GitHub への同期",
+    ),
+    ("Waiting… quits when done", "待機中… 完了したら終了します"),
+    ("w: wait, then quit", "w: 完了を待って終了"),
+    ("q: quit anyway", "q: このまま終了"),
+    (" Submit Review ", " レビュー送信 "),
+    (" Update Branch ", " ブランチ更新 "),
+    (" Discard unsent text? ", " 入力内容を破棄しますか？ "),
+    ("Pending Comments", "保留中のコメント"),
+    ("d: discard", "d: 破棄"),
+    ("k: keep editing", "k: 編集を続ける"),
+    ("s: save as draft", "s: 下書きとして保存"),
+    (
+        "Branch is behind base.",
+        "ブランチが base より遅れています。",
+    ),
+    ("m: merge base into branch", "m: base をマージ"),
+    ("r: rebase onto base", "r: base にリベース"),
+    (" Auto-merge ", " 自動マージ "),
+    ("Auto-merge is enabled", "自動マージ: 有効"),
+    ("Auto-merge is disabled", "自動マージ: 無効"),
+    ("m: merge commit", "m: マージコミット"),
+    ("s: squash and merge", "s: スカッシュしてマージ"),
+    ("r: rebase and merge", "r: リベースしてマージ"),
+    ("d: disable auto-merge", "d: 自動マージを解除"),
+    (" Close PR ", " PR を閉じる "),
+    (" Reopen PR ", " PR を開き直す "),
+    ("Close this pull request?", "この PR を閉じますか？"),
+    ("Reopen this pull request?", "この PR を開き直しますか？"),
+    ("y: close PR", "y: 閉じる"),
+    ("y: reopen PR", "y: 開き直す"),
+    (" Checkout ", " チェックアウト "),
+    (
+        "Check out this PR locally?",
+        "この PR をローカルにチェックアウトしますか？",
+    ),
+    ("y: checkout", "y: チェックアウト"),
+    (" Run external program ", " 外部プログラムの実行 "),
+    (
+        "Allow prism to run this program?",
+        "このプログラムの実行を許可しますか？",
+    ),
+    ("y: this session", "y: 今回だけ許可"),
+    (
+        "a: always (saved to allowed_commands)",
+        "a: 常に許可（allowed_commands に保存）",
+    ),
+    ("n: not in this session", "n: 今回は許可しない"),
+    (" Delete Comment ", " コメントを削除 "),
+    ("Delete your comment?", "このコメントを削除しますか？"),
+    ("y: delete", "y: 削除"),
+    ("Unsent pending comments:", "未送信の保留コメント:"),
+    // バッチレビュー
+    (
+        " a: approve | s: skip | j/k: scroll | q: finish ",
+        " a: 承認 | s: スキップ | j/k: スクロール | q: 終了 ",
+    ),
+    ("Summary", "結果"),
+    (" Press any key to exit ", " 任意のキーで終了 "),
+    ("Approved:", "承認:"),
+    ("Skipped:", "スキップ:"),
+    ("Failed:", "失敗:"),
+    ("Not reviewed:", "未処理:"),
+    // ブロッキング操作
+    ("Submitting review...", "レビューを送信中..."),
+    ("Submitting comment...", "コメントを送信中..."),
+    ("Submitting reply...", "返信を送信中..."),
+    ("Reloading PR data...", "PR データを再読み込み中..."),
+    ("Updating thread...", "スレッドを更新中..."),
+    (
+        "Syncing queued thread actions...",
+        "保留中のスレッド操作を送信中...",
+    ),
+    ("Requesting review...", "レビューを依頼中..."),
+    (
+        "Marking ready for review...",
+        "Ready for review に変更中...",
+    ),
+    ("Running command...", "コマンドを実行中..."),
+    ("Comparing commits...", "コミットを比較中..."),
+    ("Loading full file...", "ファイル全体を読み込み中..."),
+    ("Updating subscription...", "通知設定を更新中..."),
+    ("Updating branch...", "ブランチを更新中..."),
+    ("Checking out...", "チェックアウト中..."),
+    ("Loading files...", "ファイルを読み込み中..."),
+    ("Large PR summary", "大きな PR の概要"),
+    ("R: retry", "R: 再試行"),
+    ("Not authorized", "権限がありません"),
+    ("Not found", "見つかりません"),
+    ("Rate limit exceeded", "レート制限を超えました"),
+    ("Network error", "ネットワークエラー"),
+    (
+        "Unexpected API response",
+        "API のレスポンスを読み取れません",
+    ),
+    ("Failed to load", "読み込みに失敗しました"),
+    (
+        "Run `gh auth refresh -s repo,read:org` to grant the required scopes",
+        "`gh auth refresh -s repo,read:org` で必要なスコープを付与してください",
+    ),
+    (
+        "Check the PR number and repository (private repos need the repo scope)",
+        "PR 番号とリポジトリを確認してください（private リポジトリには repo スコープが必要）",
+    ),
+    (
+        "Wait for the rate limit to reset (I: API stats)",
+        "レート制限のリセットを待ってください（I: API 統計）",
+    ),
+    (
+        "Check your connection or raise network.timeout_secs in the config",
+        "接続を確認するか、設定の network.timeout_secs を増やしてください",
+    ),
+    (
+        "The API returned data prism could not read; please report this",
+        "API から読み取れないデータが返されました。報告をお願いします",
+    ),
+    (
+        "See the status line for details",
+        "詳細はステータス行を確認してください",
+    ),
+    ("Updating auto-merge...", "自動マージを設定中..."),
+    ("Closing PR...", "PR を閉じています..."),
+    ("Reopening PR...", "PR を開き直しています..."),
+    // ステータスメッセージ
+    ("Comment is empty", "コメントが空です"),
+    (
+        "✗ No permalink for this line",
+        "✗ この行のパーマリンクを作成できません",
+    ),
+    ("✗ No diff to copy", "✗ コピーする差分がありません"),
+    ("Reply is empty", "返信が空です"),
+    ("No line selection", "行が選択されていません"),
+    (
+        "No pending comments to submit",
+        "送信するコメントがありません",
+    ),
+    (
+        "You cannot approve your own PR",
+        "自分の PR は承認できません",
+    ),
+    (
+        "You cannot request changes on your own PR",
+        "自分の PR には変更をリクエストできません",
+    ),
+    (
+        "✗ You cannot approve your own PR",
+        "✗ 自分の PR は承認できません",
+    ),
+    (
+        "✗ You cannot request changes on your own PR",
+        "✗ 自分の PR には変更をリクエストできません",
+    ),
+    ("no pending comments", "保留中のコメントがありません"),
+    ("Thread info not available", "スレッド情報がありません"),
+    (
+        "✗ File not found in PR commits",
+        "✗ PR のコミットにファイルが見つかりません",
+    ),
+    (
+        "Commented line is not in the diff",
+        "コメント対象の行は差分に含まれていません",
+    ),
+    (
+        "No images or videos in PR description",
+        "PR 説明に画像・動画がありません",
+    ),
+    ("✓ Comment posted", "✓ コメントを投稿しました"),
+    ("✓ Reply posted", "✓ 返信を投稿しました"),
+    ("✓ Reloaded", "✓ 再読み込みしました"),
+    ("✓ Auto-merge disabled", "✓ 自動マージを解除しました"),
+    ("✓ Draft saved", "✓ 下書きを保存しました"),
+    ("No pending comments", "保留中のコメントはありません"),
+    ("No next file", "次のファイルはありません"),
+    ("No previous file", "前のファイルはありません"),
+    (
+        "✓ All hunks done — marked viewed",
+        "✓ 全 hunk が済んだので確認済みにしました",
+    ),
+    (
+        "✓ No unviewed files ahead",
+        "✓ この先に未確認のファイルはありません",
+    ),
+    (
+        "✓ No unviewed files behind",
+        "✓ この前に未確認のファイルはありません",
+    ),
+    (
+        "Branch is up to date with base",
+        "ブランチは base に追従しています",
+    ),
+    (
+        "✓ Marked as ready for review",
+        "✓ Ready for review にしました",
+    ),
+    ("✓ Computed diff locally", "✓ 差分をローカルで計算しました"),
+    (
+        "✓ Loaded full file content",
+        "✓ ファイル全体を読み込みました",
+    ),
+    ("✓ No textual changes", "✓ テキストの変更はありません"),
+    ("✓ No links found", "✓ リンクはありません"),
+    ("✓ No messages yet", "✓ メッセージはまだありません"),
+    ("From the PR description:", "PR の説明から:"),
+    (
+        "✓ Checklist complete — a: add it to the review body",
+        "✓ チェックリスト完了 — a: レビュー本文に追記",
+    ),
+    ("Loading checks...", "チェックを読み込み中..."),
+    (
+        "Checks could not be loaded — press r to retry",
+        "チェックを読み込めませんでした — r で再試行",
+    ),
+    ("(refreshing) ", "(更新中) "),
+    ("No matching files", "一致するファイルはありません"),
+    (
+        "✗ No files loaded yet",
+        "✗ ファイルがまだ読み込まれていません",
+    ),
+    (
+        "No checks for the head commit",
+        "head コミットにチェックはありません",
+    ),
+    (
+        "✗ Checks need a GitHub connection",
+        "✗ チェックの表示には GitHub への接続が必要です",
+    ),
+    (
+        "✗ No details URL for this check",
+        "✗ このチェックには詳細ページがありません",
+    ),
+    ("✓ Unpinned file", "✓ ファイルの固定を解除しました"),
+    (
+        "✓ Note saved (local only)",
+        "✓ メモを保存しました（ローカルのみ）",
+    ),
+    ("✓ Note deleted", "✓ メモを削除しました"),
+    ("✓ Thread muted", "✓ スレッドをミュートしました"),
+    ("✓ Thread unmuted", "✓ スレッドのミュートを解除しました"),
+    ("✗ Commit not found", "✗ コミットが見つかりません"),
+    (
+        "✗ Conversation loading. Please wait.",
+        "✗ 会話を読み込み中です。お待ちください。",
+    ),
+    (
+        "✗ Initial loading in progress. Please wait.",
+        "✗ 初期読み込み中です。お待ちください。",
+    ),
+    (
+        "✗ Cannot reload with pending comments. Submit or discard first.",
+        "✗ 未送信のコメントがあるため再読み込みできません。先に送信または破棄してください。",
+    ),
+    (
+        "✗ Diff is already available",
+        "✗ 差分は既に表示されています",
+    ),
+    (
+        "✗ Failed to copy to clipboard",
+        "✗ クリップボードへのコピーに失敗しました",
+    ),
+    ("✗ File is binary", "✗ バイナリファイルです"),
+    ("✗ Invalid repo format", "✗ リポジトリの形式が不正です"),
+    (
+        "✗ No API client available",
+        "✗ API クライアントがありません",
+    ),
+    (
+        "✗ No checklist items configured",
+        "✗ チェックリスト項目が設定されていません",
+    ),
+    ("✗ No commit selected", "✗ コミットが選択されていません"),
+    ("✗ No commits available", "✗ コミットがありません"),
+    ("✗ No notes to export", "✗ 書き出すメモがありません"),
+    (
+        "✗ No reviewers to re-request",
+        "✗ 再依頼するレビュアーがいません",
+    ),
+    ("✗ Not a review thread", "✗ レビュースレッドではありません"),
+    ("✗ Note is empty", "✗ メモが空です"),
+    (
+        "✗ PR node ID not available",
+        "✗ PR のノード ID がありません",
+    ),
+    (
+        "✗ No command configured (use --exec)",
+        "✗ コマンドが設定されていません（--exec を指定）",
+    ),
+    (
+        "✗ Summary is only available for large PRs",
+        "✗ 概要は大きな PR でのみ表示できます",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tr_in() {
+        assert_eq!(tr_in(Locale::Ja, "Submit review"), "レビューを送信");
+        assert_eq!(tr_in(Locale::En, "Submit review"), "Submit review");
+        // 未登録の文字列は原文のまま
+        assert_eq!(tr_in(Locale::Ja, "✓ Copied abc1234"), "✓ Copied abc1234");
+    }
+
+    #[test]
+    fn test_ja_table_has_unique_keys() {
+        let mut keys: Vec<&str> = JA.iter().map(|(en, _)| *en).collect();
+        keys.sort_unstable();
+        let before = keys.len();
+        keys.dedup();
+        assert_eq!(keys.len(), before);
+    }
+
+    #[test]
+    fn test_resolve_locale() {
+        assert_eq!(
+            resolve_locale(Language::Auto, None, Some("ja_JP.UTF-8")),
+            Locale::Ja
+        );
+        assert_eq!(
+            resolve_locale(Language::Auto, None, Some("en_US.UTF-8")),
+            Locale::En
+        );
+        assert_eq!(resolve_locale(Language::Auto, None, None), Locale::En);
+        assert_eq!(
+            resolve_locale(Language::Ja, None, Some("en_US.UTF-8")),
+            Locale::Ja
+        );
+        // GH_PRISM_LANG は設定ファイルより優先
+        assert_eq!(resolve_locale(Language::Ja, Some("en"), None), Locale::En);
+    }
+}
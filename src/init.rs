@@ -0,0 +1,210 @@
+use crate::config::{self, Config, Keymap, ThemePreference, TimeFormat};
+use crate::i18n::Language;
+use color_eyre::Result;
+use std::io::{BufRead, Write};
+use std::process::Stdio;
+
+/// コマンドが正常終了するか（出力は破棄。許可されていなければ実行せず false）
+fn command_succeeds(program: &str, args: &[&str]) -> bool {
+    crate::exec::command(program).is_some_and(|mut cmd| {
+        cmd.args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success())
+    })
+}
+
+/// 許可されていないプログラムの説明（バージョン確認のためでも起動しない）
+const NOT_ALLOWED: &str = "not in allowed_commands (prism asks the first time it is needed)";
+
+/// チェック結果を 1 行で表示する
+fn report(label: &str, ok: bool, detail: &str) {
+    let mark = if ok { "✅" } else { "⚠️ " };
+    eprintln!("  {mark} {label}: {detail}");
+}
+
+/// 選択肢の入力を解釈する（空入力はデフォルト、先頭一致も許可）
+fn parse_choice<'a>(input: &str, choices: &[&'a str], default: &'a str) -> Option<&'a str> {
+    let input = input.trim().to_ascii_lowercase();
+    if input.is_empty() {
+        return Some(default);
+    }
+    choices.iter().copied().find(|c| c.starts_with(&input))
+}
+
+/// 選択肢を提示して有効な入力があるまで繰り返し尋ねる（EOF ではデフォルト）
+fn prompt_choice<'a>(question: &str, choices: &[&'a str], default: &'a str) -> Result<&'a str> {
+    let stdin = std::io::stdin();
+    loop {
+        eprint!("{question} [{}] (default: {default}): ", choices.join("/"));
+        std::io::stderr().flush()?;
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            eprintln!();
+            return Ok(default);
+        }
+        match parse_choice(&line, choices, default) {
+            Some(choice) => return Ok(choice),
+            None => eprintln!("  Please answer one of: {}", choices.join(", ")),
+        }
+    }
+}
+
+/// 環境チェック（gh / 認証 / delta / bat / 画像プロトコル）
+fn check_environment() {
+    eprintln!("Checking environment...");
+    let has_gh = command_succeeds("gh", &["--version"]);
+    report(
+        "gh",
+        has_gh,
+        if has_gh {
+            "installed"
+        } else {
+            "not found (https://cli.github.com)"
+        },
+    );
+    let authed = has_gh && command_succeeds("gh", &["auth", "status"]);
+    report(
+        "gh auth",
+        authed,
+        if authed {
+            "logged in"
+        } else {
+            "not logged in (run `gh auth login`)"
+        },
+    );
+    let has_delta = command_succeeds("delta", &["--version"]);
+    report(
+        "delta",
+        has_delta,
+        if has_delta {
+            "installed (syntax-highlighted diffs)"
+        } else if !crate::exec::is_allowed("delta") {
+            NOT_ALLOWED
+        } else {
+            "not found (diffs use basic coloring)"
+        },
+    );
+    let has_bat = command_succeeds("bat", &["--version"]);
+    report(
+        "bat",
+        has_bat,
+        if has_bat {
+            "installed (highlighted markdown)"
+        } else if !crate::exec::is_allowed("bat") {
+            NOT_ALLOWED
+        } else {
+            "not found (markdown shown as plain text)"
+        },
+    );
+    match ratatui_image::picker::Picker::from_query_stdio() {
+        Ok(picker) => report(
+            "images",
+            true,
+            &format!("{:?} protocol", picker.protocol_type()),
+        ),
+        Err(_) => report(
+            "images",
+            false,
+            "could not query terminal (media viewer limited)",
+        ),
+    }
+    eprintln!();
+}
+
+/// `prism init`: 環境チェックと設定ファイルの対話的生成
+pub fn run_init() -> Result<()> {
+    let current = config::read_config();
+    crate::exec::init(current.allowed_commands.iter().cloned().collect(), false);
+    check_environment();
+
+    let theme_default = match current.theme {
+        ThemePreference::Auto => "auto",
+        ThemePreference::Light => "light",
+        ThemePreference::Dark => "dark",
+    };
+    let theme = match prompt_choice("Theme", &["auto", "light", "dark"], theme_default)? {
+        "light" => ThemePreference::Light,
+        "dark" => ThemePreference::Dark,
+        _ => ThemePreference::Auto,
+    };
+    let keymap_default = match current.keymap {
+        Keymap::Vim => "vim",
+        Keymap::Emacs => "emacs",
+    };
+    let keymap = match prompt_choice(
+        "Keybindings (emacs adds Ctrl+N/Ctrl+P)",
+        &["vim", "emacs"],
+        keymap_default,
+    )? {
+        "emacs" => Keymap::Emacs,
+        _ => Keymap::Vim,
+    };
+
+    let time_format_default = match current.time_format {
+        TimeFormat::Relative => "relative",
+        TimeFormat::Absolute => "absolute",
+    };
+    let time_format = match prompt_choice(
+        "Timestamps (toggle with T)",
+        &["relative", "absolute"],
+        time_format_default,
+    )? {
+        "absolute" => TimeFormat::Absolute,
+        _ => TimeFormat::Relative,
+    };
+
+    let language_default = match current.language {
+        Language::Auto => "auto",
+        Language::En => "en",
+        Language::Ja => "ja",
+    };
+    let language = match prompt_choice("Language", &["auto", "en", "ja"], language_default)? {
+        "en" => Language::En,
+        "ja" => Language::Ja,
+        _ => Language::Auto,
+    };
+
+    let check_update = prompt_choice(
+        "Check for new releases on startup",
+        &["no", "yes"],
+        if current.check_update { "yes" } else { "no" },
+    )? == "yes";
+
+    let path = config::write_config(&Config {
+        theme,
+        keymap,
+        check_update,
+        time_format,
+        language,
+        tmux_status: current.tmux_status,
+        no_mouse: current.no_mouse,
+        allowed_commands: current.allowed_commands,
+        network: current.network,
+        display: current.display,
+        large_pr: current.large_pr,
+        memory: current.memory,
+        diff: current.diff,
+        tests: current.tests,
+        attach: current.attach,
+        review: current.review,
+    })?;
+    eprintln!("\nWrote {}", path.display());
+    eprintln!("Run `gh prism <PR_NUMBER>` to start reviewing.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_choice() {
+        let choices = ["auto", "light", "dark"];
+        assert_eq!(parse_choice("\n", &choices, "auto"), Some("auto"));
+        assert_eq!(parse_choice("Dark\n", &choices, "auto"), Some("dark"));
+        assert_eq!(parse_choice("l", &choices, "auto"), Some("light"));
+        assert_eq!(parse_choice("x", &choices, "auto"), None);
+    }
+}
@@ -0,0 +1,224 @@
+//! メモリ予算付きの LRU（おおよそのバイト数で管理し、超えたら古いものから捨てる）
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// キーごとのおおよそのサイズと最終利用順を管理する（値は持たない）
+#[derive(Debug, Clone)]
+pub struct LruBudget<K> {
+    budget: usize,
+    total: usize,
+    tick: u64,
+    entries: HashMap<K, (usize, u64)>,
+}
+
+impl<K: Hash + Eq + Clone> LruBudget<K> {
+    pub fn new(budget: usize) -> Self {
+        Self {
+            budget,
+            total: 0,
+            tick: 0,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// キーのサイズ（管理していなければ None）
+    pub fn size(&self, key: &K) -> Option<usize> {
+        self.entries.get(key).map(|(size, _)| *size)
+    }
+
+    /// 管理中の合計サイズ
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// 最近使ったものとして記録する
+    pub fn touch(&mut self, key: &K) {
+        self.tick += 1;
+        if let Some(entry) = self.entries.get_mut(key) {
+            entry.1 = self.tick;
+        }
+    }
+
+    /// 追加（既存なら置き換え）して、予算を超えた分の古いキーを返す
+    pub fn insert(&mut self, key: K, size: usize) -> Vec<K> {
+        self.tick += 1;
+        if let Some((old, _)) = self.entries.insert(key, (size, self.tick)) {
+            self.total -= old;
+        }
+        self.total += size;
+        self.evict()
+    }
+
+    pub fn remove(&mut self, key: &K) {
+        if let Some((size, _)) = self.entries.remove(key) {
+            self.total -= size;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.total = 0;
+    }
+
+    /// keep が false のキーを捨てる
+    pub fn retain(&mut self, mut keep: impl FnMut(&K) -> bool) {
+        let total = &mut self.total;
+        self.entries.retain(|key, (size, _)| {
+            let kept = keep(key);
+            if !kept {
+                *total -= *size;
+            }
+            kept
+        });
+    }
+
+    /// 予算を変更して、超えた分の古いキーを返す
+    pub fn set_budget(&mut self, budget: usize) -> Vec<K> {
+        self.budget = budget;
+        self.evict()
+    }
+
+    /// 予算内に収まるまで古いものから捨てる（最後に使った 1 件は予算を超えていても残す）
+    fn evict(&mut self) -> Vec<K> {
+        let mut evicted = Vec::new();
+        while self.total > self.budget && self.entries.len() > 1 {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, tick))| *tick)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.remove(&oldest);
+            evicted.push(oldest);
+        }
+        evicted
+    }
+}
+
+/// 値も保持する LRU キャッシュ
+#[derive(Debug, Clone)]
+pub struct SizedLru<K, V> {
+    values: HashMap<K, V>,
+    usage: LruBudget<K>,
+}
+
+impl<K: Hash + Eq + Clone, V> SizedLru<K, V> {
+    pub fn new(budget: usize) -> Self {
+        Self {
+            values: HashMap::new(),
+            usage: LruBudget::new(budget),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.values.contains_key(key)
+    }
+
+    /// 保持中の値の合計サイズ
+    pub fn total(&self) -> usize {
+        self.usage.total()
+    }
+
+    /// 取得して最近使ったものとして記録する
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.usage.touch(key);
+        self.values.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.usage.touch(key);
+        self.values.get_mut(key)
+    }
+
+    /// 利用順を変えずに参照する（描画中など &self しかないとき）
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.values.get(key)
+    }
+
+    /// 追加して、予算を超えた分の古いキーを返す
+    pub fn insert(&mut self, key: K, value: V, size: usize) -> Vec<K> {
+        self.values.insert(key.clone(), value);
+        let evicted = self.usage.insert(key, size);
+        for key in &evicted {
+            self.values.remove(key);
+        }
+        evicted
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.usage.clear();
+    }
+
+    pub fn set_budget(&mut self, budget: usize) -> Vec<K> {
+        let evicted = self.usage.set_budget(budget);
+        for key in &evicted {
+            self.values.remove(key);
+        }
+        evicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut lru = SizedLru::new(10);
+        assert!(lru.insert("a", 1, 4).is_empty());
+        assert!(lru.insert("b", 2, 4).is_empty());
+        // a を使うと b が一番古くなる
+        assert_eq!(lru.get(&"a"), Some(&1));
+        assert_eq!(lru.insert("c", 3, 4), vec!["b"]);
+        assert!(lru.contains(&"a"));
+        assert!(!lru.contains(&"b"));
+        assert_eq!(lru.usage.total, 8);
+    }
+
+    #[test]
+    fn test_keeps_latest_entry_over_budget() {
+        let mut lru = SizedLru::new(10);
+        lru.insert("a", 1, 4);
+        assert_eq!(lru.insert("big", 2, 20), vec!["a"]);
+        assert_eq!(lru.peek(&"big"), Some(&2));
+        assert_eq!(lru.set_budget(0), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_replace_updates_total() {
+        let mut usage = LruBudget::new(100);
+        usage.insert("a", 30);
+        usage.insert("a", 10);
+        assert_eq!(usage.total, 10);
+        usage.remove(&"a");
+        assert_eq!(usage.total, 0);
+        assert_eq!(usage.len(), 0);
+    }
+
+    #[test]
+    fn test_retain_updates_total() {
+        let mut usage = LruBudget::new(100);
+        usage.insert("a", 30);
+        usage.insert("b", 10);
+        usage.retain(|key| *key == "b");
+        assert_eq!(usage.total, 10);
+        assert_eq!(usage.size(&"a"), None);
+        assert_eq!(usage.size(&"b"), Some(10));
+    }
+}
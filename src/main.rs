@@ -1,21 +1,40 @@
 mod app;
+mod automation;
+mod batch;
+mod cache_admin;
+mod config;
+mod coverage;
+mod doctor;
+mod exec;
+mod format;
 mod git;
 mod github;
+mod i18n;
+mod init;
+mod lru;
+mod mouse;
+mod platform;
+mod title;
+mod update;
 
 use app::{App, CodeCommentReply, ConversationEntry, ConversationKind, ThemeMode};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use color_eyre::Result;
 use futures::stream::{FuturesUnordered, StreamExt};
+use github::cache::{
+    CHECKLIST_SUFFIX, MUTE_SUFFIX, NOTES_SUFFIX, RESOLVE_QUEUE_SUFFIX, SEEN_SUFFIX, VIEWED_SUFFIX,
+    read_pr_json,
+};
 use github::comments::{IssueComment, ReviewComment, ReviewThread};
 use github::commits::CommitInfo;
+use github::error::ApiErrorKind;
 use github::files::DiffFile;
 use github::media::MediaCache;
 use github::review::ReviewSummary;
 use octocrab::Octocrab;
 use octocrab::models::pulls::PullRequest;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-const SHORT_SHA_LEN: usize = 7;
 const THEME_DETECT_TIMEOUT_MS: u64 = 100;
 
 pub struct PrMetadata {
@@ -26,6 +45,46 @@ pub struct PrMetadata {
     pub pr_head_branch: String,
     pub pr_created_at: String,
     pub pr_state: String,
+    /// レビュー依頼中のユーザー
+    pub requested_reviewers: Vec<String>,
+    /// レビュー依頼中のチーム（slug）
+    pub requested_teams: Vec<String>,
+    /// 担当者（assignee）
+    pub assignees: Vec<String>,
+    /// GraphQL 用の PR ノード ID
+    pub pr_node_id: String,
+    /// base より遅れていて "Update branch" 可能か（mergeable_state == behind）
+    pub behind_base: bool,
+    /// fork からの PR なら head リポジトリの owner
+    pub fork_owner: Option<String>,
+    /// fork の PR でメンテナーによる push が許可されているか
+    pub maintainer_can_modify: bool,
+    /// 変更ファイル数・コミット数・追加削除行数（大きな PR の判定用）
+    pub changed_files: u64,
+    pub commit_count: u64,
+    pub changed_lines: u64,
+}
+
+impl PrMetadata {
+    /// 設定のしきい値を超える大きな PR か
+    pub fn is_large(&self, large_pr: &config::LargePrConfig) -> bool {
+        large_pr.exceeded_by(self.changed_files, self.commit_count, self.changed_lines)
+    }
+}
+
+/// fork からの PR なら head リポジトリの owner を返す（fork が削除済みなら label から判定）
+fn fork_owner(pr: &PullRequest) -> Option<String> {
+    let label_owner = |label: Option<&String>| {
+        label
+            .and_then(|l| l.split_once(':'))
+            .map(|(owner, _)| owner.to_string())
+    };
+    let head_owner = label_owner(pr.head.label.as_ref())?;
+    let same_repo = match (&pr.head.repo, &pr.base.repo) {
+        (Some(head), Some(base)) => head.id == base.id,
+        _ => label_owner(pr.base.label.as_ref()).as_ref() == Some(&head_owner),
+    };
+    (!same_repo).then_some(head_owner)
 }
 
 pub fn extract_pr_metadata(pr: &PullRequest) -> PrMetadata {
@@ -39,14 +98,8 @@ pub fn extract_pr_metadata(pr: &PullRequest) -> PrMetadata {
             .unwrap_or_default(),
         pr_base_branch: pr.base.ref_field.clone(),
         pr_head_branch: pr.head.ref_field.clone(),
-        pr_created_at: pr
-            .created_at
-            .map(|dt| {
-                dt.with_timezone(&chrono::Local)
-                    .format("%Y-%m-%d %H:%M %z")
-                    .to_string()
-            })
-            .unwrap_or_default(),
+        // 表示形式は App 側で切り替えるため RFC 3339 のまま保持
+        pr_created_at: pr.created_at.map(|dt| dt.to_rfc3339()).unwrap_or_default(),
         pr_state: if pr.merged_at.is_some() {
             "Merged".to_string()
         } else {
@@ -55,6 +108,31 @@ pub fn extract_pr_metadata(pr: &PullRequest) -> PrMetadata {
                 _ => "Closed".to_string(),
             }
         },
+        requested_reviewers: pr
+            .requested_reviewers
+            .iter()
+            .flatten()
+            .map(|u| u.login.clone())
+            .collect(),
+        requested_teams: pr
+            .requested_teams
+            .iter()
+            .flatten()
+            .map(|t| t.slug.clone())
+            .collect(),
+        assignees: pr
+            .assignees
+            .iter()
+            .flatten()
+            .map(|u| u.login.clone())
+            .collect(),
+        pr_node_id: pr.node_id.clone().unwrap_or_default(),
+        behind_base: pr.mergeable_state == Some(octocrab::models::pulls::MergeableState::Behind),
+        fork_owner: fork_owner(pr),
+        maintainer_can_modify: pr.maintainer_can_modify,
+        changed_files: pr.changed_files.unwrap_or_default(),
+        commit_count: pr.commits.unwrap_or_default(),
+        changed_lines: pr.additions.unwrap_or_default() + pr.deletions.unwrap_or_default(),
     }
 }
 
@@ -68,6 +146,8 @@ pub enum AsyncErrorKind {
 /// バックグラウンド非同期タスクから App に送信するデータ
 pub enum AsyncData {
     FilesMap(HashMap<String, Vec<DiffFile>>),
+    /// 大きな PR の概要（PR 全体の変更ファイルの統計、パッチなし）
+    FileStats(Vec<DiffFile>),
     ConversationData {
         review_comments: Vec<ReviewComment>,
         issue_comments: Vec<IssueComment>,
@@ -75,7 +155,35 @@ pub enum AsyncData {
         review_threads: Vec<ReviewThread>,
     },
     MediaData(MediaCache),
-    Error(AsyncErrorKind, String),
+    /// レビュー依頼の日時（取得に失敗しても他の表示には影響しないため Error は送らない）
+    ReviewRequests(github::review::ReviewRequestTimes),
+    /// head コミットの CI チェック（取得中に head が変わったら捨てるため SHA を添える）
+    Checks {
+        sha: String,
+        result: Result<Vec<github::checks::Check>, String>,
+    },
+    /// レビュー依頼中のチームのうち自分が所属するもの（確認した時点の依頼中チームを添える）
+    TeamMembership {
+        requested: Vec<String>,
+        mine: Vec<String>,
+    },
+    /// 現在の自動マージ設定（未設定なら None）
+    AutoMerge(Result<Option<github::pr::MergeMethod>, String>),
+    /// 起動時のアップデート確認で見つかった新しいリリースのタグ（なければ None）
+    UpdateAvailable(Option<String>),
+    /// ブランチ更新後に新しい head コミットが現れたか（更新前の head を添える）
+    BranchUpdated {
+        old_head: String,
+        landed: bool,
+    },
+    /// GitHub で "Viewed" にチェックされたファイル
+    ViewedFiles(Result<std::collections::HashSet<String>, String>),
+    /// "Viewed" の同期結果（送れた変更と、送れなかったファイル名とエラー）
+    ViewedSynced {
+        synced: Vec<(String, bool)>,
+        failed: Vec<(String, String)>,
+    },
+    Error(AsyncErrorKind, ApiErrorKind, String),
 }
 
 const VERSION: &str = match option_env!("GH_PRISM_VERSION") {
@@ -86,9 +194,18 @@ const VERSION: &str = match option_env!("GH_PRISM_VERSION") {
 #[derive(Parser)]
 #[command(name = "prism", version = VERSION)]
 #[command(about = "A TUI tool for reviewing GitHub Pull Requests")]
+#[command(subcommand_negates_reqs = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+
     /// Pull Request number
-    pr_number: u64,
+    #[arg(required_unless_present = "update")]
+    pr_number: Option<u64>,
+
+    /// Update prism to the latest release via `gh extension upgrade`
+    #[arg(long)]
+    update: bool,
 
     /// Repository in owner/repo format (default: detect from git remote)
     #[arg(short, long)]
@@ -98,6 +215,14 @@ struct Cli {
     #[arg(long)]
     no_cache: bool,
 
+    /// Load every commit's files up front even for PRs above the large_pr thresholds
+    #[arg(long)]
+    full: bool,
+
+    /// Disable mouse capture (keeps the terminal's own text selection)
+    #[arg(long)]
+    no_mouse: bool,
+
     /// Force light theme
     #[arg(long, conflicts_with = "dark")]
     light: bool,
@@ -105,6 +230,114 @@ struct Cli {
     /// Force dark theme
     #[arg(long, conflicts_with = "light")]
     dark: bool,
+
+    /// LCOV coverage report (file path or URL) to overlay on added lines
+    #[arg(long, value_name = "PATH_OR_URL")]
+    coverage: Option<String>,
+
+    /// Shell command to run with `!` (e.g. "cargo test")
+    #[arg(long, value_name = "CMD")]
+    exec: Option<String>,
+
+    /// Review checklist items, comma-separated (default: Security,Tests,Docs,Migrations)
+    #[arg(long, value_name = "ITEMS", value_delimiter = ',')]
+    checklist: Vec<String>,
+
+    /// Diff algorithm for locally computed diffs (overrides config)
+    #[arg(long, value_enum, value_name = "ALGORITHM")]
+    diff_algorithm: Option<config::DiffAlgorithm>,
+
+    /// Context lines for locally computed diffs (overrides config)
+    #[arg(long, value_name = "N")]
+    context: Option<u32>,
+
+    /// Display width of tabs in diffs, 0 keeps tabs (overrides config)
+    #[arg(long, value_name = "N")]
+    tab_width: Option<usize>,
+
+    /// Extra argument passed to delta, repeatable (replaces config delta_args)
+    #[arg(long = "delta-arg", value_name = "ARG", allow_hyphen_values = true)]
+    delta_args: Vec<String>,
+
+    /// Format of the review report written with `X`
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = github::export::ReportFormat::Markdown)]
+    export_format: github::export::ReportFormat,
+}
+
+impl Cli {
+    /// 設定の diff 表示設定に CLI フラグを上書きする
+    fn diff_config(&self, mut diff: config::DiffConfig) -> config::DiffConfig {
+        if let Some(algorithm) = self.diff_algorithm {
+            diff.algorithm = algorithm;
+        }
+        if let Some(context) = self.context {
+            diff.context_lines = context;
+        }
+        if let Some(tab_width) = self.tab_width {
+            diff.tab_width = tab_width;
+        }
+        if !self.delta_args.is_empty() {
+            diff.delta_args = self.delta_args.clone();
+        }
+        diff
+    }
+}
+
+#[derive(Subcommand)]
+enum CliCommand {
+    /// Check the environment and write the initial config file interactively
+    Init,
+    /// Diagnose setup problems (gh auth scopes, API access, images, delta, cache, terminal)
+    Doctor,
+    /// Review a series of small PRs in a row, approving or skipping each one
+    Batch {
+        /// Pull Request numbers (default: open PRs by --author)
+        pr_numbers: Vec<u64>,
+
+        /// Author of the PRs to review when no numbers are given
+        #[arg(long, default_value = "app/dependabot")]
+        author: String,
+    },
+    /// Print the active key bindings (same table as the in-app help) for wikis and cheatsheets
+    Keys {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = app::KeymapFormat::Markdown)]
+        format: app::KeymapFormat,
+    },
+    /// Inspect and clean up the local cache (cleared entries are archived first)
+    Cache {
+        #[command(subcommand)]
+        action: cache_admin::CacheAction,
+    },
+    /// Submit a review without the TUI and print the result as JSON
+    ///
+    /// Exit codes: 0 submitted, 2 invalid input, 3 not allowed (e.g. PR closed or
+    /// approving your own PR), 4 GitHub API error.
+    #[command(group(clap::ArgGroup::new("event").required(true)))]
+    Review {
+        /// Pull Request number
+        pr_number: u64,
+
+        /// Approve the PR
+        #[arg(long, group = "event")]
+        approve: bool,
+
+        /// Request changes (requires a body)
+        #[arg(long, group = "event")]
+        request_changes: bool,
+
+        /// Comment without approving (requires a body)
+        #[arg(long, group = "event")]
+        comment: bool,
+
+        /// Review body
+        #[arg(long, conflicts_with = "comment_file")]
+        body: Option<String>,
+
+        /// Read the review body from a file
+        #[arg(long, value_name = "FILE")]
+        comment_file: Option<std::path::PathBuf>,
+    },
 }
 
 /// termbg でターミナル背景色を検出し、ライト/ダークモードを判定する。
@@ -129,7 +362,7 @@ fn resolve_repo(repo_arg: &Option<String>) -> Result<(String, String)> {
     }
 
     // 2. gh repo view で自動検出
-    let output = std::process::Command::new("gh")
+    let output = exec::require("gh")?
         .args([
             "repo",
             "view",
@@ -157,10 +390,8 @@ fn resolve_repo(repo_arg: &Option<String>) -> Result<(String, String)> {
 
 /// 現在の認証ユーザーのログイン名を取得
 pub fn fetch_current_user() -> String {
-    std::process::Command::new("gh")
-        .args(["api", "user", "-q", ".login"])
-        .output()
-        .ok()
+    exec::command("gh")
+        .and_then(|mut cmd| cmd.args(["api", "user", "-q", ".login"]).output().ok())
         .and_then(|o| String::from_utf8(o.stdout).ok())
         .map(|s| s.trim().to_string())
         .unwrap_or_default()
@@ -285,6 +516,7 @@ pub fn build_conversation(
             sorted_replies.sort_by(|a, b| a.created_at.cmp(&b.created_at));
             for r in sorted_replies {
                 replies.push(CodeCommentReply {
+                    id: r.id,
                     author: r.user.login.clone(),
                     body: r.body.clone(),
                     created_at: r.created_at.clone(),
@@ -324,11 +556,13 @@ pub struct ReloadedData {
 }
 
 /// PR データを API から一括再取得する（キャッシュをスキップして最新データを取得）
+/// `lazy_commits` が Some なら（大きな PR の遅延読み込み中）読み込み済みのコミットのファイルだけを取得し、キャッシュも書かない
 pub async fn reload_pr_data(
     client: &Octocrab,
     owner: &str,
     repo: &str,
     pr_number: u64,
+    lazy_commits: Option<&std::collections::HashSet<String>>,
 ) -> Result<ReloadedData> {
     // コミット一覧と PR 情報を並列取得
     let (commits, pr) = tokio::try_join!(
@@ -348,7 +582,15 @@ pub async fn reload_pr_data(
     };
 
     // ファイル取得とレビューコメント・Issue コメント・Reviews を並列実行
-    let data_future = fetch_all(client, owner, repo, &commits, true);
+    let file_commits: Vec<CommitInfo> = match lazy_commits {
+        Some(loaded) => commits
+            .iter()
+            .filter(|c| loaded.contains(&c.sha))
+            .cloned()
+            .collect(),
+        None => commits.clone(),
+    };
+    let data_future = fetch_all(client, owner, repo, &file_commits, true);
     let comments_future = github::comments::fetch_review_comments(client, owner, repo, pr_number);
     let issue_comments_future =
         github::comments::fetch_issue_comments(client, owner, repo, pr_number);
@@ -363,18 +605,20 @@ pub async fn reload_pr_data(
 
     let review_threads = threads_handle.await.unwrap_or_default();
 
-    // 新しいキャッシュを書き込み
-    github::cache::write_cache(
-        owner,
-        repo,
-        pr_number,
-        &github::cache::PrCache {
-            version: github::cache::CACHE_VERSION,
-            head_sha: head_sha.to_string(),
-            files_map: files_map.clone(),
-            review_threads: review_threads.clone(),
-        },
-    );
+    // 新しいキャッシュを書き込み（一部のコミットしか読み込んでいなければ書かない）
+    if lazy_commits.is_none() {
+        github::cache::write_cache(
+            owner,
+            repo,
+            pr_number,
+            &github::cache::PrCache {
+                version: github::cache::CACHE_VERSION,
+                head_sha: head_sha.to_string(),
+                files_map: files_map.clone(),
+                review_threads: review_threads.clone(),
+            },
+        );
+    }
 
     Ok(ReloadedData {
         metadata,
@@ -391,27 +635,176 @@ pub async fn reload_pr_data(
 async fn main() {
     let _ = color_eyre::install();
     if let Err(e) = run().await {
-        // エラーチェーンから根本原因メッセージを抽出してユーザーフレンドリーに表示
-        let root = e.root_cause().to_string();
-        let message = if root.contains("Not Found") {
-            "PR or repository not found. Check the PR number and repository name.".to_string()
-        } else if root.contains("rate limit") {
-            "GitHub API rate limit exceeded. Please try again later.".to_string()
-        } else if root.contains("401") || root.contains("Bad credentials") {
-            "Authentication failed. Run `gh auth login` to authenticate.".to_string()
-        } else {
-            format!("{e:#}")
+        // エラーの種類に応じてユーザーフレンドリーに表示
+        let message = match github::error::classify(&e) {
+            ApiErrorKind::NotFound => {
+                "PR or repository not found. Check the PR number and repository name.".to_string()
+            }
+            ApiErrorKind::RateLimit => {
+                "GitHub API rate limit exceeded. Please try again later.".to_string()
+            }
+            ApiErrorKind::Auth => {
+                "Authentication failed. Run `gh auth login` (or `gh auth refresh -s repo,read:org`) to authenticate.".to_string()
+            }
+            kind @ (ApiErrorKind::Network | ApiErrorKind::Parse) => {
+                format!("{e:#}\nHint: {}", kind.hint())
+            }
+            ApiErrorKind::Other => format!("{e:#}"),
         };
         eprintln!("Error: {message}");
         std::process::exit(1);
     }
 }
 
+/// `prism batch`: 複数の PR を取得してバッチレビューを実行し、結果を出力する
+async fn run_batch(repo_arg: &Option<String>, pr_numbers: Vec<u64>, author: &str) -> Result<()> {
+    let config = config::read_config();
+    i18n::init(i18n::detect_locale(config.language));
+    exec::init(config.allowed_commands.iter().cloned().collect(), false);
+    github::retry::init(config.network);
+    format::init(config.display);
+    let (owner, repo) = resolve_repo(repo_arg)?;
+    let client = github::client::create_client()?;
+
+    let pr_numbers = if pr_numbers.is_empty() {
+        github::pr::list_open_prs_by_author(&owner, &repo, author)?
+    } else {
+        pr_numbers
+    };
+    if pr_numbers.is_empty() {
+        eprintln!("No open PRs by {author}");
+        return Ok(());
+    }
+
+    let mut prs = Vec::new();
+    for number in pr_numbers {
+        eprintln!("Fetching PR #{}...", number);
+        let (pr, files) = tokio::try_join!(
+            github::pr::fetch_pr(&client, &owner, &repo, number),
+            github::files::fetch_pr_files(&client, &owner, &repo, number),
+        )?;
+        if github::files::pr_files_truncated(files.len()) {
+            eprintln!(
+                "Warning: PR #{number}: GitHub returned only the first {} files; the rest are not shown",
+                files.len()
+            );
+        }
+        prs.push(batch::BatchPr {
+            number,
+            title: pr.title.unwrap_or_default(),
+            head_sha: pr.head.sha,
+            files,
+        });
+    }
+
+    let terminal = ratatui::init();
+    let result = batch::run_batch(terminal, &client, &owner, &repo, &prs);
+    ratatui::restore();
+
+    for line in batch::summary_lines(&prs, &result?) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// `prism review`: TUI なしでレビューを送信し、結果を JSON と終了コードで返す
+async fn run_review(
+    repo_arg: &Option<String>,
+    pr_number: u64,
+    event: &'static str,
+    body: Option<String>,
+    comment_file: Option<&std::path::Path>,
+) -> Result<()> {
+    let config = config::read_config();
+    github::retry::init(config.network);
+    format::init(config.display);
+    let (owner, repo) = resolve_repo(repo_arg)?;
+    let client = github::client::create_client()?;
+    let current_user = fetch_current_user();
+    let request = automation::ReviewRequest {
+        owner: &owner,
+        repo: &repo,
+        pr_number,
+        event,
+        body,
+        body_file: comment_file,
+    };
+    let outcome = automation::submit(&client, &request, &current_user).await;
+    println!("{}", serde_json::to_string(&outcome)?);
+    std::process::exit(outcome.exit_code);
+}
+
 async fn run() -> Result<()> {
     use app::LoadPhase;
     use tokio::sync::mpsc;
 
     let cli = Cli::parse();
+    match cli.command {
+        Some(CliCommand::Init) => return init::run_init(),
+        Some(CliCommand::Doctor) => return doctor::run_doctor(),
+        Some(CliCommand::Keys { format }) => {
+            let config = config::read_config();
+            i18n::init(i18n::detect_locale(config.language));
+            print!("{}", app::export_keymap(config.keymap, format));
+            return Ok(());
+        }
+        Some(CliCommand::Batch { pr_numbers, author }) => {
+            return run_batch(&cli.repo, pr_numbers, &author).await;
+        }
+        Some(CliCommand::Cache { action }) => {
+            return match action {
+                cache_admin::CacheAction::List => cache_admin::list(),
+                cache_admin::CacheAction::Clear { pr_numbers } => {
+                    let (owner, repo) = resolve_repo(&cli.repo)?;
+                    cache_admin::clear(&owner, &repo, &pr_numbers)
+                }
+                cache_admin::CacheAction::Prune { older_than, merged } => {
+                    let client = if merged {
+                        github::retry::init(config::read_config().network);
+                        Some(github::client::create_client()?)
+                    } else {
+                        None
+                    };
+                    cache_admin::prune(client.as_ref(), older_than).await
+                }
+            };
+        }
+        Some(CliCommand::Review {
+            pr_number,
+            approve,
+            request_changes,
+            comment: _,
+            body,
+            comment_file,
+        }) => {
+            let event = if approve {
+                "APPROVE"
+            } else if request_changes {
+                "REQUEST_CHANGES"
+            } else {
+                "COMMENT"
+            };
+            return run_review(&cli.repo, pr_number, event, body, comment_file.as_deref()).await;
+        }
+        None => {}
+    }
+    if cli.update {
+        return update::run_update(VERSION);
+    }
+    // サブコマンドなしの場合 clap が PR 番号を必須として検証済み
+    let pr_number = cli.pr_number.expect("PR number is required");
+    let config = config::read_config();
+    i18n::init(i18n::detect_locale(config.language));
+    // 外部プログラムの実行許可（未許可のものは TUI で初めて使うときに確認する）
+    let mut allowed: HashSet<String> = config.allowed_commands.iter().cloned().collect();
+    // tmux_status を有効にしていれば tmux も許可済みとする（タイトル設定は確認ダイアログを出せる前に行うため）
+    if config.tmux_status {
+        allowed.insert("tmux".to_string());
+    }
+    exec::init(allowed, true);
+    github::retry::init(config.network);
+    format::init(config.display.clone());
+    git::diff::init(cli.diff_config(config.diff.clone()));
 
     // リポジトリ情報を解決
     let (owner, repo) = resolve_repo(&cli.repo)?;
@@ -420,32 +813,31 @@ async fn run() -> Result<()> {
 
     // GitHub APIクライアントを作成
     let client = github::client::create_client()?;
-    eprintln!("Fetching PR #{}...", cli.pr_number);
+    eprintln!("Fetching PR #{}...", pr_number);
 
     // ── Phase A: ブロッキング ──
     // コミット一覧とPR情報を常にAPI取得
     // （HEAD SHA判定 + キャッシュヒット時もPR状態の最新性を保証するため）
+    let phase_a_start = std::time::Instant::now();
     let (commits, pr) = tokio::try_join!(
-        github::commits::fetch_commits(&client, &owner, &repo, cli.pr_number),
-        github::pr::fetch_pr(&client, &owner, &repo, cli.pr_number),
+        github::commits::fetch_commits(&client, &owner, &repo, pr_number),
+        github::pr::fetch_pr(&client, &owner, &repo, pr_number),
     )?;
+    github::metrics::record_phase("commits + PR", phase_a_start.elapsed());
     let metadata = extract_pr_metadata(&pr);
     let head_sha = commits.last().map(|c| c.sha.clone()).unwrap_or_default();
 
     // キャッシュ判定
     let (files_map, cached_review_threads, cache_hit) = if !cli.no_cache {
-        if let Some(cached) = github::cache::read_cache(&owner, &repo, cli.pr_number) {
+        if let Some(cached) = github::cache::read_cache(&owner, &repo, pr_number) {
             if cached.head_sha == head_sha {
-                eprintln!(
-                    "Using cached data (HEAD: {})",
-                    &head_sha[..SHORT_SHA_LEN.min(head_sha.len())]
-                );
+                eprintln!("Using cached data (HEAD: {})", format::short_sha(&head_sha));
                 (cached.files_map, cached.review_threads, true)
             } else {
                 eprintln!(
                     "Cache stale (expected {}, got {})",
-                    &cached.head_sha[..SHORT_SHA_LEN.min(cached.head_sha.len())],
-                    &head_sha[..SHORT_SHA_LEN.min(head_sha.len())]
+                    format::short_sha(&cached.head_sha),
+                    format::short_sha(&head_sha)
                 );
                 (HashMap::new(), Vec::new(), false)
             }
@@ -458,13 +850,45 @@ async fn run() -> Result<()> {
         (HashMap::new(), Vec::new(), false)
     };
 
+    github::metrics::record_cache(cache_hit);
+    let large_pr = !cache_hit && !cli.full && metadata.is_large(&config.large_pr);
+    if large_pr {
+        eprintln!(
+            "Large PR ({} files, {} commits, {} lines): loading a summary first (use --full to load everything)",
+            metadata.changed_files, metadata.commit_count, metadata.changed_lines
+        );
+    }
+
     // テーマ検出（ratatui::init() の前に実行 — raw mode では OSC クエリが動かない）
+    // CLI フラグ > 設定ファイル > 自動検出 の順で優先
     let theme = if cli.light {
         ThemeMode::Light
     } else if cli.dark {
         ThemeMode::Dark
     } else {
-        detect_theme()
+        match config.theme {
+            config::ThemePreference::Light => ThemeMode::Light,
+            config::ThemePreference::Dark => ThemeMode::Dark,
+            config::ThemePreference::Auto => detect_theme(),
+        }
+    };
+
+    // カバレッジレポート読み込み（失敗しても起動は継続）
+    let coverage_report = if let Some(source) = &cli.coverage {
+        match coverage::load_coverage(source).await {
+            Ok(report) => {
+                if report.is_empty() {
+                    eprintln!("Warning: coverage report has no line data: {}", source);
+                }
+                Some(report)
+            }
+            Err(e) => {
+                eprintln!("Warning: failed to load coverage report: {e}");
+                None
+            }
+        }
+    } else {
+        None
     };
 
     // 画像プロトコル検出（ratatui::init() の前に実行 — raw mode では OSC クエリが動かない）
@@ -493,8 +917,8 @@ async fn run() -> Result<()> {
         let client = client.clone();
         let owner = owner.clone();
         let repo = repo.clone();
-        let pr_number = cli.pr_number;
         tokio::spawn(async move {
+            let start = std::time::Instant::now();
             let threads_handle = {
                 let owner = owner.clone();
                 let repo = repo.clone();
@@ -513,6 +937,7 @@ async fn run() -> Result<()> {
             match result {
                 Ok((review_comments, issue_comments, reviews)) => {
                     let review_threads = threads_handle.await.unwrap_or_default();
+                    github::metrics::record_phase("conversation", start.elapsed());
                     let _ = tx.send(AsyncData::ConversationData {
                         review_comments,
                         issue_comments,
@@ -523,6 +948,7 @@ async fn run() -> Result<()> {
                 Err(e) => {
                     let _ = tx.send(AsyncData::Error(
                         AsyncErrorKind::Conversation,
+                        github::error::classify(&e),
                         format!("Failed to load conversation: {e}"),
                     ));
                 }
@@ -530,21 +956,57 @@ async fn run() -> Result<()> {
         });
     }
 
+    // B2': 大きな PR は全コミットのファイルを先読みせず、概要（ファイルごとの統計）だけを取得する
+    if large_pr {
+        let tx = tx.clone();
+        let client = client.clone();
+        let owner = owner.clone();
+        let repo = repo.clone();
+        let changed_files = metadata.changed_files;
+        tokio::spawn(async move {
+            let start = std::time::Instant::now();
+            match github::files::fetch_pr_file_stats(
+                &client,
+                &owner,
+                &repo,
+                pr_number,
+                changed_files,
+            )
+            .await
+            {
+                Ok(stats) => {
+                    github::metrics::record_phase("file stats", start.elapsed());
+                    let _ = tx.send(AsyncData::FileStats(stats));
+                }
+                Err(e) => {
+                    let _ = tx.send(AsyncData::Error(
+                        AsyncErrorKind::Files,
+                        github::error::classify(&e),
+                        format!("Failed to load file stats: {e}"),
+                    ));
+                }
+            }
+        });
+    }
+
     // B2: ファイル差分（キャッシュミス時のみ）
-    if !cache_hit {
+    if !cache_hit && !large_pr {
         let tx = tx.clone();
         let client = client.clone();
         let owner = owner.clone();
         let repo = repo.clone();
         let commits = commits.clone();
         tokio::spawn(async move {
+            let start = std::time::Instant::now();
             match fetch_all(&client, &owner, &repo, &commits, true).await {
                 Ok(files_map) => {
+                    github::metrics::record_phase("files", start.elapsed());
                     let _ = tx.send(AsyncData::FilesMap(files_map));
                 }
                 Err(e) => {
                     let _ = tx.send(AsyncData::Error(
                         AsyncErrorKind::Files,
+                        github::error::classify(&e),
                         format!("Failed to load files: {e}"),
                     ));
                 }
@@ -557,25 +1019,56 @@ async fn run() -> Result<()> {
         let tx = tx.clone();
         let pr_body = metadata.pr_body.clone();
         tokio::spawn(async move {
+            let start = std::time::Instant::now();
             let image_urls = app::collect_image_urls(&pr_body);
             let media_cache = if image_urls.is_empty() {
                 github::media::MediaCache::new()
             } else {
                 github::media::download_media(image_urls).await
             };
+            github::metrics::record_phase("media", start.elapsed());
             let _ = tx.send(AsyncData::MediaData(media_cache));
         });
     }
 
+    // B4: レビュー依頼の日時（Timeline API、待ち時間の表示用）
+    {
+        let tx = tx.clone();
+        let client = client.clone();
+        let owner = owner.clone();
+        let repo = repo.clone();
+        tokio::spawn(async move {
+            if let Ok(times) =
+                github::review::fetch_review_requests(&client, &owner, &repo, pr_number).await
+            {
+                let _ = tx.send(AsyncData::ReviewRequests(times));
+            }
+        });
+    }
+
     // sender を全 spawn に clone 済みなので元の tx を drop
     drop(tx);
 
     // ── TUI 起動 ──
     let terminal = ratatui::init();
-    crossterm::execute!(std::io::stdout(), crossterm::event::EnableMouseCapture)?;
+    let mouse_wanted = !cli.no_mouse && !config.no_mouse;
+    let mouse_captured = mouse::enable(mouse_wanted);
+    title::set(
+        &format!("{}/{}", owner, repo),
+        pr_number,
+        &metadata.pr_title,
+        config.tmux_status,
+    );
 
+    let requested_reviewers = metadata.requested_reviewers;
+    let requested_teams = metadata.requested_teams;
+    let assignees = metadata.assignees;
+    let pr_node_id = metadata.pr_node_id;
+    let behind_base = metadata.behind_base;
+    let fork_owner = metadata.fork_owner;
+    let maintainer_can_modify = metadata.maintainer_can_modify;
     let mut app = App::new(
-        cli.pr_number,
+        pr_number,
         format!("{}/{}", owner, repo),
         metadata.pr_title,
         metadata.pr_body,
@@ -599,10 +1092,58 @@ async fn run() -> Result<()> {
         cache_hit, // キャッシュヒット = 既に書き込み済み → 再書き込みスキップ
     );
     app.set_media(picker, MediaCache::new());
+    app.set_coverage(coverage_report);
+    app.set_run_command(cli.exec);
+    app.set_keymap(config.keymap);
+    app.set_report_format(cli.export_format);
+    app.set_time_format(config.time_format);
+    app.set_review_requests(requested_reviewers, requested_teams);
+    app.set_assignees(assignees);
+    app.set_review_sla(config.review.clone());
+    app.set_review_templates(config.review.templates.clone());
+    app.set_behind_base(behind_base);
+    app.set_fork(fork_owner, maintainer_can_modify);
+    app.set_lazy_files(large_pr);
+    app.set_memory_limits(config.memory);
+    app.set_mouse_capture(mouse_wanted, mouse_captured);
+    app.set_test_files(config.tests.clone());
+    app.set_attach_upload_command(config.attach.upload_command.clone());
+    // 起動時のアップデート確認（設定で有効な場合のみ、初回描画後にバックグラウンドで確認）
+    if config.check_update {
+        app.schedule_update_check();
+    }
+    let checklist_labels: Vec<String> = if cli.checklist.is_empty() {
+        app::DEFAULT_CHECKLIST
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    } else {
+        cli.checklist
+            .iter()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    };
+    app.set_checklist(
+        checklist_labels,
+        read_pr_json(&owner, &repo, pr_number, CHECKLIST_SUFFIX),
+    );
+    app.set_notes(read_pr_json(&owner, &repo, pr_number, NOTES_SUFFIX));
+    app.set_seen_entries(read_pr_json(&owner, &repo, pr_number, SEEN_SUFFIX));
+    app.set_mutes(
+        pr_node_id,
+        read_pr_json(&owner, &repo, pr_number, MUTE_SUFFIX),
+    );
+    app.set_resolve_queue(read_pr_json(&owner, &repo, pr_number, RESOLVE_QUEUE_SUFFIX));
+    app.set_viewed_files(read_pr_json(&owner, &repo, pr_number, VIEWED_SUFFIX));
+    app.schedule_viewed_fetch();
+    app.schedule_auto_merge_check();
+    app.schedule_checks_fetch();
     let result = app.run(terminal);
 
-    crossterm::execute!(std::io::stdout(), crossterm::event::DisableMouseCapture)?;
+    mouse::disable(app.mouse_captured());
     ratatui::restore();
+    title::restore(config.tmux_status);
     result
 }
 
@@ -633,6 +1174,7 @@ mod tests {
             },
             created_at: created_at.to_string(),
             in_reply_to_id,
+            diff_hunk: None,
         }
     }
 
@@ -772,4 +1314,32 @@ mod tests {
             _ => panic!("Expected CodeComment"),
         }
     }
+
+    #[test]
+    fn test_cli_diff_flags_override_config() {
+        let cli = Cli::try_parse_from([
+            "prism",
+            "1",
+            "--diff-algorithm",
+            "histogram",
+            "--tab-width",
+            "2",
+            "--delta-arg=--syntax-theme=Nord",
+            "--delta-arg",
+            "--max-line-length=0",
+        ])
+        .unwrap();
+        let diff = cli.diff_config(config::DiffConfig {
+            context_lines: 5,
+            delta_args: vec!["--light".to_string()],
+            ..config::DiffConfig::default()
+        });
+        assert_eq!(diff.algorithm, config::DiffAlgorithm::Histogram);
+        assert_eq!(diff.context_lines, 5);
+        assert_eq!(diff.tab_width, 2);
+        assert_eq!(
+            diff.delta_args,
+            vec!["--syntax-theme=Nord", "--max-line-length=0"]
+        );
+    }
 }
@@ -0,0 +1,15 @@
+//! マウスキャプチャの有効化・解除（設定で無効にでき、端末が対応していなければ無効のまま続行する）
+
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+
+/// マウスキャプチャを有効にする（無効設定なら何もしない）。有効にできたかを返す
+pub fn enable(wanted: bool) -> bool {
+    wanted && crossterm::execute!(std::io::stdout(), EnableMouseCapture).is_ok()
+}
+
+/// enable で有効にできた場合だけ解除する（失敗は無視）
+pub fn disable(active: bool) {
+    if active {
+        let _ = crossterm::execute!(std::io::stdout(), DisableMouseCapture);
+    }
+}
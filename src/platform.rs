@@ -0,0 +1,206 @@
+//! クリップボードとブラウザの OS 差分（macOS / Windows / Wayland / X11、SSH 越しは OSC 52）
+//!
+//! 外部プログラムは exec::command 経由で起動するため、許可されていないものは候補から外れる。
+//! どのプログラムも使えなければ端末のエスケープシーケンス（OSC 52）でコピーする。
+
+use std::io::Write;
+use std::process::Stdio;
+
+/// クリップボードへの書き込みに使ったもの
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyMethod {
+    Program(&'static str),
+    /// 端末経由（OSC 52）
+    Osc52,
+}
+
+/// 環境変数が空でなく設定されているか
+fn env_set(name: &str) -> bool {
+    std::env::var_os(name).is_some_and(|value| !value.is_empty())
+}
+
+/// SSH 越しのセッションか（手元のクリップボードには OSC 52 でしか届かない）
+fn is_remote() -> bool {
+    env_set("SSH_TTY") || env_set("SSH_CONNECTION")
+}
+
+/// クリップボードへの書き込みに使うプログラムと引数（優先順）
+pub fn clipboard_programs() -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        vec![("pbcopy", &[])]
+    } else if cfg!(windows) {
+        vec![("clip", &[])]
+    } else {
+        let mut programs: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+        if env_set("WAYLAND_DISPLAY") {
+            programs.push(("wl-copy", &[]));
+        }
+        if env_set("WSL_DISTRO_NAME") {
+            programs.push(("clip.exe", &[]));
+        }
+        programs.push(("xclip", &["-selection", "clipboard"]));
+        programs
+    }
+}
+
+/// クリップボードの画像を読み出すプログラム
+pub fn clipboard_image_program() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "pngpaste"
+    } else {
+        "xclip"
+    }
+}
+
+/// 他のユーザーから読めないディレクトリを作る DirBuilder（0700）
+#[cfg(unix)]
+pub fn private_dir_builder() -> std::fs::DirBuilder {
+    use std::os::unix::fs::DirBuilderExt;
+    let mut builder = std::fs::DirBuilder::new();
+    builder.mode(0o700);
+    builder
+}
+
+/// 他のユーザーから読めないディレクトリを作る DirBuilder（権限はユーザーのプロファイルに任せる）
+#[cfg(not(unix))]
+pub fn private_dir_builder() -> std::fs::DirBuilder {
+    std::fs::DirBuilder::new()
+}
+
+/// URL を開くのに使うプログラム
+pub fn browser_program() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(windows) {
+        "cmd"
+    } else {
+        "xdg-open"
+    }
+}
+
+/// プログラムに渡す入力（clip は BOM 付き UTF-16LE でないと非 ASCII が化ける）
+fn clipboard_input(program: &str, text: &str) -> Vec<u8> {
+    if program.starts_with("clip") {
+        std::iter::once(0xFEFF)
+            .chain(text.encode_utf16())
+            .flat_map(u16::to_le_bytes)
+            .collect()
+    } else {
+        text.as_bytes().to_vec()
+    }
+}
+
+/// プログラムの標準入力に書き込んでコピーする
+fn copy_with(program: &str, args: &[&str], text: &str) -> bool {
+    let Some(mut cmd) = crate::exec::command(program) else {
+        return false;
+    };
+    cmd.args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(&clipboard_input(program, text))?;
+            }
+            child.wait()
+        })
+        .is_ok_and(|status| status.success())
+}
+
+/// OSC 52 のエスケープシーケンス（tmux 内ではパススルーで包む）
+fn osc52_sequence(text: &str, in_tmux: bool) -> String {
+    let osc = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    if in_tmux {
+        format!("\x1bPtmux;{}\x1b\\", osc.replace('\x1b', "\x1b\x1b"))
+    } else {
+        osc
+    }
+}
+
+/// テキストをクリップボードにコピーする（SSH 越しか、使えるプログラムがなければ OSC 52）
+pub fn copy_text(text: &str) -> std::io::Result<CopyMethod> {
+    if !is_remote() {
+        for (program, args) in clipboard_programs() {
+            if copy_with(program, args, text) {
+                return Ok(CopyMethod::Program(program));
+            }
+        }
+    }
+    let mut stdout = std::io::stdout();
+    stdout.write_all(osc52_sequence(text, env_set("TMUX")).as_bytes())?;
+    stdout.flush()?;
+    Ok(CopyMethod::Osc52)
+}
+
+/// URL をシステムのデフォルトブラウザで開く
+pub fn open_url(url: &str) -> Result<(), String> {
+    let program = browser_program();
+    let Some(mut cmd) = crate::exec::command(program) else {
+        return Err(format!("{program} is not in allowed_commands"));
+    };
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        // start の最初の引用符付き引数はウィンドウタイトル。URL の & を cmd に解釈させないよう引用符で囲む
+        cmd.raw_arg(format!("/C start \"\" \"{url}\""));
+    }
+    #[cfg(not(windows))]
+    cmd.arg(url);
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(drop)
+        .map_err(|e| format!("{program}: {e}"))
+}
+
+/// 標準の base64（パディングあり）
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | (u32::from(b) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"M"), "TQ==");
+        assert_eq!(base64(b"Ma"), "TWE=");
+        assert_eq!(base64(b"Man"), "TWFu");
+        assert_eq!(base64(b"\xfb\xff"), "+/8=");
+    }
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_clipboard_input() {
+        assert_eq!(clipboard_input("xclip", "é"), "é".as_bytes());
+        assert_eq!(clipboard_input("clip.exe", "a"), vec![0xFF, 0xFE, b'a', 0]);
+    }
+}
@@ -0,0 +1,71 @@
+//! ターミナルタイトル（OSC 0/2）と tmux ペイン変数の設定・復元
+
+use std::io::Write;
+
+/// 元のタイトルを退避する（XTWINOPS: CSI 22 ; 0 t）
+const PUSH_TITLE: &str = "\x1b[22;0t";
+/// 退避したタイトルを復元する（XTWINOPS: CSI 23 ; 0 t）
+const POP_TITLE: &str = "\x1b[23;0t";
+/// tmux のステータスラインから `#{@prism_pr}` で参照できるペイン変数
+const TMUX_PANE_OPTION: &str = "@prism_pr";
+
+/// "prism: owner/repo#123 — PR title"
+pub fn title_text(repo: &str, pr_number: u64, pr_title: &str) -> String {
+    format!("prism: {repo}#{pr_number} — {pr_title}")
+}
+
+/// tmux 内で動いているか
+fn in_tmux() -> bool {
+    std::env::var_os("TMUX").is_some_and(|v| !v.is_empty())
+}
+
+/// tmux のペイン変数を設定（value が None なら解除）。失敗しても無視する
+fn set_tmux_pane_option(value: Option<&str>) {
+    let Some(mut cmd) = crate::exec::command("tmux") else {
+        return;
+    };
+    cmd.args(["set-option", "-p"]);
+    match value {
+        Some(v) => cmd.args([TMUX_PANE_OPTION, v]),
+        None => cmd.args(["-u", TMUX_PANE_OPTION]),
+    };
+    let _ = cmd.output();
+}
+
+/// 実行中のタイトルを設定する（元のタイトルは退避し、restore で戻す）
+pub fn set(repo: &str, pr_number: u64, pr_title: &str, tmux_status: bool) {
+    let mut out = std::io::stdout();
+    // タイトル内の制御文字は除去（OSC を途中で終端させない）
+    let title: String = title_text(repo, pr_number, pr_title)
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect();
+    let _ = write!(out, "{PUSH_TITLE}\x1b]0;{title}\x07");
+    let _ = out.flush();
+    if tmux_status && in_tmux() {
+        set_tmux_pane_option(Some(&format!("{repo}#{pr_number}")));
+    }
+}
+
+/// 起動前のタイトルに戻す
+pub fn restore(tmux_status: bool) {
+    let mut out = std::io::stdout();
+    let _ = write!(out, "{POP_TITLE}");
+    let _ = out.flush();
+    if tmux_status && in_tmux() {
+        set_tmux_pane_option(None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_text() {
+        assert_eq!(
+            title_text("kawarimidoll/gh-prism", 123, "Add title support"),
+            "prism: kawarimidoll/gh-prism#123 — Add title support"
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use color_eyre::{Result, eyre::eyre};
+use serde::Deserialize;
+
+/// リリース取得元リポジトリ
+const RELEASE_REPO: &str = "kawarimidoll/gh-prism";
+/// gh extension としてのコマンド名
+const EXTENSION_NAME: &str = "prism";
+/// アップデート後に表示する changelog の最大行数
+const CHANGELOG_MAX_LINES: usize = 20;
+
+/// GitHub Release の必要なフィールドのみ
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    pub html_url: String,
+}
+
+/// "v26.2.28" / "26.2.28-abc1234" → [26, 2, 28]（数値として解釈できなければ None）
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim().trim_start_matches('v');
+    // ローカルビルドの "-<hash>" 接尾辞は比較対象外
+    let core = version.split('-').next()?;
+    core.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// `latest` が `current` より新しいか（解釈できない場合は false）
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    match (parse_version(latest), parse_version(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+/// gh api で最新リリースを取得（ネットワーク設定のタイムアウトとリトライに従う）
+pub fn fetch_latest_release() -> Result<Release> {
+    let output = crate::github::metrics::run_gh(
+        "latest release",
+        &["api", &format!("repos/{RELEASE_REPO}/releases/latest")],
+    )?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "Failed to fetch latest release: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+/// 最新リリースが現在より新しければそのリリースを返す（起動時チェック用）
+pub fn check_for_update(current: &str) -> Option<Release> {
+    fetch_latest_release()
+        .ok()
+        .filter(|release| is_newer(&release.tag_name, current))
+}
+
+/// リリースノートを先頭から最大行数まで切り出す
+fn changelog_summary(body: &str) -> String {
+    let lines: Vec<&str> = body.lines().collect();
+    let mut summary = lines
+        .iter()
+        .take(CHANGELOG_MAX_LINES)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    if lines.len() > CHANGELOG_MAX_LINES {
+        summary.push_str("\n...");
+    }
+    summary
+}
+
+/// `prism --update`: 最新リリースを確認し gh extension upgrade で更新する
+pub fn run_update(current: &str) -> Result<()> {
+    eprintln!("Checking latest release of {RELEASE_REPO}...");
+    let release = fetch_latest_release()?;
+    if !is_newer(&release.tag_name, current) {
+        eprintln!(
+            "Already up to date ({current}, latest: {}).",
+            release.tag_name
+        );
+        return Ok(());
+    }
+
+    eprintln!("Updating {current} → {}...", release.tag_name);
+    let status = crate::exec::command("gh")
+        .ok_or_else(|| eyre!("gh is not allowed to run"))?
+        .args(["extension", "upgrade", EXTENSION_NAME])
+        .status()?;
+    if !status.success() {
+        // Nix 等 gh extension 以外でインストールされている場合
+        return Err(eyre!(
+            "`gh extension upgrade {EXTENSION_NAME}` failed. If you installed prism another way, \
+             update it with your package manager or download it from {}",
+            release.html_url
+        ));
+    }
+
+    eprintln!("\nUpdated to {}. Changes:\n", release.tag_name);
+    match release.body.as_deref().map(str::trim) {
+        Some(body) if !body.is_empty() => eprintln!("{}", changelog_summary(body)),
+        _ => eprintln!("(no release notes)"),
+    }
+    eprintln!("\nFull release notes: {}", release.html_url);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("v26.2.28"), Some(vec![26, 2, 28]));
+        assert_eq!(parse_version("26.2.28-abc1234"), Some(vec![26, 2, 28]));
+        assert_eq!(parse_version("nightly"), None);
+    }
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("v26.3.1", "26.2.28"));
+        assert!(is_newer("v26.10.1", "26.9.30"));
+        assert!(!is_newer("v26.2.28", "26.2.28-abc1234"));
+        assert!(!is_newer("v26.2.1", "26.2.28"));
+        assert!(!is_newer("garbage", "26.2.28"));
+    }
+
+    #[test]
+    fn test_changelog_summary_truncates() {
+        let body = (0..30)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let summary = changelog_summary(&body);
+        assert_eq!(summary.lines().count(), CHANGELOG_MAX_LINES + 1);
+        assert!(summary.ends_with("..."));
+    }
+}